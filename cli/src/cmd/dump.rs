@@ -33,7 +33,7 @@ pub fn dump(arg: &ArgMatches) -> anyhow::Result<()> {
         println!("{tokens:#?}");
     }
 
-    let (mut ast, scope_counter, local_counter) = dash_parser::Parser::new(interner, &source, tokens)
+    let (mut ast, scope_counter, local_counter, is_strict) = dash_parser::Parser::new(interner, &source, tokens)
         .parse_all()
         .map_err(|err| anyhow!("{}", err.formattable(&source, true)))?;
 
@@ -64,7 +64,7 @@ pub fn dump(arg: &ArgMatches) -> anyhow::Result<()> {
     }
 
     let bytecode = dash_compiler::FunctionCompiler::new(&source, opt, nameres, scope_counter, interner)
-        .compile_ast(ast, true)
+        .compile_ast(ast, true, is_strict)
         .map_err(|err| anyhow!("{}", [err].formattable(&source, true)))?;
 
     if dump_bytecode {
@@ -1,8 +1,13 @@
+use std::rc::Rc;
+
 use dash_middle::compiler::FunctionCallKind;
+use dash_middle::compiler::constant::TemplateStrings;
 use dash_middle::compiler::instruction::Instruction;
 use dash_middle::interner::sym;
 use dash_middle::parser::error::Error;
-use dash_middle::parser::expr::{CallArgumentKind, ExprKind, FunctionCall, LiteralExpr, PropertyAccessExpr};
+use dash_middle::parser::expr::{
+    CallArgumentKind, ExprKind, FunctionCall, LiteralExpr, PropertyAccessExpr, TaggedTemplate,
+};
 use dash_middle::sourcemap::Span;
 use dash_middle::visitor::Visitor;
 
@@ -142,6 +147,36 @@ impl InstructionBuilder<'_, '_> {
         self.lower_function_call_common(span, target_span, has_this, kind, fc.arguments)
     }
 
+    /// Lowers a tagged template to a call of the tag, with the template object
+    /// containing the strings as the first argument, followed by the substitutions
+    pub fn lower_tagged_template(&mut self, span: Span, tt: TaggedTemplate) -> Result<(), Error> {
+        let target_span = tt.tag.span;
+        let argc = u8::try_from(tt.substitutions.len() + 1).map_err(|_| Error::ParameterLimitExceeded(span))?;
+
+        let has_this = if let ExprKind::PropertyAccess(p) = tt.tag.kind {
+            self.visit_property_access_expr(target_span, p, true)?;
+            true
+        } else {
+            self.accept_expr(*tt.tag)?;
+            false
+        };
+
+        let strings = TemplateStrings {
+            cooked: tt.segments.iter().map(|segment| segment.cooked).collect(),
+            raw: tt.segments.iter().map(|segment| segment.raw).collect(),
+        };
+        self.build_template_object(Rc::new(strings))
+            .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
+
+        for substitution in tt.substitutions {
+            self.accept_expr(substitution)?;
+        }
+
+        self.build_call(argc, has_this, FunctionCallKind::Function, Vec::new(), target_span);
+
+        Ok(())
+    }
+
     /// Lowers parts of a function call, assuming that the receiver is on the stack
     pub fn lower_function_call_common(
         &mut self,
@@ -1,34 +1,125 @@
 use std::mem;
 
 use dash_middle::compiler::external::PossiblyExternalId;
-use dash_middle::compiler::instruction::AssignKind;
+use dash_middle::compiler::instruction::{AssignKind, Instruction};
 use dash_middle::compiler::scope::BackLocalId;
 use dash_middle::interner::{Symbol, sym};
 use dash_middle::lexer::token::TokenType;
 use dash_middle::parser::error::Error;
 use dash_middle::parser::expr::{Expr, ExprKind};
 use dash_middle::parser::statement::{
-    BlockStatement, ScopeId, Statement, StatementKind, VariableBinding, VariableDeclaration, VariableDeclarations,
-    WhileLoop,
+    Asyncness, BlockStatement, ScopeId, Statement, StatementKind, VariableBinding, VariableDeclaration,
+    VariableDeclarations,
 };
 use dash_middle::sourcemap::Span;
 use dash_middle::visitor::Visitor;
 
-use crate::builder::InstructionBuilder;
+use crate::builder::{InstructionBuilder, Label};
 use crate::instruction::compile_local_load;
+use crate::{BreakStmt, FinallyLabel};
 
 pub enum ForEachLoopKind {
-    ForOf,
+    /// A for..of loop, or a for await..of loop if async
+    ForOf(Asyncness),
     ForIn,
 }
 
+/// The iterator of an enclosing for..of loop, which needs to be closed when the loop is exited early
+/// through `break` or `continue` to an outer loop
+#[derive(Debug, Clone, Copy)]
+pub struct ForOfIterator {
+    /// The index of the loop in the function's breakables
+    breakable_idx: usize,
+    /// The local that stores the iterator
+    iterator_local: BackLocalId,
+    /// Whether the result of `return()` needs to be awaited (for await..of)
+    is_await: bool,
+    /// The try depth within the loop body
+    try_depth: u16,
+}
+
+/// Emits code for closing the iterator stored in `iterator_local` by calling its `return` method, if it has one.
+/// The result is awaited for async iterators.
+///
+/// When closing an iterator because of an exception, `check_result` should be false: the result is then ignored,
+/// as the original exception takes precedence. Otherwise, `return` must return an object.
+pub fn build_iterator_close(
+    ib: &mut InstructionBuilder<'_, '_>,
+    iterator_local: BackLocalId,
+    is_await: bool,
+    check_result: bool,
+) {
+    let mut ib = InstructionBuilder::new(ib);
+    ib.build_iterator_return(iterator_local);
+    ib.build_jmpfalsep(Label::IfEnd, true);
+    if is_await {
+        ib.build_await();
+    }
+    if check_result {
+        ib.build_check_iterator_result();
+    } else {
+        ib.build_pop();
+    }
+    ib.add_local_label(Label::IfEnd);
+}
+
+/// Emits code for closing the iterator stored in `iterator_local` after an exception was thrown,
+/// ignoring any exceptions thrown by `return()`
+pub fn build_iterator_close_on_throw(ib: &mut InstructionBuilder<'_, '_>, iterator_local: BackLocalId, is_await: bool) {
+    let mut ib = InstructionBuilder::new(ib);
+    ib.build_try_block(true, None);
+    ib.current_function_mut().try_depth += 1;
+    build_iterator_close(&mut ib, iterator_local, is_await, false);
+    ib.current_function_mut().try_depth -= 1;
+    ib.build_pop_try();
+    ib.build_jmp(Label::TryEnd, true);
+    ib.add_local_label(Label::Catch);
+    ib.writew(u16::MAX);
+    ib.add_local_label(Label::TryEnd);
+}
+
+/// Emits code for leaving the for..of loops that were entered since the breakable at `breakable_idx`
+/// (and the breakable itself, if it is a for..of loop) when jumping to it.
+///
+/// The try blocks of the loop bodies are popped first so that an exception thrown by `return()` does not close the iterators again.
+pub fn build_for_of_exits(ib: &mut InstructionBuilder<'_, '_>, breakable_idx: usize, brk: BreakStmt) {
+    let exited = ib
+        .current_function()
+        .for_of_iterators
+        .iter()
+        .filter(|it| it.breakable_idx >= breakable_idx)
+        .copied()
+        .collect::<Vec<_>>();
+
+    let Some(outermost) = exited.first() else {
+        return;
+    };
+
+    for _ in 0..=ib.current_function().try_depth - outermost.try_depth {
+        ib.build_pop_try();
+    }
+
+    for iterator in exited.iter().rev() {
+        // `continue` stays in the loop and does not close its own iterator
+        if matches!(brk, BreakStmt::Continue) && iterator.breakable_idx == breakable_idx {
+            continue;
+        }
+
+        build_iterator_close(ib, iterator.iterator_local, iterator.is_await, true);
+    }
+}
+
 /// Helper for desugaring for each-like loops that iterate through an iterator
-/// (for..in and for..of), as well as `yield*`
+/// (for..in, for..of and for await..of), as well as `yield*`
 pub struct ForEachDesugarCtxt<'a, 'cx, 'interner> {
     /// The local that stores the iterator
     iterator_local: BackLocalId,
     /// The local that stores the intermediate next() call result
     gen_step_local: BackLocalId,
+    /// Whether the next() call results are awaited (for await..of)
+    is_await: bool,
+    /// Whether the iterator needs to be closed when the loop is exited early (for..of and for await..of)
+    close_iterator: bool,
     ib: &'a mut InstructionBuilder<'cx, 'interner>,
 }
 
@@ -46,6 +137,8 @@ impl<'a, 'cx, 'interner> ForEachDesugarCtxt<'a, 'cx, 'interner> {
             ib,
             iterator_local,
             gen_step_local,
+            is_await: false,
+            close_iterator: false,
         })
     }
 
@@ -53,9 +146,12 @@ impl<'a, 'cx, 'interner> ForEachDesugarCtxt<'a, 'cx, 'interner> {
     pub fn init_iterator(&mut self, kind: ForEachLoopKind, iterable: Expr) -> Result<(), Error> {
         self.ib.accept_expr(iterable)?;
         match kind {
-            ForEachLoopKind::ForOf => self.ib.build_symbol_iterator(),
+            ForEachLoopKind::ForOf(Asyncness::No) => self.ib.build_symbol_iterator(),
+            ForEachLoopKind::ForOf(Asyncness::Yes) => self.ib.build_symbol_async_iterator(),
             ForEachLoopKind::ForIn => self.ib.build_for_in_iterator(),
         }
+        self.is_await = matches!(kind, ForEachLoopKind::ForOf(Asyncness::Yes));
+        self.close_iterator = matches!(kind, ForEachLoopKind::ForOf(_));
         self.ib
             .build_local_store(AssignKind::Assignment, PossiblyExternalId::Local(self.iterator_local));
         self.ib.build_pop();
@@ -101,63 +197,151 @@ impl<'a, 'cx, 'interner> ForEachDesugarCtxt<'a, 'cx, 'interner> {
 
     /// Emits a loop, assuming that `iterator_local` has been initialized with the iterator
     pub fn compile_loop(&mut self, label: Option<Symbol>, body: Box<Statement>) -> Result<(), Error> {
-        self.ib.visit_while_loop(
-            Span::COMPILER_GENERATED,
-            label,
-            WhileLoop {
-                condition: Expr {
+        let mut next_call = Expr {
+            span: Span::COMPILER_GENERATED,
+            kind: ExprKind::function_call(
+                Expr {
                     span: Span::COMPILER_GENERATED,
-                    kind: ExprKind::unary(
-                        TokenType::LogicalNot,
+                    kind: ExprKind::property_access(
+                        false,
                         Expr {
                             span: Span::COMPILER_GENERATED,
-                            kind: ExprKind::property_access(
-                                false,
-                                Expr {
-                                    span: Span::COMPILER_GENERATED,
-                                    kind: ExprKind::assignment_local_space(
-                                        self.gen_step_local,
-                                        Expr {
-                                            span: Span::COMPILER_GENERATED,
-                                            kind: ExprKind::function_call(
-                                                Expr {
-                                                    span: Span::COMPILER_GENERATED,
-                                                    kind: ExprKind::property_access(
-                                                        false,
-                                                        Expr {
-                                                            span: Span::COMPILER_GENERATED,
-                                                            kind: ExprKind::compiled(compile_local_load(
-                                                                PossiblyExternalId::Local(self.iterator_local),
-                                                            )),
-                                                        },
-                                                        Expr {
-                                                            span: Span::COMPILER_GENERATED,
-                                                            kind: ExprKind::identifier(sym::next),
-                                                        },
-                                                    ),
-                                                },
-                                                Vec::new(),
-                                                false,
-                                            ),
-                                        },
-                                        TokenType::Assignment,
-                                    ),
-                                },
-                                Expr {
-                                    span: Span::COMPILER_GENERATED,
-                                    kind: ExprKind::identifier(sym::done),
-                                },
+                            kind: ExprKind::compiled(compile_local_load(PossiblyExternalId::Local(
+                                self.iterator_local,
+                            ))),
+                        },
+                        Expr {
+                            span: Span::COMPILER_GENERATED,
+                            kind: ExprKind::identifier(sym::next),
+                        },
+                    ),
+                },
+                Vec::new(),
+                false,
+            ),
+        };
+        if self.is_await {
+            next_call = Expr {
+                span: Span::COMPILER_GENERATED,
+                kind: ExprKind::unary(TokenType::Await, next_call),
+            };
+        }
+
+        let condition = Expr {
+            span: Span::COMPILER_GENERATED,
+            kind: ExprKind::unary(
+                TokenType::LogicalNot,
+                Expr {
+                    span: Span::COMPILER_GENERATED,
+                    kind: ExprKind::property_access(
+                        false,
+                        Expr {
+                            span: Span::COMPILER_GENERATED,
+                            kind: ExprKind::assignment_local_space(
+                                self.gen_step_local,
+                                next_call,
+                                TokenType::Assignment,
                             ),
                         },
+                        Expr {
+                            span: Span::COMPILER_GENERATED,
+                            kind: ExprKind::identifier(sym::done),
+                        },
                     ),
                 },
-                body,
-            },
-        )?;
+            ),
+        };
+
+        // Equivalent to `visit_while_loop`, except that the body may need to be wrapped
+        let loop_id = self.ib.current_function_mut().prepare_loop(label);
+        let breakable_idx = self.ib.current_function().breakables.len() - 1;
+
+        self.ib
+            .current_function_mut()
+            .add_global_label(Label::LoopCondition { loop_id });
+        self.ib
+            .current_function_mut()
+            .add_global_label(Label::LoopIterationEnd { loop_id });
+        self.ib.accept_expr(condition)?;
+        self.ib.build_jmpfalsep(Label::LoopEnd { loop_id }, false);
+
+        self.compile_body(breakable_idx, body)?;
+        self.ib.build_jmp(Label::LoopCondition { loop_id }, false);
+
+        self.ib
+            .current_function_mut()
+            .add_global_label(Label::LoopEnd { loop_id });
+        self.ib.current_function_mut().breakables.pop();
 
         Ok(())
     }
 
+    /// Compiles the loop body. For for..of loops, the body is wrapped in a try block that closes the iterator
+    /// if the loop is exited through an exception or a `return`:
+    ///
+    /// ```js
+    /// try {
+    ///     body
+    /// } catch (e) {
+    ///     try { __forOfIter.return?.() } catch {}
+    ///     throw e;
+    /// } finally {
+    ///     // Only reached through `return`, after running the finally blocks within the body
+    ///     __forOfIter.return?.()
+    /// }
+    /// ```
+    ///
+    /// `break` and `continue` to an outer loop close the iterator where they are compiled.
+    fn compile_body(&mut self, breakable_idx: usize, body: Box<Statement>) -> Result<(), Error> {
+        if !self.close_iterator {
+            return self.ib.accept(*body);
+        }
+
+        let mut ib = InstructionBuilder::new(self.ib);
+        let finally_id = ib.current_function_mut().finally_counter.inc();
+        ib.build_try_block(true, Some(finally_id));
+
+        ib.current_function_mut().try_depth += 1;
+        let try_depth = ib.current_function().try_depth;
+        ib.current_function_mut().for_of_iterators.push(ForOfIterator {
+            breakable_idx,
+            iterator_local: self.iterator_local,
+            is_await: self.is_await,
+            try_depth,
+        });
+        ib.current_function_mut().finally_labels.push(FinallyLabel {
+            label: Label::Finally { finally_id },
+            try_depth,
+            is_for_of: true,
+        });
+        let res = ib.accept(*body);
+        ib.current_function_mut().finally_labels.pop();
+        ib.current_function_mut().for_of_iterators.pop();
+        ib.current_function_mut().try_depth -= 1;
+        res?;
+
+        ib.build_pop_try();
+        ib.build_jmp(Label::TryEnd, true);
+
+        // The step result is not needed anymore at this point, so its local can be reused for storing the exception
+        ib.add_local_label(Label::Catch);
+        ib.writew(self.gen_step_local.0);
+        // Entering the catch block re-pushed the try block for the finally block, which must not run for exceptions
+        ib.build_pop_try();
+        build_iterator_close_on_throw(&mut ib, self.iterator_local, self.is_await);
+        ib.build_local_load(PossiblyExternalId::Local(self.gen_step_local));
+        ib.build_throw();
+
+        ib.current_function_mut()
+            .add_global_label(Label::Finally { finally_id });
+        build_iterator_close(&mut ib, self.iterator_local, self.is_await, true);
+        ib.write_instr(Instruction::FinallyEnd);
+        ib.writew(ib.current_function().try_depth);
+
+        ib.add_local_label(Label::TryEnd);
+        Ok(())
+    }
+
     /// Convenience function for fully desugaring a for..of/for..in loop given an iterable
     pub fn desugar_for_each_kinded_loop(
         &mut self,
@@ -182,6 +366,11 @@ impl<'a, 'cx, 'interner> ForEachDesugarCtxt<'a, 'cx, 'interner> {
         //        console.log(x)
         //    }
         //
+        // The loop body of for-of loops is additionally wrapped in a try block,
+        // so that the iterator can be closed by calling `__forOfIter.return?.()` when the loop exits early
+        // through an exception. `break`, `continue <label>` and `return` close the iterator before jumping.
+        //
+        // For-Await-Of loops use the async iterator instead and await the result of `next()`.
         // For-In loops are desugared almost equivalently, except an iterator over the object keys is used
 
         self.init_iterator(kind, iterable)?;
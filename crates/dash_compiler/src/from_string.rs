@@ -13,14 +13,36 @@ impl<'interner> FunctionCompiler<'interner> {
         interner: &'interner mut StringInterner,
         input: &str,
         opt: OptLevel,
+    ) -> Result<CompileResult, Vec<Error>> {
+        Self::compile_str_inner(interner, input, opt, false)
+    }
+
+    /// Compiles the input as module code, which is always strict mode code
+    pub fn compile_module_str(
+        interner: &'interner mut StringInterner,
+        input: &str,
+        opt: OptLevel,
+    ) -> Result<CompileResult, Vec<Error>> {
+        Self::compile_str_inner(interner, input, opt, true)
+    }
+
+    fn compile_str_inner(
+        interner: &'interner mut StringInterner,
+        input: &str,
+        opt: OptLevel,
+        is_module: bool,
     ) -> Result<CompileResult, Vec<Error>> {
         let tokens = Lexer::new(interner, input).scan_all()?;
-        let (ast, scope_counter, local_counter) = Parser::new(interner, input, tokens).parse_all()?;
+        let mut parser = Parser::new(interner, input, tokens);
+        if is_module {
+            parser = parser.module();
+        }
+        let (ast, scope_counter, local_counter, is_strict) = parser.parse_all()?;
 
         let nameres = name_res(&ast, scope_counter.len(), local_counter.len());
 
         Self::new(input, opt, nameres, scope_counter, interner)
-            .compile_ast(ast, true)
+            .compile_ast(ast, true, is_strict)
             .map_err(|err| vec![err])
     }
 }
@@ -2,6 +2,7 @@ use std::rc::Rc;
 
 use dash_middle::compiler::constant::{
     BooleanConstant, Function, FunctionConstant, LimitExceededError, NumberConstant, RegexConstant, SymbolConstant,
+    TemplateConstant, TemplateStrings,
 };
 use dash_middle::compiler::external::{ExternalId, PossiblyExternalId};
 use dash_middle::compiler::instruction::{AssignKind, Instruction, IntrinsicOperation};
@@ -78,9 +79,16 @@ impl InstructionBuilder<'_, '_> {
         build_nan Instruction::Nan,
         build_undef Instruction::Undef,
         build_symbol_iterator Instruction::CallSymbolIterator,
+        build_symbol_async_iterator Instruction::CallSymbolAsyncIterator,
         build_for_in_iterator Instruction::CallForInIterator,
         build_dynamic_delete Instruction::DeletePropertyDynamic,
-        build_new_target Instruction::NewTarget
+        build_new_target Instruction::NewTarget,
+        build_super_prop_access Instruction::SuperPropAccess,
+        build_super_prop_assign Instruction::SuperPropAssign,
+        build_super_prop_delete Instruction::SuperPropDelete,
+        build_set_home_object Instruction::SetHomeObject,
+        build_set_prototype Instruction::SetPrototype,
+        build_check_iterator_result Instruction::CheckIteratorResult
     }
 
     pub fn build_ret(&mut self, tc_depth: u16) {
@@ -88,6 +96,21 @@ impl InstructionBuilder<'_, '_> {
         self.writew(tc_depth);
     }
 
+    /// Returns the value on the stack, running enclosing finally blocks first.
+    /// This includes the implicit finally blocks of for..of loops, which close their iterators.
+    pub fn build_return(&mut self) {
+        if let Some(finally) = self.current_function().enclosing_finally() {
+            // Pop the try blocks nested in the finally-protected try block, as well as that try block itself
+            for _ in finally.try_depth..=self.current_function().try_depth {
+                self.build_pop_try();
+            }
+            self.write_instr(Instruction::DelayedReturn);
+            self.build_jmp(finally.label, false);
+        } else {
+            self.build_ret(self.current_function().try_depth);
+        }
+    }
+
     pub fn write_bool(&mut self, b: bool) {
         self.write(b.into());
     }
@@ -134,6 +157,13 @@ impl InstructionBuilder<'_, '_> {
         Ok(())
     }
 
+    pub fn build_bigint_constant(&mut self, sym: Symbol) -> Result<(), LimitExceededError> {
+        let SymbolConstant(id) = self.current_function_mut().cp.add_symbol(sym)?;
+        self.write_instr(Instruction::BigInt);
+        self.writew(id);
+        Ok(())
+    }
+
     pub fn build_regex_constant(&mut self, regex: dash_regex::Regex, sym: Symbol) -> Result<(), LimitExceededError> {
         let RegexConstant(id) = self.current_function_mut().cp.add_regex((regex, sym))?;
         self.write_instr(Instruction::Regex);
@@ -141,6 +171,13 @@ impl InstructionBuilder<'_, '_> {
         Ok(())
     }
 
+    pub fn build_template_object(&mut self, strings: Rc<TemplateStrings>) -> Result<(), LimitExceededError> {
+        let TemplateConstant(id) = self.current_function_mut().cp.add_template(strings)?;
+        self.write_instr(Instruction::TemplateObject);
+        self.writew(id);
+        Ok(())
+    }
+
     pub fn build_null_constant(&mut self) -> Result<(), LimitExceededError> {
         self.write_instr(Instruction::Null);
         Ok(())
@@ -407,9 +444,19 @@ impl InstructionBuilder<'_, '_> {
         self.writew(count);
     }
 
-    pub fn build_arraydestruct(&mut self, count: u16) {
-        self.write_instr(Instruction::ArrayDestruct);
-        self.writew(count);
+    pub fn build_iterator_step(&mut self, iterator: BackLocalId) {
+        self.write_instr(Instruction::IteratorStep);
+        self.writew(iterator.0);
+    }
+
+    pub fn build_iterator_rest(&mut self, iterator: BackLocalId) {
+        self.write_instr(Instruction::IteratorRest);
+        self.writew(iterator.0);
+    }
+
+    pub fn build_iterator_return(&mut self, iterator: BackLocalId) {
+        self.write_instr(Instruction::IteratorReturn);
+        self.writew(iterator.0);
     }
 
     pub fn build_intrinsic_op(&mut self, op: IntrinsicOperation) {
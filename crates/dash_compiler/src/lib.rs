@@ -13,7 +13,7 @@ use dash_middle::parser::error::Error;
 use dash_middle::parser::expr::{
     ArrayLiteral, ArrayMemberKind, AssignmentExpr, AssignmentTarget, BinaryExpr, CallArgumentKind, ConditionalExpr,
     Expr, ExprKind, FunctionCall, GroupingExpr, LiteralExpr, ObjectLiteral, ObjectMemberKind,
-    OptionalChainingComponent, OptionalChainingExpression, Postfix, PropertyAccessExpr, Seq, UnaryExpr,
+    OptionalChainingComponent, OptionalChainingExpression, Postfix, PropertyAccessExpr, Seq, TaggedTemplate, UnaryExpr,
 };
 use dash_middle::parser::statement::{
     Asyncness, Binding, BlockStatement, Class, ClassMember, ClassMemberKey, ClassMemberValue, DoWhileLoop, ExportKind,
@@ -27,7 +27,7 @@ use dash_middle::visitor::Visitor;
 use dash_optimizer::OptLevel;
 use dash_optimizer::consteval::ConstFunctionEvalCtx;
 use dash_optimizer::type_infer::{InferMode, LocalDeclToSlot, NameResolutionResults, TypeInferCtx};
-use for_each::{ForEachDesugarCtxt, ForEachLoopKind};
+use for_each::{ForEachDesugarCtxt, ForEachLoopKind, ForOfIterator};
 use instruction::compile_local_load;
 use jump_container::JumpContainer;
 
@@ -58,6 +58,16 @@ enum Breakable {
     Named { sym: Symbol, label_id: usize },
 }
 
+/// A `finally` block that a `return` needs to jump to before leaving the function
+#[derive(Debug, Clone, Copy)]
+struct FinallyLabel {
+    label: Label,
+    /// The try depth of the code protected by the `finally` block
+    try_depth: u16,
+    /// Whether this is the implicit `finally` block of a for..of loop, which closes the iterator
+    is_for_of: bool,
+}
+
 /// Function-specific state, such as
 #[derive(Debug)]
 struct FunctionLocalState {
@@ -65,10 +75,11 @@ struct FunctionLocalState {
     buf: Vec<u8>,
     /// A list of constants used throughout this function
     cp: ConstantPool,
-    /// Current `try` depth (note that this does NOT include `catch`es)
+    /// Current `try` depth (note that this does NOT include `catch`es,
+    /// except for those of try blocks with a `finally` block, as their try block is re-pushed when entering the `catch`)
     try_depth: u16,
-    /// A stack of try-catch-finally blocks and their optional `finally` label that can be jumped to
-    finally_labels: Vec<Option<Label>>,
+    /// A stack of the enclosing `finally` blocks that can be jumped to
+    finally_labels: Vec<FinallyLabel>,
     finally_counter: Counter<usize>,
     /// Counter for user-defined labels
     user_label_counter: Counter<usize>,
@@ -78,6 +89,8 @@ struct FunctionLocalState {
     jc: JumpContainer,
     /// A stack of breakable labels (loop/switch)
     breakables: Vec<Breakable>,
+    /// A stack of iterators of the enclosing for..of loops
+    for_of_iterators: Vec<ForOfIterator>,
     /// Keeps track of the total number of loops to be able to have unique IDs
     loop_counter: usize,
     /// Keeps track of the total number of loops to be able to have unique IDs
@@ -90,6 +103,9 @@ struct FunctionLocalState {
     ///
     /// Also tracks the span for error reporting, but is discarded past the compiler stage.
     references_arguments: Option<(Span, BackLocalId)>,
+    /// Whether this function accesses properties on `super` anywhere in its body,
+    /// including in nested arrow functions
+    references_super: bool,
     /// If this is a subclass, then this vec contains the member initializers that need to be inserted after a `super()` call in the constructor.
     member_initializers_for_super: Vec<ClassMember>,
 }
@@ -103,6 +119,7 @@ macro_rules! exit_breakable {
     };
 }
 
+/// The kind of statement that jumps to a breakable
 #[derive(Copy, Clone)]
 enum BreakStmt {
     Break,
@@ -121,12 +138,14 @@ impl FunctionLocalState {
             ty,
             jc: JumpContainer::new(),
             breakables: Vec::new(),
+            for_of_iterators: Vec::new(),
             loop_counter: 0,
             switch_counter: 0,
             id,
             debug_symbols: DebugSymbols::default(),
             externals: IndexVec::new(),
             references_arguments: None,
+            references_super: false,
             member_initializers_for_super: Vec::new(),
         }
     }
@@ -160,12 +179,13 @@ impl FunctionLocalState {
         jump_container::add_jump(&mut self.jc, label, &mut self.buf)
     }
 
-    /// Tries to find the target to jump to for a `break` (or `continue`).
-    fn find_breakable(&self, label: Option<Symbol>, brk_stmt: BreakStmt) -> Option<Breakable> {
+    /// Tries to find the target to jump to for a `break` (or `continue`), along with its index in `breakables`.
+    fn find_breakable(&self, label: Option<Symbol>, brk_stmt: BreakStmt) -> Option<(usize, Breakable)> {
         self.breakables
             .iter()
+            .enumerate()
             .rev()
-            .find(|brk| match (brk, brk_stmt, label) {
+            .find(|(_, brk)| match (brk, brk_stmt, label) {
                 (
                     Breakable::Named { sym, label_id: _ }
                     | Breakable::Loop {
@@ -182,18 +202,23 @@ impl FunctionLocalState {
                 (Breakable::Switch { .. }, BreakStmt::Continue, None) => false,
                 (Breakable::Loop { .. } | Breakable::Switch { .. }, _, Some(_)) => false,
             })
-            .copied()
+            .map(|(idx, brk)| (idx, *brk))
     }
 
     pub fn is_async(&self) -> bool {
         match self.ty {
-            FunctionKind::Function(a) => matches!(a, Asyncness::Yes),
-            FunctionKind::Generator | FunctionKind::Arrow => false,
+            FunctionKind::Function(a) | FunctionKind::Generator(a) => matches!(a, Asyncness::Yes),
+            FunctionKind::Arrow => false,
         }
     }
 
-    fn enclosing_finally(&self) -> Option<Label> {
-        self.finally_labels.iter().copied().rev().find_map(|lbl| lbl)
+    fn enclosing_finally(&self) -> Option<FinallyLabel> {
+        self.finally_labels.last().copied()
+    }
+
+    /// Checks if there is an enclosing `finally` block written by the user (as opposed to one of a for..of loop)
+    fn has_enclosing_user_finally(&self) -> bool {
+        self.finally_labels.iter().any(|finally| !finally.is_for_of)
     }
 }
 
@@ -241,7 +266,12 @@ impl<'interner> FunctionCompiler<'interner> {
         }
     }
 
-    pub fn compile_ast(mut self, mut ast: Vec<Statement>, implicit_return: bool) -> Result<CompileResult, Error> {
+    pub fn compile_ast(
+        mut self,
+        mut ast: Vec<Statement>,
+        implicit_return: bool,
+        is_strict: bool,
+    ) -> Result<CompileResult, Error> {
         let compile_span = span!(Level::TRACE, "compile ast");
         let _enter = compile_span.enter();
 
@@ -291,6 +321,7 @@ impl<'interner> FunctionCompiler<'interner> {
             externals: root.externals,
             source: self.source,
             debug_symbols: root.debug_symbols,
+            is_strict,
         })
     }
 
@@ -309,6 +340,18 @@ impl<'interner> FunctionCompiler<'interner> {
             .expect("Function must be present")
     }
 
+    /// Marks the nearest enclosing non-arrow function as accessing properties on `super`
+    fn mark_references_super(&mut self) {
+        let function = self
+            .function_stack
+            .iter_mut()
+            .rev()
+            .find(|f| !matches!(f.ty, FunctionKind::Arrow))
+            .expect("Root function must be present");
+
+        function.references_super = true;
+    }
+
     fn current_function(&self) -> &FunctionLocalState {
         self.function_stack.last().expect("Function must be present")
     }
@@ -407,6 +450,7 @@ impl<'interner> FunctionCompiler<'interner> {
             mut statements,
             ty,
             ty_segment: _,
+            is_strict,
         }: FunctionDeclaration,
         constructor: Option<ConstructorData>,
     ) -> Result<(), Error> {
@@ -514,6 +558,8 @@ impl<'interner> FunctionCompiler<'interner> {
                     source: Rc::clone(&ib.source),
                     arguments_local: cmp.references_arguments.map(|(_, local_id)| local_id),
                     has_extends_clause,
+                    is_strict,
+                    references_super: cmp.references_super,
                 };
                 ib.build_function_constant(function)
                     .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
@@ -561,6 +607,7 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
             ExprKind::Literal(l) => self.visit_literal_expression(span, l),
             ExprKind::Unary(e) => self.visit_unary_expression(span, e),
             ExprKind::Call(e) => self.visit_function_call(span, e),
+            ExprKind::TaggedTemplate(e) => self.visit_tagged_template(span, e),
             ExprKind::Conditional(e) => self.visit_conditional_expr(span, e),
             ExprKind::PropertyAccess(e) => self.visit_property_access_expr(span, e, false),
             ExprKind::Sequence(e) => self.visit_sequence_expr(span, e),
@@ -767,6 +814,9 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
             LiteralExpr::String(s) => ib
                 .build_string_constant(s)
                 .map_err(|_| Error::ConstantPoolLimitExceeded(span)),
+            LiteralExpr::BigInt(s) => ib
+                .build_bigint_constant(s)
+                .map_err(|_| Error::ConstantPoolLimitExceeded(span)),
             LiteralExpr::Identifier(_) => unreachable!("identifiers are handled in visit_identifier_expression"),
             LiteralExpr::Regex(regex, sym) => ib
                 .build_regex_constant(regex, sym)
@@ -782,8 +832,8 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
         let mut ib = InstructionBuilder::new(self);
 
         match ident {
-            // super() handled specifically in call visitor
-            sym::super_ => unimplementedc!(span, "super keyword outside of a call"),
+            // super() and super.x are handled specifically in the call and property access visitors
+            sym::super_ => unimplementedc!(span, "super keyword outside of a call or property access"),
             ident => {
                 let loc = ib.find_local(span, ident);
                 match loc {
@@ -814,6 +864,14 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
         // Delete operator works different from other unary operators
         if let TokenType::Delete = operator {
             match expr.kind {
+                ExprKind::PropertyAccess(PropertyAccessExpr {
+                    computed,
+                    property,
+                    target,
+                }) if matches!(target.kind, ExprKind::Literal(LiteralExpr::Identifier(sym::super_))) => {
+                    compile_super_property_key(&mut ib, *property, computed)?;
+                    ib.build_super_prop_delete();
+                }
                 ExprKind::PropertyAccess(PropertyAccessExpr {
                     computed,
                     property,
@@ -870,11 +928,17 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
                 ib.build_undef();
             }
             TokenType::Yield => {
-                if !matches!(ib.current_function().ty, FunctionKind::Generator) {
+                if !matches!(ib.current_function().ty, FunctionKind::Generator(_)) {
                     return Err(Error::YieldOutsideGenerator { yield_expr: span });
                 }
 
                 ib.build_yield();
+
+                // A generator resumed through `return()` behaves as if there was a `return` statement here.
+                // Resuming pushes a boolean indicating that in addition to the resumed value.
+                ib.build_jmpfalsep(Label::IfEnd, true);
+                ib.build_return();
+                ib.add_local_label(Label::IfEnd);
             }
             TokenType::Await => {
                 if !ib.current_function().is_async() {
@@ -890,9 +954,9 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
     }
 
     fn visit_yield_star(&mut self, span: Span, right: Box<Expr>) -> Result<(), Error> {
-        if !matches!(self.current_function().ty, FunctionKind::Generator) {
+        let FunctionKind::Generator(asyncness) = self.current_function().ty else {
             return Err(Error::YieldOutsideGenerator { yield_expr: span });
-        }
+        };
 
         // Desugar `yield* right` to:
         //
@@ -902,10 +966,14 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
         //     yield _item.value;
         // }
         // _item.value;
+        //
+        // In async generators, the async iterator is used and the `next()` results are awaited.
+
+        let kind = ForEachLoopKind::ForOf(asyncness);
 
         let mut ib = InstructionBuilder::new(self);
         let mut fcx = ForEachDesugarCtxt::new(&mut ib, span)?;
-        fcx.init_iterator(ForEachLoopKind::ForOf, *right)?;
+        fcx.init_iterator(kind, *right)?;
         fcx.compile_loop(
             None,
             Box::new(Statement {
@@ -1213,6 +1281,84 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
                         }
                     }
                 }
+                ExprKind::PropertyAccess(prop)
+                    if matches!(prop.target.kind, ExprKind::Literal(LiteralExpr::Identifier(sym::super_))) =>
+                {
+                    ib.mark_references_super();
+
+                    macro_rules! compoundassign {
+                        ($op:ident) => {{
+                            // Desugar `super.x += y` to `super.x = super.x + y`, evaluating the key only once
+                            let key = compile_super_property_key_local(&mut ib, span, *prop.property, prop.computed)?;
+                            ib.build_local_load(key);
+                            ib.build_local_load(key);
+                            ib.build_super_prop_access();
+                            ib.accept_expr(*right)?;
+                            ib.$op();
+                            ib.build_super_prop_assign();
+                        }};
+                    }
+
+                    match operator {
+                        TokenType::Assignment => {
+                            compile_super_property_key(&mut ib, *prop.property, prop.computed)?;
+                            ib.accept_expr(*right)?;
+                            ib.build_super_prop_assign();
+                        }
+                        TokenType::AdditionAssignment => compoundassign!(build_add),
+                        TokenType::SubtractionAssignment => compoundassign!(build_sub),
+                        TokenType::MultiplicationAssignment => compoundassign!(build_mul),
+                        TokenType::DivisionAssignment => compoundassign!(build_div),
+                        TokenType::RemainderAssignment => compoundassign!(build_rem),
+                        TokenType::ExponentiationAssignment => compoundassign!(build_pow),
+                        TokenType::LeftShiftAssignment => compoundassign!(build_bitshl),
+                        TokenType::RightShiftAssignment => compoundassign!(build_bitshr),
+                        TokenType::UnsignedRightShiftAssignment => compoundassign!(build_bitushr),
+                        TokenType::BitwiseAndAssignment => compoundassign!(build_bitand),
+                        TokenType::BitwiseOrAssignment => compoundassign!(build_bitor),
+                        TokenType::BitwiseXorAssignment => compoundassign!(build_bitxor),
+                        TokenType::LogicalOrAssignment => {
+                            // Desugar `super.x ||= y` to `super.x || (super.x = y)`
+                            let key = compile_super_property_key_local(&mut ib, span, *prop.property, prop.computed)?;
+                            ib.build_local_load(key);
+                            ib.build_super_prop_access();
+                            ib.build_jmptruenp(Label::IfEnd, true);
+                            ib.build_pop();
+                            ib.build_local_load(key);
+                            ib.accept_expr(*right)?;
+                            ib.build_super_prop_assign();
+                            ib.add_local_label(Label::IfEnd);
+                        }
+                        TokenType::LogicalAndAssignment => {
+                            // Desugar `super.x &&= y` to `super.x && (super.x = y)`
+                            let key = compile_super_property_key_local(&mut ib, span, *prop.property, prop.computed)?;
+                            ib.build_local_load(key);
+                            ib.build_super_prop_access();
+                            ib.build_jmpfalsenp(Label::IfEnd, true);
+                            ib.build_pop();
+                            ib.build_local_load(key);
+                            ib.accept_expr(*right)?;
+                            ib.build_super_prop_assign();
+                            ib.add_local_label(Label::IfEnd);
+                        }
+                        TokenType::LogicalNullishAssignment => {
+                            // Desugar `super.x ??= y` to `super.x ?? (super.x = y)`
+                            let key = compile_super_property_key_local(&mut ib, span, *prop.property, prop.computed)?;
+                            ib.build_local_load(key);
+                            ib.build_super_prop_access();
+                            ib.build_jmpnullishnp(Label::IfBranch { branch_id: 0 }, true);
+                            ib.build_jmp(Label::IfEnd, true);
+
+                            ib.add_local_label(Label::IfBranch { branch_id: 0 });
+                            ib.build_pop();
+                            ib.build_local_load(key);
+                            ib.accept_expr(*right)?;
+                            ib.build_super_prop_assign();
+                            ib.add_local_label(Label::IfEnd);
+                        }
+                        _ => unimplementedc!(span, "unknown assignment operator {}", operator.fmt_for_expected_tys()),
+                    }
+                }
                 ExprKind::PropertyAccess(prop) => {
                     ib.accept_expr(*prop.target)?;
 
@@ -1327,111 +1473,25 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
                         other => unimplementedc!(span, "assignment to computed property {other:?}"),
                     }
                 }
-                _ => {
-                    match &left.kind {
-                        ExprKind::Object(ObjectLiteral {
-                            members: object,
-                            parenthesized: false,
-                        }) => {
-                            let mut rest = None;
-
-                            for (kind, expr) in object {
-                                if let ObjectMemberKind::Spread = kind {
-                                    let Some(ident) = expr.kind.as_identifier() else {
-                                        unimplementedc!(span, "rest binding must be an identifier")
-                                    };
-
-                                    let Some((PossiblyExternalId::Local(local), _)) = ib.find_local(expr.span, ident) else {
-                                        unimplementedc!(span, "rest binding must be defined in the current function")
-                                    };
-
-                                    if rest.is_some() {
-                                        unimplementedc!(span, "duplicate rest binding in object destructuring");
-                                    }
-
-                                    rest = Some(local);
-                                }
-                            }
-
-                            let object_local = ib.add_unnameable_local(sym::empty).map_err(|_| Error::LocalLimitExceeded(span))?;
-                            ib.accept_expr(*right)?;
-                            ib.build_local_store(AssignKind::Assignment, PossiblyExternalId::Local(object_local));
-
-                            ib.build_objdestruct((object.len() - rest.is_some() as usize).try_into().map_err(|_| Error::DestructureLimitExceeded(span))?, rest);
-
-                            for (kind, expr) in object {
-                                let name = match kind {
-                                    ObjectMemberKind::Spread => continue,
-                                    ObjectMemberKind::Static(sym) => *sym,
-                                    other => unimplementedc!(span, "invalid object member in destructuring: {:?}", other)
-                                };
-                                let Some(alias) = expr.kind.as_identifier() else {
-                                    unimplementedc!(span, "binding must be an identifier")
-                                };
-
-                                let Some((PossiblyExternalId::Local(local), _)) = ib.find_local(expr.span, alias) else {
-                                    unimplementedc!(span, "binding must be defined in the current function")
-                                };
-
-                                let NumberConstant(var_id) = ib
-                                    .current_function_mut()
-                                    .cp
-                                    .add_number(local.0 as f64)
-                                    .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
-                                let SymbolConstant(ident_id) = ib
-                                    .current_function_mut()
-                                    .cp
-                                    .add_symbol(name)
-                                    .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
-                                ib.write_bool(false); // Defaults are unsupported
-                                ib.writew(var_id); // FIXME: stop using constants and write the id into bytecode directly
-                                ib.writew(ident_id);
-                            }
-
-                            ib.build_local_load(PossiblyExternalId::Local(object_local));
-                        },
-                        ExprKind::Array(ArrayLiteral {
-                            members,
-                            parenthesized:false
-                        }) => {
-                            let array_local = ib.add_unnameable_local(sym::empty).map_err(|_| Error::LocalLimitExceeded(span))?;
-                            ib.accept_expr(*right)?;
-                            ib.build_local_store(AssignKind::Assignment, PossiblyExternalId::Local(array_local));
-
-                            ib.build_arraydestruct(members.len().try_into().map_err(|_| Error::LocalLimitExceeded(span))?);
-
-                            for kind in members {
-                                match kind {
-                                    ArrayMemberKind::Empty => ib.write_bool(false),
-                                    ArrayMemberKind::Item(expr) => {
-                                        ib.write_bool(true);
-
-                                        let Some(alias) = expr.kind.as_identifier() else {
-                                            unimplementedc!(span, "binding must be an identifier")
-                                        };
-
-                                        let Some((PossiblyExternalId::Local(local), _)) = ib.find_local(expr.span, alias) else {
-                                            unimplementedc!(span, "binding must be defined in the current function")
-                                        };
-
-                                        let NumberConstant(var_id) = ib
-                                            .current_function_mut()
-                                            .cp
-                                            .add_number(local.0 as f64)
-                                            .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
-
-                                        ib.write_bool(false); // Defaults are unsupported
-                                        ib.writew(var_id);
-                                    }
-                                    ArrayMemberKind::Spread(_) => unimplementedc!(span, "rest operator in array destructuring is unsupported"),
-                                }
-                            }
-
-                            ib.build_local_load(PossiblyExternalId::Local(array_local));
-                        }
-                        _ => unimplementedc!(span, "assignment to non-identifier")
-                    }
-                },
+                ExprKind::Object(ObjectLiteral {
+                    parenthesized: false,
+                    ..
+                })
+                | ExprKind::Array(ArrayLiteral {
+                    parenthesized: false,
+                    ..
+                }) => {
+                    let source = ib.add_unnameable_local(sym::empty).map_err(|_| Error::LocalLimitExceeded(span))?;
+                    ib.accept_expr(*right)?;
+                    ib.build_local_store(AssignKind::Assignment, PossiblyExternalId::Local(source));
+                    ib.build_pop();
+
+                    compile_destructuring_assignment(&mut ib, span, *left, source)?;
+
+                    // The destructuring assignment evaluates to the righthand side
+                    ib.build_local_load(PossiblyExternalId::Local(source));
+                }
+                _ => unimplementedc!(span, "assignment to non-identifier"),
             },
             AssignmentTarget::LocalId(id) => {
                 ib.accept_expr(*right)?;
@@ -1446,20 +1506,14 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
         InstructionBuilder::new(self).lower_function_call_expr(span, fc)
     }
 
+    fn visit_tagged_template(&mut self, span: Span, tt: TaggedTemplate) -> Result<(), Error> {
+        InstructionBuilder::new(self).lower_tagged_template(span, tt)
+    }
+
     fn visit_return_statement(&mut self, _span: Span, ReturnStatement(stmt): ReturnStatement) -> Result<(), Error> {
         let mut ib = InstructionBuilder::new(self);
-        let finally = ib.current_function().enclosing_finally();
-
-        let tc_depth = ib.current_function().try_depth;
         ib.accept_expr(stmt)?;
-        if let Some(finally) = finally {
-            ib.build_pop_try();
-            ib.write_instr(Instruction::DelayedReturn);
-            ib.build_jmp(finally, false);
-        } else {
-            ib.build_ret(tc_depth);
-        }
-
+        ib.build_return();
         Ok(())
     }
 
@@ -1495,6 +1549,17 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
     ) -> Result<(), Error> {
         let mut ib = InstructionBuilder::new(self);
 
+        if let ExprKind::Literal(LiteralExpr::Identifier(sym::super_)) = target.kind {
+            ib.mark_references_super();
+
+            if preserve_this {
+                ib.build_this();
+            }
+            compile_super_property_key(&mut ib, *property, computed)?;
+            ib.build_super_prop_access();
+            return Ok(());
+        }
+
         ib.accept_expr(*target)?;
 
         match (*property, computed) {
@@ -1642,6 +1707,21 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
                     }
                 }
             }
+            ExprKind::PropertyAccess(prop)
+                if matches!(
+                    prop.target.kind,
+                    ExprKind::Literal(LiteralExpr::Identifier(sym::super_))
+                ) =>
+            {
+                ib.mark_references_super();
+
+                let kind = match tt {
+                    TokenType::Increment => AssignKind::PostfixIncrement,
+                    TokenType::Decrement => AssignKind::PostfixDecrement,
+                    _ => unreachable!("Token never emitted"),
+                };
+                compile_super_property_update(&mut ib, span, *prop.property, prop.computed, kind)?;
+            }
             ExprKind::PropertyAccess(prop) => {
                 ib.accept_expr(*prop.target)?;
 
@@ -1715,6 +1795,21 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
                     }
                 }
             }
+            ExprKind::PropertyAccess(prop)
+                if matches!(
+                    prop.target.kind,
+                    ExprKind::Literal(LiteralExpr::Identifier(sym::super_))
+                ) =>
+            {
+                ib.mark_references_super();
+
+                let kind = match tt {
+                    TokenType::Increment => AssignKind::PrefixIncrement,
+                    TokenType::Decrement => AssignKind::PrefixDecrement,
+                    _ => unreachable!("Token never emitted"),
+                };
+                compile_super_property_update(&mut ib, span, *prop.property, prop.computed, kind)?;
+            }
             ExprKind::PropertyAccess(prop) => {
                 ib.accept_expr(*prop.target)?;
 
@@ -1808,6 +1903,15 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
             parenthesized: _,
         }: ObjectLiteral,
     ) -> Result<(), Error> {
+        // Object literals that are destructuring patterns are compiled elsewhere and may repeat `__proto__`
+        if let Some((_, duplicate)) = exprs
+            .iter()
+            .filter(|(kind, _)| matches!(kind, ObjectMemberKind::Static(sym::__proto__)))
+            .nth(1)
+        {
+            return Err(Error::DuplicateProto(duplicate.span));
+        }
+
         let mut ib = InstructionBuilder::new(self);
 
         let (members, member_stack_values) = compile_object_members(&mut ib, exprs.iter().cloned())?;
@@ -1879,9 +1983,14 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
         ib.build_try_block(catch.is_some(), finally.as_ref().map(|&(id, _)| id));
 
         ib.current_function_mut().try_depth += 1;
-        ib.current_function_mut()
-            .finally_labels
-            .push(finally.as_ref().map(|&(finally_id, _)| Label::Finally { finally_id }));
+        if let Some(&(finally_id, _)) = finally.as_ref() {
+            let try_depth = ib.current_function().try_depth;
+            ib.current_function_mut().finally_labels.push(FinallyLabel {
+                label: Label::Finally { finally_id },
+                try_depth,
+                is_for_of: false,
+            });
+        }
         let res = ib.accept(*try_);
         ib.current_function_mut().try_depth -= 1;
         res?;
@@ -1910,13 +2019,21 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
                 ib.writew(u16::MAX);
             }
 
-            ib.visit_block_statement(catch.body_span, catch.body)?;
-
             if finally.is_some() {
+                // The try block is re-pushed for the finally block when entering the catch block
+                ib.current_function_mut().try_depth += 1;
+                let res = ib.visit_block_statement(catch.body_span, catch.body);
+                ib.current_function_mut().try_depth -= 1;
+                res?;
+
                 ib.build_pop_try();
+            } else {
+                ib.visit_block_statement(catch.body_span, catch.body)?;
             }
         }
-        ib.current_function_mut().finally_labels.pop();
+        if finally.is_some() {
+            ib.current_function_mut().finally_labels.pop();
+        }
 
         if let Some((finally_id, finally)) = finally {
             ib.current_function_mut()
@@ -1997,10 +2114,20 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
             expr,
             body,
             scope,
+            is_await,
         }: ForOfLoop,
     ) -> Result<(), Error> {
+        let kind = if is_await {
+            if !self.current_function().is_async() {
+                return Err(Error::AwaitOutsideAsync { await_expr: span });
+            }
+            ForEachLoopKind::ForOf(Asyncness::Yes)
+        } else {
+            ForEachLoopKind::ForOf(Asyncness::No)
+        };
+
         ForEachDesugarCtxt::new(&mut InstructionBuilder::new(self), span)?.desugar_for_each_kinded_loop(
-            ForEachLoopKind::ForOf,
+            kind,
             binding,
             expr,
             body,
@@ -2128,15 +2255,17 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
     fn visit_break(&mut self, span: Span, sym: Option<Symbol>) -> Result<(), Error> {
         let mut ib = InstructionBuilder::new(self);
 
-        if ib.current_function().enclosing_finally().is_some() {
+        if ib.current_function().has_enclosing_user_finally() {
             unimplementedc!(span, "`break` in a try-finally block");
         }
 
-        let breakable = ib
+        let (breakable_idx, breakable) = ib
             .current_function_mut()
             .find_breakable(sym, BreakStmt::Break)
             .ok_or(Error::IllegalBreak(span))?;
 
+        for_each::build_for_of_exits(&mut ib, breakable_idx, BreakStmt::Break);
+
         match breakable {
             Breakable::Loop { loop_id, label: _ } => {
                 ib.build_jmp(Label::LoopEnd { loop_id }, false);
@@ -2154,15 +2283,17 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
     fn visit_continue(&mut self, span: Span, sym: Option<Symbol>) -> Result<(), Error> {
         let mut ib = InstructionBuilder::new(self);
 
-        if ib.current_function().enclosing_finally().is_some() {
+        if ib.current_function().has_enclosing_user_finally() {
             unimplementedc!(span, "`continue` in a try-finally block");
         }
 
-        let breakable = ib
+        let (breakable_idx, breakable) = ib
             .current_function_mut()
             .find_breakable(sym, BreakStmt::Continue)
             .ok_or(Error::IllegalBreak(span))?;
 
+        for_each::build_for_of_exits(&mut ib, breakable_idx, BreakStmt::Continue);
+
         match breakable {
             Breakable::Loop { loop_id, label: _ } => {
                 ib.build_jmp(Label::LoopIterationEnd { loop_id }, false);
@@ -2277,6 +2408,8 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
             statements,
             ty: FunctionKind::Function(Asyncness::No),
             ty_segment: None,
+            // Classes are always strict mode code
+            is_strict: true,
         };
 
         ib.visit_function_expr_possibly_constructor(
@@ -2308,6 +2441,11 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
             ),
         };
 
+        // The home object of the constructor is Class.prototype, which is needed for `super.x` in the constructor
+        ib.accept_expr(load_class_binding.clone())?;
+        ib.accept_expr(class_prototype.clone())?;
+        ib.build_set_home_object();
+
         let methods = class.members.iter().filter(|member| {
             matches!(
                 member.value,
@@ -2342,57 +2480,26 @@ impl Visitor<Result<(), Error>> for FunctionCompiler<'_> {
 
         if let Some(super_id) = load_super_class {
             // Add the superclass' prototype to our prototype chain
-            // Class.prototype.__proto__ = Superclass.prototype
-
-            ib.visit_expression_statement(Expr {
+            // Object.setPrototypeOf(Class.prototype, Superclass.prototype)
+            ib.accept_expr(class_prototype)?;
+            ib.accept_expr(Expr {
                 span: Span::COMPILER_GENERATED,
-                kind: ExprKind::assignment(
-                    Expr {
-                        span: Span::COMPILER_GENERATED,
-                        kind: ExprKind::property_access(
-                            false,
-                            class_prototype.clone(),
-                            Expr {
-                                span: Span::COMPILER_GENERATED,
-                                kind: ExprKind::identifier(sym::__proto__),
-                            },
-                        ),
-                    },
+                kind: ExprKind::property_access(
+                    false,
+                    super_id.clone(),
                     Expr {
                         span: Span::COMPILER_GENERATED,
-                        kind: ExprKind::property_access(
-                            false,
-                            super_id.clone(),
-                            Expr {
-                                span: Span::COMPILER_GENERATED,
-                                kind: ExprKind::identifier(sym::prototype),
-                            },
-                        ),
+                        kind: ExprKind::identifier(sym::prototype),
                     },
-                    TokenType::Assignment,
                 ),
             })?;
+            ib.build_set_prototype();
 
             // Set the [[Prototype]] of this class to its superclass
-            // Class.__proto__ = Superclass
-            ib.visit_expression_statement(Expr {
-                span: Span::COMPILER_GENERATED,
-                kind: ExprKind::assignment(
-                    Expr {
-                        span: Span::COMPILER_GENERATED,
-                        kind: ExprKind::property_access(
-                            false,
-                            load_class_binding.clone(),
-                            Expr {
-                                span: Span::COMPILER_GENERATED,
-                                kind: ExprKind::identifier(sym::__proto__),
-                            },
-                        ),
-                    },
-                    super_id,
-                    TokenType::Assignment,
-                ),
-            })?;
+            // Object.setPrototypeOf(Class, Superclass)
+            ib.accept_expr(load_class_binding.clone())?;
+            ib.accept_expr(super_id)?;
+            ib.build_set_prototype();
         }
 
         // Load it one last time since the `class` expression ultimately should evaluate to that class
@@ -2582,6 +2689,76 @@ fn compile_object_members(
     Ok((members, stack_values))
 }
 
+/// Compiles the key of a `super` property access, i.e. `x` in `super.x` or `super[x]`
+fn compile_super_property_key(
+    ib: &mut InstructionBuilder<'_, '_>,
+    property: Expr,
+    computed: bool,
+) -> Result<(), Error> {
+    match (property, computed) {
+        (
+            Expr {
+                kind: ExprKind::Literal(LiteralExpr::Identifier(ident)),
+                span,
+            },
+            false,
+        ) => ib
+            .build_string_constant(ident)
+            .map_err(|_| Error::ConstantPoolLimitExceeded(span)),
+        (expr, _) => ib.accept_expr(expr),
+    }
+}
+
+/// Compiles the key of a `super` property access into a new local,
+/// for expressions that both read and write the property, like `super.x += 1`
+fn compile_super_property_key_local(
+    ib: &mut InstructionBuilder<'_, '_>,
+    span: Span,
+    property: Expr,
+    computed: bool,
+) -> Result<PossiblyExternalId, Error> {
+    let key = ib
+        .add_unnameable_local(sym::empty)
+        .map_err(|_| Error::LocalLimitExceeded(span))?;
+    let key = PossiblyExternalId::Local(key);
+
+    compile_super_property_key(ib, property, computed)?;
+    ib.build_local_store(AssignKind::Assignment, key);
+    ib.build_pop();
+    Ok(key)
+}
+
+/// Compiles an update expression on a `super` property, like `super.x++`.
+///
+/// The old value is copied into a local, so that the local store with the given postfix or prefix assign kind
+/// can convert and update it. The updated local is then written back to the property.
+fn compile_super_property_update(
+    ib: &mut InstructionBuilder<'_, '_>,
+    span: Span,
+    property: Expr,
+    computed: bool,
+    kind: AssignKind,
+) -> Result<(), Error> {
+    let key = compile_super_property_key_local(ib, span, property, computed)?;
+    let value = ib
+        .add_unnameable_local(sym::empty)
+        .map_err(|_| Error::LocalLimitExceeded(span))?;
+    let value = PossiblyExternalId::Local(value);
+
+    ib.build_local_load(key);
+    ib.build_super_prop_access();
+    ib.build_local_store(AssignKind::Assignment, value);
+    ib.build_pop();
+
+    // Leaves the result of the update expression on the stack
+    ib.build_local_store(kind, value);
+    ib.build_local_load(key);
+    ib.build_local_load(value);
+    ib.build_super_prop_assign();
+    ib.build_pop();
+    Ok(())
+}
+
 fn compile_destructuring_pattern(
     ib: &mut InstructionBuilder<'_, '_>,
     from: Expr,
@@ -2629,46 +2806,434 @@ fn compile_destructuring_pattern(
             }
         }
         Pattern::Array { fields, rest } => {
-            if rest.is_some() {
-                unimplementedc!(at, "rest operator in array destructuring");
+            let source = ib
+                .add_unnameable_local(sym::empty)
+                .map_err(|_| Error::LocalLimitExceeded(at))?;
+            ib.accept_expr(from)?;
+            ib.build_local_store(AssignKind::Assignment, PossiblyExternalId::Local(source));
+            ib.build_pop();
+
+            let mut elements = Vec::with_capacity(fields.len());
+            for field in fields {
+                elements.push(match *field {
+                    Some((name, ref default)) => Some((
+                        DestructuringTarget::Local(ib.find_local_from_binding(name)),
+                        default.clone(),
+                    )),
+                    None => None,
+                });
             }
+            let rest = rest.map(|rest| DestructuringTarget::Local(ib.find_local_from_binding(rest)));
 
-            let field_count = fields
-                .len()
-                .try_into()
-                .map_err(|_| Error::DestructureLimitExceeded(at))?;
+            compile_array_destructuring(ib, at, source, elements, rest)?;
+        }
+    }
 
-            for field in fields.iter().rev() {
-                if let Some((_, Some(default))) = field {
-                    ib.accept_expr(default.clone())?;
-                }
+    Ok(())
+}
+
+/// The target of an element in an array destructuring pattern
+enum DestructuringTarget {
+    /// The local of a variable declaration or parameter
+    Local(BackLocalId),
+    /// An assignment target, which can also be a nested destructuring pattern
+    Expr(Expr),
+}
+
+/// Destructures the iterable stored in the `source` local using the iterator protocol:
+///
+/// ```js
+/// let __iter = source[Symbol.iterator]();
+/// try {
+///     target1 = IteratorStep(__iter) ?? default1; // only replaces undefined
+///     target2 = IteratorStep(__iter);
+///     rest = IteratorRest(__iter);
+/// } catch (e) {
+///     try { __iter.return?.() } catch {}
+///     throw e;
+/// }
+/// __iter.return?.();
+/// ```
+///
+/// `IteratorStep` and `IteratorRest` replace the iterator with `undefined` once it is done (or throws),
+/// so only iterators that are exited early are closed.
+fn compile_array_destructuring(
+    ib: &mut InstructionBuilder<'_, '_>,
+    span: Span,
+    source: BackLocalId,
+    elements: Vec<Option<(DestructuringTarget, Option<Expr>)>>,
+    rest: Option<DestructuringTarget>,
+) -> Result<(), Error> {
+    let iterator = ib
+        .add_unnameable_local(sym::empty)
+        .map_err(|_| Error::LocalLimitExceeded(span))?;
+    let value = ib
+        .add_unnameable_local(sym::empty)
+        .map_err(|_| Error::LocalLimitExceeded(span))?;
+
+    ib.build_local_load(PossiblyExternalId::Local(source));
+    ib.build_symbol_iterator();
+    ib.build_local_store(AssignKind::Assignment, PossiblyExternalId::Local(iterator));
+    ib.build_pop();
+
+    let mut ib = InstructionBuilder::new(ib);
+    ib.build_try_block(true, None);
+    ib.current_function_mut().try_depth += 1;
+    let res = (|| {
+        for element in elements {
+            ib.build_iterator_step(iterator);
+            ib.build_local_store(AssignKind::Assignment, PossiblyExternalId::Local(value));
+            ib.build_pop();
+
+            if let Some((target, default)) = element {
+                compile_destructuring_element(&mut ib, span, target, default, value)?;
             }
+        }
 
-            ib.accept_expr(from)?;
+        if let Some(rest) = rest {
+            ib.build_iterator_rest(iterator);
+            ib.build_local_store(AssignKind::Assignment, PossiblyExternalId::Local(value));
+            ib.build_pop();
+            compile_destructuring_element(&mut ib, span, rest, None, value)?;
+        }
 
-            ib.build_arraydestruct(field_count);
+        Ok(())
+    })();
+    ib.current_function_mut().try_depth -= 1;
+    res?;
 
-            for name in fields {
-                ib.write_bool(name.is_some());
-                if let Some((name, ref default)) = *name {
-                    let id = ib.find_local_from_binding(name);
+    ib.build_pop_try();
+    ib.build_jmp(Label::TryEnd, true);
 
-                    let NumberConstant(id) = ib
-                        .current_function_mut()
-                        .cp
-                        .add_number(id.0 as f64)
-                        .map_err(|_| Error::ConstantPoolLimitExceeded(at))?;
+    // The exception is stored in the element value local, which is not needed anymore at this point
+    ib.add_local_label(Label::Catch);
+    ib.writew(value.0);
+    for_each::build_iterator_close_on_throw(&mut ib, iterator, false);
+    ib.build_local_load(PossiblyExternalId::Local(value));
+    ib.build_throw();
+
+    ib.add_local_label(Label::TryEnd);
+    for_each::build_iterator_close(&mut ib, iterator, false, true);
+
+    Ok(())
+}
+
+/// Assigns the value stored in the `value` local to the target of an array destructuring pattern
+fn compile_destructuring_element(
+    ib: &mut InstructionBuilder<'_, '_>,
+    span: Span,
+    target: DestructuringTarget,
+    default: Option<Expr>,
+    value: BackLocalId,
+) -> Result<(), Error> {
+    match target {
+        DestructuringTarget::Local(id) => {
+            if let Some(default) = default {
+                compile_destructuring_default(ib, span, default, value)?;
+            }
+            ib.build_local_load(PossiblyExternalId::Local(value));
+            ib.build_local_store(AssignKind::Assignment, PossiblyExternalId::Local(id));
+            ib.build_pop();
+            Ok(())
+        }
+        DestructuringTarget::Expr(target) => compile_destructuring_target(ib, span, target, default, value),
+    }
+}
+
+/// Assigns the value stored in the `source` local to an assignment target,
+/// which can also be a (nested) object or array destructuring pattern.
+fn compile_destructuring_assignment(
+    ib: &mut InstructionBuilder<'_, '_>,
+    span: Span,
+    target: Expr,
+    source: BackLocalId,
+) -> Result<(), Error> {
+    match target.kind {
+        ExprKind::Object(ObjectLiteral {
+            members,
+            parenthesized: false,
+        }) => {
+            // Patterns with computed keys are lowered to a property access per member, so that the keys
+            // are evaluated in order, interleaved with the assignments
+            if members
+                .iter()
+                .any(|(kind, _)| matches!(kind, ObjectMemberKind::Dynamic(_)))
+            {
+                return compile_computed_object_destructuring(ib, span, members, source);
+            }
+
+            let mut fields = Vec::with_capacity(members.len());
+            let mut targets = Vec::with_capacity(members.len());
+            let mut rest = None;
+
+            for (kind, value) in members {
+                let (name, target, default) = match kind {
+                    ObjectMemberKind::Static(name) => {
+                        let (target, default) = split_destructuring_default(value);
+                        (name, target, default)
+                    }
+                    // `{ x = 1 }`
+                    ObjectMemberKind::Default(name) => (
+                        name,
+                        Expr {
+                            span: value.span,
+                            kind: ExprKind::identifier(name),
+                        },
+                        Some(value),
+                    ),
+                    ObjectMemberKind::Spread => {
+                        if rest.is_some() {
+                            unimplementedc!(span, "duplicate rest binding in object destructuring");
+                        }
+
+                        let rest_local = ib
+                            .add_unnameable_local(sym::empty)
+                            .map_err(|_| Error::LocalLimitExceeded(span))?;
+                        rest = Some((rest_local, value));
+                        continue;
+                    }
+                    ObjectMemberKind::Dynamic(_) => unreachable!("computed keys are handled separately"),
+                    ObjectMemberKind::Getter(_)
+                    | ObjectMemberKind::DynamicGetter(_)
+                    | ObjectMemberKind::Setter(_)
+                    | ObjectMemberKind::DynamicSetter(_) => {
+                        return Err(Error::Unexpected(value.span, ACCESSOR_IN_PATTERN));
+                    }
+                };
+
+                let local = ib
+                    .add_unnameable_local(sym::empty)
+                    .map_err(|_| Error::LocalLimitExceeded(span))?;
+                fields.push((local, name));
+                targets.push((local, target, default));
+            }
+
+            ib.build_local_load(PossiblyExternalId::Local(source));
+            ib.build_objdestruct(
+                fields
+                    .len()
+                    .try_into()
+                    .map_err(|_| Error::DestructureLimitExceeded(span))?,
+                rest.as_ref().map(|&(local, _)| local),
+            );
+
+            for (local, name) in fields {
+                let NumberConstant(var_id) = ib
+                    .current_function_mut()
+                    .cp
+                    .add_number(local.0 as f64)
+                    .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
+                let SymbolConstant(ident_id) = ib
+                    .current_function_mut()
+                    .cp
+                    .add_symbol(name)
+                    .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
+                // Defaults are evaluated lazily when assigning to the targets below
+                ib.write_bool(false);
+                ib.writew(var_id);
+                ib.writew(ident_id);
+            }
+
+            for (local, target, default) in targets {
+                compile_destructuring_target(ib, span, target, default, local)?;
+            }
+
+            if let Some((local, target)) = rest {
+                compile_destructuring_assignment(ib, span, target, local)?;
+            }
+        }
+        ExprKind::Array(ArrayLiteral {
+            members,
+            parenthesized: false,
+        }) => {
+            let mut elements = Vec::with_capacity(members.len());
+            let mut rest = None;
+
+            let member_count = members.len();
+            for (index, kind) in members.into_iter().enumerate() {
+                match kind {
+                    ArrayMemberKind::Empty => elements.push(None),
+                    ArrayMemberKind::Item(expr) => {
+                        let (target, default) = split_destructuring_default(expr);
+                        elements.push(Some((DestructuringTarget::Expr(target), default)));
+                    }
+                    ArrayMemberKind::Spread(expr) => {
+                        if index != member_count - 1 {
+                            unimplementedc!(span, "rest element must be last element");
+                        }
 
-                    ib.write_bool(default.is_some());
-                    ib.writew(id);
+                        rest = Some(DestructuringTarget::Expr(expr));
+                    }
                 }
             }
+
+            compile_array_destructuring(ib, span, source, elements, rest)?;
+        }
+        kind => {
+            ib.visit_assignment_expression(
+                span,
+                AssignmentExpr::new_expr_place(
+                    Expr {
+                        span: target.span,
+                        kind,
+                    },
+                    Expr {
+                        span: Span::COMPILER_GENERATED,
+                        kind: ExprKind::compiled(compile_local_load(PossiblyExternalId::Local(source))),
+                    },
+                    TokenType::Assignment,
+                ),
+            )?;
+            ib.build_pop();
         }
     }
 
     Ok(())
 }
 
+const ACCESSOR_IN_PATTERN: &str = "getter or setter in destructuring pattern";
+
+/// Destructures an object pattern containing computed keys, e.g. `({ [key]: x, y } = source)`,
+/// by assigning `source[key]` and `source.y` to the targets in order
+fn compile_computed_object_destructuring(
+    ib: &mut InstructionBuilder<'_, '_>,
+    span: Span,
+    members: Vec<(ObjectMemberKind, Expr)>,
+    source: BackLocalId,
+) -> Result<(), Error> {
+    let load_source = Expr {
+        span: Span::COMPILER_GENERATED,
+        kind: ExprKind::compiled(compile_local_load(PossiblyExternalId::Local(source))),
+    };
+
+    for (kind, value) in members {
+        let (computed, key, target, default) = match kind {
+            ObjectMemberKind::Static(name) => {
+                let (target, default) = split_destructuring_default(value);
+                (false, ExprKind::identifier(name), target, default)
+            }
+            ObjectMemberKind::Default(name) => (
+                false,
+                ExprKind::identifier(name),
+                Expr {
+                    span: value.span,
+                    kind: ExprKind::identifier(name),
+                },
+                Some(value),
+            ),
+            ObjectMemberKind::Dynamic(key) => {
+                let (target, default) = split_destructuring_default(value);
+                (true, key.kind, target, default)
+            }
+            ObjectMemberKind::Spread => {
+                unimplementedc!(span, "rest element in object destructuring with computed keys")
+            }
+            ObjectMemberKind::Getter(_)
+            | ObjectMemberKind::DynamicGetter(_)
+            | ObjectMemberKind::Setter(_)
+            | ObjectMemberKind::DynamicSetter(_) => return Err(Error::Unexpected(value.span, ACCESSOR_IN_PATTERN)),
+        };
+        let property = ExprKind::property_access(
+            computed,
+            load_source.clone(),
+            Expr {
+                span: Span::COMPILER_GENERATED,
+                kind: key,
+            },
+        );
+
+        let local = ib
+            .add_unnameable_local(sym::empty)
+            .map_err(|_| Error::LocalLimitExceeded(span))?;
+        ib.visit_assignment_expression(
+            span,
+            AssignmentExpr::new_local_place(
+                local,
+                Expr {
+                    span: Span::COMPILER_GENERATED,
+                    kind: property,
+                },
+                TokenType::Assignment,
+            ),
+        )?;
+        ib.build_pop();
+
+        compile_destructuring_target(ib, span, target, default, local)?;
+    }
+
+    Ok(())
+}
+
+/// Assigns a destructured value to its target, replacing it with the default value first if it is `undefined`
+fn compile_destructuring_target(
+    ib: &mut InstructionBuilder<'_, '_>,
+    span: Span,
+    target: Expr,
+    default: Option<Expr>,
+    value: BackLocalId,
+) -> Result<(), Error> {
+    if let Some(default) = default {
+        compile_destructuring_default(ib, span, default, value)?;
+    }
+
+    compile_destructuring_assignment(ib, span, target, value)
+}
+
+/// Replaces the value stored in the `value` local with `default` if it is undefined
+fn compile_destructuring_default(
+    ib: &mut InstructionBuilder<'_, '_>,
+    span: Span,
+    default: Expr,
+    value: BackLocalId,
+) -> Result<(), Error> {
+    let load_value = Expr {
+        span: Span::COMPILER_GENERATED,
+        kind: ExprKind::compiled(compile_local_load(PossiblyExternalId::Local(value))),
+    };
+
+    // value = value === undefined ? default : value
+    ib.visit_assignment_expression(
+        span,
+        AssignmentExpr::new_local_place(
+            value,
+            Expr {
+                span: Span::COMPILER_GENERATED,
+                kind: ExprKind::conditional(
+                    Expr {
+                        span: Span::COMPILER_GENERATED,
+                        kind: ExprKind::binary(
+                            load_value.clone(),
+                            Expr {
+                                span: Span::COMPILER_GENERATED,
+                                kind: ExprKind::undefined_literal(),
+                            },
+                            TokenType::StrictEquality,
+                        ),
+                    },
+                    default,
+                    load_value,
+                ),
+            },
+            TokenType::Assignment,
+        ),
+    )?;
+    ib.build_pop();
+
+    Ok(())
+}
+
+/// Splits a destructuring target with a default value, e.g. `x = 1`, into the target and its default value
+fn split_destructuring_default(expr: Expr) -> (Expr, Option<Expr>) {
+    match expr.kind {
+        ExprKind::Assignment(AssignmentExpr {
+            left: AssignmentTarget::Expr(target),
+            right: default,
+            operator: TokenType::Assignment,
+        }) => (*target, Some(*default)),
+        kind => (Expr { span: expr.span, kind }, None),
+    }
+}
+
 fn compile_class_members(
     ib: &mut InstructionBuilder<'_, '_>,
     span: Span,
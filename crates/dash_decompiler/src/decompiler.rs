@@ -1,5 +1,5 @@
 use dash_middle::compiler::constant::{
-    BooleanConstant, ConstantPool, FunctionConstant, NumberConstant, RegexConstant, SymbolConstant,
+    BooleanConstant, ConstantPool, FunctionConstant, NumberConstant, RegexConstant, SymbolConstant, TemplateConstant,
 };
 use dash_middle::compiler::instruction::{Instruction, IntrinsicOperation};
 use dash_middle::compiler::{FunctionCallKind, ObjectMemberKind};
@@ -130,7 +130,11 @@ impl<'interner, 'buf> FunctionDecompiler<'interner, 'buf> {
                     functions.push(fun);
                     self.handle_op_instr("function", &[&format_args!("function {name}")]);
                 }
-                Instruction::String | Instruction::Number | Instruction::Boolean | Instruction::Regex => {
+                Instruction::String
+                | Instruction::Number
+                | Instruction::Boolean
+                | Instruction::Regex
+                | Instruction::BigInt => {
                     let id = self.read_u16()?;
                     let (name, args) = match instr {
                         Instruction::String => (
@@ -145,6 +149,10 @@ impl<'interner, 'buf> FunctionDecompiler<'interner, 'buf> {
                             "boolean",
                             &self.constants.booleans[BooleanConstant(id)] as &dyn fmt::Display,
                         ),
+                        Instruction::BigInt => (
+                            "bigint",
+                            &self.interner.resolve(self.constants.symbols[SymbolConstant(id)]) as &dyn fmt::Display,
+                        ),
                         Instruction::Regex => (
                             "regex",
                             &self.interner.resolve(self.constants.regexes[RegexConstant(id)].1) as &dyn fmt::Display,
@@ -153,6 +161,16 @@ impl<'interner, 'buf> FunctionDecompiler<'interner, 'buf> {
                     };
                     self.handle_op_instr(name, &[args]);
                 }
+                Instruction::TemplateObject => {
+                    let id = self.read_u16()?;
+                    let raw = self.constants.templates[TemplateConstant(id)]
+                        .raw
+                        .iter()
+                        .map(|&sym| self.interner.resolve(sym).to_string())
+                        .collect::<Vec<_>>()
+                        .join("${}");
+                    self.handle_op_instr("templateobject", &[&format_args!("`{raw}`")]);
+                }
                 Instruction::Null => self.handle_opless_instr("null"),
                 Instruction::Undefined => self.handle_opless_instr("undefined"),
                 Instruction::LdLocal => {
@@ -416,6 +434,7 @@ impl<'interner, 'buf> FunctionDecompiler<'interner, 'buf> {
                     }
                 }
                 Instruction::CallSymbolIterator => self.handle_opless_instr("@@iterator"),
+                Instruction::CallSymbolAsyncIterator => self.handle_opless_instr("@@asyncIterator"),
                 Instruction::CallForInIterator => self.handle_opless_instr("@@forInIterator"),
                 Instruction::DeletePropertyStatic => self.handle_incw_op_instr("deletepropertystatic")?,
                 Instruction::DeletePropertyDynamic => self.handle_opless_instr("deletepropertydynamic"),
@@ -427,13 +446,6 @@ impl<'interner, 'buf> FunctionDecompiler<'interner, 'buf> {
                     }
                     self.handle_op_map_instr("objdestruct", &[("count", &count)])
                 }
-                Instruction::ArrayDestruct => {
-                    let count = self.read_u16()?;
-                    for _ in 0..count {
-                        self.read_u16()?; // discard var id
-                    }
-                    self.handle_op_map_instr("arraydestruct", &[("count", &count)])
-                }
                 Instruction::AssignProperties => {
                     let member_kind_count = self.read_u16()?;
                     let stack_value_count = self.read_u16()?;
@@ -449,6 +461,15 @@ impl<'interner, 'buf> FunctionDecompiler<'interner, 'buf> {
                     )
                 }
                 Instruction::NewTarget => self.handle_opless_instr("new.target"),
+                Instruction::SuperPropAccess => self.handle_opless_instr("superpropaccess"),
+                Instruction::SuperPropAssign => self.handle_opless_instr("superpropassign"),
+                Instruction::SuperPropDelete => self.handle_opless_instr("superpropdelete"),
+                Instruction::SetHomeObject => self.handle_opless_instr("sethomeobject"),
+                Instruction::SetPrototype => self.handle_opless_instr("setprototype"),
+                Instruction::IteratorStep => self.handle_incw_op_instr("iteratorstep")?,
+                Instruction::IteratorRest => self.handle_incw_op_instr("iteratorrest")?,
+                Instruction::IteratorReturn => self.handle_incw_op_instr("iteratorreturn")?,
+                Instruction::CheckIteratorResult => self.handle_opless_instr("checkiteratorresult"),
                 Instruction::Nop => self.handle_opless_instr("nop"),
             }
         }
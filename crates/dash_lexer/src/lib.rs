@@ -7,7 +7,7 @@ use std::borrow::Cow;
 use std::ops::Range;
 
 use dash_middle::interner::{StringInterner, Symbol, sym};
-use dash_middle::lexer::token::{EXPR_PRECEDED_TOKENS, Token, TokenType, as_token};
+use dash_middle::lexer::token::{EXPR_PRECEDED_TOKENS, TemplateSegment, Token, TokenType, as_token};
 use dash_middle::parser::error::Error;
 use dash_middle::sourcemap::Span;
 use dash_middle::util;
@@ -177,7 +177,9 @@ impl<'a, 'interner> Lexer<'a, 'interner> {
             }
 
             if cur == b'\\' {
-                self.read_escape_character(&mut lexeme_starting_idx, &mut lexeme);
+                if let Err(err) = self.read_escape_character(&mut lexeme_starting_idx, &mut lexeme) {
+                    self.error(err);
+                }
                 continue;
             }
 
@@ -219,10 +221,27 @@ impl<'a, 'interner> Lexer<'a, 'interner> {
             }
         }
 
+        if self.read_bigint_suffix() {
+            return;
+        }
+
         let sym = self.interner.intern(self.current_lexeme());
         self.token(ty_ctor(sym));
     }
 
+    /// Checks if the number literal that was just read is followed by a `BigInt` suffix (`n`)
+    /// and emits a `BigInt` token if so
+    fn read_bigint_suffix(&mut self) -> bool {
+        if self.is_eof() || self.current_real() != b'n' {
+            return false;
+        }
+
+        let sym = self.interner.intern(self.current_lexeme());
+        self.advance();
+        self.token(TokenType::NumberBigInt(sym));
+        true
+    }
+
     /// Reads a number literal
     fn read_number_literal(&mut self) {
         let mut is_float = false;
@@ -259,11 +278,48 @@ impl<'a, 'interner> Lexer<'a, 'interner> {
 
             self.advance();
         }
+
+        if !is_float && !is_exp && self.read_bigint_suffix() {
+            return;
+        }
+
         let sym = self.interner.intern(self.current_lexeme());
         self.token(TokenType::NumberDec(sym));
     }
 
-    fn read_escape_character(&mut self, lexeme_starting_idx: &mut usize, lexeme: &mut Option<Cow<'a, str>>) {
+    /// Reads the code point of a `\uXXXX` or `\u{X...}` escape sequence, assuming the `u` has already been consumed
+    fn read_unicode_escape(&mut self) -> Result<u32, Error> {
+        let (hex, len) = if self.current() == Some(b'{') {
+            let Some(end) = self.input[self.idx..].find('}') else {
+                return Err(Error::UnexpectedEof);
+            };
+            (self.input.get(self.idx + 1..self.idx + end), end + 1)
+        } else {
+            (self.input.get(self.idx..self.idx + 4), 4)
+        };
+
+        let Some(hex) = hex else {
+            return Err(Error::UnexpectedEof);
+        };
+
+        if hex.is_empty() || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(Error::InvalidEscapeSequence(self.span()));
+        }
+
+        match u32::from_str_radix(hex, 16) {
+            Ok(code_point) if code_point <= 0x0010_FFFF => {
+                self.advance_n(len);
+                Ok(code_point)
+            }
+            _ => Err(Error::InvalidEscapeSequence(self.span())),
+        }
+    }
+
+    fn read_escape_character(
+        &mut self,
+        lexeme_starting_idx: &mut usize,
+        lexeme: &mut Option<Cow<'a, str>>,
+    ) -> Result<(), Error> {
         // Append borrowed segment since last escape sequence
         let segment = self.subslice(*lexeme_starting_idx..self.idx);
         match lexeme {
@@ -298,30 +354,45 @@ impl<'a, 'interner> Lexer<'a, 'interner> {
                         lexeme.as_mut().unwrap().to_mut().push(num as char);
                         self.advance_n(2);
                     }
-                    Some(Err(_)) => {
-                        self.error(Error::InvalidEscapeSequence(self.span()));
-                        return;
-                    }
-                    None => {
-                        self.error(Error::UnexpectedEof);
-                        return;
-                    }
+                    Some(Err(_)) => return Err(Error::InvalidEscapeSequence(self.span())),
+                    None => return Err(Error::UnexpectedEof),
                 }
             }
             b'u' => {
                 self.advance();
-                let Some(hex) = self.input.get(self.idx..self.idx + 4) else {
-                    self.error(Error::UnexpectedEof);
-                    return;
-                };
+                let mut code_point = self.read_unicode_escape()?;
+
+                // Strings are stored as UTF-8, so a leading surrogate must be followed by an escaped trailing surrogate
+                // which together form a single code point
+                if (0xD800..0xDC00).contains(&code_point) {
+                    if self.input.get(self.idx..self.idx + 2) != Some("\\u") {
+                        return Err(Error::InvalidEscapeSequence(self.span()));
+                    }
+                    self.advance_n(2);
 
-                let Some(escaped) = u32::from_str_radix(hex, 16).ok().and_then(char::from_u32) else {
-                    self.error(Error::InvalidEscapeSequence(self.span()));
-                    return;
+                    let trail = self.read_unicode_escape()?;
+                    if !(0xDC00..0xE000).contains(&trail) {
+                        return Err(Error::InvalidEscapeSequence(self.span()));
+                    }
+                    code_point = 0x10000 + ((code_point - 0xD800) << 10) + (trail - 0xDC00);
+                }
+
+                let Some(escaped) = char::from_u32(code_point) else {
+                    return Err(Error::InvalidEscapeSequence(self.span()));
                 };
 
                 lexeme.as_mut().unwrap().to_mut().push(escaped);
-                self.advance_n(4);
+            }
+            b'\r' | b'\n' => {
+                // Line continuation: the escaped line terminator is not part of the string,
+                // but the lexeme can no longer be borrowed from the input as a whole
+                lexeme.as_mut().unwrap().to_mut();
+                if escape == b'\r' && self.peek() == Some(b'\n') {
+                    self.advance();
+                }
+                self.advance();
+                self.line += 1;
+                self.line_idx = self.idx;
             }
             other if !other.is_ascii() => {
                 // if the escaped character is non-ascii, decode UTF-8
@@ -329,18 +400,20 @@ impl<'a, 'interner> Lexer<'a, 'interner> {
                 lexeme.as_mut().unwrap().to_mut().push(c);
                 self.advance_n(len);
             }
-            // TODO: handle \u, \x
             other => {
                 lexeme.as_mut().unwrap().to_mut().push(other as char);
                 self.advance();
             }
         }
         *lexeme_starting_idx = self.idx;
+        Ok(())
     }
 
     fn read_template_literal_segment(&mut self) {
         let mut found_end = false;
         let mut is_interpolated = false;
+        // Invalid escape sequences are only an error in untagged templates, which the parser checks
+        let mut has_invalid_escape = false;
 
         let mut lexeme: Option<Cow<'a, str>> = None;
         let mut lexeme_starting_idx = self.idx;
@@ -354,7 +427,12 @@ impl<'a, 'interner> Lexer<'a, 'interner> {
             }
 
             if cur == b'\\' {
-                self.read_escape_character(&mut lexeme_starting_idx, &mut lexeme);
+                if self
+                    .read_escape_character(&mut lexeme_starting_idx, &mut lexeme)
+                    .is_err()
+                {
+                    has_invalid_escape = true;
+                }
                 continue;
             }
 
@@ -382,17 +460,29 @@ impl<'a, 'interner> Lexer<'a, 'interner> {
 
         let end = if is_interpolated { self.idx } else { self.idx - 1 };
 
-        let lexeme = match lexeme {
-            None => Cow::Borrowed(self.subslice(self.start + 1..end)),
+        let cooked = match lexeme {
+            _ if has_invalid_escape => None,
+            None => Some(Cow::Borrowed(self.subslice(self.start + 1..end))),
             Some(Cow::Owned(mut lexeme)) => {
                 lexeme.push_str(self.subslice(lexeme_starting_idx..end));
-                Cow::Owned(lexeme)
+                Some(Cow::Owned(lexeme))
             }
             Some(Cow::Borrowed(..)) => unreachable!("Lexeme cannot be borrowed at this point"),
         };
 
-        let sym = self.interner.intern(lexeme);
-        self.token(TokenType::TemplateLiteral(sym)); // TODO: check if the spans created by this call are right!!
+        // Line terminators in the raw string are normalized to \n
+        let raw = self.subslice(self.start + 1..end);
+        let raw = if raw.contains('\r') {
+            Cow::Owned(raw.replace("\r\n", "\n").replace('\r', "\n"))
+        } else {
+            Cow::Borrowed(raw)
+        };
+
+        let segment = TemplateSegment {
+            cooked: cooked.map(|cooked| self.interner.intern(cooked)),
+            raw: self.interner.intern(raw),
+        };
+        self.token(TokenType::TemplateLiteral(segment)); // TODO: check if the spans created by this call are right!!
     }
 
     /// Assumes one character has already been read.
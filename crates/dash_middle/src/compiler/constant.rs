@@ -104,6 +104,11 @@ pub struct Function {
     pub source: Rc<str>,
     pub debug_symbols: DebugSymbols,
     pub has_extends_clause: bool,
+    /// Whether this function is strict mode code
+    pub is_strict: bool,
+    /// Whether this function (or an arrow function nested in it) accesses properties on `super`,
+    /// which requires it to have a home object
+    pub references_super: bool,
 }
 
 index_type!(
@@ -117,8 +122,22 @@ index_type!(
     pub struct RegexConstant(pub u16);
     #[derive(Copy, Default, Debug, Clone)]
     pub struct SymbolConstant(pub u16);
+    #[derive(Copy, Default, Debug, Clone)]
+    pub struct TemplateConstant(pub u16);
 );
 
+/// The strings of a tagged template.
+///
+/// Evaluating the same tagged template multiple times must pass the same strings array to the tag,
+/// so the VM caches the array it creates for a template by its address.
+#[cfg_attr(feature = "format", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+pub struct TemplateStrings {
+    /// The cooked strings, which are `None` for segments containing an invalid escape sequence
+    pub cooked: Vec<Option<Symbol>>,
+    pub raw: Vec<Symbol>,
+}
+
 #[cfg_attr(feature = "format", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Default, Debug, Clone)]
 pub struct ConstantPool {
@@ -128,6 +147,7 @@ pub struct ConstantPool {
     pub booleans: IndexThinVec<bool, BooleanConstant>,
     pub functions: IndexThinVec<Rc<Function>, FunctionConstant>,
     pub regexes: IndexThinVec<(Regex, Symbol), RegexConstant>,
+    pub templates: IndexThinVec<Rc<TemplateStrings>, TemplateConstant>,
 }
 
 pub struct LimitExceededError;
@@ -154,7 +174,8 @@ impl ConstantPool {
         add_symbol(symbols, Symbol) -> SymbolConstant,
         add_boolean(booleans, bool) -> BooleanConstant,
         add_function(functions, Rc<Function>) -> FunctionConstant,
-        add_regex(regexes, (Regex, Symbol)) -> RegexConstant
+        add_regex(regexes, (Regex, Symbol)) -> RegexConstant,
+        add_template(templates, Rc<TemplateStrings>) -> TemplateConstant
     );
 
     pub fn shrink_to_fit(&mut self) {
@@ -164,11 +185,13 @@ impl ConstantPool {
             booleans,
             functions,
             regexes,
+            templates,
         } = self;
         numbers.shrink_to_fit();
         symbols.shrink_to_fit();
         booleans.shrink_to_fit();
         functions.shrink_to_fit();
         regexes.shrink_to_fit();
+        templates.shrink_to_fit();
     }
 }
@@ -25,6 +25,8 @@ pub enum Instruction {
     Boolean,
     Number,
     Regex,
+    /// Loads a BigInt constant, stored as a symbol constant containing the source literal
+    BigInt,
     Null,
     Undefined,
     Function,
@@ -89,14 +91,38 @@ pub enum Instruction {
     Infinity,
     IntrinsicOp,
     CallSymbolIterator,
+    CallSymbolAsyncIterator,
     CallForInIterator,
     DeletePropertyStatic,
     DeletePropertyDynamic,
     ObjDestruct,
-    ArrayDestruct,
     AssignProperties,
     DelayedReturn,
+    TemplateObject,
     NewTarget,
+    /// Pops a property key and loads the property from the prototype of the current function's home object,
+    /// using the current `this` value as the receiver
+    SuperPropAccess,
+    /// Pops a property key and a value and assigns the value to the property on the prototype
+    /// of the current function's home object, using the current `this` value as the receiver
+    SuperPropAssign,
+    /// Pops a property key and throws a ReferenceError, as properties on `super` cannot be deleted
+    SuperPropDelete,
+    /// Pops a home object and a function and sets the home object of that function
+    SetHomeObject,
+    /// Pops a prototype and an object and sets the prototype of that object.
+    /// Unlike assigning to `__proto__`, this does not depend on `Object.prototype.__proto__`
+    SetPrototype,
+    /// Calls `next()` on the iterator stored in a local and pushes the value, or `undefined` if the iterator is done.
+    /// The local is replaced with `undefined` once the iterator is done or throws, so that it is not closed.
+    IteratorStep,
+    /// Collects the remaining values of the iterator stored in a local into an array and replaces the local with `undefined`
+    IteratorRest,
+    /// Calls the `return` method of the iterator stored in a local, if it has one and is not done.
+    /// Pushes the result and `true` if it was called, otherwise just `false`.
+    IteratorReturn,
+    /// Pops the result of an iterator's `return` method and throws a TypeError if it is not an object
+    CheckIteratorResult,
     // Nop exists solely for the sake of benchmarking the raw throughput of the VM dispatch loop
     Nop,
 }
@@ -29,6 +29,8 @@ pub struct CompileResult {
     pub externals: IndexVec<External, ExternalId>,
     pub debug_symbols: DebugSymbols,
     pub source: Rc<str>,
+    /// Whether the top level code is strict mode code
+    pub is_strict: bool,
 }
 
 /// For error purposes, this contains source code snippets used to improve errors, e.g. `x is not a function`
@@ -59,7 +59,8 @@ pub mod sym {
                 switch,
                 case,
                 get,
-                set
+                set,
+                with
             },
             // Other preinterned symbols that can be referred to statically
             Symbols {
@@ -135,6 +136,7 @@ pub mod sym {
                 URIError,
                 AggregateError,
                 Function,
+                AsyncGenerator,
                 bind,
                 call,
                 create,
@@ -177,6 +179,7 @@ pub mod sym {
                 toFixed,
                 Boolean,
                 fromCharCode,
+                raw,
                 String,
                 charAt,
                 charCodeAt,
@@ -287,26 +290,237 @@ pub mod sym {
                 freeze,
                 seal,
                 arguments,
+                callee,
                 propertyIsEnumerable,
                 apply,
-                target
+                target,
+                Proxy,
+                Reflect,
+                revocable,
+                revoke,
+                proxy,
+                construct,
+                deleteProperty,
+                ownKeys,
+                BigInt,
+                asIntN,
+                asUintN,
+                stringify,
+                toJSON,
+                getDate,
+                getDay,
+                getFullYear,
+                getHours,
+                getMilliseconds,
+                getMinutes,
+                getMonth,
+                getSeconds,
+                getTimezoneOffset,
+                getUTCDate,
+                getUTCDay,
+                getUTCFullYear,
+                getUTCHours,
+                getUTCMilliseconds,
+                getUTCMinutes,
+                getUTCMonth,
+                getUTCSeconds,
+                setDate,
+                setFullYear,
+                setHours,
+                setMilliseconds,
+                setMinutes,
+                setMonth,
+                setSeconds,
+                setTime,
+                setUTCDate,
+                setUTCFullYear,
+                setUTCHours,
+                setUTCMilliseconds,
+                setUTCMinutes,
+                setUTCMonth,
+                setUTCSeconds,
+                toISOString,
+                toUTCString,
+                toDateString,
+                toTimeString,
+                UTC,
+                all,
+                allSettled,
+                race,
+                any,
+                withResolvers,
+                status,
+                fulfilled,
+                rejected,
+                reason,
+                promise,
+                errors,
+                lastIndex,
+                global,
+                ignoreCase,
+                multiline,
+                dotAll,
+                unicode,
+                unicodeSets,
+                sticky,
+                hasIndices,
+                source,
+                flags,
+                index,
+                input,
+                indices,
+                groups,
+                reduceRight,
+                flatMap,
+                findLast,
+                findLastIndex,
+                copyWithin,
+                toSorted,
+                toReversed,
+                toSpliced,
+                fromAsync,
+                fromEntries,
+                is,
+                groupBy,
+                hasOwn,
+                preventExtensions,
+                isExtensible,
+                isFrozen,
+                isSealed,
+                toPrecision,
+                toExponential,
+                Intl,
+                NumberFormat,
+                DateTimeFormat,
+                Collator,
+                format,
+                formatToParts,
+                resolvedOptions,
+                compare,
+                supportedLocalesOf,
+                getCanonicalLocales,
+                localeCompare,
+                toLocaleString,
+                toLocaleDateString,
+                toLocaleTimeString,
+                locale,
+                localeMatcher,
+                numberingSystem,
+                calendar,
+                style,
+                currency,
+                currencyDisplay,
+                useGrouping,
+                minimumIntegerDigits,
+                minimumFractionDigits,
+                maximumFractionDigits,
+                minimumSignificantDigits,
+                maximumSignificantDigits,
+                signDisplay,
+                timeZone,
+                dateStyle,
+                timeStyle,
+                weekday,
+                year,
+                month,
+                day,
+                hour,
+                minute,
+                second,
+                fractionalSecondDigits,
+                hour12,
+                hourCycle,
+                timeZoneName,
+                usage,
+                sensitivity,
+                ignorePunctuation,
+                numeric,
+                caseFirst,
+                collation,
+                notation,
+                TypedArray,
+                Uint8ClampedArray,
+                BigInt64Array,
+                BigUint64Array,
+                Float16Array,
+                BYTES_PER_ELEMENT,
+                buffer,
+                byteOffset,
+                subarray,
+                DataView,
+                getInt8,
+                getUint8,
+                getInt16,
+                getUint16,
+                getInt32,
+                getUint32,
+                getFloat16,
+                getFloat32,
+                getFloat64,
+                getBigInt64,
+                getBigUint64,
+                setInt8,
+                setUint8,
+                setInt16,
+                setUint16,
+                setInt32,
+                setUint32,
+                setFloat16,
+                setFloat32,
+                setFloat64,
+                setBigInt64,
+                setBigUint64,
+                maxByteLength,
+                resizable,
+                detached,
+                resize,
+                transfer,
+                transferToFixedLength,
+                isView,
+                SharedArrayBuffer,
+                growable,
+                grow,
+                WeakRef,
+                deref,
+                FinalizationRegistry,
+                register,
+                unregister,
+                type_: "type"
             }
         ]
     }
 }
 
+#[derive(Clone, Debug)]
+enum StringValue {
+    /// An interned string. There is only ever one symbol for the same interned string.
+    Interned(Rc<str>),
+    /// A string that is not interned, which is a prefix of a growable buffer.
+    ///
+    /// These are created when concatenating long strings. If the string ends at the end of its buffer,
+    /// concatenating to it again can append to the buffer in place, which keeps building up a string in a loop linear.
+    Builder { buffer: u32, len: usize },
+}
+
 #[derive(Clone, Debug)]
 struct StringData {
     visited: Cell<bool>,
-    value: Rc<str>,
+    value: StringValue,
 }
 
+/// Strings shorter than this are always interned when concatenated
+const MIN_BUILDER_LEN: usize = 64;
+
 #[derive(Default, Clone, Debug)]
 pub struct StringInterner {
     store: Vec<Option<StringData>>,
     mapping: hashbrown::HashMap<Rc<str>, RawSymbol, BuildHasherDefault<FxHasher>>,
     /// List of free indices in the storage
     free: Vec<RawSymbol>,
+    /// Buffers that builder strings point into
+    buffers: Vec<Option<String>>,
+    /// List of free indices in the buffer storage
+    free_buffers: Vec<u32>,
 }
 
 fn fxhash(s: &str) -> u64 {
@@ -327,7 +541,7 @@ impl StringInterner {
             mapping.insert(s.clone(), index.0);
             store.push(Some(StringData {
                 visited: Cell::new(false),
-                value: s,
+                value: StringValue::Interned(s),
             }));
         }
 
@@ -335,11 +549,37 @@ impl StringInterner {
             store,
             mapping,
             free: Vec::new(),
+            buffers: Vec::new(),
+            free_buffers: Vec::new(),
         }
     }
 
     pub fn resolve(&self, symbol: Symbol) -> &str {
-        self.store[symbol.0 as usize].as_ref().unwrap().value.as_ref()
+        match self.store[symbol.0 as usize].as_ref().unwrap().value {
+            StringValue::Interned(ref value) => value,
+            StringValue::Builder { buffer, len } => &self.buffers[buffer as usize].as_ref().unwrap()[..len],
+        }
+    }
+
+    /// Checks if the symbol refers to an interned string, as opposed to a builder string created by [`StringInterner::concat`].
+    ///
+    /// Two interned symbols are equal if and only if their strings are equal.
+    pub fn is_interned(&self, symbol: Symbol) -> bool {
+        matches!(
+            self.store[symbol.0 as usize].as_ref().unwrap().value,
+            StringValue::Interned(_)
+        )
+    }
+
+    fn insert(&mut self, data: StringData) -> RawSymbol {
+        if let Some(id) = self.free.pop() {
+            self.store[id as usize] = Some(data);
+            id
+        } else {
+            let id = self.store.len() as RawSymbol;
+            self.store.push(Some(data));
+            id
+        }
     }
 
     // TODO: perf improvement idea: use interior mutability and allow calling with just a `&self`
@@ -351,26 +591,98 @@ impl StringInterner {
         match self.mapping.raw_entry_mut().from_hash(hash, |k| &**k == value) {
             RawEntryMut::Occupied(entry) => Symbol(*entry.get()),
             RawEntryMut::Vacant(entry) => {
-                if let Some(id) = self.free.pop() {
-                    let value: Rc<str> = Rc::from(value);
-                    self.store[id as usize] = Some(StringData {
-                        value: Rc::clone(&value),
-                        visited: Cell::new(false),
-                    });
-                    entry.insert_hashed_nocheck(hash, value, id);
-                    Symbol(id)
+                let value: Rc<str> = Rc::from(value);
+                let data = StringData {
+                    value: StringValue::Interned(Rc::clone(&value)),
+                    visited: Cell::new(false),
+                };
+
+                // Can't call `self.insert` while the entry borrows the mapping
+                let id = if let Some(id) = self.free.pop() {
+                    self.store[id as usize] = Some(data);
+                    id
                 } else {
                     let id = self.store.len() as RawSymbol;
-                    let value: Rc<str> = Rc::from(value);
-                    self.store.push(Some(StringData {
-                        value: Rc::clone(&value),
-                        visited: Cell::new(false),
-                    }));
-                    entry.insert_hashed_nocheck(hash, value, id);
-                    Symbol(id)
+                    self.store.push(Some(data));
+                    id
+                };
+                entry.insert_hashed_nocheck(hash, value, id);
+                Symbol(id)
+            }
+        }
+    }
+
+    /// Returns the interned symbol for the string of the given symbol, interning it if it is a builder string
+    pub fn intern_symbol(&mut self, symbol: Symbol) -> Symbol {
+        if self.is_interned(symbol) {
+            symbol
+        } else {
+            let value = self.resolve(symbol).to_owned();
+            self.intern(value)
+        }
+    }
+
+    /// Concatenates two strings.
+    ///
+    /// Short results are interned, while longer ones are created as builder strings that are not interned.
+    pub fn concat(&mut self, left: Symbol, right: Symbol) -> Symbol {
+        let left_len = self.resolve(left).len();
+        let len = left_len + self.resolve(right).len();
+
+        if len < MIN_BUILDER_LEN {
+            let value = [self.resolve(left), self.resolve(right)].concat();
+            return self.intern(value);
+        }
+
+        // Reuse the buffer of the left string if nothing has been appended to it yet, otherwise start a new one
+        let buffer = match self.store[left.0 as usize].as_ref().unwrap().value {
+            StringValue::Builder { buffer, len } if self.buffers[buffer as usize].as_ref().unwrap().len() == len => {
+                buffer
+            }
+            _ => {
+                let mut value = String::with_capacity(len * 2);
+                value.push_str(self.resolve(left));
+                if let Some(id) = self.free_buffers.pop() {
+                    self.buffers[id as usize] = Some(value);
+                    id
+                } else {
+                    self.buffers.push(Some(value));
+                    (self.buffers.len() - 1) as u32
+                }
+            }
+        };
+
+        match self.store[right.0 as usize].as_ref().unwrap().value {
+            StringValue::Interned(ref value) => {
+                let value = Rc::clone(value);
+                self.buffers[buffer as usize].as_mut().unwrap().push_str(&value);
+            }
+            StringValue::Builder {
+                buffer: right_buffer,
+                len: right_len,
+            } => {
+                if right_buffer == buffer {
+                    self.buffers[buffer as usize]
+                        .as_mut()
+                        .unwrap()
+                        .extend_from_within(..right_len);
+                } else {
+                    let [target, source] = self
+                        .buffers
+                        .get_disjoint_mut([buffer as usize, right_buffer as usize])
+                        .unwrap();
+                    target
+                        .as_mut()
+                        .unwrap()
+                        .push_str(&source.as_ref().unwrap()[..right_len]);
                 }
             }
         }
+
+        Symbol(self.insert(StringData {
+            visited: Cell::new(false),
+            value: StringValue::Builder { buffer, len },
+        }))
     }
 
     pub fn intern_usize(&mut self, mut val: usize) -> Symbol {
@@ -421,18 +733,31 @@ impl StringInterner {
     /// You must mark all reachable symbols before calling this.
     /// It won't cause undefined behavior if you don't (hence not unsafe), but it can lead to oddities such as panics.
     pub fn sweep(&mut self) {
+        let mut live_buffers = vec![false; self.buffers.len()];
+
         // Preinterned symbols are always kept, since they can be referred to statically.
         for i in sym::PREINTERNED.len()..self.store.len() {
             if let Some(data) = self.store[i].as_ref() {
                 if !data.visited.get() {
-                    self.mapping.remove(&data.value);
+                    if let StringValue::Interned(value) = &data.value {
+                        self.mapping.remove(value);
+                    }
                     self.store[i] = None;
                     self.free.push(i as RawSymbol);
                 } else {
+                    if let StringValue::Builder { buffer, .. } = data.value {
+                        live_buffers[buffer as usize] = true;
+                    }
                     data.visited.set(false);
                 }
             }
         }
+
+        for (id, live) in live_buffers.into_iter().enumerate() {
+            if !live && self.buffers[id].take().is_some() {
+                self.free_buffers.push(id as u32);
+            }
+        }
     }
 }
 
@@ -533,4 +858,32 @@ mod tests {
         let k4 = interner.intern_char('ä');
         assert_eq!(interner.resolve(k4), "ä");
     }
+
+    #[test]
+    fn concat() {
+        let interner = &mut StringInterner::new();
+        let short = interner.intern("a");
+        let long = interner.intern("b".repeat(100));
+
+        let s1 = interner.concat(short, short);
+        assert!(interner.is_interned(s1));
+        assert_eq!(s1, interner.intern("aa"));
+
+        let s2 = interner.concat(long, short);
+        let s3 = interner.concat(s2, short);
+        // Appending to `s2` again after `s3` already extended its buffer must not affect `s3`
+        let s4 = interner.concat(s2, long);
+        assert!(!interner.is_interned(s2));
+        assert_eq!(interner.resolve(s2), "b".repeat(100) + "a");
+        assert_eq!(interner.resolve(s3), "b".repeat(100) + "aa");
+        assert_eq!(interner.resolve(s4), "b".repeat(100) + "a" + &"b".repeat(100));
+
+        let s5 = interner.concat(s3, s3);
+        assert_eq!(interner.resolve(s5), ("b".repeat(100) + "aa").repeat(2));
+        assert_eq!(interner.intern_symbol(s3), interner.intern("b".repeat(100) + "aa"));
+
+        interner.mark(s5);
+        interner.sweep();
+        assert_eq!(interner.resolve(s5), ("b".repeat(100) + "aa").repeat(2));
+    }
 }
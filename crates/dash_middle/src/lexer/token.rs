@@ -4,6 +4,17 @@ use crate::interner::{Symbol, sym};
 use crate::sourcemap::Span;
 use derive_more::Display;
 
+/// A segment of a template literal, i.e. the string parts between substitutions
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TemplateSegment {
+    /// The string with escape sequences processed.
+    ///
+    /// This is `None` if the segment contains an invalid escape sequence, which is only allowed in tagged templates
+    pub cooked: Option<Symbol>,
+    /// The string as it appears in the source code
+    pub raw: Symbol,
+}
+
 /// The type of a token
 ///
 /// These are generated by the lexer, and used by the Parser to
@@ -196,7 +207,7 @@ pub enum TokenType {
 
     /// Template literal segment: `foo`
     #[display("<template literal>")]
-    TemplateLiteral(Symbol),
+    TemplateLiteral(TemplateSegment),
 
     /// Number: 42
     NumberDec(Symbol),
@@ -214,6 +225,12 @@ pub enum TokenType {
     #[display("0o")]
     NumberOct(Symbol),
 
+    /// BigInt literal: 42n
+    ///
+    /// The symbol holds the literal without the `n` suffix, including a radix prefix if present
+    #[display("<bigint literal>")]
+    NumberBigInt(Symbol),
+
     #[display("if")]
     If,
 
@@ -292,6 +309,9 @@ pub enum TokenType {
     #[display("while")]
     While,
 
+    #[display("with")]
+    With,
+
     #[display("do")]
     Do,
 
@@ -430,6 +450,7 @@ impl TokenType {
             Self::For => Some(sym::for_),
             Self::Do => Some(sym::do_),
             Self::While => Some(sym::while_),
+            Self::With => Some(sym::with),
             Self::In => Some(sym::in_),
             Self::Instanceof => Some(sym::instanceof),
             Self::Async => Some(sym::async_),
@@ -476,7 +497,10 @@ impl TokenType {
 
     /// Returns a "dummy" template literal.
     /// Should only be used in `ErrorKind`s.
-    pub const DUMMY_TEMPLATE_LITERAL: Self = Self::TemplateLiteral(sym::empty);
+    pub const DUMMY_TEMPLATE_LITERAL: Self = Self::TemplateLiteral(TemplateSegment {
+        cooked: Some(sym::empty),
+        raw: sym::empty,
+    });
 
     pub fn fmt_for_expected_tys(&self) -> impl fmt::Display + '_ {
         struct DisplayExpectedTys<'a>(&'a TokenType);
@@ -516,6 +540,7 @@ pub fn as_token(s: Symbol) -> TokenType {
         sym::for_ => TokenType::For,
         sym::do_ => TokenType::Do,
         sym::while_ => TokenType::While,
+        sym::with => TokenType::With,
         sym::in_ => TokenType::In,
         sym::instanceof => TokenType::Instanceof,
         sym::async_ => TokenType::Async,
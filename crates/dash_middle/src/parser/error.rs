@@ -74,6 +74,15 @@ pub enum Error {
     ArgumentsInRoot(Span),
     Unexpected(Span, &'static str),
     NewTargetOutsideFunction(Span),
+    DuplicateProto(Span),
+    SuperPropertyOutsideMethod(Span),
+    /* Strict mode early errors */
+    DuplicateParameter(Span),
+    WithInStrictMode(Span),
+    OctalLiteralInStrictMode(Span),
+    OctalEscapeInStrictMode(Span),
+    EvalOrArgumentsAssignment(Span),
+    UseStrictWithNonSimpleParameters(Span),
 }
 
 impl Error {
@@ -402,6 +411,40 @@ impl fmt::Display for FormattableError<'_, '_> {
                 diag.message(format!("unexpected {descr}"));
                 diag.span_error(span, "");
             }
+            Error::DuplicateProto(span) => {
+                diag.message("duplicate `__proto__` fields are not allowed in object literals");
+                diag.span_error(span, "");
+            }
+            Error::SuperPropertyOutsideMethod(span) => {
+                diag.message("`super` properties can only be accessed in methods");
+                diag.span_error(span, "");
+            }
+            Error::DuplicateParameter(span) => {
+                diag.message("duplicate parameter names are not allowed in strict mode");
+                diag.span_error(span, "parameter list defined here");
+            }
+            Error::WithInStrictMode(span) => {
+                diag.message("`with` statements are not allowed in strict mode");
+                diag.span_error(span, "");
+            }
+            Error::OctalLiteralInStrictMode(span) => {
+                diag.message("octal literals are not allowed in strict mode");
+                diag.span_error(span, "");
+                diag.help("consider using the `0o` prefix instead");
+            }
+            Error::OctalEscapeInStrictMode(span) => {
+                diag.message("octal escape sequences are not allowed in strict mode");
+                diag.span_error(span, "");
+                diag.help("consider using a `\\x` or `\\u` escape sequence instead");
+            }
+            Error::EvalOrArgumentsAssignment(span) => {
+                diag.message("cannot assign to `eval` or `arguments` in strict mode");
+                diag.span_error(span, "");
+            }
+            Error::UseStrictWithNonSimpleParameters(span) => {
+                diag.message("\"use strict\" is not allowed in functions with non-simple parameters");
+                diag.span_error(span, "parameter list defined here");
+            }
         }
         fmt::Display::fmt(&diag, f)
     }
@@ -4,7 +4,7 @@ use derive_more::Display;
 
 use crate::compiler::scope::BackLocalId;
 use crate::interner::{Symbol, sym};
-use crate::lexer::token::TokenType;
+use crate::lexer::token::{TemplateSegment, TokenType};
 use crate::sourcemap::Span;
 
 use super::statement::{Class, FunctionDeclaration, fmt_list};
@@ -33,6 +33,8 @@ pub enum ExprKind {
     Assignment(AssignmentExpr),
     /// A function call expression
     Call(FunctionCall),
+    /// A tagged template expression, i.e. ``foo`bar${baz}` ``
+    TaggedTemplate(TaggedTemplate),
     /// new.target
     NewTarget,
     /// A conditional expression, i.e. `foo ? bar : baz`
@@ -175,6 +177,11 @@ impl ExprKind {
         Self::Literal(LiteralExpr::Number(n))
     }
 
+    /// Creates a BigInt literal expression
+    pub fn bigint_literal(s: Symbol) -> Self {
+        Self::Literal(LiteralExpr::BigInt(s))
+    }
+
     /// Creates a string literal expression
     pub fn string_literal(s: Symbol) -> Self {
         Self::Literal(LiteralExpr::String(s))
@@ -456,6 +463,30 @@ impl fmt::Display for FunctionCall {
     }
 }
 
+/// A tagged template expression
+#[derive(Debug, Clone)]
+pub struct TaggedTemplate {
+    /// The function that is called with the template strings and substitutions
+    pub tag: Box<Expr>,
+    /// The string segments of the template literal. There is always one more segment than there are substitutions
+    pub segments: Vec<TemplateSegment>,
+    /// The expressions in `${}`
+    pub substitutions: Vec<Expr>,
+}
+
+impl fmt::Display for TaggedTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}`", self.tag)?;
+        for (index, segment) in self.segments.iter().enumerate() {
+            write!(f, "{}", segment.raw)?;
+            if let Some(substitution) = self.substitutions.get(index) {
+                write!(f, "${{{substitution}}}")?;
+            }
+        }
+        f.write_str("`")
+    }
+}
+
 /// The target of an assignment
 #[derive(Debug, Clone, Display)]
 pub enum AssignmentTarget {
@@ -558,6 +589,9 @@ pub enum LiteralExpr {
     Identifier(Symbol),
     /// Number literal
     Number(f64),
+    /// BigInt literal, stored as it appears in the source (without the `n` suffix)
+    #[display("{_0}n")]
+    BigInt(Symbol),
     /// String literal, borrowed from input string
     #[display("\"{_0}\"")]
     String(Symbol),
@@ -581,6 +615,7 @@ impl LiteralExpr {
             Self::Boolean(b) => Some(*b),
             Self::Identifier(_) => None,
             Self::Number(n) => Some(*n != 0.0),
+            Self::BigInt(_) => None,
             Self::String(s) => Some(*s != sym::empty),
             Self::Null => Some(false),
             Self::Regex(..) => Some(true),
@@ -304,6 +304,8 @@ pub struct ForOfLoop {
     /// The body of this loop
     pub body: Box<Statement>,
     pub scope: ScopeId,
+    /// Whether this is a `for await..of` loop
+    pub is_await: bool,
 }
 
 /// A for loop
@@ -403,7 +405,7 @@ pub enum FunctionKind {
     /// A normal function
     Function(Asyncness),
     /// A generator function
-    Generator,
+    Generator(Asyncness),
     /// An arrow function
     Arrow,
 }
@@ -481,6 +483,9 @@ pub struct FunctionDeclaration {
     /// The type of function
     pub ty: FunctionKind,
     pub ty_segment: Option<TypeSegment>,
+    /// Whether this function is strict mode code, either because it is nested in strict mode code,
+    /// is part of a class or has a `"use strict"` directive
+    pub is_strict: bool,
 }
 
 impl fmt::Display for FunctionDeclaration {
@@ -1,7 +1,7 @@
 use crate::interner::Symbol;
 use crate::parser::expr::{
     ArrayLiteral, AssignmentExpr, BinaryExpr, ConditionalExpr, Expr, ExprKind, FunctionCall, GroupingExpr, LiteralExpr,
-    ObjectLiteral, OptionalChainingExpression, Postfix, Prefix, PropertyAccessExpr, Seq, UnaryExpr,
+    ObjectLiteral, OptionalChainingExpression, Postfix, Prefix, PropertyAccessExpr, Seq, TaggedTemplate, UnaryExpr,
 };
 use crate::parser::statement::{
     BlockStatement, Class, DoWhileLoop, ExportKind, ForInLoop, ForLoop, ForOfLoop, FunctionDeclaration, IfStatement,
@@ -65,6 +65,9 @@ pub trait Visitor<V> {
     /// Visits a function call
     fn visit_function_call(&mut self, span: Span, c: FunctionCall) -> V;
 
+    /// Visits a tagged template
+    fn visit_tagged_template(&mut self, span: Span, t: TaggedTemplate) -> V;
+
     /// Visits a return statement
     fn visit_return_statement(&mut self, span: Span, s: ReturnStatement) -> V;
 
@@ -188,6 +191,7 @@ where
         ExprKind::Literal(l) => this.visit_literal_expression(span, l),
         ExprKind::Unary(e) => this.visit_unary_expression(span, e),
         ExprKind::Call(e) => this.visit_function_call(span, e),
+        ExprKind::TaggedTemplate(e) => this.visit_tagged_template(span, e),
         ExprKind::Conditional(e) => this.visit_conditional_expr(span, e),
         ExprKind::PropertyAccess(e) => this.visit_property_access_expr(span, e, false),
         ExprKind::Sequence(e) => this.visit_sequence_expr(span, e),
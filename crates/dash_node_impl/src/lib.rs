@@ -339,9 +339,12 @@ impl Object for RequireFunction {
         object,
         get_own_property_descriptor,
         set_property,
+        define_property,
         delete_property,
         set_prototype,
         get_prototype,
+        prevent_extensions,
+        is_extensible,
         own_keys
     );
 
@@ -391,7 +394,7 @@ impl Object for RequireFunction {
             if canonicalized_path.extension() == Some(OsStr::new("json")) {
                 match dash_vm::json::parser::Parser::new(source.as_bytes(), scope).parse() {
                     Ok(val) => Ok(val.into()),
-                    Err(err) => throw!(scope, SyntaxError, "{}", err.to_string()),
+                    Err(err) => throw!(scope, SyntaxError, "{}", err.describe(source.as_bytes())),
                 }
             } else {
                 let module = match execute_node_module(
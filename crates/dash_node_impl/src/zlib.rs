@@ -78,9 +78,12 @@ impl Object for Inflate {
         object,
         get_own_property_descriptor,
         set_property,
+        define_property,
         delete_property,
         set_prototype,
         get_prototype,
+        prevent_extensions,
+        is_extensible,
         apply,
         own_keys
     );
@@ -5,7 +5,7 @@ use dash_middle::lexer::token::TokenType;
 use dash_middle::parser::expr::{
     ArrayLiteral, ArrayMemberKind, AssignmentExpr, AssignmentTarget, BinaryExpr, CallArgumentKind, ConditionalExpr,
     Expr, ExprKind, FunctionCall, GroupingExpr, LiteralExpr, ObjectLiteral, ObjectMemberKind,
-    OptionalChainingExpression, PropertyAccessExpr, UnaryExpr,
+    OptionalChainingExpression, PropertyAccessExpr, TaggedTemplate, UnaryExpr,
 };
 use dash_middle::parser::statement::{
     BlockStatement, Class, ClassMemberValue, DoWhileLoop, ExportKind, ForInLoop, ForLoop, ForOfLoop,
@@ -173,6 +173,7 @@ impl<'b, 'interner> ConstFunctionEvalCtx<'b, 'interner> {
                 body,
                 binding,
                 scope,
+                is_await: _,
             }) => {
                 self.with_scope(*scope, |this| {
                     this.visit_variable_binding(binding, None);
@@ -242,6 +243,10 @@ impl<'b, 'interner> ConstFunctionEvalCtx<'b, 'interner> {
             ExprKind::Unary(..) => self.visit_unary_expression(expression),
             ExprKind::Assignment(..) => self.visit_assignment_expression(expression),
             ExprKind::Call(..) => self.visit_call_expression(expression),
+            ExprKind::TaggedTemplate(TaggedTemplate { tag, substitutions, .. }) => {
+                self.visit(tag);
+                substitutions.iter_mut().for_each(|e| self.visit(e));
+            }
             ExprKind::Conditional(..) => self.visit_conditional_expression(expression),
             ExprKind::PropertyAccess(..) => self.visit_property_access_expression(expression),
             ExprKind::Sequence(..) => self.visit_seq_expression(expression),
@@ -530,7 +535,7 @@ fn expr_has_side_effects(expr: &Expr) -> bool {
             parenthesized: _,
         }) => array.iter().any(|k| match k {
             ArrayMemberKind::Item(e) => expr_has_side_effects(e),
-            ArrayMemberKind::Spread(e) => expr_has_side_effects(e),
+            ArrayMemberKind::Spread(_) => true, // invokes @@iterator
             ArrayMemberKind::Empty => false,
         }),
         ExprKind::Binary(BinaryExpr { left, right, .. }) => expr_has_side_effects(left) || expr_has_side_effects(right),
@@ -547,12 +552,17 @@ fn expr_has_side_effects(expr: &Expr) -> bool {
         ExprKind::Literal(LiteralExpr::This) => false,
         ExprKind::Literal(LiteralExpr::Null) => false,
         ExprKind::Literal(LiteralExpr::Number(..)) => false,
+        ExprKind::Literal(LiteralExpr::BigInt(..)) => true, // operators throw when mixing BigInts with other types
         ExprKind::Literal(LiteralExpr::Regex(..)) => false,
         ExprKind::Literal(LiteralExpr::String(..)) => false,
         ExprKind::Object(ObjectLiteral {
             members,
             parenthesized: _,
         }) => members.iter().any(|(kind, expr)| {
+            // spreading invokes getters on the source object
+            if let ObjectMemberKind::Spread = kind {
+                return true;
+            }
             if let ObjectMemberKind::Dynamic(dynamic) = kind
                 && expr_has_side_effects(dynamic)
             {
@@ -10,7 +10,7 @@ use dash_middle::lexer::token::TokenType;
 use dash_middle::parser::expr::{
     ArrayLiteral, ArrayMemberKind, AssignmentExpr, AssignmentTarget, BinaryExpr, CallArgumentKind, ConditionalExpr,
     Expr, ExprKind, FunctionCall, GroupingExpr, LiteralExpr, ObjectLiteral, ObjectMemberKind,
-    OptionalChainingComponent, OptionalChainingExpression, PropertyAccessExpr, UnaryExpr,
+    OptionalChainingComponent, OptionalChainingExpression, PropertyAccessExpr, TaggedTemplate, UnaryExpr,
 };
 use dash_middle::parser::statement::{
     Binding, BlockStatement, Class, ClassMemberKey, ClassMemberValue, DoWhileLoop, ExportKind, ForInLoop, ForLoop,
@@ -298,6 +298,7 @@ impl<'s> TypeInferCtx<'s> {
                 body,
                 binding,
                 scope,
+                is_await: _,
             }) => {
                 self.with_block_scope(*scope, |this| {
                     this.visit_variable_binding(binding, None);
@@ -398,6 +399,7 @@ impl<'s> TypeInferCtx<'s> {
             ExprKind::Unary(expr) => self.visit_unary_expression(expr),
             ExprKind::Assignment(expr) => self.visit_assignment_expression(expr),
             ExprKind::Call(expr) => self.visit_call_expression(expr),
+            ExprKind::TaggedTemplate(expr) => self.visit_tagged_template(expr),
             ExprKind::Conditional(expr) => self.visit_conditional_expression(expr),
             ExprKind::PropertyAccess(expr) => self.visit_property_access_expression(expr),
             ExprKind::Sequence(..) => panic!("Unemitted expr type: Sequence"),
@@ -458,8 +460,8 @@ impl<'s> TypeInferCtx<'s> {
             (_, _, TokenType::Inequality) => Some(CompileValueType::Boolean),
             (_, _, TokenType::StrictEquality) => Some(CompileValueType::Boolean),
             (_, _, TokenType::StrictInequality) => Some(CompileValueType::Boolean),
+            // NB: arithmetic operators can also produce BigInts, so we can only infer a number if both sides are numbers
             (Some(CompileValueType::Number), Some(CompileValueType::Number), _) => Some(CompileValueType::Number),
-            (_, _, TokenType::Minus | TokenType::Star | TokenType::Slash) => Some(CompileValueType::Number),
             _ => None,
         }
     }
@@ -507,6 +509,7 @@ impl<'s> TypeInferCtx<'s> {
                 _ => None,
             },
             LiteralExpr::Number(..) => Some(CompileValueType::Number),
+            LiteralExpr::BigInt(..) => None,
             LiteralExpr::String(..) => Some(CompileValueType::String),
             LiteralExpr::Regex(..) => None,
             LiteralExpr::Null => Some(CompileValueType::Null),
@@ -515,9 +518,10 @@ impl<'s> TypeInferCtx<'s> {
     }
 
     pub fn visit_unary_expression(&mut self, UnaryExpr { expr, operator }: &UnaryExpr) -> Option<CompileValueType> {
-        self.visit(expr);
+        let ty = self.visit(expr);
         match operator {
-            TokenType::Plus | TokenType::Minus => Some(CompileValueType::Number),
+            TokenType::Plus => Some(CompileValueType::Number),
+            TokenType::Minus => ty.filter(|ty| matches!(ty, CompileValueType::Number)),
             TokenType::Typeof => Some(CompileValueType::String),
             _ => None,
         }
@@ -590,6 +594,17 @@ impl<'s> TypeInferCtx<'s> {
         None
     }
 
+    pub fn visit_tagged_template(
+        &mut self,
+        TaggedTemplate { tag, substitutions, .. }: &TaggedTemplate,
+    ) -> Option<CompileValueType> {
+        self.visit(tag);
+        for substitution in substitutions {
+            self.visit(substitution);
+        }
+        None
+    }
+
     pub fn visit_call_arguments(&mut self, arguments: &[CallArgumentKind]) {
         for argument in arguments {
             match argument {
@@ -626,13 +641,13 @@ impl<'s> TypeInferCtx<'s> {
     }
 
     pub fn visit_prefix_expression(&mut self, expression: &Expr, _: TokenType) -> Option<CompileValueType> {
-        self.visit(expression);
-        Some(CompileValueType::Number)
+        // Increments and decrements preserve the numeric type (number or BigInt) of the operand
+        self.visit(expression).filter(|ty| matches!(ty, CompileValueType::Number))
     }
 
     pub fn visit_postfix_expression(&mut self, expression: &Expr, _: TokenType) -> Option<CompileValueType> {
-        self.visit(expression);
-        Some(CompileValueType::Number)
+        // Increments and decrements preserve the numeric type (number or BigInt) of the operand
+        self.visit(expression).filter(|ty| matches!(ty, CompileValueType::Number))
     }
 
     pub fn visit_function_expression(
@@ -1,9 +1,10 @@
 use dash_middle::interner::{Symbol, sym};
-use dash_middle::lexer::token::{ASSIGNMENT_TYPES, Token, TokenType};
+use dash_middle::lexer::token::{ASSIGNMENT_TYPES, TemplateSegment, Token, TokenType};
 use dash_middle::parser::error::Error;
 use dash_middle::parser::expr::{
     ArrayLiteral, ArrayMemberKind, AssignmentExpr, AssignmentTarget, CallArgumentKind, Expr, ExprKind, LiteralExpr,
     ObjectLiteral, ObjectMemberKind, OptionalChainingComponent, OptionalChainingExpression, PropertyAccessExpr,
+    TaggedTemplate,
 };
 use dash_middle::parser::statement::{
     Asyncness, BlockStatement, FunctionDeclaration, FunctionKind, Parameter, Pattern, ReturnStatement, Statement,
@@ -13,6 +14,9 @@ use dash_middle::sourcemap::Span;
 
 use crate::{Parser, any};
 
+/// The segments of a template literal along with their spans, and the substitutions between them
+type TemplateParts = (Vec<(Span, TemplateSegment)>, Vec<Expr>);
+
 impl Parser<'_, '_> {
     pub fn parse_expression(&mut self) -> Option<Expr> {
         self.parse_sequence()
@@ -62,6 +66,7 @@ impl Parser<'_, '_> {
 
         if self.eat(any(ASSIGNMENT_TYPES), false).is_some() {
             let operator = self.previous()?.ty;
+            self.check_assignment_target(&expr)?;
             let rval = self.parse_yield()?;
             expr = Expr::assignment(expr, rval, operator);
         }
@@ -69,6 +74,17 @@ impl Parser<'_, '_> {
         Some(expr)
     }
 
+    /// Reports an error for assignments to `eval` or `arguments` in strict mode code
+    fn check_assignment_target(&mut self, target: &Expr) -> Option<()> {
+        if self.strict
+            && let Some(span) = eval_or_arguments_target(target)
+        {
+            self.error(Error::EvalOrArgumentsAssignment(span));
+            return None;
+        }
+        Some(())
+    }
+
     fn parse_ternary(&mut self) -> Option<Expr> {
         let mut expr = self.parse_nullish_coalescing()?;
 
@@ -287,6 +303,7 @@ impl Parser<'_, '_> {
             let span = span.to(rval.span);
 
             if [TokenType::Increment, TokenType::Decrement].contains(&ty) {
+                self.check_assignment_target(&rval)?;
                 Some(Expr {
                     span,
                     kind: ExprKind::prefix(ty, rval),
@@ -309,6 +326,7 @@ impl Parser<'_, '_> {
             .is_some()
         {
             let Token { span, ty } = *self.previous()?;
+            self.check_assignment_target(&expr)?;
             return Some(Expr {
                 span: expr.span.to(span),
                 kind: ExprKind::postfix(ty, expr),
@@ -371,7 +389,24 @@ impl Parser<'_, '_> {
             ],
         };
 
-        while self.eat(any(matcher), false).is_some() {
+        loop {
+            if let Some(head) = self.expect_template_literal(false) {
+                let (segments, substitutions) = self.parse_template_parts(self.previous()?.span, head)?;
+                expr = Expr {
+                    span: expr.span.to(self.previous()?.span),
+                    kind: ExprKind::TaggedTemplate(TaggedTemplate {
+                        tag: Box::new(expr),
+                        segments: segments.into_iter().map(|(_, segment)| segment).collect(),
+                        substitutions,
+                    }),
+                };
+                continue;
+            }
+
+            if self.eat(any(matcher), false).is_none() {
+                break;
+            }
+
             let previous = self.previous()?.ty;
 
             match previous {
@@ -455,6 +490,38 @@ impl Parser<'_, '_> {
         Some(expr)
     }
 
+    /// Parses the rest of a template literal after its first segment has been consumed,
+    /// returning all segments along with the substitutions between them
+    fn parse_template_parts(&mut self, span: Span, head: TemplateSegment) -> Option<TemplateParts> {
+        let mut segments = vec![(span, head)];
+        let mut substitutions = Vec::new();
+
+        while self.eat(TokenType::Dollar, false).is_some() {
+            self.eat(TokenType::LeftBrace, true)?;
+            substitutions.push(self.parse_expression()?);
+            self.eat(TokenType::RightBrace, true)?;
+
+            let segment = self.expect_template_literal(true)?;
+            segments.push((self.previous()?.span, segment));
+        }
+
+        Some((segments, substitutions))
+    }
+
+    /// Returns the string literal of a template segment in an untagged template
+    fn cooked_template_segment(&mut self, span: Span, segment: TemplateSegment) -> Option<Expr> {
+        match segment.cooked {
+            Some(sym) => Some(Expr {
+                span,
+                kind: ExprKind::string_literal(sym),
+            }),
+            None => {
+                self.error(Error::InvalidEscapeSequence(span));
+                None
+            }
+        }
+    }
+
     /// Parses a call expression after the callee and the `(` has been consumed
     fn parse_call_parentheses(&mut self, is_optional: bool, is_constructor: bool, mut callee: Expr) -> Option<Expr> {
         let mut arguments = Vec::new();
@@ -527,26 +594,16 @@ impl Parser<'_, '_> {
 
         let expr = match current.ty {
             // removed to resolve #58
-            TokenType::TemplateLiteral(sym) => {
-                let mut left = Expr {
-                    span: current.span,
-                    kind: ExprKind::string_literal(sym),
-                };
-                while !self.is_eof() {
-                    if self.eat(TokenType::Dollar, false).is_some() {
-                        self.eat(TokenType::LeftBrace, true)?;
-                        let right = self.parse_expression()?;
-                        self.eat(TokenType::RightBrace, true)?;
-                        left = Expr::binary(left, right, TokenType::Plus);
-                    } else if let Some(sym) = self.expect_template_literal(false) {
-                        let right = Expr {
-                            span: self.previous()?.span,
-                            kind: ExprKind::string_literal(sym),
-                        };
-                        left = Expr::binary(left, right, TokenType::Plus);
-                    } else {
-                        break;
-                    }
+            TokenType::TemplateLiteral(head) => {
+                let (segments, substitutions) = self.parse_template_parts(current.span, head)?;
+                let mut segments = segments.into_iter();
+
+                let (span, head) = segments.next()?;
+                let mut left = self.cooked_template_segment(span, head)?;
+                for (substitution, (span, segment)) in substitutions.into_iter().zip(segments) {
+                    left = Expr::binary(left, substitution, TokenType::Plus);
+                    let right = self.cooked_template_segment(span, segment)?;
+                    left = Expr::binary(left, right, TokenType::Plus);
                 }
                 left
             }
@@ -566,10 +623,17 @@ impl Parser<'_, '_> {
                 span: current.span,
                 kind: ExprKind::this_literal(),
             },
-            TokenType::String(sym) => Expr {
-                span: current.span,
-                kind: ExprKind::string_literal(sym),
-            },
+            TokenType::String(sym) => {
+                if self.strict && has_legacy_octal_escape(self.source.resolve(current.span)) {
+                    self.error(Error::OctalEscapeInStrictMode(current.span));
+                    return None;
+                }
+
+                Expr {
+                    span: current.span,
+                    kind: ExprKind::string_literal(sym),
+                }
+            }
             TokenType::LeftSquareBrace => {
                 let mut items = Vec::new();
                 while self.eat(TokenType::RightSquareBrace, false).is_none() {
@@ -600,6 +664,31 @@ impl Parser<'_, '_> {
                         break;
                     }
 
+                    // `async` is only a method modifier if it isn't used as the property name itself,
+                    // as in `{ async: 1 }` or `{ async() {} }`
+                    let asyncness = match self.eat(TokenType::Async, false) {
+                        Some(_)
+                            if self.current().is_some_and(|tok| {
+                                !matches!(
+                                    tok.ty,
+                                    TokenType::Colon
+                                        | TokenType::LeftParen
+                                        | TokenType::Comma
+                                        | TokenType::RightBrace
+                                        | TokenType::Assignment
+                                )
+                            }) =>
+                        {
+                            Asyncness::Yes
+                        }
+                        Some(_) => {
+                            self.advance_back();
+                            Asyncness::No
+                        }
+                        None => Asyncness::No,
+                    };
+                    let is_generator = self.eat(TokenType::Star, false).is_some();
+
                     let token = *self.next()?;
                     let key = match token.ty {
                         TokenType::Get => {
@@ -662,13 +751,21 @@ impl Parser<'_, '_> {
                                 items.push((key, value));
                             } else if self.eat(TokenType::LeftParen, false).is_some() {
                                 // Method.
-                                let parameters = self.parse_parameter_list()?;
-                                self.eat(TokenType::LeftBrace, true)?;
-                                let body = self.parse_block()?;
+                                let lparen = self.previous()?.span;
+                                let (parameters, (body, is_strict)) =
+                                    self.with_super_property_allowed(true, |this| {
+                                        let parameters = this.parse_parameter_list()?;
+                                        let parameters_span = lparen.to(this.previous()?.span);
+                                        let body = this.parse_function_body(&parameters, parameters_span, |this| {
+                                            this.eat(TokenType::LeftBrace, true)?;
+                                            this.parse_block()
+                                        })?;
+                                        Some((parameters, body))
+                                    })?;
                                 let body_scope = self.scope_count.inc();
                                 let parameters_scope = self.scope_count.inc();
                                 items.push((
-                                    key,
+                                    own_property_key(key, token.span),
                                     Expr {
                                         span: current.span.to(self.previous()?.span),
                                         kind: ExprKind::function(FunctionDeclaration {
@@ -677,15 +774,19 @@ impl Parser<'_, '_> {
                                             name: None,
                                             parameters,
                                             statements: body.0,
-                                            ty: FunctionKind::Function(Asyncness::No),
+                                            ty: match is_generator {
+                                                true => FunctionKind::Generator(asyncness),
+                                                false => FunctionKind::Function(asyncness),
+                                            },
                                             ty_segment: None,
+                                            is_strict,
                                         }),
                                     },
                                 ));
                             } else {
                                 match key {
                                     ObjectMemberKind::Static(name) => items.push((
-                                        key,
+                                        own_property_key(key, token.span),
                                         Expr {
                                             span: token.span,
                                             kind: ExprKind::identifier(name),
@@ -701,7 +802,9 @@ impl Parser<'_, '_> {
                         }
                         ObjectMemberKind::Getter(..) | ObjectMemberKind::Setter(..) => {
                             self.eat(TokenType::LeftParen, true)?;
-                            let params = self.parse_parameter_list()?;
+                            let lparen = self.previous()?.span;
+                            let params = self.with_super_property_allowed(true, Self::parse_parameter_list)?;
+                            let parameters_span = lparen.to(self.previous()?.span);
 
                             // Make sure parameter count is correct
                             match key {
@@ -728,8 +831,13 @@ impl Parser<'_, '_> {
                                 _ => unreachable!(),
                             }
 
-                            self.eat(TokenType::LeftBrace, true)?;
-                            let BlockStatement(stmts, scope_id) = self.parse_block()?;
+                            let (BlockStatement(stmts, scope_id), is_strict) =
+                                self.with_super_property_allowed(true, |this| {
+                                    this.parse_function_body(&params, parameters_span, |this| {
+                                        this.eat(TokenType::LeftBrace, true)?;
+                                        this.parse_block()
+                                    })
+                                })?;
                             let parameters_scope = self.scope_count.inc();
 
                             let fun = FunctionDeclaration {
@@ -740,6 +848,7 @@ impl Parser<'_, '_> {
                                 statements: stmts,
                                 ty: FunctionKind::Function(Asyncness::No),
                                 ty_segment: None,
+                                is_strict,
                             };
                             items.push((
                                 key,
@@ -763,13 +872,31 @@ impl Parser<'_, '_> {
                 }
             }
             // TODO: this unwrap is not safe
-            TokenType::NumberDec(sym) => Expr {
-                span: current.span,
-                kind: ExprKind::number_literal(self.interner.resolve(sym).parse::<f64>().unwrap()),
-            },
+            TokenType::NumberDec(sym) => {
+                let src = self.interner.resolve(sym);
+                let is_legacy_octal = src.len() > 1 && src.starts_with('0') && src.bytes().all(|b| b.is_ascii_digit());
+                if is_legacy_octal && self.strict {
+                    self.error(Error::OctalLiteralInStrictMode(current.span));
+                    return None;
+                }
+
+                let value = match is_legacy_octal.then(|| u64::from_str_radix(src, 8)) {
+                    Some(Ok(value)) => value as f64,
+                    // Literals like `09` that contain non-octal digits are decimal
+                    Some(Err(_)) | None => src.parse::<f64>().unwrap(),
+                };
+                Expr {
+                    span: current.span,
+                    kind: ExprKind::number_literal(value),
+                }
+            }
             TokenType::NumberHex(sym) => self.parse_prefixed_number_literal(current.span, sym, 16)?,
             TokenType::NumberBin(sym) => self.parse_prefixed_number_literal(current.span, sym, 2)?,
             TokenType::NumberOct(sym) => self.parse_prefixed_number_literal(current.span, sym, 8)?,
+            TokenType::NumberBigInt(sym) => Expr {
+                span: current.span,
+                kind: ExprKind::bigint_literal(sym),
+            },
             TokenType::LeftParen => {
                 // Parsing groups and closures
                 if self.eat(TokenType::RightParen, false).is_some() {
@@ -837,18 +964,21 @@ impl Parser<'_, '_> {
                         kind: ExprKind::function(f),
                     })?
                 } else if self.eat(TokenType::LeftParen, true).is_some() {
+                    let lparen = self.previous()?.span;
                     let params = self.parse_parameter_list()?;
+                    let parameters_span = lparen.to(self.previous()?.span);
                     self.eat(TokenType::FatArrow, true)?;
-                    let statement = if self.eat(TokenType::LeftBrace, false).is_some() {
-                        self.advance_back();
-                        self.parse_statement()?
-                    } else {
-                        let expr = self.parse_expression_no_comma()?;
-                        Statement {
-                            span: expr.span,
-                            kind: StatementKind::Return(ReturnStatement(expr)),
+                    let (statement, is_strict) = self.parse_function_body(&params, parameters_span, |this| {
+                        if this.matches(TokenType::LeftBrace) {
+                            this.parse_statement()
+                        } else {
+                            let expr = this.parse_expression_no_comma()?;
+                            Some(Statement {
+                                span: expr.span,
+                                kind: StatementKind::Return(ReturnStatement(expr)),
+                            })
                         }
-                    };
+                    })?;
 
                     Expr {
                         span: current.span.to(statement.span),
@@ -862,6 +992,7 @@ impl Parser<'_, '_> {
                             // as if they're simply async functions
                             ty: FunctionKind::Function(Asyncness::Yes),
                             ty_segment: None,
+                            is_strict,
                         }),
                     }
                 } else {
@@ -893,9 +1024,10 @@ impl Parser<'_, '_> {
                         return None;
                     }
                 };
+                let source = full.to_owned();
                 Expr {
                     span: current.span,
-                    kind: ExprKind::regex_literal(regex, literal),
+                    kind: ExprKind::regex_literal(regex, self.interner.intern(source)),
                 }
             }
             other if other.is_identifier() => {
@@ -904,6 +1036,15 @@ impl Parser<'_, '_> {
                     kind: ExprKind::identifier(other.as_identifier().unwrap()),
                 };
 
+                // `super.x` and `super[x]` can only be used in methods
+                if other.as_identifier() == Some(sym::super_)
+                    && !self.allow_super_property
+                    && self.matches(any(&[TokenType::Dot, TokenType::LeftSquareBrace]))
+                {
+                    self.error(Error::SuperPropertyOutsideMethod(current.span));
+                    return None;
+                }
+
                 // If this identifier is followed by an arrow, this is an arrow function
                 if self.eat(TokenType::FatArrow, false).is_some() {
                     return self.parse_arrow_function_end(current.span, vec![expr], None);
@@ -925,13 +1066,7 @@ impl Parser<'_, '_> {
         let is_generator = self.eat(TokenType::Star, false).is_some();
 
         let ty = if is_generator {
-            if is_async {
-                let star_span = self.previous().unwrap().span;
-                self.error(Error::Unimplemented(star_span, "async generator".into()));
-                return None;
-            }
-
-            FunctionKind::Generator
+            FunctionKind::Generator(is_async.into())
         } else {
             FunctionKind::Function(is_async.into())
         };
@@ -948,19 +1083,26 @@ impl Parser<'_, '_> {
         };
 
         self.eat(TokenType::LeftParen, true)?;
+        let lparen = self.previous()?.span;
 
-        let arguments = self.parse_parameter_list()?;
+        let (arguments, ty_seg, (BlockStatement(statements, scope_id), is_strict)) =
+            self.with_super_property_allowed(false, |this| {
+                let arguments = this.parse_parameter_list()?;
+                let parameters_span = lparen.to(this.previous()?.span);
 
-        // Parse type param
-        let ty_seg = if self.eat(TokenType::Colon, false).is_some() {
-            Some(self.parse_type_segment()?)
-        } else {
-            None
-        };
-
-        self.eat(TokenType::LeftBrace, true)?;
+                // Parse type param
+                let ty_seg = if this.eat(TokenType::Colon, false).is_some() {
+                    Some(this.parse_type_segment()?)
+                } else {
+                    None
+                };
 
-        let BlockStatement(statements, scope_id) = self.parse_block()?;
+                let body = this.parse_function_body(&arguments, parameters_span, |this| {
+                    this.eat(TokenType::LeftBrace, true)?;
+                    this.parse_block()
+                })?;
+                Some((arguments, ty_seg, body))
+            })?;
         let parameters_scope = self.scope_count.inc();
 
         Some((
@@ -972,6 +1114,7 @@ impl Parser<'_, '_> {
                 statements,
                 ty,
                 ty_segment: ty_seg,
+                is_strict,
             },
             self.previous()?.span,
         ))
@@ -1053,8 +1196,13 @@ impl Parser<'_, '_> {
                     let mut rest = None;
                     for (key, value) in properties {
                         match key {
-                            // `x: a` aliases x to 1
-                            ObjectMemberKind::Static(symbol) => {
+                            // `x: a` aliases x to 1. String literal computed keys are also accepted,
+                            // since that is how a shorthand `__proto__` property is represented
+                            ObjectMemberKind::Static(symbol)
+                            | ObjectMemberKind::Dynamic(Expr {
+                                kind: ExprKind::Literal(LiteralExpr::String(symbol)),
+                                ..
+                            }) => {
                                 if let Some(alias) = value.kind.as_identifier() {
                                     fields.push((parser.local_count.inc(), symbol, Some(alias), None))
                                 } else {
@@ -1125,22 +1273,21 @@ impl Parser<'_, '_> {
             list.push((Parameter::SpreadIdentifier(self.create_binding(ident)), None, None));
         }
 
-        let is_statement = self.eat(TokenType::LeftBrace, false).is_some();
-
-        let body = if is_statement {
-            // Go one back ( to the `{` ), so that the next statement is parsed as a block containing all statements
-            self.advance_back();
-
-            self.parse_statement()?
-        } else {
-            let lo_span = self.current()?.span;
-            let expr = self.parse_yield()?;
-            let hi_span = self.previous()?.span;
-            Statement {
-                kind: StatementKind::Return(ReturnStatement(expr)),
-                span: lo_span.to(hi_span),
+        let parameters_span = pre_span.to(self.previous()?.span);
+        let (body, is_strict) = self.parse_function_body(&list, parameters_span, |this| {
+            if this.matches(TokenType::LeftBrace) {
+                // Parse the `{` as part of the statement, so that it is parsed as a block containing all statements
+                this.parse_statement()
+            } else {
+                let lo_span = this.current()?.span;
+                let expr = this.parse_yield()?;
+                let hi_span = this.previous()?.span;
+                Some(Statement {
+                    kind: StatementKind::Return(ReturnStatement(expr)),
+                    span: lo_span.to(hi_span),
+                })
             }
-        };
+        })?;
 
         let body_scope = self.scope_count.inc();
         let parameters_scope = self.scope_count.inc();
@@ -1154,7 +1301,62 @@ impl Parser<'_, '_> {
                 statements: vec![body],
                 ty: FunctionKind::Arrow,
                 ty_segment: None,
+                is_strict,
             }),
         })
     }
 }
+
+/// Returns the span of an `eval` or `arguments` identifier that is assigned to by an assignment target.
+/// Destructuring assignment targets are checked recursively.
+fn eval_or_arguments_target(target: &Expr) -> Option<Span> {
+    match &target.kind {
+        ExprKind::Literal(LiteralExpr::Identifier(sym::eval | sym::arguments)) => Some(target.span),
+        // An element with a default value, e.g. `[x = 1] = []`
+        ExprKind::Assignment(AssignmentExpr {
+            left: AssignmentTarget::Expr(left),
+            operator: TokenType::Assignment,
+            ..
+        }) => eval_or_arguments_target(left),
+        ExprKind::Array(ArrayLiteral { members, .. }) => members.iter().find_map(|member| match member {
+            ArrayMemberKind::Item(expr) | ArrayMemberKind::Spread(expr) => eval_or_arguments_target(expr),
+            ArrayMemberKind::Empty => None,
+        }),
+        ExprKind::Object(ObjectLiteral { members, .. }) => members.iter().find_map(|(kind, value)| match kind {
+            ObjectMemberKind::Default(sym::eval | sym::arguments) => Some(value.span),
+            _ => eval_or_arguments_target(value),
+        }),
+        _ => None,
+    }
+}
+
+/// Checks if the source of a string literal contains a legacy octal escape sequence like `\01`,
+/// or one of the non-octal decimal escape sequences `\8` and `\9`
+fn has_legacy_octal_escape(src: &str) -> bool {
+    let mut bytes = src.bytes();
+    while let Some(byte) = bytes.next() {
+        if byte == b'\\' {
+            match bytes.next() {
+                // `\0` is only a null character escape if it is not followed by another digit
+                Some(b'0') if bytes.clone().next().is_some_and(|b| b.is_ascii_digit()) => return true,
+                Some(b'1'..=b'9') => return true,
+                _ => {}
+            }
+        }
+    }
+    false
+}
+
+/// Returns the key of a shorthand property or method.
+///
+/// Only the `__proto__: value` form of a property definition sets the prototype, so a `__proto__` key here
+/// is turned into a computed key, which always defines an own property.
+fn own_property_key(key: ObjectMemberKind, span: Span) -> ObjectMemberKind {
+    match key {
+        ObjectMemberKind::Static(sym::__proto__) => ObjectMemberKind::Dynamic(Expr {
+            span,
+            kind: ExprKind::string_literal(sym::__proto__),
+        }),
+        key => key,
+    }
+}
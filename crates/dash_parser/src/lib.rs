@@ -1,6 +1,6 @@
 use dash_log::{Level, debug, span};
 use dash_middle::interner::{StringInterner, Symbol};
-use dash_middle::lexer::token::{Token, TokenType};
+use dash_middle::lexer::token::{TemplateSegment, Token, TokenType};
 use dash_middle::parser::error::{Error, TokenTypeSuggestion};
 use dash_middle::parser::expr::{Expr, ExprKind};
 use dash_middle::parser::statement::{Binding, FrontLocalId, ScopeId, Statement};
@@ -11,7 +11,8 @@ mod expr;
 mod stmt;
 mod types;
 
-pub type ParseResult = Result<(Vec<Statement>, Counter<ScopeId>, Counter<FrontLocalId>), Vec<Error>>;
+/// The parsed statements, scope and local counters, and whether the top level code is strict mode code
+pub type ParseResult = Result<(Vec<Statement>, Counter<ScopeId>, Counter<FrontLocalId>, bool), Vec<Error>>;
 
 /// A JavaScript source code parser
 pub struct Parser<'a, 'interner> {
@@ -23,6 +24,11 @@ pub struct Parser<'a, 'interner> {
     source: SourceMap<'a>,
     scope_count: Counter<ScopeId>,
     local_count: Counter<FrontLocalId>,
+    /// Whether the code that is currently being parsed is strict mode code
+    strict: bool,
+    /// Whether `super` properties can be referenced, i.e. the code is in a method,
+    /// or in an arrow function or class field nested in one
+    allow_super_property: bool,
 }
 
 impl<'a, 'interner> Parser<'a, 'interner> {
@@ -51,9 +57,17 @@ impl<'a, 'interner> Parser<'a, 'interner> {
             interner,
             scope_count,
             local_count: Counter::new(),
+            strict: false,
+            allow_super_property: false,
         }
     }
 
+    /// Parses the input as module code, which is always strict mode code.
+    pub fn module(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
     fn create_binding(&mut self, ident: Symbol) -> Binding {
         Binding {
             ident,
@@ -76,6 +90,7 @@ impl<'a, 'interner> Parser<'a, 'interner> {
     /// The AST will be folded by passing true as the `fold` parameter.
     pub fn parse_all(mut self) -> ParseResult {
         let mut stmts = Vec::new();
+        self.strict |= self.has_use_strict_directive(0);
 
         while !self.is_eof() {
             if let Some(stmt) = self.parse() {
@@ -86,7 +101,7 @@ impl<'a, 'interner> Parser<'a, 'interner> {
         if !self.errors.is_empty() {
             Err(self.errors)
         } else {
-            Ok((stmts, self.scope_count, self.local_count))
+            Ok((stmts, self.scope_count, self.local_count, self.strict))
         }
     }
 
@@ -163,7 +178,7 @@ impl<'a, 'interner> Parser<'a, 'interner> {
         self.previous()
     }
 
-    pub fn expect_template_literal(&mut self, emit_error: bool) -> Option<Symbol> {
+    pub fn expect_template_literal(&mut self, emit_error: bool) -> Option<TemplateSegment> {
         self.eat(
             (
                 |tok: Token| {
@@ -237,6 +252,48 @@ impl<'a, 'interner> Parser<'a, 'interner> {
         src.contains('\n')
     }
 
+    /// Checks if the directive prologue that starts at the token at `idx` contains a `"use strict"` directive.
+    ///
+    /// The directive prologue is the sequence of string literal expression statements at the start of
+    /// a script or function body.
+    fn has_use_strict_directive(&self, mut idx: usize) -> bool {
+        while let Some(&Token {
+            ty: TokenType::String(_),
+            span,
+        }) = self.tokens.get(idx)
+        {
+            idx += 1;
+
+            let terminated = match self.tokens.get(idx) {
+                Some(next) if next.ty == TokenType::Semicolon => {
+                    idx += 1;
+                    true
+                }
+                Some(next) if next.ty == TokenType::RightBrace => true,
+                Some(next) => self
+                    .source
+                    .resolve(Span {
+                        lo: span.hi,
+                        hi: next.span.lo,
+                    })
+                    .contains('\n'),
+                None => true,
+            };
+
+            // `"use strict" + x` is a plain expression statement rather than a directive
+            if !terminated {
+                return false;
+            }
+
+            // The directive must not contain any escape sequences, so look at the raw source
+            if matches!(self.source.resolve(span), "\"use strict\"" | "'use strict'") {
+                return true;
+            }
+        }
+
+        false
+    }
+
     pub fn eat<M: Matcher>(&mut self, mut matcher: M, emit_error: bool) -> Option<M::Output> {
         let current = match self.current() {
             Some(k) => *k,
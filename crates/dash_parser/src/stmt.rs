@@ -1,3 +1,5 @@
+use std::mem;
+
 use dash_middle::interner::{Symbol, sym};
 use dash_middle::lexer::token::{Token, TokenType, VARIABLE_TYPES};
 use dash_middle::parser::error::Error;
@@ -44,6 +46,15 @@ impl Parser<'_, '_> {
             TokenType::Break => Some(StatementKind::Break(self.parse_break_continue_label())),
             TokenType::Debugger => Some(StatementKind::Debugger),
             TokenType::Semicolon => Some(StatementKind::Empty),
+            TokenType::With => {
+                let span = self.previous().unwrap().span;
+                if self.strict {
+                    self.error(Error::WithInStrictMode(span));
+                } else {
+                    self.error(Error::Unimplemented(span, "with statement".into()));
+                }
+                None
+            }
             other => 'other: {
                 if let TokenType::Identifier(label) = other
                     && self.eat(TokenType::Colon, false).is_some()
@@ -92,6 +103,14 @@ impl Parser<'_, '_> {
     }
 
     pub fn parse_class(&mut self) -> Option<Class> {
+        // All parts of a class are strict mode code
+        let outer_strict = mem::replace(&mut self.strict, true);
+        let class = self.parse_class_inner();
+        self.strict = outer_strict;
+        class
+    }
+
+    fn parse_class_inner(&mut self) -> Option<Class> {
         let name = self.expect_identifier(false).map(|ident| self.create_binding(ident));
 
         let extends = if self.eat(TokenType::Extends, false).is_some() {
@@ -153,17 +172,22 @@ impl Parser<'_, '_> {
             let is_method = self.eat(TokenType::LeftParen, false).is_some();
 
             if is_method {
-                let arguments = self.parse_parameter_list()?;
-
-                // Parse type param
-                // TODO: this should probably be part of parse_aprameter_list
-                let ty_seg = if self.eat(TokenType::Colon, false).is_some() {
-                    Some(self.parse_type_segment()?)
-                } else {
-                    None
-                };
+                let lparen = self.previous()?.span;
+                let (arguments, ty_seg, (body, is_strict)) = self.with_super_property_allowed(true, |this| {
+                    let arguments = this.parse_parameter_list()?;
+                    let parameters_span = lparen.to(this.previous()?.span);
+
+                    // Parse type param
+                    // TODO: this should probably be part of parse_aprameter_list
+                    let ty_seg = if this.eat(TokenType::Colon, false).is_some() {
+                        Some(this.parse_type_segment()?)
+                    } else {
+                        None
+                    };
 
-                let body = self.parse_statement()?;
+                    let body = this.parse_function_body(&arguments, parameters_span, |this| this.parse_statement())?;
+                    Some((arguments, ty_seg, body))
+                })?;
 
                 let body_scope = self.scope_count.inc();
                 let parameters_scope = self.scope_count.inc();
@@ -178,10 +202,11 @@ impl Parser<'_, '_> {
                     parameters: arguments,
                     statements: vec![body],
                     ty: match is_generator {
-                        true => FunctionKind::Generator,
+                        true => FunctionKind::Generator(asyncness),
                         false => FunctionKind::Function(asyncness),
                     },
                     ty_segment: ty_seg,
+                    is_strict,
                 };
 
                 members.push(ClassMember {
@@ -198,7 +223,7 @@ impl Parser<'_, '_> {
                 let kind = self.next()?.ty;
 
                 let value = match kind {
-                    TokenType::Assignment => Some(self.parse_expression()?),
+                    TokenType::Assignment => Some(self.with_super_property_allowed(true, Self::parse_expression)?),
                     TokenType::Semicolon => None,
                     _ => {
                         // We don't know what this token is, so we assume the user left out the semicolon and meant to declare a property
@@ -347,7 +372,14 @@ impl Parser<'_, '_> {
         binding: VariableBinding,
         in_or_of: TokenType,
         scope: ScopeId,
+        is_await: bool,
     ) -> Option<Loop> {
+        if is_await && in_or_of == TokenType::In {
+            let span = self.previous()?.span;
+            self.error(Error::unexpected_token(span, TokenType::Of));
+            return None;
+        }
+
         let expr = self.parse_expression()?;
 
         self.eat(TokenType::RightParen, true)?;
@@ -366,12 +398,14 @@ impl Parser<'_, '_> {
                 expr,
                 body,
                 scope,
+                is_await,
             }),
             _ => unreachable!(),
         })
     }
 
     fn parse_for_loop(&mut self) -> Option<Loop> {
+        let is_await = self.eat(TokenType::Await, false).is_some();
         self.eat(TokenType::LeftParen, true)?;
         let scope = self.scope_count.inc();
 
@@ -397,6 +431,7 @@ impl Parser<'_, '_> {
                         },
                         in_or_of,
                         scope,
+                        is_await,
                     );
                 } else {
                     // Back to the identifier to re-parse it as a regular for statement
@@ -414,7 +449,7 @@ impl Parser<'_, '_> {
                 if let Some(in_or_of) = self.eat(any(&[TokenType::Of, TokenType::In]), false) {
                     // for (const binding in ..)
 
-                    return self.parse_in_of_loop_after_binding(binding, in_or_of, scope);
+                    return self.parse_in_of_loop_after_binding(binding, in_or_of, scope, is_await);
                 } else {
                     let value = self.parse_variable_definition();
 
@@ -457,6 +492,12 @@ impl Parser<'_, '_> {
             expr
         };
 
+        if is_await {
+            let span = self.previous()?.span;
+            self.error(Error::Unexpected(span, "for await loop without `of`"));
+            return None;
+        }
+
         let body = self.parse_statement()?;
 
         Some(ForLoop::new(init, cond, finalizer, body, scope).into())
@@ -761,6 +802,54 @@ impl Parser<'_, '_> {
         Some(parameters)
     }
 
+    /// Parses the body of a function with the given parameters using `parse`,
+    /// and returns it along with whether the function is strict mode code.
+    ///
+    /// A function is strict if it is nested in strict mode code or if its body starts with a `"use strict"` directive.
+    pub fn parse_function_body<T>(
+        &mut self,
+        parameters: &[(Parameter, Option<Expr>, Option<TypeSegment>)],
+        parameters_span: Span,
+        parse: impl FnOnce(&mut Self) -> Option<T>,
+    ) -> Option<(T, bool)> {
+        let outer_strict = self.strict;
+        // Expression bodies of arrow functions cannot have directives
+        if self.matches(TokenType::LeftBrace) && self.has_use_strict_directive(self.idx + 1) {
+            if !has_simple_parameters(parameters) {
+                self.error(Error::UseStrictWithNonSimpleParameters(parameters_span));
+                return None;
+            }
+            self.strict = true;
+        }
+        let is_strict = self.strict;
+
+        let body = parse(self);
+        self.strict = outer_strict;
+        let body = body?;
+
+        if is_strict && has_duplicate_parameters(parameters) {
+            self.error(Error::DuplicateParameter(parameters_span));
+            return None;
+        }
+
+        Some((body, is_strict))
+    }
+
+    /// Parses a function's parameters and body or a class field's initializer using `parse`,
+    /// allowing `super` properties if it belongs to a method or class.
+    ///
+    /// Arrow functions inherit this from the enclosing code and should not call this.
+    pub fn with_super_property_allowed<T>(
+        &mut self,
+        allowed: bool,
+        parse: impl FnOnce(&mut Self) -> Option<T>,
+    ) -> Option<T> {
+        let outer = mem::replace(&mut self.allow_super_property, allowed);
+        let result = parse(self);
+        self.allow_super_property = outer;
+        result
+    }
+
     /// Parses the `x` in `let x = 1`, `[x, y]` in `let [x, y] = [1, 2]`, etc.
     fn parse_variable_binding_with_kind(&mut self, kind: VariableDeclarationKind) -> Option<VariableBinding> {
         let name = if let Some(ident) = self.expect_identifier(false) {
@@ -853,3 +942,22 @@ impl Parser<'_, '_> {
         })
     }
 }
+
+/// Checks if a parameter list only consists of plain identifiers, without defaults, rest parameters or patterns
+fn has_simple_parameters(parameters: &[(Parameter, Option<Expr>, Option<TypeSegment>)]) -> bool {
+    parameters
+        .iter()
+        .all(|(parameter, default, _)| matches!(parameter, Parameter::Identifier(_)) && default.is_none())
+}
+
+fn has_duplicate_parameters(parameters: &[(Parameter, Option<Expr>, Option<TypeSegment>)]) -> bool {
+    let mut names = Vec::new();
+    parameters.iter().any(|(parameter, ..)| match parameter {
+        Parameter::Identifier(binding) | Parameter::SpreadIdentifier(binding) => {
+            let duplicate = names.contains(&binding.ident);
+            names.push(binding.ident);
+            duplicate
+        }
+        Parameter::Pattern(..) | Parameter::SpreadPattern(..) => false,
+    })
+}
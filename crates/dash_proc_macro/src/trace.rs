@@ -19,13 +19,12 @@ pub fn trace_impl(tt: TokenStream) -> TokenStream {
         Data::Struct(DataStruct {
             fields: Fields::Named(ref fields),
             ..
-        }) => Box::new(
-            fields
-                .named
-                .iter()
-                .map(|x| x.ident.as_ref().unwrap())
-                .map(|x| quote! { self.#x.trace(cx); }),
-        ) as Box<dyn Iterator<Item = _>>,
+        }) => Box::new(fields.named.iter().map(|x| {
+            // Fields that are only present with some features must only be traced if they exist
+            let cfgs = x.attrs.iter().filter(|attr| attr.path().is_ident("cfg"));
+            let ident = x.ident.as_ref().unwrap();
+            quote! { #(#cfgs)* self.#ident.trace(cx); }
+        })) as Box<dyn Iterator<Item = _>>,
         Data::Struct(DataStruct {
             fields: Fields::Unnamed(ref fields),
             ..
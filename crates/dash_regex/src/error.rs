@@ -7,12 +7,30 @@ pub enum Error {
     #[error("unexpected end of file")]
     UnexpectedEof,
 
-    #[error("unexpected character: {}", *.0 as char)]
-    UnexpectedChar(u8),
+    #[error("unexpected character: {0}")]
+    UnexpectedChar(char),
 
     #[error("number too large to fit in a u32")]
     Overflow,
 
+    #[error("range out of order in character class")]
+    RangeOutOfOrder,
+
+    #[error("invalid property name: {0}")]
+    UnknownProperty(String),
+
+    #[error("invalid unicode escape")]
+    InvalidUnicodeEscape,
+
+    #[error("invalid backreference")]
+    InvalidBackreference,
+
+    #[error("duplicate capture group name: {0}")]
+    DuplicateGroupName(String),
+
+    #[error("undefined capture group name: {0}")]
+    UnknownGroupName(String),
+
     #[error("{0}")]
     Flags(#[from] flags::Error),
 }
@@ -1,3 +1,4 @@
+use std::fmt;
 use std::str::FromStr;
 
 use bitflags::bitflags;
@@ -9,6 +10,36 @@ bitflags! {
     pub struct Flags: u8 {
         const GLOBAL = 1;
         const IGNORE_CASE = 2;
+        /// `^` and `$` also match at line terminators
+        const MULTILINE = 4;
+        /// `.` also matches line terminators
+        const DOT_ALL = 8;
+        const UNICODE = 16;
+        /// Only match at `lastIndex`
+        const STICKY = 32;
+        /// Generate the start and end indices of each group
+        const HAS_INDICES = 64;
+        /// Unicode mode with set notation in character classes
+        const UNICODE_SETS = 128;
+    }
+}
+
+/// All flags with their character, in the order used by `RegExp.prototype.flags`
+const FLAG_CHARS: [(Flags, char); 8] = [
+    (Flags::HAS_INDICES, 'd'),
+    (Flags::GLOBAL, 'g'),
+    (Flags::IGNORE_CASE, 'i'),
+    (Flags::MULTILINE, 'm'),
+    (Flags::DOT_ALL, 's'),
+    (Flags::UNICODE, 'u'),
+    (Flags::UNICODE_SETS, 'v'),
+    (Flags::STICKY, 'y'),
+];
+
+impl Flags {
+    /// Whether the `u` or `v` flag is set
+    pub fn is_unicode(self) -> bool {
+        self.intersects(Flags::UNICODE | Flags::UNICODE_SETS)
     }
 }
 
@@ -16,6 +47,12 @@ bitflags! {
 pub enum Error {
     #[error("unknown flag: {0}")]
     UnknownFlag(char),
+
+    #[error("duplicate flag: {0}")]
+    DuplicateFlag(char),
+
+    #[error("the u and v flags cannot be combined")]
+    UnicodeAndUnicodeSets,
 }
 
 impl FromStr for Flags {
@@ -23,12 +60,28 @@ impl FromStr for Flags {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut flags = Flags::empty();
         for c in s.chars() {
-            match c {
-                'g' => flags |= Flags::GLOBAL,
-                'i' => flags |= Flags::IGNORE_CASE,
-                o => return Err(Error::UnknownFlag(o)),
+            let Some(&(flag, _)) = FLAG_CHARS.iter().find(|&&(_, f)| f == c) else {
+                return Err(Error::UnknownFlag(c));
+            };
+            if flags.contains(flag) {
+                return Err(Error::DuplicateFlag(c));
             }
+            flags |= flag;
+        }
+        if flags.contains(Flags::UNICODE | Flags::UNICODE_SETS) {
+            return Err(Error::UnicodeAndUnicodeSets);
         }
         Ok(flags)
     }
 }
+
+impl fmt::Display for Flags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (flag, c) in FLAG_CHARS {
+            if self.contains(flag) {
+                write!(f, "{c}")?;
+            }
+        }
+        Ok(())
+    }
+}
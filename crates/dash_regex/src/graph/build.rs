@@ -16,12 +16,13 @@ pub fn number_groups(regex: &ParsedRegex) -> CaptureGroupMap {
         if let Some((node, rest)) = nodes.split_first() {
             match node {
                 ParseNode::Group(id, nodes) => {
-                    if let GroupCaptureMode::Id(id) = *id {
+                    if let GroupCaptureMode::Id(id) | GroupCaptureMode::Named { id, .. } = *id {
                         map.insert(node, id.try_into().unwrap());
                     }
 
                     inner(map, _counter, nodes);
                 }
+                ParseNode::Lookaround { nodes, .. } => inner(map, _counter, nodes),
                 ParseNode::Optional(node) => inner(map, _counter, slice::from_ref(&**node)),
                 ParseNode::Or(left, right) => {
                     inner(map, _counter, left);
@@ -31,8 +32,11 @@ pub fn number_groups(regex: &ParsedRegex) -> CaptureGroupMap {
                 ParseNode::AnyCharacter
                 | ParseNode::MetaSequence(_)
                 | ParseNode::LiteralCharacter(_)
-                | ParseNode::CharacterClass(_)
-                | ParseNode::Anchor(_) => {} // cannot contain group nodes
+                | ParseNode::CharacterClass { .. }
+                | ParseNode::Property { .. }
+                | ParseNode::Anchor(_)
+                | ParseNode::Backreference(_)
+                | ParseNode::NamedBackreference(_) => {} // cannot contain group nodes
             }
 
             inner(map, _counter, rest);
@@ -45,9 +49,35 @@ pub fn number_groups(regex: &ParsedRegex) -> CaptureGroupMap {
     map
 }
 
+fn lower_class_item(item: &ParsedCharacterClassItem) -> CharacterClassItem {
+    match *item {
+        ParsedCharacterClassItem::Node(ParseNode::AnyCharacter) => CharacterClassItem::AnyCharacter,
+        ParsedCharacterClassItem::Node(ParseNode::LiteralCharacter(literal)) => CharacterClassItem::Literal(literal),
+        ParsedCharacterClassItem::Node(ParseNode::MetaSequence(meta)) => CharacterClassItem::Meta(meta),
+        ParsedCharacterClassItem::Node(ParseNode::Property { property, negated }) => {
+            CharacterClassItem::Property { property, negated }
+        }
+        ParsedCharacterClassItem::Node(ParseNode::CharacterClass { ref items, negated }) => CharacterClassItem::Class {
+            items: items.iter().map(lower_class_item).collect(),
+            negated,
+        },
+        ParsedCharacterClassItem::Node(ref node) => {
+            panic!("cannot lower {node:?} in character class")
+        }
+        ParsedCharacterClassItem::Range(from, to) => CharacterClassItem::Range(from, to),
+        ParsedCharacterClassItem::Intersection(ref left, ref right) => {
+            CharacterClassItem::Intersection(Box::new((lower_class_item(left), lower_class_item(right))))
+        }
+        ParsedCharacterClassItem::Subtraction(ref left, ref right) => {
+            CharacterClassItem::Subtraction(Box::new((lower_class_item(left), lower_class_item(right))))
+        }
+    }
+}
+
 pub fn build(group_numbers: &CaptureGroupMap, regex: &ParsedRegex) -> (Graph, NodeId) {
     fn lower_repetition(
         graph: &mut BuildGraph,
+        regex: &ParsedRegex,
         group_numbers: &CaptureGroupMap,
         node: &ParseNode,
         min: u32,
@@ -60,7 +90,7 @@ pub fn build(group_numbers: &CaptureGroupMap, regex: &ParsedRegex) -> (Graph, No
                 start: NodeId::DUMMY, // will be set later
             },
         });
-        let inner_id = inner(graph, group_numbers, slice::from_ref(node), end_id);
+        let inner_id = inner(graph, regex, group_numbers, slice::from_ref(node), end_id);
         let start_id = graph.push(Node {
             next: Some(next),
             kind: NodeKind::RepetitionStart {
@@ -78,12 +108,13 @@ pub fn build(group_numbers: &CaptureGroupMap, regex: &ParsedRegex) -> (Graph, No
 
     fn inner(
         graph: &mut BuildGraph,
+        regex: &ParsedRegex,
         group_numbers: &CaptureGroupMap,
         nodes: &[ParseNode],
         outer_next: NodeId,
     ) -> NodeId {
         if let Some((current, rest)) = nodes.split_first() {
-            let next = inner(graph, group_numbers, rest, outer_next);
+            let next = inner(graph, regex, group_numbers, rest, outer_next);
             match *current {
                 ParseNode::AnyCharacter => graph.push(Node {
                     next: Some(next),
@@ -94,55 +125,77 @@ pub fn build(group_numbers: &CaptureGroupMap, regex: &ParsedRegex) -> (Graph, No
                     kind: NodeKind::Meta(meta),
                 }),
                 ParseNode::Repetition { ref node, min, max } => {
-                    lower_repetition(graph, group_numbers, node, min, max, next)
+                    lower_repetition(graph, regex, group_numbers, node, min, max, next)
                 }
                 ParseNode::LiteralCharacter(literal) => graph.push(Node {
                     next: Some(next),
                     kind: NodeKind::Literal(literal),
                 }),
-                ParseNode::CharacterClass(ref parse_items) => {
-                    let items = parse_items
-                        .iter()
-                        .map(|item| match *item {
-                            ParsedCharacterClassItem::Node(ParseNode::AnyCharacter) => CharacterClassItem::AnyCharacter,
-                            ParsedCharacterClassItem::Node(ParseNode::LiteralCharacter(literal)) => {
-                                CharacterClassItem::Literal(literal)
-                            }
-                            ParsedCharacterClassItem::Node(ParseNode::MetaSequence(meta)) => {
-                                CharacterClassItem::Meta(meta)
-                            }
-                            ParsedCharacterClassItem::Node(ref node) => {
-                                panic!("cannot lower {node:?} in character class")
-                            }
-                            ParsedCharacterClassItem::Range(from, to) => CharacterClassItem::Range(from, to),
-                        })
-                        .collect::<Box<[_]>>();
-
-                    graph.push(Node {
-                        next: Some(next),
-                        kind: NodeKind::CharacterClass(items),
-                    })
-                }
+                ParseNode::CharacterClass { ref items, negated } => graph.push(Node {
+                    next: Some(next),
+                    kind: NodeKind::CharacterClass {
+                        items: items.iter().map(lower_class_item).collect(),
+                        negated,
+                    },
+                }),
+                ParseNode::Property { property, negated } => graph.push(Node {
+                    next: Some(next),
+                    kind: NodeKind::CharacterClass {
+                        items: Box::new([CharacterClassItem::Property { property, negated }]),
+                        negated: false,
+                    },
+                }),
                 ParseNode::Anchor(anchor) => graph.push(Node {
                     next: Some(next),
                     kind: NodeKind::Anchor(anchor),
                 }),
                 ParseNode::Or(ref left, ref right) => {
-                    let left = inner(graph, group_numbers, left, next);
-                    let right = inner(graph, group_numbers, right, next);
+                    let left = inner(graph, regex, group_numbers, left, next);
+                    let right = inner(graph, regex, group_numbers, right, next);
                     graph.push(Node {
                         next: Some(next),
                         kind: NodeKind::Or(left, right),
                     })
                 }
-                ParseNode::Optional(ref node) => lower_repetition(graph, group_numbers, node, 0, Some(1), next),
+                ParseNode::Optional(ref node) => lower_repetition(graph, regex, group_numbers, node, 0, Some(1), next),
+                ParseNode::Lookaround {
+                    kind,
+                    negated,
+                    ref nodes,
+                } => {
+                    let end = graph.push(Node {
+                        next: None,
+                        kind: NodeKind::LookaroundEnd { kind },
+                    });
+                    let inner_id = inner(graph, regex, group_numbers, nodes, end);
+                    graph.push(Node {
+                        next: Some(next),
+                        kind: NodeKind::Lookaround {
+                            inner: inner_id,
+                            kind,
+                            negated,
+                        },
+                    })
+                }
+                ParseNode::Backreference(id) => graph.push(Node {
+                    next: Some(next),
+                    kind: NodeKind::Backreference(id.try_into().unwrap()),
+                }),
+                ParseNode::NamedBackreference(ref name) => {
+                    // The parser has already checked that the group exists
+                    let &(_, id) = regex.group_names.iter().find(|(n, _)| n == name).unwrap();
+                    graph.push(Node {
+                        next: Some(next),
+                        kind: NodeKind::Backreference(id.try_into().unwrap()),
+                    })
+                }
                 ParseNode::Group(_, ref nodes) => {
                     let group_id = group_numbers.get(&(current as *const ParseNode)).copied();
                     let end = graph.push(Node {
                         next: Some(next),
                         kind: NodeKind::GroupEnd { group_id },
                     });
-                    let inner_id = inner(graph, group_numbers, nodes, end);
+                    let inner_id = inner(graph, regex, group_numbers, nodes, end);
                     graph.push(Node {
                         next: Some(inner_id),
                         kind: NodeKind::GroupStart { group_id },
@@ -159,6 +212,6 @@ pub fn build(group_numbers: &CaptureGroupMap, regex: &ParsedRegex) -> (Graph, No
         kind: NodeKind::End,
         next: None,
     });
-    let root = inner(&mut graph, group_numbers, &regex.nodes, end);
+    let root = inner(&mut graph, regex, group_numbers, &regex.nodes, end);
     (graph.finalize(), root)
 }
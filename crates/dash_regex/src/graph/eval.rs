@@ -2,7 +2,8 @@ use std::cell::Cell;
 use std::ops::Range;
 
 use crate::graph::node::CharacterClassItem;
-use crate::node::Anchor;
+use crate::node::{Anchor, LookaroundKind};
+use crate::{Flags, unicode};
 
 use super::Regex;
 use super::node::{Graph, NodeId, NodeKind};
@@ -16,27 +17,74 @@ pub enum ProcessedGroupState {
 struct Shared<'a> {
     processed_groups: &'a mut [Option<(u32, u32, ProcessedGroupState)>],
     pending_groups: &'a mut [(Option<u32>, Option<u32>)],
-    /// The full input source of this "attempt".
-    full_input: &'a [u8],
+    /// The full input string.
+    input: &'a str,
     graph: &'a Graph,
-    /// The offset of `full_input` in the *original* input string.
-    offset_from_original: u32,
+    flags: Flags,
     end_offset: Option<u32>,
+    /// The offsets at which the lookbehinds currently being evaluated must end
+    lookbehind_ends: Vec<u32>,
 }
 impl Shared<'_> {
-    /// Returns the offset of the passed in slice relative to the full input.
-    /// The slice must actually be obtained from the full input for the return value to make sense.
-    /// The value is unspecified (but not undefined) if passed an input slice from somewhere else.
-    pub fn offset_of(&self, remaining: &[u8]) -> u32 {
-        (remaining.as_ptr().addr() - self.full_input.as_ptr().addr()) as u32
+    /// Returns the offset of the remaining input relative to the full input.
+    /// The slice must be a suffix of the full input for the return value to make sense.
+    pub fn offset_of(&self, remaining: &str) -> u32 {
+        (self.input.len() - remaining.len()) as u32
     }
 
-    /// Same as `offset`, but returns it relative to the original input.
-    pub fn offset_of_from_original(&self, remaining: &[u8]) -> u32 {
-        self.offset_from_original + self.offset_of(remaining)
+    /// Returns the character before the remaining input
+    pub fn previous_char(&self, remaining: &str) -> Option<char> {
+        self.input[..self.offset_of(remaining) as usize].chars().next_back()
+    }
+
+    /// Checks if the character is in the character class, taking the `i` flag into account
+    pub fn class_matches(&self, items: &[CharacterClassItem], c: char) -> bool {
+        if self.flags.contains(Flags::IGNORE_CASE) {
+            unicode::case_variants(c, self.flags.is_unicode()).any(|c| items.iter().any(|item| item.matches(c)))
+        } else {
+            items.iter().any(|item| item.matches(c))
+        }
+    }
+
+    pub fn literal_matches(&self, literal: char, c: char) -> bool {
+        if self.flags.contains(Flags::IGNORE_CASE) {
+            let unicode = self.flags.is_unicode();
+            unicode::canonicalize(literal, unicode) == unicode::canonicalize(c, unicode)
+        } else {
+            literal == c
+        }
+    }
+
+    /// Checks if the remaining input starts with the text captured by the given group.
+    /// Returns the input after it, or the input itself if the group did not participate in the match.
+    fn backreference_matches<'a>(&self, group_id: u32, remaining: &'a str) -> Option<&'a str> {
+        let Some((start, end, _)) = self.processed_groups[group_id as usize] else {
+            return Some(remaining);
+        };
+        let captured = &self.input[start as usize..end as usize];
+
+        let mut rest = remaining.chars();
+        for expected in captured.chars() {
+            if !rest.next().is_some_and(|c| self.literal_matches(expected, c)) {
+                return None;
+            }
+        }
+        Some(rest.as_str())
+    }
+
+    fn is_word_boundary(&self, remaining: &str) -> bool {
+        let before = self.previous_char(remaining).is_some_and(unicode::is_word_character);
+        let after = remaining.chars().next().is_some_and(unicode::is_word_character);
+        before != after
     }
 }
 
+/// Splits off the first character if it satisfies the predicate
+fn split_char_if(remaining: &str, predicate: impl FnOnce(char) -> bool) -> Option<&str> {
+    let c = remaining.chars().next().filter(|&c| predicate(c))?;
+    Some(&remaining[c.len_utf8()..])
+}
+
 #[derive(Debug, Clone)]
 struct Cx<'a> {
     /// How many iterations have matched so far
@@ -48,7 +96,7 @@ struct Cx<'a> {
 }
 
 impl<'a> Cx<'a> {
-    pub fn for_node(&'a self, shared: &Shared<'_>, target: NodeId, origin: NodeId, remaining: &[u8]) -> Cx<'a> {
+    pub fn for_node(&'a self, shared: &Shared<'_>, target: NodeId, origin: NodeId, remaining: &str) -> Cx<'a> {
         let mut current_repetition_count = self.current_repetition_count.clone();
         let mut current_repetition_start = self.current_repetition_start.clone();
         let mut parent = self.parent;
@@ -74,7 +122,7 @@ impl<'a> Cx<'a> {
     }
 }
 
-fn step(shared: &mut Shared<'_>, cx: Cx<'_>, node_id: NodeId, mut remaining: &[u8]) -> bool {
+fn step(shared: &mut Shared<'_>, cx: Cx<'_>, node_id: NodeId, mut remaining: &str) -> bool {
     // The reason for shadowing cx with a borrow here is so that you're forced to go through `Cx::for_node` when calling `step(...)`.
     // You can't pass the same `cx` when evaluating a sub-node.
     let mut cx = &cx;
@@ -82,7 +130,8 @@ fn step(shared: &mut Shared<'_>, cx: Cx<'_>, node_id: NodeId, mut remaining: &[u
 
     let mut matches = match node.kind {
         NodeKind::AnyCharacter => {
-            if let Some(rest) = remaining.get(1..) {
+            let dot_all = shared.flags.contains(Flags::DOT_ALL);
+            if let Some(rest) = split_char_if(remaining, |c| dot_all || !unicode::is_line_terminator(c)) {
                 remaining = rest;
                 true
             } else {
@@ -112,27 +161,31 @@ fn step(shared: &mut Shared<'_>, cx: Cx<'_>, node_id: NodeId, mut remaining: &[u
             current_repetition_count >= min
         }
         NodeKind::Anchor(Anchor::StartOfString) => {
-            // Make sure it's both at the start of the current attempt as well as from all previous failed attempts
-            shared.offset_from_original == 0 && remaining.len() == shared.full_input.len()
+            let multiline = shared.flags.contains(Flags::MULTILINE);
+            match shared.previous_char(remaining) {
+                Some(c) => multiline && unicode::is_line_terminator(c),
+                None => true,
+            }
         }
-        NodeKind::Anchor(Anchor::EndOfString) => remaining.is_empty(),
+        NodeKind::Anchor(Anchor::EndOfString) => {
+            let multiline = shared.flags.contains(Flags::MULTILINE);
+            match remaining.chars().next() {
+                Some(c) => multiline && unicode::is_line_terminator(c),
+                None => true,
+            }
+        }
+        NodeKind::Anchor(Anchor::WordBoundary) => shared.is_word_boundary(remaining),
+        NodeKind::Anchor(Anchor::NotWordBoundary) => !shared.is_word_boundary(remaining),
         NodeKind::Meta(meta) => {
-            if let Some((_, rest)) = remaining.split_first().filter(|&(&c, _)| meta.matches(c)) {
+            if let Some(rest) = split_char_if(remaining, |c| meta.matches(c)) {
                 remaining = rest;
                 true
             } else {
                 false
             }
         }
-        NodeKind::CharacterClass(ref items) => {
-            if let Some((_, rest)) = remaining.split_first().filter(|&(&c, _)| {
-                items.iter().copied().any(|item| match item {
-                    CharacterClassItem::Literal(lit) => lit == c,
-                    CharacterClassItem::AnyCharacter => true,
-                    CharacterClassItem::Meta(meta) => meta.matches(c),
-                    CharacterClassItem::Range(min, max) => (min..=max).contains(&c),
-                })
-            }) {
+        NodeKind::CharacterClass { ref items, negated } => {
+            if let Some(rest) = split_char_if(remaining, |c| shared.class_matches(items, c) != negated) {
                 remaining = rest;
                 true
             } else {
@@ -140,7 +193,7 @@ fn step(shared: &mut Shared<'_>, cx: Cx<'_>, node_id: NodeId, mut remaining: &[u
             }
         }
         NodeKind::Literal(lit) => {
-            if let Some((_, rest)) = remaining.split_first().filter(|&(&c, _)| c == lit) {
+            if let Some(rest) = split_char_if(remaining, |c| shared.literal_matches(lit, c)) {
                 remaining = rest;
                 true
             } else {
@@ -154,16 +207,18 @@ fn step(shared: &mut Shared<'_>, cx: Cx<'_>, node_id: NodeId, mut remaining: &[u
         NodeKind::RepetitionEnd { start } => {
             let end_off = shared.offset_of(remaining);
             if cx.current_repetition_start.get().unwrap() == end_off {
-                // We haven't made any progress in this repetition iteration and won't. Treat this as the end of the regex.
-                shared.end_offset = Some(shared.offset_of(remaining));
-                return true;
+                // We haven't made any progress in this repetition iteration and won't.
+                // Leave the repetition and continue with the rest of the regex in the parent context.
+                let next = node.next.unwrap();
+                let parent = cx.parent.unwrap();
+                return step(shared, parent.for_node(shared, next, start, remaining), next, remaining);
             } else {
                 return step(shared, cx.for_node(shared, start, node_id, remaining), start, remaining);
             }
         }
         NodeKind::GroupStart { group_id } => {
             if let Some(group_id) = group_id {
-                let offset = shared.offset_of_from_original(remaining);
+                let offset = shared.offset_of(remaining);
                 shared.pending_groups[group_id as usize] = (Some(offset), None);
             }
             true
@@ -173,7 +228,7 @@ fn step(shared: &mut Shared<'_>, cx: Cx<'_>, node_id: NodeId, mut remaining: &[u
                 let group_id = group_id as usize;
                 let old = shared.processed_groups[group_id];
                 let start = shared.pending_groups[group_id].0.unwrap();
-                let end = shared.offset_of_from_original(remaining);
+                let end = shared.offset_of(remaining);
                 shared.processed_groups[group_id] = Some((start, end, ProcessedGroupState::Unconfirmed));
 
                 return if let Some(next) = node.next {
@@ -204,6 +259,57 @@ fn step(shared: &mut Shared<'_>, cx: Cx<'_>, node_id: NodeId, mut remaining: &[u
             }
             true
         }
+        NodeKind::Lookaround { inner, kind, negated } => {
+            let saved_groups = shared.processed_groups.to_vec();
+            let matched = match kind {
+                LookaroundKind::Ahead => step(shared, cx.for_node(shared, inner, node_id, remaining), inner, remaining),
+                LookaroundKind::Behind => {
+                    // Try all possible start positions, leftmost first, until the inner nodes end exactly here
+                    let end = shared.offset_of(remaining);
+                    shared.lookbehind_ends.push(end);
+                    let input = shared.input;
+                    let matched = (0..=end as usize)
+                        .filter(|&start| input.is_char_boundary(start))
+                        .any(|start| {
+                            let candidate = &input[start..];
+                            step(shared, cx.for_node(shared, inner, node_id, candidate), inner, candidate)
+                        });
+                    shared.lookbehind_ends.pop();
+                    matched
+                }
+            };
+
+            if matched == negated {
+                shared.processed_groups.copy_from_slice(&saved_groups);
+                return false;
+            }
+            if negated {
+                // Captures in negative lookarounds are never visible outside of them
+                shared.processed_groups.copy_from_slice(&saved_groups);
+            }
+
+            // Lookarounds are atomic, so if the rest fails to match there is nothing to backtrack into
+            let next = node.next.unwrap();
+            if step(shared, cx.for_node(shared, next, node_id, remaining), next, remaining) {
+                return true;
+            }
+            shared.processed_groups.copy_from_slice(&saved_groups);
+            return false;
+        }
+        NodeKind::LookaroundEnd { kind } => {
+            return match kind {
+                LookaroundKind::Ahead => true,
+                LookaroundKind::Behind => shared.lookbehind_ends.last() == Some(&shared.offset_of(remaining)),
+            };
+        }
+        NodeKind::Backreference(group_id) => {
+            if let Some(rest) = shared.backreference_matches(group_id, remaining) {
+                remaining = rest;
+                true
+            } else {
+                false
+            }
+        }
         NodeKind::End => {
             shared.end_offset = Some(shared.offset_of(remaining));
             assert!(node.next.is_none());
@@ -232,17 +338,22 @@ impl EvalSuccess {
 #[derive(Debug)]
 pub struct NoMatch;
 
-pub fn eval(regex: &Regex, mut input: &[u8]) -> Result<EvalSuccess, NoMatch> {
+/// Searches for a match starting at the byte offset `start`.
+///
+/// With the sticky flag, only a match at exactly `start` is attempted.
+pub fn eval(regex: &Regex, input: &str, mut start: usize) -> Result<EvalSuccess, NoMatch> {
+    if start > input.len() {
+        return Err(NoMatch);
+    }
+    while !input.is_char_boundary(start) {
+        start += 1;
+    }
+
     let mut processed_groups = vec![None; regex.group_count as usize].into_boxed_slice();
     let mut pending_groups = vec![(None, None); regex.group_count as usize].into_boxed_slice();
-    let mut offset_from_original = 0;
     loop {
         // TODO: add a fast reject path where we find the first required character and seek to it in `input`
-        processed_groups[0] = Some((
-            offset_from_original,
-            offset_from_original,
-            ProcessedGroupState::Confirmed,
-        ));
+        processed_groups[0] = Some((start as u32, start as u32, ProcessedGroupState::Confirmed));
         processed_groups[1..].fill(None);
         pending_groups.fill((None, None));
 
@@ -253,18 +364,19 @@ pub fn eval(regex: &Regex, mut input: &[u8]) -> Result<EvalSuccess, NoMatch> {
         };
         let (current_repetition_count, current_repetition_start, outer_cx) =
             if let NodeKind::RepetitionStart { .. } = regex.graph[regex.root].kind {
-                (Some(0), Some(0), Some(&outer_cx))
+                (Some(0), Some(start as u32), Some(&outer_cx))
             } else {
                 (None, None, None)
             };
 
         let mut shared = Shared {
-            full_input: input,
+            input,
             graph: &regex.graph,
-            offset_from_original,
+            flags: regex.flags,
             pending_groups: &mut pending_groups,
             processed_groups: &mut processed_groups,
             end_offset: None,
+            lookbehind_ends: Vec::new(),
         };
         let cx = Cx {
             current_repetition_count: Cell::new(current_repetition_count),
@@ -272,19 +384,21 @@ pub fn eval(regex: &Regex, mut input: &[u8]) -> Result<EvalSuccess, NoMatch> {
             parent: outer_cx,
         };
 
-        if step(&mut shared, cx, regex.root, input) {
-            processed_groups[0].as_mut().unwrap().1 += shared.end_offset.unwrap();
+        if step(&mut shared, cx, regex.root, &input[start..]) {
+            processed_groups[0].as_mut().unwrap().1 = shared.end_offset.unwrap();
             return Ok(EvalSuccess {
                 groups: processed_groups,
             });
         }
 
-        if let Some(rest) = input.get(1..) {
-            offset_from_original += 1;
-            input = rest;
-        } else {
+        if regex.flags.contains(Flags::STICKY) {
             break;
         }
+
+        match input[start..].chars().next() {
+            Some(c) => start += c.len_utf8(),
+            None => break,
+        }
     }
 
     Err(NoMatch)
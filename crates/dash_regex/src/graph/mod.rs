@@ -16,11 +16,20 @@ pub struct Regex {
     flags: Flags,
     root: NodeId,
     group_count: u32,
+    group_names: Box<[(Box<str>, u32)]>,
 }
 
 impl Regex {
     pub fn eval(&self, input: &str) -> Result<EvalSuccess, NoMatch> {
-        eval::eval(self, input.as_bytes())
+        eval::eval(self, input, 0)
+    }
+
+    /// Searches for a match starting at the given byte offset.
+    /// With the sticky flag, the match must start exactly at that offset.
+    ///
+    /// Group offsets in the result are relative to the start of `input`.
+    pub fn eval_from(&self, input: &str, start: usize) -> Result<EvalSuccess, NoMatch> {
+        eval::eval(self, input, start)
     }
 
     pub fn matches(&self, input: &str) -> bool {
@@ -30,6 +39,11 @@ impl Regex {
     pub fn flags(&self) -> Flags {
         self.flags
     }
+
+    /// Returns the names of all named groups along with their group index, in the order they appear in the pattern
+    pub fn group_names(&self) -> &[(Box<str>, u32)] {
+        &self.group_names
+    }
 }
 
 pub fn compile(regex: ParsedRegex, flags: Flags) -> Regex {
@@ -42,10 +56,16 @@ pub fn compile(regex: ParsedRegex, flags: Flags) -> Regex {
     let numbered = build::number_groups(regex);
     let (graph, root) = build::build(&numbered, regex);
     let group_count = u32::try_from(regex.group_count).unwrap();
+    let group_names = regex
+        .group_names
+        .iter()
+        .map(|(name, id)| (name.clone(), u32::try_from(*id).unwrap()))
+        .collect();
 
     Regex {
         graph,
         group_count,
+        group_names,
         flags,
         root,
     }
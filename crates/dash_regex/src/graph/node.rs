@@ -1,6 +1,7 @@
 use std::ops::{Deref, DerefMut, Index, IndexMut};
 
-use crate::node::{Anchor, MetaSequence};
+use crate::node::{Anchor, LookaroundKind, MetaSequence};
+use crate::unicode::Property;
 
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "format", derive(serde::Serialize, serde::Deserialize))]
@@ -28,8 +29,11 @@ pub enum NodeKind {
     },
     Anchor(Anchor),
     Meta(MetaSequence),
-    CharacterClass(Box<[CharacterClassItem]>),
-    Literal(u8),
+    CharacterClass {
+        items: Box<[CharacterClassItem]>,
+        negated: bool,
+    },
+    Literal(char),
     Or(NodeId, NodeId),
     RepetitionEnd {
         /// The `RepetitionStart` node to jump to when executing the next repetition iteration
@@ -41,16 +45,54 @@ pub enum NodeKind {
     GroupEnd {
         group_id: Option<u32>,
     },
+    /// A zero-width assertion that checks if the nodes starting at `inner` match
+    Lookaround {
+        inner: NodeId,
+        kind: LookaroundKind,
+        negated: bool,
+    },
+    /// The last node of a lookaround's inner nodes
+    LookaroundEnd {
+        kind: LookaroundKind,
+    },
+    Backreference(u32),
     End,
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "format", derive(serde::Serialize, serde::Deserialize))]
 pub enum CharacterClassItem {
-    Literal(u8),
+    Literal(char),
     AnyCharacter,
     Meta(MetaSequence),
-    Range(u8, u8),
+    Range(char, char),
+    Property {
+        property: Property,
+        negated: bool,
+    },
+    /// A nested character class
+    Class {
+        items: Box<[CharacterClassItem]>,
+        negated: bool,
+    },
+    Intersection(Box<(CharacterClassItem, CharacterClassItem)>),
+    Subtraction(Box<(CharacterClassItem, CharacterClassItem)>),
+}
+
+impl CharacterClassItem {
+    /// Checks if the character is in this set. This does not take case-insensitivity into account.
+    pub fn matches(&self, c: char) -> bool {
+        match *self {
+            Self::Literal(lit) => lit == c,
+            Self::AnyCharacter => true,
+            Self::Meta(meta) => meta.matches(c),
+            Self::Range(min, max) => (min..=max).contains(&c),
+            Self::Property { property, negated } => property.matches(c) != negated,
+            Self::Class { ref items, negated } => items.iter().any(|item| item.matches(c)) != negated,
+            Self::Intersection(ref operands) => operands.0.matches(c) && operands.1.matches(c),
+            Self::Subtraction(ref operands) => operands.0.matches(c) && !operands.1.matches(c),
+        }
+    }
 }
 
 pub type BuildGraph = Graph<Vec<Node>>;
@@ -7,6 +7,7 @@ pub mod error;
 pub mod flags;
 mod node;
 mod parser;
+mod unicode;
 
 mod graph;
 
@@ -31,8 +32,9 @@ impl ParseFlags for Flags {
 }
 
 pub fn compile(input: &str, flags: impl ParseFlags) -> Result<Regex, Error> {
-    let parsed = Parser::new(input.as_bytes()).parse_all()?;
     let flags = flags.parse()?;
+    let input = input.chars().collect::<Vec<_>>();
+    let parsed = Parser::new(&input, flags).parse_all()?;
     Ok(graph::compile(parsed, flags))
 }
 
@@ -84,4 +86,73 @@ pub fn test() {
     // ^ anchor must not match when retrying substrings
     assert!(!compile("^m", "").unwrap().matches("ama"));
     assert!(compile("^m", "").unwrap().matches("ma"));
+
+    // Code points
+    assert_matches_groups(&compile("(é+)", "").unwrap(), "cafééé", &["ééé"]);
+    assert!(compile("^.$", "u").unwrap().matches("😀"));
+    assert!(compile("^[😀-😂]$", "u").unwrap().matches("😁"));
+    assert!(compile(r"^\u{1F600}$", "u").unwrap().matches("😀"));
+    assert!(compile(r"^\uD83D\uDE00$", "").unwrap().matches("😀"));
+    assert!(compile("^ÄB$", "i").unwrap().matches("äb"));
+
+    // Flags
+    assert!(!compile(".", "").unwrap().matches("\n"));
+    assert!(compile(".", "s").unwrap().matches("\n"));
+    assert!(compile("^b$", "m").unwrap().matches("a\nb\nc"));
+    assert!(!compile("^b$", "").unwrap().matches("a\nb\nc"));
+    assert!(compile("a", "y").unwrap().eval_from("ba", 1).is_ok());
+    assert!(compile("a", "y").unwrap().eval_from("ba", 0).is_err());
+    assert!(compile("a", "gg").is_err());
+    assert!(compile("a", "uv").is_err());
+    assert_eq!("gimsuyd".parse::<Flags>().unwrap().to_string(), "dgimsuy");
+
+    // Escapes and classes
+    assert!(compile(r"\bfoo\b", "").unwrap().matches("a foo"));
+    assert!(!compile(r"\bfoo\b", "").unwrap().matches("afoo"));
+    assert!(compile("^[^abc]$", "").unwrap().matches("d"));
+    assert!(!compile("^[^abc]$", "").unwrap().matches("a"));
+    assert!(compile(r"^[\x00-\x1f]$", "").unwrap().matches("\t"));
+    assert!(compile("[b-a]", "").is_err());
+
+    // Unicode properties and sets
+    assert!(compile(r"^\p{Letter}+$", "u").unwrap().matches("Ωmega"));
+    assert!(!compile(r"\p{Lu}", "u").unwrap().matches("abc"));
+    assert!(compile(r"^\P{L}$", "u").unwrap().matches("1"));
+    assert!(compile(r"^\p{Script=Greek}$", "u").unwrap().matches("α"));
+    assert!(compile(r"\p{Foo}", "u").is_err());
+    assert!(compile(r"^[\p{L}--[a-z]]$", "v").unwrap().matches("A"));
+    assert!(!compile(r"^[\p{L}--[a-z]]$", "v").unwrap().matches("a"));
+    assert!(compile(r"^[[a-z]&&[aeiou]]$", "v").unwrap().matches("e"));
+    assert!(!compile(r"^[[a-z]&&[aeiou]]$", "v").unwrap().matches("b"));
+
+    // Lookarounds
+    assert_matches_groups(&compile(r"(\w+)(?=!)", "").unwrap(), "hey there!", &["there"]);
+    assert!(!compile(r"^foo(?!bar)", "").unwrap().matches("foobar"));
+    assert!(compile(r"^foo(?!bar)", "").unwrap().matches("foobaz"));
+    assert_matches_groups(&compile(r"(?<=\$)(\d+)", "").unwrap(), "cost: 5 or $42", &["42"]);
+    assert_matches_groups(&compile(r"(?<!\$)\b(\d+)", "").unwrap(), "$5 or 42", &["42"]);
+    assert!(compile(r"(?<=^a*)b", "").unwrap().matches("aaab"));
+    assert!(!compile(r"(?<=^a*)b", "").unwrap().matches("acab"));
+
+    // Backreferences
+    assert_matches_groups(&compile(r"(\w)\1", "").unwrap(), "abccd", &["c"]);
+    assert!(!compile(r"^(a+)b\1$", "").unwrap().matches("aabaaa"));
+    assert!(compile(r"^(a+)b\1$", "").unwrap().matches("aabaa"));
+    assert!(compile(r"^(a)\1$", "i").unwrap().matches("aA"));
+    assert!(compile(r"^\1(a)$", "").unwrap().matches("a"));
+    assert!(compile(r"^\2$", "").unwrap().matches("\u{2}"));
+    assert!(compile(r"\2", "u").is_err());
+
+    // Named groups
+    let date = compile(r"(?<year>\d{4})-(?<month>\d{2})", "").unwrap();
+    assert_matches_groups(&date, "on 2024-05", &["2024", "05"]);
+    assert_eq!(date.group_names(), [("year".into(), 1), ("month".into(), 2)]);
+    assert!(compile(r"^(?<q>['\x22]).*\k<q>$", "").unwrap().matches("'hi'"));
+    assert!(!compile(r"^(?<q>['\x22]).*\k<q>$", "").unwrap().matches("'hi\x22"));
+    assert!(compile(r"(?<a>x)(?<a>y)", "").is_err());
+    assert!(compile(r"(?<a>x)\k<b>", "").is_err());
+    assert!(compile(r"\k", "").unwrap().matches("k"));
+
+    // Repetitions that stop making progress must still match the rest of the pattern
+    assert!(!compile("(a?)*b", "").unwrap().matches("c"));
 }
@@ -1,20 +1,37 @@
 #[cfg(feature = "format")]
 use serde::{Deserialize, Serialize};
 
+use crate::unicode::{self, Property};
+
 #[cfg_attr(feature = "format", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq)]
 pub enum CharacterClassItem {
     Node(Node),
-    Range(u8, u8),
+    Range(char, char),
+    /// `A&&B`, only allowed with the `v` flag
+    Intersection(Box<CharacterClassItem>, Box<CharacterClassItem>),
+    /// `A--B`, only allowed with the `v` flag
+    Subtraction(Box<CharacterClassItem>, Box<CharacterClassItem>),
 }
 
 #[cfg_attr(feature = "format", derive(Serialize, Deserialize))]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum GroupCaptureMode {
     /// `(?:...)`
     None,
     /// `(...)`
     Id(usize),
+    /// `(?<name>...)`
+    Named { id: usize, name: Box<str> },
+}
+
+#[cfg_attr(feature = "format", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LookaroundKind {
+    /// `(?=...)` or `(?!...)`
+    Ahead,
+    /// `(?<=...)` or `(?<!...)`
+    Behind,
 }
 
 #[cfg_attr(feature = "format", derive(Serialize, Deserialize))]
@@ -27,12 +44,29 @@ pub enum Node {
         min: u32,
         max: Option<u32>,
     },
-    LiteralCharacter(u8),
-    CharacterClass(Vec<CharacterClassItem>),
+    LiteralCharacter(char),
+    CharacterClass {
+        items: Vec<CharacterClassItem>,
+        negated: bool,
+    },
+    /// `\p{...}` or `\P{...}`
+    Property {
+        property: Property,
+        negated: bool,
+    },
     Anchor(Anchor),
     Or(Vec<Node>, Vec<Node>),
     Optional(Box<Node>),
     Group(GroupCaptureMode, Vec<Node>),
+    Lookaround {
+        kind: LookaroundKind,
+        negated: bool,
+        nodes: Vec<Node>,
+    },
+    /// `\1`
+    Backreference(usize),
+    /// `\k<name>`
+    NamedBackreference(Box<str>),
 }
 
 impl Node {
@@ -59,16 +93,22 @@ impl Node {
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MetaSequence {
     Digit,
+    NotDigit,
     Word,
+    NotWord,
     Whitespace,
+    NotWhitespace,
 }
 
 impl MetaSequence {
-    pub fn matches(self, c: u8) -> bool {
+    pub fn matches(self, c: char) -> bool {
         match self {
             MetaSequence::Digit => c.is_ascii_digit(),
-            MetaSequence::Word => c.is_ascii_alphanumeric() || c == b'_',
-            MetaSequence::Whitespace => c.is_ascii_whitespace(),
+            MetaSequence::NotDigit => !c.is_ascii_digit(),
+            MetaSequence::Word => unicode::is_word_character(c),
+            MetaSequence::NotWord => !unicode::is_word_character(c),
+            MetaSequence::Whitespace => unicode::is_whitespace(c),
+            MetaSequence::NotWhitespace => !unicode::is_whitespace(c),
         }
     }
 }
@@ -76,6 +116,12 @@ impl MetaSequence {
 #[cfg_attr(feature = "format", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Anchor {
+    /// `^`, which also matches after a line terminator with the `m` flag
     StartOfString,
+    /// `$`, which also matches before a line terminator with the `m` flag
     EndOfString,
+    /// `\b`
+    WordBoundary,
+    /// `\B`
+    NotWordBoundary,
 }
@@ -1,12 +1,21 @@
 use std::mem;
 
 use crate::error::Error;
-use crate::node::{Anchor, CharacterClassItem, GroupCaptureMode, MetaSequence, Node};
+use crate::flags::Flags;
+use crate::node::{Anchor, CharacterClassItem, GroupCaptureMode, LookaroundKind, MetaSequence, Node};
+use crate::unicode::Property;
 
 pub struct Parser<'a> {
     index: usize,
-    input: &'a [u8],
+    input: &'a [char],
     group_index: usize,
+    flags: Flags,
+    /// The total number of capturing groups in the pattern, used for telling apart backreferences and octal escapes
+    total_groups: usize,
+    /// Whether the pattern contains any named groups, in which case `\k` is always a named backreference
+    has_named_groups: bool,
+    group_names: Vec<(Box<str>, usize)>,
+    named_backreferences: Vec<Box<str>>,
 }
 
 #[derive(Debug, Clone)]
@@ -14,22 +23,61 @@ pub struct Parser<'a> {
 pub struct ParsedRegex {
     pub nodes: Vec<Node>,
     pub group_count: usize,
+    /// The names of named groups and their group index
+    pub group_names: Vec<(Box<str>, usize)>,
+}
+
+/// Counts the capturing groups in a pattern without parsing it, and checks if any of them are named.
+fn scan_groups(input: &[char]) -> (usize, bool) {
+    let mut count = 0;
+    let mut has_named_groups = false;
+    let mut in_class = false;
+    let mut chars = input.iter().enumerate();
+
+    while let Some((index, &c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '(' if !in_class => match input.get(index + 1..index + 3) {
+                Some(['?', '<']) => {
+                    if !matches!(input.get(index + 3), Some('=' | '!')) {
+                        count += 1;
+                        has_named_groups = true;
+                    }
+                }
+                Some(['?', _]) => {}
+                _ => count += 1,
+            },
+            _ => {}
+        }
+    }
+
+    (count, has_named_groups)
 }
 
 impl<'a> Parser<'a> {
-    pub fn new(input: &'a [u8]) -> Self {
+    pub fn new(input: &'a [char], flags: Flags) -> Self {
+        let (total_groups, has_named_groups) = scan_groups(input);
         Self {
             index: 0,
             input,
             group_index: 1, // 0 is the entire match
+            flags,
+            total_groups,
+            has_named_groups,
+            group_names: Vec::new(),
+            named_backreferences: Vec::new(),
         }
     }
 
-    /// Advances the index and returns the previous byte
-    pub fn next_byte(&mut self) -> Option<u8> {
-        let byte = self.input.get(self.index);
+    /// Advances the index and returns the previous character
+    pub fn next_char(&mut self) -> Option<char> {
+        let c = self.input.get(self.index);
         self.index += 1;
-        byte.copied()
+        c.copied()
     }
 
     pub fn advance(&mut self) {
@@ -40,10 +88,14 @@ impl<'a> Parser<'a> {
         self.index -= 1;
     }
 
-    pub fn current(&self) -> Option<u8> {
+    pub fn current(&self) -> Option<char> {
         self.input.get(self.index).copied()
     }
 
+    pub fn peek(&self) -> Option<char> {
+        self.input.get(self.index + 1).copied()
+    }
+
     pub fn is_eof(&self) -> bool {
         self.index >= self.input.len()
     }
@@ -51,14 +103,14 @@ impl<'a> Parser<'a> {
     pub fn parse_all(mut self) -> Result<ParsedRegex, Error> {
         let mut nodes = Vec::new();
         while !self.is_eof() {
-            if let Some(b'|') = self.current() {
+            if let Some('|') = self.current() {
                 self.advance();
 
                 let left = mem::take(&mut nodes);
                 let mut right = Vec::new();
 
                 while !self.is_eof() {
-                    if let Some(b'|') = self.current() {
+                    if let Some('|') = self.current() {
                         // handle in outer loop
                         break;
                     }
@@ -69,30 +121,47 @@ impl<'a> Parser<'a> {
                 nodes.push(self.parse_primary()?);
             }
         }
+
+        if let Some(name) = self
+            .named_backreferences
+            .iter()
+            .find(|name| !self.group_names.iter().any(|(n, _)| n == *name))
+        {
+            return Err(Error::UnknownGroupName(name.to_string()));
+        }
+
         Ok(ParsedRegex {
             nodes,
             group_count: self.group_index,
+            group_names: self.group_names,
         })
     }
 
     fn parse_primary(&mut self) -> Result<Node, Error> {
-        let mut node = match self.next_byte() {
-            Some(b'.') => Ok(Node::AnyCharacter),
-            Some(b'\\') => self.parse_escape(),
-            Some(b'[') => self.parse_character_class(),
-            Some(b'(') => self.parse_group(),
-            // Anchor = Return early as they cannot have quantifiers
-            Some(b'^') => return Ok(Node::Anchor(Anchor::StartOfString)),
-            Some(b'$') => return Ok(Node::Anchor(Anchor::EndOfString)),
+        let mut node = match self.next_char() {
+            Some('.') => Ok(Node::AnyCharacter),
+            Some('\\') => match self.parse_escape()? {
+                // Anchor = Return early as they cannot have quantifiers
+                node @ Node::Anchor(_) => return Ok(node),
+                node => Ok(node),
+            },
+            Some('[') => self.parse_character_class(),
+            Some('(') => match self.parse_group()? {
+                // Lookarounds are assertions and cannot have quantifiers
+                node @ Node::Lookaround { .. } => return Ok(node),
+                node => Ok(node),
+            },
+            Some('^') => return Ok(Node::Anchor(Anchor::StartOfString)),
+            Some('$') => return Ok(Node::Anchor(Anchor::EndOfString)),
             Some(other) => Ok(Node::LiteralCharacter(other)),
             None => Err(Error::UnexpectedEof),
         }?;
 
-        match self.next_byte() {
-            Some(b'+') => node = Node::unbounded_max_repetition(node, 1),
-            Some(b'*') => node = Node::unbounded_max_repetition(node, 0),
-            Some(b'?') => node = Node::optional(node),
-            Some(b'{') => node = self.parse_bounded_repetition(node)?,
+        match self.next_char() {
+            Some('+') => node = Node::unbounded_max_repetition(node, 1),
+            Some('*') => node = Node::unbounded_max_repetition(node, 0),
+            Some('?') => node = Node::optional(node),
+            Some('{') => node = self.parse_bounded_repetition(node)?,
             _ => self.back(), // back undos the advance in next()
         }
 
@@ -101,11 +170,11 @@ impl<'a> Parser<'a> {
 
     fn read_u32(&mut self) -> Result<u32, Error> {
         let mut number = 0u32;
-        while let Some(byte) = self.current() {
-            match byte {
-                b'0'..=b'9' => {
+        while let Some(c) = self.current() {
+            match c.to_digit(10) {
+                Some(digit) => {
                     number = number.checked_mul(10).ok_or(Error::Overflow)?;
-                    number = number.checked_add((byte - b'0') as u32).ok_or(Error::Overflow)?;
+                    number = number.checked_add(digit).ok_or(Error::Overflow)?;
 
                     self.advance();
                 }
@@ -118,10 +187,10 @@ impl<'a> Parser<'a> {
     fn parse_bounded_repetition(&mut self, node: Node) -> Result<Node, Error> {
         let min = self.read_u32()?;
         match self.current() {
-            Some(b',') => {
+            Some(',') => {
                 self.advance();
                 match self.current() {
-                    Some(b'}') => {
+                    Some('}') => {
                         self.advance();
                         Ok(Node::unbounded_max_repetition(node, min))
                     }
@@ -132,7 +201,7 @@ impl<'a> Parser<'a> {
                     }
                 }
             }
-            Some(b'}') => {
+            Some('}') => {
                 self.advance();
                 Ok(Node::repetition(node, min, min))
             }
@@ -142,72 +211,188 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_character_class(&mut self) -> Result<Node, Error> {
+        let negated = self.current() == Some('^');
+        if negated {
+            self.advance();
+        }
+
+        let items = if self.flags.contains(Flags::UNICODE_SETS) {
+            self.parse_class_set()?
+        } else {
+            self.parse_class_ranges()?
+        };
+
+        Ok(Node::CharacterClass { items, negated })
+    }
+
+    fn parse_class_ranges(&mut self) -> Result<Vec<CharacterClassItem>, Error> {
         let mut nodes = Vec::new();
 
         while !self.is_eof() {
             match self.current() {
-                Some(b']') => {
+                Some(']') => {
                     self.advance();
                     break;
                 }
-                Some(b'-') => {
+                Some('-') => {
                     self.advance();
                     match nodes.last() {
                         Some(&CharacterClassItem::Node(Node::LiteralCharacter(start)))
-                            if start.is_ascii_alphanumeric() =>
+                            if self.current().is_some_and(|c| c != ']') =>
                         {
-                            let end = self.next_byte().ok_or(Error::UnexpectedEof)?;
-                            nodes.pop();
-                            nodes.push(CharacterClassItem::Range(start, end));
+                            match self.parse_class_atom()? {
+                                Node::LiteralCharacter(end) => {
+                                    if start > end {
+                                        return Err(Error::RangeOutOfOrder);
+                                    }
+                                    nodes.pop();
+                                    nodes.push(CharacterClassItem::Range(start, end));
+                                }
+                                other => {
+                                    // Something like [a-\d], which is a union of the three
+                                    nodes.push(CharacterClassItem::Node(Node::LiteralCharacter('-')));
+                                    nodes.push(CharacterClassItem::Node(other));
+                                }
+                            }
                         }
-                        _ => nodes.push(CharacterClassItem::Node(Node::LiteralCharacter(b'-'))),
+                        _ => nodes.push(CharacterClassItem::Node(Node::LiteralCharacter('-'))),
                     }
                 }
-                Some(b'\\') => {
-                    self.advance();
-                    nodes.push(CharacterClassItem::Node(self.parse_escape()?));
+                Some(_) => {
+                    let node = self.parse_class_atom()?;
+                    nodes.push(CharacterClassItem::Node(node));
                 }
-                Some(other) => {
+                None => break,
+            }
+        }
+
+        Ok(nodes)
+    }
+
+    /// Parses a single character or escape in a character class
+    fn parse_class_atom(&mut self) -> Result<Node, Error> {
+        match self.next_char() {
+            Some('\\') => self.parse_class_escape(),
+            Some(other) => Ok(Node::LiteralCharacter(other)),
+            None => Err(Error::UnexpectedEof),
+        }
+    }
+
+    /// Parses the contents of a character class in `v` mode, which can contain nested classes and set operations
+    fn parse_class_set(&mut self) -> Result<Vec<CharacterClassItem>, Error> {
+        let mut items = Vec::new();
+
+        loop {
+            match self.current() {
+                Some(']') => {
                     self.advance();
-                    nodes.push(CharacterClassItem::Node(Node::LiteralCharacter(other)))
+                    break;
                 }
-                None => break,
+                Some(_) => {
+                    let mut item = self.parse_class_set_operand()?;
+
+                    loop {
+                        match (self.current(), self.peek()) {
+                            (Some('&'), Some('&')) => {
+                                self.index += 2;
+                                let right = self.parse_class_set_operand()?;
+                                item = CharacterClassItem::Intersection(Box::new(item), Box::new(right));
+                            }
+                            (Some('-'), Some('-')) => {
+                                self.index += 2;
+                                let right = self.parse_class_set_operand()?;
+                                item = CharacterClassItem::Subtraction(Box::new(item), Box::new(right));
+                            }
+                            _ => break,
+                        }
+                    }
+
+                    items.push(item);
+                }
+                None => return Err(Error::UnexpectedEof),
             }
         }
 
-        Ok(Node::CharacterClass(nodes))
+        Ok(items)
+    }
+
+    fn parse_class_set_operand(&mut self) -> Result<CharacterClassItem, Error> {
+        let start = match self.current() {
+            Some('[') => {
+                self.advance();
+                return Ok(CharacterClassItem::Node(self.parse_character_class()?));
+            }
+            _ => self.parse_class_atom()?,
+        };
+
+        match (start, self.current(), self.peek()) {
+            (Node::LiteralCharacter(start), Some('-'), Some(next)) if next != '-' && next != ']' => {
+                self.advance();
+                match self.parse_class_atom()? {
+                    Node::LiteralCharacter(end) if start <= end => Ok(CharacterClassItem::Range(start, end)),
+                    Node::LiteralCharacter(_) => Err(Error::RangeOutOfOrder),
+                    _ => Err(Error::UnexpectedChar(next)),
+                }
+            }
+            (start, ..) => Ok(CharacterClassItem::Node(start)),
+        }
     }
 
     fn parse_group(&mut self) -> Result<Node, Error> {
-        let mut nodes = Vec::new();
-        // ?: = non-capturing group
-        let capture_mode = if self.current() == Some(b'?') {
+        enum GroupKind {
+            Group(GroupCaptureMode),
+            Lookaround(LookaroundKind, bool),
+        }
+
+        let kind = if self.current() == Some('?') {
             self.advance();
-            if self.current() == Some(b':') {
-                self.advance();
-                GroupCaptureMode::None
-            } else {
-                self.group_index += 1;
-                GroupCaptureMode::Id(self.group_index - 1)
+            match self.next_char() {
+                // ?: = non-capturing group
+                Some(':') => GroupKind::Group(GroupCaptureMode::None),
+                Some('=') => GroupKind::Lookaround(LookaroundKind::Ahead, false),
+                Some('!') => GroupKind::Lookaround(LookaroundKind::Ahead, true),
+                Some('<') if self.current() == Some('=') => {
+                    self.advance();
+                    GroupKind::Lookaround(LookaroundKind::Behind, false)
+                }
+                Some('<') if self.current() == Some('!') => {
+                    self.advance();
+                    GroupKind::Lookaround(LookaroundKind::Behind, true)
+                }
+                Some('<') => {
+                    let name = self.parse_group_name()?;
+                    if self.group_names.iter().any(|(n, _)| *n == name) {
+                        return Err(Error::DuplicateGroupName(name.into()));
+                    }
+
+                    let id = self.group_index;
+                    self.group_index += 1;
+                    self.group_names.push((name.clone(), id));
+                    GroupKind::Group(GroupCaptureMode::Named { id, name })
+                }
+                Some(other) => return Err(Error::UnexpectedChar(other)),
+                None => return Err(Error::UnexpectedEof),
             }
         } else {
+            let id = self.group_index;
             self.group_index += 1;
-            GroupCaptureMode::Id(self.group_index - 1)
+            GroupKind::Group(GroupCaptureMode::Id(id))
         };
 
+        let mut nodes = Vec::new();
         while !self.is_eof() {
             match self.current() {
-                Some(b')') => {
+                Some(')') => {
                     self.advance();
                     break;
                 }
-                Some(b'|') => {
+                Some('|') => {
                     self.advance();
                     let left = mem::take(&mut nodes);
                     let mut right = Vec::new();
 
                     while !self.is_eof() {
-                        if let Some(b')' | b'|') = self.current() {
+                        if let Some(')' | '|') = self.current() {
                             // handle in outer loop
                             break;
                         }
@@ -219,16 +404,210 @@ impl<'a> Parser<'a> {
             }
         }
 
-        Ok(Node::Group(capture_mode, nodes))
+        Ok(match kind {
+            GroupKind::Group(mode) => Node::Group(mode, nodes),
+            GroupKind::Lookaround(kind, negated) => Node::Lookaround { kind, negated, nodes },
+        })
+    }
+
+    /// Parses the name of a named group or backreference, assuming the `<` has already been consumed
+    fn parse_group_name(&mut self) -> Result<Box<str>, Error> {
+        let mut name = String::new();
+        loop {
+            match self.next_char() {
+                Some('>') if !name.is_empty() => return Ok(name.into()),
+                Some(c) if c == '_' || c == '$' || c.is_alphabetic() || (!name.is_empty() && c.is_alphanumeric()) => {
+                    name.push(c)
+                }
+                Some(other) => return Err(Error::UnexpectedChar(other)),
+                None => return Err(Error::UnexpectedEof),
+            }
+        }
+    }
+
+    /// Parses a decimal escape outside of a character class, which is either a backreference or a legacy octal escape
+    fn parse_decimal_escape(&mut self, first: char) -> Result<Node, Error> {
+        let start = self.index;
+        let mut number = first.to_digit(10).unwrap() as usize;
+        while let Some(digit) = self.current().and_then(|c| c.to_digit(10)) {
+            number = number.saturating_mul(10).saturating_add(digit as usize);
+            self.advance();
+        }
+
+        if number <= self.total_groups {
+            return Ok(Node::Backreference(number));
+        }
+        if self.flags.is_unicode() {
+            return Err(Error::InvalidBackreference);
+        }
+
+        self.index = start;
+        Ok(Node::LiteralCharacter(self.parse_octal_escape(first)))
+    }
+
+    /// Parses a legacy octal escape like `\12`, or returns the digit itself for `\8` and `\9`
+    fn parse_octal_escape(&mut self, first: char) -> char {
+        let Some(mut value) = first.to_digit(8) else {
+            return first;
+        };
+
+        for _ in 0..2 {
+            match self.current().and_then(|c| c.to_digit(8)) {
+                Some(digit) if value * 8 + digit <= 0o377 => {
+                    value = value * 8 + digit;
+                    self.advance();
+                }
+                _ => break,
+            }
+        }
+
+        char::from_u32(value).unwrap()
     }
 
     fn parse_escape(&mut self) -> Result<Node, Error> {
-        match self.next_byte() {
-            Some(b'd') => Ok(Node::MetaSequence(MetaSequence::Digit)),
-            Some(b'w') => Ok(Node::MetaSequence(MetaSequence::Word)),
-            Some(b's') => Ok(Node::MetaSequence(MetaSequence::Whitespace)),
-            Some(other) => Ok(Node::LiteralCharacter(other)),
+        match self.next_char() {
+            Some('b') => Ok(Node::Anchor(Anchor::WordBoundary)),
+            Some('B') => Ok(Node::Anchor(Anchor::NotWordBoundary)),
+            Some(c @ '1'..='9') => self.parse_decimal_escape(c),
+            Some('k') if self.has_named_groups || self.flags.is_unicode() => {
+                if self.next_char() != Some('<') {
+                    return Err(Error::InvalidBackreference);
+                }
+                let name = self.parse_group_name()?;
+                self.named_backreferences.push(name.clone());
+                Ok(Node::NamedBackreference(name))
+            }
+            Some(other) => self.parse_escape_common(other),
+            None => Err(Error::UnexpectedEof),
+        }
+    }
+
+    fn parse_class_escape(&mut self) -> Result<Node, Error> {
+        match self.next_char() {
+            Some('b') => Ok(Node::LiteralCharacter('\u{8}')),
+            Some('-') => Ok(Node::LiteralCharacter('-')),
+            Some(c @ '1'..='9') if !self.flags.is_unicode() => Ok(Node::LiteralCharacter(self.parse_octal_escape(c))),
+            Some(other) => self.parse_escape_common(other),
             None => Err(Error::UnexpectedEof),
         }
     }
+
+    /// Parses the escapes that have the same meaning inside and outside of character classes
+    fn parse_escape_common(&mut self, c: char) -> Result<Node, Error> {
+        let c = match c {
+            'd' => return Ok(Node::MetaSequence(MetaSequence::Digit)),
+            'D' => return Ok(Node::MetaSequence(MetaSequence::NotDigit)),
+            'w' => return Ok(Node::MetaSequence(MetaSequence::Word)),
+            'W' => return Ok(Node::MetaSequence(MetaSequence::NotWord)),
+            's' => return Ok(Node::MetaSequence(MetaSequence::Whitespace)),
+            'S' => return Ok(Node::MetaSequence(MetaSequence::NotWhitespace)),
+            'p' | 'P' if self.flags.is_unicode() => return self.parse_property(c == 'P'),
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'v' => '\u{b}',
+            'f' => '\u{c}',
+            '0' if !self.current().is_some_and(|c| c.is_ascii_digit()) => '\0',
+            'c' => match self.current() {
+                Some(letter) if letter.is_ascii_alphabetic() => {
+                    self.advance();
+                    char::from(letter as u8 % 32)
+                }
+                _ => {
+                    // Not a control escape, so this is a literal backslash followed by a c
+                    self.back();
+                    '\\'
+                }
+            },
+            'x' => match self.read_hex(2) {
+                Some(value) => char::from_u32(value).unwrap(),
+                None if self.flags.is_unicode() => return Err(Error::InvalidUnicodeEscape),
+                None => 'x',
+            },
+            'u' => self.parse_unicode_escape()?,
+            other => other,
+        };
+
+        Ok(Node::LiteralCharacter(c))
+    }
+
+    /// Reads exactly `digits` hex digits, or nothing if there aren't enough
+    fn read_hex(&mut self, digits: usize) -> Option<u32> {
+        let hex = self.input.get(self.index..self.index + digits)?;
+        let value = hex
+            .iter()
+            .try_fold(0, |acc, c| c.to_digit(16).map(|digit| acc * 16 + digit))?;
+        self.index += digits;
+        Some(value)
+    }
+
+    /// Parses `\uXXXX`, `\u{X...}` or a surrogate pair of `\uXXXX` escapes
+    fn parse_unicode_escape(&mut self) -> Result<char, Error> {
+        if self.flags.is_unicode() && self.current() == Some('{') {
+            self.advance();
+            let mut value = 0u32;
+            loop {
+                match self.next_char() {
+                    Some('}') => break,
+                    Some(c) => {
+                        let digit = c.to_digit(16).ok_or(Error::InvalidUnicodeEscape)?;
+                        value = value
+                            .checked_mul(16)
+                            .and_then(|v| v.checked_add(digit))
+                            .ok_or(Error::InvalidUnicodeEscape)?;
+                    }
+                    None => return Err(Error::UnexpectedEof),
+                }
+            }
+            return char::from_u32(value).ok_or(Error::InvalidUnicodeEscape);
+        }
+
+        let Some(value) = self.read_hex(4) else {
+            if self.flags.is_unicode() {
+                return Err(Error::InvalidUnicodeEscape);
+            }
+            return Ok('u');
+        };
+
+        if (0xd800..0xdc00).contains(&value) && self.input.get(self.index..self.index + 2) == Some(&['\\', 'u']) {
+            let before = self.index;
+            self.index += 2;
+            match self.read_hex(4) {
+                Some(low @ 0xdc00..0xe000) => {
+                    let c = 0x10000 + ((value - 0xd800) << 10) + (low - 0xdc00);
+                    return Ok(char::from_u32(c).unwrap());
+                }
+                _ => self.index = before,
+            }
+        }
+
+        // Strings cannot contain lone surrogates, so they are replaced just like when they appear in strings
+        Ok(char::from_u32(value).unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    /// Parses the `{name}` or `{name=value}` part of a `\p` escape
+    fn parse_property(&mut self, negated: bool) -> Result<Node, Error> {
+        if self.next_char() != Some('{') {
+            return Err(Error::UnknownProperty(String::new()));
+        }
+
+        let mut name = String::new();
+        let mut value = None::<String>;
+        loop {
+            match self.next_char() {
+                Some('}') => break,
+                Some('=') if value.is_none() => value = Some(String::new()),
+                Some(c) => value.as_mut().unwrap_or(&mut name).push(c),
+                None => return Err(Error::UnexpectedEof),
+            }
+        }
+
+        match Property::lookup(&name, value.as_deref()) {
+            Some(property) => Ok(Node::Property { property, negated }),
+            None => Err(Error::UnknownProperty(match value {
+                Some(value) => format!("{name}={value}"),
+                None => name,
+            })),
+        }
+    }
 }
@@ -0,0 +1,211 @@
+use unicode_general_category::{GeneralCategory, get_general_category};
+use unicode_script::{Script, UnicodeScript};
+
+/// A property matched by `\p{...}`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "format", derive(serde::Serialize, serde::Deserialize))]
+pub enum Property {
+    Any,
+    Ascii,
+    AsciiHexDigit,
+    Alphabetic,
+    Lowercase,
+    Uppercase,
+    WhiteSpace,
+    /// A set of general categories, as a bitmask of the indices into [`GENERAL_CATEGORIES`]
+    GeneralCategory(u32),
+    /// The four letter short name of a script
+    Script {
+        name: [u8; 4],
+        extensions: bool,
+    },
+}
+
+/// All general categories with their short and long name
+const GENERAL_CATEGORIES: [(GeneralCategory, &str, &str); 30] = [
+    (GeneralCategory::UppercaseLetter, "Lu", "Uppercase_Letter"),
+    (GeneralCategory::LowercaseLetter, "Ll", "Lowercase_Letter"),
+    (GeneralCategory::TitlecaseLetter, "Lt", "Titlecase_Letter"),
+    (GeneralCategory::ModifierLetter, "Lm", "Modifier_Letter"),
+    (GeneralCategory::OtherLetter, "Lo", "Other_Letter"),
+    (GeneralCategory::NonspacingMark, "Mn", "Nonspacing_Mark"),
+    (GeneralCategory::SpacingMark, "Mc", "Spacing_Mark"),
+    (GeneralCategory::EnclosingMark, "Me", "Enclosing_Mark"),
+    (GeneralCategory::DecimalNumber, "Nd", "Decimal_Number"),
+    (GeneralCategory::LetterNumber, "Nl", "Letter_Number"),
+    (GeneralCategory::OtherNumber, "No", "Other_Number"),
+    (GeneralCategory::ConnectorPunctuation, "Pc", "Connector_Punctuation"),
+    (GeneralCategory::DashPunctuation, "Pd", "Dash_Punctuation"),
+    (GeneralCategory::OpenPunctuation, "Ps", "Open_Punctuation"),
+    (GeneralCategory::ClosePunctuation, "Pe", "Close_Punctuation"),
+    (GeneralCategory::InitialPunctuation, "Pi", "Initial_Punctuation"),
+    (GeneralCategory::FinalPunctuation, "Pf", "Final_Punctuation"),
+    (GeneralCategory::OtherPunctuation, "Po", "Other_Punctuation"),
+    (GeneralCategory::MathSymbol, "Sm", "Math_Symbol"),
+    (GeneralCategory::CurrencySymbol, "Sc", "Currency_Symbol"),
+    (GeneralCategory::ModifierSymbol, "Sk", "Modifier_Symbol"),
+    (GeneralCategory::OtherSymbol, "So", "Other_Symbol"),
+    (GeneralCategory::SpaceSeparator, "Zs", "Space_Separator"),
+    (GeneralCategory::LineSeparator, "Zl", "Line_Separator"),
+    (GeneralCategory::ParagraphSeparator, "Zp", "Paragraph_Separator"),
+    (GeneralCategory::Control, "Cc", "Control"),
+    (GeneralCategory::Format, "Cf", "Format"),
+    (GeneralCategory::Surrogate, "Cs", "Surrogate"),
+    (GeneralCategory::PrivateUse, "Co", "Private_Use"),
+    (GeneralCategory::Unassigned, "Cn", "Unassigned"),
+];
+
+/// Groups of general categories, identified by the first letter of their short names
+const GENERAL_CATEGORY_GROUPS: [(&str, &str, &str); 8] = [
+    ("L", "Letter", "L"),
+    ("LC", "Cased_Letter", "LuLlLt"),
+    ("M", "Mark", "M"),
+    ("N", "Number", "N"),
+    ("P", "Punctuation", "P"),
+    ("S", "Symbol", "S"),
+    ("Z", "Separator", "Z"),
+    ("C", "Other", "C"),
+];
+
+/// Property aliases that are not the short or long name
+const GENERAL_CATEGORY_ALIASES: [(&str, &str); 3] = [("digit", "Nd"), ("punct", "P"), ("Combining_Mark", "M")];
+
+fn general_category_mask(name: &str) -> Option<u32> {
+    let name = GENERAL_CATEGORY_ALIASES
+        .iter()
+        .find(|&&(alias, _)| alias == name)
+        .map_or(name, |&(_, short)| short);
+
+    if let Some(index) = GENERAL_CATEGORIES
+        .iter()
+        .position(|&(_, short, long)| short == name || long == name)
+    {
+        return Some(1 << index);
+    }
+
+    let &(_, _, members) = GENERAL_CATEGORY_GROUPS
+        .iter()
+        .find(|&&(short, long, _)| short == name || long == name)?;
+
+    let mask = GENERAL_CATEGORIES
+        .iter()
+        .enumerate()
+        .filter(|&(_, &(_, short, _))| {
+            // Members are either a single letter prefix or a list of two letter short names
+            if members.len() == 1 {
+                short.starts_with(members)
+            } else {
+                members.as_bytes().chunks(2).any(|m| m == short.as_bytes())
+            }
+        })
+        .fold(0, |mask, (index, _)| mask | (1 << index));
+
+    Some(mask)
+}
+
+fn script(name: &str, extensions: bool) -> Option<Property> {
+    let script = Script::from_full_name(name).or_else(|| Script::from_short_name(name))?;
+    let name = script.short_name().as_bytes().try_into().ok()?;
+    Some(Property::Script { name, extensions })
+}
+
+impl Property {
+    /// Looks up a property by the name and optional value of a `\p{name=value}` escape
+    pub fn lookup(name: &str, value: Option<&str>) -> Option<Self> {
+        match (name, value) {
+            ("General_Category" | "gc", Some(value)) => general_category_mask(value).map(Self::GeneralCategory),
+            ("Script" | "sc", Some(value)) => script(value, false),
+            ("Script_Extensions" | "scx", Some(value)) => script(value, true),
+            (_, Some(_)) => None,
+            ("Any", None) => Some(Self::Any),
+            ("ASCII", None) => Some(Self::Ascii),
+            ("ASCII_Hex_Digit" | "AHex", None) => Some(Self::AsciiHexDigit),
+            ("Alphabetic" | "Alpha", None) => Some(Self::Alphabetic),
+            ("Lowercase" | "Lower", None) => Some(Self::Lowercase),
+            ("Uppercase" | "Upper", None) => Some(Self::Uppercase),
+            ("White_Space" | "space", None) => Some(Self::WhiteSpace),
+            (name, None) => general_category_mask(name).map(Self::GeneralCategory),
+        }
+    }
+
+    pub fn matches(self, c: char) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Ascii => c.is_ascii(),
+            Self::AsciiHexDigit => c.is_ascii_hexdigit(),
+            Self::Alphabetic => c.is_alphabetic(),
+            Self::Lowercase => c.is_lowercase(),
+            Self::Uppercase => c.is_uppercase(),
+            Self::WhiteSpace => c.is_whitespace(),
+            Self::GeneralCategory(mask) => {
+                let category = get_general_category(c);
+                GENERAL_CATEGORIES
+                    .iter()
+                    .position(|&(gc, ..)| gc == category)
+                    .is_some_and(|index| mask & (1 << index) != 0)
+            }
+            Self::Script { name, extensions } => {
+                let Some(script) = std::str::from_utf8(&name).ok().and_then(Script::from_short_name) else {
+                    return false;
+                };
+                if extensions {
+                    c.script_extension().contains_script(script)
+                } else {
+                    c.script() == script
+                }
+            }
+        }
+    }
+}
+
+pub fn is_line_terminator(c: char) -> bool {
+    matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+/// Checks if the character is matched by `\s`
+pub fn is_whitespace(c: char) -> bool {
+    let space_separator = matches!(c, ' ' | '\u{a0}' | '\u{1680}' | '\u{2000}'..='\u{200a}')
+        || matches!(c, '\u{202f}' | '\u{205f}' | '\u{3000}');
+    space_separator || matches!(c, '\t' | '\u{b}' | '\u{c}' | '\u{feff}') || is_line_terminator(c)
+}
+
+/// Checks if the character is matched by `\w`
+pub fn is_word_character(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn single_char(mut iter: impl Iterator<Item = char>) -> Option<char> {
+    let c = iter.next()?;
+    iter.next().is_none().then_some(c)
+}
+
+/// Implements the Canonicalize abstract operation used for case-insensitive matching.
+///
+/// In unicode mode this approximates simple case folding by lowercasing, otherwise characters are uppercased,
+/// except when that would map a non-ASCII character to an ASCII one.
+pub fn canonicalize(c: char, unicode: bool) -> char {
+    if unicode {
+        single_char(c.to_lowercase()).unwrap_or(c)
+    } else {
+        match single_char(c.to_uppercase()) {
+            Some(upper) if c.is_ascii() || !upper.is_ascii() => upper,
+            _ => c,
+        }
+    }
+}
+
+/// Returns the characters that canonicalize to the same character as `c`, including `c` itself
+pub fn case_variants(c: char, unicode: bool) -> impl Iterator<Item = char> {
+    let canonical = canonicalize(c, unicode);
+    let lower = single_char(c.to_lowercase());
+    let upper = single_char(c.to_uppercase());
+
+    [
+        Some(c),
+        lower.filter(|&l| l != c),
+        upper.filter(|&u| u != c && Some(u) != lower),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(move |&v| v == c || canonicalize(v, unicode) == canonical)
+}
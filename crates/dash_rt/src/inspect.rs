@@ -1,3 +1,4 @@
+use std::cell::Cell;
 use std::fmt::Write;
 
 use dash_middle::interner::{Symbol, sym};
@@ -82,15 +83,15 @@ fn inspect_array_into(
     Ok(())
 }
 
-fn inspect_arraybuffer_into(sc: &LocalScope<'_>, arraybuffer: &ArrayBuffer, constructor: Symbol, out: &mut String) {
-    write!(out, "{}({}) {{ ", sc.interner.resolve(constructor), arraybuffer.len()).unwrap();
-    for (i, byte) in arraybuffer.storage().iter().enumerate().take(32) {
+fn inspect_arraybuffer_into(sc: &LocalScope<'_>, storage: &[Cell<u8>], constructor: Symbol, out: &mut String) {
+    write!(out, "{}({}) {{ ", sc.interner.resolve(constructor), storage.len()).unwrap();
+    for (i, byte) in storage.iter().enumerate().take(32) {
         if i > 0 {
             *out += " ";
         }
         write!(out, "{:02x}", byte.get()).unwrap();
     }
-    if arraybuffer.len() > 32 {
+    if storage.len() > 32 {
         *out += " ...";
     }
     *out += " }";
@@ -131,6 +132,10 @@ fn inspect_inner_into(
         ValueKind::Symbol(symbol) => colored(out, options, YELLOW, |s| {
             *s += &*("@@".to_owned() + scope.interner.resolve(symbol.sym()));
         }),
+        ValueKind::BigInt(bigint) => colored(out, options, YELLOW, |s| {
+            *s += &bigint.get(scope).to_string();
+            *s += "n";
+        }),
         ValueKind::Object(object) => {
             let constructor = object
                 .get_property(sym::constructor.to_key(scope), scope)
@@ -154,11 +159,12 @@ fn inspect_inner_into(
             }
 
             // ArrayBuffer and views
-            if let Some(arraybuffer) = object
+            if let Some(storage) = object
                 .extract::<ArrayBuffer>(scope)
-                .or_else(|| object.extract::<TypedArray>(scope).map(|t| t.arraybuffer(scope)))
+                .map(ArrayBuffer::storage)
+                .or_else(|| object.extract::<TypedArray>(scope).map(|t| t.storage(scope)))
             {
-                inspect_arraybuffer_into(scope, arraybuffer, constructor_name, out);
+                inspect_arraybuffer_into(scope, storage, constructor_name, out);
                 return Ok(());
             }
 
@@ -40,6 +40,11 @@ impl Runtime {
             params = params.set_math_random_callback(random_callback);
         }
 
+        #[cfg(feature = "timezone")]
+        {
+            params = params.set_timezone_offset_callback(timezone_offset_callback);
+        }
+
         params = params
             .set_time_millis_callback(time_callback)
             .set_unhandled_task_exception_callback(|scope, value, cause| {
@@ -157,6 +162,17 @@ fn time_callback(_: &mut Vm) -> Result<u64, Unrooted> {
         .as_millis() as u64)
 }
 
+#[cfg(feature = "timezone")]
+fn timezone_offset_callback(_: &mut Vm, utc_millis: i64) -> Result<i64, Unrooted> {
+    use chrono::{Local, Offset, TimeZone};
+
+    let offset = Local
+        .timestamp_millis_opt(utc_millis)
+        .single()
+        .map_or(0, |time| time.offset().fix().local_minus_utc());
+    Ok(i64::from(offset) * 1000)
+}
+
 fn import_callback(vm: &mut Vm, import_ty: StaticImportKind, path: JsString) -> Result<Unrooted, Unrooted> {
     let mut sc = vm.scope();
 
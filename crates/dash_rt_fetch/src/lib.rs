@@ -173,9 +173,12 @@ impl Object for HttpResponse {
         get_property,
         get_property_descriptor,
         set_property,
+        define_property,
         delete_property,
         set_prototype,
         get_prototype,
+        prevent_extensions,
+        is_extensible,
         apply,
         own_keys
     );
@@ -63,12 +63,7 @@ fn write_file(cx: CallContext) -> Result<Value, Value> {
     if let Some(array) = buf.extract::<TypedArray>(cx.scope) {
         let path = PathBuf::from(path.res(cx.scope));
 
-        let storage = array
-            .arraybuffer(cx.scope)
-            .storage()
-            .iter()
-            .map(Cell::get)
-            .collect::<Vec<_>>();
+        let storage = array.storage(cx.scope).iter().map(Cell::get).collect::<Vec<_>>();
 
         wrap_async(cx, tokio::fs::write(path, storage), |sc, res| match res {
             Ok(()) => Ok(Value::undefined()),
@@ -68,7 +68,7 @@ fn write_file_sync(cx: CallContext) -> Result<Value, Value> {
     let path = path.to_js_string(cx.scope)?;
     if let Some(array) = buf.extract::<TypedArray>(cx.scope) {
         let path = Path::new(path.res(cx.scope));
-        let storage = array.arraybuffer(cx.scope).storage();
+        let storage = array.storage(cx.scope);
         // SAFETY: Cell<u8> has the same layout as u8
         let view = unsafe { slice::from_raw_parts(storage.as_ptr().cast::<u8>(), storage.len()) };
 
@@ -159,9 +159,12 @@ impl Object for HttpContext {
         get_property,
         get_property_descriptor,
         set_property,
+        define_property,
         delete_property,
         set_prototype,
         get_prototype,
+        prevent_extensions,
+        is_extensible,
         apply,
         own_keys
     );
@@ -38,16 +38,16 @@ impl Object for TcpListenerConstructor {
         _key: PropertyKey,
         _value: dash_vm::value::object::PropertyValue,
         _sc: &mut dash_vm::localscope::LocalScope,
-    ) -> Result<(), dash_vm::value::Value> {
-        Ok(())
+    ) -> Result<bool, dash_vm::value::Value> {
+        Ok(false)
     }
 
     fn delete_property(
         &self,
         _key: PropertyKey,
         _sc: &mut dash_vm::localscope::LocalScope,
-    ) -> Result<dash_vm::value::Unrooted, dash_vm::value::Value> {
-        Ok(Unrooted::new(Value::undefined()))
+    ) -> Result<bool, dash_vm::value::Value> {
+        Ok(true)
     }
 
     fn set_prototype(
@@ -197,9 +197,12 @@ impl Object for TcpListenerHandle {
         object,
         get_own_property_descriptor,
         set_property,
+        define_property,
         delete_property,
         set_prototype,
         get_prototype,
+        prevent_extensions,
+        is_extensible,
         apply,
         own_keys
     );
@@ -273,9 +276,12 @@ impl Object for TcpStreamHandle {
         object,
         get_own_property_descriptor,
         set_property,
+        define_property,
         delete_property,
         set_prototype,
         get_prototype,
+        prevent_extensions,
+        is_extensible,
         apply,
         own_keys
     );
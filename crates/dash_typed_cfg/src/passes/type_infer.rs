@@ -125,6 +125,7 @@ impl<Q: TypeInferQuery> TypeInferCtxt<'_, '_, Q> {
                 }
                 Instruction::String
                 | Instruction::Regex
+                | Instruction::BigInt
                 | Instruction::Null
                 | Instruction::Undefined
                 | Instruction::Function => {
@@ -62,6 +62,7 @@ impl<'a> DecodeCtxt<'a> {
             | Instruction::Number
             | Instruction::String
             | Instruction::Regex
+            | Instruction::BigInt
             | Instruction::Null
             | Instruction::Undefined
             | Instruction::Function => drop(self.next_byte()),
@@ -139,7 +139,6 @@ mod extract {
 
     use crate::gc::ObjectId;
     use crate::value::object::PropertyValue;
-    use crate::value::ops::conversions::ValueConversion;
     use crate::value::propertykey::{PropertyKey, ToPropertyKey};
     use crate::value::string::JsString;
     use crate::value::{Unpack, Unrooted, Value, ValueKind};
@@ -245,6 +244,7 @@ mod extract {
         Static { key: PropertyKey, value: PropertyValue },
         Getter { key: PropertyKey, value: ObjectId },
         Setter { key: PropertyKey, value: ObjectId },
+        Prototype(Value),
         Spread(Value),
     }
 
@@ -389,12 +389,16 @@ mod extract {
                     }
                 }
                 ObjectMemberKind::Static => {
-                    let key = extract::<IdentW>(cx).0;
+                    let key = extract::<IdentW>(cx).0.to_key(&mut cx.scope);
                     let value = extract_front(seq, cx);
 
-                    Self::Static {
-                        key: key.to_key(&mut cx.scope),
-                        value: PropertyValue::static_default(value),
+                    if key == PropertyKey::PROTO {
+                        Self::Prototype(value)
+                    } else {
+                        Self::Static {
+                            key,
+                            value: PropertyValue::static_default(value),
+                        }
                     }
                 }
                 ObjectMemberKind::Dynamic => {
@@ -432,7 +436,7 @@ mod extract {
     #[derive(Debug)]
     pub enum ArrayElement {
         Single(Value),
-        Spread(Value, usize),
+        Spread(Value),
         Hole(u32),
     }
 
@@ -442,12 +446,7 @@ mod extract {
         fn extract_front<U>(seq: &mut ForwardSequence<U>, cx: &mut DispatchContext<'_>) -> Result<Self, Self::Error> {
             Ok(match extract::<ArrayMemberKind>(cx) {
                 ArrayMemberKind::Item => ArrayElement::Single(extract_front(seq, cx)),
-                ArrayMemberKind::Spread => {
-                    let value: Value = extract_front(seq, cx);
-                    // TODO: make this work for array-like values, not just arrays, by calling @@iterator on it
-                    let len = value.length_of_array_like(&mut cx.scope)?;
-                    ArrayElement::Spread(value, len)
-                }
+                ArrayMemberKind::Spread => ArrayElement::Spread(extract_front(seq, cx)),
                 ArrayMemberKind::Empty => {
                     let count = cx.fetch_and_inc_ip();
                     ArrayElement::Hole(count.into())
@@ -529,7 +528,7 @@ mod extract {
 }
 
 mod handlers {
-    use dash_middle::compiler::constant::{BooleanConstant, FunctionConstant, RegexConstant};
+    use dash_middle::compiler::constant::{BooleanConstant, FunctionConstant, RegexConstant, TemplateConstant};
     use dash_middle::compiler::external::{External, PossiblyExternalId};
     use dash_middle::compiler::instruction::{AssignKind, IntrinsicOperation};
     use dash_middle::compiler::{FunctionCallKind, StaticImportKind};
@@ -539,26 +538,37 @@ mod handlers {
     use handlers::extract::{ForwardSequence, FrontIteratorWith, extract};
     use if_chain::if_chain;
     use smallvec::SmallVec;
-    use std::ops::{Add, ControlFlow, Div, Mul, Rem, Sub};
+    use std::convert::Infallible;
+    use std::ops::ControlFlow::{self, Continue};
+    use std::ops::{Add, Div, Mul, Rem, Sub};
     use std::rc::Rc;
 
     use crate::frame::{FrameState, Ip, Sp, TryBlock};
+    use crate::gc::ObjectId;
+    use crate::js_std::array::for_each_iterable_element;
     use crate::throw;
     use crate::util::unlikely;
     use crate::value::array::table::ArrayTable;
     use crate::value::array::{Array, ArrayIterator};
+    use crate::value::async_iterator::AsyncFromSyncIterator;
+    use crate::value::bigint::{JsBigInt, parse_bigint_literal};
     use crate::value::function::args::CallArgs;
     use crate::value::function::r#async::AsyncFunction;
+    use crate::value::function::async_generator::AsyncGeneratorFunction;
     use crate::value::function::closure::Closure;
     use crate::value::function::generator::GeneratorFunction;
     use crate::value::function::user::UserFunction;
     use crate::value::function::{Function, FunctionKind, adjust_stack_from_flat_call, this_for_new_target};
-    use crate::value::object::{Object, OrdObject, OwnKeysMode, PropertyValue, PropertyValueKind, This, ThisKind};
+    use crate::value::object::ordinary::{reject_set_property, set_with_receiver};
+    use crate::value::object::{
+        IntegrityLevel, Object, OrdObject, OwnKeysMode, PropertyValue, PropertyValueKind, This, ThisKind,
+    };
     use crate::value::ops::conversions::ValueConversion;
     use crate::value::ops::equality;
     use crate::value::primitive::Number;
     use crate::value::propertykey::{PropertyKey, ToPropertyKey};
     use crate::value::regex::RegExp;
+    use crate::value::root_ext::RootErrExt;
     use crate::value::{Unpack, ValueKind};
 
     use self::extract::{ArrayElement, BackwardSequence, ExportProperty, IdentW, NumberWConstant, ObjectProperty};
@@ -586,6 +596,15 @@ mod handlers {
         Ok(None)
     }
 
+    pub fn bigint_constant(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
+        let id = cx.fetchw_and_inc_ip();
+        let sym = cx.constants().symbols[SymbolConstant(id)];
+        let value = parse_bigint_literal(cx.scope.interner.resolve(sym)).expect("invalid BigInt literal");
+        let bigint = JsBigInt::new(&mut cx.scope, value);
+        cx.push_stack(Value::bigint(bigint).into());
+        Ok(None)
+    }
+
     pub fn regex_constant(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
         let id = cx.fetchw_and_inc_ip();
         let (regex, source) = &cx.constants().regexes[RegexConstant(id)];
@@ -596,6 +615,39 @@ mod handlers {
         Ok(None)
     }
 
+    pub fn template_object(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
+        let id = cx.fetchw_and_inc_ip();
+        let strings = Rc::clone(&cx.constants().templates[TemplateConstant(id)]);
+
+        // The same template object is used every time the template is evaluated
+        if let Some(&(_, object)) = cx.template_objects.get(&Rc::as_ptr(&strings)) {
+            cx.push_stack(Value::object(object).into());
+            return Ok(None);
+        }
+
+        let raw = strings
+            .raw
+            .iter()
+            .map(|&sym| PropertyValue::static_default(Value::string(sym.into())))
+            .collect();
+        let raw = cx.scope.register(Array::from_vec(raw, &cx.scope));
+        raw.set_integrity_level(IntegrityLevel::Frozen, &mut cx.scope)?;
+
+        let cooked = strings
+            .cooked
+            .iter()
+            .map(|&sym| PropertyValue::static_default(sym.map_or(Value::undefined(), |sym| Value::string(sym.into()))))
+            .collect();
+        let object = cx.scope.register(Array::from_vec(cooked, &cx.scope));
+        let key = sym::raw.to_key(&mut cx.scope);
+        object.set_property(key, PropertyValue::static_empty(Value::object(raw)), &mut cx.scope)?;
+        object.set_integrity_level(IntegrityLevel::Frozen, &mut cx.scope)?;
+
+        cx.template_objects.insert(Rc::as_ptr(&strings), (strings, object));
+        cx.push_stack(Value::object(object).into());
+        Ok(None)
+    }
+
     pub fn null_constant(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
         cx.push_stack(Value::null().into());
         Ok(None)
@@ -651,11 +703,19 @@ mod handlers {
         let kind = match ty {
             ParserFunctionKind::Function(Asyncness::Yes) => FunctionKind::Async(AsyncFunction::new(fun)),
             ParserFunctionKind::Function(Asyncness::No) => FunctionKind::User(fun),
-            ParserFunctionKind::Arrow => FunctionKind::Closure(Closure {
-                fun,
-                this: cx.scope.frames.current_this(),
-            }),
-            ParserFunctionKind::Generator => FunctionKind::Generator(GeneratorFunction::new(fun)),
+            ParserFunctionKind::Arrow => {
+                // Arrow functions use the home object of the enclosing function for `super` property lookups
+                fun.set_home_object(cx.scope.frames.current_home_object());
+
+                FunctionKind::Closure(Closure {
+                    fun,
+                    this: cx.scope.frames.current_this(),
+                })
+            }
+            ParserFunctionKind::Generator(Asyncness::No) => FunctionKind::Generator(GeneratorFunction::new(fun)),
+            ParserFunctionKind::Generator(Asyncness::Yes) => {
+                FunctionKind::AsyncGenerator(AsyncGeneratorFunction::new(fun))
+            }
         };
 
         let function = Function::builder(kind).maybe_name(name).alloc_in_scope(&mut cx.scope);
@@ -721,22 +781,8 @@ mod handlers {
 
     pub fn objin(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
         cx.evaluate_binary_with_scope(|property, target, sc| {
-            let property = property.to_js_string(sc)?;
-            let found = target
-                .for_each_prototype(sc, |sc, target| {
-                    let contains = target
-                        .own_keys(sc, OwnKeysMode::All)?
-                        .iter()
-                        .any(|v| matches!(v.unpack(), ValueKind::String(s) if s == property));
-
-                    if contains {
-                        Ok(ControlFlow::Break(()))
-                    } else {
-                        Ok(ControlFlow::Continue(()))
-                    }
-                })?
-                .is_break();
-
+            let property = PropertyKey::from_value(sc, property)?;
+            let found = target.has_property(property, sc).root_err(sc)?;
             Ok(Value::boolean(found))
         })
     }
@@ -774,17 +820,17 @@ mod handlers {
     }
 
     pub fn strict_eq(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
-        cx.evaluate_binary_with_scope(|l, r, _| Ok(Value::boolean(equality::strict_eq(l, r))))
+        cx.evaluate_binary_with_scope(|l, r, sc| Ok(Value::boolean(equality::strict_eq(l, r, sc))))
     }
 
     pub fn strict_ne(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
-        cx.evaluate_binary_with_scope(|l, r, _| Ok(Value::boolean(equality::strict_ne(l, r))))
+        cx.evaluate_binary_with_scope(|l, r, sc| Ok(Value::boolean(equality::strict_ne(l, r, sc))))
     }
 
     pub fn neg(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
         let value = cx.pop_stack_rooted();
-        let result = value.to_number(&mut cx)?;
-        cx.stack.push(Value::number(-result));
+        let result = value.neg(&mut cx)?;
+        cx.stack.push(result);
         Ok(None)
     }
 
@@ -817,19 +863,22 @@ mod handlers {
         let tc_depth = cx.fetchw_and_inc_ip();
 
         if let Some(ret) = cx.frames.take_delayed_ret() {
-            let ret = ret?.root(&mut cx.scope);
+            let ret = ret?;
             let frame_idx = cx.frames.current_id();
             // NOTE: the try block was re-pushed in handle_rt_error
             let enclosing_finally = cx
                 .try_blocks
                 .iter()
-                .find_map(|tc| if tc.frame_idx == frame_idx { tc.finally_ip } else { None });
+                .rposition(|tc| tc.frame_idx == frame_idx && tc.finally_ip.is_some());
 
-            if let Some(finally) = enclosing_finally {
-                let lower_tcp = cx.try_blocks.len() - usize::from(tc_depth);
-                drop(cx.try_blocks.drain(lower_tcp..));
+            if let Some(index) = enclosing_finally {
+                let finally = cx.try_blocks[index].finally_ip.unwrap();
+                drop(cx.try_blocks.drain(index..));
+                // The enclosing finally block needs to perform the return once it finishes
+                cx.frames.set_delayed_ret(Some(Ok(ret)));
                 cx.frames.set_ip(finally);
             } else {
+                let ret = ret.root(&mut cx.scope);
                 let this = cx.pop_frame();
                 return ret_inner(cx, tc_depth, ret, this);
             }
@@ -863,7 +912,7 @@ mod handlers {
                 if_chain! {
                     if new_target.is_some() && !matches!(value.unpack(), ValueKind::Object(_) | ValueKind::External(_));
                     then {
-                        let this = this.this.to_value(&mut cx.scope)?;
+                        let this = this.this.to_value(this.function.is_strict, &mut cx.scope)?;
                         // If this is a constructor call and the return value is not an object,
                         // return `this`
                         if is_flat_call {
@@ -916,6 +965,7 @@ mod handlers {
         let id = cx.fetchw_and_inc_ip();
         let name = JsString::from(cx.constants().symbols[SymbolConstant(id)]);
         let kind = AssignKind::from_repr(cx.fetch_and_inc_ip()).unwrap();
+        let is_strict = cx.frames.current_is_strict();
 
         macro_rules! op {
             ($op:expr) => {{
@@ -927,9 +977,11 @@ mod handlers {
                     .root(&mut cx.scope)?;
 
                 let res = $op(value, right, &mut cx)?;
-                cx.global.clone().set_property(
+                assign_property(
+                    Value::object(cx.global),
                     name.to_key(&mut cx.scope),
-                    PropertyValue::static_default(res.clone()),
+                    res.clone(),
+                    is_strict,
                     &mut cx.scope,
                 )?;
                 cx.stack.push(res);
@@ -943,13 +995,15 @@ mod handlers {
                     .clone()
                     .get_property(name.to_key(&mut cx.scope), &mut cx.scope)
                     .root(&mut cx.scope)?;
-                let value = Value::number(value.to_number(&mut cx)?);
+                let value = value.to_numeric(&mut cx)?;
 
-                let right = Value::number(1.0);
+                let right = value.numeric_one(&mut cx);
                 let res = $op(value, right, &mut cx)?;
-                cx.global.clone().set_property(
+                assign_property(
+                    Value::object(cx.global),
                     name.to_key(&mut cx.scope),
-                    PropertyValue::static_default(res.clone()),
+                    res.clone(),
+                    is_strict,
                     &mut cx.scope,
                 )?;
                 cx.stack.push(res);
@@ -963,13 +1017,15 @@ mod handlers {
                     .clone()
                     .get_property(name.to_key(&mut cx.scope), &mut cx.scope)
                     .root(&mut cx.scope)?;
-                let value = Value::number(value.to_number(&mut cx)?);
+                let value = value.to_numeric(&mut cx)?;
 
-                let right = Value::number(1.0);
+                let right = value.numeric_one(&mut cx);
                 let res = $op(value, right, &mut cx)?;
-                cx.global.clone().set_property(
+                assign_property(
+                    Value::object(cx.global),
                     name.to_key(&mut cx.scope),
-                    PropertyValue::static_default(res),
+                    res,
+                    is_strict,
                     &mut cx.scope,
                 )?;
                 cx.stack.push(value);
@@ -980,9 +1036,22 @@ mod handlers {
             AssignKind::Assignment => {
                 let value = cx.pop_stack_rooted();
 
-                cx.global.clone().set_property(
+                // Assignments to undeclared variables do not implicitly create a global in strict mode
+                if is_strict
+                    && !cx
+                        .global
+                        .clone()
+                        .has_property(name.to_key(&mut cx.scope), &mut cx.scope)?
+                {
+                    let name = name.res(&cx.scope).to_owned();
+                    throw!(&mut cx, ReferenceError, "{} is not defined", name)
+                }
+
+                assign_property(
+                    Value::object(cx.global),
                     name.to_key(&mut cx.scope),
-                    PropertyValue::static_default(value),
+                    value,
+                    is_strict,
                     &mut cx.scope,
                 )?;
                 cx.stack.push(value);
@@ -1070,14 +1139,9 @@ mod handlers {
                 let adjusted_spread_index = (sp_before_call as isize + spread_index as isize + spread_count) as usize;
 
                 let iterable = cx.stack[adjusted_spread_index];
-                let length = iterable.length_of_array_like(&mut cx.scope)?;
+                splice_args.extend(spread_iterable(&mut cx.scope, iterable)?);
+                let length = splice_args.len();
 
-                for i in 0..length {
-                    let value = iterable
-                        .get_property(i.to_key(&mut cx.scope), &mut cx.scope)?
-                        .root(&mut cx.scope);
-                    splice_args.push(value);
-                }
                 cx.stack.splice(
                     adjusted_spread_index..=adjusted_spread_index,
                     splice_args.iter().copied(),
@@ -1131,14 +1195,8 @@ mod handlers {
 
                 for (index, value) in raw_args.into_iter().enumerate() {
                     if indices_iter.peek().is_some_and(|&v| usize::from(v) == index) {
-                        let len = value.length_of_array_like(&mut cx.scope)?;
-                        for i in 0..len {
-                            let value = value
-                                .get_property(i.to_key(&mut cx.scope), &mut cx.scope)?
-                                .root(&mut cx.scope);
-                            // NB: no need to push into `refs` since we already rooted it
-                            args.push(value);
-                        }
+                        // NB: no need to push into `refs` since the values are already rooted
+                        args.extend(spread_iterable(&mut cx.scope, value)?);
                         indices_iter.next();
                     } else {
                         args.push(value);
@@ -1350,8 +1408,8 @@ mod handlers {
         macro_rules! prefix {
             ($op:expr) => {{
                 let value = cx.get_local(id);
-                let value = Value::number(value.to_number(&mut cx)?);
-                let one = Value::number(1.0);
+                let value = value.to_numeric(&mut cx)?;
+                let one = value.numeric_one(&mut cx);
                 let res = $op(value, one, &mut cx)?;
                 cx.set_local(id, res.clone().into());
                 cx.stack.push(res);
@@ -1361,8 +1419,8 @@ mod handlers {
         macro_rules! postfix {
             ($op:expr) => {{
                 let value = cx.get_local(id);
-                let value = Value::number(value.to_number(&mut cx)?);
-                let one = Value::number(1.0);
+                let value = value.to_numeric(&mut cx)?;
+                let one = value.numeric_one(&mut cx);
                 let res = $op(value, one, &mut cx)?;
                 cx.set_local(id, res.into());
                 cx.stack.push(value);
@@ -1405,6 +1463,16 @@ mod handlers {
         Ok(None)
     }
 
+    /// Collects the elements of a spread iterable (`...iterable`) by going through its `@@iterator` method
+    fn spread_iterable(scope: &mut LocalScope<'_>, iterable: Value) -> Result<Vec<Value>, Value> {
+        let mut values = Vec::new();
+        let Continue(()) = for_each_iterable_element(scope, iterable, |_, value| {
+            values.push(value);
+            Ok(ControlFlow::<Infallible, _>::Continue(()))
+        })?;
+        Ok(values)
+    }
+
     fn with_arraylit_elements(
         cx: &mut DispatchContext<'_>,
         len: usize,
@@ -1415,11 +1483,8 @@ mod handlers {
         while let Some(element) = iter.next_front(cx) {
             match element? {
                 ArrayElement::Single(value) => fun(ArrayElement::Single(value)),
-                ArrayElement::Spread(source, len) => {
-                    for i in 0..len {
-                        let value = source
-                            .get_property(i.to_key(&mut cx.scope), &mut cx.scope)?
-                            .root(&mut cx.scope);
+                ArrayElement::Spread(source) => {
+                    for value in spread_iterable(&mut cx.scope, source)? {
                         fun(ArrayElement::Single(value));
                     }
                 }
@@ -1468,14 +1533,65 @@ mod handlers {
         Ok(None)
     }
 
+    /// Returns the function of a method defined in an object literal or class body
+    /// if it accesses properties on `super` and does not have a home object yet
+    fn method_without_home_object(property: &ObjectProperty, vm: &Vm) -> Option<ObjectId> {
+        let method = match *property {
+            ObjectProperty::Static {
+                value:
+                    PropertyValue {
+                        kind: PropertyValueKind::Static(value),
+                        ..
+                    },
+                ..
+            } => match value.unpack() {
+                ValueKind::Object(method) => method,
+                _ => return None,
+            },
+            ObjectProperty::Getter { value, .. } | ObjectProperty::Setter { value, .. } => value,
+            _ => return None,
+        };
+
+        let function = method.extract::<Function>(vm)?.inner_user_function()?;
+        (function.inner().references_super && function.home_object().is_none()).then_some(method)
+    }
+
+    fn assign_home_object(method: ObjectId, home_object: ObjectId, vm: &Vm) {
+        if let Some(function) = method.extract::<Function>(vm).and_then(Function::inner_user_function) {
+            function.set_home_object(Some(home_object));
+        }
+    }
+
+    /// Copies the own enumerable properties of `source` to `target` as data properties, invoking getters.
+    /// This implements the spread operator in object literals.
+    fn copy_data_properties(target: &impl Object, source: Value, sc: &mut LocalScope<'_>) -> Result<(), Value> {
+        if source.is_nullish() {
+            return Ok(());
+        }
+
+        for key in source.own_keys(sc, OwnKeysMode::OnlyEnumerable)? {
+            let key = PropertyKey::from_value(sc, key)?;
+            let value = source.get_property(key, sc).root_err(sc)?.root(sc);
+            target.set_property(key, PropertyValue::static_default(value), sc)?;
+        }
+
+        Ok(())
+    }
+
     pub fn objlit(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
         let key_count = cx.fetchw_and_inc_ip() as usize;
         let stack_value_count = cx.fetchw_and_inc_ip() as usize;
         let mut iter = ForwardSequence::<ObjectProperty>::from_len(&mut cx, key_count, stack_value_count);
 
         let obj = OrdObject::new(&cx.scope);
+        let mut methods = Vec::new();
         while let Some(property) = iter.next_front(&mut cx) {
-            match property? {
+            let property = property?;
+            if let Some(method) = method_without_home_object(&property, &cx.scope) {
+                methods.push(method);
+            }
+
+            match property {
                 ObjectProperty::Static { key, value } => drop(obj.set_property(key, value, &mut cx.scope)),
                 ObjectProperty::Getter { key, value } => match obj.get_own_property_descriptor(key, &mut cx.scope)? {
                     Some(prop) => {
@@ -1493,16 +1609,13 @@ mod handlers {
                         obj.set_property(key, PropertyValue::setter_default(value), &mut cx.scope)?;
                     }
                 },
-                ObjectProperty::Spread(value) => {
-                    if let ValueKind::Object(object) = value.unpack() {
-                        for key in object.own_keys(&mut cx.scope, OwnKeysMode::OnlyEnumerable)? {
-                            let key = PropertyKey::from_value(&mut cx.scope, key)?;
-                            if let Some(value) = object.get_own_property_descriptor(key, &mut cx.scope)? {
-                                obj.set_property(key, value, &mut cx.scope)?;
-                            }
-                        }
+                ObjectProperty::Prototype(value) => {
+                    // `__proto__: value` in a literal sets the prototype; values other than objects and null are ignored
+                    if let ValueKind::Object(_) | ValueKind::Null(_) = value.unpack() {
+                        obj.set_prototype(value, &mut cx.scope)?;
                     }
                 }
+                ObjectProperty::Spread(value) => copy_data_properties(&obj, value, &mut cx.scope)?,
             }
         }
 
@@ -1510,6 +1623,9 @@ mod handlers {
         cx.stack.truncate(stack_len - stack_value_count);
 
         let handle = cx.scope.register(obj);
+        for method in methods {
+            assign_home_object(method, handle, &cx.scope);
+        }
         cx.stack.push(handle.into());
 
         Ok(None)
@@ -1526,8 +1642,16 @@ mod handlers {
             let property = property?;
             let is_getter = matches!(property, ObjectProperty::Getter { .. });
 
+            if let ValueKind::Object(target) = target.unpack()
+                && let Some(method) = method_without_home_object(&property, &cx.scope)
+            {
+                assign_home_object(method, target, &cx.scope);
+            }
+
             match property {
-                ObjectProperty::Static { key, value } => target.set_property(key, value, &mut cx.scope)?,
+                ObjectProperty::Static { key, value } => {
+                    target.set_property(key, value, &mut cx.scope)?;
+                }
                 ObjectProperty::Getter { key, value } | ObjectProperty::Setter { key, value } => {
                     let prop = target.get_property_descriptor(key, &mut cx.scope)?;
                     let prop = match prop {
@@ -1552,7 +1676,11 @@ mod handlers {
 
                     target.set_property(key, prop, &mut cx.scope)?;
                 }
-                ObjectProperty::Spread(_) => unimplemented!("spread operator in AssignProperties"),
+                // Only object literals set the prototype, this is an ordinary property in classes
+                ObjectProperty::Prototype(value) => {
+                    target.set_property(PropertyKey::PROTO, PropertyValue::static_default(value), &mut cx.scope)?;
+                }
+                ObjectProperty::Spread(value) => copy_data_properties(&target, value, &mut cx.scope)?,
             }
         }
 
@@ -1580,10 +1708,28 @@ mod handlers {
         Ok(None)
     }
 
+    /// Assigns a value to a property in a property assignment expression.
+    ///
+    /// Assignments to non-writable properties and new properties of non-extensible objects are rejected,
+    /// which throws a TypeError in strict mode code (`is_strict`) and is silently ignored otherwise.
+    fn assign_property(
+        target: Value,
+        key: PropertyKey,
+        value: Value,
+        is_strict: bool,
+        sc: &mut LocalScope<'_>,
+    ) -> Result<(), Value> {
+        if !set_with_receiver(target, key, value, target, sc)? && is_strict {
+            reject_set_property(target, key, sc)?;
+        }
+        Ok(())
+    }
+
     pub fn staticpropertyassign(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
         let kind = AssignKind::from_repr(cx.fetch_and_inc_ip()).unwrap();
         let id = cx.fetchw_and_inc_ip();
         let key = JsString::from(cx.constants().symbols[SymbolConstant(id)]);
+        let is_strict = cx.frames.current_is_strict();
 
         macro_rules! op {
             ($op:expr) => {{
@@ -1594,9 +1740,11 @@ mod handlers {
                     .root(&mut cx.scope);
                 let res = $op(p, value, &mut cx)?;
 
-                target.set_property(
+                assign_property(
+                    target,
                     key.to_key(&mut cx.scope),
-                    PropertyValue::static_default(res.clone()),
+                    res.clone(),
+                    is_strict,
                     &mut cx.scope,
                 )?;
                 cx.stack.push(res);
@@ -1609,14 +1757,10 @@ mod handlers {
                 let prop = target
                     .get_property(key.to_key(&mut cx.scope), &mut cx.scope)?
                     .root(&mut cx.scope);
-                let prop = Value::number(prop.to_number(&mut cx)?);
-                let one = Value::number(1.0);
+                let prop = prop.to_numeric(&mut cx)?;
+                let one = prop.numeric_one(&mut cx);
                 let res = $op(prop, one, &mut cx)?;
-                target.set_property(
-                    key.to_key(&mut cx.scope),
-                    PropertyValue::static_default(res),
-                    &mut cx.scope,
-                )?;
+                assign_property(target, key.to_key(&mut cx.scope), res, is_strict, &mut cx.scope)?;
                 cx.stack.push(prop);
             }};
         }
@@ -1627,13 +1771,14 @@ mod handlers {
                 let prop = target
                     .get_property(key.to_key(&mut cx.scope), &mut cx.scope)?
                     .root(&mut cx.scope);
-                let prop = Value::number(prop.to_number(&mut cx)?);
-                // TODO: check that it encodes at comptime, if not make a constant Value::ONE
-                let one = Value::number(1.0);
+                let prop = prop.to_numeric(&mut cx)?;
+                let one = prop.numeric_one(&mut cx);
                 let res = $op(prop, one, &mut cx)?;
-                target.set_property(
+                assign_property(
+                    target,
                     key.to_key(&mut cx.scope),
-                    PropertyValue::static_default(res.clone()),
+                    res.clone(),
+                    is_strict,
                     &mut cx.scope,
                 )?;
                 cx.stack.push(res);
@@ -1643,11 +1788,7 @@ mod handlers {
         match kind {
             AssignKind::Assignment => {
                 let (target, value) = cx.pop_stack2_rooted();
-                target.set_property(
-                    key.to_key(&mut cx.scope),
-                    PropertyValue::static_default(value),
-                    &mut cx.scope,
-                )?;
+                assign_property(target, key.to_key(&mut cx.scope), value, is_strict, &mut cx.scope)?;
                 cx.stack.push(value);
             }
             AssignKind::AddAssignment => op!(Value::add),
@@ -1673,6 +1814,7 @@ mod handlers {
 
     pub fn dynamicpropertyassign(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
         let kind = AssignKind::from_repr(cx.fetch_and_inc_ip()).unwrap();
+        let is_strict = cx.frames.current_is_strict();
 
         macro_rules! op {
             ($op:expr) => {{
@@ -1685,7 +1827,7 @@ mod handlers {
 
                 let result = $op(prop, value, &mut cx)?;
 
-                target.set_property(key, PropertyValue::static_default(result.clone()), &mut cx.scope)?;
+                assign_property(target, key, result.clone(), is_strict, &mut cx.scope)?;
                 cx.stack.push(result);
             }};
         }
@@ -1697,10 +1839,10 @@ mod handlers {
                 let prop = target
                     .get_property(key.clone(), &mut cx.scope)?
                     .root(&mut cx.scope);
-                let prop = Value::number(prop.to_number(&mut cx)?);
-                let one = Value::number(1.0);
+                let prop = prop.to_numeric(&mut cx)?;
+                let one = prop.numeric_one(&mut cx);
                 let res = $op(prop, one, &mut cx)?;
-                target.set_property(key, PropertyValue::static_default(res), &mut cx.scope)?;
+                assign_property(target, key, res, is_strict, &mut cx.scope)?;
                 cx.stack.push(prop);
             }};
         }
@@ -1712,10 +1854,10 @@ mod handlers {
                 let prop = target
                     .get_property(key.clone(), &mut cx.scope)?
                     .root(&mut cx.scope);
-                let prop = Value::number(prop.to_number(&mut cx)?);
-                let one = Value::number(1.0);
+                let prop = prop.to_numeric(&mut cx)?;
+                let one = prop.numeric_one(&mut cx);
                 let res = $op(prop, one, &mut cx)?;
-                target.set_property(key, PropertyValue::static_default(res.clone()), &mut cx.scope)?;
+                assign_property(target, key, res.clone(), is_strict, &mut cx.scope)?;
                 cx.stack.push(res);
             }};
         }
@@ -1726,7 +1868,7 @@ mod handlers {
 
                 let key = PropertyKey::from_value(&mut cx, key)?;
 
-                target.set_property(key, PropertyValue::static_default(value), &mut cx.scope)?;
+                assign_property(target, key, value, is_strict, &mut cx.scope)?;
                 cx.stack.push(value);
             }
             AssignKind::AddAssignment => op!(Value::add),
@@ -1800,8 +1942,10 @@ mod handlers {
 
         macro_rules! prefix {
             ($op:expr) => {{
-                let value = Value::external(cx.get_external(id).id()).unbox_external(&cx.scope);
-                let right = Value::number(1.0);
+                let value = Value::external(cx.get_external(id).id())
+                    .unbox_external(&cx.scope)
+                    .to_numeric(&mut cx)?;
+                let right = value.numeric_one(&mut cx);
                 let res = $op(value, right, &mut cx)?;
                 let external = cx.scope.get_external(id);
                 assign_to_external(&mut cx.scope, external, res.clone());
@@ -1811,8 +1955,10 @@ mod handlers {
 
         macro_rules! postfix {
             ($op:expr) => {{
-                let value = Value::external(cx.get_external(id).id()).unbox_external(&cx.scope);
-                let right = Value::number(1.0);
+                let value = Value::external(cx.get_external(id).id())
+                    .unbox_external(&cx.scope)
+                    .to_numeric(&mut cx)?;
+                let right = value.numeric_one(&mut cx);
                 let res = $op(value, right, &mut cx)?;
                 let external = cx.scope.get_external(id);
                 assign_to_external(&mut cx.scope, external, res);
@@ -1974,7 +2120,8 @@ mod handlers {
     }
 
     pub fn this(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
-        let value = cx.frames.current_this().to_value(&mut cx.scope)?;
+        let is_strict = cx.frames.current_is_strict();
+        let value = cx.frames.current_this().to_value(is_strict, &mut cx.scope)?;
         cx.stack.push(value);
         Ok(None)
     }
@@ -1995,6 +2142,76 @@ mod handlers {
         throw!(cx, SyntaxError, "`super` keyword unexpected in this context");
     }
 
+    /// Returns the prototype of the current function's home object, which is where `super` property lookups start
+    fn super_base(cx: &mut DispatchContext<'_>) -> Result<ObjectId, Value> {
+        let Some(home_object) = cx.frames.current_home_object() else {
+            throw!(cx, SyntaxError, "'super' keyword unexpected here");
+        };
+
+        match home_object.get_prototype(&mut cx.scope)?.unpack() {
+            ValueKind::Object(base) => Ok(base),
+            _ => throw!(cx, TypeError, "Cannot access properties of a null super base"),
+        }
+    }
+
+    pub fn super_property_access(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
+        let key = cx.pop_stack_rooted();
+        let key = PropertyKey::from_value(&mut cx, key)?;
+        let base = super_base(&mut cx)?;
+        let is_strict = cx.frames.current_is_strict();
+        let this = cx.frames.current_this().to_value(is_strict, &mut cx.scope)?;
+
+        let value = Object::get_property(&base, This::bound(this), key, &mut cx.scope)?;
+        cx.push_stack(value);
+        Ok(None)
+    }
+
+    pub fn super_property_assign(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
+        let (key, value) = cx.pop_stack2_rooted();
+        let key = PropertyKey::from_value(&mut cx, key)?;
+        let base = super_base(&mut cx)?;
+        let is_strict = cx.frames.current_is_strict();
+        let this = cx.frames.current_this().to_value(is_strict, &mut cx.scope)?;
+
+        // The lookup starts at the super base, but setters are called with the current `this`
+        // and data properties are created on `this` itself
+        if !set_with_receiver(Value::object(base), key, value, this, &mut cx.scope)? && is_strict {
+            reject_set_property(this, key, &mut cx.scope)?;
+        }
+
+        cx.stack.push(value);
+        Ok(None)
+    }
+
+    pub fn super_property_delete(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
+        // The key is still evaluated, but never converted to a property key
+        cx.pop_stack_rooted();
+        throw!(&mut cx, ReferenceError, "Unsupported reference to 'super'")
+    }
+
+    pub fn set_home_object(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
+        let (method, home_object) = cx.pop_stack2_rooted();
+
+        if let (ValueKind::Object(method), ValueKind::Object(home_object)) = (method.unpack(), home_object.unpack()) {
+            assign_home_object(method, home_object, &cx.scope);
+        }
+        Ok(None)
+    }
+
+    pub fn set_prototype(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
+        let (target, prototype) = cx.pop_stack2_rooted();
+
+        if !matches!(prototype.unpack(), ValueKind::Object(_) | ValueKind::Null(_)) {
+            throw!(
+                &mut cx.scope,
+                TypeError,
+                "Object prototype may only be an Object or null"
+            );
+        }
+        target.set_prototype(prototype, &mut cx.scope)?;
+        Ok(None)
+    }
+
     pub fn undef(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
         cx.stack.push(Value::undefined());
         Ok(None)
@@ -2021,6 +2238,33 @@ mod handlers {
         Ok(None)
     }
 
+    pub fn call_symbol_async_iterator(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
+        let value = cx.pop_stack_rooted();
+        let symbol_async_iterator = cx.statics.symbol_async_iterator;
+        let method = value
+            .get_property(symbol_async_iterator.to_key(&mut cx.scope), &mut cx.scope)?
+            .root(&mut cx.scope);
+
+        if !method.is_nullish() {
+            let iterator = method.apply(This::bound(value), CallArgs::empty(), &mut cx.scope)?;
+            cx.push_stack(iterator);
+            return Ok(None);
+        }
+
+        // No async iterator, fall back to the sync iterator and wrap it
+        let symbol_iterator = cx.statics.symbol_iterator;
+        let method = value
+            .get_property(symbol_iterator.to_key(&mut cx.scope), &mut cx.scope)?
+            .root(&mut cx.scope);
+        let sync_iterator = method
+            .apply(This::bound(value), CallArgs::empty(), &mut cx.scope)?
+            .root(&mut cx.scope);
+        let obj = OrdObject::with_prototype(cx.statics.async_from_sync_iterator_prototype);
+        let iterator = cx.register(AsyncFromSyncIterator::new(sync_iterator, obj));
+        cx.stack.push(Value::object(iterator));
+        Ok(None)
+    }
+
     pub fn call_for_in_iterator(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
         let value = cx.pop_stack_rooted();
 
@@ -2041,27 +2285,37 @@ mod handlers {
         Ok(None)
     }
 
+    /// Deletes a property in a `delete` expression. Deleting a non-configurable property fails,
+    /// which throws a TypeError in strict mode code.
+    fn delete_property(
+        mut cx: DispatchContext<'_>,
+        target: Value,
+        key: PropertyKey,
+    ) -> Result<Option<HandleResult>, Unrooted> {
+        let deleted = target.delete_property(key, &mut cx.scope)?;
+        if !deleted && cx.frames.current_is_strict() {
+            let key = JsString::from(key.any_js_string(&mut cx.scope))
+                .res(&cx.scope)
+                .to_owned();
+            throw!(&mut cx, TypeError, "Cannot delete property '{}'", key)
+        }
+
+        cx.stack.push(Value::boolean(deleted));
+        Ok(None)
+    }
+
     pub fn delete_property_dynamic(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
         let (property, target) = cx.pop_stack2_rooted();
         let key = PropertyKey::from_value(&mut cx, property)?;
-        let value = target.delete_property(key, &mut cx.scope)?;
-
-        // TODO: not correct, as `undefined` might have been the actual value
-        let did_delete = !matches!(value.root(&mut cx.scope).unpack(), ValueKind::Undefined(..));
-        cx.stack.push(Value::boolean(did_delete));
-        Ok(None)
+        delete_property(cx, target, key)
     }
 
     pub fn delete_property_static(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
         let target = cx.pop_stack_rooted();
         let cid = cx.fetchw_and_inc_ip();
         let con = JsString::from(cx.constants().symbols[SymbolConstant(cid)]);
-        let value = target.delete_property(con.to_key(&mut cx.scope), &mut cx.scope)?;
-
-        // TODO: not correct, as `undefined` might have been the actual value
-        let did_delete = !matches!(value.root(&mut cx.scope).unpack(), ValueKind::Undefined(..));
-        cx.stack.push(Value::boolean(did_delete));
-        Ok(None)
+        let key = con.to_key(&mut cx.scope);
+        delete_property(cx, target, key)
     }
 
     pub fn objdestruct(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
@@ -2121,29 +2375,96 @@ mod handlers {
         Ok(None)
     }
 
-    pub fn arraydestruct(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
-        let array = cx.pop_stack_rooted();
-
-        let mut iter = BackwardSequence::<Option<(bool, NumberWConstant)>>::new_u16(&mut cx).enumerate();
+    /// Advances the iterator stored in the given local and returns the next value, or `None` if it is done.
+    ///
+    /// The local is replaced with `undefined` if the iterator is done or throws, so that it is not closed afterwards.
+    fn step_iterator_local(cx: &mut DispatchContext<'_>, id: BackLocalId) -> Result<Option<Value>, Unrooted> {
+        let iterator = cx.get_local(id);
+        if matches!(iterator.unpack(), ValueKind::Undefined(_)) {
+            return Ok(None);
+        }
 
-        while let Some((i, id)) = iter.next_infallible(&mut cx) {
-            if let Some((has_default, NumberWConstant(id))) = id {
-                let id = BackLocalId(id as u16);
-                let mut prop = array
-                    .get_property(i.to_key(&mut cx.scope), &mut cx.scope)?
-                    .root(&mut cx.scope);
+        let result = (|| {
+            let next = iterator
+                .get_property(sym::next.to_key(&mut cx.scope), &mut cx.scope)?
+                .root(&mut cx.scope);
+            let result = next
+                .apply(This::bound(iterator), CallArgs::empty(), &mut cx.scope)?
+                .root(&mut cx.scope);
+            if !matches!(result.unpack(), ValueKind::Object(_)) {
+                throw!(&mut cx.scope, TypeError, "Iterator result is not an object");
+            }
 
-                if has_default {
-                    // NB: we need to at least pop it from the stack even if the property exists
-                    let default = cx.pop_stack_rooted();
-                    if matches!(prop.unpack(), ValueKind::Undefined(_)) {
-                        prop = default;
-                    }
-                }
-                cx.set_local(id, prop.into());
+            let done = result
+                .get_property(sym::done.to_key(&mut cx.scope), &mut cx.scope)?
+                .root(&mut cx.scope)
+                .is_truthy(&mut cx.scope);
+            if done {
+                return Ok(None);
             }
+
+            let value = result
+                .get_property(sym::value.to_key(&mut cx.scope), &mut cx.scope)?
+                .root(&mut cx.scope);
+            Ok(Some(value))
+        })();
+
+        if !matches!(result, Ok(Some(_))) {
+            cx.set_local(id, Value::undefined().into());
+        }
+        result
+    }
+
+    pub fn iterator_step(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
+        let id = BackLocalId(cx.fetchw_and_inc_ip());
+        let value = step_iterator_local(&mut cx, id)?;
+        cx.push_stack(value.unwrap_or(Value::undefined()).into());
+        Ok(None)
+    }
+
+    pub fn iterator_rest(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
+        let id = BackLocalId(cx.fetchw_and_inc_ip());
+
+        let mut elements = Vec::new();
+        while let Some(value) = step_iterator_local(&mut cx, id)? {
+            elements.push(PropertyValue::static_default(value));
         }
 
+        let rest = cx.scope.register(Array::from_vec(elements, &cx.scope));
+        cx.push_stack(Value::object(rest).into());
+        Ok(None)
+    }
+
+    pub fn iterator_return(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
+        let id = BackLocalId(cx.fetchw_and_inc_ip());
+        let iterator = cx.get_local(id);
+        if matches!(iterator.unpack(), ValueKind::Undefined(_)) {
+            cx.push_stack(Value::boolean(false).into());
+            return Ok(None);
+        }
+
+        // The iterator is closed at most once
+        cx.set_local(id, Value::undefined().into());
+
+        let method = iterator
+            .get_property(sym::return_.to_key(&mut cx.scope), &mut cx.scope)?
+            .root(&mut cx.scope);
+        if method.is_nullish() {
+            cx.push_stack(Value::boolean(false).into());
+            return Ok(None);
+        }
+
+        let result = method.apply(This::bound(iterator), CallArgs::empty(), &mut cx.scope)?;
+        cx.push_stack(result);
+        cx.push_stack(Value::boolean(true).into());
+        Ok(None)
+    }
+
+    pub fn check_iterator_result(mut cx: DispatchContext<'_>) -> Result<Option<HandleResult>, Unrooted> {
+        let result = cx.pop_stack_rooted();
+        if !matches!(result.unpack(), ValueKind::Object(_)) {
+            throw!(&mut cx.scope, TypeError, "Iterator result is not an object");
+        }
         Ok(None)
     }
 
@@ -2371,6 +2692,8 @@ pub fn handle(vm: &mut Vm, instruction: Instruction) -> Result<Option<HandleResu
         Instruction::Boolean => handlers::boolean_constant(cx),
         Instruction::Number => handlers::number_constant(cx),
         Instruction::Regex => handlers::regex_constant(cx),
+        Instruction::TemplateObject => handlers::template_object(cx),
+        Instruction::BigInt => handlers::bigint_constant(cx),
         Instruction::Null => handlers::null_constant(cx),
         Instruction::Undefined => handlers::undefined_constant(cx),
         Instruction::Function => handlers::function_constant(cx),
@@ -2425,17 +2748,26 @@ pub fn handle(vm: &mut Vm, instruction: Instruction) -> Result<Option<HandleResu
         Instruction::Debugger => handlers::debugger(cx),
         Instruction::Global => handlers::global_this(cx),
         Instruction::Super => handlers::super_(cx),
+        Instruction::SuperPropAccess => handlers::super_property_access(cx),
+        Instruction::SuperPropAssign => handlers::super_property_assign(cx),
+        Instruction::SuperPropDelete => handlers::super_property_delete(cx),
+        Instruction::SetHomeObject => handlers::set_home_object(cx),
+        Instruction::SetPrototype => handlers::set_prototype(cx),
+        Instruction::IteratorStep => handlers::iterator_step(cx),
+        Instruction::IteratorRest => handlers::iterator_rest(cx),
+        Instruction::IteratorReturn => handlers::iterator_return(cx),
+        Instruction::CheckIteratorResult => handlers::check_iterator_result(cx),
         Instruction::Undef => handlers::undef(cx),
         Instruction::Await => handlers::await_(cx),
         Instruction::Nan => handlers::nan(cx),
         Instruction::Infinity => handlers::infinity(cx),
         Instruction::IntrinsicOp => handlers::intrinsic_op(cx),
         Instruction::CallSymbolIterator => handlers::call_symbol_iterator(cx),
+        Instruction::CallSymbolAsyncIterator => handlers::call_symbol_async_iterator(cx),
         Instruction::CallForInIterator => handlers::call_for_in_iterator(cx),
         Instruction::DeletePropertyStatic => handlers::delete_property_static(cx),
         Instruction::DeletePropertyDynamic => handlers::delete_property_dynamic(cx),
         Instruction::ObjDestruct => handlers::objdestruct(cx),
-        Instruction::ArrayDestruct => handlers::arraydestruct(cx),
         Instruction::AssignProperties => handlers::assign_properties(cx),
         Instruction::DelayedReturn => handlers::delayed_ret(cx),
         Instruction::NewTarget => handlers::new_target(cx),
@@ -8,7 +8,7 @@ use dash_parser::Parser;
 
 use crate::frame::Frame;
 use crate::localscope::LocalScope;
-use crate::value::object::{Object, OrdObject, PropertyValue};
+use crate::value::object::{Object, OrdObject, PropertyValue, This};
 use crate::value::propertykey::ToPropertyKey;
 use crate::value::{Root, Unrooted, Value};
 use crate::{Vm, throw};
@@ -24,17 +24,19 @@ impl Vm {
         let tokens = Lexer::new(&mut self.interner, input)
             .scan_all()
             .map_err(EvalError::Middle)?;
-        let (ast, scope_counter, local_counter) = Parser::new(&mut self.interner, input, tokens)
+        let (ast, scope_counter, local_counter, is_strict) = Parser::new(&mut self.interner, input, tokens)
             .parse_all()
             .map_err(EvalError::Middle)?;
 
         let nameres = name_res(&ast, scope_counter.len(), local_counter.len());
 
         let cr = FunctionCompiler::new(input, opt, nameres, scope_counter, &mut self.interner)
-            .compile_ast(ast, true)
+            .compile_ast(ast, true, is_strict)
             .map_err(|err| EvalError::Middle(vec![err]))?;
         let mut frame = Frame::from_compile_result(cr);
         frame.sp = self.active_sp();
+        // `this` at the top level of scripts is the global object, even in strict mode
+        frame.this = This::bound(Value::object(self.global));
         let val = self.execute_frame(frame).map_err(EvalError::Exception)?;
         Ok(val.into_value())
     }
@@ -45,7 +47,7 @@ impl Vm {
         import_ty: StaticImportKind,
         opt: OptLevel,
     ) -> Result<Unrooted, Unrooted> {
-        let re = match FunctionCompiler::compile_str(&mut sc.interner, input, opt) {
+        let re = match FunctionCompiler::compile_module_str(&mut sc.interner, input, opt) {
             Ok(re) => re,
             Err(err) => throw!(sc, SyntaxError, "Middle error: {:?}", err),
         };
@@ -174,6 +174,9 @@ pub struct ExtendedFrame {
 
     /// Counts the number of backjumps to a particular loop header, to find hot loops
     pub loop_counter: LoopCounterMap,
+
+    /// The home object of the executing function, used as the base for `super` property lookups
+    pub home_object: Option<ObjectId>,
 }
 
 #[derive(Debug, Clone, Trace)]
@@ -200,6 +203,9 @@ pub struct Frame {
 
     /// Counts the number of backjumps to a particular loop header, to find hot loops
     pub loop_counter: LoopCounterMap,
+
+    /// The home object of the executing function, used as the base for `super` property lookups
+    pub home_object: Option<ObjectId>,
 }
 
 impl Frame {
@@ -225,6 +231,7 @@ impl Frame {
             },
             loop_counter: LoopCounterMap::default(),
             arguments,
+            home_object: uf.home_object(),
         }
     }
 
@@ -241,6 +248,7 @@ impl Frame {
             state: FrameState::Module(Exports::default()),
             loop_counter: LoopCounterMap::default(),
             arguments,
+            home_object: None,
         }
     }
 
@@ -266,6 +274,8 @@ impl Frame {
             debug_symbols: cr.debug_symbols,
             arguments_local: None,
             has_extends_clause: false,
+            is_strict: cr.is_strict,
+            references_super: false,
         };
 
         Self {
@@ -283,6 +293,7 @@ impl Frame {
             loop_counter: LoopCounterMap::default(),
             // Root function never has arguments
             arguments: None,
+            home_object: None,
         }
     }
 }
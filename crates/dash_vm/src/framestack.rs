@@ -6,6 +6,7 @@ use dash_middle::interner::Symbol;
 use dash_proc_macro::Trace;
 
 use crate::frame::{BaseFrame, ExtendedFrame, Frame, FrameState, Ip, Sp};
+use crate::gc::ObjectId;
 use crate::value::object::This;
 use crate::value::{ExternalValue, Unrooted};
 
@@ -85,6 +86,16 @@ impl FrameStack {
         self.current_extended().this
     }
 
+    /// Checks if the currently executing function is strict mode code.
+    /// Returns false if there is no active frame.
+    pub fn current_is_strict(&self) -> bool {
+        self.current_base.as_ref().is_some_and(|base| base.function.is_strict)
+    }
+
+    pub fn current_home_object(&self) -> Option<ObjectId> {
+        self.current_extended().home_object
+    }
+
     pub fn current_external(&self, id: ExternalId) -> ExternalValue {
         self.current_extended().externals[id.0 as usize].clone()
     }
@@ -158,6 +169,7 @@ impl FrameStack {
             delayed_ret: extended.delayed_ret,
             arguments: extended.arguments,
             loop_counter: extended.loop_counter,
+            home_object: extended.home_object,
         }
     }
 
@@ -186,6 +198,13 @@ impl FrameStack {
         self.extended.len() as u32
     }
 
+    /// Pops frames until there are at most `len` frames left.
+    pub fn truncate(&mut self, len: u32) {
+        while self.len() > len {
+            self.pop_discard();
+        }
+    }
+
     pub fn push(&mut self, frame: Frame) -> Result<(), ()> {
         if self.len() < MAX_FRAME_COUNT {
             self.push_base(BaseFrame {
@@ -201,6 +220,7 @@ impl FrameStack {
                 delayed_ret: frame.delayed_ret,
                 arguments: frame.arguments,
                 loop_counter: frame.loop_counter,
+                home_object: frame.home_object,
             });
             Ok(())
         } else {
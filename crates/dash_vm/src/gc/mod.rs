@@ -36,11 +36,16 @@ pub struct ObjectVTable {
         unsafe fn(*const (), This, PropertyKey, &mut LocalScope<'_>) -> Result<Unrooted, Unrooted>,
     pub(crate) js_get_property_descriptor:
         unsafe fn(*const (), PropertyKey, &mut LocalScope<'_>) -> Result<Option<PropertyValue>, Unrooted>,
+    pub(crate) js_has_property: unsafe fn(*const (), PropertyKey, &mut LocalScope<'_>) -> Result<bool, Unrooted>,
     pub(crate) js_set_property:
-        unsafe fn(*const (), PropertyKey, PropertyValue, &mut LocalScope<'_>) -> Result<(), Value>,
-    pub(crate) js_delete_property: unsafe fn(*const (), PropertyKey, &mut LocalScope<'_>) -> Result<Unrooted, Value>,
+        unsafe fn(*const (), PropertyKey, PropertyValue, &mut LocalScope<'_>) -> Result<bool, Value>,
+    pub(crate) js_define_property:
+        unsafe fn(*const (), PropertyKey, PropertyValue, &mut LocalScope<'_>) -> Result<bool, Value>,
+    pub(crate) js_delete_property: unsafe fn(*const (), PropertyKey, &mut LocalScope<'_>) -> Result<bool, Value>,
     pub(crate) js_set_prototype: unsafe fn(*const (), Value, &mut LocalScope<'_>) -> Result<(), Value>,
     pub(crate) js_get_prototype: unsafe fn(*const (), &mut LocalScope<'_>) -> Result<Value, Value>,
+    pub(crate) js_prevent_extensions: unsafe fn(*const (), &mut LocalScope<'_>) -> Result<(), Value>,
+    pub(crate) js_is_extensible: unsafe fn(*const (), &mut LocalScope<'_>) -> Result<bool, Value>,
     pub(crate) js_apply:
         unsafe fn(*const (), ObjectId, This, CallArgs, &mut LocalScope<'_>) -> Result<Unrooted, Unrooted>,
     pub(crate) js_construct:
@@ -74,9 +79,15 @@ macro_rules! object_vtable_for_ty {
                 js_get_property_descriptor: |ptr, key, scope| unsafe {
                     <$ty as Object>::get_property_descriptor(&*(ptr.cast::<$ty>()), key, scope)
                 },
+                js_has_property: |ptr, key, scope| unsafe {
+                    <$ty as Object>::has_property(&*(ptr.cast::<$ty>()), key, scope)
+                },
                 js_set_property: |ptr, key, value, scope| unsafe {
                     <$ty as Object>::set_property(&*(ptr.cast::<$ty>()), key, value, scope)
                 },
+                js_define_property: |ptr, key, value, scope| unsafe {
+                    <$ty as Object>::define_property(&*(ptr.cast::<$ty>()), key, value, scope)
+                },
                 js_delete_property: |ptr, key, scope| unsafe {
                     <$ty as Object>::delete_property(&*(ptr.cast::<$ty>()), key, scope)
                 },
@@ -84,6 +95,10 @@ macro_rules! object_vtable_for_ty {
                     <$ty as Object>::set_prototype(&*(ptr.cast::<$ty>()), proto, scope)
                 },
                 js_get_prototype: |ptr, scope| unsafe { <$ty as Object>::get_prototype(&*(ptr.cast::<$ty>()), scope) },
+                js_prevent_extensions: |ptr, scope| unsafe {
+                    <$ty as Object>::prevent_extensions(&*(ptr.cast::<$ty>()), scope)
+                },
+                js_is_extensible: |ptr, scope| unsafe { <$ty as Object>::is_extensible(&*(ptr.cast::<$ty>()), scope) },
                 js_apply: |ptr, callee, this, args, scope| unsafe {
                     <$ty as Object>::apply(&*(ptr.cast::<$ty>()), callee, this, args, scope)
                 },
@@ -666,6 +681,12 @@ impl Allocator {
         unsafe { self.chunk(id.chunk()).metadata(id.local()) }
     }
 
+    /// Checks if an allocation has been marked as reachable.
+    /// This is only meaningful between tracing the roots and sweeping, and is used for processing weak references.
+    pub fn is_marked<M>(&self, id: AllocId<M>) -> bool {
+        self.info(id).flags.get().contains(AllocFlags::VISITED)
+    }
+
     /// # Safety
     /// Callers must ensure that objects that are deleted as a result of not having been marked are never accessed again.
     /// In practice this is ensured by marking everything reachable first.
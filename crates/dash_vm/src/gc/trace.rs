@@ -4,13 +4,17 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
-use dash_middle::compiler::constant::ConstantPool;
+use dash_middle::compiler::constant::{ConstantPool, TemplateStrings};
 use dash_middle::indexvec::{Index, IndexVec};
 use dash_middle::interner::StringInterner;
 use dash_regex::Regex;
 
 use crate::value::Unrooted;
+use crate::value::array::ArrayIteratorKind;
+use crate::value::function::async_generator::{AsyncGeneratorAction, AsyncGeneratorRequestKind};
+use crate::value::map::MapIteratorKind;
 use crate::value::primitive::{Null, Number, Undefined};
+use crate::value::set::SetIteratorKind;
 use crate::value::typedarray::TypedArrayKind;
 
 use super::{AllocFlags, Allocator, ObjectId};
@@ -183,6 +187,7 @@ unsafe impl Trace for dash_middle::compiler::constant::Function {
                     booleans,
                     functions,
                     regexes,
+                    templates,
                 },
             externals: _,
             rest_local: _,
@@ -190,6 +195,8 @@ unsafe impl Trace for dash_middle::compiler::constant::Function {
             debug_symbols: _,
             has_extends_clause: _,
             arguments_local: _,
+            is_strict: _,
+            references_super: _,
         } = self;
         name.trace(cx);
         numbers.as_slice().trace(cx);
@@ -200,6 +207,12 @@ unsafe impl Trace for dash_middle::compiler::constant::Function {
         for (Regex { .. }, sym) in regexes.as_slice() {
             sym.trace(cx);
         }
+
+        for template in templates.as_slice() {
+            let TemplateStrings { cooked, raw } = &**template;
+            cooked.trace(cx);
+            raw.trace(cx);
+        }
     }
 }
 
@@ -233,6 +246,11 @@ unsafe_empty_trace!(
     Null,
     Number,
     TypedArrayKind,
+    ArrayIteratorKind,
+    MapIteratorKind,
+    SetIteratorKind,
+    AsyncGeneratorRequestKind,
+    AsyncGeneratorAction,
     PathBuf,
     Path,
     String,
@@ -1,20 +1,30 @@
 use ControlFlow::{Break, Continue};
-use std::cmp;
+use std::cell::RefCell;
+use std::cmp::{self, Ordering};
 use std::convert::Infallible;
 use std::ops::{ControlFlow, Range};
+use std::rc::Rc;
 
+use dash_proc_macro::Trace;
+
+use crate::gc::ObjectId;
+use crate::gc::trace::{Trace, TraceCtxt};
+use crate::js_std::promise::invoke_then;
 use crate::localscope::LocalScope;
-use crate::throw;
 use crate::value::array::{Array, ArrayIterator, require_valid_array_length};
 use crate::value::function::args::CallArgs;
 use crate::value::function::native::CallContext;
-use crate::value::object::{Object as _, OrdObject, PropertyValue, This};
+use crate::value::object::ordinary::{delete_property_or_throw, reject_set_property, set_property_or_throw};
+use crate::value::object::{Object, OrdObject, PropertyValue, This};
 use crate::value::ops::conversions::ValueConversion;
 use crate::value::ops::equality::strict_eq;
+use crate::value::primitive::Symbol;
+use crate::value::promise::{Promise, wrap_resolved_promise};
 use crate::value::propertykey::ToPropertyKey;
 use crate::value::root_ext::RootErrExt;
 use crate::value::string::JsString;
-use crate::value::{Root, Unpack, Value, ValueContext, array};
+use crate::value::{Root, Typeof, Unpack, Unrooted, Value, ValueContext, ValueKind, array};
+use crate::{MAX_FRAME_STACK_SIZE, PromiseAction, Vm, delegate, extract, throw};
 use dash_middle::interner::sym;
 
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
@@ -78,7 +88,10 @@ pub fn to_string(cx: CallContext) -> Result<Value, Value> {
 }
 
 pub fn join(cx: CallContext) -> Result<Value, Value> {
-    let sep = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+    let sep = match cx.args.first().copied().and_then(Value::into_option) {
+        Some(sep) => sep.to_js_string(cx.scope)?,
+        None => sym::comma.into(),
+    };
     join_inner(cx.scope, cx.this, sep)
 }
 
@@ -201,7 +214,9 @@ pub fn fill(mut cx: CallContext) -> Result<Value, Value> {
     };
 
     for i in k..final_ {
-        array::spec_array_set_property(cx.scope, &this, i, PropertyValue::static_default(value))?;
+        if !array::spec_array_set_property(cx.scope, &this, i, PropertyValue::static_default(value))? {
+            reject_set_property(this, i.to_key(cx.scope), cx.scope)?;
+        }
     }
 
     if let Some(arr) = this.unpack().downcast_ref::<Array>(cx.scope) {
@@ -211,6 +226,39 @@ pub fn fill(mut cx: CallContext) -> Result<Value, Value> {
     Ok(this)
 }
 
+pub fn copy_within(mut cx: CallContext) -> Result<Value, Value> {
+    let this = Value::object(cx.this.to_object(cx.scope)?);
+    let len = this.length_of_array_like(cx.scope)?;
+
+    let to = wrapping_index_val(cx.args.first().unwrap_or_undefined(), &mut cx, len)?;
+    let from = wrapping_index_val(cx.args.get(1).unwrap_or_undefined(), &mut cx, len)?;
+    let final_ = match cx.args.get(2).copied().and_then(Value::into_option) {
+        Some(end) => wrapping_index_val(end, &mut cx, len)?,
+        None => len,
+    };
+    let count = cmp::min(final_.saturating_sub(from), len - to);
+
+    // Copy back to front if the ranges overlap and the target comes after the source,
+    // so that elements are read before they are overwritten
+    let backwards = from < to && to < from + count;
+
+    for i in 0..count {
+        let offset = if backwards { count - i - 1 } else { i };
+        let from_key = (from + offset).to_key(cx.scope);
+        let to_key = (to + offset).to_key(cx.scope);
+
+        match this.get_property_descriptor(from_key, cx.scope).root_err(cx.scope)? {
+            Some(value) => {
+                let value = value.get_or_apply(cx.scope, This::bound(this)).root(cx.scope)?;
+                set_property_or_throw(this, to_key, value, cx.scope)?;
+            }
+            None => delete_property_or_throw(this, to_key, cx.scope)?,
+        }
+    }
+
+    Ok(this)
+}
+
 pub fn filter(cx: CallContext) -> Result<Value, Value> {
     let this = Value::object(cx.this.to_object(cx.scope)?);
     let callback = cx.args.first().unwrap_or_undefined();
@@ -267,6 +315,46 @@ pub fn reduce(cx: CallContext) -> Result<Value, Value> {
     Ok(accumulator)
 }
 
+pub fn reduce_right(cx: CallContext) -> Result<Value, Value> {
+    let this = Value::object(cx.this.to_object(cx.scope)?);
+    let len = this.length_of_array_like(cx.scope)?;
+    let callback = cx.args.first().unwrap_or_undefined();
+
+    let mut k = len;
+    let mut accumulator = match cx.args.get(1) {
+        Some(&initial) => initial,
+        // Without an initial value, the last present element is used as the accumulator
+        None => loop {
+            if k == 0 {
+                throw!(cx.scope, TypeError, "Reduce of empty array with no initial value");
+            }
+            k -= 1;
+
+            if let Some(value) = this
+                .get_property_descriptor(k.to_key(cx.scope), cx.scope)
+                .root_err(cx.scope)?
+            {
+                break value.get_or_apply(cx.scope, This::bound(this)).root(cx.scope)?;
+            }
+        },
+    };
+
+    while k > 0 {
+        k -= 1;
+
+        if let Some(value) = this
+            .get_property_descriptor(k.to_key(cx.scope), cx.scope)
+            .root_err(cx.scope)?
+        {
+            let value = value.get_or_apply(cx.scope, This::bound(this)).root(cx.scope)?;
+            let args = [accumulator, value, Value::number(k as f64), this].into();
+            accumulator = callback.apply(This::default(), args, cx.scope).root(cx.scope)?;
+        }
+    }
+
+    Ok(accumulator)
+}
+
 pub fn find(cx: CallContext) -> Result<Value, Value> {
     let this = Value::object(cx.this.to_object(cx.scope)?);
     let callback = cx.args.first().unwrap_or_undefined();
@@ -319,8 +407,120 @@ pub fn find_index(cx: CallContext) -> Result<Value, Value> {
     })
 }
 
+/// Calls the predicate for each index from the end of the array to the start (including holes),
+/// and returns the first index and element for which it returned a truthy value
+fn find_last_inner(cx: &mut CallContext) -> Result<Option<(usize, Value)>, Value> {
+    let this = Value::object(cx.this.to_object(cx.scope)?);
+    let len = this.length_of_array_like(cx.scope)?;
+    let predicate = cx.args.first().unwrap_or_undefined();
+    let cb_this = match cx.args.get(1) {
+        Some(v) => Value::object(v.to_object(cx.scope)?),
+        None => Value::undefined(),
+    };
+
+    for k in (0..len).rev() {
+        let value = this.get_property(k.to_key(cx.scope), cx.scope).root(cx.scope)?;
+        if predicate
+            .apply(
+                This::bound(cb_this),
+                [value, Value::number(k as f64), this].into(),
+                cx.scope,
+            )
+            .root(cx.scope)?
+            .to_boolean(cx.scope)?
+        {
+            return Ok(Some((k, value)));
+        }
+    }
+
+    Ok(None)
+}
+
+pub fn find_last(mut cx: CallContext) -> Result<Value, Value> {
+    Ok(match find_last_inner(&mut cx)? {
+        Some((_, value)) => value,
+        None => Value::undefined(),
+    })
+}
+
+pub fn find_last_index(mut cx: CallContext) -> Result<Value, Value> {
+    Ok(match find_last_inner(&mut cx)? {
+        Some((index, _)) => Value::number(index as f64),
+        None => Value::number(-1.0),
+    })
+}
+
+/// Implements the FlattenIntoArray abstract operation.
+///
+/// Elements of `source` are appended to `target`, with nested arrays being flattened up to `depth` levels.
+/// If a mapper is given, it is called with each element before it is flattened (used by `flatMap`).
+fn flatten_into_array(
+    scope: &mut LocalScope<'_>,
+    target: &mut Vec<PropertyValue>,
+    source: Value,
+    depth: f64,
+    mapper: Option<(Value, Value)>,
+    nesting: usize,
+) -> Result<(), Value> {
+    if nesting > MAX_FRAME_STACK_SIZE {
+        throw!(scope, RangeError, "Maximum call stack size exceeded");
+    }
+
+    let len = source.length_of_array_like(scope)?;
+    for k in 0..len {
+        let Some(element) = source.get_property_descriptor(k.to_key(scope), scope).root_err(scope)? else {
+            continue;
+        };
+        let mut element = element.get_or_apply(scope, This::bound(source)).root(scope)?;
+
+        if let Some((mapper, this_arg)) = mapper {
+            element = mapper
+                .apply(
+                    This::bound(this_arg),
+                    [element, Value::number(k as f64), source].into(),
+                    scope,
+                )
+                .root(scope)?;
+        }
+
+        if depth > 0.0 && array::is_array(element, scope)? {
+            flatten_into_array(scope, target, element, depth - 1.0, None, nesting + 1)?;
+        } else {
+            require_valid_array_length(scope, target.len() + 1)?;
+            target.push(PropertyValue::static_default(element));
+        }
+    }
+
+    Ok(())
+}
+
 pub fn flat(cx: CallContext) -> Result<Value, Value> {
-    throw!(cx.scope, Error, "Not implemented")
+    let this = Value::object(cx.this.to_object(cx.scope)?);
+    let depth = match cx.args.first().copied().and_then(Value::into_option) {
+        Some(depth) => depth.to_integer_or_infinity(cx.scope)?.max(0.0),
+        None => 1.0,
+    };
+
+    let mut values = Vec::new();
+    flatten_into_array(cx.scope, &mut values, this, depth, None, 0)?;
+
+    let values = Array::from_vec(values, cx.scope);
+    Ok(cx.scope.register(values).into())
+}
+
+pub fn flat_map(cx: CallContext) -> Result<Value, Value> {
+    let this = Value::object(cx.this.to_object(cx.scope)?);
+    let mapper = cx.args.first().unwrap_or_undefined();
+    if !matches!(mapper.type_of(cx.scope), Typeof::Function) {
+        throw!(cx.scope, TypeError, "flatMap mapper function is not callable");
+    }
+    let this_arg = cx.args.get(1).unwrap_or_undefined();
+
+    let mut values = Vec::new();
+    flatten_into_array(cx.scope, &mut values, this, 1.0, Some((mapper, this_arg)), 0)?;
+
+    let values = Array::from_vec(values, cx.scope);
+    Ok(cx.scope.register(values).into())
 }
 
 pub fn for_each(cx: CallContext) -> Result<Value, Value> {
@@ -348,7 +548,7 @@ pub fn includes(cx: CallContext) -> Result<Value, Value> {
 
     for k in 0..len {
         let pkv = this.get_property(k.to_key(cx.scope), cx.scope).root(cx.scope)?;
-        if strict_eq(pkv, search_element) {
+        if strict_eq(pkv, search_element, cx.scope) {
             return Ok(true.into());
         }
     }
@@ -379,7 +579,7 @@ pub fn index_of(cx: CallContext) -> Result<Value, Value> {
 
     for k in from_index..len {
         let pkv = this.get_property(k.to_key(cx.scope), cx.scope).root(cx.scope)?;
-        if strict_eq(pkv, search_element) {
+        if strict_eq(pkv, search_element, cx.scope) {
             return Ok(Value::number(k as f64));
         }
     }
@@ -410,7 +610,7 @@ pub fn last_index_of(cx: CallContext) -> Result<Value, Value> {
 
     for k in (0..=from_index).rev() {
         let pkv = this.get_property(k.to_key(cx.scope), cx.scope).root(cx.scope)?;
-        if strict_eq(pkv, search_element) {
+        if strict_eq(pkv, search_element, cx.scope) {
             return Ok(Value::number(k as f64));
         }
     }
@@ -449,10 +649,12 @@ pub fn pop(cx: CallContext) -> Result<Value, Value> {
     }
 
     let new_len = len - 1;
-    let value = this.delete_property(new_len.to_key(cx.scope), cx.scope)?.root(cx.scope);
-    this.set_property(
+    let value = this.get_property(new_len.to_key(cx.scope), cx.scope).root(cx.scope)?;
+    delete_property_or_throw(this, new_len.to_key(cx.scope), cx.scope)?;
+    set_property_or_throw(
+        this,
         sym::length.to_key(cx.scope),
-        PropertyValue::static_default(Value::number(new_len as f64)),
+        Value::number(new_len as f64),
         cx.scope,
     )?;
 
@@ -463,22 +665,17 @@ pub fn push(cx: CallContext) -> Result<Value, Value> {
     let this = Value::object(cx.this.to_object(cx.scope)?);
     let len = this.length_of_array_like(cx.scope)?;
 
-    let mut last = Value::undefined();
-
-    if cx.args.is_empty() {
-        this.set_property(
-            len.to_key(cx.scope),
-            PropertyValue::static_default(Value::undefined()),
-            cx.scope,
-        )?;
-    }
+    let new_len = len + cx.args.len();
 
     for (idx, arg) in cx.args.into_iter().enumerate() {
-        last = arg;
-        array::spec_array_set_property(cx.scope, &this, idx + len, PropertyValue::static_default(arg))?;
+        if !array::spec_array_set_property(cx.scope, &this, idx + len, PropertyValue::static_default(arg))? {
+            reject_set_property(this, (idx + len).to_key(cx.scope), cx.scope)?;
+        }
     }
 
-    Ok(last)
+    let new_len = Value::number(new_len as f64);
+    set_property_or_throw(this, sym::length.to_key(cx.scope), new_len, cx.scope)?;
+    Ok(new_len)
 }
 
 pub fn reverse(cx: CallContext) -> Result<Value, Value> {
@@ -490,8 +687,8 @@ pub fn reverse(cx: CallContext) -> Result<Value, Value> {
         let pkv = this.get_property(k.to_key(cx.scope), cx.scope).root(cx.scope)?;
         let k2 = len - k - 1;
         let pk2v = this.get_property(k2.to_key(cx.scope), cx.scope).root(cx.scope)?;
-        this.set_property(k.to_key(cx.scope), PropertyValue::static_default(pk2v), cx.scope)?;
-        this.set_property(k2.to_key(cx.scope), PropertyValue::static_default(pkv), cx.scope)?;
+        set_property_or_throw(this, k.to_key(cx.scope), pk2v, cx.scope)?;
+        set_property_or_throw(this, k2.to_key(cx.scope), pkv, cx.scope)?;
     }
 
     Ok(this)
@@ -505,19 +702,20 @@ pub fn shift(cx: CallContext) -> Result<Value, Value> {
         return Ok(Value::undefined());
     }
 
-    let prop = this
-        .delete_property(sym::zero.to_key(cx.scope), cx.scope)?
-        .root(cx.scope);
+    let prop = this.get_property(sym::zero.to_key(cx.scope), cx.scope).root(cx.scope)?;
 
     for k in 1..len {
         let prev_pk = k - 1;
         let pkv = this.get_property(k.to_key(cx.scope), cx.scope).root(cx.scope)?;
-        this.set_property(prev_pk.to_key(cx.scope), PropertyValue::static_default(pkv), cx.scope)?;
+        set_property_or_throw(this, prev_pk.to_key(cx.scope), pkv, cx.scope)?;
     }
 
-    this.set_property(
+    delete_property_or_throw(this, (len - 1).to_key(cx.scope), cx.scope)?;
+
+    set_property_or_throw(
+        this,
         sym::length.to_key(cx.scope),
-        PropertyValue::static_default(Value::number((len - 1) as f64)),
+        Value::number((len - 1) as f64),
         cx.scope,
     )?;
 
@@ -545,11 +743,7 @@ fn shift_array(
     // set the length of the array to the new length.
     // Technically this isn't needed, and we can just let the array grow as needed, but this is for clarity
     if range.end + shift_by > len as isize {
-        arr.set_property(
-            sym::length.to_key(scope),
-            PropertyValue::static_default(Value::number(new_len as f64)),
-            scope,
-        )?;
+        set_property_or_throw(*arr, sym::length.to_key(scope), Value::number(new_len as f64), scope)?;
     }
 
     // Start shifting the elements by the shift_by (can be either negative or positive) amount
@@ -557,17 +751,16 @@ fn shift_array(
         let pk = scope.intern_isize(k);
         let shift_pk = scope.intern_isize(k + shift_by);
         let pkv = arr.get_property(pk.to_key(scope), scope).root(scope)?;
-        arr.set_property(shift_pk.to_key(scope), PropertyValue::static_default(pkv), scope)?;
+        set_property_or_throw(*arr, shift_pk.to_key(scope), pkv, scope)?;
     }
 
     // If the shift_by is negative, we need to delete the remaining elements at the end that were shifted
     // This must be done after the shifting, otherwise we would be deleting elements before they can be shifted
     if shift_by < 0 {
-        arr.set_property(
-            sym::length.to_key(scope),
-            PropertyValue::static_default(Value::number(new_len as f64)),
-            scope,
-        )?;
+        for k in (new_len..len).rev() {
+            delete_property_or_throw(*arr, k.to_key(scope), scope)?;
+        }
+        set_property_or_throw(*arr, sym::length.to_key(scope), Value::number(new_len as f64), scope)?;
     }
 
     Ok(())
@@ -582,7 +775,7 @@ pub fn unshift(cx: CallContext) -> Result<Value, Value> {
     shift_array(cx.scope, &this, len, arg_len as isize, 0..len)?;
 
     for (idx, arg) in cx.args.into_iter().enumerate() {
-        this.set_property(idx.to_key(cx.scope), PropertyValue::static_default(arg), cx.scope)?;
+        set_property_or_throw(this, idx.to_key(cx.scope), arg, cx.scope)?;
     }
 
     Ok(Value::number(new_len as f64))
@@ -627,14 +820,7 @@ pub fn slice(cx: CallContext) -> Result<Value, Value> {
 }
 
 pub fn is_array(cx: CallContext) -> Result<Value, Value> {
-    Ok(Value::boolean(
-        cx.args
-            .first()
-            .unwrap_or_undefined()
-            .unpack()
-            .downcast_ref::<Array>(cx.scope)
-            .is_some(),
-    ))
+    array::is_array(cx.args.first().unwrap_or_undefined(), cx.scope).map(Value::boolean)
 }
 
 pub fn for_each_js_iterator_element<B, F: FnMut(&mut LocalScope<'_>, Value) -> Result<ControlFlow<B>, Value>>(
@@ -653,23 +839,92 @@ pub fn for_each_js_iterator_element<B, F: FnMut(&mut LocalScope<'_>, Value) -> R
             break;
         }
         let value = item.get_property(sym::value.to_key(scope), scope).root(scope)?;
-        if let Break(val) = f(scope, value)? {
-            return Ok(Break(val));
+        let flow = f(scope, value);
+        if !matches!(flow, Ok(Continue(()))) {
+            return iterator_close(scope, iter, flow);
         }
     }
 
     Ok(Continue(()))
 }
 
+/// Calls the `return` method of an iterator that is exited early (the IteratorClose abstract operation).
+///
+/// If `completion` is an error, it takes precedence over any error thrown by `return`.
+/// Otherwise, `return` must return an object.
+pub fn iterator_close<T>(scope: &mut LocalScope<'_>, iter: Value, completion: Result<T, Value>) -> Result<T, Value> {
+    let result = iter
+        .get_property(sym::return_.to_key(scope), scope)
+        .root(scope)
+        .and_then(|method| {
+            if method.is_nullish() {
+                return Ok(None);
+            }
+            method.apply(This::bound(iter), CallArgs::empty(), scope).root(scope).map(Some)
+        });
+
+    let completion = completion?;
+    match result? {
+        Some(result) if !matches!(result.unpack(), ValueKind::Object(_)) => {
+            throw!(scope, TypeError, "Iterator result is not an object")
+        }
+        _ => Ok(completion),
+    }
+}
+
+/// Calls the `@@iterator` method of an iterable value and calls `f` for each element of the returned iterator
+pub fn for_each_iterable_element<B, F: FnMut(&mut LocalScope<'_>, Value) -> Result<ControlFlow<B>, Value>>(
+    scope: &mut LocalScope<'_>,
+    iterable: Value,
+    f: F,
+) -> Result<ControlFlow<B>, Value> {
+    let symbol_iterator = scope.statics.symbol_iterator;
+    let method = iterable
+        .get_property(symbol_iterator.to_key(scope), scope)
+        .root(scope)?;
+    if !matches!(method.type_of(scope), Typeof::Function) {
+        throw!(scope, TypeError, "object is not iterable")
+    }
+
+    let iter = method.apply(This::bound(iterable), CallArgs::empty(), scope).root(scope)?;
+    for_each_js_iterator_element(scope, iter, f)
+}
+
+/// Validates the optional `mapFn` and `thisArg` arguments of `Array.from` and `Array.fromAsync`
+fn array_from_mapper(scope: &mut LocalScope<'_>, args: &[Value]) -> Result<Option<(Value, Value)>, Value> {
+    match args.get(1).copied().and_then(Value::into_option) {
+        None => Ok(None),
+        Some(mapper) if matches!(mapper.type_of(scope), Typeof::Function) => {
+            Ok(Some((mapper, args.get(2).unwrap_or_undefined())))
+        }
+        Some(_) => throw!(scope, TypeError, "Array.from mapper is not a function"),
+    }
+}
+
+pub(crate) fn apply_array_from_mapper(
+    scope: &mut LocalScope<'_>,
+    mapper: Option<(Value, Value)>,
+    value: Value,
+    index: usize,
+) -> Result<Value, Value> {
+    match mapper {
+        Some((mapper, this_arg)) => mapper
+            .apply(
+                This::bound(this_arg),
+                [value, Value::number(index as f64)].into(),
+                scope,
+            )
+            .root(scope),
+        None => Ok(value),
+    }
+}
+
 pub fn from(cx: CallContext) -> Result<Value, Value> {
-    fn with_iterator(scope: &mut LocalScope, items: Value, mapper: Option<Value>) -> Result<Value, Value> {
+    fn with_iterator(scope: &mut LocalScope, items: Value, mapper: Option<(Value, Value)>) -> Result<Value, Value> {
         let mut values = Vec::new();
 
         let Continue(()) = for_each_js_iterator_element(scope, items, |scope, value| {
-            let value = match &mapper {
-                Some(mapper) => mapper.apply(This::default(), [value].into(), scope).root(scope)?,
-                None => value,
-            };
+            let value = apply_array_from_mapper(scope, mapper, value, values.len())?;
             values.push(PropertyValue::static_default(value));
             Ok(ControlFlow::<Infallible, _>::Continue(()))
         })?;
@@ -678,17 +933,14 @@ pub fn from(cx: CallContext) -> Result<Value, Value> {
         Ok(Value::object(scope.register(values)))
     }
 
-    fn with_array_like(scope: &mut LocalScope, items: Value, mapper: Option<Value>) -> Result<Value, Value> {
+    fn with_array_like(scope: &mut LocalScope, items: Value, mapper: Option<(Value, Value)>) -> Result<Value, Value> {
         let len = items.length_of_array_like(scope)?;
 
         let mut values = Vec::new();
 
         for i in 0..len {
             let value = items.get_property(i.to_key(scope), scope).root(scope)?;
-            let value = match &mapper {
-                Some(mapper) => mapper.apply(This::default(), [value].into(), scope).root(scope)?,
-                None => value,
-            };
+            let value = apply_array_from_mapper(scope, mapper, value, i)?;
             values.push(PropertyValue::static_default(value));
         }
 
@@ -696,10 +948,8 @@ pub fn from(cx: CallContext) -> Result<Value, Value> {
         Ok(Value::object(scope.register(values)))
     }
 
-    let mut args = cx.args.into_iter();
-
-    let items = args.next().unwrap_or_undefined();
-    let mapper = args.next();
+    let items = cx.args.first().unwrap_or_undefined();
+    let mapper = array_from_mapper(cx.scope, &cx.args)?;
 
     let items_iterator = {
         let iterator = cx.scope.statics.symbol_iterator;
@@ -720,49 +970,347 @@ pub fn from(cx: CallContext) -> Result<Value, Value> {
     }
 }
 
+pub fn of(cx: CallContext) -> Result<Value, Value> {
+    Ok(register_array(cx.scope, cx.args))
+}
+
+pub fn from_async(cx: CallContext) -> Result<Value, Value> {
+    let promise = cx.scope.mk_promise();
+
+    if let Err(err) = cx.scope.catch_exception(|sc| from_async_inner(sc, &cx.args, promise)) {
+        // Errors are never thrown synchronously but reject the returned promise
+        FromAsyncState::reject(cx.scope, promise, err);
+    }
+
+    Ok(Value::object(promise))
+}
+
+fn from_async_inner(scope: &mut LocalScope<'_>, args: &[Value], promise: ObjectId) -> Result<(), Value> {
+    let items = args.first().unwrap_or_undefined();
+    let mapper = array_from_mapper(scope, args)?;
+
+    let get_method = |scope: &mut LocalScope<'_>, symbol: Symbol| -> Result<Option<Value>, Value> {
+        Ok(items
+            .get_property(symbol.to_key(scope), scope)
+            .root(scope)?
+            .into_option())
+    };
+
+    let symbol_async_iterator = scope.statics.symbol_async_iterator;
+    let symbol_iterator = scope.statics.symbol_iterator;
+    let (source, next_method, async_iterator, length) = if let Some(method) = get_method(scope, symbol_async_iterator)?
+    {
+        let iterator = method.apply(This::bound(items), CallArgs::empty(), scope).root(scope)?;
+        let next = iterator.get_property(sym::next.to_key(scope), scope).root(scope)?;
+        (iterator, Some(next), true, 0)
+    } else if let Some(method) = get_method(scope, symbol_iterator)? {
+        let iterator = method.apply(This::bound(items), CallArgs::empty(), scope).root(scope)?;
+        let next = iterator.get_property(sym::next.to_key(scope), scope).root(scope)?;
+        (iterator, Some(next), false, 0)
+    } else {
+        let items = Value::object(items.to_object(scope)?);
+        let length = items.length_of_array_like(scope)?;
+        (items, None, false, length)
+    };
+
+    let state = Rc::new(FromAsyncState {
+        source,
+        next_method,
+        async_iterator,
+        length,
+        mapper,
+        values: RefCell::new(Vec::new()),
+        promise,
+    });
+    state.advance(scope)
+}
+
+/// The state of a single `Array.fromAsync` call, shared by the `.then()` callbacks that drive it
+#[derive(Debug, Trace)]
+struct FromAsyncState {
+    /// The iterator, or the array-like object if the input is not iterable
+    source: Value,
+    /// The `next` method of the iterator, `None` for array-likes
+    next_method: Option<Value>,
+    /// Whether `source` is an async iterator, in which case only the iterator results are awaited
+    async_iterator: bool,
+    /// The length of the array-like object
+    length: usize,
+    mapper: Option<(Value, Value)>,
+    values: RefCell<Vec<Value>>,
+    /// The promise returned by `Array.fromAsync`
+    promise: ObjectId,
+}
+
+impl FromAsyncState {
+    /// Requests the next element from the source, or resolves the promise once it is exhausted
+    fn advance(self: &Rc<Self>, scope: &mut LocalScope<'_>) -> Result<(), Value> {
+        match self.next_method {
+            Some(next) => {
+                let result = next
+                    .apply(This::bound(self.source), CallArgs::empty(), scope)
+                    .root(scope)?;
+                if self.async_iterator {
+                    self.await_value(scope, result, FromAsyncStepKind::IteratorResult)
+                } else {
+                    self.iterator_result(scope, result)
+                }
+            }
+            None => {
+                let index = self.values.borrow().len();
+                if index >= self.length {
+                    self.finish(scope);
+                    return Ok(());
+                }
+
+                let value = self.source.get_property(index.to_key(scope), scope).root(scope)?;
+                self.await_value(scope, value, FromAsyncStepKind::Element)
+            }
+        }
+    }
+
+    fn iterator_result(self: &Rc<Self>, scope: &mut LocalScope<'_>, result: Value) -> Result<(), Value> {
+        if !matches!(result.unpack(), ValueKind::Object(_)) {
+            throw!(scope, TypeError, "Iterator result is not an object");
+        }
+
+        if result
+            .get_property(sym::done.to_key(scope), scope)
+            .root(scope)?
+            .is_truthy(scope)
+        {
+            self.finish(scope);
+            return Ok(());
+        }
+
+        let value = result.get_property(sym::value.to_key(scope), scope).root(scope)?;
+        if self.async_iterator {
+            self.element(scope, value)
+        } else {
+            // Values of sync iterators are awaited, as if iterating over them with `for await`
+            self.await_value(scope, value, FromAsyncStepKind::Element)
+        }
+    }
+
+    fn element(self: &Rc<Self>, scope: &mut LocalScope<'_>, value: Value) -> Result<(), Value> {
+        if self.mapper.is_some() {
+            let index = self.values.borrow().len();
+            let value = apply_array_from_mapper(scope, self.mapper, value, index)?;
+            self.await_value(scope, value, FromAsyncStepKind::MappedElement)
+        } else {
+            self.values.borrow_mut().push(value);
+            self.advance(scope)
+        }
+    }
+
+    fn await_value(
+        self: &Rc<Self>,
+        scope: &mut LocalScope<'_>,
+        value: Value,
+        kind: FromAsyncStepKind,
+    ) -> Result<(), Value> {
+        let promise = wrap_resolved_promise(scope, value);
+        let on_fulfilled = FromAsyncStep::new(scope, self.clone(), kind);
+        let on_fulfilled = Value::object(scope.register(on_fulfilled));
+        let on_rejected = FromAsyncStep::new(scope, self.clone(), FromAsyncStepKind::Reject);
+        let on_rejected = Value::object(scope.register(on_rejected));
+        invoke_then(scope, promise, on_fulfilled, on_rejected)?;
+        Ok(())
+    }
+
+    fn finish(&self, scope: &mut LocalScope<'_>) {
+        let values = register_array(scope, self.values.borrow().iter().copied());
+        scope.drive_promise(
+            PromiseAction::Resolve,
+            self.promise.extract::<Promise>(scope).unwrap(),
+            self.promise,
+            [values].into(),
+        );
+    }
+
+    fn reject(scope: &mut LocalScope<'_>, promise: ObjectId, reason: Value) {
+        scope.drive_promise(
+            PromiseAction::Reject,
+            promise.extract::<Promise>(scope).unwrap(),
+            promise,
+            [reason].into(),
+        );
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum FromAsyncStepKind {
+    /// Receives the awaited result of an async iterator's `next()` call
+    IteratorResult,
+    /// Receives an awaited element
+    Element,
+    /// Receives the awaited return value of the mapper function
+    MappedElement,
+    /// Receives the reason of a rejected promise
+    Reject,
+}
+
+unsafe impl Trace for FromAsyncStepKind {
+    fn trace(&self, _: &mut TraceCtxt<'_>) {}
+}
+
+/// A `.then()` callback that continues an `Array.fromAsync` call after an awaited value has settled
+#[derive(Debug, Trace)]
+struct FromAsyncStep {
+    state: Rc<FromAsyncState>,
+    kind: FromAsyncStepKind,
+    obj: OrdObject,
+}
+
+impl FromAsyncStep {
+    fn new(vm: &Vm, state: Rc<FromAsyncState>, kind: FromAsyncStepKind) -> Self {
+        Self {
+            state,
+            kind,
+            obj: OrdObject::new(vm),
+        }
+    }
+}
+
+impl Object for FromAsyncStep {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        own_keys
+    );
+
+    fn apply(
+        &self,
+        _callee: ObjectId,
+        _this: This,
+        args: CallArgs,
+        scope: &mut LocalScope<'_>,
+    ) -> Result<Unrooted, Unrooted> {
+        let value = args.first().unwrap_or_undefined();
+
+        let result = scope.catch_exception(|scope| match self.kind {
+            FromAsyncStepKind::IteratorResult => self.state.iterator_result(scope, value),
+            FromAsyncStepKind::Element => self.state.element(scope, value),
+            FromAsyncStepKind::MappedElement => {
+                self.state.values.borrow_mut().push(value);
+                self.state.advance(scope)
+            }
+            FromAsyncStepKind::Reject => Err(value),
+        });
+
+        if let Err(err) = result {
+            FromAsyncState::reject(scope, self.state.promise, err);
+        }
+
+        Ok(Value::undefined().into())
+    }
+
+    fn type_of(&self, _: &Vm) -> Typeof {
+        Typeof::Function
+    }
+
+    extract!(self);
+}
+
+/// Compares two array elements as specified by the SortCompare abstract operation.
+///
+/// `undefined` is always sorted to the end. Without a compare function, elements are compared by their string representation.
+fn sort_compare(scope: &mut LocalScope<'_>, x: Value, y: Value, compare_fn: Option<Value>) -> Result<Ordering, Value> {
+    match (x.unpack(), y.unpack()) {
+        (ValueKind::Undefined(_), ValueKind::Undefined(_)) => return Ok(Ordering::Equal),
+        (ValueKind::Undefined(_), _) => return Ok(Ordering::Greater),
+        (_, ValueKind::Undefined(_)) => return Ok(Ordering::Less),
+        _ => {}
+    }
+
+    if let Some(compare_fn) = compare_fn {
+        let ordering = compare_fn
+            .apply(This::default(), [x, y].into(), scope)
+            .root(scope)?
+            .to_number(scope)?;
+        return Ok(ordering.partial_cmp(&0.0).unwrap_or(Ordering::Equal));
+    }
+
+    let x = x.to_js_string(scope)?;
+    let y = y.to_js_string(scope)?;
+    Ok(x.res(scope).encode_utf16().cmp(y.res(scope).encode_utf16()))
+}
+
 // This implements an insertion sort for now since it's simple and okay for small arrays.
 // We can always improve it later.
 // It's worth noting that we unfortunately cannot use the sorting algorithm in the standard library,
 // since that must happen in a closure that needs to return an `Ordering`, without the ability to
 // return errors, but calling into JS can throw exceptions.
-pub fn sort(cx: CallContext) -> Result<Value, Value> {
-    let this = Value::object(cx.this.to_object(cx.scope)?);
-    let len = this.length_of_array_like(cx.scope)?;
-
-    let Some(compare_fn) = cx.args.first().cloned() else {
-        throw!(
-            cx.scope,
-            Error,
-            "Array.prototype.sort currently requires an explicit compare function, try `.sort((a, b) => a - b)` to sort from lowest to highest"
-        );
-    };
-
-    for i in 1..len {
+pub(crate) fn sort_values_by(
+    scope: &mut LocalScope<'_>,
+    values: &mut [Value],
+    mut compare: impl FnMut(&mut LocalScope<'_>, Value, Value) -> Result<Ordering, Value>,
+) -> Result<(), Value> {
+    for i in 1..values.len() {
         for j in (1..=i).rev() {
-            let idx = j;
-            let prev_idx = j - 1;
-
-            let previous = this.get_property(prev_idx.to_key(cx.scope), cx.scope).root(cx.scope)?;
-            let current = this.get_property(idx.to_key(cx.scope), cx.scope).root(cx.scope)?;
-            let ordering = compare_fn
-                .apply(This::default(), [previous, current].into(), cx.scope)
-                .root(cx.scope)?
-                .to_int32(cx.scope)?;
-
-            if ordering > 0 {
-                this.set_property(
-                    prev_idx.to_key(cx.scope),
-                    PropertyValue::static_default(current),
-                    cx.scope,
-                )?;
-                this.set_property(idx.to_key(cx.scope), PropertyValue::static_default(previous), cx.scope)?;
+            if compare(scope, values[j - 1], values[j])? == Ordering::Greater {
+                values.swap(j - 1, j);
             } else {
                 break;
             }
         }
     }
 
-    Ok(cx.this)
+    Ok(())
+}
+
+fn sort_values(scope: &mut LocalScope<'_>, values: &mut [Value], compare_fn: Option<Value>) -> Result<(), Value> {
+    sort_values_by(scope, values, |scope, x, y| sort_compare(scope, x, y, compare_fn))
+}
+
+pub(crate) fn sort_compare_fn(scope: &mut LocalScope<'_>, compare_fn: Option<&Value>) -> Result<Option<Value>, Value> {
+    match compare_fn.copied().and_then(Value::into_option) {
+        None => Ok(None),
+        Some(compare_fn) if matches!(compare_fn.type_of(scope), Typeof::Function) => Ok(Some(compare_fn)),
+        Some(_) => throw!(
+            scope,
+            TypeError,
+            "The comparison function must be either a function or undefined"
+        ),
+    }
+}
+
+pub fn sort(cx: CallContext) -> Result<Value, Value> {
+    let compare_fn = sort_compare_fn(cx.scope, cx.args.first())?;
+    let this = Value::object(cx.this.to_object(cx.scope)?);
+    let len = this.length_of_array_like(cx.scope)?;
+
+    // Holes are skipped when collecting the elements and end up after all of the sorted elements
+    let mut values = Vec::new();
+    for k in 0..len {
+        if let Some(value) = this
+            .get_property_descriptor(k.to_key(cx.scope), cx.scope)
+            .root_err(cx.scope)?
+        {
+            values.push(value.get_or_apply(cx.scope, This::bound(this)).root(cx.scope)?);
+        }
+    }
+
+    sort_values(cx.scope, &mut values, compare_fn)?;
+
+    let sorted_len = values.len();
+    for (k, value) in values.into_iter().enumerate() {
+        set_property_or_throw(this, k.to_key(cx.scope), value, cx.scope)?;
+    }
+    for k in sorted_len..len {
+        delete_property_or_throw(this, k.to_key(cx.scope), cx.scope)?;
+    }
+
+    Ok(this)
 }
 
 pub fn splice(mut cx: CallContext) -> Result<Value, Value> {
@@ -799,7 +1347,7 @@ pub fn splice(mut cx: CallContext) -> Result<Value, Value> {
         // Since we delete more than we insert, overwrite elements at the delete index
         for (i, value) in cx.args.iter().skip(2).enumerate() {
             let i = i + start;
-            this.set_property(i.to_key(cx.scope), PropertyValue::static_default(*value), cx.scope)?;
+            set_property_or_throw(this, i.to_key(cx.scope), *value, cx.scope)?;
         }
 
         // Now shift the rest to the left and update the length
@@ -826,9 +1374,80 @@ pub fn splice(mut cx: CallContext) -> Result<Value, Value> {
 
         for (i, value) in cx.args.iter().skip(2).enumerate() {
             let i = i + start;
-            this.set_property(i.to_key(cx.scope), PropertyValue::static_default(*value), cx.scope)?;
+            set_property_or_throw(this, i.to_key(cx.scope), *value, cx.scope)?;
         }
     }
 
     Ok(cx.scope.register(Array::from_vec(values, cx.scope)).into())
 }
+
+/// Reads the elements of an array-like at indices `range` into a vector, with holes being read as `undefined`
+fn array_like_elements(scope: &mut LocalScope<'_>, this: Value, range: Range<usize>) -> Result<Vec<Value>, Value> {
+    let mut values = Vec::with_capacity(range.len());
+    for k in range {
+        values.push(this.get_property(k.to_key(scope), scope).root(scope)?);
+    }
+    Ok(values)
+}
+
+fn register_array(scope: &mut LocalScope<'_>, values: impl IntoIterator<Item = Value>) -> Value {
+    let values = values.into_iter().map(PropertyValue::static_default).collect();
+    let values = Array::from_vec(values, scope);
+    Value::object(scope.register(values))
+}
+
+pub fn to_reversed(cx: CallContext) -> Result<Value, Value> {
+    let this = Value::object(cx.this.to_object(cx.scope)?);
+    let len = this.length_of_array_like(cx.scope)?;
+
+    let values = array_like_elements(cx.scope, this, 0..len)?;
+    Ok(register_array(cx.scope, values.into_iter().rev()))
+}
+
+pub fn to_sorted(cx: CallContext) -> Result<Value, Value> {
+    let compare_fn = sort_compare_fn(cx.scope, cx.args.first())?;
+    let this = Value::object(cx.this.to_object(cx.scope)?);
+    let len = this.length_of_array_like(cx.scope)?;
+
+    let mut values = array_like_elements(cx.scope, this, 0..len)?;
+    sort_values(cx.scope, &mut values, compare_fn)?;
+    Ok(register_array(cx.scope, values))
+}
+
+pub fn to_spliced(mut cx: CallContext) -> Result<Value, Value> {
+    let this = Value::object(cx.this.to_object(cx.scope)?);
+    let len = this.length_of_array_like(cx.scope)?;
+
+    let start = wrapping_index_val(cx.args.first().unwrap_or_undefined(), &mut cx, len)?;
+    let skip_count = match *cx.args {
+        [] => 0,
+        [_] => len - start,
+        [_, v, ..] => isize::clamp(v.to_integer_or_infinity(cx.scope)? as isize, 0, (len - start) as isize) as usize,
+    };
+    let items = cx.args.get(2..).unwrap_or_default();
+    require_valid_array_length(cx.scope, len - skip_count + items.len())?;
+
+    let mut values = array_like_elements(cx.scope, this, 0..start)?;
+    values.extend_from_slice(items);
+    values.extend(array_like_elements(cx.scope, this, start + skip_count..len)?);
+    Ok(register_array(cx.scope, values))
+}
+
+pub fn with(cx: CallContext) -> Result<Value, Value> {
+    let this = Value::object(cx.this.to_object(cx.scope)?);
+    let len = this.length_of_array_like(cx.scope)?;
+
+    let relative_index = cx.args.first().unwrap_or_undefined().to_integer_or_infinity(cx.scope)?;
+    let actual_index = if relative_index >= 0.0 {
+        relative_index
+    } else {
+        len as f64 + relative_index
+    };
+    if actual_index < 0.0 || actual_index >= len as f64 {
+        throw!(cx.scope, RangeError, "Invalid index: {}", relative_index);
+    }
+
+    let mut values = array_like_elements(cx.scope, this, 0..len)?;
+    values[actual_index as usize] = cx.args.get(1).unwrap_or_undefined();
+    Ok(register_array(cx.scope, values))
+}
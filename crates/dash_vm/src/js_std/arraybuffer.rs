@@ -1,26 +1,295 @@
+use dash_middle::interner::sym;
+
+use crate::localscope::LocalScope;
 use crate::throw;
-use crate::value::Value;
-use crate::value::arraybuffer::ArrayBuffer;
+use crate::value::arraybuffer::{ArrayBuffer, SharedArrayBuffer};
+use crate::value::dataview::DataView;
 use crate::value::function::native::CallContext;
 use crate::value::object::OrdObject;
 use crate::value::ops::conversions::ValueConversion;
+use crate::value::propertykey::ToPropertyKey;
+use crate::value::typedarray::TypedArray;
+use crate::value::{Root, Unpack, Value, ValueContext, ValueKind};
 
 use super::receiver_t;
 
-pub fn constructor(cx: CallContext) -> Result<Value, Value> {
-    let length = match cx.args.first() {
-        Some(length) => length.to_number(cx.scope)? as usize,
-        None => 0,
+/// Creates a fixed-length or resizable buffer from the `length` and `options` constructor arguments
+fn allocate(cx: &mut CallContext, obj: OrdObject) -> Result<ArrayBuffer, Value> {
+    let length = cx.args.first().unwrap_or_undefined().to_index(cx.scope)?;
+    let max_byte_length = match cx.args.get(1).map(|v| v.unpack()) {
+        Some(ValueKind::Object(options)) => options
+            .get_property(sym::maxByteLength.to_key(cx.scope), cx.scope)
+            .root(cx.scope)?
+            .into_option()
+            .map(|max| max.to_index(cx.scope))
+            .transpose()?,
+        _ => None,
     };
-    if let Some(new_target) = cx.new_target {
-        let buf = ArrayBuffer::with_capacity(length, OrdObject::instance_for_new_target(new_target, cx.scope)?);
-        Ok(cx.scope.register(buf).into())
-    } else {
+
+    let buf = match max_byte_length {
+        Some(max_byte_length) if length > max_byte_length => {
+            throw!(
+                cx.scope,
+                RangeError,
+                "Invalid array buffer length: {} exceeds maxByteLength {}",
+                length,
+                max_byte_length
+            )
+        }
+        Some(max_byte_length) => ArrayBuffer::resizable(length, max_byte_length, obj),
+        None => ArrayBuffer::with_capacity(length, obj),
+    };
+
+    match buf {
+        Some(buf) => Ok(buf),
+        None => allocation_failed(cx.scope),
+    }
+}
+
+/// Throws the RangeError for buffer lengths that cannot be allocated
+fn allocation_failed<T>(sc: &mut LocalScope<'_>) -> Result<T, Value> {
+    throw!(sc, RangeError, "Array buffer allocation failed")
+}
+
+pub fn constructor(mut cx: CallContext) -> Result<Value, Value> {
+    let Some(new_target) = cx.new_target else {
         throw!(cx.scope, TypeError, "ArrayBuffer constructor requires new")
+    };
+    let obj = OrdObject::instance_for_new_target(new_target, cx.scope)?;
+    let buf = allocate(&mut cx, obj)?;
+    Ok(cx.scope.register(buf).into())
+}
+
+pub fn is_view(cx: CallContext) -> Result<Value, Value> {
+    let is_view = match cx.args.first().unwrap_or_undefined().unpack() {
+        ValueKind::Object(obj) => {
+            obj.extract::<TypedArray>(cx.scope).is_some() || obj.extract::<DataView>(cx.scope).is_some()
+        }
+        _ => false,
+    };
+    Ok(Value::boolean(is_view))
+}
+
+/// Returns the `ArrayBuffer` receiver, which must not be a `SharedArrayBuffer`
+fn this_arraybuffer<'a>(
+    sc: &mut LocalScope<'_>,
+    this: &'a Value,
+    what: &'static str,
+) -> Result<&'a ArrayBuffer, Value> {
+    let buf = receiver_t::<ArrayBuffer>(sc, this, what)?;
+    if this.extract::<SharedArrayBuffer>(sc).is_some() {
+        throw!(sc, TypeError, "{} called on incompatible receiver", what);
     }
+    Ok(buf)
+}
+
+/// Copies the bytes in the range given by the `start` and `end` arguments into a new fixed-length buffer
+fn slice_inner(
+    sc: &mut LocalScope<'_>,
+    args: &[Value],
+    buf: &ArrayBuffer,
+    what: &'static str,
+) -> Result<Vec<u8>, Value> {
+    if buf.is_detached() {
+        throw!(sc, TypeError, "{} called on detached ArrayBuffer", what);
+    }
+
+    let len = buf.len();
+    let relative_index = |value: f64| {
+        if value < 0.0 {
+            (len as f64 + value).max(0.0) as usize
+        } else {
+            value.min(len as f64) as usize
+        }
+    };
+    let start = relative_index(args.first().unwrap_or_undefined().to_integer_or_infinity(sc)?);
+    let end = match args.get(1).copied().and_then(Value::into_option) {
+        Some(end) => relative_index(end.to_integer_or_infinity(sc)?),
+        None => len,
+    };
+
+    if buf.is_detached() {
+        throw!(sc, TypeError, "{} called on detached ArrayBuffer", what);
+    }
+
+    // Converting the arguments may have shrunk the buffer
+    Ok(buf
+        .storage()
+        .get(start..end)
+        .unwrap_or_default()
+        .iter()
+        .map(|b| b.get())
+        .collect())
 }
 
 pub fn byte_length(cx: CallContext) -> Result<Value, Value> {
-    let this = receiver_t::<ArrayBuffer>(cx.scope, &cx.this, "ArrayBuffer.prototype.byteLength")?;
+    let this = this_arraybuffer(cx.scope, &cx.this, "ArrayBuffer.prototype.byteLength")?;
     Ok(Value::number(this.len() as f64))
 }
+
+pub fn max_byte_length(cx: CallContext) -> Result<Value, Value> {
+    let this = this_arraybuffer(cx.scope, &cx.this, "ArrayBuffer.prototype.maxByteLength")?;
+    Ok(Value::number(this.max_byte_length() as f64))
+}
+
+pub fn resizable(cx: CallContext) -> Result<Value, Value> {
+    let this = this_arraybuffer(cx.scope, &cx.this, "ArrayBuffer.prototype.resizable")?;
+    Ok(Value::boolean(this.is_resizable()))
+}
+
+pub fn detached(cx: CallContext) -> Result<Value, Value> {
+    let this = this_arraybuffer(cx.scope, &cx.this, "ArrayBuffer.prototype.detached")?;
+    Ok(Value::boolean(this.is_detached()))
+}
+
+pub fn slice(cx: CallContext) -> Result<Value, Value> {
+    let this = this_arraybuffer(cx.scope, &cx.this, "ArrayBuffer.prototype.slice")?;
+    let bytes = slice_inner(cx.scope, &cx.args, this, "ArrayBuffer.prototype.slice")?;
+    let buf = ArrayBuffer::from_storage(cx.scope, bytes.into_iter().map(Into::into).collect());
+    Ok(cx.scope.register(buf).into())
+}
+
+pub fn resize(cx: CallContext) -> Result<Value, Value> {
+    let this = this_arraybuffer(cx.scope, &cx.this, "ArrayBuffer.prototype.resize")?;
+    if !this.is_resizable() {
+        throw!(
+            cx.scope,
+            TypeError,
+            "ArrayBuffer.prototype.resize called on fixed-length ArrayBuffer"
+        );
+    }
+
+    let new_length = cx.args.first().unwrap_or_undefined().to_index(cx.scope)?;
+    if this.is_detached() {
+        throw!(
+            cx.scope,
+            TypeError,
+            "ArrayBuffer.prototype.resize called on detached ArrayBuffer"
+        );
+    }
+    if new_length > this.max_byte_length() {
+        throw!(
+            cx.scope,
+            RangeError,
+            "Invalid array buffer length: {} exceeds maxByteLength {}",
+            new_length,
+            this.max_byte_length()
+        );
+    }
+
+    // SAFETY: no slices of the storage are alive here
+    if !unsafe { this.resize(new_length) } {
+        return allocation_failed(cx.scope);
+    }
+    Ok(Value::undefined())
+}
+
+/// Implements the ArrayBufferCopyAndDetach abstract operation
+fn transfer_inner(cx: CallContext, preserve_resizability: bool, what: &'static str) -> Result<Value, Value> {
+    let this = this_arraybuffer(cx.scope, &cx.this, what)?;
+    let new_length = match cx.args.first().copied().and_then(Value::into_option) {
+        Some(new_length) => new_length.to_index(cx.scope)?,
+        None => this.len(),
+    };
+    if this.is_detached() {
+        throw!(cx.scope, TypeError, "{} called on detached ArrayBuffer", what);
+    }
+
+    let obj = OrdObject::with_prototype(cx.scope.statics.arraybuffer_prototype);
+    let buf = if preserve_resizability && this.is_resizable() {
+        let max_byte_length = this.max_byte_length();
+        if new_length > max_byte_length {
+            throw!(
+                cx.scope,
+                RangeError,
+                "Invalid array buffer length: {} exceeds maxByteLength {}",
+                new_length,
+                max_byte_length
+            );
+        }
+        ArrayBuffer::resizable(new_length, max_byte_length, obj)
+    } else {
+        ArrayBuffer::with_capacity(new_length, obj)
+    };
+    let Some(buf) = buf else {
+        return allocation_failed(cx.scope);
+    };
+
+    for (dest, src) in buf.storage().iter().zip(this.storage()) {
+        dest.set(src.get());
+    }
+    this.detach();
+
+    Ok(cx.scope.register(buf).into())
+}
+
+pub fn transfer(cx: CallContext) -> Result<Value, Value> {
+    transfer_inner(cx, true, "ArrayBuffer.prototype.transfer")
+}
+
+pub fn transfer_to_fixed_length(cx: CallContext) -> Result<Value, Value> {
+    transfer_inner(cx, false, "ArrayBuffer.prototype.transferToFixedLength")
+}
+
+pub mod shared {
+    use super::*;
+
+    pub fn constructor(mut cx: CallContext) -> Result<Value, Value> {
+        let Some(new_target) = cx.new_target else {
+            throw!(cx.scope, TypeError, "SharedArrayBuffer constructor requires new")
+        };
+        let obj = OrdObject::instance_for_new_target(new_target, cx.scope)?;
+        let buf = SharedArrayBuffer::new(allocate(&mut cx, obj)?);
+        Ok(cx.scope.register(buf).into())
+    }
+
+    pub fn byte_length(cx: CallContext) -> Result<Value, Value> {
+        let this = receiver_t::<SharedArrayBuffer>(cx.scope, &cx.this, "SharedArrayBuffer.prototype.byteLength")?;
+        Ok(Value::number(this.inner().len() as f64))
+    }
+
+    pub fn max_byte_length(cx: CallContext) -> Result<Value, Value> {
+        let this = receiver_t::<SharedArrayBuffer>(cx.scope, &cx.this, "SharedArrayBuffer.prototype.maxByteLength")?;
+        Ok(Value::number(this.inner().max_byte_length() as f64))
+    }
+
+    pub fn growable(cx: CallContext) -> Result<Value, Value> {
+        let this = receiver_t::<SharedArrayBuffer>(cx.scope, &cx.this, "SharedArrayBuffer.prototype.growable")?;
+        Ok(Value::boolean(this.inner().is_resizable()))
+    }
+
+    pub fn grow(cx: CallContext) -> Result<Value, Value> {
+        let this = receiver_t::<SharedArrayBuffer>(cx.scope, &cx.this, "SharedArrayBuffer.prototype.grow")?.inner();
+        if !this.is_resizable() {
+            throw!(
+                cx.scope,
+                TypeError,
+                "SharedArrayBuffer.prototype.grow called on non-growable SharedArrayBuffer"
+            );
+        }
+
+        let new_length = cx.args.first().unwrap_or_undefined().to_index(cx.scope)?;
+        if new_length < this.len() || new_length > this.max_byte_length() {
+            throw!(cx.scope, RangeError, "Invalid length parameter: {}", new_length);
+        }
+
+        // SAFETY: no slices of the storage are alive here
+        if !unsafe { this.resize(new_length) } {
+            return allocation_failed(cx.scope);
+        }
+        Ok(Value::undefined())
+    }
+
+    pub fn slice(cx: CallContext) -> Result<Value, Value> {
+        let this = receiver_t::<SharedArrayBuffer>(cx.scope, &cx.this, "SharedArrayBuffer.prototype.slice")?.inner();
+        let bytes = slice_inner(cx.scope, &cx.args, this, "SharedArrayBuffer.prototype.slice")?;
+        let obj = OrdObject::with_prototype(cx.scope.statics.sharedarraybuffer_prototype);
+        let Some(buf) = ArrayBuffer::with_capacity(bytes.len(), obj) else {
+            return allocation_failed(cx.scope);
+        };
+        for (dest, src) in buf.storage().iter().zip(bytes) {
+            dest.set(src);
+        }
+        Ok(cx.scope.register(SharedArrayBuffer::new(buf)).into())
+    }
+}
@@ -0,0 +1,244 @@
+use crate::gc::ObjectId;
+use crate::localscope::LocalScope;
+use crate::value::error::TypeError;
+use crate::value::function::async_generator::{
+    AsyncGeneratorAction, AsyncGeneratorIterator, AsyncGeneratorRequest, AsyncGeneratorRequestKind, AsyncGeneratorTask,
+};
+use crate::value::function::generator::GeneratorState;
+use crate::value::function::native::CallContext;
+use crate::value::object::This;
+use crate::value::promise::{Promise, wrap_resolved_promise};
+use crate::value::root_ext::RootErrExt;
+use crate::value::{Unpack, Value, ValueContext, ValueKind};
+use crate::{PromiseAction, throw};
+
+use super::generator::{GeneratorResult, create_generator_value, resume_next, resume_return, resume_throw};
+
+fn enqueue(cx: CallContext, kind: AsyncGeneratorRequestKind, what: &'static str) -> Result<Value, Value> {
+    let value = cx.args.first().unwrap_or_undefined();
+    let promise = cx.scope.mk_promise();
+
+    let generator = match cx.this.unpack() {
+        ValueKind::Object(generator) if generator.extract::<AsyncGeneratorIterator>(cx.scope).is_some() => generator,
+        _ => {
+            // Errors from an incompatible receiver are reported through the returned promise
+            let error = TypeError::new(cx.scope, format!("{what} invoked on incompatible receiver"));
+            let error = Value::object(cx.scope.register(error));
+            let promise_ref = promise.extract::<Promise>(cx.scope).unwrap();
+            cx.scope
+                .drive_promise(PromiseAction::Reject, promise_ref, promise, [error].into());
+            return Ok(Value::object(promise));
+        }
+    };
+
+    generator_ref(cx.scope, &generator)?
+        .queue()
+        .borrow_mut()
+        .push_back(AsyncGeneratorRequest { kind, value, promise });
+
+    drain_queue(cx.scope, generator)?;
+
+    Ok(Value::object(promise))
+}
+
+pub fn next(cx: CallContext) -> Result<Value, Value> {
+    enqueue(cx, AsyncGeneratorRequestKind::Next, "AsyncGenerator.prototype.next")
+}
+
+pub fn return_(cx: CallContext) -> Result<Value, Value> {
+    enqueue(cx, AsyncGeneratorRequestKind::Return, "AsyncGenerator.prototype.return")
+}
+
+pub fn throw(cx: CallContext) -> Result<Value, Value> {
+    enqueue(cx, AsyncGeneratorRequestKind::Throw, "AsyncGenerator.prototype.throw")
+}
+
+fn generator_ref<'a>(scope: &mut LocalScope<'_>, generator: &'a ObjectId) -> Result<&'a AsyncGeneratorIterator, Value> {
+    match generator.extract::<AsyncGeneratorIterator>(scope) {
+        Some(generator) => Ok(generator),
+        None => throw!(scope, TypeError, "Incompatible async generator"),
+    }
+}
+
+/// Settles the promise of the request at the front of the queue and removes it
+fn settle_front(
+    scope: &mut LocalScope<'_>,
+    generator: ObjectId,
+    action: PromiseAction,
+    value: Value,
+) -> Result<(), Value> {
+    let request = generator_ref(scope, &generator)?.queue().borrow_mut().pop_front();
+
+    if let Some(AsyncGeneratorRequest { promise, .. }) = request {
+        let promise_ref = promise.extract::<Promise>(scope).unwrap();
+        scope.drive_promise(action, promise_ref, promise, [value].into());
+    }
+
+    Ok(())
+}
+
+/// Waits for the value to settle, and then continues the generator with the given actions
+fn await_value(
+    scope: &mut LocalScope<'_>,
+    generator: ObjectId,
+    value: Value,
+    on_fulfilled: AsyncGeneratorAction,
+    on_rejected: AsyncGeneratorAction,
+) -> Result<(), Value> {
+    let promise = wrap_resolved_promise(scope, value);
+    let on_fulfilled = scope.register(AsyncGeneratorTask::new(scope, generator, on_fulfilled));
+    let on_rejected = scope.register(AsyncGeneratorTask::new(scope, generator, on_rejected));
+
+    scope
+        .statics
+        .promise_then
+        .clone()
+        .apply(
+            This::bound(promise),
+            [Value::object(on_fulfilled), Value::object(on_rejected)].into(),
+            scope,
+        )
+        .root_err(scope)?;
+
+    Ok(())
+}
+
+/// Processes requests in the queue until the generator is suspended at an `await`, or the queue is empty
+fn drain_queue(scope: &mut LocalScope<'_>, generator: ObjectId) -> Result<(), Value> {
+    loop {
+        let generator_ref = generator_ref(scope, &generator)?;
+        if generator_ref.is_executing() {
+            return Ok(());
+        }
+
+        let Some((kind, value)) = generator_ref.queue().borrow().front().map(|req| (req.kind, req.value)) else {
+            return Ok(());
+        };
+
+        let inner = generator_ref.inner();
+        if kind != AsyncGeneratorRequestKind::Next && !inner.did_run() {
+            // Returning or throwing before the generator has started completes it without running any code
+            inner.state().replace(GeneratorState::Finished);
+        }
+
+        let is_finished = matches!(*inner.state().borrow(), GeneratorState::Finished);
+
+        match kind {
+            AsyncGeneratorRequestKind::Next if is_finished => {
+                let result = create_generator_value(scope, true, None)?;
+                settle_front(scope, generator, PromiseAction::Resolve, result)?;
+            }
+            AsyncGeneratorRequestKind::Throw if is_finished => {
+                settle_front(scope, generator, PromiseAction::Reject, value)?;
+            }
+            AsyncGeneratorRequestKind::Return if is_finished => {
+                generator_ref.set_executing(true);
+                await_value(
+                    scope,
+                    generator,
+                    value,
+                    AsyncGeneratorAction::Return,
+                    AsyncGeneratorAction::Reject,
+                )?;
+            }
+            AsyncGeneratorRequestKind::Return => {
+                // The value is awaited before returning from the suspended generator
+                generator_ref.set_executing(true);
+                return await_value(
+                    scope,
+                    generator,
+                    value,
+                    AsyncGeneratorAction::ResumeReturn,
+                    AsyncGeneratorAction::ResumeThrow,
+                );
+            }
+            AsyncGeneratorRequestKind::Next | AsyncGeneratorRequestKind::Throw => {
+                generator_ref.set_executing(true);
+                let action = match kind {
+                    AsyncGeneratorRequestKind::Next => AsyncGeneratorAction::ResumeNext,
+                    _ => AsyncGeneratorAction::ResumeThrow,
+                };
+                return resume(scope, generator, action, value);
+            }
+        }
+    }
+}
+
+/// Resumes the generator and handles the point at which it suspends
+fn resume(
+    scope: &mut LocalScope<'_>,
+    generator: ObjectId,
+    action: AsyncGeneratorAction,
+    value: Value,
+) -> Result<(), Value> {
+    let inner = generator_ref(scope, &generator)?.inner();
+    let result = scope.catch_exception(|scope| match action {
+        AsyncGeneratorAction::ResumeThrow => resume_throw(scope, inner, value),
+        AsyncGeneratorAction::ResumeReturn => resume_return(scope, inner, value),
+        _ => resume_next(scope, inner, value),
+    });
+
+    match result {
+        Ok(GeneratorResult::Await(value)) => await_value(
+            scope,
+            generator,
+            value,
+            AsyncGeneratorAction::ResumeNext,
+            AsyncGeneratorAction::ResumeThrow,
+        ),
+        Ok(GeneratorResult::Yield(value)) => await_value(
+            scope,
+            generator,
+            value,
+            AsyncGeneratorAction::Yield,
+            AsyncGeneratorAction::ResumeThrow,
+        ),
+        Ok(GeneratorResult::Return(value)) => await_value(
+            scope,
+            generator,
+            value,
+            AsyncGeneratorAction::Return,
+            AsyncGeneratorAction::Reject,
+        ),
+        Ok(GeneratorResult::Finished) => complete(scope, generator, PromiseAction::Resolve, None),
+        Err(err) => complete(scope, generator, PromiseAction::Reject, Some(err)),
+    }
+}
+
+/// Settles the current request and continues with the next one in the queue
+fn complete(
+    scope: &mut LocalScope<'_>,
+    generator: ObjectId,
+    action: PromiseAction,
+    value: Option<Value>,
+) -> Result<(), Value> {
+    let value = match action {
+        PromiseAction::Resolve => create_generator_value(scope, true, value)?,
+        PromiseAction::Reject => value.unwrap_or_undefined(),
+    };
+    settle_front(scope, generator, action, value)?;
+    generator_ref(scope, &generator)?.set_executing(false);
+    drain_queue(scope, generator)
+}
+
+/// Called once a promise awaited by the async generator is settled
+pub(crate) fn perform_action(
+    scope: &mut LocalScope<'_>,
+    generator: ObjectId,
+    action: AsyncGeneratorAction,
+    value: Value,
+) -> Result<(), Value> {
+    match action {
+        AsyncGeneratorAction::ResumeNext | AsyncGeneratorAction::ResumeThrow | AsyncGeneratorAction::ResumeReturn => {
+            resume(scope, generator, action, value)
+        }
+        AsyncGeneratorAction::Yield => {
+            let value = create_generator_value(scope, false, Some(value))?;
+            settle_front(scope, generator, PromiseAction::Resolve, value)?;
+            generator_ref(scope, &generator)?.set_executing(false);
+            drain_queue(scope, generator)
+        }
+        AsyncGeneratorAction::Return => complete(scope, generator, PromiseAction::Resolve, Some(value)),
+        AsyncGeneratorAction::Reject => complete(scope, generator, PromiseAction::Reject, Some(value)),
+    }
+}
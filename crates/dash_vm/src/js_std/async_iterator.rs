@@ -0,0 +1,93 @@
+use crate::localscope::LocalScope;
+use crate::throw;
+use crate::value::async_iterator::AsyncFromSyncIterator;
+use crate::value::function::args::CallArgs;
+use crate::value::function::bound::BoundFunction;
+use crate::value::function::native::CallContext;
+use crate::value::object::This;
+use crate::value::promise::{Promise, wrap_resolved_promise};
+use crate::value::propertykey::ToPropertyKey;
+use crate::value::{Root, Value, ValueContext};
+use dash_middle::interner::{Symbol, sym};
+
+use super::generator::create_generator_value;
+use super::receiver_t;
+
+/// Calls the method of the sync iterator and converts its result to a promise of an iterator result.
+/// If the sync iterator has no such method, `None` is returned.
+fn call_sync_method(
+    scope: &mut LocalScope<'_>,
+    sync_iterator: Value,
+    method: Symbol,
+    args: CallArgs,
+) -> Result<Option<Value>, Value> {
+    let result = scope.catch_exception(|scope| {
+        let method = sync_iterator.get_property(method.to_key(scope), scope).root(scope)?;
+        if method.is_nullish() {
+            return Ok(None);
+        }
+
+        let result = method.apply(This::bound(sync_iterator), args, scope).root(scope)?;
+        let done = result.get_property(sym::done.to_key(scope), scope).root(scope)?;
+        let value = result.get_property(sym::value.to_key(scope), scope).root(scope)?;
+        Ok(Some((done.is_truthy(scope), value)))
+    });
+
+    let (done, value) = match result {
+        Ok(Some(result)) => result,
+        Ok(None) => return Ok(None),
+        Err(err) => return Ok(Some(Value::object(Promise::rejected(scope, err)))),
+    };
+
+    // The value of the sync iterator result may itself be a promise, which needs to be awaited
+    let value = wrap_resolved_promise(scope, value);
+    let on_fulfilled = BoundFunction::new(
+        scope,
+        scope.statics.async_from_sync_iterator_result,
+        None,
+        [Value::boolean(done)].into(),
+    );
+    let on_fulfilled = scope.register(on_fulfilled);
+
+    scope
+        .statics
+        .promise_then
+        .clone()
+        .apply(This::bound(value), [Value::object(on_fulfilled)].into(), scope)
+        .root(scope)
+        .map(Some)
+}
+
+pub fn next(cx: CallContext) -> Result<Value, Value> {
+    let sync_iterator =
+        receiver_t::<AsyncFromSyncIterator>(cx.scope, &cx.this, "AsyncFromSyncIterator.prototype.next")?
+            .sync_iterator();
+
+    match call_sync_method(cx.scope, sync_iterator, sym::next, CallArgs::empty())? {
+        Some(result) => Ok(result),
+        None => throw!(cx.scope, TypeError, "Iterator has no next method"),
+    }
+}
+
+pub fn return_(cx: CallContext) -> Result<Value, Value> {
+    let sync_iterator =
+        receiver_t::<AsyncFromSyncIterator>(cx.scope, &cx.this, "AsyncFromSyncIterator.prototype.return")?
+            .sync_iterator();
+    let value = cx.args.first().unwrap_or_undefined();
+
+    match call_sync_method(cx.scope, sync_iterator, sym::return_, [value].into())? {
+        Some(result) => Ok(result),
+        None => {
+            let result = create_generator_value(cx.scope, true, Some(value))?;
+            Ok(wrap_resolved_promise(cx.scope, result))
+        }
+    }
+}
+
+/// Creates the iterator result once the value of a sync iterator result has settled.
+/// The first argument is the `done` flag of the sync iterator result, bound ahead of time.
+pub fn from_sync_result(cx: CallContext) -> Result<Value, Value> {
+    let done = cx.args.first().unwrap_or_undefined().is_truthy(cx.scope);
+    let value = cx.args.get(1).unwrap_or_undefined();
+    create_generator_value(cx.scope, done, Some(value))
+}
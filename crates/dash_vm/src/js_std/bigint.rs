@@ -0,0 +1,116 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+use crate::localscope::LocalScope;
+use crate::throw;
+use crate::value::bigint::{JsBigInt, MAX_BIGINT_BITS, number_to_bigint, to_bigint};
+use crate::value::function::native::CallContext;
+use crate::value::object::Object;
+use crate::value::ops::conversions::{PreferredType, ValueConversion};
+use crate::value::primitive::MAX_SAFE_INTEGERF;
+use crate::value::{Unpack, Value, ValueContext, ValueKind};
+
+pub fn constructor(cx: CallContext) -> Result<Value, Value> {
+    if cx.new_target.is_some() {
+        throw!(cx.scope, TypeError, "BigInt is not a constructor")
+    }
+
+    let value = cx
+        .args
+        .first()
+        .unwrap_or_undefined()
+        .to_primitive(cx.scope, Some(PreferredType::Number))?;
+
+    if let ValueKind::Number(n) = value.unpack() {
+        return match number_to_bigint(n.0) {
+            Some(b) => Ok(Value::bigint(JsBigInt::new(cx.scope, b))),
+            None => throw!(
+                cx.scope,
+                RangeError,
+                "The number {} cannot be converted to a BigInt because it is not an integer",
+                n
+            ),
+        };
+    }
+
+    to_bigint(value, cx.scope).map(Value::bigint)
+}
+
+/// Implements the ToIndex abstract operation for the `bits` argument of asIntN/asUintN
+fn to_bits(sc: &mut LocalScope<'_>, value: Value) -> Result<u64, Value> {
+    let bits = value.to_integer_or_infinity(sc)?;
+    if !(0.0..=MAX_SAFE_INTEGERF).contains(&bits) {
+        throw!(sc, RangeError, "Invalid value: not (convertible to) a safe integer")
+    }
+    Ok(bits as u64)
+}
+
+pub fn as_int_n(cx: CallContext) -> Result<Value, Value> {
+    let bits = to_bits(cx.scope, cx.args.first().unwrap_or_undefined())?;
+    let bigint = to_bigint(cx.args.get(1).unwrap_or_undefined(), cx.scope)?;
+
+    let value = bigint.get(cx.scope);
+    if bits == 0 {
+        return Ok(Value::bigint(JsBigInt::new(cx.scope, BigInt::zero())));
+    }
+    if value.bits() < bits {
+        // Already in range [-2^(bits-1), 2^(bits-1))
+        return Ok(Value::bigint(bigint));
+    }
+
+    let modulus = BigInt::one() << bits;
+    let mut result = value.mod_floor(&modulus);
+    if result.bits() == bits {
+        // result >= 2^(bits-1)
+        result -= modulus;
+    }
+    Ok(Value::bigint(JsBigInt::new(cx.scope, result)))
+}
+
+pub fn as_uint_n(cx: CallContext) -> Result<Value, Value> {
+    let bits = to_bits(cx.scope, cx.args.first().unwrap_or_undefined())?;
+    let bigint = to_bigint(cx.args.get(1).unwrap_or_undefined(), cx.scope)?;
+
+    let value = bigint.get(cx.scope);
+    if value.sign() != num_bigint::Sign::Minus && value.bits() <= bits {
+        // Already in range [0, 2^bits)
+        return Ok(Value::bigint(bigint));
+    }
+    if bits > MAX_BIGINT_BITS {
+        throw!(cx.scope, RangeError, "Maximum BigInt size exceeded")
+    }
+
+    let result = value.mod_floor(&(BigInt::one() << bits));
+    Ok(Value::bigint(JsBigInt::new(cx.scope, result)))
+}
+
+fn this_bigint_value(cx: &mut CallContext, method: &str) -> Result<JsBigInt, Value> {
+    match cx
+        .this
+        .internal_slots(cx.scope)
+        .and_then(|slots| slots.bigint_value(cx.scope))
+    {
+        Some(bigint) => Ok(bigint),
+        None => throw!(cx.scope, TypeError, "{} requires that 'this' be a BigInt", method),
+    }
+}
+
+pub fn to_string(mut cx: CallContext) -> Result<Value, Value> {
+    let bigint = this_bigint_value(&mut cx, "BigInt.prototype.toString")?;
+
+    let radix = match cx.args.first().copied().and_then(Value::into_option) {
+        Some(radix) => radix.to_integer_or_infinity(cx.scope)?,
+        None => 10.0,
+    };
+    if !(2.0..=36.0).contains(&radix) {
+        throw!(cx.scope, RangeError, "toString() radix must be between 2 and 36")
+    }
+
+    let string = bigint.get(cx.scope).to_str_radix(radix as u32);
+    Ok(Value::string(cx.scope.intern(string).into()))
+}
+
+pub fn value_of(mut cx: CallContext) -> Result<Value, Value> {
+    this_bigint_value(&mut cx, "BigInt.prototype.valueOf").map(Value::bigint)
+}
@@ -0,0 +1,155 @@
+use crate::throw;
+use crate::value::arraybuffer::ArrayBuffer;
+use crate::value::dataview::DataView;
+use crate::value::function::native::CallContext;
+use crate::value::object::OrdObject;
+use crate::value::ops::conversions::ValueConversion;
+use crate::value::typedarray::{TypedArrayKind, read_bytes};
+use crate::value::{Unpack, Value, ValueContext, ValueKind};
+
+use super::receiver_t;
+
+pub fn constructor(cx: CallContext) -> Result<Value, Value> {
+    let Some(new_target) = cx.new_target else {
+        throw!(cx.scope, TypeError, "DataView constructor requires new")
+    };
+    let buffer = match cx.args.first().unwrap_or_undefined().unpack() {
+        ValueKind::Object(obj) if obj.extract::<ArrayBuffer>(cx.scope).is_some() => obj,
+        _ => throw!(
+            cx.scope,
+            TypeError,
+            "First argument to DataView constructor must be an ArrayBuffer"
+        ),
+    };
+    let byte_offset = cx.args.get(1).unwrap_or_undefined().to_index(cx.scope)?;
+    let byte_length = cx.args.get(2).copied().and_then(Value::into_option);
+    let byte_length = byte_length.map(|len| len.to_index(cx.scope)).transpose()?;
+
+    let arraybuffer = buffer.extract::<ArrayBuffer>(cx.scope).unwrap();
+    if arraybuffer.is_detached() {
+        throw!(
+            cx.scope,
+            TypeError,
+            "Cannot construct a DataView from a detached ArrayBuffer"
+        );
+    }
+    let buffer_len = arraybuffer.len();
+    if byte_offset > buffer_len {
+        throw!(
+            cx.scope,
+            RangeError,
+            "Start offset {} is outside the bounds of the buffer",
+            byte_offset
+        );
+    }
+
+    let byte_length = match byte_length {
+        Some(byte_length) => {
+            if byte_offset + byte_length > buffer_len {
+                throw!(cx.scope, RangeError, "Invalid DataView length {}", byte_length);
+            }
+            Some(byte_length)
+        }
+        // Views of resizable buffers without an explicit length track the length of the buffer
+        None if arraybuffer.is_resizable() => None,
+        None => Some(buffer_len - byte_offset),
+    };
+
+    let instance = OrdObject::instance_for_new_target(new_target, cx.scope)?;
+    let view = DataView::new(buffer, byte_offset, byte_length, instance);
+    Ok(cx.scope.register(view).into())
+}
+
+/// Implements the GetViewValue abstract operation
+fn get_view_value(cx: CallContext, kind: TypedArrayKind, name: &'static str) -> Result<Value, Value> {
+    let this = receiver_t::<DataView>(cx.scope, &cx.this, name)?;
+    let index = cx.args.first().unwrap_or_undefined().to_index(cx.scope)?;
+    let little_endian = cx.args.get(1).unwrap_or_undefined().is_truthy(cx.scope);
+
+    let Some(storage) = this.storage(cx.scope) else {
+        throw!(cx.scope, TypeError, "{}: DataView is out of bounds", name)
+    };
+    let Some(bytes) = storage.get(index..index + kind.bytes_per_element()).map(read_bytes) else {
+        throw!(cx.scope, RangeError, "Offset is outside the bounds of the DataView")
+    };
+
+    Ok(kind.decode(cx.scope, bytes, little_endian))
+}
+
+/// Implements the SetViewValue abstract operation
+fn set_view_value(cx: CallContext, kind: TypedArrayKind, name: &'static str) -> Result<Value, Value> {
+    let this = receiver_t::<DataView>(cx.scope, &cx.this, name)?;
+    let index = cx.args.first().unwrap_or_undefined().to_index(cx.scope)?;
+    let value = cx.args.get(1).unwrap_or_undefined();
+    let little_endian = cx.args.get(2).unwrap_or_undefined().is_truthy(cx.scope);
+    let bytes = kind.encode(cx.scope, value, little_endian)?;
+
+    let Some(storage) = this.storage(cx.scope) else {
+        throw!(cx.scope, TypeError, "{}: DataView is out of bounds", name)
+    };
+    let Some(dest) = storage.get(index..index + kind.bytes_per_element()) else {
+        throw!(cx.scope, RangeError, "Offset is outside the bounds of the DataView")
+    };
+
+    for (dest, src) in dest.iter().zip(bytes) {
+        dest.set(src);
+    }
+    Ok(Value::undefined())
+}
+
+macro_rules! view_accessors {
+    ($($get:ident, $set:ident, $kind:ident, $name:literal);*) => {
+        $(
+            pub fn $get(cx: CallContext) -> Result<Value, Value> {
+                get_view_value(cx, TypedArrayKind::$kind, concat!("DataView.prototype.get", $name))
+            }
+
+            pub fn $set(cx: CallContext) -> Result<Value, Value> {
+                set_view_value(cx, TypedArrayKind::$kind, concat!("DataView.prototype.set", $name))
+            }
+        )*
+    };
+}
+
+view_accessors!(
+    get_int8, set_int8, Int8Array, "Int8";
+    get_uint8, set_uint8, Uint8Array, "Uint8";
+    get_int16, set_int16, Int16Array, "Int16";
+    get_uint16, set_uint16, Uint16Array, "Uint16";
+    get_int32, set_int32, Int32Array, "Int32";
+    get_uint32, set_uint32, Uint32Array, "Uint32";
+    get_float16, set_float16, Float16Array, "Float16";
+    get_float32, set_float32, Float32Array, "Float32";
+    get_float64, set_float64, Float64Array, "Float64";
+    get_bigint64, set_bigint64, BigInt64Array, "BigInt64";
+    get_biguint64, set_biguint64, BigUint64Array, "BigUint64"
+);
+
+pub fn buffer(cx: CallContext) -> Result<Value, Value> {
+    let this = receiver_t::<DataView>(cx.scope, &cx.this, "DataView.prototype.buffer")?;
+    Ok(Value::object(this.arraybuffer_id()))
+}
+
+pub fn byte_length(cx: CallContext) -> Result<Value, Value> {
+    let this = receiver_t::<DataView>(cx.scope, &cx.this, "DataView.prototype.byteLength")?;
+    let Some(storage) = this.storage(cx.scope) else {
+        throw!(
+            cx.scope,
+            TypeError,
+            "DataView.prototype.byteLength: DataView is out of bounds"
+        )
+    };
+    Ok(Value::number(storage.len() as f64))
+}
+
+pub fn byte_offset(cx: CallContext) -> Result<Value, Value> {
+    let this = receiver_t::<DataView>(cx.scope, &cx.this, "DataView.prototype.byteOffset")?;
+    if this.storage(cx.scope).is_none() {
+        throw!(
+            cx.scope,
+            TypeError,
+            "DataView.prototype.byteOffset: DataView is out of bounds"
+        )
+    }
+    Ok(Value::number(this.byte_offset() as f64))
+}
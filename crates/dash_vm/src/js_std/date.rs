@@ -1,9 +1,14 @@
+use dash_middle::interner::sym;
+
+use crate::localscope::LocalScope;
 use crate::throw;
-use crate::value::Value;
-use crate::value::date::Date;
+use crate::value::date::{self, Date, DateFields, MS_PER_MINUTE};
 use crate::value::function::native::CallContext;
-use crate::value::object::OrdObject;
+use crate::value::object::{Object, OrdObject, This};
+use crate::value::ops::conversions::{PreferredType, ValueConversion};
+use crate::value::propertykey::ToPropertyKey;
 use crate::value::root_ext::RootErrExt;
+use crate::value::{Root, Typeof, Unpack, Value, ValueContext, ValueKind};
 
 use super::receiver_t;
 
@@ -16,10 +21,85 @@ pub fn time_millis(cx: &mut CallContext) -> Result<u64, Value> {
     callback(cx.scope).root_err(cx.scope)
 }
 
-pub fn constructor(cx: CallContext) -> Result<Value, Value> {
-    // FIXME: `new` has special behavior
-    let new_target = cx.new_target.unwrap_or(cx.scope.statics.date_ctor);
-    let date = Date::new_with_object(OrdObject::instance_for_new_target(new_target, cx.scope)?, cx.scope)?;
+/// Formats a time value in the format of `Date.prototype.toString`
+fn date_to_string(sc: &mut LocalScope<'_>, t: f64) -> Result<String, Value> {
+    if t.is_nan() {
+        return Ok(String::from("Invalid Date"));
+    }
+
+    let offset = date::local_tza(sc, t)?;
+    let fields = DateFields::from_time(t + offset);
+    Ok(format!(
+        "{} {}",
+        date::format_date(&fields),
+        date::format_time(&fields, offset)
+    ))
+}
+
+/// Parses a date string, returning the time value or NaN if the string is not a valid date
+fn parse_date(sc: &mut LocalScope<'_>, s: &str) -> Result<f64, Value> {
+    match date::parse_date(s) {
+        Some(parsed) if parsed.local => date::utc(sc, parsed.time),
+        Some(parsed) => Ok(parsed.time),
+        None => Ok(f64::NAN),
+    }
+}
+
+/// Computes a time value from the year, month, etc. arguments passed to the Date constructor and `Date.UTC`
+fn time_from_components(sc: &mut LocalScope<'_>, args: &[Value]) -> Result<f64, Value> {
+    let mut components = [f64::NAN, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+    for (component, arg) in components.iter_mut().zip(args) {
+        *component = arg.to_number(sc)?;
+    }
+
+    let [year, month, day, hours, minutes, seconds, ms] = components;
+    // Two digit years refer to the 1900s
+    let year = if (0.0..=99.0).contains(&year.trunc()) {
+        1900.0 + year.trunc()
+    } else {
+        year
+    };
+
+    Ok(date::make_date(
+        date::make_day(year, month, day),
+        date::make_time(hours, minutes, seconds, ms),
+    ))
+}
+
+pub fn constructor(mut cx: CallContext) -> Result<Value, Value> {
+    let Some(new_target) = cx.new_target else {
+        // Called as a function: all arguments are ignored and the current time is returned as a string
+        let now = time_millis(&mut cx)?;
+        let string = date_to_string(cx.scope, now as f64)?;
+        return Ok(Value::string(cx.scope.intern(string).into()));
+    };
+
+    let timestamp = match *cx.args {
+        [] => None,
+        [value] => match value.extract::<Date>(cx.scope) {
+            Some(date) => Some(date.timestamp.get()),
+            None => {
+                let value = value.to_primitive(cx.scope, None)?;
+                match value.unpack() {
+                    ValueKind::String(s) => {
+                        let s = s.res(cx.scope).to_owned();
+                        Some(parse_date(cx.scope, &s)?)
+                    }
+                    _ => Some(value.to_number(cx.scope)?),
+                }
+            }
+        },
+        ref args => {
+            let time = time_from_components(cx.scope, args)?;
+            Some(date::utc(cx.scope, time)?)
+        }
+    };
+
+    let object = OrdObject::instance_for_new_target(new_target, cx.scope)?;
+    let date = match timestamp {
+        Some(timestamp) => Date::with_timestamp(object, timestamp),
+        None => Date::new_with_object(object, cx.scope)?,
+    };
     Ok(cx.scope.register(date).into())
 }
 
@@ -28,7 +108,418 @@ pub fn now(mut cx: CallContext) -> Result<Value, Value> {
     Ok(Value::number(time as f64))
 }
 
-pub fn get_time(cx: CallContext) -> Result<Value, Value> {
-    let this = receiver_t::<Date>(cx.scope, &cx.this, "Date.prototype.getTime")?;
-    Ok(Value::number(this.timestamp as f64))
+pub fn parse(cx: CallContext) -> Result<Value, Value> {
+    let s = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+    let s = s.res(cx.scope).to_owned();
+    parse_date(cx.scope, &s).map(Value::number)
+}
+
+pub fn utc(cx: CallContext) -> Result<Value, Value> {
+    let time = time_from_components(cx.scope, &cx.args)?;
+    Ok(Value::number(date::time_clip(time)))
+}
+
+fn this_time_value(cx: &mut CallContext, method: &'static str) -> Result<f64, Value> {
+    let this = receiver_t::<Date>(cx.scope, &cx.this, method)?;
+    Ok(this.timestamp.get())
+}
+
+/// Returns a component of the date in local time (or UTC if `local` is false)
+fn get_field(
+    mut cx: CallContext,
+    method: &'static str,
+    local: bool,
+    field: fn(&DateFields) -> f64,
+) -> Result<Value, Value> {
+    let t = this_time_value(&mut cx, method)?;
+    if t.is_nan() {
+        return Ok(Value::number(f64::NAN));
+    }
+
+    let t = if local { date::local_time(cx.scope, t)? } else { t };
+    Ok(Value::number(field(&DateFields::from_time(t))))
+}
+
+pub fn get_time(mut cx: CallContext) -> Result<Value, Value> {
+    this_time_value(&mut cx, "Date.prototype.getTime").map(Value::number)
+}
+
+pub fn value_of(mut cx: CallContext) -> Result<Value, Value> {
+    this_time_value(&mut cx, "Date.prototype.valueOf").map(Value::number)
+}
+
+pub fn get_timezone_offset(mut cx: CallContext) -> Result<Value, Value> {
+    let t = this_time_value(&mut cx, "Date.prototype.getTimezoneOffset")?;
+    if t.is_nan() {
+        return Ok(Value::number(f64::NAN));
+    }
+
+    let offset = date::local_tza(cx.scope, t)?;
+    Ok(Value::number(-offset / MS_PER_MINUTE))
+}
+
+pub fn get_full_year(cx: CallContext) -> Result<Value, Value> {
+    get_field(cx, "Date.prototype.getFullYear", true, |f| f.year)
+}
+
+pub fn get_month(cx: CallContext) -> Result<Value, Value> {
+    get_field(cx, "Date.prototype.getMonth", true, |f| f.month)
+}
+
+pub fn get_date(cx: CallContext) -> Result<Value, Value> {
+    get_field(cx, "Date.prototype.getDate", true, |f| f.date)
+}
+
+pub fn get_day(cx: CallContext) -> Result<Value, Value> {
+    get_field(cx, "Date.prototype.getDay", true, |f| f.weekday)
+}
+
+pub fn get_hours(cx: CallContext) -> Result<Value, Value> {
+    get_field(cx, "Date.prototype.getHours", true, |f| f.hours)
+}
+
+pub fn get_minutes(cx: CallContext) -> Result<Value, Value> {
+    get_field(cx, "Date.prototype.getMinutes", true, |f| f.minutes)
+}
+
+pub fn get_seconds(cx: CallContext) -> Result<Value, Value> {
+    get_field(cx, "Date.prototype.getSeconds", true, |f| f.seconds)
+}
+
+pub fn get_milliseconds(cx: CallContext) -> Result<Value, Value> {
+    get_field(cx, "Date.prototype.getMilliseconds", true, |f| f.milliseconds)
+}
+
+pub fn get_utc_full_year(cx: CallContext) -> Result<Value, Value> {
+    get_field(cx, "Date.prototype.getUTCFullYear", false, |f| f.year)
+}
+
+pub fn get_utc_month(cx: CallContext) -> Result<Value, Value> {
+    get_field(cx, "Date.prototype.getUTCMonth", false, |f| f.month)
+}
+
+pub fn get_utc_date(cx: CallContext) -> Result<Value, Value> {
+    get_field(cx, "Date.prototype.getUTCDate", false, |f| f.date)
+}
+
+pub fn get_utc_day(cx: CallContext) -> Result<Value, Value> {
+    get_field(cx, "Date.prototype.getUTCDay", false, |f| f.weekday)
+}
+
+pub fn get_utc_hours(cx: CallContext) -> Result<Value, Value> {
+    get_field(cx, "Date.prototype.getUTCHours", false, |f| f.hours)
+}
+
+pub fn get_utc_minutes(cx: CallContext) -> Result<Value, Value> {
+    get_field(cx, "Date.prototype.getUTCMinutes", false, |f| f.minutes)
+}
+
+pub fn get_utc_seconds(cx: CallContext) -> Result<Value, Value> {
+    get_field(cx, "Date.prototype.getUTCSeconds", false, |f| f.seconds)
+}
+
+pub fn get_utc_milliseconds(cx: CallContext) -> Result<Value, Value> {
+    get_field(cx, "Date.prototype.getUTCMilliseconds", false, |f| f.milliseconds)
+}
+
+/// The calendar fields in the order they are laid out in setter arguments
+#[derive(Copy, Clone)]
+enum Field {
+    Year,
+    Month,
+    Date,
+    Hours,
+    Minutes,
+    Seconds,
+    Milliseconds,
+}
+
+/// Implements the setters, which replace the field `first` and optionally the fields following it (up to and including `last`)
+fn set_fields(
+    mut cx: CallContext,
+    method: &'static str,
+    local: bool,
+    first: Field,
+    last: Field,
+) -> Result<Value, Value> {
+    let (first, last) = (first as usize, last as usize);
+    let t = this_time_value(&mut cx, method)?;
+
+    let count = cx.args.len().clamp(1, last - first + 1);
+    let mut args = [f64::NAN; 4];
+    for (i, arg) in args.iter_mut().enumerate().take(count) {
+        *arg = cx.args.get(i).unwrap_or_undefined().to_number(cx.scope)?;
+    }
+
+    let t = match t.is_nan() {
+        // Setting the year of an invalid date starts from +0
+        true if first == Field::Year as usize => 0.0,
+        true => return Ok(Value::number(f64::NAN)),
+        false if local => date::local_time(cx.scope, t)?,
+        false => t,
+    };
+
+    let fields = DateFields::from_time(t);
+    let mut components = [
+        fields.year,
+        fields.month,
+        fields.date,
+        fields.hours,
+        fields.minutes,
+        fields.seconds,
+        fields.milliseconds,
+    ];
+    components[first..first + count].copy_from_slice(&args[..count]);
+
+    let [year, month, day, hours, minutes, seconds, ms] = components;
+    let mut new = date::make_date(
+        date::make_day(year, month, day),
+        date::make_time(hours, minutes, seconds, ms),
+    );
+    if local {
+        new = date::utc(cx.scope, new)?;
+    }
+    let new = date::time_clip(new);
+
+    receiver_t::<Date>(cx.scope, &cx.this, method)?.timestamp.set(new);
+    Ok(Value::number(new))
+}
+
+pub fn set_time(mut cx: CallContext) -> Result<Value, Value> {
+    this_time_value(&mut cx, "Date.prototype.setTime")?;
+    let time = cx.args.first().unwrap_or_undefined().to_number(cx.scope)?;
+    let time = date::time_clip(time);
+
+    receiver_t::<Date>(cx.scope, &cx.this, "Date.prototype.setTime")?
+        .timestamp
+        .set(time);
+    Ok(Value::number(time))
+}
+
+pub fn set_full_year(cx: CallContext) -> Result<Value, Value> {
+    set_fields(cx, "Date.prototype.setFullYear", true, Field::Year, Field::Date)
+}
+
+pub fn set_month(cx: CallContext) -> Result<Value, Value> {
+    set_fields(cx, "Date.prototype.setMonth", true, Field::Month, Field::Date)
+}
+
+pub fn set_date(cx: CallContext) -> Result<Value, Value> {
+    set_fields(cx, "Date.prototype.setDate", true, Field::Date, Field::Date)
+}
+
+pub fn set_hours(cx: CallContext) -> Result<Value, Value> {
+    set_fields(cx, "Date.prototype.setHours", true, Field::Hours, Field::Milliseconds)
+}
+
+pub fn set_minutes(cx: CallContext) -> Result<Value, Value> {
+    set_fields(
+        cx,
+        "Date.prototype.setMinutes",
+        true,
+        Field::Minutes,
+        Field::Milliseconds,
+    )
+}
+
+pub fn set_seconds(cx: CallContext) -> Result<Value, Value> {
+    set_fields(
+        cx,
+        "Date.prototype.setSeconds",
+        true,
+        Field::Seconds,
+        Field::Milliseconds,
+    )
+}
+
+pub fn set_milliseconds(cx: CallContext) -> Result<Value, Value> {
+    set_fields(
+        cx,
+        "Date.prototype.setMilliseconds",
+        true,
+        Field::Milliseconds,
+        Field::Milliseconds,
+    )
+}
+
+pub fn set_utc_full_year(cx: CallContext) -> Result<Value, Value> {
+    set_fields(cx, "Date.prototype.setUTCFullYear", false, Field::Year, Field::Date)
+}
+
+pub fn set_utc_month(cx: CallContext) -> Result<Value, Value> {
+    set_fields(cx, "Date.prototype.setUTCMonth", false, Field::Month, Field::Date)
+}
+
+pub fn set_utc_date(cx: CallContext) -> Result<Value, Value> {
+    set_fields(cx, "Date.prototype.setUTCDate", false, Field::Date, Field::Date)
+}
+
+pub fn set_utc_hours(cx: CallContext) -> Result<Value, Value> {
+    set_fields(
+        cx,
+        "Date.prototype.setUTCHours",
+        false,
+        Field::Hours,
+        Field::Milliseconds,
+    )
+}
+
+pub fn set_utc_minutes(cx: CallContext) -> Result<Value, Value> {
+    set_fields(
+        cx,
+        "Date.prototype.setUTCMinutes",
+        false,
+        Field::Minutes,
+        Field::Milliseconds,
+    )
+}
+
+pub fn set_utc_seconds(cx: CallContext) -> Result<Value, Value> {
+    set_fields(
+        cx,
+        "Date.prototype.setUTCSeconds",
+        false,
+        Field::Seconds,
+        Field::Milliseconds,
+    )
+}
+
+pub fn set_utc_milliseconds(cx: CallContext) -> Result<Value, Value> {
+    set_fields(
+        cx,
+        "Date.prototype.setUTCMilliseconds",
+        false,
+        Field::Milliseconds,
+        Field::Milliseconds,
+    )
+}
+
+pub fn to_string(mut cx: CallContext) -> Result<Value, Value> {
+    let t = this_time_value(&mut cx, "Date.prototype.toString")?;
+    let string = date_to_string(cx.scope, t)?;
+    Ok(Value::string(cx.scope.intern(string).into()))
+}
+
+pub fn to_date_string(mut cx: CallContext) -> Result<Value, Value> {
+    let t = this_time_value(&mut cx, "Date.prototype.toDateString")?;
+    let string = if t.is_nan() {
+        String::from("Invalid Date")
+    } else {
+        let t = date::local_time(cx.scope, t)?;
+        date::format_date(&DateFields::from_time(t))
+    };
+    Ok(Value::string(cx.scope.intern(string).into()))
+}
+
+pub fn to_time_string(mut cx: CallContext) -> Result<Value, Value> {
+    let t = this_time_value(&mut cx, "Date.prototype.toTimeString")?;
+    let string = if t.is_nan() {
+        String::from("Invalid Date")
+    } else {
+        let offset = date::local_tza(cx.scope, t)?;
+        date::format_time(&DateFields::from_time(t + offset), offset)
+    };
+    Ok(Value::string(cx.scope.intern(string).into()))
+}
+
+#[cfg(feature = "intl")]
+pub fn to_locale_string(mut cx: CallContext) -> Result<Value, Value> {
+    use crate::value::intl::date_time_format::{DefaultComponents, RequiredComponents};
+
+    let t = this_time_value(&mut cx, "Date.prototype.toLocaleString")?;
+    super::intl::format_date_locale_string(cx, t, RequiredComponents::Any, DefaultComponents::All)
+}
+
+#[cfg(feature = "intl")]
+pub fn to_locale_date_string(mut cx: CallContext) -> Result<Value, Value> {
+    use crate::value::intl::date_time_format::{DefaultComponents, RequiredComponents};
+
+    let t = this_time_value(&mut cx, "Date.prototype.toLocaleDateString")?;
+    super::intl::format_date_locale_string(cx, t, RequiredComponents::Date, DefaultComponents::Date)
+}
+
+#[cfg(feature = "intl")]
+pub fn to_locale_time_string(mut cx: CallContext) -> Result<Value, Value> {
+    use crate::value::intl::date_time_format::{DefaultComponents, RequiredComponents};
+
+    let t = this_time_value(&mut cx, "Date.prototype.toLocaleTimeString")?;
+    super::intl::format_date_locale_string(cx, t, RequiredComponents::Time, DefaultComponents::Time)
+}
+
+// Without locale data, the locale methods fall back to the formats of their non-locale counterparts
+
+#[cfg(not(feature = "intl"))]
+pub fn to_locale_string(cx: CallContext) -> Result<Value, Value> {
+    to_string(cx)
+}
+
+#[cfg(not(feature = "intl"))]
+pub fn to_locale_date_string(cx: CallContext) -> Result<Value, Value> {
+    to_date_string(cx)
+}
+
+#[cfg(not(feature = "intl"))]
+pub fn to_locale_time_string(cx: CallContext) -> Result<Value, Value> {
+    to_time_string(cx)
+}
+
+pub fn to_utc_string(mut cx: CallContext) -> Result<Value, Value> {
+    let t = this_time_value(&mut cx, "Date.prototype.toUTCString")?;
+    let string = if t.is_nan() {
+        String::from("Invalid Date")
+    } else {
+        date::format_utc(&DateFields::from_time(t))
+    };
+    Ok(Value::string(cx.scope.intern(string).into()))
+}
+
+pub fn to_iso_string(mut cx: CallContext) -> Result<Value, Value> {
+    let t = this_time_value(&mut cx, "Date.prototype.toISOString")?;
+    if t.is_nan() {
+        throw!(cx.scope, RangeError, "Invalid time value")
+    }
+
+    let string = date::format_iso(&DateFields::from_time(t));
+    Ok(Value::string(cx.scope.intern(string).into()))
+}
+
+pub fn to_json(cx: CallContext) -> Result<Value, Value> {
+    let this = Value::object(cx.this.to_object(cx.scope)?);
+    let time = this.to_primitive(cx.scope, Some(PreferredType::Number))?;
+    if let ValueKind::Number(n) = time.unpack()
+        && !n.0.is_finite()
+    {
+        return Ok(Value::null());
+    }
+
+    let to_iso_string = this
+        .get_property(sym::toISOString.to_key(cx.scope), cx.scope)
+        .root(cx.scope)?;
+    if !matches!(to_iso_string.type_of(cx.scope), Typeof::Function) {
+        throw!(cx.scope, TypeError, "toISOString is not a function")
+    }
+    to_iso_string
+        .apply(This::bound(this), [].into(), cx.scope)
+        .root(cx.scope)
+}
+
+pub fn to_primitive(cx: CallContext) -> Result<Value, Value> {
+    if !matches!(cx.this.unpack(), ValueKind::Object(_)) {
+        throw!(
+            cx.scope,
+            TypeError,
+            "Date.prototype[Symbol.toPrimitive] called on non-object"
+        )
+    }
+
+    let hint = match cx.args.first().unpack() {
+        Some(ValueKind::String(hint)) => match hint.res(cx.scope) {
+            "string" | "default" => PreferredType::String,
+            "number" => PreferredType::Number,
+            _ => throw!(cx.scope, TypeError, "Invalid hint"),
+        },
+        _ => throw!(cx.scope, TypeError, "Invalid hint"),
+    };
+
+    cx.this.ordinary_to_primitive(cx.scope, hint)
 }
@@ -1,11 +1,16 @@
+use std::convert::Infallible;
+use std::ops::ControlFlow;
+
+use crate::js_std::array::for_each_iterable_element;
+use crate::value::array::Array;
 use crate::value::error::{
     AggregateError, Error, EvalError, RangeError, ReferenceError, SyntaxError, TypeError, URIError,
 };
 use crate::value::function::native::CallContext;
-use crate::value::object::OrdObject;
+use crate::value::object::{Object, OrdObject, PropertyValue};
 use crate::value::ops::conversions::ValueConversion;
 use crate::value::propertykey::ToPropertyKey;
-use crate::value::{Root, Value, ValueContext};
+use crate::value::{Root, Unpack, Value, ValueContext, ValueKind};
 use dash_middle::interner::sym;
 
 macro_rules! define_other_error_constructors {
@@ -31,10 +36,38 @@ define_other_error_constructors!(
     reference_error_constructor ReferenceError,
     syntax_error_constructor SyntaxError,
     type_error_constructor TypeError,
-    uri_error_constructor URIError,
-    aggregate_error_constructor AggregateError
+    uri_error_constructor URIError
 );
 
+pub fn aggregate_error_constructor(cx: CallContext) -> Result<Value, Value> {
+    let message = match cx.args.get(1).unpack() {
+        None | Some(ValueKind::Undefined(_)) => sym::empty.into(),
+        Some(_) => cx.args[1].to_js_string(cx.scope)?,
+    };
+    let obj = if let Some(new_target) = cx.new_target {
+        OrdObject::instance_for_new_target(new_target, cx.scope)?
+    } else {
+        AggregateError::object(cx.scope)
+    };
+    let error = AggregateError::new_with_js_string(cx.scope, obj, message);
+    let error = cx.scope.register(error);
+
+    let mut errors = Vec::new();
+    for_each_iterable_element(cx.scope, cx.args.first().unwrap_or_undefined(), |_, value| {
+        errors.push(PropertyValue::static_default(value));
+        Ok(ControlFlow::<Infallible>::Continue(()))
+    })?;
+    let errors = Array::from_vec(errors, cx.scope);
+    let errors = Value::object(cx.scope.register(errors));
+    error.set_property(
+        sym::errors.to_key(cx.scope),
+        PropertyValue::static_non_enumerable(errors),
+        cx.scope,
+    )?;
+
+    Ok(error.into())
+}
+
 pub fn error_constructor(cx: CallContext) -> Result<Value, Value> {
     let message = cx.args.first().cloned().map(|v| v.to_js_string(cx.scope)).transpose()?;
 
@@ -0,0 +1,92 @@
+use crate::throw;
+use crate::value::finalizationregistry::FinalizationRegistry;
+use crate::value::function::native::CallContext;
+use crate::value::object::{Object, OrdObject, This};
+use crate::value::ops::equality;
+use crate::value::{Root, Typeof, Unpack, Value, ValueContext, ValueKind};
+
+use super::receiver_t;
+
+pub fn constructor(cx: CallContext) -> Result<Value, Value> {
+    let Some(new_target) = cx.new_target else {
+        throw!(cx.scope, TypeError, "FinalizationRegistry constructor requires new")
+    };
+    let cleanup_callback = cx.args.first().unwrap_or_undefined();
+    let (ValueKind::Object(cleanup_callback), Typeof::Function) =
+        (cleanup_callback.unpack(), cleanup_callback.type_of(cx.scope))
+    else {
+        throw!(cx.scope, TypeError, "FinalizationRegistry: cleanup must be callable")
+    };
+
+    let registry = FinalizationRegistry::new(
+        cleanup_callback,
+        OrdObject::instance_for_new_target(new_target, cx.scope)?,
+    );
+    let registry = cx.scope.register(registry);
+    cx.scope.add_weak_holder(registry);
+    Ok(Value::object(registry))
+}
+
+pub fn register(cx: CallContext) -> Result<Value, Value> {
+    let this = receiver_t::<FinalizationRegistry>(cx.scope, &cx.this, "FinalizationRegistry.prototype.register")?;
+    let target = cx.args.first().unwrap_or_undefined();
+    let held_value = cx.args.get(1).unwrap_or_undefined();
+
+    let ValueKind::Object(target_id) = target.unpack() else {
+        throw!(
+            cx.scope,
+            TypeError,
+            "FinalizationRegistry.prototype.register: invalid target"
+        )
+    };
+    if equality::same_value(target, held_value, cx.scope) {
+        throw!(
+            cx.scope,
+            TypeError,
+            "FinalizationRegistry.prototype.register: target and holdings must not be same"
+        )
+    }
+    let unregister_token = match cx.args.get(2).unwrap_or_undefined().unpack() {
+        ValueKind::Object(token) => Some(token),
+        ValueKind::Undefined(_) => None,
+        _ => throw!(
+            cx.scope,
+            TypeError,
+            "FinalizationRegistry.prototype.register: invalid unregister token"
+        ),
+    };
+
+    this.register(target_id, held_value, unregister_token);
+    Ok(Value::undefined())
+}
+
+pub fn unregister(cx: CallContext) -> Result<Value, Value> {
+    let this = receiver_t::<FinalizationRegistry>(cx.scope, &cx.this, "FinalizationRegistry.prototype.unregister")?;
+    let ValueKind::Object(unregister_token) = cx.args.first().unwrap_or_undefined().unpack() else {
+        throw!(
+            cx.scope,
+            TypeError,
+            "FinalizationRegistry.prototype.unregister: invalid unregister token"
+        )
+    };
+
+    Ok(Value::boolean(this.unregister(unregister_token)))
+}
+
+/// The job queued by the GC after targets of a registry were collected, which calls the cleanup callback
+/// for each cell of a collected target that was not unregistered in the meantime
+pub fn cleanup(cx: CallContext) -> Result<Value, Value> {
+    let this = receiver_t::<FinalizationRegistry>(cx.scope, &cx.this, "FinalizationRegistry cleanup")?;
+    this.start_cleanup();
+    let Some(callback) = this.cleanup_callback() else {
+        return Ok(Value::undefined());
+    };
+
+    while let Some(held_value) = this.take_collected() {
+        Value::object(callback)
+            .apply(This::default(), [held_value].into(), cx.scope)
+            .root(cx.scope)?;
+    }
+
+    Ok(Value::undefined())
+}
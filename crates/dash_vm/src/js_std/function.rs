@@ -83,3 +83,12 @@ pub fn to_string(cx: CallContext) -> Result<Value, Value> {
     );
     Ok(Value::string(cx.scope.intern(name.as_ref()).into()))
 }
+
+/// The %ThrowTypeError% intrinsic, which is the getter and setter of `callee` on the `arguments` object of strict functions
+pub fn throw_type_error(cx: CallContext) -> Result<Value, Value> {
+    throw!(
+        cx.scope,
+        TypeError,
+        "'caller', 'callee', and 'arguments' properties may not be accessed on strict mode functions or the arguments objects for calls to them"
+    )
+}
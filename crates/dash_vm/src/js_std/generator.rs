@@ -16,31 +16,50 @@ use dash_middle::interner::sym;
 
 use super::receiver_t;
 
+/// The result of resuming a generator
+pub(crate) enum GeneratorResult {
+    /// The generator was already finished
+    Finished,
+    Yield(Value),
+    Await(Value),
+    Return(Value),
+}
+
 /// Sets up the generator frame and does all the necessary setup, calls the closure which can then perform operations within that generator,
-/// and after the closure returns, cleans up the frame, and creates a generator result value (or propagates an uncaught exception).
+/// and after the closure returns, cleans up the frame (or propagates an uncaught exception).
+///
+/// The closure is also passed whether the generator is suspended at a `yield`.
 #[deny(
     clippy::question_mark_used,
     reason = "broken generator state needs to be fixed and `?` must not be used in that state"
 )]
 fn bootstrap_generator(
     scope: &mut LocalScope<'_>,
-    this: Value,
-    process: &dyn Fn(&mut LocalScope<'_>, Frame) -> Result<HandleResult, Unrooted>,
-) -> Result<Value, Value> {
-    #[expect(clippy::question_mark_used)]
-    let generator = receiver_t::<GeneratorIterator>(scope, &this, "GeneratorIterator.prototype.next")?;
-
-    let frame = {
-        let (ip, old_stack, arguments, mut try_blocks, this) = match &mut *generator.state().borrow_mut() {
-            GeneratorState::Finished => return create_generator_value(scope, true, None),
-            GeneratorState::Running {
-                ip,
-                stack,
-                arguments,
-                try_blocks,
-                this,
-            } => (*ip, mem::take(stack), arguments.take(), mem::take(try_blocks), *this),
-        };
+    generator: &GeneratorIterator,
+    process: &dyn Fn(&mut LocalScope<'_>, Frame, bool) -> Result<HandleResult, Unrooted>,
+) -> Result<GeneratorResult, Value> {
+    let (frame, at_yield) = {
+        let (ip, old_stack, arguments, mut try_blocks, this, delayed_ret, at_yield) =
+            match &mut *generator.state().borrow_mut() {
+                GeneratorState::Finished => return Ok(GeneratorResult::Finished),
+                GeneratorState::Running {
+                    ip,
+                    stack,
+                    arguments,
+                    try_blocks,
+                    this,
+                    delayed_ret,
+                    at_yield,
+                } => (
+                    *ip,
+                    mem::take(stack),
+                    arguments.take(),
+                    mem::take(try_blocks),
+                    *this,
+                    delayed_ret.take(),
+                    *at_yield,
+                ),
+            };
 
         for tb in &mut try_blocks {
             // frame_idx is 0-based, but we haven't pushed the frame yet and will later, which will make this correct.
@@ -51,6 +70,7 @@ fn bootstrap_generator(
         let function = match function.extract::<Function>(scope).map(|fun| fun.kind()) {
             Some(FunctionKind::Generator(generator)) => &generator.function,
             Some(FunctionKind::Async(fun)) => &fun.inner.function,
+            Some(FunctionKind::AsyncGenerator(fun)) => &fun.inner.function,
             _ => throw!(scope, TypeError, "Incompatible generator function"),
         };
 
@@ -62,6 +82,7 @@ fn bootstrap_generator(
         let mut frame = Frame::from_function(this, function, None, false, arguments);
         frame.ip = ip;
         frame.sp = current_sp;
+        frame.delayed_ret = delayed_ret;
 
         if !generator.did_run() {
             // If it hasn't run before, do the stack space management initially (push undefined values for locals)
@@ -69,10 +90,10 @@ fn bootstrap_generator(
             scope.init_stack_for_frame(&frame);
         }
 
-        frame
+        (frame, at_yield)
     };
 
-    let result = match process(scope, frame) {
+    let result = match process(scope, frame, at_yield) {
         Ok(res) => res,
         Err(err) => {
             generator.state().replace(GeneratorState::Finished);
@@ -83,12 +104,9 @@ fn bootstrap_generator(
     match result {
         HandleResult::Return(value) => {
             generator.state().replace(GeneratorState::Finished);
-            let value = value.root(scope);
-
-            create_generator_value(scope, true, Some(value))
+            Ok(GeneratorResult::Return(value.root(scope)))
         }
         HandleResult::Yield(value) | HandleResult::Await(value) => {
-            // Async functions are desugared to generators, so `await` is treated equivalent to `yield`, for now...
             let value = value.root(scope);
 
             let frame_idx = scope.frames.current_id();
@@ -115,34 +133,108 @@ fn bootstrap_generator(
                 arguments: frame.arguments,
                 try_blocks,
                 this: frame.this,
+                delayed_ret: frame.delayed_ret,
+                at_yield: matches!(result, HandleResult::Yield(_)),
             });
 
-            create_generator_value(scope, false, Some(value))
+            Ok(match result {
+                HandleResult::Yield(_) => GeneratorResult::Yield(value),
+                _ => GeneratorResult::Await(value),
+            })
         }
     }
 }
 
-pub fn next(cx: CallContext) -> Result<Value, Value> {
-    let val = cx.args.first().unwrap_or_undefined();
-    bootstrap_generator(cx.scope, cx.this, &|scope, frame| {
+/// Resumes the generator, passing the value to the `yield` (or `await`) expression it is suspended at
+pub(crate) fn resume_next(
+    scope: &mut LocalScope<'_>,
+    generator: &GeneratorIterator,
+    value: Value,
+) -> Result<GeneratorResult, Value> {
+    bootstrap_generator(scope, generator, &|scope, frame, at_yield| {
         // We're going to resume the generator after having evaluated a `yield` expression,
         // which expects a value to be on the stack (the resumed value)
-        scope.stack.push(val);
+        scope.stack.push(value);
+        if at_yield {
+            // ... and whether it was resumed through `return()`
+            scope.stack.push(Value::boolean(false));
+        }
         scope.execute_frame_raw(frame)
     })
 }
 
-pub fn throw(cx: CallContext) -> Result<Value, Value> {
-    let val = cx.args.first().unwrap_or_undefined();
-    bootstrap_generator(cx.scope, cx.this, &|scope, frame| {
+/// Resumes the generator as if there was a `return` statement at the `yield` expression it is suspended at,
+/// running any enclosing finally blocks.
+pub(crate) fn resume_return(
+    scope: &mut LocalScope<'_>,
+    generator: &GeneratorIterator,
+    value: Value,
+) -> Result<GeneratorResult, Value> {
+    let at_yield = match &*generator.state().borrow() {
+        GeneratorState::Running { ip, at_yield, .. } => ip.0 != 0 && *at_yield,
+        GeneratorState::Finished => false,
+    };
+
+    if !at_yield {
+        // A generator that hasn't started yet (or already finished) completes immediately without running any code
+        generator.state().replace(GeneratorState::Finished);
+        return Ok(GeneratorResult::Return(value));
+    }
+
+    bootstrap_generator(scope, generator, &|scope, frame, _| {
+        scope.stack.push(value);
+        scope.stack.push(Value::boolean(true));
+        scope.execute_frame_raw(frame)
+    })
+}
+
+/// Resumes the generator by throwing the value at the `yield` (or `await`) expression it is suspended at
+pub(crate) fn resume_throw(
+    scope: &mut LocalScope<'_>,
+    generator: &GeneratorIterator,
+    value: Value,
+) -> Result<GeneratorResult, Value> {
+    bootstrap_generator(scope, generator, &|scope, frame, _| {
         let fp = FrameId(scope.frames.len());
         scope.try_push_frame(frame)?;
-        scope.handle_rt_error(val.into(), fp)?; // FIXME: is this `?` fine?
+        scope.handle_rt_error(value.into(), fp)?; // FIXME: is this `?` fine?
         scope.handle_instruction_loop()
     })
 }
 
-fn create_generator_value(scope: &mut LocalScope, done: bool, value: Option<Value>) -> Result<Value, Value> {
+fn generator_result_value(scope: &mut LocalScope<'_>, result: GeneratorResult) -> Result<Value, Value> {
+    match result {
+        GeneratorResult::Finished => create_generator_value(scope, true, None),
+        GeneratorResult::Return(value) => create_generator_value(scope, true, Some(value)),
+        // Async functions are desugared to generators, so `await` is treated equivalent to `yield`, for now...
+        GeneratorResult::Yield(value) | GeneratorResult::Await(value) => {
+            create_generator_value(scope, false, Some(value))
+        }
+    }
+}
+
+pub fn next(cx: CallContext) -> Result<Value, Value> {
+    let val = cx.args.first().unwrap_or_undefined();
+    let generator = receiver_t::<GeneratorIterator>(cx.scope, &cx.this, "GeneratorIterator.prototype.next")?;
+    let result = resume_next(cx.scope, generator, val)?;
+    generator_result_value(cx.scope, result)
+}
+
+pub fn return_(cx: CallContext) -> Result<Value, Value> {
+    let val = cx.args.first().unwrap_or_undefined();
+    let generator = receiver_t::<GeneratorIterator>(cx.scope, &cx.this, "GeneratorIterator.prototype.return")?;
+    let result = resume_return(cx.scope, generator, val)?;
+    generator_result_value(cx.scope, result)
+}
+
+pub fn throw(cx: CallContext) -> Result<Value, Value> {
+    let val = cx.args.first().unwrap_or_undefined();
+    let generator = receiver_t::<GeneratorIterator>(cx.scope, &cx.this, "GeneratorIterator.prototype.throw")?;
+    let result = resume_throw(cx.scope, generator, val)?;
+    generator_result_value(cx.scope, result)
+}
+
+pub(crate) fn create_generator_value(scope: &mut LocalScope, done: bool, value: Option<Value>) -> Result<Value, Value> {
     let obj = OrdObject::new(scope);
     obj.set_property(
         sym::done.to_key(scope),
@@ -60,37 +60,137 @@ pub fn is_finite(cx: CallContext) -> Result<Value, Value> {
     Ok(Value::boolean(num.is_finite()))
 }
 
+/// Trims leading whitespace and line terminators as defined by TrimString(string, start)
+fn trim_js_start(s: &str) -> &str {
+    s.trim_start_matches(|c: char| c.is_whitespace() || c == '\u{feff}')
+}
+
 pub fn parse_float(cx: CallContext) -> Result<Value, Value> {
     // 1. Let inputString be ? ToString(string).
     let input_string = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
     // 2. Let trimmedString be ! TrimString(inputString, start).
-    let trimmed_string = input_string.res(cx.scope).trim();
+    let trimmed_string = trim_js_start(input_string.res(cx.scope));
+
+    // 3. Let trimmed be StringToCodePoints(trimmedString).
+    // 4. Let trimmedPrefix be the longest prefix of trimmed that satisfies the syntax of a StrDecimalLiteral, which might be trimmed itself.
+    let bytes = trimmed_string.as_bytes();
+    let mut end = 0;
+    if matches!(bytes.first(), Some(b'+' | b'-')) {
+        end += 1;
+    }
+
+    if trimmed_string[end..].starts_with("Infinity") {
+        let num = if bytes[0] == b'-' {
+            f64::NEG_INFINITY
+        } else {
+            f64::INFINITY
+        };
+        return Ok(Value::number(num));
+    }
+
+    let digits_from = |mut i: usize| {
+        while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+            i += 1;
+        }
+        i
+    };
 
-    // TODO: follow spec
-    let num = Value::number(trimmed_string.parse().unwrap_or(f64::NAN));
+    let integer_end = digits_from(end);
+    let mut has_digits = integer_end > end;
+    end = integer_end;
+    if bytes.get(end) == Some(&b'.') {
+        let fraction_end = digits_from(end + 1);
+        has_digits |= fraction_end > end + 1;
+        end = fraction_end;
+    }
+
+    // 5. If trimmedPrefix does not contain any code points, return NaN.
+    if !has_digits {
+        return Ok(Value::number(f64::NAN));
+    }
+
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        let mut exponent_start = end + 1;
+        if matches!(bytes.get(exponent_start), Some(b'+' | b'-')) {
+            exponent_start += 1;
+        }
+        let exponent_end = digits_from(exponent_start);
+        if exponent_end > exponent_start {
+            end = exponent_end;
+        }
+    }
 
-    Ok(num)
+    // 6. Let parsedNumber be ParseText(trimmedPrefix, StrDecimalLiteral).
+    let num = trimmed_string[..end].parse().unwrap_or(f64::NAN);
+
+    Ok(Value::number(num))
 }
 
 pub fn parse_int(cx: CallContext) -> Result<Value, Value> {
+    // 1. Let inputString be ? ToString(string).
     let input_string = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
-    let radix = cx
-        .args
-        .get(1)
-        .cloned()
-        .map(|v| v.to_number(cx.scope))
-        .transpose()?
-        .map(|r| r as u32)
-        .unwrap_or(10);
-
-    let trimmed_string = input_string.res(cx.scope).trim();
-
-    // TODO: follow spec
-    let num = Value::number(
-        i32::from_str_radix(trimmed_string, radix)
-            .map(|n| n as f64)
-            .unwrap_or(f64::NAN),
-    );
-
-    Ok(num)
+    // 6. Let R be ℝ(? ToInt32(radix)).
+    let mut radix = cx.args.get(1).unwrap_or_undefined().to_int32(cx.scope)?;
+
+    // 2. Let S be ! TrimString(inputString, start).
+    let mut s = trim_js_start(input_string.res(cx.scope));
+
+    // 3. Let sign be 1.
+    // 4. If S is not empty and the first code unit of S is the code unit 0x002D (HYPHEN-MINUS), set sign to -1.
+    // 5. If S is not empty and the first code unit of S is either the code unit 0x002B (PLUS SIGN) or the code unit 0x002D (HYPHEN-MINUS), set S to the substring of S from index 1.
+    let sign = if s.starts_with('-') { -1.0 } else { 1.0 };
+    if s.starts_with(['+', '-']) {
+        s = &s[1..];
+    }
+
+    // 7. Let stripPrefix be true.
+    let mut strip_prefix = true;
+    if radix != 0 {
+        // 8. If R ≠ 0, then
+        // a. If R < 2 or R > 36, return NaN.
+        if !(2..=36).contains(&radix) {
+            return Ok(Value::number(f64::NAN));
+        }
+        // b. If R ≠ 16, set stripPrefix to false.
+        if radix != 16 {
+            strip_prefix = false;
+        }
+    } else {
+        // 9. Else, set R to 10.
+        radix = 10;
+    }
+
+    // 10. If stripPrefix is true, then
+    // a. If the length of S is at least 2 and the first two code units of S are either "0x" or "0X", then
+    if strip_prefix && (s.starts_with("0x") || s.starts_with("0X")) {
+        // i. Remove the first two code units from S.
+        s = &s[2..];
+        // ii. Set R to 16.
+        radix = 16;
+    }
+
+    // 11. If S contains a code unit that is not a radix-R digit, let end be the index within S of the first such code unit; otherwise, let end be the length of S.
+    let end = s.find(|c: char| !c.is_digit(radix as u32)).unwrap_or(s.len());
+    // 12. Let Z be the substring of S from 0 to end.
+    let z = &s[..end];
+    // 13. If Z is empty, return NaN.
+    if z.is_empty() {
+        return Ok(Value::number(f64::NAN));
+    }
+
+    // 14. Let mathInt be the integer value that is represented by Z in radix-R notation
+    let math_int = if radix == 10 {
+        // Decimal strings can be converted exactly
+        z.parse::<f64>().unwrap()
+    } else {
+        z.chars().fold(0.0, |acc, c| {
+            acc * f64::from(radix) + f64::from(c.to_digit(radix as u32).unwrap())
+        })
+    };
+
+    // 15. If mathInt = 0, then
+    // a. If sign = -1, return -0𝔽.
+    // b. Return +0𝔽.
+    // 16. Return 𝔽(sign × mathInt).
+    Ok(Value::number(sign * math_int))
 }
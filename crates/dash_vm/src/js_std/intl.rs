@@ -0,0 +1,347 @@
+use dash_middle::interner::{Symbol, sym};
+
+use crate::gc::ObjectId;
+use crate::localscope::LocalScope;
+use crate::throw;
+use crate::value::array::Array;
+use crate::value::function::bound::BoundFunction;
+use crate::value::function::native::CallContext;
+use crate::value::intl::collator::{Collator, CollatorOptions};
+use crate::value::intl::date_time_format::{
+    DateTimeFormat, DateTimeFormatOptions, DefaultComponents, RequiredComponents, ResolvedComponent,
+};
+use crate::value::intl::number_format::{Grouping, MathematicalValue, NumberFormat, NumberFormatOptions, Rounding};
+use crate::value::intl::{self, coerce_options_to_object, get_string_option};
+use crate::value::object::{Object, OrdObject, PropertyValue};
+use crate::value::ops::conversions::{PreferredType, ValueConversion};
+use crate::value::propertykey::ToPropertyKey;
+use crate::value::{Unpack, Value, ValueContext, ValueKind, date};
+
+use super::receiver_t;
+
+fn string_array(sc: &mut LocalScope<'_>, strings: Vec<String>) -> Value {
+    let elements = strings
+        .into_iter()
+        .map(|s| PropertyValue::static_default(Value::string(sc.intern(s).into())))
+        .collect();
+    let array = Array::from_vec(elements, sc);
+    Value::object(sc.register(array))
+}
+
+fn create_object(
+    sc: &mut LocalScope<'_>,
+    properties: impl IntoIterator<Item = (Symbol, Value)>,
+) -> Result<Value, Value> {
+    let object = OrdObject::new(sc);
+    for (key, value) in properties {
+        object.set_property(key.to_key(sc), PropertyValue::static_default(value), sc)?;
+    }
+    Ok(Value::object(sc.register(object)))
+}
+
+/// Creates an array of `{ type, value }` objects, as returned by the `formatToParts` methods
+fn parts_to_array<K: Copy>(
+    sc: &mut LocalScope<'_>,
+    parts: Vec<(K, String)>,
+    name: fn(K) -> &'static str,
+) -> Result<Value, Value> {
+    let mut elements = Vec::with_capacity(parts.len());
+    for (kind, value) in parts {
+        let kind = Value::string(sc.intern(name(kind)).into());
+        let value = Value::string(sc.intern(value).into());
+        let part = create_object(sc, [(sym::type_, kind), (sym::value, value)])?;
+        elements.push(PropertyValue::static_default(part));
+    }
+    let array = Array::from_vec(elements, sc);
+    Ok(Value::object(sc.register(array)))
+}
+
+fn string_value(sc: &mut LocalScope<'_>, s: &str) -> Value {
+    Value::string(sc.intern(s).into())
+}
+
+/// Returns the object that a service constructor initializes. The constructors can also be called without `new`.
+fn instance_object(
+    sc: &mut LocalScope<'_>,
+    new_target: Option<ObjectId>,
+    prototype: ObjectId,
+) -> Result<OrdObject, Value> {
+    match new_target {
+        Some(new_target) => OrdObject::instance_for_new_target(new_target, sc),
+        None => Ok(OrdObject::with_prototype(prototype)),
+    }
+}
+
+/// Returns the function cached in `slot`, creating a function that calls `target` with `this` bound to `receiver` if it does not exist yet
+fn bound_function(
+    sc: &mut LocalScope<'_>,
+    slot: &std::cell::Cell<Option<ObjectId>>,
+    target: ObjectId,
+    receiver: Value,
+) -> Value {
+    if let Some(function) = slot.get() {
+        return Value::object(function);
+    }
+
+    let function = BoundFunction::new(sc, target, Some(receiver), Default::default());
+    let function = sc.register(function);
+    slot.set(Some(function));
+    Value::object(function)
+}
+
+pub fn get_canonical_locales(cx: CallContext) -> Result<Value, Value> {
+    let locales = intl::canonicalize_locale_list(cx.scope, cx.args.first().unwrap_or_undefined())?;
+    Ok(string_array(cx.scope, locales))
+}
+
+/// Implements `supportedLocalesOf` for all services, which share the same locale data
+pub fn supported_locales_of(cx: CallContext) -> Result<Value, Value> {
+    let requested = intl::canonicalize_locale_list(cx.scope, cx.args.first().unwrap_or_undefined())?;
+    let options = coerce_options_to_object(cx.scope, cx.args.get(1).unwrap_or_undefined())?;
+    get_string_option(
+        cx.scope,
+        options,
+        sym::localeMatcher,
+        &[("lookup", ()), ("best fit", ())],
+    )?;
+    Ok(string_array(cx.scope, intl::supported_locales(requested)))
+}
+
+/// Implements the ToIntlMathematicalValue abstract operation
+fn to_intl_mathematical_value(sc: &mut LocalScope<'_>, value: Value) -> Result<MathematicalValue, Value> {
+    let primitive = value.to_primitive(sc, Some(PreferredType::Number))?;
+    match primitive.unpack() {
+        ValueKind::BigInt(bigint) => Ok(MathematicalValue::from_integer_string(&bigint.get(sc).to_string())),
+        _ => Ok(MathematicalValue::from_f64(primitive.to_number(sc)?)),
+    }
+}
+
+pub fn number_format_constructor(cx: CallContext) -> Result<Value, Value> {
+    let object = instance_object(cx.scope, cx.new_target, cx.scope.statics.intl.number_format_prototype)?;
+    let locales = cx.args.first().unwrap_or_undefined();
+    let options = cx.args.get(1).unwrap_or_undefined();
+    let options = NumberFormatOptions::new(cx.scope, locales, options)?;
+    let number_format = NumberFormat::new(options, object);
+    Ok(Value::object(cx.scope.register(number_format)))
+}
+
+pub fn number_format_format(cx: CallContext) -> Result<Value, Value> {
+    let number_format = receiver_t::<NumberFormat>(cx.scope, &cx.this, "get Intl.NumberFormat.prototype.format")?;
+    let target = cx.scope.statics.intl.number_format_bound_format;
+    Ok(bound_function(cx.scope, &number_format.bound_format, target, cx.this))
+}
+
+/// The function returned by the `format` getter
+pub fn number_format_bound_format(cx: CallContext) -> Result<Value, Value> {
+    let value = to_intl_mathematical_value(cx.scope, cx.args.first().unwrap_or_undefined())?;
+    let number_format = receiver_t::<NumberFormat>(cx.scope, &cx.this, "Intl.NumberFormat.prototype.format")?;
+    let string = number_format.options.format(value);
+    Ok(Value::string(cx.scope.intern(string).into()))
+}
+
+pub fn number_format_format_to_parts(cx: CallContext) -> Result<Value, Value> {
+    let value = to_intl_mathematical_value(cx.scope, cx.args.first().unwrap_or_undefined())?;
+    let number_format = receiver_t::<NumberFormat>(cx.scope, &cx.this, "Intl.NumberFormat.prototype.formatToParts")?;
+    let parts = number_format.options.format_to_parts(value);
+    parts_to_array(cx.scope, parts, |kind| kind.name())
+}
+
+pub fn number_format_resolved_options(cx: CallContext) -> Result<Value, Value> {
+    let options =
+        &receiver_t::<NumberFormat>(cx.scope, &cx.this, "Intl.NumberFormat.prototype.resolvedOptions")?.options;
+
+    let mut properties = vec![
+        (sym::locale, string_value(cx.scope, options.locale.tag())),
+        (sym::numberingSystem, string_value(cx.scope, "latn")),
+        (sym::style, string_value(cx.scope, options.style.name())),
+    ];
+    if let Some(currency) = &options.currency {
+        properties.push((sym::currency, string_value(cx.scope, currency)));
+        properties.push((
+            sym::currencyDisplay,
+            string_value(cx.scope, options.currency_display.name()),
+        ));
+    }
+    properties.push((
+        sym::minimumIntegerDigits,
+        Value::number(f64::from(options.minimum_integer_digits)),
+    ));
+    match options.rounding {
+        Rounding::Fraction { min, max } => {
+            properties.push((sym::minimumFractionDigits, Value::number(f64::from(min))));
+            properties.push((sym::maximumFractionDigits, Value::number(f64::from(max))));
+        }
+        Rounding::Significant { min, max } => {
+            properties.push((sym::minimumSignificantDigits, Value::number(f64::from(min))));
+            properties.push((sym::maximumSignificantDigits, Value::number(f64::from(max))));
+        }
+    }
+    let grouping = match options.grouping {
+        Grouping::Always => string_value(cx.scope, "always"),
+        Grouping::Auto => string_value(cx.scope, "auto"),
+        Grouping::Min2 => string_value(cx.scope, "min2"),
+        Grouping::Never => Value::boolean(false),
+    };
+    properties.push((sym::useGrouping, grouping));
+    properties.push((sym::notation, string_value(cx.scope, "standard")));
+    properties.push((sym::signDisplay, string_value(cx.scope, options.sign_display.name())));
+
+    create_object(cx.scope, properties)
+}
+
+/// Implements the ToDateTimeFormattable abstract operation followed by TimeClip
+fn to_time_value(cx: &mut CallContext) -> Result<f64, Value> {
+    let value = cx.args.first().unwrap_or_undefined();
+    let t = if matches!(value.unpack(), ValueKind::Undefined(_)) {
+        super::date::time_millis(cx)? as f64
+    } else {
+        date::time_clip(value.to_number(cx.scope)?)
+    };
+
+    if t.is_nan() {
+        throw!(&mut cx.scope, RangeError, "Invalid time value");
+    }
+    Ok(t)
+}
+
+pub fn date_time_format_constructor(cx: CallContext) -> Result<Value, Value> {
+    let object = instance_object(
+        cx.scope,
+        cx.new_target,
+        cx.scope.statics.intl.date_time_format_prototype,
+    )?;
+    let locales = cx.args.first().unwrap_or_undefined();
+    let options = cx.args.get(1).unwrap_or_undefined();
+    let options = DateTimeFormatOptions::new(
+        cx.scope,
+        locales,
+        options,
+        RequiredComponents::Any,
+        DefaultComponents::Date,
+    )?;
+    let date_time_format = DateTimeFormat::new(options, object);
+    Ok(Value::object(cx.scope.register(date_time_format)))
+}
+
+pub fn date_time_format_format(cx: CallContext) -> Result<Value, Value> {
+    let date_time_format =
+        receiver_t::<DateTimeFormat>(cx.scope, &cx.this, "get Intl.DateTimeFormat.prototype.format")?;
+    let target = cx.scope.statics.intl.date_time_format_bound_format;
+    Ok(bound_function(
+        cx.scope,
+        &date_time_format.bound_format,
+        target,
+        cx.this,
+    ))
+}
+
+/// The function returned by the `format` getter
+pub fn date_time_format_bound_format(mut cx: CallContext) -> Result<Value, Value> {
+    let t = to_time_value(&mut cx)?;
+    let date_time_format = receiver_t::<DateTimeFormat>(cx.scope, &cx.this, "Intl.DateTimeFormat.prototype.format")?;
+    let string = date_time_format.options.format(cx.scope, t)?;
+    Ok(Value::string(cx.scope.intern(string).into()))
+}
+
+pub fn date_time_format_format_to_parts(mut cx: CallContext) -> Result<Value, Value> {
+    let t = to_time_value(&mut cx)?;
+    let date_time_format =
+        receiver_t::<DateTimeFormat>(cx.scope, &cx.this, "Intl.DateTimeFormat.prototype.formatToParts")?;
+    let parts = date_time_format.options.format_to_parts(cx.scope, t)?;
+    parts_to_array(cx.scope, parts, |kind| kind.name())
+}
+
+pub fn date_time_format_resolved_options(cx: CallContext) -> Result<Value, Value> {
+    let options =
+        &receiver_t::<DateTimeFormat>(cx.scope, &cx.this, "Intl.DateTimeFormat.prototype.resolvedOptions")?.options;
+
+    let mut properties = vec![
+        (sym::locale, string_value(cx.scope, options.locale.tag())),
+        (sym::calendar, string_value(cx.scope, "gregory")),
+        (sym::numberingSystem, string_value(cx.scope, "latn")),
+        (sym::timeZone, string_value(cx.scope, &options.time_zone_id)),
+    ];
+    if let Some(hour_cycle) = options.hour_cycle {
+        properties.push((sym::hourCycle, string_value(cx.scope, hour_cycle.name())));
+        properties.push((sym::hour12, Value::boolean(hour_cycle.is_twelve_hour())));
+    }
+
+    if options.date_style.is_some() || options.time_style.is_some() {
+        if let Some(style) = options.date_style_name() {
+            properties.push((sym::dateStyle, string_value(cx.scope, style)));
+        }
+        if let Some(style) = options.time_style_name() {
+            properties.push((sym::timeStyle, string_value(cx.scope, style)));
+        }
+    } else {
+        for (key, component) in options.resolved_components() {
+            let value = match component {
+                ResolvedComponent::String(s) => string_value(cx.scope, s),
+                ResolvedComponent::Number(n) => Value::number(f64::from(n)),
+            };
+            properties.push((key, value));
+        }
+    }
+
+    create_object(cx.scope, properties)
+}
+
+/// Formats a time value for `Date.prototype.toLocaleString` and friends,
+/// using a new `DateTimeFormat` with the given required and default components
+pub fn format_date_locale_string(
+    cx: CallContext,
+    t: f64,
+    required: RequiredComponents,
+    defaults: DefaultComponents,
+) -> Result<Value, Value> {
+    if t.is_nan() {
+        return Ok(string_value(cx.scope, "Invalid Date"));
+    }
+
+    let locales = cx.args.first().unwrap_or_undefined();
+    let options = cx.args.get(1).unwrap_or_undefined();
+    let options = DateTimeFormatOptions::new(cx.scope, locales, options, required, defaults)?;
+    let string = options.format(cx.scope, t)?;
+    Ok(Value::string(cx.scope.intern(string).into()))
+}
+
+pub fn collator_constructor(cx: CallContext) -> Result<Value, Value> {
+    let object = instance_object(cx.scope, cx.new_target, cx.scope.statics.intl.collator_prototype)?;
+    let locales = cx.args.first().unwrap_or_undefined();
+    let options = cx.args.get(1).unwrap_or_undefined();
+    let options = CollatorOptions::new(cx.scope, locales, options)?;
+    let collator = Collator::new(options, object);
+    Ok(Value::object(cx.scope.register(collator)))
+}
+
+pub fn collator_compare(cx: CallContext) -> Result<Value, Value> {
+    let collator = receiver_t::<Collator>(cx.scope, &cx.this, "get Intl.Collator.prototype.compare")?;
+    let target = cx.scope.statics.intl.collator_bound_compare;
+    Ok(bound_function(cx.scope, &collator.bound_compare, target, cx.this))
+}
+
+/// The function returned by the `compare` getter
+pub fn collator_bound_compare(cx: CallContext) -> Result<Value, Value> {
+    let left = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+    let right = cx.args.get(1).unwrap_or_undefined().to_js_string(cx.scope)?;
+    let collator = receiver_t::<Collator>(cx.scope, &cx.this, "Intl.Collator.prototype.compare")?;
+    let ordering = collator.options.compare(left.res(cx.scope), right.res(cx.scope));
+    Ok(Value::number(ordering as i8 as f64))
+}
+
+pub fn collator_resolved_options(cx: CallContext) -> Result<Value, Value> {
+    let options = receiver_t::<Collator>(cx.scope, &cx.this, "Intl.Collator.prototype.resolvedOptions")?
+        .options
+        .clone();
+
+    let properties = [
+        (sym::locale, string_value(cx.scope, options.locale.tag())),
+        (sym::usage, string_value(cx.scope, options.usage.name())),
+        (sym::sensitivity, string_value(cx.scope, options.sensitivity.name())),
+        (sym::ignorePunctuation, Value::boolean(options.ignore_punctuation)),
+        (sym::collation, string_value(cx.scope, "default")),
+        (sym::numeric, Value::boolean(options.numeric)),
+        (sym::caseFirst, string_value(cx.scope, options.case_first.name())),
+    ];
+    create_object(cx.scope, properties)
+}
@@ -1,6 +1,14 @@
+use dash_middle::interner::sym;
+
+use crate::gc::ObjectId;
+use crate::json::stringifier::Stringifier;
+use crate::localscope::LocalScope;
+use crate::value::array::is_array;
 use crate::value::function::native::CallContext;
+use crate::value::object::{Object, OrdObject, OwnKeysMode, PropertyValue, This};
 use crate::value::ops::conversions::ValueConversion;
-use crate::value::{Value, ValueContext};
+use crate::value::propertykey::{PropertyKey, ToPropertyKey};
+use crate::value::{Root, Typeof, Unpack, Value, ValueContext, ValueKind};
 use crate::{json, throw};
 
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
@@ -13,8 +21,66 @@ pub fn parse(cx: CallContext) -> Result<Value, Value> {
     let parse = match json::parser::Parser::new(&bytes, cx.scope).parse() {
         Ok(v) => v,
         Err(e) => {
-            throw!(cx.scope, SyntaxError, "{}", e.to_string())
+            throw!(cx.scope, SyntaxError, "{}", e.describe(&bytes))
         }
     };
-    Ok(parse)
+
+    match cx.args.get(1).copied() {
+        Some(reviver) if matches!(reviver.type_of(cx.scope), Typeof::Function) => {
+            let root = OrdObject::new(cx.scope);
+            let root = cx.scope.register(root);
+            let key = sym::empty.to_key(cx.scope);
+            root.set_property(key, PropertyValue::static_default(parse), cx.scope)?;
+            internalize_property(cx.scope, reviver, root, key)
+        }
+        _ => Ok(parse),
+    }
+}
+
+/// Implements the InternalizeJSONProperty abstract operation, calling the reviver for every property bottom-up
+fn internalize_property(
+    sc: &mut LocalScope<'_>,
+    reviver: Value,
+    holder: ObjectId,
+    name: PropertyKey,
+) -> Result<Value, Value> {
+    let value = holder.get_property(name, sc).root(sc)?;
+
+    if let ValueKind::Object(object) = value.unpack() {
+        let keys = if is_array(value, sc)? {
+            let len = value.length_of_array_like(sc)?;
+            (0..len).map(|i| i.to_key(sc)).collect::<Vec<_>>()
+        } else {
+            let keys = object.own_keys(sc, OwnKeysMode::OnlyEnumerable)?;
+            keys.into_iter()
+                .filter(|key| !matches!(key.unpack(), ValueKind::Symbol(_)))
+                .map(|key| PropertyKey::from_value(sc, key))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        for key in keys {
+            let element = internalize_property(sc, reviver, object, key)?;
+            if let ValueKind::Undefined(_) = element.unpack() {
+                object.delete_property(key, sc)?;
+            } else {
+                object.set_property(key, PropertyValue::static_default(element), sc)?;
+            }
+        }
+    }
+
+    let name = name.to_value(sc);
+    reviver
+        .apply(This::bound(Value::object(holder)), [name, value].into(), sc)
+        .root(sc)
+}
+
+pub fn stringify(cx: CallContext) -> Result<Value, Value> {
+    let value = cx.args.first().unwrap_or_undefined();
+    let replacer = cx.args.get(1).unwrap_or_undefined();
+    let space = cx.args.get(2).unwrap_or_undefined();
+
+    match Stringifier::new(cx.scope, replacer, space)?.stringify(value)? {
+        Some(json) => Ok(Value::string(cx.scope.intern(json).into())),
+        None => Ok(Value::undefined()),
+    }
 }
@@ -1,13 +1,18 @@
+use std::convert::Infallible;
+use std::ops::ControlFlow::{self, Continue};
+
 use crate::throw;
+use crate::value::array::Array;
 use crate::value::function::native::CallContext;
-use crate::value::map::Map;
-use crate::value::object::OrdObject;
-use crate::value::ops::conversions::ValueConversion;
+use crate::value::map::{Map, MapIterator, MapIteratorKind, TableCursor};
+use crate::value::object::{OrdObject, PropertyValue, This};
 use crate::value::propertykey::ToPropertyKey;
-use crate::value::{Root, Value, ValueContext};
+use crate::value::root_ext::RootErrExt;
+use crate::value::{Root, Unpack, Value, ValueContext, ValueKind};
 use dash_middle::interner::sym;
 
-use super::receiver_t;
+use super::array::for_each_iterable_element;
+use super::{create_iter_result, receiver_t};
 
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
     let Some(new_target) = cx.new_target else {
@@ -15,22 +20,38 @@ pub fn constructor(cx: CallContext) -> Result<Value, Value> {
     };
 
     let map = Map::with_obj(OrdObject::instance_for_new_target(new_target, cx.scope)?);
-    if let Some(iter) = cx.args.first() {
-        let len = iter.length_of_array_like(cx.scope)?;
-
-        for i in 0..len {
-            let item = iter.get_property(i.to_key(cx.scope), cx.scope).root(cx.scope)?;
-            let k = item.get_property(sym::zero.to_key(cx.scope), cx.scope).root(cx.scope)?;
-            let v = item.get_property(sym::one.to_key(cx.scope), cx.scope).root(cx.scope)?;
-            map.set(k, v);
-        }
+    if let Some(iterable) = cx.args.first().filter(|v| !v.is_nullish()) {
+        let Continue(()) = for_each_iterable_element(cx.scope, *iterable, |scope, item| {
+            if !matches!(item.unpack(), ValueKind::Object(_)) {
+                throw!(scope, TypeError, "Iterator value is not an entry object")
+            }
+
+            let k = item.get_property(sym::zero.to_key(scope), scope).root(scope)?;
+            let v = item.get_property(sym::one.to_key(scope), scope).root(scope)?;
+            map.set(k.interned(scope), v);
+            Ok(ControlFlow::<Infallible, _>::Continue(()))
+        })?;
+    }
+
+    Ok(Value::object(cx.scope.register(map)))
+}
+
+pub fn group_by(cx: CallContext) -> Result<Value, Value> {
+    let items = cx.args.first().unwrap_or_undefined();
+    let callback = cx.args.get(1).unwrap_or_undefined();
+    let groups = super::object::group_by(cx.scope, items, callback, |scope, key| Ok(key.interned(scope)))?;
+
+    let map = Map::new(cx.scope);
+    for (key, elements) in groups {
+        let elements = Array::from_vec(elements, cx.scope);
+        map.set(key, Value::object(cx.scope.register(elements)));
     }
 
     Ok(Value::object(cx.scope.register(map)))
 }
 
 pub fn set(cx: CallContext) -> Result<Value, Value> {
-    let k = cx.args.first().unwrap_or_undefined();
+    let k = cx.args.first().unwrap_or_undefined().interned(cx.scope);
     let v = cx.args.get(1).unwrap_or_undefined();
     receiver_t::<Map>(cx.scope, &cx.this, "Map.prototype.set")?.set(k, v);
 
@@ -38,21 +59,21 @@ pub fn set(cx: CallContext) -> Result<Value, Value> {
 }
 
 pub fn has(cx: CallContext) -> Result<Value, Value> {
-    let item = cx.args.first().unwrap_or_undefined();
+    let item = cx.args.first().unwrap_or_undefined().interned(cx.scope);
     Ok(Value::boolean(
         receiver_t::<Map>(cx.scope, &cx.this, "Map.prototype.has")?.has(&item),
     ))
 }
 
 pub fn get(cx: CallContext) -> Result<Value, Value> {
-    let item = cx.args.first().unwrap_or_undefined();
+    let item = cx.args.first().unwrap_or_undefined().interned(cx.scope);
     Ok(receiver_t::<Map>(cx.scope, &cx.this, "Map.prototype.get")?
         .get(&item)
         .unwrap_or_undefined())
 }
 
 pub fn delete(cx: CallContext) -> Result<Value, Value> {
-    let item = cx.args.first().unwrap_or_undefined();
+    let item = cx.args.first().unwrap_or_undefined().interned(cx.scope);
     let did_delete = receiver_t::<Map>(cx.scope, &cx.this, "Map.prototype.delete")?.delete(&item);
     Ok(Value::boolean(did_delete))
 }
@@ -67,3 +88,58 @@ pub fn size(cx: CallContext) -> Result<Value, Value> {
         receiver_t::<Map>(cx.scope, &cx.this, "Map.prototype.size")?.size() as f64,
     ))
 }
+
+fn create_iterator(cx: CallContext, kind: MapIteratorKind, what: &'static str) -> Result<Value, Value> {
+    receiver_t::<Map>(cx.scope, &cx.this, what)?;
+    let iter = MapIterator::new(cx.scope, cx.this, kind);
+    Ok(Value::object(cx.scope.register(iter)))
+}
+
+pub fn keys(cx: CallContext) -> Result<Value, Value> {
+    create_iterator(cx, MapIteratorKind::Keys, "Map.prototype.keys")
+}
+
+pub fn values(cx: CallContext) -> Result<Value, Value> {
+    create_iterator(cx, MapIteratorKind::Values, "Map.prototype.values")
+}
+
+pub fn entries(cx: CallContext) -> Result<Value, Value> {
+    create_iterator(cx, MapIteratorKind::Entries, "Map.prototype.entries")
+}
+
+pub fn for_each(cx: CallContext) -> Result<Value, Value> {
+    let map = receiver_t::<Map>(cx.scope, &cx.this, "Map.prototype.forEach")?;
+    let callback = cx.args.first().unwrap_or_undefined();
+    let this_arg = cx.args.get(1).unwrap_or_undefined();
+
+    // Entries added during iteration are visited too, so this walks the live table rather than a snapshot
+    let cursor = TableCursor::default();
+    while let Some((k, v)) = map.next_entry(&cursor) {
+        callback
+            .apply(This::bound(this_arg), [v, k, cx.this].into(), cx.scope)
+            .root_err(cx.scope)?;
+    }
+
+    Ok(Value::undefined())
+}
+
+pub fn iterator_next(cx: CallContext) -> Result<Value, Value> {
+    let iterator = receiver_t::<MapIterator>(cx.scope, &cx.this, "MapIterator.prototype.next")?;
+
+    let value = match iterator.next(cx.scope) {
+        Some((k, v)) => Some(match iterator.kind() {
+            MapIteratorKind::Keys => k,
+            MapIteratorKind::Values => v,
+            MapIteratorKind::Entries => {
+                let entry = Array::from_vec(
+                    vec![PropertyValue::static_default(k), PropertyValue::static_default(v)],
+                    cx.scope,
+                );
+                Value::object(cx.scope.register(entry))
+            }
+        }),
+        None => None,
+    };
+
+    create_iter_result(cx.scope, value)
+}
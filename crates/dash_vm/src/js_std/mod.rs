@@ -1,28 +1,41 @@
 use crate::localscope::LocalScope;
 use crate::value::function::native::CallContext;
-use crate::value::{ExceptionContext, Value};
+use crate::value::object::{Object, OrdObject, PropertyValue};
+use crate::value::propertykey::ToPropertyKey;
+use crate::value::{ExceptionContext, Value, ValueContext};
+use dash_middle::interner::sym;
 
 pub mod array;
 pub mod array_iterator;
 pub mod arraybuffer;
+pub mod async_generator;
+pub mod async_iterator;
+pub mod bigint;
 pub mod boolean;
+pub mod dataview;
 pub mod date;
 pub mod error;
+pub mod finalizationregistry;
 pub mod function;
 pub mod generator;
 pub mod global;
+#[cfg(feature = "intl")]
+pub mod intl;
 pub mod json;
 pub mod map;
 pub mod math;
 pub mod number;
 pub mod object;
 pub mod promise;
+pub mod proxy;
+pub mod reflect;
 pub mod regex;
 pub mod set;
 pub mod string;
 pub mod symbol;
 pub mod typedarray;
 pub mod weakmap;
+pub mod weakref;
 pub mod weakset;
 
 pub fn receiver_t<'a, T: 'static>(
@@ -38,3 +51,22 @@ pub fn receiver_t<'a, T: 'static>(
 pub fn identity_this(cx: CallContext) -> Result<Value, Value> {
     Ok(cx.this)
 }
+
+/// Creates an iterator result object (`{ value, done }`). A `None` value produces a result with `done: true`.
+pub fn create_iter_result(sc: &mut LocalScope<'_>, value: Option<Value>) -> Result<Value, Value> {
+    let done = value.is_none();
+
+    let obj = OrdObject::new(sc);
+    obj.set_property(
+        sym::value.to_key(sc),
+        PropertyValue::static_default(value.unwrap_or_undefined()),
+        sc,
+    )?;
+    obj.set_property(
+        sym::done.to_key(sc),
+        PropertyValue::static_default(Value::boolean(done)),
+        sc,
+    )?;
+
+    Ok(sc.register(obj).into())
+}
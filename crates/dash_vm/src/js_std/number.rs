@@ -1,5 +1,6 @@
 use crate::throw;
-use crate::util::intern_f64;
+use crate::util::{exact_digits, intern_f64, push_exponent, round_digits, shortest_digits};
+use crate::value::bigint::bigint_to_number;
 use crate::value::function::native::CallContext;
 use crate::value::object::{OrdObject, Object};
 use crate::value::ops::conversions::ValueConversion;
@@ -7,7 +8,11 @@ use crate::value::primitive::{MAX_SAFE_INTEGER, MIN_SAFE_INTEGER, Number};
 use crate::value::{Unpack, Value, ValueContext, ValueKind, boxed};
 
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
-    let value = cx.args.first().unwrap_or_undefined().to_number(cx.scope)?;
+    let value = cx.args.first().unwrap_or_undefined().to_numeric(cx.scope)?;
+    let value = match value.unpack() {
+        ValueKind::BigInt(b) => bigint_to_number(b.get(cx.scope)),
+        _ => value.to_number(cx.scope)?,
+    };
     if let Some(new_target) = cx.new_target {
         let value = boxed::Number::with_obj(value, OrdObject::instance_for_new_target(new_target, cx.scope)?);
         Ok(Value::object(cx.scope.register(value)))
@@ -16,38 +21,126 @@ pub fn constructor(cx: CallContext) -> Result<Value, Value> {
     }
 }
 
-pub fn to_string(cx: CallContext) -> Result<Value, Value> {
-    let radix = cx
-        .args
-        .first()
-        .cloned()
-        .map(|v| v.to_number(cx.scope))
-        .transpose()?
-        .map(|n| n as u8)
-        .unwrap_or(10);
-
-    let Some(num) = cx
+fn this_number_value(cx: &mut CallContext, method: &str) -> Result<f64, Value> {
+    match cx
         .this
         .internal_slots(cx.scope)
         .and_then(|slots| slots.number_value(cx.scope))
-    else {
-        throw!(
+    {
+        Some(num) => Ok(num),
+        None => throw!(
             cx.scope,
             TypeError,
-            "Number.prototype.toString called on non-number value"
-        )
+            "Number.prototype.{} called on non-number value",
+            method
+        ),
+    }
+}
+
+pub fn to_string(mut cx: CallContext) -> Result<Value, Value> {
+    let num = this_number_value(&mut cx, "toString")?;
+    let radix = match cx.args.first().copied().and_then(Value::into_option) {
+        Some(radix) => radix.to_integer_or_infinity(cx.scope)?,
+        None => 10.0,
     };
 
-    let re = match radix {
-        2 => cx.scope.intern(format!("{:b}", num as u64).as_ref()),
+    if !(2.0..=36.0).contains(&radix) {
+        throw!(cx.scope, RangeError, "toString() radix must be between 2 and 36")
+    }
+
+    let re = match radix as u32 {
         10 => intern_f64(cx.scope, num),
-        16 => cx.scope.intern(format!("{:x}", num as u64)),
-        _ => throw!(cx.scope, RangeError, "Invalid radix: {}", radix),
+        radix => cx.scope.intern(to_radix_string(num, radix).as_ref()),
     };
 
     Ok(Value::string(re.into()))
 }
 
+/// Converts a number to a string in the given radix, producing the shortest digits that round trip,
+/// like `(0.5).toString(2) === '0.1'`.
+fn to_radix_string(num: f64, radix: u32) -> String {
+    const CHARS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    if num.is_nan() {
+        return String::from("NaN");
+    }
+    if num.is_infinite() {
+        return String::from(if num > 0.0 { "Infinity" } else { "-Infinity" });
+    }
+
+    let value = num.abs();
+    let radix_f = f64::from(radix);
+    let mut integer = value.floor();
+    let mut fraction = value - integer;
+
+    // Half the distance to the next representable number. Digits are generated until
+    // the remaining fraction is within that precision, so that the result round trips
+    let mut delta = (0.5 * (f64::from_bits(value.to_bits() + 1) - value)).max(f64::from_bits(1));
+
+    let mut fraction_digits = Vec::new();
+    if fraction >= delta {
+        loop {
+            fraction *= radix_f;
+            delta *= radix_f;
+            let digit = fraction as u32;
+            fraction_digits.push(digit);
+            fraction -= f64::from(digit);
+
+            if (fraction > 0.5 || (fraction == 0.5 && (digit & 1) == 1)) && fraction + delta > 1.0 {
+                // Round up, propagating the carry through the digits and possibly into the integer part
+                loop {
+                    match fraction_digits.pop() {
+                        Some(digit) if digit + 1 < radix => {
+                            fraction_digits.push(digit + 1);
+                            break;
+                        }
+                        Some(_) => {}
+                        None => {
+                            integer += 1.0;
+                            break;
+                        }
+                    }
+                }
+                break;
+            }
+
+            if fraction < delta {
+                break;
+            }
+        }
+    }
+
+    let mut integer_digits = Vec::new();
+    // Digits beyond the precision of a double are not meaningful
+    while integer / radix_f >= 2f64.powi(53) {
+        integer /= radix_f;
+        integer_digits.push(b'0');
+    }
+    loop {
+        let remainder = integer % radix_f;
+        integer_digits.push(CHARS[remainder as usize]);
+        integer = (integer - remainder) / radix_f;
+        if integer <= 0.0 {
+            break;
+        }
+    }
+
+    let mut out = String::new();
+    if num < 0.0 {
+        out.push('-');
+    }
+    out.extend(integer_digits.iter().rev().map(|&c| char::from(c)));
+    if !fraction_digits.is_empty() {
+        out.push('.');
+        out.extend(fraction_digits.iter().map(|&d| char::from(CHARS[d as usize])));
+    }
+    out
+}
+
+fn digits_to_str(digits: &[u8]) -> &str {
+    std::str::from_utf8(digits).unwrap()
+}
+
 pub fn value_of(cx: CallContext) -> Result<Value, Value> {
     if let Some(num) = cx
         .this
@@ -104,26 +197,163 @@ pub fn is_integer(cx: CallContext) -> Result<Value, Value> {
     Ok(Value::boolean(as_integer(num).is_some()))
 }
 
-pub fn to_fixed(cx: CallContext) -> Result<Value, Value> {
-    let num = cx.this.to_number(cx.scope)?;
-    let decimals = cx
-        .args
-        .first()
-        .cloned()
-        .map(|v| v.to_number(cx.scope))
-        .transpose()?
-        .map(|n| n as usize)
-        .unwrap_or(0);
+pub fn to_fixed(mut cx: CallContext) -> Result<Value, Value> {
+    let num = this_number_value(&mut cx, "toFixed")?;
+    let decimals = cx.args.first().unwrap_or_undefined().to_integer_or_infinity(cx.scope)?;
 
-    if decimals > 100 {
+    if !(0.0..=100.0).contains(&decimals) {
         throw!(
             cx.scope,
             RangeError,
             "toFixed() fractional digits must be between 0 and 100 inclusive"
         )
     };
+    let decimals = decimals as usize;
+
+    if !num.is_finite() || num.abs() >= 1e21 {
+        return Ok(Value::string(intern_f64(cx.scope, num).into()));
+    }
+
+    let mut re = String::new();
+    if num < 0.0 {
+        re.push('-');
+    }
+
+    // Let n be an integer for which n / 10^f - x is as close to zero as possible
+    let mut n = Vec::new();
+    if num != 0.0 {
+        let (digits, exponent) = exact_digits(num.abs());
+        if let Ok(precision) = usize::try_from(exponent + decimals as i32) {
+            let (digits, exponent) = round_digits((digits, exponent), precision);
+            let trailing_zeros = (exponent + decimals as i32) as usize - digits.len();
+            n = digits;
+            n.resize(n.len() + trailing_zeros, b'0');
+        }
+    }
+    if n.is_empty() {
+        n.push(b'0');
+    }
+
+    if decimals == 0 {
+        re.push_str(digits_to_str(&n));
+    } else {
+        if n.len() <= decimals {
+            n.splice(0..0, std::iter::repeat_n(b'0', decimals + 1 - n.len()));
+        }
+        let (integer, fraction) = n.split_at(n.len() - decimals);
+        re.push_str(digits_to_str(integer));
+        re.push('.');
+        re.push_str(digits_to_str(fraction));
+    }
 
-    let re = format!("{num:.decimals$}");
+    Ok(Value::string(cx.scope.intern(re.as_ref()).into()))
+}
+
+pub fn to_exponential(mut cx: CallContext) -> Result<Value, Value> {
+    let num = this_number_value(&mut cx, "toExponential")?;
+    let fraction_digits = cx.args.first().copied().and_then(Value::into_option);
+    let fraction_digits = fraction_digits
+        .map(|f| f.to_integer_or_infinity(cx.scope))
+        .transpose()?;
+
+    if !num.is_finite() {
+        return Ok(Value::string(intern_f64(cx.scope, num).into()));
+    }
+
+    if fraction_digits.is_some_and(|f| !(0.0..=100.0).contains(&f)) {
+        throw!(
+            cx.scope,
+            RangeError,
+            "toExponential() argument must be between 0 and 100"
+        )
+    }
+
+    let (digits, exponent) = match fraction_digits {
+        _ if num == 0.0 => (vec![b'0'; fraction_digits.map_or(1, |f| f as usize + 1)], 1),
+        Some(f) => round_digits(exact_digits(num.abs()), f as usize + 1),
+        None => shortest_digits(num),
+    };
+
+    let mut re = String::new();
+    if num < 0.0 {
+        re.push('-');
+    }
+    re.push_str(digits_to_str(&digits[..1]));
+    if digits.len() > 1 {
+        re.push('.');
+        re.push_str(digits_to_str(&digits[1..]));
+    }
+    push_exponent(&mut re, exponent - 1);
+
+    Ok(Value::string(cx.scope.intern(re.as_ref()).into()))
+}
+
+#[cfg(feature = "intl")]
+pub fn to_locale_string(mut cx: CallContext) -> Result<Value, Value> {
+    use crate::value::intl::number_format::{MathematicalValue, NumberFormatOptions};
+
+    let num = this_number_value(&mut cx, "toLocaleString")?;
+    let locales = cx.args.first().unwrap_or_undefined();
+    let options = cx.args.get(1).unwrap_or_undefined();
+    let number_format = NumberFormatOptions::new(cx.scope, locales, options)?;
+    let string = number_format.format(MathematicalValue::from_f64(num));
+    Ok(Value::string(cx.scope.intern(string).into()))
+}
+
+/// Without locale data, this is the same as `Number.prototype.toString`
+#[cfg(not(feature = "intl"))]
+pub fn to_locale_string(mut cx: CallContext) -> Result<Value, Value> {
+    let num = this_number_value(&mut cx, "toLocaleString")?;
+    Ok(Value::string(intern_f64(cx.scope, num).into()))
+}
+
+pub fn to_precision(mut cx: CallContext) -> Result<Value, Value> {
+    let num = this_number_value(&mut cx, "toPrecision")?;
+    let Some(precision) = cx.args.first().copied().and_then(Value::into_option) else {
+        return Ok(Value::string(intern_f64(cx.scope, num).into()));
+    };
+    let precision = precision.to_integer_or_infinity(cx.scope)?;
+
+    if !num.is_finite() {
+        return Ok(Value::string(intern_f64(cx.scope, num).into()));
+    }
+
+    if !(1.0..=100.0).contains(&precision) {
+        throw!(cx.scope, RangeError, "toPrecision() argument must be between 1 and 100")
+    }
+    let precision = precision as usize;
+
+    let (digits, exponent) = if num == 0.0 {
+        (vec![b'0'; precision], 1)
+    } else {
+        round_digits(exact_digits(num.abs()), precision)
+    };
+    let e = exponent - 1;
+
+    let mut re = String::new();
+    if num < 0.0 {
+        re.push('-');
+    }
+
+    if e < -6 || e >= precision as i32 {
+        re.push_str(digits_to_str(&digits[..1]));
+        if precision > 1 {
+            re.push('.');
+            re.push_str(digits_to_str(&digits[1..]));
+        }
+        push_exponent(&mut re, e);
+    } else if e >= 0 {
+        let split = e as usize + 1;
+        re.push_str(digits_to_str(&digits[..split]));
+        if split < precision {
+            re.push('.');
+            re.push_str(digits_to_str(&digits[split..]));
+        }
+    } else {
+        re.push_str("0.");
+        re.extend(std::iter::repeat_n('0', (-(e + 1)) as usize));
+        re.push_str(digits_to_str(&digits));
+    }
 
     Ok(Value::string(cx.scope.intern(re.as_ref()).into()))
 }
@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::hash::Hash;
 use std::ops::ControlFlow;
 
 use crate::gc::ObjectId;
@@ -5,13 +8,19 @@ use crate::localscope::LocalScope;
 use crate::throw;
 use crate::value::array::Array;
 use crate::value::function::native::CallContext;
-use crate::value::object::{IntegrityLevel, Object, OrdObject, OwnKeysMode, PropertyDataDescriptor, PropertyValue};
+use crate::value::object::ordinary::set_property_or_throw;
+use crate::value::object::{
+    IntegrityLevel, Object, OrdObject, OwnKeysMode, PropertyDataDescriptor, PropertyValue, This,
+};
 use crate::value::ops::conversions::ValueConversion;
+use crate::value::ops::equality;
 use crate::value::propertykey::{PropertyKey, ToPropertyKey};
 use crate::value::root_ext::RootErrExt;
 use crate::value::{Root, Typeof, Unpack, Value, ValueContext, ValueKind};
 use dash_middle::interner::sym;
 
+use super::array::for_each_iterable_element;
+
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
     match cx.args.first() {
         Some(v) => v.to_object(cx.scope).map(Value::object),
@@ -58,6 +67,14 @@ pub fn get_own_property_symbols(cx: CallContext) -> Result<Value, Value> {
 
 pub fn to_string(cx: CallContext) -> Result<Value, Value> {
     fn to_string_inner(scope: &mut LocalScope<'_>, o: ObjectId) -> Result<Value, Value> {
+        let tag = o
+            .get_property(scope.statics.symbol_to_string_tag.to_key(scope), scope)
+            .root(scope)?;
+        if let ValueKind::String(tag) = tag.unpack() {
+            let tag = format!("[object {}]", tag.res(scope));
+            return Ok(Value::string(scope.intern(tag).into()));
+        }
+
         let constructor = o
             .get_property(sym::constructor.to_key(scope), scope)
             .root(scope)?
@@ -144,10 +161,23 @@ pub fn has_own_property(cx: CallContext) -> Result<Value, Value> {
 
     let key = cx.args.first().unwrap_or_undefined();
     let key = PropertyKey::from_value(cx.scope, key)?;
-    let desc = o.get_property_descriptor(key, cx.scope).root_err(cx.scope)?;
+    let desc = o.get_own_property_descriptor(key, cx.scope).root_err(cx.scope)?;
     Ok(Value::boolean(desc.is_some()))
 }
 
+fn define_property_or_throw(
+    object: ObjectId,
+    key: PropertyKey,
+    value: PropertyValue,
+    sc: &mut LocalScope<'_>,
+) -> Result<(), Value> {
+    if !object.define_property(key, value, sc)? {
+        let key = key.to_value(sc).to_js_string(sc)?.res(sc).to_owned();
+        throw!(sc, TypeError, "Cannot redefine property: {}", key)
+    }
+    Ok(())
+}
+
 pub fn define_property(cx: CallContext) -> Result<Value, Value> {
     let object = match cx.args.first().unpack() {
         Some(ValueKind::Object(o)) => o,
@@ -176,7 +206,26 @@ pub fn define_property(cx: CallContext) -> Result<Value, Value> {
 
     let value = PropertyValue::from_descriptor_value(cx.scope, Value::object(descriptor))?;
 
-    object.set_property(property, value, cx.scope)?;
+    if !object.is_extensible(cx.scope)?
+        && object
+            .get_own_property_descriptor(property, cx.scope)
+            .root_err(cx.scope)?
+            .is_none()
+    {
+        let property = property
+            .to_value(cx.scope)
+            .to_js_string(cx.scope)?
+            .res(cx.scope)
+            .to_owned();
+        throw!(
+            cx.scope,
+            TypeError,
+            "Cannot define property {}, object is not extensible",
+            property
+        )
+    }
+
+    define_property_or_throw(object, property, value, cx.scope)?;
 
     Ok(Value::object(object))
 }
@@ -196,7 +245,7 @@ pub fn define_properties(cx: CallContext) -> Result<Value, Value> {
         let key = key.to_js_string(cx.scope)?;
         let descriptor = properties.get_property(key.to_key(cx.scope), cx.scope).root(cx.scope)?;
         let descriptor = PropertyValue::from_descriptor_value(cx.scope, descriptor)?;
-        object.set_property(key.to_key(cx.scope), descriptor, cx.scope)?;
+        define_property_or_throw(object, key.to_key(cx.scope), descriptor, cx.scope)?;
     }
 
     Ok(Value::object(object))
@@ -210,7 +259,7 @@ pub fn assign(cx: CallContext) -> Result<Value, Value> {
         for key in source.own_keys(cx.scope, OwnKeysMode::OnlyEnumerable)? {
             let key = PropertyKey::from_value(cx.scope, key)?;
             let desc = source.get_own_property(key, cx.scope).root(cx.scope)?;
-            to.set_property(key, PropertyValue::static_default(desc), cx.scope)?;
+            set_property_or_throw(Value::object(to), key, desc, cx.scope)?;
         }
     }
     Ok(Value::object(to))
@@ -236,11 +285,140 @@ pub fn entries(cx: CallContext) -> Result<Value, Value> {
     Ok(Value::object(cx.scope.register(entries)))
 }
 
+pub fn values(cx: CallContext) -> Result<Value, Value> {
+    let mut values = Vec::new();
+    let obj = cx.args.first().unwrap_or_undefined().to_object(cx.scope)?;
+    for key in obj.own_keys(cx.scope, OwnKeysMode::OnlyEnumerable)? {
+        let key = PropertyKey::from_value(cx.scope, key)?;
+        let value = obj.get_own_property(key, cx.scope).root(cx.scope)?;
+        values.push(PropertyValue::static_default(value));
+    }
+
+    let values = Array::from_vec(values, cx.scope);
+    Ok(Value::object(cx.scope.register(values)))
+}
+
+pub fn from_entries(cx: CallContext) -> Result<Value, Value> {
+    let iterable = cx.args.first().unwrap_or_undefined();
+    if iterable.is_nullish() {
+        throw!(cx.scope, TypeError, "Object.fromEntries requires an iterable")
+    }
+
+    let obj = cx.scope.register(OrdObject::new(cx.scope));
+    let ControlFlow::Continue(()) = for_each_iterable_element(cx.scope, iterable, |scope, entry| {
+        if !matches!(entry.unpack(), ValueKind::Object(_)) {
+            throw!(scope, TypeError, "Iterator value is not an entry object")
+        }
+
+        let key = entry.get_property(sym::zero.to_key(scope), scope).root(scope)?;
+        let value = entry.get_property(sym::one.to_key(scope), scope).root(scope)?;
+        let key = PropertyKey::from_value(scope, key)?;
+        obj.set_property(key, PropertyValue::static_default(value), scope)?;
+        Ok(ControlFlow::<Infallible, _>::Continue(()))
+    })?;
+
+    Ok(Value::object(obj))
+}
+
+pub fn is(cx: CallContext) -> Result<Value, Value> {
+    let left = cx.args.first().unwrap_or_undefined();
+    let right = cx.args.get(1).unwrap_or_undefined();
+    Ok(Value::boolean(equality::same_value(left, right, cx.scope)))
+}
+
+/// Implements the GroupBy abstract operation, collecting the items of an iterable into groups
+/// in the order in which each group key is first returned by the callback
+pub fn group_by<K: Hash + Eq + Copy>(
+    scope: &mut LocalScope<'_>,
+    items: Value,
+    callback: Value,
+    mut coerce_key: impl FnMut(&mut LocalScope<'_>, Value) -> Result<K, Value>,
+) -> Result<Vec<(K, Vec<PropertyValue>)>, Value> {
+    if items.is_nullish() {
+        throw!(scope, TypeError, "groupBy requires an iterable")
+    }
+    if !matches!(callback.type_of(scope), Typeof::Function) {
+        throw!(scope, TypeError, "groupBy callback is not a function")
+    }
+
+    let mut groups: Vec<(K, Vec<PropertyValue>)> = Vec::new();
+    let mut indices = HashMap::new();
+    let mut k = 0;
+
+    let ControlFlow::Continue(()) = for_each_iterable_element(scope, items, |scope, value| {
+        let key = callback
+            .apply(This::default(), [value, Value::number(k as f64)].into(), scope)
+            .root(scope)?;
+        let key = coerce_key(scope, key)?;
+        k += 1;
+
+        let index = *indices.entry(key).or_insert_with(|| {
+            groups.push((key, Vec::new()));
+            groups.len() - 1
+        });
+        groups[index].1.push(PropertyValue::static_default(value));
+        Ok(ControlFlow::<Infallible, _>::Continue(()))
+    })?;
+
+    Ok(groups)
+}
+
+pub fn group_by_object(cx: CallContext) -> Result<Value, Value> {
+    let items = cx.args.first().unwrap_or_undefined();
+    let callback = cx.args.get(1).unwrap_or_undefined();
+    let groups = group_by(cx.scope, items, callback, |scope, key| {
+        PropertyKey::from_value(scope, key)
+    })?;
+
+    let obj = cx.scope.register(OrdObject::null());
+    for (key, elements) in groups {
+        let elements = Array::from_vec(elements, cx.scope);
+        let elements = cx.scope.register(elements);
+        obj.set_property(key, PropertyValue::static_default(Value::object(elements)), cx.scope)?;
+    }
+
+    Ok(Value::object(obj))
+}
+
+pub fn has_own(cx: CallContext) -> Result<Value, Value> {
+    let obj = cx.args.first().unwrap_or_undefined().to_object(cx.scope)?;
+    let key = PropertyKey::from_value(cx.scope, cx.args.get(1).unwrap_or_undefined())?;
+    let desc = obj.get_own_property_descriptor(key, cx.scope).root_err(cx.scope)?;
+    Ok(Value::boolean(desc.is_some()))
+}
+
 pub fn get_prototype_of(cx: CallContext) -> Result<Value, Value> {
     let obj = cx.args.first().unwrap_or_undefined().to_object(cx.scope)?;
     obj.get_prototype(cx.scope)
 }
 
+/// The getter of `Object.prototype.__proto__`
+pub fn proto_getter(cx: CallContext) -> Result<Value, Value> {
+    let obj = cx.this.to_object(cx.scope)?;
+    obj.get_prototype(cx.scope)
+}
+
+/// The setter of `Object.prototype.__proto__`
+pub fn proto_setter(cx: CallContext) -> Result<Value, Value> {
+    if cx.this.is_nullish() {
+        throw!(
+            cx.scope,
+            TypeError,
+            "Object.prototype.__proto__ called on null or undefined"
+        );
+    }
+
+    // Non-object prototypes are ignored, as is setting the prototype of a primitive
+    let prototype = cx.args.first().unwrap_or_undefined();
+    if let ValueKind::Object(_) | ValueKind::Null(_) = prototype.unpack()
+        && let ValueKind::Object(obj) = cx.this.unpack()
+    {
+        obj.set_prototype(prototype, cx.scope)?;
+    }
+
+    Ok(Value::undefined())
+}
+
 pub fn set_prototype_of(cx: CallContext) -> Result<Value, Value> {
     let obj = cx.args.first().unwrap_or_undefined().to_object(cx.scope)?;
     let target = cx.args.get(1).unwrap_or_undefined();
@@ -296,3 +474,36 @@ pub fn seal(cx: CallContext) -> Result<Value, Value> {
         Ok(arg)
     }
 }
+
+pub fn prevent_extensions(cx: CallContext) -> Result<Value, Value> {
+    let arg = cx.args.first().unwrap_or_undefined();
+    if let ValueKind::Object(o) = arg.unpack() {
+        o.prevent_extensions(cx.scope)?;
+    }
+    Ok(arg)
+}
+
+pub fn is_extensible(cx: CallContext) -> Result<Value, Value> {
+    match cx.args.first().unwrap_or_undefined().unpack() {
+        ValueKind::Object(o) => o.is_extensible(cx.scope).map(Value::boolean),
+        _ => Ok(Value::boolean(false)),
+    }
+}
+
+pub fn is_frozen(cx: CallContext) -> Result<Value, Value> {
+    match cx.args.first().unwrap_or_undefined().unpack() {
+        ValueKind::Object(o) => o
+            .test_integrity_level(IntegrityLevel::Frozen, cx.scope)
+            .map(Value::boolean),
+        _ => Ok(Value::boolean(true)),
+    }
+}
+
+pub fn is_sealed(cx: CallContext) -> Result<Value, Value> {
+    match cx.args.first().unwrap_or_undefined().unpack() {
+        ValueKind::Object(o) => o
+            .test_integrity_level(IntegrityLevel::Sealed, cx.scope)
+            .map(Value::boolean),
+        _ => Ok(Value::boolean(true)),
+    }
+}
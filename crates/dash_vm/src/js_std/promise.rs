@@ -1,16 +1,24 @@
+use std::cell::{Cell, RefCell};
+use std::convert::Infallible;
+use std::ops::ControlFlow;
+use std::rc::Rc;
+
 use dash_proc_macro::Trace;
 
 use crate::gc::ObjectId;
+use crate::gc::trace::{Trace, TraceCtxt};
+use crate::js_std::array::for_each_iterable_element;
 use crate::localscope::LocalScope;
+use crate::value::array::Array;
 use crate::value::function::args::CallArgs;
 use crate::value::function::bound::BoundFunction;
 use crate::value::function::native::CallContext;
-use crate::value::object::{Object, OrdObject, This};
-use crate::value::promise::{Promise, PromiseRejecter, PromiseResolver, PromiseState};
+use crate::value::object::{Object, OrdObject, PropertyValue, This};
+use crate::value::promise::{Promise, PromiseRejecter, PromiseResolver, PromiseState, wrap_resolved_promise};
 use crate::value::propertykey::ToPropertyKey;
 use crate::value::root_ext::RootErrExt;
 use crate::value::{Root, Typeof, Unpack, Unrooted, Value, ValueContext, ValueKind};
-use crate::{Vm, delegate, extract, throw};
+use crate::{PromiseAction, Vm, delegate, extract, throw};
 use dash_middle::interner::sym;
 
 use super::receiver_t;
@@ -28,23 +36,35 @@ pub fn constructor(cx: CallContext) -> Result<Value, Value> {
     let promise = Promise::with_obj(OrdObject::instance_for_new_target(new_target, cx.scope)?);
     let promise = cx.scope.register(promise);
 
-    let (resolve, reject) = {
-        let r1 = PromiseResolver::new(cx.scope, promise);
-        let r2 = PromiseRejecter::new(cx.scope, promise);
-        (cx.scope.register(r1), cx.scope.register(r2))
-    };
+    let (resolve, reject) = resolving_functions(cx.scope, promise);
 
-    initiator
-        .apply(
-            This::default(),
-            [Value::object(resolve), Value::object(reject)].into(),
-            cx.scope,
-        )
-        .root_err(cx.scope)?;
+    let result = cx.scope.catch_exception(|sc| {
+        initiator
+            .apply(
+                This::default(),
+                [Value::object(resolve), Value::object(reject)].into(),
+                sc,
+            )
+            .root_err(sc)
+    });
+
+    if let Err(err) = result {
+        // Exceptions thrown by the executor reject the promise
+        reject
+            .apply(This::default(), [err].into(), cx.scope)
+            .root_err(cx.scope)?;
+    }
 
     Ok(Value::object(cx.scope.register(promise)))
 }
 
+/// Creates a resolve and reject function pair for the given promise
+fn resolving_functions(sc: &mut LocalScope<'_>, promise: ObjectId) -> (ObjectId, ObjectId) {
+    let resolver = PromiseResolver::new(sc, promise);
+    let rejecter = PromiseRejecter::new(sc, promise);
+    (sc.register(resolver), sc.register(rejecter))
+}
+
 pub fn resolve(cx: CallContext) -> Result<Value, Value> {
     let value = cx.args.first().unwrap_or_undefined();
     if value.extract::<Promise>(cx.scope).is_some() {
@@ -80,59 +100,332 @@ pub fn then(cx: CallContext) -> Result<Value, Value> {
     let mut state = promise.state().borrow_mut();
 
     let then_promise = cx.scope.mk_promise();
-    let resolver = cx.scope.register(PromiseResolver::new(cx.scope, then_promise));
-    let fulfill_handler = fulfill_handler.map(|handler| {
-        cx.scope
-            .register(ThenTask::new(cx.scope, then_promise, handler, resolver))
-    });
-    let reject_handler = reject_handler.map(|handler| {
-        cx.scope
-            .register(ThenTask::new(cx.scope, then_promise, handler, resolver))
-    });
+    let (resolver, rejecter) = resolving_functions(cx.scope, then_promise);
+    let fulfill_task = ThenTask::new(cx.scope, fulfill_handler, PromiseAction::Resolve, resolver, rejecter);
+    let fulfill_task = cx.scope.register(fulfill_task);
+    let reject_task = ThenTask::new(cx.scope, reject_handler, PromiseAction::Reject, resolver, rejecter);
+    let reject_task = cx.scope.register(reject_task);
 
     match &mut *state {
         PromiseState::Pending { resolve, reject } => {
-            if let Some(handler) = fulfill_handler {
-                resolve.push(handler);
-            }
-            if let Some(handler) = reject_handler {
-                reject.push(handler);
-            }
+            resolve.push(fulfill_task);
+            reject.push(reject_task);
         }
         PromiseState::Resolved(value) => {
-            if let Some(handler) = fulfill_handler {
-                let bf = BoundFunction::new(cx.scope, handler, None, [*value].into());
-                let bf = cx.scope.register(bf);
-                cx.scope.add_async_task(bf);
-            }
+            let bf = BoundFunction::new(cx.scope, fulfill_task, None, [*value].into());
+            let bf = cx.scope.register(bf);
+            cx.scope.add_async_task(bf);
         }
         PromiseState::Rejected { value, caught } => {
-            if let Some(handler) = reject_handler {
-                *caught = true;
-                let bf = BoundFunction::new(cx.scope, handler, None, [*value].into());
-                let bf = cx.scope.register(bf);
-                cx.scope.add_async_task(bf);
-            }
+            *caught = true;
+            let bf = BoundFunction::new(cx.scope, reject_task, None, [*value].into());
+            let bf = cx.scope.register(bf);
+            cx.scope.add_async_task(bf);
         }
     }
 
     Ok(Value::object(then_promise))
 }
 
+/// Calls `promise.then(on_fulfilled, on_rejected)`, looking up `then` dynamically
+pub fn invoke_then(
+    sc: &mut LocalScope<'_>,
+    promise: Value,
+    on_fulfilled: Value,
+    on_rejected: Value,
+) -> Result<Value, Value> {
+    let then = promise.get_property(sym::then.to_key(sc), sc).root(sc)?;
+    then.apply(This::bound(promise), [on_fulfilled, on_rejected].into(), sc)
+        .root(sc)
+}
+
+pub fn catch(cx: CallContext) -> Result<Value, Value> {
+    let on_rejected = cx.args.first().unwrap_or_undefined();
+    invoke_then(cx.scope, cx.this, Value::undefined(), on_rejected)
+}
+
+pub fn finally(cx: CallContext) -> Result<Value, Value> {
+    let on_finally = cx.args.first().unwrap_or_undefined();
+
+    let (on_fulfilled, on_rejected) = match on_finally.unpack() {
+        ValueKind::Object(on_finally) if matches!(on_finally.type_of(cx.scope), Typeof::Function) => {
+            let on_fulfilled = FinallyHandler::new(cx.scope, on_finally, PromiseAction::Resolve);
+            let on_rejected = FinallyHandler::new(cx.scope, on_finally, PromiseAction::Reject);
+            (
+                Value::object(cx.scope.register(on_fulfilled)),
+                Value::object(cx.scope.register(on_rejected)),
+            )
+        }
+        _ => (on_finally, on_finally),
+    };
+
+    invoke_then(cx.scope, cx.this, on_fulfilled, on_rejected)
+}
+
+pub fn with_resolvers(cx: CallContext) -> Result<Value, Value> {
+    let promise = cx.scope.mk_promise();
+    let (resolver, rejecter) = resolving_functions(cx.scope, promise);
+
+    let result = OrdObject::new(cx.scope);
+    for (key, value) in [
+        (sym::promise, promise),
+        (sym::resolve, resolver),
+        (sym::reject, rejecter),
+    ] {
+        result.set_property(
+            key.to_key(cx.scope),
+            PropertyValue::static_default(Value::object(value)),
+            cx.scope,
+        )?;
+    }
+
+    Ok(Value::object(cx.scope.register(result)))
+}
+
+pub fn all(cx: CallContext) -> Result<Value, Value> {
+    combinator(cx, Combinator::All)
+}
+
+pub fn all_settled(cx: CallContext) -> Result<Value, Value> {
+    combinator(cx, Combinator::AllSettled)
+}
+
+pub fn any(cx: CallContext) -> Result<Value, Value> {
+    combinator(cx, Combinator::Any)
+}
+
+pub fn race(cx: CallContext) -> Result<Value, Value> {
+    combinator(cx, Combinator::Race)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Combinator {
+    All,
+    AllSettled,
+    Any,
+    Race,
+}
+
+/// Shared state of a single `Promise.all`, `Promise.allSettled` or `Promise.any` call
+#[derive(Debug, Trace)]
+struct CombinatorState {
+    /// The fulfilled values (or rejection reasons for `Promise.any`) in iteration order
+    values: RefCell<Vec<Value>>,
+    /// Whether an element function has already been called for each index
+    called: RefCell<Vec<bool>>,
+    /// The number of elements that have not settled yet, plus one while the iterable is still being consumed
+    remaining: Cell<usize>,
+    /// The promise returned by the combinator
+    promise: ObjectId,
+}
+
+impl CombinatorState {
+    /// Decrements the remaining element count and settles the combined promise once it reaches zero
+    fn finish_element(&self, sc: &mut LocalScope<'_>, combinator: Combinator) -> Result<(), Value> {
+        self.remaining.set(self.remaining.get() - 1);
+        if self.remaining.get() > 0 {
+            return Ok(());
+        }
+
+        let values = self
+            .values
+            .borrow()
+            .iter()
+            .copied()
+            .map(PropertyValue::static_default)
+            .collect();
+        let values = Value::object(sc.register(Array::from_vec(values, sc)));
+
+        let (action, value) = if combinator == Combinator::Any {
+            let message = Value::string(sc.intern("All promises were rejected").into());
+            let error = Value::object(sc.statics.aggregate_error_ctor)
+                .construct(This::default(), [values, message].into(), sc)
+                .root(sc)?;
+            (PromiseAction::Reject, error)
+        } else {
+            (PromiseAction::Resolve, values)
+        };
+
+        sc.drive_promise(
+            action,
+            self.promise.extract::<Promise>(sc).unwrap(),
+            self.promise,
+            [value].into(),
+        );
+        Ok(())
+    }
+}
+
+/// Implements the shared iteration logic of the promise combinators
+fn combinator(cx: CallContext, combinator: Combinator) -> Result<Value, Value> {
+    let promise = cx.scope.mk_promise();
+    let (resolver, rejecter) = resolving_functions(cx.scope, promise);
+    let state = Rc::new(CombinatorState {
+        values: RefCell::new(Vec::new()),
+        called: RefCell::new(Vec::new()),
+        remaining: Cell::new(1),
+        promise,
+    });
+
+    let iterable = cx.args.first().unwrap_or_undefined();
+    let result = cx.scope.catch_exception(|sc| {
+        for_each_iterable_element(sc, iterable, |sc, value| {
+            let index = state.values.borrow().len();
+            state.values.borrow_mut().push(Value::undefined());
+            state.called.borrow_mut().push(false);
+            state.remaining.set(state.remaining.get() + 1);
+
+            let mut element = |kind| {
+                let element = CombinatorElement::new(sc, state.clone(), index, kind);
+                Value::object(sc.register(element))
+            };
+            let (on_fulfilled, on_rejected) = match combinator {
+                Combinator::All => (element(ElementKind::AllResolve), Value::object(rejecter)),
+                Combinator::AllSettled => (
+                    element(ElementKind::AllSettledResolve),
+                    element(ElementKind::AllSettledReject),
+                ),
+                Combinator::Any => (Value::object(resolver), element(ElementKind::AnyReject)),
+                Combinator::Race => (Value::object(resolver), Value::object(rejecter)),
+            };
+
+            let next = wrap_resolved_promise(sc, value);
+            invoke_then(sc, next, on_fulfilled, on_rejected)?;
+            Ok(ControlFlow::<Infallible>::Continue(()))
+        })
+        .and_then(|_| match combinator {
+            Combinator::Race => Ok(()),
+            _ => state.finish_element(sc, combinator),
+        })
+    });
+
+    if let Err(err) = result {
+        // Errors during iteration reject the returned promise
+        rejecter
+            .apply(This::default(), [err].into(), cx.scope)
+            .root_err(cx.scope)?;
+    }
+
+    Ok(Value::object(promise))
+}
+
+#[derive(Debug, Copy, Clone)]
+enum ElementKind {
+    AllResolve,
+    AllSettledResolve,
+    AllSettledReject,
+    AnyReject,
+}
+
+unsafe impl Trace for ElementKind {
+    fn trace(&self, _: &mut TraceCtxt<'_>) {}
+}
+
+/// The per-element resolve or reject function passed to `.then()` by `Promise.all`, `Promise.allSettled` and `Promise.any`
+#[derive(Debug, Trace)]
+struct CombinatorElement {
+    state: Rc<CombinatorState>,
+    index: usize,
+    kind: ElementKind,
+    obj: OrdObject,
+}
+
+impl CombinatorElement {
+    fn new(vm: &Vm, state: Rc<CombinatorState>, index: usize, kind: ElementKind) -> Self {
+        Self {
+            state,
+            index,
+            kind,
+            obj: OrdObject::new(vm),
+        }
+    }
+}
+
+impl Object for CombinatorElement {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        own_keys
+    );
+
+    fn apply(
+        &self,
+        _callee: ObjectId,
+        _this: This,
+        args: CallArgs,
+        scope: &mut LocalScope<'_>,
+    ) -> Result<Unrooted, Unrooted> {
+        if std::mem::replace(&mut self.state.called.borrow_mut()[self.index], true) {
+            return Ok(Value::undefined().into());
+        }
+
+        let value = args.first().unwrap_or_undefined();
+        let (value, combinator) = match self.kind {
+            ElementKind::AllResolve => (value, Combinator::All),
+            ElementKind::AnyReject => (value, Combinator::Any),
+            ElementKind::AllSettledResolve | ElementKind::AllSettledReject => {
+                let (status, key) = match self.kind {
+                    ElementKind::AllSettledResolve => (sym::fulfilled, sym::value),
+                    _ => (sym::rejected, sym::reason),
+                };
+                let result = OrdObject::new(scope);
+                result.set_property(
+                    sym::status.to_key(scope),
+                    PropertyValue::static_default(Value::string(status.into())),
+                    scope,
+                )?;
+                result.set_property(key.to_key(scope), PropertyValue::static_default(value), scope)?;
+                (Value::object(scope.register(result)), Combinator::AllSettled)
+            }
+        };
+
+        self.state.values.borrow_mut()[self.index] = value;
+        self.state.finish_element(scope, combinator)?;
+        Ok(Value::undefined().into())
+    }
+
+    fn type_of(&self, _: &Vm) -> Typeof {
+        Typeof::Function
+    }
+
+    extract!(self);
+}
+
+/// A reaction to a settled promise, created by `Promise.prototype.then`.
+///
+/// Calls the handler (if there is one) and settles the promise returned by `then()` with its result.
 #[derive(Debug, Trace)]
 struct ThenTask {
-    then_promise: ObjectId,
-    handler: ObjectId,
+    handler: Option<ObjectId>,
+    /// What to do with the value if there is no handler
+    action: PromiseAction,
     resolver: ObjectId,
+    rejecter: ObjectId,
     obj: OrdObject,
 }
 
 impl ThenTask {
-    pub fn new(vm: &Vm, then_promise: ObjectId, handler: ObjectId, resolver: ObjectId) -> Self {
+    pub fn new(
+        vm: &Vm,
+        handler: Option<ObjectId>,
+        action: PromiseAction,
+        resolver: ObjectId,
+        rejecter: ObjectId,
+    ) -> Self {
         Self {
-            then_promise,
             handler,
+            action,
             resolver,
+            rejecter,
             obj: OrdObject::new(vm),
         }
     }
@@ -145,9 +438,12 @@ impl Object for ThenTask {
         get_property,
         get_property_descriptor,
         set_property,
+        define_property,
         delete_property,
         set_prototype,
         get_prototype,
+        prevent_extensions,
+        is_extensible,
         own_keys
     );
 
@@ -158,30 +454,43 @@ impl Object for ThenTask {
         args: CallArgs,
         scope: &mut LocalScope<'_>,
     ) -> Result<Unrooted, Unrooted> {
-        let resolved = args.first().unwrap_or_undefined();
-        let ret = self
-            .handler
-            .apply(This::default(), [resolved].into(), scope)
-            .root(scope)?;
+        let value = args.first().unwrap_or_undefined();
 
-        let ret_then = ret
-            .into_option()
-            .map(|ret| ret.get_property(sym::then.to_key(scope), scope))
-            .transpose()?
-            .root(scope)
-            .unwrap_or_undefined();
-
-        match ret_then.unpack() {
-            ValueKind::Undefined(..) => {
-                // Not a promise. Call resolver(value)
-                let bf = BoundFunction::new(scope, self.resolver, None, [ret].into());
+        let ret = match (self.handler, self.action) {
+            (Some(handler), _) => {
+                scope.catch_exception(|sc| handler.apply(This::default(), [value].into(), sc).root(sc))
+            }
+            (None, PromiseAction::Resolve) => Ok(value),
+            (None, PromiseAction::Reject) => Err(value),
+        };
+
+        let ret = match ret {
+            Ok(ret) => ret,
+            Err(err) => {
+                let bf = BoundFunction::new(scope, self.rejecter, None, [err].into());
                 let bf = scope.register(bf);
                 scope.add_async_task(bf);
+                return Ok(Value::undefined().into());
             }
-            _ => {
-                // Is a promise. Call value.then(resolver)
-                ret_then.apply(This::bound(ret), [Value::object(self.resolver)].into(), scope)?;
-            }
+        };
+
+        let ret_then = match ret.unpack() {
+            ValueKind::Object(_) => ret.get_property(sym::then.to_key(scope), scope).root(scope)?,
+            _ => Value::undefined(),
+        };
+
+        if matches!(ret_then.type_of(scope), Typeof::Function) {
+            // Is a thenable. Call value.then(resolver, rejecter)
+            ret_then.apply(
+                This::bound(ret),
+                [Value::object(self.resolver), Value::object(self.rejecter)].into(),
+                scope,
+            )?;
+        } else {
+            // Not a thenable. Call resolver(value)
+            let bf = BoundFunction::new(scope, self.resolver, None, [ret].into());
+            let bf = scope.register(bf);
+            scope.add_async_task(bf);
         }
 
         Ok(Value::undefined().into())
@@ -189,3 +498,113 @@ impl Object for ThenTask {
 
     extract!(self);
 }
+
+/// The `.then()` handlers created by `Promise.prototype.finally`.
+///
+/// Calls the `onFinally` callback and then passes through the original value or reason once its result has settled.
+#[derive(Debug, Trace)]
+struct FinallyHandler {
+    on_finally: ObjectId,
+    action: PromiseAction,
+    obj: OrdObject,
+}
+
+impl FinallyHandler {
+    fn new(vm: &Vm, on_finally: ObjectId, action: PromiseAction) -> Self {
+        Self {
+            on_finally,
+            action,
+            obj: OrdObject::new(vm),
+        }
+    }
+}
+
+impl Object for FinallyHandler {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        own_keys
+    );
+
+    fn apply(
+        &self,
+        _callee: ObjectId,
+        _this: This,
+        args: CallArgs,
+        scope: &mut LocalScope<'_>,
+    ) -> Result<Unrooted, Unrooted> {
+        let value = args.first().unwrap_or_undefined();
+        let result = self
+            .on_finally
+            .apply(This::default(), CallArgs::empty(), scope)
+            .root(scope)?;
+
+        let promise = wrap_resolved_promise(scope, result);
+        let passthrough = PassthroughValue {
+            value,
+            action: self.action,
+            obj: OrdObject::new(scope),
+        };
+        let passthrough = Value::object(scope.register(passthrough));
+        Ok(invoke_then(scope, promise, passthrough, Value::undefined())?.into())
+    }
+
+    fn type_of(&self, _: &Vm) -> Typeof {
+        Typeof::Function
+    }
+
+    extract!(self);
+}
+
+/// Returns or throws a fixed value when called
+#[derive(Debug, Trace)]
+struct PassthroughValue {
+    value: Value,
+    action: PromiseAction,
+    obj: OrdObject,
+}
+
+impl Object for PassthroughValue {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        own_keys
+    );
+
+    fn apply(
+        &self,
+        _callee: ObjectId,
+        _this: This,
+        _args: CallArgs,
+        _scope: &mut LocalScope<'_>,
+    ) -> Result<Unrooted, Unrooted> {
+        match self.action {
+            PromiseAction::Resolve => Ok(self.value.into()),
+            PromiseAction::Reject => Err(self.value.into()),
+        }
+    }
+
+    fn type_of(&self, _: &Vm) -> Typeof {
+        Typeof::Function
+    }
+
+    extract!(self);
+}
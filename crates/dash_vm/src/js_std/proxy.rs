@@ -0,0 +1,52 @@
+use dash_middle::interner::sym;
+
+use crate::gc::ObjectId;
+use crate::localscope::LocalScope;
+use crate::throw;
+use crate::value::function::native::CallContext;
+use crate::value::object::{Object, OrdObject, PropertyValue};
+use crate::value::propertykey::ToPropertyKey;
+use crate::value::proxy::{Proxy, ProxyRevoker};
+use crate::value::{Unpack, Value, ValueKind};
+
+fn create_proxy(sc: &mut LocalScope<'_>, target: Option<&Value>, handler: Option<&Value>) -> Result<ObjectId, Value> {
+    match (target.unpack(), handler.unpack()) {
+        (Some(ValueKind::Object(target)), Some(ValueKind::Object(handler))) => {
+            Ok(sc.register(Proxy::new(target, handler)))
+        }
+        _ => throw!(
+            sc,
+            TypeError,
+            "Cannot create proxy with a non-object as target or handler"
+        ),
+    }
+}
+
+pub fn constructor(cx: CallContext) -> Result<Value, Value> {
+    if cx.new_target.is_none() {
+        throw!(cx.scope, TypeError, "Proxy constructor requires new")
+    }
+
+    let proxy = create_proxy(cx.scope, cx.args.first(), cx.args.get(1))?;
+    Ok(Value::object(proxy))
+}
+
+pub fn revocable(cx: CallContext) -> Result<Value, Value> {
+    let proxy = create_proxy(cx.scope, cx.args.first(), cx.args.get(1))?;
+    let revoke = ProxyRevoker::new(cx.scope, proxy);
+    let revoke = cx.scope.register(revoke);
+
+    let result = OrdObject::new(cx.scope);
+    result.set_property(
+        sym::proxy.to_key(cx.scope),
+        PropertyValue::static_default(Value::object(proxy)),
+        cx.scope,
+    )?;
+    result.set_property(
+        sym::revoke.to_key(cx.scope),
+        PropertyValue::static_default(Value::object(revoke)),
+        cx.scope,
+    )?;
+
+    Ok(Value::object(cx.scope.register(result)))
+}
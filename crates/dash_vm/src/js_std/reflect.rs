@@ -0,0 +1,168 @@
+use crate::gc::ObjectId;
+use crate::localscope::LocalScope;
+use crate::throw;
+use crate::value::array::Array;
+use crate::value::function::args::CallArgs;
+use crate::value::function::native::CallContext;
+use crate::value::object::ordinary::set_with_receiver;
+use crate::value::object::{Object, OwnKeysMode, PropertyValue, This};
+use crate::value::ops::conversions::ValueConversion;
+use crate::value::propertykey::{PropertyKey, ToPropertyKey};
+use crate::value::root_ext::RootErrExt;
+use crate::value::{Root, Typeof, Unpack, Value, ValueContext, ValueKind};
+
+fn target_object(sc: &mut LocalScope<'_>, value: Option<&Value>, what: &'static str) -> Result<ObjectId, Value> {
+    match value.unpack() {
+        Some(ValueKind::Object(o)) => Ok(o),
+        _ => throw!(sc, TypeError, "{} called on non-object", what),
+    }
+}
+
+/// Implements CreateListFromArrayLike
+fn list_from_array_like(sc: &mut LocalScope<'_>, value: Value) -> Result<CallArgs, Value> {
+    if !matches!(value.unpack(), ValueKind::Object(_)) {
+        throw!(sc, TypeError, "CreateListFromArrayLike called on non-object")
+    }
+
+    let len = value.length_of_array_like(sc)?;
+    let mut list = CallArgs::empty();
+    for i in 0..len {
+        let item = value.get_property(i.to_key(sc), sc).root(sc)?;
+        list.extend([item]);
+    }
+    Ok(list)
+}
+
+pub fn apply(cx: CallContext) -> Result<Value, Value> {
+    let target = cx.args.first().unwrap_or_undefined();
+    if !matches!(target.type_of(cx.scope), Typeof::Function) {
+        throw!(cx.scope, TypeError, "Reflect.apply target must be a function")
+    }
+    let this = cx.args.get(1).unwrap_or_undefined();
+    let args = list_from_array_like(cx.scope, cx.args.get(2).unwrap_or_undefined())?;
+
+    target.apply(This::bound(this), args, cx.scope).root(cx.scope)
+}
+
+pub fn construct(cx: CallContext) -> Result<Value, Value> {
+    let target = target_object(cx.scope, cx.args.first(), "Reflect.construct")?;
+    let new_target = match cx.args.get(2) {
+        Some(new_target) => target_object(cx.scope, Some(new_target), "Reflect.construct")?,
+        None => target,
+    };
+    let args = list_from_array_like(cx.scope, cx.args.get(1).unwrap_or_undefined())?;
+
+    target
+        .construct_with_target(This::default(), args, new_target, cx.scope)
+        .root(cx.scope)
+}
+
+pub fn define_property(cx: CallContext) -> Result<Value, Value> {
+    let target = target_object(cx.scope, cx.args.first(), "Reflect.defineProperty")?;
+    let key = PropertyKey::from_value(cx.scope, cx.args.get(1).unwrap_or_undefined())?;
+    let descriptor = match cx.args.get(2).unpack() {
+        Some(ValueKind::Object(o)) => Value::object(o),
+        _ => throw!(cx.scope, TypeError, "Property descriptor must be an object"),
+    };
+    let descriptor = PropertyValue::from_descriptor_value(cx.scope, descriptor)?;
+
+    if !target.is_extensible(cx.scope)?
+        && target
+            .get_own_property_descriptor(key, cx.scope)
+            .root_err(cx.scope)?
+            .is_none()
+    {
+        return Ok(Value::boolean(false));
+    }
+
+    target.define_property(key, descriptor, cx.scope).map(Value::boolean)
+}
+
+pub fn delete_property(cx: CallContext) -> Result<Value, Value> {
+    let target = target_object(cx.scope, cx.args.first(), "Reflect.deleteProperty")?;
+    let key = PropertyKey::from_value(cx.scope, cx.args.get(1).unwrap_or_undefined())?;
+
+    target.delete_property(key, cx.scope).map(Value::boolean)
+}
+
+pub fn get(cx: CallContext) -> Result<Value, Value> {
+    let target = target_object(cx.scope, cx.args.first(), "Reflect.get")?;
+    let key = PropertyKey::from_value(cx.scope, cx.args.get(1).unwrap_or_undefined())?;
+    let receiver = cx.args.get(2).copied().unwrap_or(Value::object(target));
+
+    Object::get_property(&target, This::bound(receiver), key, cx.scope).root(cx.scope)
+}
+
+pub fn get_own_property_descriptor(cx: CallContext) -> Result<Value, Value> {
+    let target = target_object(cx.scope, cx.args.first(), "Reflect.getOwnPropertyDescriptor")?;
+    let key = PropertyKey::from_value(cx.scope, cx.args.get(1).unwrap_or_undefined())?;
+
+    Ok(target
+        .get_own_property_descriptor(key, cx.scope)
+        .root_err(cx.scope)?
+        .map(|d| d.to_descriptor_value(cx.scope))
+        .transpose()?
+        .unwrap_or_undefined())
+}
+
+pub fn get_prototype_of(cx: CallContext) -> Result<Value, Value> {
+    let target = target_object(cx.scope, cx.args.first(), "Reflect.getPrototypeOf")?;
+    target.get_prototype(cx.scope)
+}
+
+pub fn has(cx: CallContext) -> Result<Value, Value> {
+    let target = target_object(cx.scope, cx.args.first(), "Reflect.has")?;
+    let key = PropertyKey::from_value(cx.scope, cx.args.get(1).unwrap_or_undefined())?;
+
+    let found = target.has_property(key, cx.scope).root_err(cx.scope)?;
+    Ok(Value::boolean(found))
+}
+
+pub fn is_extensible(cx: CallContext) -> Result<Value, Value> {
+    let target = target_object(cx.scope, cx.args.first(), "Reflect.isExtensible")?;
+    target.is_extensible(cx.scope).map(Value::boolean)
+}
+
+pub fn own_keys(cx: CallContext) -> Result<Value, Value> {
+    let target = target_object(cx.scope, cx.args.first(), "Reflect.ownKeys")?;
+
+    let mut keys = Vec::new();
+    for key in target.own_keys(cx.scope, OwnKeysMode::All)? {
+        // Normalize index keys into strings
+        let key = PropertyKey::from_value(cx.scope, key)?.to_value(cx.scope);
+        keys.push(PropertyValue::static_default(key));
+    }
+
+    let keys = Array::from_vec(keys, cx.scope);
+    Ok(Value::object(cx.scope.register(keys)))
+}
+
+pub fn prevent_extensions(cx: CallContext) -> Result<Value, Value> {
+    let target = target_object(cx.scope, cx.args.first(), "Reflect.preventExtensions")?;
+    target.prevent_extensions(cx.scope)?;
+    Ok(Value::boolean(true))
+}
+
+pub fn set(cx: CallContext) -> Result<Value, Value> {
+    let target = target_object(cx.scope, cx.args.first(), "Reflect.set")?;
+    let key = PropertyKey::from_value(cx.scope, cx.args.get(1).unwrap_or_undefined())?;
+    let value = cx.args.get(2).unwrap_or_undefined();
+    let receiver = cx.args.get(3).copied().unwrap_or(Value::object(target));
+
+    set_with_receiver(Value::object(target), key, value, receiver, cx.scope).map(Value::boolean)
+}
+
+pub fn set_prototype_of(cx: CallContext) -> Result<Value, Value> {
+    let target = target_object(cx.scope, cx.args.first(), "Reflect.setPrototypeOf")?;
+    let prototype = cx.args.get(1).unwrap_or_undefined();
+    if !matches!(prototype.unpack(), ValueKind::Object(_) | ValueKind::Null(_)) {
+        throw!(cx.scope, TypeError, "Object prototype may only be an Object or null")
+    }
+
+    if !target.is_extensible(cx.scope)? {
+        return Ok(Value::boolean(target.get_prototype(cx.scope)? == prototype));
+    }
+
+    target.set_prototype(prototype, cx.scope)?;
+    Ok(Value::boolean(true))
+}
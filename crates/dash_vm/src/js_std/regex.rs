@@ -1,27 +1,40 @@
+use crate::localscope::LocalScope;
 use crate::throw;
-use crate::value::array::Array;
+use crate::value::array::{Array, ArrayIterator};
 use crate::value::function::native::CallContext;
-use crate::value::object::{OrdObject, PropertyValue};
+use crate::value::object::ordinary::set_property_or_throw;
+use crate::value::object::{Object, OrdObject, PropertyValue, This};
 use crate::value::ops::conversions::ValueConversion;
+use crate::value::propertykey::ToPropertyKey;
 use crate::value::regex::{RegExp, RegExpInner};
-use crate::value::{Value, ValueContext};
+use crate::value::string::JsString;
+use crate::value::{Root, Typeof, Unpack, Value, ValueContext, ValueKind};
 use dash_middle::interner::sym;
 use dash_regex::{EvalSuccess, Flags};
 
 use super::receiver_t;
+use super::string::get_substitution;
 
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
-    let pattern = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
-    let flags = match cx
-        .args
-        .get(1)
-        .map(|v| v.to_js_string(cx.scope))
-        .transpose()?
-        .map(|s| s.res(cx.scope).parse::<Flags>())
-    {
-        Some(Ok(flags)) => flags,
-        Some(Err(err)) => throw!(cx.scope, SyntaxError, "Invalid RegExp flags: {:?}", err),
-        None => Flags::empty(),
+    let pattern = cx.args.first().unwrap_or_undefined();
+    let flags = cx.args.get(1).unwrap_or_undefined();
+
+    // new RegExp(/x/g) copies the source and, unless overridden, the flags of an existing RegExp
+    let (pattern, flags) = match pattern.extract::<RegExp>(cx.scope).and_then(RegExp::inner) {
+        Some(inner) => {
+            let flags = match flags.unpack() {
+                ValueKind::Undefined(_) => inner.regex.flags(),
+                _ => parse_flags(cx.scope, flags)?,
+            };
+            (inner.source, flags)
+        }
+        None => {
+            let pattern = match pattern.unpack() {
+                ValueKind::Undefined(_) => sym::empty.into(),
+                _ => pattern.to_js_string(cx.scope)?,
+            };
+            (pattern, parse_flags(cx.scope, flags)?)
+        }
     };
 
     let nodes = match dash_regex::compile(pattern.res(cx.scope), flags) {
@@ -39,83 +52,524 @@ pub fn constructor(cx: CallContext) -> Result<Value, Value> {
     Ok(Value::object(cx.scope.register(regex)))
 }
 
+fn parse_flags(sc: &mut LocalScope<'_>, flags: Value) -> Result<Flags, Value> {
+    if let ValueKind::Undefined(_) = flags.unpack() {
+        return Ok(Flags::empty());
+    }
+
+    let flags = flags.to_js_string(sc)?;
+    match flags.res(sc).parse::<Flags>() {
+        Ok(flags) => Ok(flags),
+        Err(err) => throw!(sc, SyntaxError, "Invalid RegExp flags: {}", err),
+    }
+}
+
+/// Implements the RegExpBuiltinExec abstract operation, minus the creation of the result array.
+///
+/// Matching starts at `lastIndex` if the global or sticky flag is set, which is then updated to the end of the match.
+fn builtin_exec(inner: &RegExpInner, text: &str) -> Option<EvalSuccess> {
+    let RegExpInner { regex, last_index, .. } = inner;
+    let uses_last_index = regex.flags().intersects(Flags::GLOBAL | Flags::STICKY);
+    let start = if uses_last_index { last_index.get() } else { 0 };
+
+    match regex.eval_from(text, start) {
+        Ok(result) => {
+            if uses_last_index {
+                last_index.set(result.full_match().end as usize);
+            }
+            Some(result)
+        }
+        Err(_) => {
+            if uses_last_index {
+                last_index.set(0);
+            }
+            None
+        }
+    }
+}
+
 pub fn test(cx: CallContext) -> Result<Value, Value> {
     let text = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
 
     let regex = receiver_t::<RegExp>(cx.scope, &cx.this, "RegExp.prototype.test")?;
 
-    let RegExpInner { regex, last_index, .. } = match regex.inner() {
-        Some(nodes) => nodes,
+    let inner = match regex.inner() {
+        Some(inner) => inner,
         None => throw!(cx.scope, TypeError, "Receiver must be an initialized RegExp object"),
     };
 
     let text = text.res(cx.scope);
-    let is_global = regex.flags().contains(Flags::GLOBAL);
+    Ok(Value::boolean(builtin_exec(inner, text).is_some()))
+}
+
+pub fn exec(cx: CallContext<'_, '_>) -> Result<Value, Value> {
+    let text = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+
+    let regex = receiver_t::<RegExp>(cx.scope, &cx.this, "RegExp.prototype.exec")?;
+
+    let inner = match regex.inner() {
+        Some(inner) => inner,
+        None => throw!(cx.scope, TypeError, "Receiver must be an initialized RegExp object"),
+    };
+
+    exec_result(cx.scope, inner, text)
+}
+
+/// Runs the regex and creates the match result array, or returns null if there is no match
+fn exec_result(sc: &mut LocalScope<'_>, inner: &RegExpInner, input: JsString) -> Result<Value, Value> {
+    let has_indices = inner.regex.flags().contains(Flags::HAS_INDICES);
+    let text = input.res(sc).to_owned();
+
+    let Some(EvalSuccess { groups }) = builtin_exec(inner, &text) else {
+        return Ok(Value::null());
+    };
+
+    let substrings = groups
+        .iter()
+        .map(|group| match *group {
+            Some((from, to, _)) => Value::string(sc.intern(&text[from as usize..to as usize]).into()),
+            None => Value::undefined(),
+        })
+        .collect::<Vec<_>>();
+    let named_groups = named_groups_object(sc, inner, &substrings)?;
 
-    if is_global && last_index.get() >= text.len() {
-        last_index.set(0);
-        return Ok(Value::boolean(false));
+    let matches = substrings.into_iter().map(PropertyValue::static_default).collect();
+    let matches = Array::from_vec(matches, sc);
+    let matches = sc.register(matches);
+
+    let index = groups[0].map_or(0, |(from, ..)| from);
+    matches.set_property(
+        sym::index.to_key(sc),
+        PropertyValue::static_default(Value::number(f64::from(index))),
+        sc,
+    )?;
+    matches.set_property(
+        sym::input.to_key(sc),
+        PropertyValue::static_default(Value::string(input)),
+        sc,
+    )?;
+    matches.set_property(sym::groups.to_key(sc), PropertyValue::static_default(named_groups), sc)?;
+
+    if has_indices {
+        let pairs = groups
+            .iter()
+            .map(|group| match *group {
+                Some((from, to, _)) => {
+                    let pair = Array::from_vec(
+                        vec![
+                            PropertyValue::static_default(Value::number(f64::from(from))),
+                            PropertyValue::static_default(Value::number(f64::from(to))),
+                        ],
+                        sc,
+                    );
+                    Value::object(sc.register(pair))
+                }
+                None => Value::undefined(),
+            })
+            .collect::<Vec<_>>();
+        let named_groups = named_groups_object(sc, inner, &pairs)?;
+
+        let indices = pairs.into_iter().map(PropertyValue::static_default).collect();
+        let indices = Array::from_vec(indices, sc);
+        let indices = sc.register(indices);
+        indices.set_property(sym::groups.to_key(sc), PropertyValue::static_default(named_groups), sc)?;
+        matches.set_property(
+            sym::indices.to_key(sc),
+            PropertyValue::static_default(Value::object(indices)),
+            sc,
+        )?;
     }
 
-    match regex.eval(&text[last_index.get()..]) {
-        Ok(EvalSuccess { groups }) => {
-            if is_global {
-                last_index.set(last_index.get() + groups[0].unwrap().1 as usize);
-            }
-            Ok(Value::boolean(true))
+    Ok(Value::object(matches))
+}
+
+/// Creates the `groups` object of a match result, which maps the name of every named group to its value in `values`.
+/// Returns undefined if the regex has no named groups.
+fn named_groups_object(sc: &mut LocalScope<'_>, inner: &RegExpInner, values: &[Value]) -> Result<Value, Value> {
+    let names = inner.regex.group_names();
+    if names.is_empty() {
+        return Ok(Value::undefined());
+    }
+
+    let object = sc.register(OrdObject::null());
+    for (name, id) in names {
+        let key = sc.intern(&**name).to_key(sc);
+        object.set_property(key, PropertyValue::static_default(values[*id as usize]), sc)?;
+    }
+    Ok(Value::object(object))
+}
+
+pub fn to_string(cx: CallContext) -> Result<Value, Value> {
+    let source = cx
+        .this
+        .get_property(sym::source.to_key(cx.scope), cx.scope)
+        .root(cx.scope)?
+        .to_js_string(cx.scope)?;
+    let flags = cx
+        .this
+        .get_property(sym::flags.to_key(cx.scope), cx.scope)
+        .root(cx.scope)?
+        .to_js_string(cx.scope)?;
+
+    let source = match source.res(cx.scope) {
+        "" => "(?:)",
+        source => source,
+    };
+    let out = format!("/{source}/{}", flags.res(cx.scope));
+    Ok(Value::string(JsString::from(cx.scope.intern(out))))
+}
+
+/// Implements the RegExpExec abstract operation, which calls the `exec` method of the object.
+fn regexp_exec(sc: &mut LocalScope<'_>, regexp: Value, text: JsString) -> Result<Value, Value> {
+    let exec = regexp.get_property(sym::exec.to_key(sc), sc).root(sc)?;
+    if let Typeof::Function = exec.type_of(sc) {
+        let result = exec
+            .apply(This::bound(regexp), [Value::string(text)].into(), sc)
+            .root(sc)?;
+        if !matches!(result.unpack(), ValueKind::Object(_) | ValueKind::Null(_)) {
+            throw!(
+                sc,
+                TypeError,
+                "RegExp exec method returned something other than an Object or null"
+            );
         }
-        Err(_) => {
-            if is_global {
-                last_index.set(0);
+        return Ok(result);
+    }
+
+    match regexp.extract::<RegExp>(sc).and_then(RegExp::inner) {
+        Some(inner) => exec_result(sc, inner, text),
+        None => throw!(sc, TypeError, "Receiver must be an initialized RegExp object"),
+    }
+}
+
+fn get_last_index(sc: &mut LocalScope<'_>, regexp: Value) -> Result<usize, Value> {
+    regexp
+        .get_property(sym::lastIndex.to_key(sc), sc)
+        .root(sc)?
+        .to_length_u(sc)
+}
+
+fn set_last_index(sc: &mut LocalScope<'_>, regexp: Value, index: usize) -> Result<(), Value> {
+    set_property_or_throw(regexp, sym::lastIndex.to_key(sc), Value::number(index as f64), sc)
+}
+
+/// Returns the index of the character after the one at `index`
+fn advance_string_index(text: &str, index: usize) -> usize {
+    match text.get(index..).and_then(|rest| rest.chars().next()) {
+        Some(c) => index + c.len_utf8(),
+        None => index + 1,
+    }
+}
+
+/// Moves `lastIndex` past an empty match so that global matching does not get stuck
+fn advance_last_index(sc: &mut LocalScope<'_>, regexp: Value, text: JsString) -> Result<(), Value> {
+    let index = get_last_index(sc, regexp)?;
+    let index = advance_string_index(text.res(sc), index);
+    set_last_index(sc, regexp, index)
+}
+
+fn get_flags(sc: &mut LocalScope<'_>, regexp: Value) -> Result<String, Value> {
+    let flags = regexp
+        .get_property(sym::flags.to_key(sc), sc)
+        .root(sc)?
+        .to_js_string(sc)?;
+    Ok(flags.res(sc).to_owned())
+}
+
+fn this_object(cx: &mut CallContext<'_, '_>, method: &str) -> Result<Value, Value> {
+    match cx.this.unpack() {
+        ValueKind::Object(_) => Ok(cx.this),
+        _ => throw!(
+            cx.scope,
+            TypeError,
+            "RegExp.prototype[{}] called on a non-object",
+            method
+        ),
+    }
+}
+
+/// Runs `exec` repeatedly for global regexes and collects all of the match results.
+/// Non-global regexes only produce at most one result.
+fn collect_results(sc: &mut LocalScope<'_>, regexp: Value, text: JsString, global: bool) -> Result<Vec<Value>, Value> {
+    if global {
+        set_last_index(sc, regexp, 0)?;
+    }
+
+    let mut results = Vec::new();
+    loop {
+        let result = regexp_exec(sc, regexp, text)?;
+        if let ValueKind::Null(_) = result.unpack() {
+            break;
+        }
+        results.push(result);
+
+        if !global {
+            break;
+        }
+
+        let matched = result
+            .get_property(sym::zero.to_key(sc), sc)
+            .root(sc)?
+            .to_js_string(sc)?;
+        if matched.res(sc).is_empty() {
+            advance_last_index(sc, regexp, text)?;
+        }
+    }
+    Ok(results)
+}
+
+pub fn symbol_match(mut cx: CallContext) -> Result<Value, Value> {
+    let regexp = this_object(&mut cx, "Symbol.match")?;
+    let text = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+
+    let global = get_flags(cx.scope, regexp)?.contains('g');
+    if !global {
+        return regexp_exec(cx.scope, regexp, text);
+    }
+
+    let results = collect_results(cx.scope, regexp, text, true)?;
+    if results.is_empty() {
+        return Ok(Value::null());
+    }
+
+    let mut matches = Vec::with_capacity(results.len());
+    for result in results {
+        let matched = result
+            .get_property(sym::zero.to_key(cx.scope), cx.scope)
+            .root(cx.scope)?;
+        matches.push(PropertyValue::static_default(matched));
+    }
+    let matches = Array::from_vec(matches, cx.scope);
+    Ok(Value::object(cx.scope.register(matches)))
+}
+
+pub fn symbol_match_all(mut cx: CallContext) -> Result<Value, Value> {
+    let regexp = this_object(&mut cx, "Symbol.matchAll")?;
+    let text = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+
+    let flags = get_flags(cx.scope, regexp)?;
+    let global = flags.contains('g');
+    let flags = Value::string(cx.scope.intern(flags).into());
+    let matcher = Value::object(cx.scope.statics.regexp_ctor)
+        .construct(This::default(), [regexp, flags].into(), cx.scope)
+        .root(cx.scope)?;
+    let last_index = get_last_index(cx.scope, regexp)?;
+    set_last_index(cx.scope, matcher, last_index)?;
+
+    // Matches are collected upfront rather than lazily when the iterator is advanced
+    let mut results = Vec::new();
+    loop {
+        let result = regexp_exec(cx.scope, matcher, text)?;
+        if let ValueKind::Null(_) = result.unpack() {
+            break;
+        }
+        results.push(PropertyValue::static_default(result));
+
+        if !global {
+            break;
+        }
+
+        let matched = result
+            .get_property(sym::zero.to_key(cx.scope), cx.scope)
+            .root(cx.scope)?
+            .to_js_string(cx.scope)?;
+        if matched.res(cx.scope).is_empty() {
+            advance_last_index(cx.scope, matcher, text)?;
+        }
+    }
+
+    let results = Array::from_vec(results, cx.scope);
+    let results = cx.scope.register(results);
+    let iter = ArrayIterator::new(cx.scope, Value::object(results))?;
+    Ok(Value::object(cx.scope.register(iter)))
+}
+
+pub fn symbol_search(mut cx: CallContext) -> Result<Value, Value> {
+    let regexp = this_object(&mut cx, "Symbol.search")?;
+    let text = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+
+    let previous_last_index = regexp
+        .get_property(sym::lastIndex.to_key(cx.scope), cx.scope)
+        .root(cx.scope)?;
+    set_last_index(cx.scope, regexp, 0)?;
+    let result = regexp_exec(cx.scope, regexp, text)?;
+    regexp.set_property(
+        sym::lastIndex.to_key(cx.scope),
+        PropertyValue::static_default(previous_last_index),
+        cx.scope,
+    )?;
+
+    if let ValueKind::Null(_) = result.unpack() {
+        Ok(Value::number(-1.0))
+    } else {
+        result
+            .get_property(sym::index.to_key(cx.scope), cx.scope)
+            .root(cx.scope)
+    }
+}
+
+pub fn symbol_replace(mut cx: CallContext) -> Result<Value, Value> {
+    let regexp = this_object(&mut cx, "Symbol.replace")?;
+    let text = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+    let replace_value = cx.args.get(1).unwrap_or_undefined();
+
+    let replace_template = match replace_value.type_of(cx.scope) {
+        Typeof::Function => None,
+        _ => Some(replace_value.to_js_string(cx.scope)?.res(cx.scope).to_owned()),
+    };
+
+    let global = get_flags(cx.scope, regexp)?.contains('g');
+    let results = collect_results(cx.scope, regexp, text, global)?;
+
+    let string = text.res(cx.scope).to_owned();
+    let mut output = String::with_capacity(string.len());
+    let mut next_source_position = 0;
+
+    for result in results {
+        let capture_count = result.length_of_array_like(cx.scope)?.saturating_sub(1);
+        let matched = result
+            .get_property(sym::zero.to_key(cx.scope), cx.scope)
+            .root(cx.scope)?
+            .to_js_string(cx.scope)?;
+        let position = result
+            .get_property(sym::index.to_key(cx.scope), cx.scope)
+            .root(cx.scope)?
+            .to_integer_or_infinity(cx.scope)?
+            .clamp(0.0, string.len() as f64) as usize;
+
+        let mut captures = Vec::with_capacity(capture_count);
+        for index in 1..=capture_count {
+            let capture = result.get_property(index.to_key(cx.scope), cx.scope).root(cx.scope)?;
+            let capture = match capture.unpack() {
+                ValueKind::Undefined(_) => capture,
+                _ => Value::string(capture.to_js_string(cx.scope)?),
+            };
+            captures.push(capture);
+        }
+        let named_captures = result
+            .get_property(sym::groups.to_key(cx.scope), cx.scope)
+            .root(cx.scope)?;
+
+        let replacement = match &replace_template {
+            Some(template) => {
+                let named_captures = match named_captures.unpack() {
+                    ValueKind::Undefined(_) => named_captures,
+                    _ => Value::object(named_captures.to_object(cx.scope)?),
+                };
+                let matched = matched.res(cx.scope).to_owned();
+                get_substitution(
+                    cx.scope,
+                    &matched,
+                    &string,
+                    position,
+                    &captures,
+                    named_captures,
+                    template,
+                )?
+            }
+            None => {
+                let mut args = Vec::with_capacity(captures.len() + 4);
+                args.push(Value::string(matched));
+                args.extend(captures);
+                args.push(Value::number(position as f64));
+                args.push(Value::string(text));
+                if !matches!(named_captures.unpack(), ValueKind::Undefined(_)) {
+                    args.push(named_captures);
+                }
+
+                let replacement = replace_value
+                    .apply(This::default(), args.into(), cx.scope)
+                    .root(cx.scope)?
+                    .to_js_string(cx.scope)?;
+                replacement.res(cx.scope).to_owned()
             }
-            Ok(Value::boolean(false))
+        };
+
+        if position >= next_source_position {
+            output.push_str(string.get(next_source_position..position).unwrap_or_default());
+            output.push_str(&replacement);
+            next_source_position = position + matched.len(cx.scope);
         }
     }
+
+    output.push_str(string.get(next_source_position..).unwrap_or_default());
+    Ok(Value::string(cx.scope.intern(output).into()))
 }
 
-pub fn exec(cx: CallContext<'_, '_>) -> Result<Value, Value> {
+pub fn symbol_split(mut cx: CallContext) -> Result<Value, Value> {
+    let regexp = this_object(&mut cx, "Symbol.split")?;
     let text = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+    let limit = cx.args.get(1).unwrap_or_undefined();
+    let limit = match limit.unpack() {
+        ValueKind::Undefined(_) => u32::MAX,
+        _ => limit.to_int32(cx.scope)? as u32,
+    } as usize;
 
-    let regex = receiver_t::<RegExp>(cx.scope, &cx.this, "RegExp.prototype.exec")?;
+    // Matching is done with a copy of the regex that has the sticky flag set, so that every position can be tried
+    let mut flags = get_flags(cx.scope, regexp)?;
+    if !flags.contains('y') {
+        flags.push('y');
+    }
+    let flags = Value::string(cx.scope.intern(flags).into());
+    let splitter = Value::object(cx.scope.statics.regexp_ctor)
+        .construct(This::default(), [regexp, flags].into(), cx.scope)
+        .root(cx.scope)?;
 
-    let RegExpInner { regex, last_index, .. } = match regex.inner() {
-        Some(nodes) => nodes,
-        None => throw!(cx.scope, TypeError, "Receiver must be an initialized RegExp object"),
+    let string = text.res(cx.scope).to_owned();
+    let mut parts = Vec::new();
+    let finish = |parts: Vec<Value>, sc: &mut LocalScope<'_>| {
+        let parts = parts.into_iter().map(PropertyValue::static_default).collect();
+        let parts = Array::from_vec(parts, sc);
+        Ok(Value::object(sc.register(parts)))
     };
 
-    let text = text.res(cx.scope).to_owned();
-    let is_global = regex.flags().contains(Flags::GLOBAL);
+    if limit == 0 {
+        return finish(parts, cx.scope);
+    }
 
-    if is_global && last_index.get() >= text.len() {
-        last_index.set(0);
-        return Ok(Value::null());
+    if string.is_empty() {
+        if let ValueKind::Null(_) = regexp_exec(cx.scope, splitter, text)?.unpack() {
+            parts.push(Value::string(text));
+        }
+        return finish(parts, cx.scope);
     }
 
-    match regex.eval(&text[last_index.get()..]) {
-        Ok(EvalSuccess { groups }) => {
-            if is_global {
-                last_index.set(last_index.get() + groups[0].unwrap().1 as usize);
-            }
+    let mut start = 0;
+    let mut position = 0;
+    while position < string.len() {
+        set_last_index(cx.scope, splitter, position)?;
+        let result = regexp_exec(cx.scope, splitter, text)?;
+        if let ValueKind::Null(_) = result.unpack() {
+            position = advance_string_index(&string, position);
+            continue;
+        }
 
-            let groups = groups
-                .into_iter()
-                .map(|group| {
-                    let sub = match group {
-                        Some((from, to, _)) => cx.scope.intern(&text[from as usize..to as usize]).into(),
-                        None => sym::null.into(),
-                    };
-                    PropertyValue::static_default(Value::string(sub))
-                })
-                .collect();
+        let end = get_last_index(cx.scope, splitter)?.min(string.len());
+        if end == start {
+            position = advance_string_index(&string, position);
+            continue;
+        }
 
-            let groups = Array::from_vec(groups, cx.scope);
-            Ok(Value::object(cx.scope.register(groups)))
+        let part = cx.scope.intern(string.get(start..position).unwrap_or_default());
+        parts.push(Value::string(part.into()));
+        if parts.len() == limit {
+            return finish(parts, cx.scope);
         }
-        Err(_) => {
-            if is_global {
-                last_index.set(0);
+
+        let capture_count = result.length_of_array_like(cx.scope)?.saturating_sub(1);
+        for index in 1..=capture_count {
+            let capture = result.get_property(index.to_key(cx.scope), cx.scope).root(cx.scope)?;
+            parts.push(capture);
+            if parts.len() == limit {
+                return finish(parts, cx.scope);
             }
-            Ok(Value::null())
         }
+
+        start = end;
+        position = start;
     }
+
+    let part = cx.scope.intern(string.get(start..).unwrap_or_default());
+    parts.push(Value::string(part.into()));
+    finish(parts, cx.scope)
 }
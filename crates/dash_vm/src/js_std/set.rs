@@ -1,12 +1,17 @@
+use std::convert::Infallible;
+use std::ops::ControlFlow::{self, Continue};
+
 use crate::throw;
+use crate::value::array::Array;
 use crate::value::function::native::CallContext;
-use crate::value::object::OrdObject;
-use crate::value::ops::conversions::ValueConversion;
-use crate::value::propertykey::ToPropertyKey;
-use crate::value::set::Set;
-use crate::value::{Root, Value, ValueContext};
+use crate::value::map::TableCursor;
+use crate::value::object::{OrdObject, PropertyValue, This};
+use crate::value::root_ext::RootErrExt;
+use crate::value::set::{Set, SetIterator, SetIteratorKind};
+use crate::value::{Value, ValueContext};
 
-use super::receiver_t;
+use super::array::for_each_iterable_element;
+use super::{create_iter_result, receiver_t};
 
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
     let Some(new_target) = cx.new_target else {
@@ -14,34 +19,32 @@ pub fn constructor(cx: CallContext) -> Result<Value, Value> {
     };
 
     let set = Set::with_obj(OrdObject::instance_for_new_target(new_target, cx.scope)?);
-    if let Some(iter) = cx.args.first() {
-        let len = iter.length_of_array_like(cx.scope)?;
-
-        for i in 0..len {
-            let item = iter.get_property(i.to_key(cx.scope), cx.scope).root(cx.scope)?;
-            set.add(item);
-        }
+    if let Some(iterable) = cx.args.first().filter(|v| !v.is_nullish()) {
+        let Continue(()) = for_each_iterable_element(cx.scope, *iterable, |scope, item| {
+            set.add(item.interned(scope));
+            Ok(ControlFlow::<Infallible, _>::Continue(()))
+        })?;
     }
 
     Ok(Value::object(cx.scope.register(set)))
 }
 
 pub fn add(cx: CallContext) -> Result<Value, Value> {
-    let item = cx.args.first().unwrap_or_undefined();
+    let item = cx.args.first().unwrap_or_undefined().interned(cx.scope);
     receiver_t::<Set>(cx.scope, &cx.this, "Set.prototype.add")?.add(item);
 
     Ok(cx.this)
 }
 
 pub fn has(cx: CallContext) -> Result<Value, Value> {
-    let item = cx.args.first().unwrap_or_undefined();
+    let item = cx.args.first().unwrap_or_undefined().interned(cx.scope);
     Ok(Value::boolean(
         receiver_t::<Set>(cx.scope, &cx.this, "Set.prototype.has")?.has(&item),
     ))
 }
 
 pub fn delete(cx: CallContext) -> Result<Value, Value> {
-    let item = cx.args.first().unwrap_or_undefined();
+    let item = cx.args.first().unwrap_or_undefined().interned(cx.scope);
     let did_delete = receiver_t::<Set>(cx.scope, &cx.this, "Set.prototype.delete")?.delete(&item);
 
     Ok(Value::boolean(did_delete))
@@ -58,3 +61,56 @@ pub fn size(cx: CallContext) -> Result<Value, Value> {
         receiver_t::<Set>(cx.scope, &cx.this, "Set.prototype.size")?.size() as f64,
     ))
 }
+
+fn create_iterator(cx: CallContext, kind: SetIteratorKind, what: &'static str) -> Result<Value, Value> {
+    receiver_t::<Set>(cx.scope, &cx.this, what)?;
+    let iter = SetIterator::new(cx.scope, cx.this, kind);
+    Ok(Value::object(cx.scope.register(iter)))
+}
+
+pub fn values(cx: CallContext) -> Result<Value, Value> {
+    create_iterator(cx, SetIteratorKind::Values, "Set.prototype.values")
+}
+
+pub fn entries(cx: CallContext) -> Result<Value, Value> {
+    create_iterator(cx, SetIteratorKind::Entries, "Set.prototype.entries")
+}
+
+pub fn for_each(cx: CallContext) -> Result<Value, Value> {
+    let set = receiver_t::<Set>(cx.scope, &cx.this, "Set.prototype.forEach")?;
+    let callback = cx.args.first().unwrap_or_undefined();
+    let this_arg = cx.args.get(1).unwrap_or_undefined();
+
+    // Values added during iteration are visited too, so this walks the live table rather than a snapshot
+    let cursor = TableCursor::default();
+    while let Some(value) = set.next_value(&cursor) {
+        callback
+            .apply(This::bound(this_arg), [value, value, cx.this].into(), cx.scope)
+            .root_err(cx.scope)?;
+    }
+
+    Ok(Value::undefined())
+}
+
+pub fn iterator_next(cx: CallContext) -> Result<Value, Value> {
+    let iterator = receiver_t::<SetIterator>(cx.scope, &cx.this, "SetIterator.prototype.next")?;
+
+    let value = match iterator.next(cx.scope) {
+        Some(value) => Some(match iterator.kind() {
+            SetIteratorKind::Values => value,
+            SetIteratorKind::Entries => {
+                let entry = Array::from_vec(
+                    vec![
+                        PropertyValue::static_default(value),
+                        PropertyValue::static_default(value),
+                    ],
+                    cx.scope,
+                );
+                Value::object(cx.scope.register(entry))
+            }
+        }),
+        None => None,
+    };
+
+    create_iter_result(cx.scope, value)
+}
@@ -1,5 +1,4 @@
 use dash_middle::interner::sym;
-use dash_regex::Flags;
 
 use crate::localscope::LocalScope;
 use crate::throw;
@@ -9,11 +8,13 @@ use crate::value::function::args::CallArgs;
 use crate::value::function::native::CallContext;
 use crate::value::object::{Object, OrdObject, PropertyValue, This};
 use crate::value::ops::conversions::ValueConversion;
+use crate::value::primitive::Symbol;
+use crate::value::propertykey::ToPropertyKey;
 use crate::value::regex::RegExp;
-use crate::value::{Root, Typeof, Value, ValueContext};
+use crate::value::string::JsString;
+use crate::value::{Root, Typeof, Unpack, Value, ValueContext, ValueKind};
 use std::cmp;
 use std::fmt::Write;
-use std::ops::Range;
 
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
     let value = match cx.args.first() {
@@ -137,21 +138,13 @@ pub fn char_code_at(cx: CallContext) -> Result<Value, Value> {
 }
 
 pub fn concat(cx: CallContext) -> Result<Value, Value> {
-    let this = cx.this.to_js_string(cx.scope)?;
-
-    let concat = if cx.args.len() > 1 {
-        // avoid interning every concatenation
-        let mut concat = String::from(this.res(cx.scope));
-        for value in &cx.args {
-            concat += value.to_js_string(cx.scope)?.res(cx.scope);
-        }
-        concat
-    } else {
-        let other = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
-        String::from(this.res(cx.scope)) + other.res(cx.scope)
-    };
+    let mut concat = cx.this.to_js_string(cx.scope)?;
+    for value in &cx.args {
+        let value = value.to_js_string(cx.scope)?;
+        concat = concat.concat(value, cx.scope);
+    }
 
-    Ok(Value::string(cx.scope.intern(concat.as_ref()).into()))
+    Ok(Value::string(concat))
 }
 
 pub fn ends_with(cx: CallContext) -> Result<Value, Value> {
@@ -172,6 +165,28 @@ pub fn includes(cx: CallContext) -> Result<Value, Value> {
     Ok(Value::boolean(this.res(cx.scope).contains(other.res(cx.scope))))
 }
 
+#[cfg(feature = "intl")]
+pub fn locale_compare(cx: CallContext) -> Result<Value, Value> {
+    use crate::value::intl::collator::CollatorOptions;
+
+    let this = cx.this.to_js_string(cx.scope)?;
+    let other = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+    let locales = cx.args.get(1).unwrap_or_undefined();
+    let options = cx.args.get(2).unwrap_or_undefined();
+    let collator = CollatorOptions::new(cx.scope, locales, options)?;
+    let ordering = collator.compare(this.res(cx.scope), other.res(cx.scope));
+    Ok(Value::number(ordering as i8 as f64))
+}
+
+/// Without locale data, strings are compared by their code points
+#[cfg(not(feature = "intl"))]
+pub fn locale_compare(cx: CallContext) -> Result<Value, Value> {
+    let this = cx.this.to_js_string(cx.scope)?;
+    let other = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+    let ordering = this.res(cx.scope).cmp(other.res(cx.scope));
+    Ok(Value::number(ordering as i8 as f64))
+}
+
 pub fn index_of(cx: CallContext) -> Result<Value, Value> {
     let this = cx.this.to_js_string(cx.scope)?;
     let other = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
@@ -284,6 +299,201 @@ pub fn repeat(cx: CallContext) -> Result<Value, Value> {
     Ok(Value::string(cx.scope.intern(result).into()))
 }
 
+/// Calls the method stored under a well-known symbol like `@@replace` on the argument of a String method,
+/// or returns `None` if the argument does not have that method.
+fn invoke_symbol_method(
+    sc: &mut LocalScope<'_>,
+    target: Value,
+    symbol: Symbol,
+    args: CallArgs,
+) -> Result<Option<Value>, Value> {
+    if target.is_nullish() {
+        return Ok(None);
+    }
+
+    let method = target.get_property(symbol.to_key(sc), sc).root(sc)?;
+    if method.is_nullish() {
+        return Ok(None);
+    }
+
+    method.apply(This::bound(target), args, sc).root(sc).map(Some)
+}
+
+/// Creates a RegExp from the argument of `match`, `matchAll` or `search` and calls its `symbol` method
+fn invoke_on_new_regexp(
+    sc: &mut LocalScope<'_>,
+    pattern: Value,
+    flags: Value,
+    symbol: Symbol,
+    string: JsString,
+) -> Result<Value, Value> {
+    let regexp = Value::object(sc.statics.regexp_ctor)
+        .construct(This::default(), [pattern, flags].into(), sc)
+        .root(sc)?;
+    let method = regexp.get_property(symbol.to_key(sc), sc).root(sc)?;
+    method
+        .apply(This::bound(regexp), [Value::string(string)].into(), sc)
+        .root(sc)
+}
+
+/// Implements the IsRegExp abstract operation
+fn is_regexp(sc: &mut LocalScope<'_>, value: Value) -> Result<bool, Value> {
+    if !matches!(value.unpack(), ValueKind::Object(_)) {
+        return Ok(false);
+    }
+
+    let symbol_match = sc.statics.symbol_match;
+    let matcher = value.get_property(symbol_match.to_key(sc), sc).root(sc)?;
+    if !matches!(matcher.unpack(), ValueKind::Undefined(_)) {
+        return matcher.to_boolean(sc);
+    }
+
+    Ok(value.extract::<RegExp>(sc).is_some())
+}
+
+/// Throws if the argument of `matchAll` or `replaceAll` is a RegExp without the global flag
+fn require_global_regexp(sc: &mut LocalScope<'_>, value: Value, method: &str) -> Result<(), Value> {
+    if is_regexp(sc, value)? {
+        let flags = value.get_property(sym::flags.to_key(sc), sc).root(sc)?;
+        if flags.is_nullish() {
+            throw!(sc, TypeError, "RegExp flags cannot be null or undefined");
+        }
+
+        let flags = flags.to_js_string(sc)?;
+        if !flags.res(sc).contains('g') {
+            throw!(
+                sc,
+                TypeError,
+                "String.prototype.{} called with a non-global RegExp argument",
+                method
+            );
+        }
+    }
+    Ok(())
+}
+
+pub fn match_(cx: CallContext) -> Result<Value, Value> {
+    let regexp = cx.args.first().unwrap_or_undefined();
+    let symbol_match = cx.scope.statics.symbol_match;
+    if let Some(result) = invoke_symbol_method(cx.scope, regexp, symbol_match, [cx.this].into())? {
+        return Ok(result);
+    }
+
+    let string = cx.this.to_js_string(cx.scope)?;
+    invoke_on_new_regexp(cx.scope, regexp, Value::undefined(), symbol_match, string)
+}
+
+pub fn match_all(cx: CallContext) -> Result<Value, Value> {
+    let regexp = cx.args.first().unwrap_or_undefined();
+    require_global_regexp(cx.scope, regexp, "matchAll")?;
+
+    let symbol_match_all = cx.scope.statics.symbol_match_all;
+    if let Some(result) = invoke_symbol_method(cx.scope, regexp, symbol_match_all, [cx.this].into())? {
+        return Ok(result);
+    }
+
+    let string = cx.this.to_js_string(cx.scope)?;
+    let flags = Value::string(cx.scope.intern_char('g').into());
+    invoke_on_new_regexp(cx.scope, regexp, flags, symbol_match_all, string)
+}
+
+pub fn search(cx: CallContext) -> Result<Value, Value> {
+    let regexp = cx.args.first().unwrap_or_undefined();
+    let symbol_search = cx.scope.statics.symbol_search;
+    if let Some(result) = invoke_symbol_method(cx.scope, regexp, symbol_search, [cx.this].into())? {
+        return Ok(result);
+    }
+
+    let string = cx.this.to_js_string(cx.scope)?;
+    invoke_on_new_regexp(cx.scope, regexp, Value::undefined(), symbol_search, string)
+}
+
+/// Implements the GetSubstitution abstract operation, which expands the `$` patterns of a replacement string.
+///
+/// `captures` must be strings or undefined, and `named_captures` must be an object or undefined.
+pub fn get_substitution(
+    sc: &mut LocalScope<'_>,
+    matched: &str,
+    string: &str,
+    position: usize,
+    captures: &[Value],
+    named_captures: Value,
+    replacement: &str,
+) -> Result<String, Value> {
+    let mut result = String::with_capacity(replacement.len());
+    let mut rest = replacement;
+
+    while let Some(dollar) = rest.find('$') {
+        result.push_str(&rest[..dollar]);
+        rest = &rest[dollar..];
+        let bytes = rest.as_bytes();
+
+        match bytes.get(1) {
+            Some(b'$') => {
+                result.push('$');
+                rest = &rest[2..];
+            }
+            Some(b'&') => {
+                result.push_str(matched);
+                rest = &rest[2..];
+            }
+            Some(b'`') => {
+                result.push_str(string.get(..position).unwrap_or_default());
+                rest = &rest[2..];
+            }
+            Some(b'\'') => {
+                let tail = cmp::min(position + matched.len(), string.len());
+                result.push_str(string.get(tail..).unwrap_or_default());
+                rest = &rest[2..];
+            }
+            Some(&digit @ b'0'..=b'9') => {
+                let one_digit = usize::from(digit - b'0');
+                let valid = |index: usize| (1..=captures.len()).contains(&index);
+
+                // Two digit references are preferred if such a group exists
+                let (index, consumed) = match bytes.get(2) {
+                    Some(&second @ b'0'..=b'9') if valid(one_digit * 10 + usize::from(second - b'0')) => {
+                        (one_digit * 10 + usize::from(second - b'0'), 3)
+                    }
+                    _ => (one_digit, 2),
+                };
+
+                if valid(index) {
+                    let capture = captures[index - 1];
+                    if !matches!(capture.unpack(), ValueKind::Undefined(_)) {
+                        result.push_str(capture.to_js_string(sc)?.res(sc));
+                    }
+                    rest = &rest[consumed..];
+                } else {
+                    result.push('$');
+                    rest = &rest[1..];
+                }
+            }
+            Some(b'<') if !matches!(named_captures.unpack(), ValueKind::Undefined(_)) => match rest.find('>') {
+                Some(end) => {
+                    let name = sc.intern(&rest[2..end]);
+                    let capture = named_captures.get_property(name.to_key(sc), sc).root(sc)?;
+                    if !matches!(capture.unpack(), ValueKind::Undefined(_)) {
+                        result.push_str(capture.to_js_string(sc)?.res(sc));
+                    }
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    result.push_str("$<");
+                    rest = &rest[2..];
+                }
+            },
+            _ => {
+                result.push('$');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+
+    Ok(result)
+}
+
 enum ReplaceFunction {
     Replace,
     ReplaceAll,
@@ -291,88 +501,81 @@ enum ReplaceFunction {
 
 /// Shared logic for `String.prototype.replace` and `String.prototype.replaceAll`.
 fn replace_inner(cx: CallContext, mode: ReplaceFunction) -> Result<Value, Value> {
-    let string = cx.this.to_js_string(cx.scope)?.res(cx.scope).to_owned();
-
     let search = cx.args.first().unwrap_or_undefined();
     let replace = cx.args.get(1).unwrap_or_undefined();
 
-    // NOTE: usually `replace` only replaces the first instance (ReplaceMode::First), whereas `replace_all` replaces all instances (ReplaceMode::All).
-    // However, if the search parameter is a regex, then whether we should stop after the first match can be overridden if /g is set.
-    let mut stop_after_first = matches!(mode, ReplaceFunction::Replace);
-
-    let find_next_match: &dyn Fn(&str) -> Option<Range<u32>> = if let Some(regex) = search.extract::<RegExp>(cx.scope) {
-        let Some(inner_regex) = regex.inner() else {
-            throw!(cx.scope, TypeError, "invalid regex object")
-        };
-        let has_global_flag = inner_regex.regex.flags().contains(Flags::GLOBAL);
-
-        match mode {
-            ReplaceFunction::Replace => stop_after_first = !has_global_flag,
-            ReplaceFunction::ReplaceAll => {
-                if !has_global_flag {
-                    throw!(
-                        cx.scope,
-                        TypeError,
-                        "String.prototype.replaceAll called with a non-global RegExp argument"
-                    );
-                }
+    if let ReplaceFunction::ReplaceAll = mode {
+        require_global_regexp(cx.scope, search, "replaceAll")?;
+    }
 
-                stop_after_first = false;
-            }
-        }
+    // Regexes (and any other object with a @@replace method) do the replacing themselves
+    let symbol_replace = cx.scope.statics.symbol_replace;
+    if let Some(result) = invoke_symbol_method(cx.scope, search, symbol_replace, [cx.this, replace].into())? {
+        return Ok(result);
+    }
 
-        &move |rest: &str| match inner_regex.regex.eval(rest) {
-            Ok(res) => Some(res.full_match()),
-            Err(_) => None,
-        }
-    } else {
-        let search = search.to_js_string(cx.scope)?.res(cx.scope).to_owned();
-        &move |rest: &str| {
-            rest.find(&search).map(|start| {
-                let end = start + search.len();
-                Range {
-                    start: start as u32,
-                    end: end as u32,
-                }
-            })
-        }
+    let string = cx.this.to_js_string(cx.scope)?;
+    let string_ref = string.res(cx.scope).to_owned();
+    let search = search.to_js_string(cx.scope)?;
+    let search_ref = search.res(cx.scope).to_owned();
+    let replace_template = match replace.type_of(cx.scope) {
+        Typeof::Function => None,
+        _ => Some(replace.to_js_string(cx.scope)?.res(cx.scope).to_owned()),
     };
 
-    let mut rest = &*string;
-    let mut output = String::with_capacity(rest.len());
-
-    while let Some(Range { start, end }) = find_next_match(rest) {
-        output.push_str(&rest[..start as usize]);
+    let mut positions = Vec::new();
+    let mut position = string_ref.find(&search_ref);
+    while let Some(found) = position {
+        positions.push(found);
+        if let ReplaceFunction::Replace = mode {
+            break;
+        }
 
-        let replace_string = if let Typeof::Function = replace.type_of(cx.scope) {
-            replace
-                .apply(This::default(), CallArgs::empty(), cx.scope)
-                .root(cx.scope)?
-                .to_js_string(cx.scope)?
-                .res(cx.scope)
-        } else {
-            replace.to_js_string(cx.scope)?.res(cx.scope)
+        // An empty search string matches between every character
+        let advance_by = match search_ref.is_empty() {
+            true => string_ref[found..].chars().next().map_or(1, char::len_utf8),
+            false => search_ref.len(),
         };
+        position = string_ref
+            .get(found + advance_by..)
+            .and_then(|rest| rest.find(&search_ref))
+            .map(|index| found + advance_by + index);
+    }
 
-        output.push_str(replace_string);
-
-        if start == end {
-            // If we're replacing an empty string, advance the search position by one to avoid an infinite loop.
-            if let Some((prev, next)) = rest.split_at_checked(1) {
-                output.push_str(prev);
-                rest = next;
-            } else {
-                break;
+    let mut output = String::with_capacity(string_ref.len());
+    let mut end_of_last_match = 0;
+
+    for position in positions {
+        output.push_str(&string_ref[end_of_last_match..position]);
+
+        let replacement = match &replace_template {
+            Some(template) => get_substitution(
+                cx.scope,
+                &search_ref,
+                &string_ref,
+                position,
+                &[],
+                Value::undefined(),
+                template,
+            )?,
+            None => {
+                let args = [
+                    Value::string(search),
+                    Value::number(position as f64),
+                    Value::string(string),
+                ];
+                let replacement = replace
+                    .apply(This::default(), args.into(), cx.scope)
+                    .root(cx.scope)?
+                    .to_js_string(cx.scope)?;
+                replacement.res(cx.scope).to_owned()
             }
-        } else {
-            rest = &rest[end as usize..];
-        }
+        };
+        output.push_str(&replacement);
 
-        if stop_after_first {
-            break;
-        }
+        end_of_last_match = position + search_ref.len();
     }
-    output.push_str(rest);
+    output.push_str(&string_ref[end_of_last_match..]);
 
     Ok(Value::string(cx.scope.intern(output).into()))
 }
@@ -386,25 +589,41 @@ pub fn replace_all(cx: CallContext) -> Result<Value, Value> {
 }
 
 pub fn split(cx: CallContext) -> Result<Value, Value> {
-    let string = cx.this.to_js_string(cx.scope)?.res(cx.scope).to_owned();
-    let separator = cx
-        .args
-        .first()
-        .unwrap_or_undefined()
-        .to_js_string(cx.scope)?
-        .res(cx.scope)
-        .to_owned();
-
-    let result = if separator.is_empty() {
-        string
-            .chars()
-            .map(|c| PropertyValue::static_default(Value::string(cx.scope.intern_char(c).into())))
-            .collect()
+    let separator = cx.args.first().unwrap_or_undefined();
+    let limit = cx.args.get(1).unwrap_or_undefined();
+
+    let symbol_split = cx.scope.statics.symbol_split;
+    if let Some(result) = invoke_symbol_method(cx.scope, separator, symbol_split, [cx.this, limit].into())? {
+        return Ok(result);
+    }
+
+    let string = cx.this.to_js_string(cx.scope)?;
+    let limit = match limit.unpack() {
+        ValueKind::Undefined(_) => u32::MAX,
+        _ => limit.to_int32(cx.scope)? as u32,
+    } as usize;
+
+    let result = if limit == 0 {
+        Vec::new()
+    } else if let ValueKind::Undefined(_) = separator.unpack() {
+        vec![PropertyValue::static_default(Value::string(string))]
     } else {
-        string
-            .split(&separator)
-            .map(|s| PropertyValue::static_default(Value::string(cx.scope.intern(s).into())))
-            .collect()
+        let string = string.res(cx.scope).to_owned();
+        let separator = separator.to_js_string(cx.scope)?.res(cx.scope).to_owned();
+
+        if separator.is_empty() {
+            string
+                .chars()
+                .take(limit)
+                .map(|c| PropertyValue::static_default(Value::string(cx.scope.intern_char(c).into())))
+                .collect()
+        } else {
+            string
+                .split(&separator)
+                .take(limit)
+                .map(|s| PropertyValue::static_default(Value::string(cx.scope.intern(s).into())))
+                .collect()
+        }
     };
 
     let array = Array::from_vec(result, cx.scope);
@@ -447,6 +666,27 @@ pub fn from_char_code(cx: CallContext) -> Result<Value, Value> {
     Ok(Value::string(cx.scope.intern_char(s).into()))
 }
 
+pub fn raw(cx: CallContext) -> Result<Value, Value> {
+    let strings = Value::object(cx.args.first().unwrap_or_undefined().to_object(cx.scope)?);
+    let raw = strings.get_property(sym::raw.to_key(cx.scope), cx.scope).root(cx.scope)?;
+    let raw = Value::object(raw.to_object(cx.scope)?);
+    let length = raw.length_of_array_like(cx.scope)?;
+
+    let mut result = String::new();
+    for i in 0..length {
+        let segment = raw.get_property(i.to_key(cx.scope), cx.scope).root(cx.scope)?;
+        result.push_str(segment.to_js_string(cx.scope)?.res(cx.scope));
+
+        if i + 1 < length
+            && let Some(substitution) = cx.args.get(i + 1)
+        {
+            result.push_str(substitution.to_js_string(cx.scope)?.res(cx.scope));
+        }
+    }
+
+    Ok(Value::string(cx.scope.intern(result).into()))
+}
+
 pub fn substr(cx: CallContext) -> Result<Value, Value> {
     let string = cx.this.to_js_string(cx.scope)?;
     let (start, end) = {
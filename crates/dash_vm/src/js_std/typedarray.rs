@@ -1,44 +1,154 @@
-use std::cell::Cell;
+use std::cmp::{self, Ordering};
 use std::convert::Infallible;
 use std::ops::ControlFlow;
 
-use crate::js_std::array::for_each_js_iterator_element;
+use crate::gc::ObjectId;
+use crate::js_std::array::{apply_array_from_mapper, for_each_js_iterator_element, sort_compare_fn, sort_values_by};
 use crate::js_std::receiver_t;
+use crate::localscope::LocalScope;
 use crate::throw;
+use crate::value::array::{ArrayIterator, ArrayIteratorKind};
 use crate::value::arraybuffer::ArrayBuffer;
+use crate::value::bigint::to_bigint;
 use crate::value::function::args::CallArgs;
 use crate::value::function::native::CallContext;
-use crate::value::object::{OrdObject, This};
+use crate::value::object::{Object, OrdObject, This};
 use crate::value::ops::conversions::ValueConversion;
+use crate::value::ops::equality::{same_value_zero, strict_eq};
 use crate::value::propertykey::ToPropertyKey;
 use crate::value::typedarray::{TypedArray, TypedArrayKind};
-use crate::value::{Root, Unpack, Value, ValueKind};
+use crate::value::{Root, Typeof, Unpack, Value, ValueContext, ValueKind};
+use dash_middle::interner::sym;
 
-fn typedarray_constructor(cx: CallContext, kind: TypedArrayKind) -> Result<Value, Value> {
-    let Some(&arg) = cx.args.first() else {
-        throw!(cx.scope, TypeError, "Missing argument")
+/// Allocates a new, zeroed typed array of the given kind with the prototype of that kind
+fn allocate(sc: &mut LocalScope<'_>, kind: TypedArrayKind, length: usize) -> Result<ObjectId, Value> {
+    let prototype = kind.prototype(sc);
+    allocate_with_obj(sc, kind, length, OrdObject::with_prototype(prototype))
+}
+
+/// Allocates a new, zeroed typed array of the given kind, throwing a RangeError if its buffer cannot be allocated
+fn allocate_with_obj(
+    sc: &mut LocalScope<'_>,
+    kind: TypedArrayKind,
+    length: usize,
+    obj: OrdObject,
+) -> Result<ObjectId, Value> {
+    let buffer_obj = OrdObject::with_prototype(sc.statics.arraybuffer_prototype);
+    let Some(buffer) = length
+        .checked_mul(kind.bytes_per_element())
+        .and_then(|byte_length| ArrayBuffer::with_capacity(byte_length, buffer_obj))
+    else {
+        throw!(sc, RangeError, "Invalid typed array length: {}", length)
     };
+    let buffer = sc.register(buffer);
+    Ok(sc.register(TypedArray::with_obj(buffer, kind, obj)))
+}
 
+/// Creates a typed array of the given kind that holds the given values
+fn from_values(
+    sc: &mut LocalScope<'_>,
+    kind: TypedArrayKind,
+    values: Vec<Value>,
+    obj: OrdObject,
+) -> Result<ObjectId, Value> {
+    let array = allocate_with_obj(sc, kind, values.len(), obj)?;
+    let typed_array = array.extract::<TypedArray>(sc).unwrap();
+    for (index, value) in values.into_iter().enumerate() {
+        typed_array.set(sc, index, value)?;
+    }
+    Ok(array)
+}
+
+fn typedarray_constructor(cx: CallContext, kind: TypedArrayKind) -> Result<Value, Value> {
     let Some(new_target) = cx.new_target else {
         throw!(cx.scope, TypeError, "TypedArray constructor requires new")
     };
     let instance = OrdObject::instance_for_new_target(new_target, cx.scope)?;
+    let arg = cx.args.first().unwrap_or_undefined();
+    let bytes_per_element = kind.bytes_per_element();
 
     if let ValueKind::Object(obj) = arg.unpack() {
-        if let Some(this) = obj.extract::<ArrayBuffer>(cx.scope) {
-            if this.len() % kind.bytes_per_element() != 0 {
+        if let Some(buffer) = obj.extract::<ArrayBuffer>(cx.scope) {
+            let byte_offset = cx.args.get(1).unwrap_or_undefined().to_index(cx.scope)?;
+            if byte_offset % bytes_per_element != 0 {
                 throw!(
                     cx.scope,
                     RangeError,
-                    "Length of array buffer must be a multiple of {}",
-                    kind.bytes_per_element()
+                    "Start offset of typed array should be a multiple of {}",
+                    bytes_per_element
                 );
             }
 
-            let array = TypedArray::with_obj(obj, kind, instance);
+            let length = cx.args.get(2).copied().and_then(Value::into_option);
+            let length = length.map(|length| length.to_index(cx.scope)).transpose()?;
+            if buffer.is_detached() {
+                throw!(
+                    cx.scope,
+                    TypeError,
+                    "Cannot construct a typed array from a detached ArrayBuffer"
+                );
+            }
+
+            let buffer_len = buffer.len();
+            let length = match length {
+                Some(length) => {
+                    if byte_offset + length * bytes_per_element > buffer_len {
+                        throw!(cx.scope, RangeError, "Invalid typed array length: {}", length);
+                    }
+                    Some(length)
+                }
+                None if buffer.is_resizable() => {
+                    if byte_offset > buffer_len {
+                        throw!(
+                            cx.scope,
+                            RangeError,
+                            "Start offset {} is outside the bounds of the buffer",
+                            byte_offset
+                        );
+                    }
+                    None
+                }
+                None => {
+                    if buffer_len % bytes_per_element != 0 {
+                        throw!(
+                            cx.scope,
+                            RangeError,
+                            "Byte length of typed array should be a multiple of {}",
+                            bytes_per_element
+                        );
+                    }
+                    if byte_offset > buffer_len {
+                        throw!(
+                            cx.scope,
+                            RangeError,
+                            "Start offset {} is outside the bounds of the buffer",
+                            byte_offset
+                        );
+                    }
+                    Some((buffer_len - byte_offset) / bytes_per_element)
+                }
+            };
+
+            let array = TypedArray::with_view(obj, kind, byte_offset, length, instance);
             return Ok(cx.scope.register(array).into());
         }
 
+        if let Some(source) = obj.extract::<TypedArray>(cx.scope) {
+            if source.kind().is_bigint() != kind.is_bigint() {
+                throw!(
+                    cx.scope,
+                    TypeError,
+                    "Content type of source and target typed arrays must match"
+                );
+            }
+
+            let values = (0..source.length(cx.scope))
+                .map(|index| source.get(cx.scope, index).unwrap_or_undefined())
+                .collect();
+            return Ok(from_values(cx.scope, kind, values, instance)?.into());
+        }
+
+        let mut values = Vec::new();
         if let Some(iterator) = obj
             .get_property(cx.scope.statics.symbol_iterator.to_key(cx.scope), cx.scope)
             .root(cx.scope)?
@@ -47,38 +157,97 @@ fn typedarray_constructor(cx: CallContext, kind: TypedArrayKind) -> Result<Value
             let iterator = iterator
                 .apply(This::bound(arg), CallArgs::empty(), cx.scope)
                 .root(cx.scope)?;
-            let mut values = Vec::new();
-
-            let ControlFlow::Continue(()) = for_each_js_iterator_element(cx.scope, iterator, |scope, value| {
-                use TypedArrayKind::*;
-
-                let value = value.to_number(scope)?;
 
-                match kind {
-                    Int8Array | Uint8Array => values.push(Cell::new(value as u8)),
-                    Uint8ClampedArray => values.push(Cell::new(value.clamp(0.0, u8::MAX as f64) as u8)),
-                    Int16Array | Uint16Array => values.extend_from_slice(&(value as u16).to_ne_bytes().map(Cell::new)),
-                    Int32Array | Uint32Array => values.extend_from_slice(&(value as u32).to_ne_bytes().map(Cell::new)),
-                    Float32Array => values.extend_from_slice(&(value as f32).to_ne_bytes().map(Cell::new)),
-                    Float64Array => values.extend_from_slice(&value.to_ne_bytes().map(Cell::new)),
-                }
+            let ControlFlow::Continue(()) = for_each_js_iterator_element(cx.scope, iterator, |_, value| {
+                values.push(value);
                 Ok(ControlFlow::<Infallible, _>::Continue(()))
             })?;
+        } else {
+            for index in 0..arg.length_of_array_like(cx.scope)? {
+                values.push(arg.get_property(index.to_key(cx.scope), cx.scope).root(cx.scope)?);
+            }
+        }
 
-            let buffer = ArrayBuffer::from_storage(cx.scope, values);
-            let buffer = cx.scope.register(buffer);
+        return Ok(from_values(cx.scope, kind, values, instance)?.into());
+    }
 
-            let array = TypedArray::with_obj(buffer, kind, instance);
-            return Ok(cx.scope.register(array).into());
+    let length = arg.to_index(cx.scope)?;
+    Ok(allocate_with_obj(cx.scope, kind, length, instance)?.into())
+}
+
+pub fn constructor(cx: CallContext) -> Result<Value, Value> {
+    throw!(
+        cx.scope,
+        TypeError,
+        "Abstract class TypedArray not directly constructable"
+    )
+}
+
+/// Implements the TypedArrayCreateFromConstructor abstract operation with a length argument
+fn create_from_constructor(sc: &mut LocalScope<'_>, constructor: Value, length: usize) -> Result<Value, Value> {
+    let array = constructor
+        .construct(This::default(), [Value::number(length as f64)].into(), sc)
+        .root(sc)?;
+    let new_length = validate(sc, &array, "TypedArray constructor")?.length(sc);
+    if new_length < length {
+        throw!(
+            sc,
+            TypeError,
+            "Derived TypedArray constructor created an array which was too small"
+        );
+    }
+    Ok(array)
+}
+
+pub fn from(cx: CallContext) -> Result<Value, Value> {
+    let source = cx.args.first().unwrap_or_undefined();
+    let mapper = match cx.args.get(1).copied().and_then(Value::into_option) {
+        None => None,
+        Some(mapper) if matches!(mapper.type_of(cx.scope), Typeof::Function) => {
+            Some((mapper, cx.args.get(2).unwrap_or_undefined()))
+        }
+        Some(_) => throw!(cx.scope, TypeError, "TypedArray.from mapper is not a function"),
+    };
+
+    let mut values = Vec::new();
+    if let Some(iterator) = source
+        .get_property(cx.scope.statics.symbol_iterator.to_key(cx.scope), cx.scope)
+        .root(cx.scope)?
+        .into_option()
+    {
+        let iterator = iterator
+            .apply(This::bound(source), CallArgs::empty(), cx.scope)
+            .root(cx.scope)?;
+
+        let ControlFlow::Continue(()) = for_each_js_iterator_element(cx.scope, iterator, |_, value| {
+            values.push(value);
+            Ok(ControlFlow::<Infallible, _>::Continue(()))
+        })?;
+    } else {
+        let source = Value::object(source.to_object(cx.scope)?);
+        for index in 0..source.length_of_array_like(cx.scope)? {
+            values.push(source.get_property(index.to_key(cx.scope), cx.scope).root(cx.scope)?);
         }
     }
 
-    let size = arg.to_number(cx.scope)? as usize;
-    let buffer = ArrayBuffer::with_capacity(size, instance);
-    let buffer = cx.scope.register(buffer);
+    let array = create_from_constructor(cx.scope, cx.this, values.len())?;
+    let target = receiver_t::<TypedArray>(cx.scope, &array, "TypedArray.from")?;
+    for (index, value) in values.into_iter().enumerate() {
+        let value = apply_array_from_mapper(cx.scope, mapper, value, index)?;
+        target.set(cx.scope, index, value)?;
+    }
 
-    let array = TypedArray::new(cx.scope, buffer, kind);
-    Ok(cx.scope.register(array).into())
+    Ok(array)
+}
+
+pub fn of(cx: CallContext) -> Result<Value, Value> {
+    let array = create_from_constructor(cx.scope, cx.this, cx.args.len())?;
+    let target = receiver_t::<TypedArray>(cx.scope, &array, "TypedArray.of")?;
+    for (index, &value) in cx.args.iter().enumerate() {
+        target.set(cx.scope, index, value)?;
+    }
+
+    Ok(array)
 }
 
 macro_rules! typedarray {
@@ -93,41 +262,660 @@ macro_rules! typedarray {
     };
 }
 
-pub fn fill(cx: CallContext) -> Result<Value, Value> {
-    let this = receiver_t::<TypedArray>(cx.scope, &cx.this, "TypedArray.prototype.fill")?;
-    let value = match cx.args.first() {
-        Some(value) => value.to_number(cx.scope)?,
-        None => throw!(cx.scope, TypeError, "Missing fill value"), // TODO: shouldn't throw
+/// Implements the ValidateTypedArray abstract operation, which requires the typed array to be in bounds of its buffer
+fn validate<'a>(sc: &mut LocalScope<'_>, this: &'a Value, what: &'static str) -> Result<&'a TypedArray, Value> {
+    let array = receiver_t::<TypedArray>(sc, this, what)?;
+    if array.is_out_of_bounds(sc) {
+        throw!(sc, TypeError, "{}: typed array is detached or out of bounds", what);
+    }
+    Ok(array)
+}
+
+/// Converts a relative index argument to an absolute index in the range `0..=len`
+fn relative_index(sc: &mut LocalScope<'_>, value: Option<&Value>, len: usize, default: usize) -> Result<usize, Value> {
+    let Some(value) = value.copied().and_then(Value::into_option) else {
+        return Ok(default);
     };
-    let buf = this.arraybuffer(cx.scope).storage();
-
-    macro_rules! fill_typed_array {
-        ($ty:ty) => {{
-            let value = <$ty>::to_ne_bytes(value as $ty);
-            for chunk in buf.chunks_exact(value.len()) {
-                // For Uint8Array, it only compiles to a memset if we use an indexed for loop
-                // It seems like zipped iterators are not smart enough
-                for index in 0..value.len() {
-                    chunk[index].set(value[index]);
+
+    let index = value.to_integer_or_infinity(sc)?;
+    Ok(if index < 0.0 {
+        (len as f64 + index).max(0.0) as usize
+    } else {
+        index.min(len as f64) as usize
+    })
+}
+
+/// Calls the callback argument for each element of the typed array, with the element, index and typed array as arguments,
+/// and passes the result of each call to `f`
+fn for_each_callback_result<B>(
+    cx: &mut CallContext,
+    name: &'static str,
+    indices: impl Iterator<Item = usize>,
+    mut f: impl FnMut(&mut LocalScope<'_>, Value, usize, Value) -> ControlFlow<B>,
+) -> Result<ControlFlow<B>, Value> {
+    let this = receiver_t::<TypedArray>(cx.scope, &cx.this, name)?;
+    let callback = cx.args.first().unwrap_or_undefined();
+    let callback_this = cx.args.get(1).unwrap_or_undefined();
+
+    for index in indices {
+        let value = this.get(cx.scope, index).unwrap_or_undefined();
+        let result = callback
+            .apply(
+                This::bound(callback_this),
+                [value, Value::number(index as f64), cx.this].into(),
+                cx.scope,
+            )
+            .root(cx.scope)?;
+
+        if let ControlFlow::Break(b) = f(cx.scope, value, index, result) {
+            return Ok(ControlFlow::Break(b));
+        }
+    }
+
+    Ok(ControlFlow::Continue(()))
+}
+
+pub fn at(cx: CallContext) -> Result<Value, Value> {
+    let this = validate(cx.scope, &cx.this, "TypedArray.prototype.at")?;
+    let len = this.length(cx.scope) as f64;
+    let mut index = cx.args.first().unwrap_or_undefined().to_integer_or_infinity(cx.scope)?;
+    if index < 0.0 {
+        index += len;
+    }
+
+    if index < 0.0 || index >= len {
+        return Ok(Value::undefined());
+    }
+
+    Ok(this.get(cx.scope, index as usize).unwrap_or_undefined())
+}
+
+pub fn copy_within(cx: CallContext) -> Result<Value, Value> {
+    let this = validate(cx.scope, &cx.this, "TypedArray.prototype.copyWithin")?;
+    let len = this.length(cx.scope);
+    let to = relative_index(cx.scope, cx.args.first(), len, 0)?;
+    let from = relative_index(cx.scope, cx.args.get(1), len, 0)?;
+    let end = relative_index(cx.scope, cx.args.get(2), len, len)?;
+    let count = cmp::min(end.saturating_sub(from), len - to);
+
+    if count > 0 {
+        let this = validate(cx.scope, &cx.this, "TypedArray.prototype.copyWithin")?;
+        let size = this.kind().bytes_per_element();
+        let storage = this.storage(cx.scope);
+        // The buffer may have shrunk while converting the arguments, so only the remaining bytes are copied
+        let len = storage.len() / size;
+        let count = cmp::min(count, cmp::min(len.saturating_sub(from), len.saturating_sub(to))) * size;
+        let (from, to) = (from * size, to * size);
+
+        // Copy back to front if the target comes after the source, so that bytes are read before they are overwritten
+        if from < to {
+            for offset in (0..count).rev() {
+                storage[to + offset].set(storage[from + offset].get());
+            }
+        } else {
+            for offset in 0..count {
+                storage[to + offset].set(storage[from + offset].get());
+            }
+        }
+    }
+
+    Ok(cx.this)
+}
+
+pub fn entries(cx: CallContext) -> Result<Value, Value> {
+    validate(cx.scope, &cx.this, "TypedArray.prototype.entries")?;
+    let iter = ArrayIterator::with_kind(cx.scope, cx.this, ArrayIteratorKind::Entries)?;
+    Ok(cx.scope.register(iter).into())
+}
+
+pub fn every(mut cx: CallContext) -> Result<Value, Value> {
+    let len = validate(cx.scope, &cx.this, "TypedArray.prototype.every")?.length(cx.scope);
+    let result = for_each_callback_result(&mut cx, "TypedArray.prototype.every", 0..len, |sc, _, _, result| {
+        if result.is_truthy(sc) {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    })?;
+    Ok(Value::boolean(result.is_continue()))
+}
+
+pub fn some(mut cx: CallContext) -> Result<Value, Value> {
+    let len = validate(cx.scope, &cx.this, "TypedArray.prototype.some")?.length(cx.scope);
+    let result = for_each_callback_result(&mut cx, "TypedArray.prototype.some", 0..len, |sc, _, _, result| {
+        if result.is_truthy(sc) {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })?;
+    Ok(Value::boolean(result.is_break()))
+}
+
+pub fn find(mut cx: CallContext) -> Result<Value, Value> {
+    let len = validate(cx.scope, &cx.this, "TypedArray.prototype.find")?.length(cx.scope);
+    let result = for_each_callback_result(&mut cx, "TypedArray.prototype.find", 0..len, |sc, value, _, result| {
+        if result.is_truthy(sc) {
+            ControlFlow::Break(value)
+        } else {
+            ControlFlow::Continue(())
+        }
+    })?;
+    Ok(result.break_value().unwrap_or_undefined())
+}
+
+pub fn find_index(mut cx: CallContext) -> Result<Value, Value> {
+    let len = validate(cx.scope, &cx.this, "TypedArray.prototype.findIndex")?.length(cx.scope);
+    let result = for_each_callback_result(
+        &mut cx,
+        "TypedArray.prototype.findIndex",
+        0..len,
+        |sc, _, index, result| {
+            if result.is_truthy(sc) {
+                ControlFlow::Break(index)
+            } else {
+                ControlFlow::Continue(())
+            }
+        },
+    )?;
+    Ok(Value::number(result.break_value().map_or(-1.0, |index| index as f64)))
+}
+
+pub fn find_last(mut cx: CallContext) -> Result<Value, Value> {
+    let len = validate(cx.scope, &cx.this, "TypedArray.prototype.findLast")?.length(cx.scope);
+    let result = for_each_callback_result(
+        &mut cx,
+        "TypedArray.prototype.findLast",
+        (0..len).rev(),
+        |sc, value, _, result| {
+            if result.is_truthy(sc) {
+                ControlFlow::Break(value)
+            } else {
+                ControlFlow::Continue(())
+            }
+        },
+    )?;
+    Ok(result.break_value().unwrap_or_undefined())
+}
+
+pub fn find_last_index(mut cx: CallContext) -> Result<Value, Value> {
+    let len = validate(cx.scope, &cx.this, "TypedArray.prototype.findLastIndex")?.length(cx.scope);
+    let result = for_each_callback_result(
+        &mut cx,
+        "TypedArray.prototype.findLastIndex",
+        (0..len).rev(),
+        |sc, _, index, result| {
+            if result.is_truthy(sc) {
+                ControlFlow::Break(index)
+            } else {
+                ControlFlow::Continue(())
+            }
+        },
+    )?;
+    Ok(Value::number(result.break_value().map_or(-1.0, |index| index as f64)))
+}
+
+pub fn for_each(mut cx: CallContext) -> Result<Value, Value> {
+    let len = validate(cx.scope, &cx.this, "TypedArray.prototype.forEach")?.length(cx.scope);
+    let ControlFlow::Continue(()) =
+        for_each_callback_result::<Infallible>(&mut cx, "TypedArray.prototype.forEach", 0..len, |_, _, _, _| {
+            ControlFlow::Continue(())
+        })?;
+    Ok(Value::undefined())
+}
+
+pub fn filter(mut cx: CallContext) -> Result<Value, Value> {
+    let this = validate(cx.scope, &cx.this, "TypedArray.prototype.filter")?;
+    let (kind, len) = (this.kind(), this.length(cx.scope));
+    let mut values = Vec::new();
+    let ControlFlow::Continue(()) = for_each_callback_result::<Infallible>(
+        &mut cx,
+        "TypedArray.prototype.filter",
+        0..len,
+        |sc, value, _, result| {
+            if result.is_truthy(sc) {
+                values.push(value);
+            }
+            ControlFlow::Continue(())
+        },
+    )?;
+
+    let prototype = kind.prototype(cx.scope);
+    Ok(from_values(cx.scope, kind, values, OrdObject::with_prototype(prototype))?.into())
+}
+
+pub fn map(mut cx: CallContext) -> Result<Value, Value> {
+    let this = validate(cx.scope, &cx.this, "TypedArray.prototype.map")?;
+    let (kind, len) = (this.kind(), this.length(cx.scope));
+    let array = allocate(cx.scope, kind, len)?;
+    let mut error = None;
+    let ControlFlow::Continue(()) =
+        for_each_callback_result(&mut cx, "TypedArray.prototype.map", 0..len, |sc, _, index, result| {
+            let target = array.extract::<TypedArray>(sc).unwrap();
+            match target.set(sc, index, result) {
+                Ok(()) => ControlFlow::Continue(()),
+                Err(err) => {
+                    error = Some(err);
+                    ControlFlow::Break(())
                 }
             }
-        }};
+        })?
+    else {
+        return Err(error.unwrap());
+    };
+
+    Ok(array.into())
+}
+
+fn reduce_inner(
+    cx: CallContext,
+    name: &'static str,
+    indices: impl DoubleEndedIterator<Item = usize>,
+) -> Result<Value, Value> {
+    let this = receiver_t::<TypedArray>(cx.scope, &cx.this, name)?;
+    let callback = cx.args.first().unwrap_or_undefined();
+    let mut indices = indices.peekable();
+
+    let mut accumulator = match cx.args.get(1) {
+        Some(&initial) => initial,
+        None => match indices.next() {
+            Some(index) => this.get(cx.scope, index).unwrap_or_undefined(),
+            None => throw!(cx.scope, TypeError, "Reduce of empty array with no initial value"),
+        },
+    };
+
+    for index in indices {
+        let value = this.get(cx.scope, index).unwrap_or_undefined();
+        accumulator = callback
+            .apply(
+                This::default(),
+                [accumulator, value, Value::number(index as f64), cx.this].into(),
+                cx.scope,
+            )
+            .root(cx.scope)?;
+    }
+
+    Ok(accumulator)
+}
+
+pub fn reduce(cx: CallContext) -> Result<Value, Value> {
+    let len = validate(cx.scope, &cx.this, "TypedArray.prototype.reduce")?.length(cx.scope);
+    reduce_inner(cx, "TypedArray.prototype.reduce", 0..len)
+}
+
+pub fn reduce_right(cx: CallContext) -> Result<Value, Value> {
+    let len = validate(cx.scope, &cx.this, "TypedArray.prototype.reduceRight")?.length(cx.scope);
+    reduce_inner(cx, "TypedArray.prototype.reduceRight", (0..len).rev())
+}
+
+pub fn fill(cx: CallContext) -> Result<Value, Value> {
+    let this = validate(cx.scope, &cx.this, "TypedArray.prototype.fill")?;
+    let kind = this.kind();
+    let len = this.length(cx.scope);
+    let value = kind.encode(
+        cx.scope,
+        cx.args.first().unwrap_or_undefined(),
+        cfg!(target_endian = "little"),
+    )?;
+    let start = relative_index(cx.scope, cx.args.get(1), len, 0)?;
+    let end = relative_index(cx.scope, cx.args.get(2), len, len)?;
+
+    let size = kind.bytes_per_element();
+    let value = &value[..size];
+    let storage = this.storage(cx.scope);
+    if let Some(range) = storage.get(start * size..cmp::max(start, end) * size) {
+        for chunk in range.chunks_exact(size) {
+            // For Uint8Array, it only compiles to a memset if we use an indexed for loop
+            // It seems like zipped iterators are not smart enough
+            for index in 0..size {
+                chunk[index].set(value[index]);
+            }
+        }
+    }
+
+    Ok(cx.this)
+}
+
+pub fn includes(cx: CallContext) -> Result<Value, Value> {
+    let this = validate(cx.scope, &cx.this, "TypedArray.prototype.includes")?;
+    let len = this.length(cx.scope);
+    let search_element = cx.args.first().unwrap_or_undefined();
+    let start = relative_index(cx.scope, cx.args.get(1), len, 0)?;
+
+    for index in start..len {
+        let value = this.get(cx.scope, index).unwrap_or_undefined();
+        if same_value_zero(value, search_element, cx.scope) {
+            return Ok(Value::boolean(true));
+        }
+    }
+
+    Ok(Value::boolean(false))
+}
+
+pub fn index_of(cx: CallContext) -> Result<Value, Value> {
+    let this = validate(cx.scope, &cx.this, "TypedArray.prototype.indexOf")?;
+    let len = this.length(cx.scope);
+    let search_element = cx.args.first().unwrap_or_undefined();
+    let start = relative_index(cx.scope, cx.args.get(1), len, 0)?;
+
+    for index in start..len {
+        let value = this.get(cx.scope, index).unwrap_or_undefined();
+        if strict_eq(value, search_element, cx.scope) {
+            return Ok(Value::number(index as f64));
+        }
     }
 
-    match this.kind() {
-        TypedArrayKind::Uint32Array => fill_typed_array!(u32),
-        TypedArrayKind::Int8Array => fill_typed_array!(i8),
-        TypedArrayKind::Uint8Array => fill_typed_array!(u8),
-        TypedArrayKind::Uint8ClampedArray => fill_typed_array!(u8),
-        TypedArrayKind::Int16Array => fill_typed_array!(i16),
-        TypedArrayKind::Uint16Array => fill_typed_array!(u16),
-        TypedArrayKind::Int32Array => fill_typed_array!(i32),
-        TypedArrayKind::Float32Array => fill_typed_array!(f32),
-        TypedArrayKind::Float64Array => fill_typed_array!(f64),
+    Ok(Value::number(-1.0))
+}
+
+pub fn keys(cx: CallContext) -> Result<Value, Value> {
+    validate(cx.scope, &cx.this, "TypedArray.prototype.keys")?;
+    let iter = ArrayIterator::with_kind(cx.scope, cx.this, ArrayIteratorKind::Keys)?;
+    Ok(cx.scope.register(iter).into())
+}
+
+pub fn last_index_of(cx: CallContext) -> Result<Value, Value> {
+    let this = validate(cx.scope, &cx.this, "TypedArray.prototype.lastIndexOf")?;
+    let len = this.length(cx.scope);
+    let search_element = cx.args.first().unwrap_or_undefined();
+    let end = match cx.args.get(1) {
+        Some(from_index) => {
+            let from_index = from_index.to_integer_or_infinity(cx.scope)?;
+            if from_index < 0.0 {
+                len as f64 + from_index + 1.0
+            } else {
+                (from_index + 1.0).min(len as f64)
+            }
+        }
+        None => len as f64,
+    };
+
+    for index in (0..end.max(0.0) as usize).rev() {
+        let value = this.get(cx.scope, index).unwrap_or_undefined();
+        if strict_eq(value, search_element, cx.scope) {
+            return Ok(Value::number(index as f64));
+        }
     }
+
+    Ok(Value::number(-1.0))
+}
+
+pub fn join(cx: CallContext) -> Result<Value, Value> {
+    let this = validate(cx.scope, &cx.this, "TypedArray.prototype.join")?;
+    let separator = match cx.args.first().copied().and_then(Value::into_option) {
+        Some(separator) => separator.to_js_string(cx.scope)?,
+        None => sym::comma.into(),
+    };
+
+    let mut result = String::new();
+    for index in 0..this.length(cx.scope) {
+        if index > 0 {
+            result.push_str(separator.res(cx.scope));
+        }
+
+        if let Some(value) = this.get(cx.scope, index) {
+            let value = value.to_js_string(cx.scope)?;
+            result.push_str(value.res(cx.scope));
+        }
+    }
+
+    Ok(Value::string(cx.scope.intern(result).into()))
+}
+
+pub fn reverse(cx: CallContext) -> Result<Value, Value> {
+    let this = validate(cx.scope, &cx.this, "TypedArray.prototype.reverse")?;
+    let size = this.kind().bytes_per_element();
+    let storage = this.storage(cx.scope);
+    let len = storage.len() / size;
+
+    for index in 0..len / 2 {
+        let (left, right) = (index * size, (len - index - 1) * size);
+        for offset in 0..size {
+            storage[left + offset].swap(&storage[right + offset]);
+        }
+    }
+
+    Ok(cx.this)
+}
+
+pub fn set(cx: CallContext) -> Result<Value, Value> {
+    let this = validate(cx.scope, &cx.this, "TypedArray.prototype.set")?;
+    let source = cx.args.first().unwrap_or_undefined();
+    let offset = cx.args.get(1).unwrap_or_undefined().to_integer_or_infinity(cx.scope)?;
+    if offset < 0.0 {
+        throw!(cx.scope, RangeError, "offset is out of bounds");
+    }
+    let len = this.length(cx.scope);
+
+    // Values are read before any of them are written, since the source may share the buffer with the target
+    let values = match source.unpack() {
+        ValueKind::Object(obj) if let Some(source) = obj.extract::<TypedArray>(cx.scope) => {
+            if source.kind().is_bigint() != this.kind().is_bigint() {
+                throw!(
+                    cx.scope,
+                    TypeError,
+                    "Content type of source and target typed arrays must match"
+                );
+            }
+
+            (0..source.length(cx.scope))
+                .map(|index| source.get(cx.scope, index).unwrap_or_undefined())
+                .collect::<Vec<_>>()
+        }
+        _ => {
+            let source = Value::object(source.to_object(cx.scope)?);
+            let mut values = Vec::new();
+            for index in 0..source.length_of_array_like(cx.scope)? {
+                values.push(source.get_property(index.to_key(cx.scope), cx.scope).root(cx.scope)?);
+            }
+            values
+        }
+    };
+
+    if offset + values.len() as f64 > len as f64 {
+        throw!(cx.scope, RangeError, "offset is out of bounds");
+    }
+
+    for (index, value) in values.into_iter().enumerate() {
+        this.set(cx.scope, offset as usize + index, value)?;
+    }
+
     Ok(Value::undefined())
 }
 
+pub fn slice(cx: CallContext) -> Result<Value, Value> {
+    let this = validate(cx.scope, &cx.this, "TypedArray.prototype.slice")?;
+    let len = this.length(cx.scope);
+    let start = relative_index(cx.scope, cx.args.first(), len, 0)?;
+    let end = relative_index(cx.scope, cx.args.get(1), len, len)?;
+    let count = end.saturating_sub(start);
+
+    let size = this.kind().bytes_per_element();
+    let array = allocate(cx.scope, this.kind(), count)?;
+    let target = array.extract::<TypedArray>(cx.scope).unwrap().storage(cx.scope);
+    // The length of the source may have changed while converting the arguments
+    if let Some(source) = this.storage(cx.scope).get(start * size..end * size) {
+        for (dest, src) in target.iter().zip(source) {
+            dest.set(src.get());
+        }
+    }
+
+    Ok(array.into())
+}
+
+/// Compares two typed array elements numerically, as specified by the TypedArray SortCompare abstract operation
+fn sort_compare(sc: &mut LocalScope<'_>, x: Value, y: Value, compare_fn: Option<Value>) -> Result<Ordering, Value> {
+    if let Some(compare_fn) = compare_fn {
+        let ordering = compare_fn
+            .apply(This::default(), [x, y].into(), sc)
+            .root(sc)?
+            .to_number(sc)?;
+        return Ok(ordering.partial_cmp(&0.0).unwrap_or(Ordering::Equal));
+    }
+
+    Ok(match (x.unpack(), y.unpack()) {
+        (ValueKind::Number(x), ValueKind::Number(y)) => match (x.0.is_nan(), y.0.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            // -0 is sorted before +0
+            (false, false) => x.0.total_cmp(&y.0),
+        },
+        (ValueKind::BigInt(x), ValueKind::BigInt(y)) => x.get(sc).cmp(y.get(sc)),
+        _ => Ordering::Equal,
+    })
+}
+
+/// Reads all elements of the typed array and sorts them
+fn sorted_values(cx: &mut CallContext, name: &'static str) -> Result<Vec<Value>, Value> {
+    let compare_fn = sort_compare_fn(cx.scope, cx.args.first())?;
+    let this = validate(cx.scope, &cx.this, name)?;
+    let mut values = (0..this.length(cx.scope))
+        .map(|index| this.get(cx.scope, index).unwrap_or_undefined())
+        .collect::<Vec<_>>();
+
+    sort_values_by(cx.scope, &mut values, |sc, x, y| sort_compare(sc, x, y, compare_fn))?;
+    Ok(values)
+}
+
+pub fn sort(mut cx: CallContext) -> Result<Value, Value> {
+    let values = sorted_values(&mut cx, "TypedArray.prototype.sort")?;
+    let this = receiver_t::<TypedArray>(cx.scope, &cx.this, "TypedArray.prototype.sort")?;
+    // The compare function may have shrunk the buffer, in which case the elements past the end are dropped
+    for (index, value) in values.into_iter().enumerate() {
+        this.set(cx.scope, index, value)?;
+    }
+
+    Ok(cx.this)
+}
+
+pub fn subarray(cx: CallContext) -> Result<Value, Value> {
+    let this = receiver_t::<TypedArray>(cx.scope, &cx.this, "TypedArray.prototype.subarray")?;
+    let kind = this.kind();
+    let len = this.length(cx.scope);
+    let begin = relative_index(cx.scope, cx.args.first(), len, 0)?;
+    let end = cx.args.get(1).copied().and_then(Value::into_option);
+
+    // A subarray of a length-tracking typed array without an explicit end keeps tracking the buffer length
+    let length = if this.is_length_tracking() && end.is_none() {
+        None
+    } else {
+        let end = relative_index(cx.scope, end.as_ref(), len, len)?;
+        Some(end.saturating_sub(begin))
+    };
+    let byte_offset = this.byte_offset() + begin * kind.bytes_per_element();
+    let prototype = kind.prototype(cx.scope);
+    let array = TypedArray::with_view(
+        this.arraybuffer_id(),
+        kind,
+        byte_offset,
+        length,
+        OrdObject::with_prototype(prototype),
+    );
+    Ok(cx.scope.register(array).into())
+}
+
+pub fn to_locale_string(cx: CallContext) -> Result<Value, Value> {
+    let this = validate(cx.scope, &cx.this, "TypedArray.prototype.toLocaleString")?;
+    let mut result = String::new();
+    for index in 0..this.length(cx.scope) {
+        if index > 0 {
+            result.push(',');
+        }
+
+        if let Some(value) = this.get(cx.scope, index) {
+            let value = value
+                .get_property(sym::toLocaleString.to_key(cx.scope), cx.scope)
+                .root(cx.scope)?
+                .apply(This::bound(value), cx.args.clone(), cx.scope)
+                .root(cx.scope)?
+                .to_js_string(cx.scope)?;
+            result.push_str(value.res(cx.scope));
+        }
+    }
+
+    Ok(Value::string(cx.scope.intern(result).into()))
+}
+
+pub fn to_reversed(cx: CallContext) -> Result<Value, Value> {
+    let this = validate(cx.scope, &cx.this, "TypedArray.prototype.toReversed")?;
+    let kind = this.kind();
+    let values = (0..this.length(cx.scope))
+        .rev()
+        .map(|index| this.get(cx.scope, index).unwrap_or_undefined())
+        .collect();
+
+    let prototype = kind.prototype(cx.scope);
+    Ok(from_values(cx.scope, kind, values, OrdObject::with_prototype(prototype))?.into())
+}
+
+pub fn to_sorted(mut cx: CallContext) -> Result<Value, Value> {
+    let values = sorted_values(&mut cx, "TypedArray.prototype.toSorted")?;
+    let kind = receiver_t::<TypedArray>(cx.scope, &cx.this, "TypedArray.prototype.toSorted")?.kind();
+    let prototype = kind.prototype(cx.scope);
+    Ok(from_values(cx.scope, kind, values, OrdObject::with_prototype(prototype))?.into())
+}
+
+pub fn values(cx: CallContext) -> Result<Value, Value> {
+    validate(cx.scope, &cx.this, "TypedArray.prototype.values")?;
+    let iter = ArrayIterator::new(cx.scope, cx.this)?;
+    Ok(cx.scope.register(iter).into())
+}
+
+pub fn with(cx: CallContext) -> Result<Value, Value> {
+    let this = validate(cx.scope, &cx.this, "TypedArray.prototype.with")?;
+    let (kind, len) = (this.kind(), this.length(cx.scope));
+    let relative_index = cx.args.first().unwrap_or_undefined().to_integer_or_infinity(cx.scope)?;
+    let actual_index = if relative_index >= 0.0 {
+        relative_index
+    } else {
+        len as f64 + relative_index
+    };
+
+    let value = cx.args.get(1).unwrap_or_undefined();
+    let value = if kind.is_bigint() {
+        Value::bigint(to_bigint(value, cx.scope)?)
+    } else {
+        Value::number(value.to_number(cx.scope)?)
+    };
+
+    // The conversion of the value may have shrunk the buffer
+    if actual_index < 0.0 || actual_index >= this.length(cx.scope) as f64 {
+        throw!(cx.scope, RangeError, "Invalid typed array index");
+    }
+
+    let mut values = (0..len)
+        .map(|index| this.get(cx.scope, index).unwrap_or_undefined())
+        .collect::<Vec<_>>();
+    values[actual_index as usize] = value;
+
+    let prototype = kind.prototype(cx.scope);
+    Ok(from_values(cx.scope, kind, values, OrdObject::with_prototype(prototype))?.into())
+}
+
+pub fn buffer(cx: CallContext) -> Result<Value, Value> {
+    let this = receiver_t::<TypedArray>(cx.scope, &cx.this, "TypedArray.prototype.buffer")?;
+    Ok(Value::object(this.arraybuffer_id()))
+}
+
+pub fn byte_length(cx: CallContext) -> Result<Value, Value> {
+    let this = receiver_t::<TypedArray>(cx.scope, &cx.this, "TypedArray.prototype.byteLength")?;
+    Ok(Value::number(this.byte_length(cx.scope) as f64))
+}
+
+pub fn byte_offset(cx: CallContext) -> Result<Value, Value> {
+    let this = receiver_t::<TypedArray>(cx.scope, &cx.this, "TypedArray.prototype.byteOffset")?;
+    if this.is_out_of_bounds(cx.scope) {
+        return Ok(Value::number(0.0));
+    }
+    Ok(Value::number(this.byte_offset() as f64))
+}
+
+pub fn length(cx: CallContext) -> Result<Value, Value> {
+    let this = receiver_t::<TypedArray>(cx.scope, &cx.this, "TypedArray.prototype.length")?;
+    Ok(Value::number(this.length(cx.scope) as f64))
+}
+
 typedarray!(module: u8array, kind: TypedArrayKind::Uint8Array);
 typedarray!(module: i8array, kind: TypedArrayKind::Int8Array);
 typedarray!(module: u8clampedarray, kind: TypedArrayKind::Uint8ClampedArray);
@@ -135,5 +923,8 @@ typedarray!(module: i16array, kind: TypedArrayKind::Int16Array);
 typedarray!(module: u16array, kind: TypedArrayKind::Uint16Array);
 typedarray!(module: i32array, kind: TypedArrayKind::Int32Array);
 typedarray!(module: u32array, kind: TypedArrayKind::Uint32Array);
+typedarray!(module: bigi64array, kind: TypedArrayKind::BigInt64Array);
+typedarray!(module: bigu64array, kind: TypedArrayKind::BigUint64Array);
+typedarray!(module: f16array, kind: TypedArrayKind::Float16Array);
 typedarray!(module: f32array, kind: TypedArrayKind::Float32Array);
 typedarray!(module: f64array, kind: TypedArrayKind::Float64Array);
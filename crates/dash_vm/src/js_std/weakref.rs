@@ -0,0 +1,33 @@
+use crate::throw;
+use crate::value::function::native::CallContext;
+use crate::value::object::OrdObject;
+use crate::value::weakref::WeakRef;
+use crate::value::{Unpack, Value, ValueContext, ValueKind};
+
+use super::receiver_t;
+
+pub fn constructor(cx: CallContext) -> Result<Value, Value> {
+    let Some(new_target) = cx.new_target else {
+        throw!(cx.scope, TypeError, "WeakRef constructor requires new")
+    };
+    let ValueKind::Object(target) = cx.args.first().unwrap_or_undefined().unpack() else {
+        throw!(cx.scope, TypeError, "WeakRef: target must be an object")
+    };
+
+    let weakref = WeakRef::new(target, OrdObject::instance_for_new_target(new_target, cx.scope)?);
+    let weakref = cx.scope.register(weakref);
+    cx.scope.add_weak_holder(weakref);
+    cx.scope.keep_alive_during_job(target);
+    Ok(Value::object(weakref))
+}
+
+pub fn deref(cx: CallContext) -> Result<Value, Value> {
+    let target = receiver_t::<WeakRef>(cx.scope, &cx.this, "WeakRef.prototype.deref")?.target();
+    match target {
+        Some(target) => {
+            cx.scope.keep_alive_during_job(target);
+            Ok(Value::object(target))
+        }
+        None => Ok(Value::undefined()),
+    }
+}
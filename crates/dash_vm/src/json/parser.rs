@@ -31,13 +31,37 @@ impl JsonParseError {
     pub fn to_string(&self) -> Cow<'_, str> {
         match self {
             Self::UnexpectedEof => Cow::Borrowed("Unexpected end of JSON input"),
-            Self::UnexpectedToken(token, position) => {
-                Cow::Owned(format!("Unexpected token {} at position {}", *token as char, *position))
-            }
-            Self::Utf8Error(_, pos) => Cow::Owned(format!("Utf8 Error at position {pos}")),
-            Self::ParseFloatError(_, pos) => Cow::Owned(format!("Failed to parse number at position {pos}")),
+            Self::UnexpectedToken(token, _) => Cow::Owned(format!("Unexpected token {}", *token as char)),
+            Self::Utf8Error(..) => Cow::Borrowed("Utf8 Error"),
+            Self::ParseFloatError(..) => Cow::Borrowed("Failed to parse number"),
         }
     }
+
+    /// Returns the byte offset of where the error occurred
+    pub fn offset(&self, source: &[u8]) -> usize {
+        match self {
+            Self::UnexpectedEof => source.len(),
+            Self::UnexpectedToken(_, pos) | Self::Utf8Error(_, pos) | Self::ParseFloatError(_, pos) => *pos,
+        }
+    }
+
+    /// Formats this error along with the line and column in `source` where it occurred
+    pub fn describe(&self, source: &[u8]) -> String {
+        let (line, column) = line_column(source, self.offset(source));
+        format!("{} at line {line} column {column}", self.to_string())
+    }
+}
+
+/// Computes the 1-based line and column of a byte offset in the source.
+///
+/// Columns are counted in characters, not bytes.
+fn line_column(source: &[u8], offset: usize) -> (usize, usize) {
+    let prefix = &source[..offset.min(source.len())];
+    let line_start = prefix.iter().rposition(|&b| b == b'\n').map_or(0, |pos| pos + 1);
+    let line = prefix.iter().filter(|&&b| b == b'\n').count() + 1;
+    // Count every byte that is not a UTF-8 continuation byte
+    let column = prefix[line_start..].iter().filter(|&&b| b & 0xc0 != 0x80).count() + 1;
+    (line, column)
 }
 
 /// An error that may occur during converting
@@ -181,7 +205,10 @@ impl<'a, 'sc, 'vm> Parser<'a, 'sc, 'vm> {
                 let obj = OrdObject::new(self.sc);
                 while let Some(cur) = self.skip_to_relevant_token() {
                     match cur {
-                        b'}' => break,
+                        b'}' => {
+                            self.idx += 1;
+                            break;
+                        }
                         b',' => self.idx += 1,
                         _ => {}
                     };
@@ -0,0 +1,303 @@
+use std::fmt::Write;
+
+use dash_middle::interner::sym;
+
+use crate::gc::ObjectId;
+use crate::localscope::LocalScope;
+use crate::throw;
+use crate::value::array::is_array;
+use crate::value::object::{Object, OrdObject, OwnKeysMode, PropertyValue, This};
+use crate::value::ops::conversions::ValueConversion;
+use crate::value::propertykey::{PropertyKey, ToPropertyKey};
+use crate::value::string::JsString;
+use crate::value::{Root, Typeof, Unpack, Value, ValueKind};
+
+/// The maximum number of characters that can be used for indentation
+const MAX_GAP: usize = 10;
+
+/// Serializes values to JSON, as specified by `JSON.stringify`
+pub struct Stringifier<'sc, 'vm> {
+    sc: &'sc mut LocalScope<'vm>,
+    /// The replacer function, if one was provided
+    replacer: Option<Value>,
+    /// The allowlist of property keys, if the replacer was an array
+    property_list: Option<Vec<JsString>>,
+    /// The string used for one level of indentation
+    gap: String,
+    /// The current indentation
+    indent: String,
+    /// Objects that are currently being serialized, used for detecting cycles
+    stack: Vec<ObjectId>,
+    out: String,
+}
+
+impl<'sc, 'vm> Stringifier<'sc, 'vm> {
+    /// Creates a new stringifier from the `replacer` and `space` arguments passed to `JSON.stringify`
+    pub fn new(sc: &'sc mut LocalScope<'vm>, replacer: Value, space: Value) -> Result<Self, Value> {
+        let mut replacer_fn = None;
+        let mut property_list = None;
+
+        if let ValueKind::Object(o) = replacer.unpack() {
+            if matches!(o.type_of(sc), Typeof::Function) {
+                replacer_fn = Some(replacer);
+            } else if is_array(replacer, sc)? {
+                property_list = Some(Self::property_list(sc, replacer)?);
+            }
+        }
+
+        let gap = Self::gap(sc, space)?;
+
+        Ok(Self {
+            sc,
+            replacer: replacer_fn,
+            property_list,
+            gap,
+            indent: String::new(),
+            stack: Vec::new(),
+            out: String::new(),
+        })
+    }
+
+    /// Collects the property keys of a replacer array
+    fn property_list(sc: &mut LocalScope<'_>, replacer: Value) -> Result<Vec<JsString>, Value> {
+        let len = replacer.length_of_array_like(sc)?;
+        let mut list = Vec::new();
+
+        for i in 0..len {
+            let item = replacer.get_property(i.to_key(sc), sc).root(sc)?;
+            let is_key = match item.unpack() {
+                ValueKind::String(_) | ValueKind::Number(_) => true,
+                ValueKind::Object(o) => o
+                    .internal_slots(sc)
+                    .is_some_and(|s| s.string_value(sc).is_some() || s.number_value(sc).is_some()),
+                _ => false,
+            };
+
+            if is_key {
+                let key = item.to_js_string(sc)?;
+                if !list.contains(&key) {
+                    list.push(key);
+                }
+            }
+        }
+
+        Ok(list)
+    }
+
+    /// Computes the indentation string from the `space` argument
+    fn gap(sc: &mut LocalScope<'_>, mut space: Value) -> Result<String, Value> {
+        if let ValueKind::Object(o) = space.unpack() {
+            let slots = o.internal_slots(sc);
+            if slots.is_some_and(|s| s.number_value(sc).is_some()) {
+                space = Value::number(space.to_number(sc)?);
+            } else if slots.is_some_and(|s| s.string_value(sc).is_some()) {
+                space = Value::string(space.to_js_string(sc)?);
+            }
+        }
+
+        Ok(match space.unpack() {
+            ValueKind::Number(n) => {
+                let n = n.0.clamp(0.0, MAX_GAP as f64) as usize;
+                " ".repeat(n)
+            }
+            ValueKind::String(s) => s.res(sc).chars().take(MAX_GAP).collect(),
+            _ => String::new(),
+        })
+    }
+
+    /// Serializes a value, returning `None` if the value has no JSON representation (e.g. `undefined` or functions)
+    pub fn stringify(mut self, value: Value) -> Result<Option<String>, Value> {
+        let wrapper = OrdObject::new(self.sc);
+        let wrapper = self.sc.register(wrapper);
+        let key = sym::empty.to_key(self.sc);
+        wrapper.set_property(key, PropertyValue::static_default(value), self.sc)?;
+
+        if self.serialize_property(Value::object(wrapper), key)? {
+            Ok(Some(self.out))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Implements the SerializeJSONProperty abstract operation.
+    ///
+    /// Returns `false` if nothing was written because the value cannot be represented in JSON.
+    fn serialize_property(&mut self, holder: Value, key: PropertyKey) -> Result<bool, Value> {
+        let mut value = holder.get_property(key, self.sc).root(self.sc)?;
+
+        if let ValueKind::Object(_) | ValueKind::BigInt(_) = value.unpack() {
+            let to_json = value.get_property(sym::toJSON.to_key(self.sc), self.sc).root(self.sc)?;
+            if matches!(to_json.type_of(self.sc), Typeof::Function) {
+                let key = key.to_value(self.sc);
+                value = to_json.apply(This::bound(value), [key].into(), self.sc).root(self.sc)?;
+            }
+        }
+
+        if let Some(replacer) = self.replacer {
+            let key = key.to_value(self.sc);
+            value = replacer
+                .apply(This::bound(holder), [key, value].into(), self.sc)
+                .root(self.sc)?;
+        }
+
+        // Unwrap boxed primitives
+        if let ValueKind::Object(o) = value.unpack() {
+            let slots = o.internal_slots(self.sc);
+            let is_number = slots.is_some_and(|s| s.number_value(self.sc).is_some());
+            let is_string = slots.is_some_and(|s| s.string_value(self.sc).is_some());
+            let boolean = slots.and_then(|s| s.boolean_value(self.sc));
+            let bigint = slots.and_then(|s| s.bigint_value(self.sc));
+
+            if is_number {
+                value = Value::number(value.to_number(self.sc)?);
+            } else if is_string {
+                value = Value::string(value.to_js_string(self.sc)?);
+            } else if let Some(b) = boolean {
+                value = Value::boolean(b);
+            } else if let Some(b) = bigint {
+                value = Value::bigint(b);
+            }
+        }
+
+        match value.unpack() {
+            ValueKind::Null(_) => self.out.push_str("null"),
+            ValueKind::Boolean(b) => self.out.push_str(if b { "true" } else { "false" }),
+            ValueKind::String(s) => quote(&mut self.out, s.res(self.sc)),
+            ValueKind::Number(n) if n.0.is_finite() => {
+                let s = value.to_js_string(self.sc)?;
+                self.out.push_str(s.res(self.sc));
+            }
+            ValueKind::Number(_) => self.out.push_str("null"),
+            ValueKind::BigInt(_) => throw!(&mut *self.sc, TypeError, "Do not know how to serialize a BigInt"),
+            ValueKind::Object(o) if !matches!(o.type_of(self.sc), Typeof::Function) => {
+                if self.stack.contains(&o) {
+                    throw!(&mut *self.sc, TypeError, "Converting circular structure to JSON")
+                }
+
+                self.stack.push(o);
+                let stepback = self.indent.clone();
+                self.indent.push_str(&self.gap);
+
+                let result = if is_array(value, self.sc)? {
+                    self.serialize_array(value)
+                } else {
+                    self.serialize_object(value)
+                };
+
+                self.indent = stepback;
+                self.stack.pop();
+                result?;
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    /// Writes a newline followed by the current indentation, if pretty printing is enabled
+    fn write_newline(&mut self, indent: &str) {
+        if !self.gap.is_empty() {
+            self.out.push('\n');
+            self.out.push_str(indent);
+        }
+    }
+
+    /// Implements the SerializeJSONObject abstract operation
+    fn serialize_object(&mut self, value: Value) -> Result<(), Value> {
+        let keys = match &self.property_list {
+            Some(list) => list.iter().copied().map(Value::string).collect(),
+            None => match value.unpack() {
+                // Symbol keys are never serialized
+                ValueKind::Object(o) => o
+                    .own_keys(self.sc, OwnKeysMode::OnlyEnumerable)?
+                    .into_iter()
+                    .filter(|key| !matches!(key.unpack(), ValueKind::Symbol(_)))
+                    .collect(),
+                _ => Vec::new(),
+            },
+        };
+
+        let stepback = self.indent[..self.indent.len() - self.gap.len()].to_owned();
+        let indent = self.indent.clone();
+        let mut empty = true;
+
+        self.out.push('{');
+        for key in keys {
+            let start = self.out.len();
+            if !empty {
+                self.out.push(',');
+            }
+            self.write_newline(&indent);
+
+            let name = key.to_js_string(self.sc)?;
+            quote(&mut self.out, name.res(self.sc));
+            self.out.push(':');
+            if !self.gap.is_empty() {
+                self.out.push(' ');
+            }
+
+            let key = PropertyKey::from_js_string(name, self.sc);
+            if self.serialize_property(value, key)? {
+                empty = false;
+            } else {
+                self.out.truncate(start);
+            }
+        }
+
+        if !empty {
+            self.write_newline(&stepback);
+        }
+        self.out.push('}');
+
+        Ok(())
+    }
+
+    /// Implements the SerializeJSONArray abstract operation
+    fn serialize_array(&mut self, value: Value) -> Result<(), Value> {
+        let len = value.length_of_array_like(self.sc)?;
+
+        let stepback = self.indent[..self.indent.len() - self.gap.len()].to_owned();
+        let indent = self.indent.clone();
+
+        self.out.push('[');
+        for i in 0..len {
+            if i > 0 {
+                self.out.push(',');
+            }
+            self.write_newline(&indent);
+
+            let key = i.to_key(self.sc);
+            if !self.serialize_property(value, key)? {
+                self.out.push_str("null");
+            }
+        }
+
+        if len > 0 {
+            self.write_newline(&stepback);
+        }
+        self.out.push(']');
+
+        Ok(())
+    }
+}
+
+/// Implements the QuoteJSONString abstract operation, appending the quoted string to `out`
+fn quote(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
@@ -26,6 +26,7 @@ use self::value::Value;
 use self::value::object::{Object, PropertyValue};
 
 use dash_log::{Level, debug, error, span};
+use dash_middle::compiler::constant::TemplateStrings;
 use dash_middle::compiler::external::ExternalId;
 use dash_middle::compiler::instruction::Instruction;
 use dash_middle::compiler::scope::BackLocalId;
@@ -33,10 +34,14 @@ use dash_middle::interner::{self, StringInterner, sym};
 use gc::trace::{Trace, TraceCtxt};
 use gc::{Allocator, ObjectId};
 use rustc_hash::{FxHashMap, FxHashSet};
+use value::bigint::JsBigInt;
+use value::finalizationregistry::FinalizationRegistry;
 use value::function::args::CallArgs;
+use value::function::bound::BoundFunction;
 use value::object::{OrdObject, extract_type};
 use value::promise::{Promise, PromiseState};
 use value::propertykey::ToPropertyKey;
+use value::weakref::WeakRef;
 use value::{ExternalValue, PureBuiltin, Unpack, Unrooted, ValueKind};
 
 pub mod dispatch;
@@ -87,6 +92,19 @@ pub struct Vm {
     // We insert into this in `Persistent::new`, and remove from it during the tracing phase.
     // We can't do that in Persistent's Drop code, because we don't have access to the VM there.
     external_refs: ExternalRefs,
+    /// Objects that hold weak references (`WeakRef`s and `FinalizationRegistry`s).
+    /// These are not roots: after tracing, holders that were not marked are removed
+    /// and the weak references of the remaining ones are processed before sweeping.
+    weak_holders: Vec<ObjectId>,
+    /// Targets of `WeakRef`s that were created or dereferenced in the current job.
+    /// They must stay alive until the job finishes.
+    kept_alive: Vec<ObjectId>,
+    /// The template objects of tagged templates that have been evaluated, keyed by the address of their strings.
+    /// The strings are kept alive so that the address cannot be reused by a different template.
+    template_objects: FxHashMap<*const TemplateStrings, (std::rc::Rc<TemplateStrings>, ObjectId)>,
+    /// Canonical handles of BigInts that were used as keys of a `Map` or `Set`, so that equal BigInts are the same key.
+    /// These are not roots: entries whose handle was not marked are removed before sweeping.
+    canonical_bigints: FxHashMap<num_bigint::BigInt, JsBigInt>,
     pub statics: Box<Statics>,
     #[cfg_attr(dash_lints, dash_lints::trusted_no_gc)]
     try_blocks: Vec<TryBlock>,
@@ -118,6 +136,10 @@ impl Vm {
             global,
             rejected_promises: FxHashSet::default(),
             external_refs: ExternalRefs::default(),
+            weak_holders: Vec::new(),
+            kept_alive: Vec::new(),
+            template_objects: FxHashMap::default(),
+            canonical_bigints: FxHashMap::default(),
             shadow_roots: Vec::new(),
             statics: Box::new(statics),
             try_blocks: Vec::new(),
@@ -210,6 +232,21 @@ impl Vm {
             base
         }
 
+        /// Registers an accessor property that only has a getter
+        fn register_getter(base: ObjectId, key: interner::Symbol, getter: ObjectId, scope: &mut LocalScope<'_>) {
+            register(getter, scope.statics.function_proto, scope.statics.function_ctor, [], [], [], None, scope);
+            let getter = PropertyValue::new(PropertyValueKind::Trap { get: Some(getter), set: None }, PropertyDataDescriptor::CONFIGURABLE);
+            base.set_property(key.to_key(scope), getter, scope).unwrap();
+        }
+
+        /// Registers an accessor property with a getter and a setter
+        fn register_accessor(base: ObjectId, key: interner::Symbol, getter: ObjectId, setter: ObjectId, scope: &mut LocalScope<'_>) {
+            register(getter, scope.statics.function_proto, scope.statics.function_ctor, [], [], [], None, scope);
+            register(setter, scope.statics.function_proto, scope.statics.function_ctor, [], [], [], None, scope);
+            let accessor = PropertyValue::new(PropertyValueKind::Trap { get: Some(getter), set: Some(setter) }, PropertyDataDescriptor::CONFIGURABLE);
+            base.set_property(key.to_key(scope), accessor, scope).unwrap();
+        }
+
         let mut scope = self.scope();
         let global = scope.global;
 
@@ -240,6 +277,8 @@ impl Vm {
             &mut scope,
         );
 
+        register(scope.statics.throw_type_error, function_proto, function_ctor, [], [], [], None, &mut scope);
+
         let object_ctor = register(
             scope.statics.object_ctor,
             function_proto,
@@ -257,6 +296,15 @@ impl Vm {
                 (sym::assign, scope.statics.object_assign),
                 (sym::freeze, scope.statics.object_freeze),
                 (sym::seal, scope.statics.object_seal),
+                (sym::preventExtensions, scope.statics.object_prevent_extensions),
+                (sym::isExtensible, scope.statics.object_is_extensible),
+                (sym::isFrozen, scope.statics.object_is_frozen),
+                (sym::isSealed, scope.statics.object_is_sealed),
+                (sym::values, scope.statics.object_values),
+                (sym::fromEntries, scope.statics.object_from_entries),
+                (sym::is, scope.statics.object_is),
+                (sym::groupBy, scope.statics.object_group_by),
+                (sym::hasOwn, scope.statics.object_has_own),
                 (sym::getPrototypeOf, scope.statics.object_get_prototype_of),
                 (sym::setPrototypeOf, scope.statics.object_set_prototype_of),
             ],
@@ -281,6 +329,7 @@ impl Vm {
             None,
             &mut scope,
         );
+        register_accessor(object_proto, sym::__proto__, scope.statics.object_proto_getter, scope.statics.object_proto_setter, &mut scope);
 
         let console = register(
             scope.statics.console,
@@ -357,6 +406,8 @@ impl Vm {
                 (sym::isNaN, scope.statics.number_is_nan),
                 (sym::isSafeInteger, scope.statics.number_is_safe_integer),
                 (sym::isInteger, scope.statics.number_is_integer),
+                (sym::parseFloat, scope.statics.parse_float),
+                (sym::parseInt, scope.statics.parse_int),
             ],
             [],
             [
@@ -381,6 +432,9 @@ impl Vm {
                 (sym::toString, scope.statics.number_tostring),
                 (sym::valueOf, scope.statics.number_valueof),
                 (sym::toFixed, scope.statics.number_to_fixed),
+                (sym::toPrecision, scope.statics.number_to_precision),
+                (sym::toExponential, scope.statics.number_to_exponential),
+                (sym::toLocaleString, scope.statics.number_to_locale_string),
             ],
             [],
             [],
@@ -413,12 +467,41 @@ impl Vm {
             &mut scope,
         );
 
+        let bigint_ctor = register(
+            scope.statics.bigint_ctor,
+            function_proto,
+            function_ctor,
+            [
+                (sym::asIntN, scope.statics.bigint_as_int_n),
+                (sym::asUintN, scope.statics.bigint_as_uint_n),
+            ],
+            [],
+            [],
+            Some((sym::BigInt, scope.statics.bigint_prototype)),
+            &mut scope,
+        );
+
+        register(
+            scope.statics.bigint_prototype,
+            object_proto,
+            bigint_ctor,
+            [
+                (sym::toString, scope.statics.bigint_tostring),
+                (sym::valueOf, scope.statics.bigint_valueof),
+            ],
+            [],
+            [],
+            None,
+            &mut scope,
+        );
+
         let string_ctor = register(
             scope.statics.string_ctor,
             function_proto,
             function_ctor,
             [
                 (sym::fromCharCode, scope.statics.string_from_char_code),
+                (sym::raw, scope.statics.string_raw),
             ],
             [],
             [],
@@ -446,6 +529,9 @@ impl Vm {
                 (sym::replace, scope.statics.string_replace),
                 (sym::replaceAll, scope.statics.string_replace_all),
                 (sym::split, scope.statics.string_split),
+                (sym::match_, scope.statics.string_match),
+                (sym::matchAll, scope.statics.string_match_all),
+                (sym::search, scope.statics.string_search),
                 (sym::toLowerCase, scope.statics.string_to_lowercase),
                 (sym::toUpperCase, scope.statics.string_to_uppercase),
                 (sym::big, scope.statics.string_big),
@@ -465,6 +551,7 @@ impl Vm {
                 (sym::substr, scope.statics.string_substr),
                 (sym::substring, scope.statics.string_substring),
                 (sym::slice, scope.statics.string_slice),
+                (sym::localeCompare, scope.statics.string_locale_compare),
             ],
            [(scope.statics.symbol_iterator, scope.statics.string_iterator)],
            [],
@@ -479,6 +566,8 @@ impl Vm {
             [
                 (sym::from, scope.statics.array_from),
                 (sym::isArray, scope.statics.array_is_array),
+                (sym::of, scope.statics.array_of),
+                (sym::fromAsync, scope.statics.array_from_async),
             ],
             [],
             [],
@@ -519,6 +608,15 @@ impl Vm {
                 (sym::unshift, scope.statics.array_unshift),
                 (sym::slice, scope.statics.array_slice),
                 (sym::lastIndexOf, scope.statics.array_last_index_of),
+                (sym::reduceRight, scope.statics.array_reduce_right),
+                (sym::flatMap, scope.statics.array_flat_map),
+                (sym::findLast, scope.statics.array_find_last),
+                (sym::findLastIndex, scope.statics.array_find_last_index),
+                (sym::copyWithin, scope.statics.array_copy_within),
+                (sym::toSorted, scope.statics.array_to_sorted),
+                (sym::toReversed, scope.statics.array_to_reversed),
+                (sym::toSpliced, scope.statics.array_to_spliced),
+                (sym::with, scope.statics.array_with),
             ],
             [(scope.statics.symbol_iterator, scope.statics.array_values)],
             [],
@@ -547,6 +645,7 @@ impl Vm {
             function_ctor, // TODO: ^
             [
                 (sym::next, scope.statics.generator_iterator_next),
+                (sym::return_, scope.statics.generator_iterator_return),
                 (sym::throw,scope.statics.generator_iterator_throw)
             ],
             [
@@ -557,6 +656,53 @@ impl Vm {
             &mut scope,
         );
 
+        register(
+            scope.statics.async_generator_prototype,
+            object_proto, // TODO: wrong
+            function_ctor, // TODO: ^
+            [
+                (sym::next, scope.statics.async_generator_next),
+                (sym::return_, scope.statics.async_generator_return),
+                (sym::throw, scope.statics.async_generator_throw),
+            ],
+            [
+                (scope.statics.symbol_async_iterator, scope.statics.self_async_iterator),
+            ],
+            [],
+            None,
+            &mut scope,
+        );
+        let to_string_tag = scope.statics.symbol_to_string_tag.to_key(&mut scope);
+        let async_generator_tag = PropertyValue::new(PropertyValueKind::Static(Value::string(sym::AsyncGenerator.into())), PropertyDataDescriptor::CONFIGURABLE);
+        scope.statics.async_generator_prototype.clone().set_property(to_string_tag, async_generator_tag, &mut scope).unwrap();
+
+        register(
+            scope.statics.async_from_sync_iterator_prototype,
+            object_proto,
+            function_ctor,
+            [
+                (sym::next, scope.statics.async_from_sync_iterator_next),
+                (sym::return_, scope.statics.async_from_sync_iterator_return),
+            ],
+            [
+                (scope.statics.symbol_async_iterator, scope.statics.self_async_iterator),
+            ],
+            [],
+            None,
+            &mut scope,
+        );
+
+        register(
+            scope.statics.async_from_sync_iterator_result,
+            function_proto,
+            function_ctor,
+            [],
+            [],
+            [],
+            None,
+            &mut scope,
+        );
+
         let symbol_ctor = register(
             scope.statics.symbol_ctor,
             function_proto,
@@ -609,7 +755,9 @@ impl Vm {
             scope.statics.arraybuffer_ctor,
             function_proto,
             function_ctor,
-            [],
+            [
+                (sym::isView, scope.statics.arraybuffer_is_view),
+            ],
             [],
             [],
             Some((sym::ArrayBuffer, scope.statics.arraybuffer_prototype)),
@@ -621,7 +769,10 @@ impl Vm {
             object_proto,
             arraybuffer_ctor,
             [
-                (sym::byteLength, scope.statics.arraybuffer_byte_length) // TODO: should be a getter really
+                (sym::slice, scope.statics.arraybuffer_slice),
+                (sym::resize, scope.statics.arraybuffer_resize),
+                (sym::transfer, scope.statics.arraybuffer_transfer),
+                (sym::transferToFixedLength, scope.statics.arraybuffer_transfer_to_fixed_length),
             ],
             [],
             [],
@@ -629,191 +780,404 @@ impl Vm {
             &mut scope,
         );
 
-        let u8array_ctor = register(
-            scope.statics.uint8array_ctor,
+        register_getter(scope.statics.arraybuffer_prototype, sym::byteLength, scope.statics.arraybuffer_byte_length, &mut scope);
+        register_getter(scope.statics.arraybuffer_prototype, sym::maxByteLength, scope.statics.arraybuffer_max_byte_length, &mut scope);
+        register_getter(scope.statics.arraybuffer_prototype, sym::resizable, scope.statics.arraybuffer_resizable, &mut scope);
+        register_getter(scope.statics.arraybuffer_prototype, sym::detached, scope.statics.arraybuffer_detached, &mut scope);
+
+        let sharedarraybuffer_ctor = register(
+            scope.statics.sharedarraybuffer_ctor,
             function_proto,
             function_ctor,
             [],
             [],
             [],
-            Some((sym::Uint8Array, scope.statics.uint8array_prototype)),
+            Some((sym::SharedArrayBuffer, scope.statics.sharedarraybuffer_prototype)),
             &mut scope,
         );
 
         register(
-            scope.statics.uint8array_prototype,
+            scope.statics.sharedarraybuffer_prototype,
             object_proto,
-            u8array_ctor,
+            sharedarraybuffer_ctor,
+            [
+                (sym::grow, scope.statics.sharedarraybuffer_grow),
+                (sym::slice, scope.statics.sharedarraybuffer_slice),
+            ],
+            [],
+            [],
+            None,
+            &mut scope,
+        );
+
+        register_getter(scope.statics.sharedarraybuffer_prototype, sym::byteLength, scope.statics.sharedarraybuffer_byte_length, &mut scope);
+        register_getter(scope.statics.sharedarraybuffer_prototype, sym::maxByteLength, scope.statics.sharedarraybuffer_max_byte_length, &mut scope);
+        register_getter(scope.statics.sharedarraybuffer_prototype, sym::growable, scope.statics.sharedarraybuffer_growable, &mut scope);
+
+        let typedarray_ctor = register(
+            scope.statics.typedarray_ctor,
+            function_proto,
+            function_ctor,
             [
+                (sym::from, scope.statics.typedarray_from),
+                (sym::of, scope.statics.typedarray_of),
+            ],
+            [],
+            [],
+            Some((sym::TypedArray, scope.statics.typedarray_prototype)),
+            &mut scope,
+        );
+
+        register(
+            scope.statics.typedarray_prototype,
+            object_proto,
+            typedarray_ctor,
+            [
+                (sym::at, scope.statics.typedarray_at),
+                (sym::copyWithin, scope.statics.typedarray_copy_within),
+                (sym::entries, scope.statics.typedarray_entries),
+                (sym::every, scope.statics.typedarray_every),
                 (sym::fill, scope.statics.typedarray_fill),
+                (sym::filter, scope.statics.typedarray_filter),
+                (sym::find, scope.statics.typedarray_find),
+                (sym::findIndex, scope.statics.typedarray_find_index),
+                (sym::findLast, scope.statics.typedarray_find_last),
+                (sym::findLastIndex, scope.statics.typedarray_find_last_index),
+                (sym::forEach, scope.statics.typedarray_for_each),
+                (sym::includes, scope.statics.typedarray_includes),
+                (sym::indexOf, scope.statics.typedarray_index_of),
+                (sym::join, scope.statics.typedarray_join),
+                (sym::keys, scope.statics.typedarray_keys),
+                (sym::lastIndexOf, scope.statics.typedarray_last_index_of),
+                (sym::map, scope.statics.typedarray_map),
+                (sym::reduce, scope.statics.typedarray_reduce),
+                (sym::reduceRight, scope.statics.typedarray_reduce_right),
+                (sym::reverse, scope.statics.typedarray_reverse),
+                (sym::set, scope.statics.typedarray_set),
+                (sym::slice, scope.statics.typedarray_slice),
+                (sym::some, scope.statics.typedarray_some),
+                (sym::sort, scope.statics.typedarray_sort),
+                (sym::subarray, scope.statics.typedarray_subarray),
+                (sym::toLocaleString, scope.statics.typedarray_to_locale_string),
+                (sym::toReversed, scope.statics.typedarray_to_reversed),
+                (sym::toSorted, scope.statics.typedarray_to_sorted),
+                (sym::values, scope.statics.typedarray_values),
+                (sym::with, scope.statics.typedarray_with),
+                (sym::toString, scope.statics.array_tostring),
             ],
+            [(scope.statics.symbol_iterator, scope.statics.typedarray_values)],
+            [],
+            None,
+            &mut scope,
+        );
+
+        register_getter(scope.statics.typedarray_prototype, sym::buffer, scope.statics.typedarray_buffer, &mut scope);
+        register_getter(scope.statics.typedarray_prototype, sym::byteLength, scope.statics.typedarray_byte_length, &mut scope);
+        register_getter(scope.statics.typedarray_prototype, sym::byteOffset, scope.statics.typedarray_byte_offset, &mut scope);
+        register_getter(scope.statics.typedarray_prototype, sym::length, scope.statics.typedarray_length, &mut scope);
+
+        let u8array_ctor = register(
+            scope.statics.uint8array_ctor,
+            typedarray_ctor,
+            function_ctor,
+            [],
+            [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(1.0), Some(PropertyDataDescriptor::empty()))],
+            Some((sym::Uint8Array, scope.statics.uint8array_prototype)),
+            &mut scope,
+        );
+
+        register(
+            scope.statics.uint8array_prototype,
+            scope.statics.typedarray_prototype,
+            u8array_ctor,
             [],
             [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(1.0), Some(PropertyDataDescriptor::empty()))],
+            None,
+            &mut scope,
+        );
+
+        let u8clampedarray_ctor = register(
+            scope.statics.uint8clampedarray_ctor,
+            typedarray_ctor,
+            function_ctor,
+            [],
+            [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(1.0), Some(PropertyDataDescriptor::empty()))],
+            Some((sym::Uint8ClampedArray, scope.statics.uint8clampedarray_prototype)),
+            &mut scope,
+        );
+
+        register(
+            scope.statics.uint8clampedarray_prototype,
+            scope.statics.typedarray_prototype,
+            u8clampedarray_ctor,
+            [],
+            [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(1.0), Some(PropertyDataDescriptor::empty()))],
             None,
             &mut scope,
         );
 
         let i8array_ctor = register(
             scope.statics.int8array_ctor,
-            function_proto,
+            typedarray_ctor,
             function_ctor,
             [],
             [],
-            [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(1.0), Some(PropertyDataDescriptor::empty()))],
             Some((sym::Int8Array, scope.statics.int8array_prototype)),
             &mut scope,
         );
 
         register(
             scope.statics.int8array_prototype,
-            object_proto,
+            scope.statics.typedarray_prototype,
             i8array_ctor,
-            [
-                (sym::fill, scope.statics.typedarray_fill),
-            ],
             [],
             [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(1.0), Some(PropertyDataDescriptor::empty()))],
             None,
             &mut scope,
         );
 
         let u16array_ctor = register(
             scope.statics.uint16array_ctor,
-            function_proto,
+            typedarray_ctor,
             function_ctor,
             [],
             [],
-            [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(2.0), Some(PropertyDataDescriptor::empty()))],
             Some((sym::Uint16Array, scope.statics.uint16array_prototype)),
             &mut scope,
         );
 
         register(
             scope.statics.uint16array_prototype,
-            object_proto,
+            scope.statics.typedarray_prototype,
             u16array_ctor,
-            [
-                (sym::fill, scope.statics.typedarray_fill),
-            ],
             [],
             [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(2.0), Some(PropertyDataDescriptor::empty()))],
             None,
             &mut scope,
         );
 
         let i16array_ctor = register(
             scope.statics.int16array_ctor,
-            function_proto,
+            typedarray_ctor,
             function_ctor,
             [],
             [],
-            [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(2.0), Some(PropertyDataDescriptor::empty()))],
             Some((sym::Int16Array, scope.statics.int16array_prototype)),
             &mut scope,
         );
 
         register(
             scope.statics.int16array_prototype,
-            object_proto,
+            scope.statics.typedarray_prototype,
             i16array_ctor,
-            [
-                (sym::fill, scope.statics.typedarray_fill),
-            ],
             [],
             [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(2.0), Some(PropertyDataDescriptor::empty()))],
             None,
             &mut scope,
         );
 
         let u32array_ctor = register(
             scope.statics.uint32array_ctor,
-            function_proto,
+            typedarray_ctor,
             function_ctor,
             [],
             [],
-            [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(4.0), Some(PropertyDataDescriptor::empty()))],
             Some((sym::Uint32Array, scope.statics.uint32array_prototype)),
             &mut scope,
         );
 
         register(
             scope.statics.uint32array_prototype,
-            object_proto,
+            scope.statics.typedarray_prototype,
             u32array_ctor,
-            [
-                (sym::fill, scope.statics.typedarray_fill),
-            ],
             [],
             [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(4.0), Some(PropertyDataDescriptor::empty()))],
             None,
             &mut scope,
         );
 
         let i32array_ctor = register(
             scope.statics.int32array_ctor,
-            function_proto,
+            typedarray_ctor,
             function_ctor,
             [],
             [],
-            [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(4.0), Some(PropertyDataDescriptor::empty()))],
             Some((sym::Int32Array, scope.statics.int32array_prototype)),
             &mut scope,
         );
 
         register(
             scope.statics.int32array_prototype,
-            object_proto,
+            scope.statics.typedarray_prototype,
             i32array_ctor,
-            [
-                (sym::fill, scope.statics.typedarray_fill),
-            ],
             [],
             [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(4.0), Some(PropertyDataDescriptor::empty()))],
+            None,
+            &mut scope,
+        );
+
+        let bigi64array_ctor = register(
+            scope.statics.bigint64array_ctor,
+            typedarray_ctor,
+            function_ctor,
+            [],
+            [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(8.0), Some(PropertyDataDescriptor::empty()))],
+            Some((sym::BigInt64Array, scope.statics.bigint64array_prototype)),
+            &mut scope,
+        );
+
+        register(
+            scope.statics.bigint64array_prototype,
+            scope.statics.typedarray_prototype,
+            bigi64array_ctor,
+            [],
+            [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(8.0), Some(PropertyDataDescriptor::empty()))],
+            None,
+            &mut scope,
+        );
+
+        let bigu64array_ctor = register(
+            scope.statics.biguint64array_ctor,
+            typedarray_ctor,
+            function_ctor,
+            [],
+            [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(8.0), Some(PropertyDataDescriptor::empty()))],
+            Some((sym::BigUint64Array, scope.statics.biguint64array_prototype)),
+            &mut scope,
+        );
+
+        register(
+            scope.statics.biguint64array_prototype,
+            scope.statics.typedarray_prototype,
+            bigu64array_ctor,
+            [],
+            [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(8.0), Some(PropertyDataDescriptor::empty()))],
+            None,
+            &mut scope,
+        );
+
+        let f16array_ctor = register(
+            scope.statics.float16array_ctor,
+            typedarray_ctor,
+            function_ctor,
+            [],
+            [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(2.0), Some(PropertyDataDescriptor::empty()))],
+            Some((sym::Float16Array, scope.statics.float16array_prototype)),
+            &mut scope,
+        );
+
+        register(
+            scope.statics.float16array_prototype,
+            scope.statics.typedarray_prototype,
+            f16array_ctor,
+            [],
+            [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(2.0), Some(PropertyDataDescriptor::empty()))],
             None,
             &mut scope,
         );
 
         let f32array_ctor = register(
             scope.statics.float32array_ctor,
-            function_proto,
+            typedarray_ctor,
             function_ctor,
             [],
             [],
-            [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(4.0), Some(PropertyDataDescriptor::empty()))],
             Some((sym::Float32Array, scope.statics.float32array_prototype)),
             &mut scope,
         );
 
         register(
             scope.statics.float32array_prototype,
-            object_proto,
+            scope.statics.typedarray_prototype,
             f32array_ctor,
-            [
-                (sym::fill, scope.statics.typedarray_fill),
-            ],
             [],
             [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(4.0), Some(PropertyDataDescriptor::empty()))],
             None,
             &mut scope,
         );
 
         let f64array_ctor = register(
             scope.statics.float64array_ctor,
-            function_proto,
+            typedarray_ctor,
             function_ctor,
             [],
             [],
-            [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(8.0), Some(PropertyDataDescriptor::empty()))],
             Some((sym::Float64Array, scope.statics.float64array_prototype)),
             &mut scope,
         );
 
         register(
             scope.statics.float64array_prototype,
-            object_proto,
+            scope.statics.typedarray_prototype,
             f64array_ctor,
+            [],
+            [],
+            [(sym::BYTES_PER_ELEMENT, Value::number(8.0), Some(PropertyDataDescriptor::empty()))],
+            None,
+            &mut scope,
+        );
+
+        let dataview_ctor = register(
+            scope.statics.dataview_ctor,
+            function_proto,
+            function_ctor,
+            [],
+            [],
+            [],
+            Some((sym::DataView, scope.statics.dataview_prototype)),
+            &mut scope,
+        );
+
+        register(
+            scope.statics.dataview_prototype,
+            object_proto,
+            dataview_ctor,
             [
-                (sym::fill, scope.statics.typedarray_fill),
+                (sym::getInt8, scope.statics.dataview_get_int8),
+                (sym::setInt8, scope.statics.dataview_set_int8),
+                (sym::getUint8, scope.statics.dataview_get_uint8),
+                (sym::setUint8, scope.statics.dataview_set_uint8),
+                (sym::getInt16, scope.statics.dataview_get_int16),
+                (sym::setInt16, scope.statics.dataview_set_int16),
+                (sym::getUint16, scope.statics.dataview_get_uint16),
+                (sym::setUint16, scope.statics.dataview_set_uint16),
+                (sym::getInt32, scope.statics.dataview_get_int32),
+                (sym::setInt32, scope.statics.dataview_set_int32),
+                (sym::getUint32, scope.statics.dataview_get_uint32),
+                (sym::setUint32, scope.statics.dataview_set_uint32),
+                (sym::getFloat16, scope.statics.dataview_get_float16),
+                (sym::setFloat16, scope.statics.dataview_set_float16),
+                (sym::getFloat32, scope.statics.dataview_get_float32),
+                (sym::setFloat32, scope.statics.dataview_set_float32),
+                (sym::getFloat64, scope.statics.dataview_get_float64),
+                (sym::setFloat64, scope.statics.dataview_set_float64),
+                (sym::getBigInt64, scope.statics.dataview_get_bigint64),
+                (sym::setBigInt64, scope.statics.dataview_set_bigint64),
+                (sym::getBigUint64, scope.statics.dataview_get_biguint64),
+                (sym::setBigUint64, scope.statics.dataview_set_biguint64),
             ],
             [],
             [],
@@ -821,6 +1185,10 @@ impl Vm {
             &mut scope,
         );
 
+        register_getter(scope.statics.dataview_prototype, sym::buffer, scope.statics.dataview_buffer, &mut scope);
+        register_getter(scope.statics.dataview_prototype, sym::byteLength, scope.statics.dataview_byte_length, &mut scope);
+        register_getter(scope.statics.dataview_prototype, sym::byteOffset, scope.statics.dataview_byte_offset, &mut scope);
+
         let promise_ctor = register(
             scope.statics.promise_ctor,
             function_proto,
@@ -828,6 +1196,11 @@ impl Vm {
             [
                 (sym::resolve, scope.statics.promise_resolve),
                 (sym::reject, scope.statics.promise_reject),
+                (sym::all, scope.statics.promise_all),
+                (sym::allSettled, scope.statics.promise_all_settled),
+                (sym::any, scope.statics.promise_any),
+                (sym::race, scope.statics.promise_race),
+                (sym::withResolvers, scope.statics.promise_with_resolvers),
             ],
             [],
             [],
@@ -841,6 +1214,8 @@ impl Vm {
             promise_ctor,
             [
                 (sym::then, scope.statics.promise_then),
+                (sym::catch, scope.statics.promise_catch),
+                (sym::finally, scope.statics.promise_finally),
             ],
             [],
             [],
@@ -869,8 +1244,23 @@ impl Vm {
                 (sym::delete, scope.statics.set_delete),
                 (sym::clear, scope.statics.set_clear),
                 (sym::size, scope.statics.set_size),
+                (sym::keys, scope.statics.set_values),
+                (sym::values, scope.statics.set_values),
+                (sym::entries, scope.statics.set_entries),
+                (sym::forEach, scope.statics.set_for_each),
             ],
+            [(scope.statics.symbol_iterator, scope.statics.set_values)],
             [],
+            None,
+            &mut scope,
+        );
+
+        register(
+            scope.statics.set_iterator_prototype,
+            object_proto, // TODO: should inherit from %IteratorPrototype%
+            function_ctor,
+            [(sym::next, scope.statics.set_iterator_next)],
+            [(scope.statics.symbol_iterator, scope.statics.self_iterator)],
             [],
             None,
             &mut scope,
@@ -880,7 +1270,7 @@ impl Vm {
             scope.statics.map_constructor,
             function_proto,
             function_ctor,
-            [],
+            [(sym::groupBy, scope.statics.map_group_by)],
             [],
             [],
             Some((sym::Map, scope.statics.map_prototype)),
@@ -898,8 +1288,23 @@ impl Vm {
                 (sym::delete, scope.statics.map_delete),
                 (sym::clear, scope.statics.map_clear),
                 (sym::size, scope.statics.map_size), // TODO: this should be a getter
+                (sym::keys, scope.statics.map_keys),
+                (sym::values, scope.statics.map_values),
+                (sym::entries, scope.statics.map_entries),
+                (sym::forEach, scope.statics.map_for_each),
             ],
+            [(scope.statics.symbol_iterator, scope.statics.map_entries)],
             [],
+            None,
+            &mut scope,
+        );
+
+        register(
+            scope.statics.map_iterator_prototype,
+            object_proto, // TODO: should inherit from %IteratorPrototype%
+            function_ctor,
+            [(sym::next, scope.statics.map_iterator_next)],
+            [(scope.statics.symbol_iterator, scope.statics.self_iterator)],
             [],
             None,
             &mut scope,
@@ -958,6 +1363,64 @@ impl Vm {
             &mut scope
         );
 
+        let weakref_ctor = register(
+            scope.statics.weakref_constructor,
+            function_proto,
+            function_ctor,
+            [],
+            [],
+            [],
+            Some((sym::WeakRef, scope.statics.weakref_prototype)),
+            &mut scope
+        );
+
+        register(
+            scope.statics.weakref_prototype,
+            object_proto,
+            weakref_ctor,
+            [(sym::deref, scope.statics.weakref_deref)],
+            [],
+            [],
+            None,
+            &mut scope
+        );
+
+        let finalizationregistry_ctor = register(
+            scope.statics.finalizationregistry_constructor,
+            function_proto,
+            function_ctor,
+            [],
+            [],
+            [],
+            Some((sym::FinalizationRegistry, scope.statics.finalizationregistry_prototype)),
+            &mut scope
+        );
+
+        register(
+            scope.statics.finalizationregistry_prototype,
+            object_proto,
+            finalizationregistry_ctor,
+            [
+                (sym::register, scope.statics.finalizationregistry_register),
+                (sym::unregister, scope.statics.finalizationregistry_unregister),
+            ],
+            [],
+            [],
+            None,
+            &mut scope
+        );
+
+        register(
+            scope.statics.finalizationregistry_cleanup,
+            function_proto,
+            function_ctor,
+            [],
+            [],
+            [],
+            None,
+            &mut scope,
+        );
+
         let regexp_ctor = register(
             scope.statics.regexp_ctor,
             function_proto,
@@ -975,9 +1438,16 @@ impl Vm {
             regexp_ctor,
             [
                 (sym::test, scope.statics.regexp_test),
-                (sym::exec, scope.statics.regexp_exec)
+                (sym::exec, scope.statics.regexp_exec),
+                (sym::toString, scope.statics.regexp_to_string),
+            ],
+            [
+                (scope.statics.symbol_match, scope.statics.regexp_symbol_match),
+                (scope.statics.symbol_match_all, scope.statics.regexp_symbol_match_all),
+                (scope.statics.symbol_replace, scope.statics.regexp_symbol_replace),
+                (scope.statics.symbol_search, scope.statics.regexp_symbol_search),
+                (scope.statics.symbol_split, scope.statics.regexp_symbol_split),
             ],
-            [],
             [],
             None,
             &mut scope,
@@ -1157,6 +1627,8 @@ impl Vm {
             function_ctor,
             [
                 (sym::now, scope.statics.date_now),
+                (sym::parse, scope.statics.date_parse),
+                (sym::UTC, scope.statics.date_utc),
             ],
             [],
             [],
@@ -1169,9 +1641,51 @@ impl Vm {
             object_proto,
             date_ctor,
             [
-                (sym::getTime, scope.statics.date_get_time)
+                (sym::getTime, scope.statics.date_get_time),
+                (sym::getDate, scope.statics.date_get_date),
+                (sym::getDay, scope.statics.date_get_day),
+                (sym::getFullYear, scope.statics.date_get_full_year),
+                (sym::getHours, scope.statics.date_get_hours),
+                (sym::getMilliseconds, scope.statics.date_get_milliseconds),
+                (sym::getMinutes, scope.statics.date_get_minutes),
+                (sym::getMonth, scope.statics.date_get_month),
+                (sym::getSeconds, scope.statics.date_get_seconds),
+                (sym::getTimezoneOffset, scope.statics.date_get_timezone_offset),
+                (sym::getUTCDate, scope.statics.date_get_utc_date),
+                (sym::getUTCDay, scope.statics.date_get_utc_day),
+                (sym::getUTCFullYear, scope.statics.date_get_utc_full_year),
+                (sym::getUTCHours, scope.statics.date_get_utc_hours),
+                (sym::getUTCMilliseconds, scope.statics.date_get_utc_milliseconds),
+                (sym::getUTCMinutes, scope.statics.date_get_utc_minutes),
+                (sym::getUTCMonth, scope.statics.date_get_utc_month),
+                (sym::getUTCSeconds, scope.statics.date_get_utc_seconds),
+                (sym::setDate, scope.statics.date_set_date),
+                (sym::setFullYear, scope.statics.date_set_full_year),
+                (sym::setHours, scope.statics.date_set_hours),
+                (sym::setMilliseconds, scope.statics.date_set_milliseconds),
+                (sym::setMinutes, scope.statics.date_set_minutes),
+                (sym::setMonth, scope.statics.date_set_month),
+                (sym::setSeconds, scope.statics.date_set_seconds),
+                (sym::setTime, scope.statics.date_set_time),
+                (sym::setUTCDate, scope.statics.date_set_utc_date),
+                (sym::setUTCFullYear, scope.statics.date_set_utc_full_year),
+                (sym::setUTCHours, scope.statics.date_set_utc_hours),
+                (sym::setUTCMilliseconds, scope.statics.date_set_utc_milliseconds),
+                (sym::setUTCMinutes, scope.statics.date_set_utc_minutes),
+                (sym::setUTCMonth, scope.statics.date_set_utc_month),
+                (sym::setUTCSeconds, scope.statics.date_set_utc_seconds),
+                (sym::toISOString, scope.statics.date_to_iso_string),
+                (sym::toJSON, scope.statics.date_to_json),
+                (sym::toString, scope.statics.date_to_string),
+                (sym::toUTCString, scope.statics.date_to_utc_string),
+                (sym::toDateString, scope.statics.date_to_date_string),
+                (sym::toTimeString, scope.statics.date_to_time_string),
+                (sym::toLocaleString, scope.statics.date_to_locale_string),
+                (sym::toLocaleDateString, scope.statics.date_to_locale_date_string),
+                (sym::toLocaleTimeString, scope.statics.date_to_locale_time_string),
+                (sym::valueOf, scope.statics.date_value_of),
             ],
-            [],
+            [(scope.statics.symbol_to_primitive, scope.statics.date_to_primitive)],
             [],
             None,
             &mut scope,
@@ -1183,6 +1697,45 @@ impl Vm {
             function_ctor,
             [
                 (sym::parse, scope.statics.json_parse),
+                (sym::stringify, scope.statics.json_stringify),
+            ],
+            [],
+            [],
+            None,
+            &mut scope,
+        );
+
+        let proxy_ctor = register(
+            scope.statics.proxy_ctor,
+            function_proto,
+            function_ctor,
+            [
+                (sym::revocable, scope.statics.proxy_revocable),
+            ],
+            [],
+            [],
+            None,
+            &mut scope,
+        );
+
+        let reflect = register(
+            scope.statics.reflect,
+            object_proto,
+            object_ctor,
+            [
+                (sym::apply, scope.statics.reflect_apply),
+                (sym::construct, scope.statics.reflect_construct),
+                (sym::defineProperty, scope.statics.reflect_define_property),
+                (sym::deleteProperty, scope.statics.reflect_delete_property),
+                (sym::get, scope.statics.reflect_get),
+                (sym::getOwnPropertyDescriptor, scope.statics.reflect_get_own_property_descriptor),
+                (sym::getPrototypeOf, scope.statics.reflect_get_prototype_of),
+                (sym::has, scope.statics.reflect_has),
+                (sym::ownKeys, scope.statics.reflect_own_keys),
+                (sym::isExtensible, scope.statics.reflect_is_extensible),
+                (sym::preventExtensions, scope.statics.reflect_prevent_extensions),
+                (sym::set, scope.statics.reflect_set),
+                (sym::setPrototypeOf, scope.statics.reflect_set_prototype_of),
             ],
             [],
             [],
@@ -1204,14 +1757,8 @@ impl Vm {
                 (sym::JsSymbol, symbol_ctor),
                 (sym::Date, date_ctor),
                 (sym::ArrayBuffer, arraybuffer_ctor),
-                (sym::Uint8Array, u8array_ctor),
-                (sym::Int8Array, i8array_ctor),
-                (sym::Uint16Array, u16array_ctor),
-                (sym::Int16Array, i16array_ctor),
-                (sym::Uint32Array, u32array_ctor),
-                (sym::Int32Array, i32array_ctor),
-                (sym::Float32Array, f32array_ctor),
-                (sym::Float64Array, f64array_ctor),
+                (sym::SharedArrayBuffer, sharedarraybuffer_ctor),
+                (sym::DataView, dataview_ctor),
                 (sym::Array, array_ctor),
                 (sym::Error, error_ctor),
                 (sym::EvalError, eval_error_ctor),
@@ -1228,12 +1775,17 @@ impl Vm {
                 (sym::Map, map_ctor),
                 (sym::WeakMap, weakmap_ctor),
                 (sym::WeakSet, weakset_ctor),
+                (sym::WeakRef, weakref_ctor),
+                (sym::FinalizationRegistry, finalizationregistry_ctor),
                 (sym::console, console),
                 (sym::Math, math),
                 (sym::Number, number_ctor),
                 (sym::Boolean, boolean_ctor),
+                (sym::BigInt, bigint_ctor),
                 (sym::Promise, promise_ctor),
                 (sym::JSON, json_ctor),
+                (sym::Proxy, proxy_ctor),
+                (sym::Reflect, reflect),
             ],
             [],
             [
@@ -1241,10 +1793,123 @@ impl Vm {
                 (sym::Infinity, Value::number(f64::INFINITY), Some(PropertyDataDescriptor::empty())),
                 (sym::undefined, Value::undefined(), Some(PropertyDataDescriptor::empty())),
                 (sym::globalThis, Value::object(global), Some(PropertyDataDescriptor::CONFIGURABLE | PropertyDataDescriptor::WRITABLE)),
+                // Registering these as methods would reset their prototype from %TypedArray% to Function.prototype
+                (sym::Uint8Array, Value::object(u8array_ctor), Some(PropertyDataDescriptor::CONFIGURABLE | PropertyDataDescriptor::WRITABLE)),
+                (sym::Uint8ClampedArray, Value::object(u8clampedarray_ctor), Some(PropertyDataDescriptor::CONFIGURABLE | PropertyDataDescriptor::WRITABLE)),
+                (sym::Int8Array, Value::object(i8array_ctor), Some(PropertyDataDescriptor::CONFIGURABLE | PropertyDataDescriptor::WRITABLE)),
+                (sym::Uint16Array, Value::object(u16array_ctor), Some(PropertyDataDescriptor::CONFIGURABLE | PropertyDataDescriptor::WRITABLE)),
+                (sym::Int16Array, Value::object(i16array_ctor), Some(PropertyDataDescriptor::CONFIGURABLE | PropertyDataDescriptor::WRITABLE)),
+                (sym::Uint32Array, Value::object(u32array_ctor), Some(PropertyDataDescriptor::CONFIGURABLE | PropertyDataDescriptor::WRITABLE)),
+                (sym::Int32Array, Value::object(i32array_ctor), Some(PropertyDataDescriptor::CONFIGURABLE | PropertyDataDescriptor::WRITABLE)),
+                (sym::BigInt64Array, Value::object(bigi64array_ctor), Some(PropertyDataDescriptor::CONFIGURABLE | PropertyDataDescriptor::WRITABLE)),
+                (sym::BigUint64Array, Value::object(bigu64array_ctor), Some(PropertyDataDescriptor::CONFIGURABLE | PropertyDataDescriptor::WRITABLE)),
+                (sym::Float16Array, Value::object(f16array_ctor), Some(PropertyDataDescriptor::CONFIGURABLE | PropertyDataDescriptor::WRITABLE)),
+                (sym::Float32Array, Value::object(f32array_ctor), Some(PropertyDataDescriptor::CONFIGURABLE | PropertyDataDescriptor::WRITABLE)),
+                (sym::Float64Array, Value::object(f64array_ctor), Some(PropertyDataDescriptor::CONFIGURABLE | PropertyDataDescriptor::WRITABLE)),
             ],
             None,
             &mut scope
         );
+
+        #[cfg(feature = "intl")]
+        {
+            let number_format_ctor = register(
+                scope.statics.intl.number_format_ctor,
+                function_proto,
+                function_ctor,
+                [(sym::supportedLocalesOf, scope.statics.intl.number_format_supported_locales_of)],
+                [],
+                [],
+                Some((sym::NumberFormat, scope.statics.intl.number_format_prototype)),
+                &mut scope
+            );
+
+            register(
+                scope.statics.intl.number_format_prototype,
+                object_proto,
+                number_format_ctor,
+                [
+                    (sym::formatToParts, scope.statics.intl.number_format_format_to_parts),
+                    (sym::resolvedOptions, scope.statics.intl.number_format_resolved_options),
+                ],
+                [],
+                [],
+                None,
+                &mut scope
+            );
+            register(scope.statics.intl.number_format_bound_format, function_proto, function_ctor, [], [], [], None, &mut scope);
+            // The `format` and `compare` properties are accessors that return a function bound to the receiver
+            register_getter(scope.statics.intl.number_format_prototype, sym::format, scope.statics.intl.number_format_format, &mut scope);
+
+            let date_time_format_ctor = register(
+                scope.statics.intl.date_time_format_ctor,
+                function_proto,
+                function_ctor,
+                [(sym::supportedLocalesOf, scope.statics.intl.date_time_format_supported_locales_of)],
+                [],
+                [],
+                Some((sym::DateTimeFormat, scope.statics.intl.date_time_format_prototype)),
+                &mut scope
+            );
+
+            register(
+                scope.statics.intl.date_time_format_prototype,
+                object_proto,
+                date_time_format_ctor,
+                [
+                    (sym::formatToParts, scope.statics.intl.date_time_format_format_to_parts),
+                    (sym::resolvedOptions, scope.statics.intl.date_time_format_resolved_options),
+                ],
+                [],
+                [],
+                None,
+                &mut scope
+            );
+            register(scope.statics.intl.date_time_format_bound_format, function_proto, function_ctor, [], [], [], None, &mut scope);
+            register_getter(scope.statics.intl.date_time_format_prototype, sym::format, scope.statics.intl.date_time_format_format, &mut scope);
+
+            let collator_ctor = register(
+                scope.statics.intl.collator_ctor,
+                function_proto,
+                function_ctor,
+                [(sym::supportedLocalesOf, scope.statics.intl.collator_supported_locales_of)],
+                [],
+                [],
+                Some((sym::Collator, scope.statics.intl.collator_prototype)),
+                &mut scope
+            );
+
+            register(
+                scope.statics.intl.collator_prototype,
+                object_proto,
+                collator_ctor,
+                [(sym::resolvedOptions, scope.statics.intl.collator_resolved_options)],
+                [],
+                [],
+                None,
+                &mut scope
+            );
+            register(scope.statics.intl.collator_bound_compare, function_proto, function_ctor, [], [], [], None, &mut scope);
+            register_getter(scope.statics.intl.collator_prototype, sym::compare, scope.statics.intl.collator_compare, &mut scope);
+
+            let intl = register(
+                scope.statics.intl.intl,
+                object_proto,
+                object_ctor,
+                [
+                    (sym::getCanonicalLocales, scope.statics.intl.get_canonical_locales),
+                    (sym::NumberFormat, number_format_ctor),
+                    (sym::DateTimeFormat, date_time_format_ctor),
+                    (sym::Collator, collator_ctor),
+                ],
+                [],
+                [],
+                None,
+                &mut scope
+            );
+
+            global.set_property(sym::Intl.to_key(&mut scope), PropertyValue::static_non_enumerable(intl.into()), &mut scope).unwrap();
+        }
     }
 
     /// Fetches the current instruction/value in the currently executing frame
@@ -1436,6 +2101,16 @@ impl Vm {
         self.async_tasks.push_back(fun);
     }
 
+    /// Registers an object that holds weak references, so that they are processed by the GC.
+    pub(crate) fn add_weak_holder(&mut self, holder: ObjectId) {
+        self.weak_holders.push(holder);
+    }
+
+    /// Keeps an object alive until the current job finishes (the AddToKeptObjects abstract operation).
+    pub(crate) fn keep_alive_during_job(&mut self, id: ObjectId) {
+        self.kept_alive.push(id);
+    }
+
     pub fn has_async_tasks(&self) -> bool {
         !self.async_tasks.is_empty()
     }
@@ -1445,18 +2120,22 @@ impl Vm {
         debug!("process async tasks");
         debug!(async_task_count = %self.async_tasks.len());
 
+        // The job that queued these tasks has finished
+        self.kept_alive.clear();
+
         let mut scope = self.scope();
         while let Some(task) = scope.async_tasks.pop_front() {
             scope.add(task);
 
             debug!("process task {:?}", task);
-            if let Err(ex) = task.apply(This::default(), CallArgs::empty(), &mut scope) {
+            if let Err(ex) = scope.catch_exception(|scope| task.apply(This::default(), CallArgs::empty(), scope)) {
                 error!("uncaught async task exception");
                 if let Some(callback) = scope.params.unhandled_task_exception_callback() {
                     let ex = ex.root(&mut scope);
                     callback(&mut scope, ex, UncaughtExceptionSource::Task);
                 }
             }
+            scope.kept_alive.clear();
         }
 
         // We're removing the rejected promises from `self`, making them unreachable from the GC, so root them beforehand.
@@ -1564,11 +2243,25 @@ impl Vm {
         trace_roots.in_scope(|| self.trace_roots());
 
         // All reachable roots are marked.
+        debug!("process weak references");
+        let finalization_tasks = self.process_weak_holders();
+        self.canonical_bigints.retain(|_, bigint| self.alloc.is_marked(bigint.id()));
+
         debug!("rss before sweep: {}", self.alloc.rss());
         let sweep = span!(Level::TRACE, "gc sweep");
         sweep.in_scope(|| unsafe { self.alloc.sweep() });
         debug!("rss after sweep: {}", self.alloc.rss());
 
+        // The registries are marked, so they survived the sweep. Their cleanup jobs remove the collected cells
+        // only when they run, so that `unregister` can still remove them until then.
+        debug!(finalization_task_count = %finalization_tasks.len());
+        for registry in finalization_tasks {
+            let cleanup = self.statics.finalizationregistry_cleanup;
+            let bf = BoundFunction::new(self, cleanup, Some(Value::object(registry)), CallArgs::empty());
+            let bf = self.alloc.alloc_object(bf);
+            self.add_async_task(bf);
+        }
+
         debug!("sweep interner");
         self.interner.sweep();
 
@@ -1577,6 +2270,28 @@ impl Vm {
         debug!("new threshold: {}", self.gc_rss_threshold);
     }
 
+    /// Clears weak references to objects that were not marked and marks their cells in finalization registries
+    /// as collected.
+    /// Returns the finalization registries that need a cleanup job.
+    fn process_weak_holders(&mut self) -> Vec<ObjectId> {
+        let mut tasks = Vec::new();
+        let mut weak_holders = mem::take(&mut self.weak_holders);
+        weak_holders.retain(|&holder| self.alloc.is_marked(holder));
+
+        for holder in &weak_holders {
+            if let Some(weakref) = holder.extract::<WeakRef>(self) {
+                weakref.clear_if_unmarked(&self.alloc);
+            } else if let Some(registry) = holder.extract::<FinalizationRegistry>(self)
+                && registry.collect_unmarked(&self.alloc)
+            {
+                tasks.push(*holder);
+            }
+        }
+
+        self.weak_holders = weak_holders;
+        tasks
+    }
+
     fn trace_roots(&mut self) {
         let mut cx = TraceCtxt::new(&mut self.interner, &mut self.alloc);
 
@@ -1592,6 +2307,12 @@ impl Vm {
         self.global.trace(&mut cx);
         debug!("trace shadow roots");
         self.shadow_roots.trace(&mut cx);
+        debug!("trace kept alive objects");
+        self.kept_alive.trace(&mut cx);
+        debug!("trace template objects");
+        for (_, object) in self.template_objects.values() {
+            object.trace(&mut cx);
+        }
         if let Some(state) = self.params.state_raw() {
             debug!("trace state");
             state.trace(&mut cx);
@@ -1631,12 +2352,18 @@ impl Vm {
         self.builtins_pure
     }
 
+    /// Returns the canonical handle of a BigInt with the same value
+    pub(crate) fn canonical_bigint(&mut self, bigint: JsBigInt) -> JsBigInt {
+        let value = bigint.get(self).clone();
+        *self.canonical_bigints.entry(value).or_insert(bigint)
+    }
+
     pub(crate) fn impure_builtins(&mut self) {
         self.builtins_pure = false;
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum PromiseAction {
     Resolve,
     Reject,
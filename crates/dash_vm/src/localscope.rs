@@ -38,6 +38,7 @@ impl TryShadowRoot for Value {
         match self.unpack() {
             ValueKind::Object(id) => Some(ShadowRoot::Object(id)),
             ValueKind::External(ext) => Some(ShadowRoot::Object(ext.id())),
+            ValueKind::BigInt(bigint) => Some(ShadowRoot::Object(bigint.id())),
             ValueKind::Symbol(sym) => Some(ShadowRoot::Symbol(sym.sym())),
             ValueKind::String(str) => Some(ShadowRoot::Symbol(str.sym())),
             _ => None,
@@ -108,6 +109,11 @@ impl<'a> LocalScope<'a> {
         let arg = args.first().unwrap_or_undefined();
         let mut state = promise.state().borrow_mut();
 
+        if !matches!(*state, PromiseState::Pending { .. }) {
+            // A promise can only be settled once
+            return;
+        }
+
         let mut has_handler = false;
 
         if let PromiseState::Pending { resolve, reject } = &mut *state {
@@ -161,6 +167,20 @@ impl<'a> LocalScope<'a> {
         sym
     }
 
+    /// Returns the interned symbol for a symbol that may refer to a builder string
+    pub fn intern_symbol(&mut self, sym: Symbol) -> Symbol {
+        let sym = self.vm.interner.intern_symbol(sym);
+        self.vm.shadow_roots.push(ShadowRoot::Symbol(sym));
+        sym
+    }
+
+    /// Concatenates two strings without necessarily interning the result. See [`StringInterner::concat`].
+    pub fn concat(&mut self, left: Symbol, right: Symbol) -> Symbol {
+        let sym = self.vm.interner.concat(left, right);
+        self.vm.shadow_roots.push(ShadowRoot::Symbol(sym));
+        sym
+    }
+
     pub fn register<O: Object + 'static>(&mut self, obj: O) -> ObjectId {
         let id = self.vm.alloc.alloc_object(obj);
         self.vm.shadow_roots.push(ShadowRoot::Object(id));
@@ -177,6 +197,25 @@ impl<'a> LocalScope<'a> {
         id
     }
 
+    /// Calls `f` and restores the stack, frames and try blocks of the VM to their prior state if it throws.
+    ///
+    /// Unwinding leaves behind the stack values and frames of the code that threw, because the exception normally keeps propagating.
+    /// Natives that catch an exception and carry on (e.g. to reject a promise) must go through this,
+    /// as the leftover values would otherwise corrupt the operands of the caller.
+    pub fn catch_exception<R, E>(&mut self, f: impl FnOnce(&mut Self) -> Result<R, E>) -> Result<R, E> {
+        let sp = self.vm.stack.len();
+        let frame_count = self.vm.frames.len();
+        let try_block_count = self.vm.try_blocks.len();
+
+        let result = f(self);
+        if result.is_err() {
+            self.vm.stack.truncate(sp);
+            self.vm.frames.truncate(frame_count);
+            self.vm.try_blocks.truncate(try_block_count);
+        }
+        result
+    }
+
     pub fn mk_promise(&mut self) -> ObjectId {
         let promise = Promise::new(self);
         self.register(promise)
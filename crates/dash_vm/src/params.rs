@@ -13,6 +13,8 @@ use super::value::Value;
 
 pub type MathRandomCallback = fn(vm: &mut Vm) -> Result<f64, Unrooted>;
 pub type TimeMillisCallback = fn(vm: &mut Vm) -> Result<u64, Unrooted>;
+/// Returns the offset of the local timezone from UTC in milliseconds at the given UTC time (in milliseconds since the epoch)
+pub type TimezoneOffsetCallback = fn(vm: &mut Vm, utc_millis: i64) -> Result<i64, Unrooted>;
 pub type StaticImportCallback = fn(vm: &mut Vm, ty: StaticImportKind, path: JsString) -> Result<Unrooted, Unrooted>;
 pub type DynamicImportCallback = fn(vm: &mut Vm, val: Value) -> Result<Unrooted, Unrooted>;
 pub type DebuggerCallback = fn(vm: &mut Vm) -> Result<(), Value>;
@@ -36,6 +38,7 @@ impl<T: Any + Trace> State for T {
 pub struct VmParams {
     pub math_random_callback: Option<MathRandomCallback>,
     pub time_millis_callback: Option<TimeMillisCallback>,
+    pub timezone_offset_callback: Option<TimezoneOffsetCallback>,
     pub static_import_callback: Option<StaticImportCallback>,
     pub dynamic_import_callback: Option<DynamicImportCallback>,
     pub debugger_callback: Option<DebuggerCallback>,
@@ -89,6 +92,11 @@ impl VmParams {
         self
     }
 
+    pub fn set_timezone_offset_callback(mut self, callback: TimezoneOffsetCallback) -> Self {
+        self.timezone_offset_callback = Some(callback);
+        self
+    }
+
     pub fn set_debugger_callback(mut self, callback: DebuggerCallback) -> Self {
         self.debugger_callback = Some(callback);
         self
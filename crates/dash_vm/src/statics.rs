@@ -4,16 +4,19 @@ use crate::gc::{Allocator, ObjectId};
 use crate::js_std;
 use crate::value::PureBuiltin;
 use crate::value::error::{AggregateError, EvalError, RangeError, ReferenceError, SyntaxError, TypeError, URIError};
+use crate::value::finalizationregistry::FinalizationRegistry;
 use crate::value::function::{Function, FunctionKind};
 use crate::value::map::Map;
 use crate::value::regex::RegExp;
 use crate::value::set::Set;
 use crate::value::weakmap::WeakMap;
+use crate::value::weakref::WeakRef;
 use crate::value::weakset::WeakSet;
 use dash_middle::interner::{self, sym};
 
 use super::value::array::{Array, ArrayIterator};
 use super::value::arraybuffer::ArrayBuffer;
+use super::value::async_iterator::AsyncFromSyncIterator;
 use super::value::boxed::{
     Boolean as BoxedBoolean, Number as BoxedNumber, String as BoxedString, Symbol as BoxedSymbol,
 };
@@ -31,6 +34,7 @@ pub struct Statics {
     pub function_bind: ObjectId,
     pub function_call: ObjectId,
     pub function_to_string: ObjectId,
+    pub throw_type_error: ObjectId,
     pub is_nan: ObjectId,
     pub eval: ObjectId,
     pub is_finite: ObjectId,
@@ -87,10 +91,21 @@ pub struct Statics {
     pub object_entries: ObjectId,
     pub object_freeze: ObjectId,
     pub object_seal: ObjectId,
+    pub object_prevent_extensions: ObjectId,
+    pub object_is_extensible: ObjectId,
+    pub object_is_frozen: ObjectId,
+    pub object_is_sealed: ObjectId,
+    pub object_values: ObjectId,
+    pub object_from_entries: ObjectId,
+    pub object_is: ObjectId,
+    pub object_group_by: ObjectId,
+    pub object_has_own: ObjectId,
     pub object_get_prototype_of: ObjectId,
     pub object_set_prototype_of: ObjectId,
     pub object_is_prototype_of: ObjectId,
     pub object_property_is_enumerable: ObjectId,
+    pub object_proto_getter: ObjectId,
+    pub object_proto_setter: ObjectId,
     pub number_ctor: ObjectId,
     pub number_prototype: ObjectId,
     pub number_tostring: ObjectId,
@@ -100,10 +115,19 @@ pub struct Statics {
     pub number_is_safe_integer: ObjectId,
     pub number_is_integer: ObjectId,
     pub number_to_fixed: ObjectId,
+    pub number_to_precision: ObjectId,
+    pub number_to_exponential: ObjectId,
+    pub number_to_locale_string: ObjectId,
     pub boolean_ctor: ObjectId,
     pub boolean_tostring: ObjectId,
     pub boolean_prototype: ObjectId,
     pub boolean_valueof: ObjectId,
+    pub bigint_ctor: ObjectId,
+    pub bigint_prototype: ObjectId,
+    pub bigint_as_int_n: ObjectId,
+    pub bigint_as_uint_n: ObjectId,
+    pub bigint_tostring: ObjectId,
+    pub bigint_valueof: ObjectId,
     pub string_ctor: ObjectId,
     pub string_prototype: ObjectId,
     pub string_tostring: ObjectId,
@@ -121,6 +145,9 @@ pub struct Statics {
     pub string_replace: ObjectId,
     pub string_replace_all: ObjectId,
     pub string_split: ObjectId,
+    pub string_match: ObjectId,
+    pub string_match_all: ObjectId,
+    pub string_search: ObjectId,
     pub string_to_uppercase: ObjectId,
     pub string_to_lowercase: ObjectId,
     pub string_big: ObjectId,
@@ -138,10 +165,12 @@ pub struct Statics {
     pub string_trim_start: ObjectId,
     pub string_trim_end: ObjectId,
     pub string_from_char_code: ObjectId,
+    pub string_raw: ObjectId,
     pub string_substr: ObjectId,
     pub string_substring: ObjectId,
     pub string_slice: ObjectId,
     pub string_iterator: ObjectId,
+    pub string_locale_compare: ObjectId,
     pub array_ctor: ObjectId,
     pub array_tostring: ObjectId,
     pub array_prototype: ObjectId,
@@ -192,9 +221,30 @@ pub struct Statics {
     pub array_last_index_of: ObjectId,
     pub array_from: ObjectId,
     pub array_is_array: ObjectId,
+    pub array_of: ObjectId,
+    pub array_from_async: ObjectId,
+    pub array_reduce_right: ObjectId,
+    pub array_flat_map: ObjectId,
+    pub array_find_last: ObjectId,
+    pub array_find_last_index: ObjectId,
+    pub array_copy_within: ObjectId,
+    pub array_to_sorted: ObjectId,
+    pub array_to_reversed: ObjectId,
+    pub array_to_spliced: ObjectId,
+    pub array_with: ObjectId,
     pub generator_iterator_prototype: ObjectId,
     pub generator_iterator_next: ObjectId,
+    pub generator_iterator_return: ObjectId,
     pub generator_iterator_throw: ObjectId,
+    pub async_generator_prototype: ObjectId,
+    pub async_generator_next: ObjectId,
+    pub async_generator_return: ObjectId,
+    pub async_generator_throw: ObjectId,
+    pub self_async_iterator: ObjectId,
+    pub async_from_sync_iterator_prototype: ObjectId,
+    pub async_from_sync_iterator_next: ObjectId,
+    pub async_from_sync_iterator_return: ObjectId,
+    pub async_from_sync_iterator_result: ObjectId,
     pub error_ctor: ObjectId,
     pub error_prototype: ObjectId,
     pub error_to_string: ObjectId,
@@ -215,8 +265,29 @@ pub struct Statics {
     pub arraybuffer_ctor: ObjectId,
     pub arraybuffer_prototype: ObjectId,
     pub arraybuffer_byte_length: ObjectId,
+    pub arraybuffer_max_byte_length: ObjectId,
+    pub arraybuffer_resizable: ObjectId,
+    pub arraybuffer_detached: ObjectId,
+    pub arraybuffer_slice: ObjectId,
+    pub arraybuffer_resize: ObjectId,
+    pub arraybuffer_transfer: ObjectId,
+    pub arraybuffer_transfer_to_fixed_length: ObjectId,
+    pub arraybuffer_is_view: ObjectId,
+    pub sharedarraybuffer_ctor: ObjectId,
+    pub sharedarraybuffer_prototype: ObjectId,
+    pub sharedarraybuffer_byte_length: ObjectId,
+    pub sharedarraybuffer_max_byte_length: ObjectId,
+    pub sharedarraybuffer_growable: ObjectId,
+    pub sharedarraybuffer_grow: ObjectId,
+    pub sharedarraybuffer_slice: ObjectId,
+    pub typedarray_ctor: ObjectId,
+    pub typedarray_prototype: ObjectId,
+    pub typedarray_from: ObjectId,
+    pub typedarray_of: ObjectId,
     pub uint8array_ctor: ObjectId,
     pub uint8array_prototype: ObjectId,
+    pub uint8clampedarray_ctor: ObjectId,
+    pub uint8clampedarray_prototype: ObjectId,
     pub int8array_ctor: ObjectId,
     pub int8array_prototype: ObjectId,
     pub uint16array_ctor: ObjectId,
@@ -227,16 +298,89 @@ pub struct Statics {
     pub uint32array_prototype: ObjectId,
     pub int32array_ctor: ObjectId,
     pub int32array_prototype: ObjectId,
+    pub bigint64array_ctor: ObjectId,
+    pub bigint64array_prototype: ObjectId,
+    pub biguint64array_ctor: ObjectId,
+    pub biguint64array_prototype: ObjectId,
+    pub float16array_ctor: ObjectId,
+    pub float16array_prototype: ObjectId,
     pub float32array_ctor: ObjectId,
     pub float32array_prototype: ObjectId,
     pub float64array_ctor: ObjectId,
     pub float64array_prototype: ObjectId,
+    pub typedarray_at: ObjectId,
+    pub typedarray_copy_within: ObjectId,
+    pub typedarray_entries: ObjectId,
+    pub typedarray_every: ObjectId,
     pub typedarray_fill: ObjectId,
+    pub typedarray_filter: ObjectId,
+    pub typedarray_find: ObjectId,
+    pub typedarray_find_index: ObjectId,
+    pub typedarray_find_last: ObjectId,
+    pub typedarray_find_last_index: ObjectId,
+    pub typedarray_for_each: ObjectId,
+    pub typedarray_includes: ObjectId,
+    pub typedarray_index_of: ObjectId,
+    pub typedarray_join: ObjectId,
+    pub typedarray_keys: ObjectId,
+    pub typedarray_last_index_of: ObjectId,
+    pub typedarray_map: ObjectId,
+    pub typedarray_reduce: ObjectId,
+    pub typedarray_reduce_right: ObjectId,
+    pub typedarray_reverse: ObjectId,
+    pub typedarray_set: ObjectId,
+    pub typedarray_slice: ObjectId,
+    pub typedarray_some: ObjectId,
+    pub typedarray_sort: ObjectId,
+    pub typedarray_subarray: ObjectId,
+    pub typedarray_to_locale_string: ObjectId,
+    pub typedarray_to_reversed: ObjectId,
+    pub typedarray_to_sorted: ObjectId,
+    pub typedarray_values: ObjectId,
+    pub typedarray_with: ObjectId,
+    pub typedarray_buffer: ObjectId,
+    pub typedarray_byte_length: ObjectId,
+    pub typedarray_byte_offset: ObjectId,
+    pub typedarray_length: ObjectId,
+    pub dataview_ctor: ObjectId,
+    pub dataview_prototype: ObjectId,
+    pub dataview_get_int8: ObjectId,
+    pub dataview_set_int8: ObjectId,
+    pub dataview_get_uint8: ObjectId,
+    pub dataview_set_uint8: ObjectId,
+    pub dataview_get_int16: ObjectId,
+    pub dataview_set_int16: ObjectId,
+    pub dataview_get_uint16: ObjectId,
+    pub dataview_set_uint16: ObjectId,
+    pub dataview_get_int32: ObjectId,
+    pub dataview_set_int32: ObjectId,
+    pub dataview_get_uint32: ObjectId,
+    pub dataview_set_uint32: ObjectId,
+    pub dataview_get_float16: ObjectId,
+    pub dataview_set_float16: ObjectId,
+    pub dataview_get_float32: ObjectId,
+    pub dataview_set_float32: ObjectId,
+    pub dataview_get_float64: ObjectId,
+    pub dataview_set_float64: ObjectId,
+    pub dataview_get_bigint64: ObjectId,
+    pub dataview_set_bigint64: ObjectId,
+    pub dataview_get_biguint64: ObjectId,
+    pub dataview_set_biguint64: ObjectId,
+    pub dataview_buffer: ObjectId,
+    pub dataview_byte_length: ObjectId,
+    pub dataview_byte_offset: ObjectId,
     pub promise_ctor: ObjectId,
     pub promise_proto: ObjectId,
     pub promise_resolve: ObjectId,
     pub promise_reject: ObjectId,
     pub promise_then: ObjectId,
+    pub promise_catch: ObjectId,
+    pub promise_finally: ObjectId,
+    pub promise_all: ObjectId,
+    pub promise_all_settled: ObjectId,
+    pub promise_any: ObjectId,
+    pub promise_race: ObjectId,
+    pub promise_with_resolvers: ObjectId,
     pub set_constructor: ObjectId,
     pub set_prototype: ObjectId,
     pub set_add: ObjectId,
@@ -244,6 +388,11 @@ pub struct Statics {
     pub set_delete: ObjectId,
     pub set_clear: ObjectId,
     pub set_size: ObjectId,
+    pub set_values: ObjectId,
+    pub set_entries: ObjectId,
+    pub set_for_each: ObjectId,
+    pub set_iterator_prototype: ObjectId,
+    pub set_iterator_next: ObjectId,
     pub map_constructor: ObjectId,
     pub map_prototype: ObjectId,
     pub map_set: ObjectId,
@@ -252,6 +401,13 @@ pub struct Statics {
     pub map_delete: ObjectId,
     pub map_clear: ObjectId,
     pub map_size: ObjectId,
+    pub map_keys: ObjectId,
+    pub map_values: ObjectId,
+    pub map_entries: ObjectId,
+    pub map_for_each: ObjectId,
+    pub map_iterator_prototype: ObjectId,
+    pub map_iterator_next: ObjectId,
+    pub map_group_by: ObjectId,
     pub weakmap_constructor: ObjectId,
     pub weakmap_prototype: ObjectId,
     pub weakmap_set: ObjectId,
@@ -263,16 +419,94 @@ pub struct Statics {
     pub weakset_add: ObjectId,
     pub weakset_has: ObjectId,
     pub weakset_delete: ObjectId,
+    pub weakref_constructor: ObjectId,
+    pub weakref_prototype: ObjectId,
+    pub weakref_deref: ObjectId,
+    pub finalizationregistry_constructor: ObjectId,
+    pub finalizationregistry_prototype: ObjectId,
+    pub finalizationregistry_register: ObjectId,
+    pub finalizationregistry_unregister: ObjectId,
+    pub finalizationregistry_cleanup: ObjectId,
     pub regexp_ctor: ObjectId,
     pub regexp_prototype: ObjectId,
     pub regexp_test: ObjectId,
     pub regexp_exec: ObjectId,
+    pub regexp_to_string: ObjectId,
+    pub regexp_symbol_match: ObjectId,
+    pub regexp_symbol_match_all: ObjectId,
+    pub regexp_symbol_replace: ObjectId,
+    pub regexp_symbol_search: ObjectId,
+    pub regexp_symbol_split: ObjectId,
     pub date_ctor: ObjectId,
     pub date_prototype: ObjectId,
     pub date_get_time: ObjectId,
     pub date_now: ObjectId,
+    pub date_parse: ObjectId,
+    pub date_utc: ObjectId,
+    pub date_get_date: ObjectId,
+    pub date_get_day: ObjectId,
+    pub date_get_full_year: ObjectId,
+    pub date_get_hours: ObjectId,
+    pub date_get_milliseconds: ObjectId,
+    pub date_get_minutes: ObjectId,
+    pub date_get_month: ObjectId,
+    pub date_get_seconds: ObjectId,
+    pub date_get_timezone_offset: ObjectId,
+    pub date_get_utc_date: ObjectId,
+    pub date_get_utc_day: ObjectId,
+    pub date_get_utc_full_year: ObjectId,
+    pub date_get_utc_hours: ObjectId,
+    pub date_get_utc_milliseconds: ObjectId,
+    pub date_get_utc_minutes: ObjectId,
+    pub date_get_utc_month: ObjectId,
+    pub date_get_utc_seconds: ObjectId,
+    pub date_set_date: ObjectId,
+    pub date_set_full_year: ObjectId,
+    pub date_set_hours: ObjectId,
+    pub date_set_milliseconds: ObjectId,
+    pub date_set_minutes: ObjectId,
+    pub date_set_month: ObjectId,
+    pub date_set_seconds: ObjectId,
+    pub date_set_time: ObjectId,
+    pub date_set_utc_date: ObjectId,
+    pub date_set_utc_full_year: ObjectId,
+    pub date_set_utc_hours: ObjectId,
+    pub date_set_utc_milliseconds: ObjectId,
+    pub date_set_utc_minutes: ObjectId,
+    pub date_set_utc_month: ObjectId,
+    pub date_set_utc_seconds: ObjectId,
+    pub date_to_iso_string: ObjectId,
+    pub date_to_json: ObjectId,
+    pub date_to_string: ObjectId,
+    pub date_to_utc_string: ObjectId,
+    pub date_to_date_string: ObjectId,
+    pub date_to_time_string: ObjectId,
+    pub date_to_locale_string: ObjectId,
+    pub date_to_locale_date_string: ObjectId,
+    pub date_to_locale_time_string: ObjectId,
+    pub date_value_of: ObjectId,
+    pub date_to_primitive: ObjectId,
     pub json_ctor: ObjectId,
     pub json_parse: ObjectId,
+    pub json_stringify: ObjectId,
+    pub proxy_ctor: ObjectId,
+    pub proxy_revocable: ObjectId,
+    pub reflect: ObjectId,
+    pub reflect_apply: ObjectId,
+    pub reflect_construct: ObjectId,
+    pub reflect_define_property: ObjectId,
+    pub reflect_delete_property: ObjectId,
+    pub reflect_get: ObjectId,
+    pub reflect_get_own_property_descriptor: ObjectId,
+    pub reflect_get_prototype_of: ObjectId,
+    pub reflect_has: ObjectId,
+    pub reflect_own_keys: ObjectId,
+    pub reflect_is_extensible: ObjectId,
+    pub reflect_prevent_extensions: ObjectId,
+    pub reflect_set: ObjectId,
+    pub reflect_set_prototype_of: ObjectId,
+    #[cfg(feature = "intl")]
+    pub intl: IntlStatics,
 }
 
 fn builtin_object<O: Object + 'static>(gc: &mut Allocator, obj: O) -> ObjectId {
@@ -302,6 +536,7 @@ impl Statics {
             function_bind: function(gc, sym::bind, js_std::function::bind, false),
             function_call: function(gc, sym::call, js_std::function::call, false),
             function_to_string: function(gc, sym::toString, js_std::function::to_string, false),
+            throw_type_error: function(gc, sym::empty, js_std::function::throw_type_error, false),
             console: empty_object(gc),
             console_log: function(gc, sym::log, js_std::global::log_stdout, false),
             console_error: function(gc, sym::error, js_std::global::log_stderr, false),
@@ -344,6 +579,8 @@ impl Statics {
             object_get_prototype_of: function(gc, sym::getPrototypeOf, js_std::object::get_prototype_of, false),
             object_set_prototype_of: function(gc, sym::setPrototypeOf, js_std::object::set_prototype_of, false),
             object_is_prototype_of: function(gc, sym::isPrototypeOf, js_std::object::is_prototype_of, false),
+            object_proto_getter: function(gc, sym::__proto__, js_std::object::proto_getter, false),
+            object_proto_setter: function(gc, sym::__proto__, js_std::object::proto_setter, false),
             object_property_is_enumerable: function(
                 gc,
                 sym::propertyIsEnumerable,
@@ -352,6 +589,20 @@ impl Statics {
             ),
             object_freeze: function(gc, sym::freeze, js_std::object::freeze, false),
             object_seal: function(gc, sym::seal, js_std::object::seal, false),
+            object_prevent_extensions: function(
+                gc,
+                sym::preventExtensions,
+                js_std::object::prevent_extensions,
+                false,
+            ),
+            object_is_extensible: function(gc, sym::isExtensible, js_std::object::is_extensible, false),
+            object_is_frozen: function(gc, sym::isFrozen, js_std::object::is_frozen, false),
+            object_is_sealed: function(gc, sym::isSealed, js_std::object::is_sealed, false),
+            object_values: function(gc, sym::values, js_std::object::values, false),
+            object_from_entries: function(gc, sym::fromEntries, js_std::object::from_entries, false),
+            object_is: function(gc, sym::is, js_std::object::is, false),
+            object_group_by: function(gc, sym::groupBy, js_std::object::group_by_object, false),
+            object_has_own: function(gc, sym::hasOwn, js_std::object::has_own, false),
             number_ctor: function(gc, sym::Number, js_std::number::constructor, true),
             number_prototype: builtin_object(gc, BoxedNumber::with_obj(0.0, OrdObject::null())),
             number_tostring: function(gc, sym::toString, js_std::number::to_string, false),
@@ -359,6 +610,12 @@ impl Statics {
             boolean_ctor: function(gc, sym::Boolean, js_std::boolean::constructor, true),
             boolean_tostring: function(gc, sym::toString, js_std::boolean::to_string, false),
             boolean_prototype: builtin_object(gc, BoxedBoolean::with_obj(false, OrdObject::null())),
+            bigint_ctor: function(gc, sym::BigInt, js_std::bigint::constructor, false),
+            bigint_prototype: empty_object(gc),
+            bigint_as_int_n: function(gc, sym::asIntN, js_std::bigint::as_int_n, false),
+            bigint_as_uint_n: function(gc, sym::asUintN, js_std::bigint::as_uint_n, false),
+            bigint_tostring: function(gc, sym::toString, js_std::bigint::to_string, false),
+            bigint_valueof: function(gc, sym::valueOf, js_std::bigint::value_of, false),
             string_ctor: function(gc, sym::String, js_std::string::constructor, true),
             string_prototype: builtin_object(gc, BoxedString::with_obj(sym::empty.into(), OrdObject::null())),
             is_nan: function(gc, sym::isNaN, js_std::global::is_nan, false),
@@ -401,6 +658,9 @@ impl Statics {
             number_is_safe_integer: function(gc, sym::isSafeInteger, js_std::number::is_safe_integer, false),
             number_is_integer: function(gc, sym::isInteger, js_std::number::is_integer, false),
             number_to_fixed: function(gc, sym::toFixed, js_std::number::to_fixed, false),
+            number_to_precision: function(gc, sym::toPrecision, js_std::number::to_precision, false),
+            number_to_exponential: function(gc, sym::toExponential, js_std::number::to_exponential, false),
+            number_to_locale_string: function(gc, sym::toLocaleString, js_std::number::to_locale_string, false),
             boolean_valueof: function(gc, sym::valueOf, js_std::boolean::value_of, false),
             string_tostring: function(gc, sym::toString, js_std::string::to_string, false),
             string_char_at: function(gc, sym::charAt, js_std::string::char_at, false),
@@ -417,6 +677,9 @@ impl Statics {
             string_replace: function(gc, sym::replace, js_std::string::replace, false),
             string_replace_all: function(gc, sym::replaceAll, js_std::string::replace_all, false),
             string_split: function(gc, sym::split, js_std::string::split, false),
+            string_match: function(gc, sym::match_, js_std::string::match_, false),
+            string_match_all: function(gc, sym::matchAll, js_std::string::match_all, false),
+            string_search: function(gc, sym::search, js_std::string::search, false),
             string_to_uppercase: function(gc, sym::toUpperCase, js_std::string::to_uppercase, false),
             string_to_lowercase: function(gc, sym::toLowerCase, js_std::string::to_lowercase, false),
             string_big: function(gc, sym::big, js_std::string::big, false),
@@ -434,10 +697,12 @@ impl Statics {
             string_trim_start: function(gc, sym::trimStart, js_std::string::trim_start, false),
             string_trim_end: function(gc, sym::trimEnd, js_std::string::trim_end, false),
             string_from_char_code: function(gc, sym::fromCharCode, js_std::string::from_char_code, false),
+            string_raw: function(gc, sym::raw, js_std::string::raw, false),
             string_substr: function(gc, sym::substr, js_std::string::substr, false),
             string_substring: function(gc, sym::substring, js_std::string::substring, false),
             string_slice: function(gc, sym::slice, js_std::string::slice, false),
             string_iterator: function(gc, sym::iterator, js_std::string::iterator, false),
+            string_locale_compare: function(gc, sym::localeCompare, js_std::string::locale_compare, false),
             array_ctor: function(gc, sym::Array, js_std::array::constructor, true),
             array_tostring: function(gc, sym::toString, js_std::array::to_string, false),
             array_prototype: builtin_object(gc, Array::with_obj(OrdObject::null())),
@@ -491,12 +756,33 @@ impl Statics {
             array_last_index_of: function(gc, sym::lastIndexOf, js_std::array::last_index_of, false),
             array_from: function(gc, sym::from, js_std::array::from, false),
             array_is_array: function(gc, sym::isArray, js_std::array::is_array, false),
+            array_of: function(gc, sym::of, js_std::array::of, false),
+            array_from_async: function(gc, sym::fromAsync, js_std::array::from_async, false),
+            array_reduce_right: function(gc, sym::reduceRight, js_std::array::reduce_right, false),
+            array_flat_map: function(gc, sym::flatMap, js_std::array::flat_map, false),
+            array_find_last: function(gc, sym::findLast, js_std::array::find_last, false),
+            array_find_last_index: function(gc, sym::findLastIndex, js_std::array::find_last_index, false),
+            array_copy_within: function(gc, sym::copyWithin, js_std::array::copy_within, false),
+            array_to_sorted: function(gc, sym::toSorted, js_std::array::to_sorted, false),
+            array_to_reversed: function(gc, sym::toReversed, js_std::array::to_reversed, false),
+            array_to_spliced: function(gc, sym::toSpliced, js_std::array::to_spliced, false),
+            array_with: function(gc, sym::with, js_std::array::with, false),
             generator_iterator_prototype: {
                 let obj: ObjectId = empty_object(gc);
                 builtin_object(gc, GeneratorIterator::empty(obj))
             },
             generator_iterator_next: function(gc, sym::next, js_std::generator::next, false),
+            generator_iterator_return: function(gc, sym::return_, js_std::generator::return_, false),
             generator_iterator_throw: function(gc, sym::throw, js_std::generator::throw, false),
+            async_generator_prototype: empty_object(gc),
+            async_generator_next: function(gc, sym::next, js_std::async_generator::next, false),
+            async_generator_return: function(gc, sym::return_, js_std::async_generator::return_, false),
+            async_generator_throw: function(gc, sym::throw, js_std::async_generator::throw, false),
+            self_async_iterator: function(gc, sym::asyncIterator, js_std::identity_this, false),
+            async_from_sync_iterator_prototype: builtin_object(gc, AsyncFromSyncIterator::empty()),
+            async_from_sync_iterator_next: function(gc, sym::next, js_std::async_iterator::next, false),
+            async_from_sync_iterator_return: function(gc, sym::return_, js_std::async_iterator::return_, false),
+            async_from_sync_iterator_result: function(gc, sym::empty, js_std::async_iterator::from_sync_result, false),
             error_ctor: function(gc, sym::Error, js_std::error::error_constructor, true),
             error_prototype: builtin_object(gc, Error::empty()),
             error_to_string: function(gc, sym::toString, js_std::error::to_string, false),
@@ -527,8 +813,39 @@ impl Statics {
             arraybuffer_ctor: function(gc, sym::ArrayBuffer, js_std::arraybuffer::constructor, true),
             arraybuffer_prototype: builtin_object(gc, ArrayBuffer::empty()),
             arraybuffer_byte_length: function(gc, sym::byteLength, js_std::arraybuffer::byte_length, false),
+            arraybuffer_max_byte_length: function(gc, sym::maxByteLength, js_std::arraybuffer::max_byte_length, false),
+            arraybuffer_resizable: function(gc, sym::resizable, js_std::arraybuffer::resizable, false),
+            arraybuffer_detached: function(gc, sym::detached, js_std::arraybuffer::detached, false),
+            arraybuffer_slice: function(gc, sym::slice, js_std::arraybuffer::slice, false),
+            arraybuffer_resize: function(gc, sym::resize, js_std::arraybuffer::resize, false),
+            arraybuffer_transfer: function(gc, sym::transfer, js_std::arraybuffer::transfer, false),
+            arraybuffer_transfer_to_fixed_length: function(
+                gc,
+                sym::transferToFixedLength,
+                js_std::arraybuffer::transfer_to_fixed_length,
+                false,
+            ),
+            arraybuffer_is_view: function(gc, sym::isView, js_std::arraybuffer::is_view, false),
+            sharedarraybuffer_ctor: function(gc, sym::SharedArrayBuffer, js_std::arraybuffer::shared::constructor, true),
+            sharedarraybuffer_prototype: empty_object(gc),
+            sharedarraybuffer_byte_length: function(gc, sym::byteLength, js_std::arraybuffer::shared::byte_length, false),
+            sharedarraybuffer_max_byte_length: function(
+                gc,
+                sym::maxByteLength,
+                js_std::arraybuffer::shared::max_byte_length,
+                false,
+            ),
+            sharedarraybuffer_growable: function(gc, sym::growable, js_std::arraybuffer::shared::growable, false),
+            sharedarraybuffer_grow: function(gc, sym::grow, js_std::arraybuffer::shared::grow, false),
+            sharedarraybuffer_slice: function(gc, sym::slice, js_std::arraybuffer::shared::slice, false),
+            typedarray_ctor: function(gc, sym::TypedArray, js_std::typedarray::constructor, true),
+            typedarray_prototype: empty_object(gc),
+            typedarray_from: function(gc, sym::from, js_std::typedarray::from, false),
+            typedarray_of: function(gc, sym::of, js_std::typedarray::of, false),
             uint8array_ctor: function(gc, sym::Uint8Array, js_std::typedarray::u8array::constructor, true),
             uint8array_prototype: empty_object(gc),
+            uint8clampedarray_ctor: function(gc, sym::Uint8ClampedArray, js_std::typedarray::u8clampedarray::constructor, true),
+            uint8clampedarray_prototype: empty_object(gc),
             int8array_ctor: function(gc, sym::Int8Array, js_std::typedarray::i8array::constructor, true),
             int8array_prototype: empty_object(gc),
             uint16array_ctor: function(gc, sym::Uint16Array, js_std::typedarray::u16array::constructor, true),
@@ -539,16 +856,89 @@ impl Statics {
             uint32array_prototype: empty_object(gc),
             int32array_ctor: function(gc, sym::Int32Array, js_std::typedarray::i32array::constructor, true),
             int32array_prototype: empty_object(gc),
+            bigint64array_ctor: function(gc, sym::BigInt64Array, js_std::typedarray::bigi64array::constructor, true),
+            bigint64array_prototype: empty_object(gc),
+            biguint64array_ctor: function(gc, sym::BigUint64Array, js_std::typedarray::bigu64array::constructor, true),
+            biguint64array_prototype: empty_object(gc),
+            float16array_ctor: function(gc, sym::Float16Array, js_std::typedarray::f16array::constructor, true),
+            float16array_prototype: empty_object(gc),
             float32array_ctor: function(gc, sym::Float32Array, js_std::typedarray::f32array::constructor, true),
             float32array_prototype: empty_object(gc),
             float64array_ctor: function(gc, sym::Float64Array, js_std::typedarray::f64array::constructor, true),
             float64array_prototype: empty_object(gc),
+            typedarray_at: function(gc, sym::at, js_std::typedarray::at, false),
+            typedarray_copy_within: function(gc, sym::copyWithin, js_std::typedarray::copy_within, false),
+            typedarray_entries: function(gc, sym::entries, js_std::typedarray::entries, false),
+            typedarray_every: function(gc, sym::every, js_std::typedarray::every, false),
             typedarray_fill: function(gc, sym::fill, js_std::typedarray::fill, false),
+            typedarray_filter: function(gc, sym::filter, js_std::typedarray::filter, false),
+            typedarray_find: function(gc, sym::find, js_std::typedarray::find, false),
+            typedarray_find_index: function(gc, sym::findIndex, js_std::typedarray::find_index, false),
+            typedarray_find_last: function(gc, sym::findLast, js_std::typedarray::find_last, false),
+            typedarray_find_last_index: function(gc, sym::findLastIndex, js_std::typedarray::find_last_index, false),
+            typedarray_for_each: function(gc, sym::forEach, js_std::typedarray::for_each, false),
+            typedarray_includes: function(gc, sym::includes, js_std::typedarray::includes, false),
+            typedarray_index_of: function(gc, sym::indexOf, js_std::typedarray::index_of, false),
+            typedarray_join: function(gc, sym::join, js_std::typedarray::join, false),
+            typedarray_keys: function(gc, sym::keys, js_std::typedarray::keys, false),
+            typedarray_last_index_of: function(gc, sym::lastIndexOf, js_std::typedarray::last_index_of, false),
+            typedarray_map: function(gc, sym::map, js_std::typedarray::map, false),
+            typedarray_reduce: function(gc, sym::reduce, js_std::typedarray::reduce, false),
+            typedarray_reduce_right: function(gc, sym::reduceRight, js_std::typedarray::reduce_right, false),
+            typedarray_reverse: function(gc, sym::reverse, js_std::typedarray::reverse, false),
+            typedarray_set: function(gc, sym::set, js_std::typedarray::set, false),
+            typedarray_slice: function(gc, sym::slice, js_std::typedarray::slice, false),
+            typedarray_some: function(gc, sym::some, js_std::typedarray::some, false),
+            typedarray_sort: function(gc, sym::sort, js_std::typedarray::sort, false),
+            typedarray_subarray: function(gc, sym::subarray, js_std::typedarray::subarray, false),
+            typedarray_to_locale_string: function(gc, sym::toLocaleString, js_std::typedarray::to_locale_string, false),
+            typedarray_to_reversed: function(gc, sym::toReversed, js_std::typedarray::to_reversed, false),
+            typedarray_to_sorted: function(gc, sym::toSorted, js_std::typedarray::to_sorted, false),
+            typedarray_values: function(gc, sym::values, js_std::typedarray::values, false),
+            typedarray_with: function(gc, sym::with, js_std::typedarray::with, false),
+            typedarray_buffer: function(gc, sym::buffer, js_std::typedarray::buffer, false),
+            typedarray_byte_length: function(gc, sym::byteLength, js_std::typedarray::byte_length, false),
+            typedarray_byte_offset: function(gc, sym::byteOffset, js_std::typedarray::byte_offset, false),
+            typedarray_length: function(gc, sym::length, js_std::typedarray::length, false),
+            dataview_ctor: function(gc, sym::DataView, js_std::dataview::constructor, true),
+            dataview_prototype: empty_object(gc),
+            dataview_get_int8: function(gc, sym::getInt8, js_std::dataview::get_int8, false),
+            dataview_set_int8: function(gc, sym::setInt8, js_std::dataview::set_int8, false),
+            dataview_get_uint8: function(gc, sym::getUint8, js_std::dataview::get_uint8, false),
+            dataview_set_uint8: function(gc, sym::setUint8, js_std::dataview::set_uint8, false),
+            dataview_get_int16: function(gc, sym::getInt16, js_std::dataview::get_int16, false),
+            dataview_set_int16: function(gc, sym::setInt16, js_std::dataview::set_int16, false),
+            dataview_get_uint16: function(gc, sym::getUint16, js_std::dataview::get_uint16, false),
+            dataview_set_uint16: function(gc, sym::setUint16, js_std::dataview::set_uint16, false),
+            dataview_get_int32: function(gc, sym::getInt32, js_std::dataview::get_int32, false),
+            dataview_set_int32: function(gc, sym::setInt32, js_std::dataview::set_int32, false),
+            dataview_get_uint32: function(gc, sym::getUint32, js_std::dataview::get_uint32, false),
+            dataview_set_uint32: function(gc, sym::setUint32, js_std::dataview::set_uint32, false),
+            dataview_get_float16: function(gc, sym::getFloat16, js_std::dataview::get_float16, false),
+            dataview_set_float16: function(gc, sym::setFloat16, js_std::dataview::set_float16, false),
+            dataview_get_float32: function(gc, sym::getFloat32, js_std::dataview::get_float32, false),
+            dataview_set_float32: function(gc, sym::setFloat32, js_std::dataview::set_float32, false),
+            dataview_get_float64: function(gc, sym::getFloat64, js_std::dataview::get_float64, false),
+            dataview_set_float64: function(gc, sym::setFloat64, js_std::dataview::set_float64, false),
+            dataview_get_bigint64: function(gc, sym::getBigInt64, js_std::dataview::get_bigint64, false),
+            dataview_set_bigint64: function(gc, sym::setBigInt64, js_std::dataview::set_bigint64, false),
+            dataview_get_biguint64: function(gc, sym::getBigUint64, js_std::dataview::get_biguint64, false),
+            dataview_set_biguint64: function(gc, sym::setBigUint64, js_std::dataview::set_biguint64, false),
+            dataview_buffer: function(gc, sym::buffer, js_std::dataview::buffer, false),
+            dataview_byte_length: function(gc, sym::byteLength, js_std::dataview::byte_length, false),
+            dataview_byte_offset: function(gc, sym::byteOffset, js_std::dataview::byte_offset, false),
             promise_ctor: function(gc, sym::Promise, js_std::promise::constructor, true),
             promise_proto: empty_object(gc),
             promise_resolve: function(gc, sym::resolve, js_std::promise::resolve, false),
             promise_reject: function(gc, sym::reject, js_std::promise::reject, false),
             promise_then: function(gc, sym::then, js_std::promise::then, false),
+            promise_catch: function(gc, sym::catch, js_std::promise::catch, false),
+            promise_finally: function(gc, sym::finally, js_std::promise::finally, false),
+            promise_all: function(gc, sym::all, js_std::promise::all, false),
+            promise_all_settled: function(gc, sym::allSettled, js_std::promise::all_settled, false),
+            promise_any: function(gc, sym::any, js_std::promise::any, false),
+            promise_race: function(gc, sym::race, js_std::promise::race, false),
+            promise_with_resolvers: function(gc, sym::withResolvers, js_std::promise::with_resolvers, false),
             set_constructor: function(gc, sym::Set, js_std::set::constructor, true),
             set_add: function(gc, sym::add, js_std::set::add, false),
             set_has: function(gc, sym::has, js_std::set::has, false),
@@ -556,6 +946,11 @@ impl Statics {
             set_prototype: builtin_object(gc, Set::with_obj(OrdObject::null())),
             set_clear: function(gc, sym::clear, js_std::set::clear, false),
             set_size: function(gc, sym::size, js_std::set::size, false),
+            set_values: function(gc, sym::values, js_std::set::values, false),
+            set_entries: function(gc, sym::entries, js_std::set::entries, false),
+            set_for_each: function(gc, sym::forEach, js_std::set::for_each, false),
+            set_iterator_prototype: empty_object(gc),
+            set_iterator_next: function(gc, sym::next, js_std::set::iterator_next, false),
             map_constructor: function(gc, sym::Map, js_std::map::constructor, true),
             map_set: function(gc, sym::set, js_std::map::set, false),
             map_get: function(gc, sym::get, js_std::map::get, false),
@@ -564,16 +959,85 @@ impl Statics {
             map_prototype: builtin_object(gc, Map::with_obj(OrdObject::null())),
             map_clear: function(gc, sym::clear, js_std::map::clear, false),
             map_size: function(gc, sym::size, js_std::map::size, false),
+            map_keys: function(gc, sym::keys, js_std::map::keys, false),
+            map_values: function(gc, sym::values, js_std::map::values, false),
+            map_entries: function(gc, sym::entries, js_std::map::entries, false),
+            map_for_each: function(gc, sym::forEach, js_std::map::for_each, false),
+            map_iterator_prototype: empty_object(gc),
+            map_iterator_next: function(gc, sym::next, js_std::map::iterator_next, false),
+            map_group_by: function(gc, sym::groupBy, js_std::map::group_by, false),
             regexp_ctor: function(gc, sym::RegExp, js_std::regex::constructor, true),
             regexp_prototype: builtin_object(gc, RegExp::empty()),
             regexp_test: function(gc, sym::test, js_std::regex::test, false),
             regexp_exec: function(gc, sym::exec, js_std::regex::exec, false),
+            regexp_to_string: function(gc, sym::toString, js_std::regex::to_string, false),
+            regexp_symbol_match: function(gc, sym::match_, js_std::regex::symbol_match, false),
+            regexp_symbol_match_all: function(gc, sym::matchAll, js_std::regex::symbol_match_all, false),
+            regexp_symbol_replace: function(gc, sym::replace, js_std::regex::symbol_replace, false),
+            regexp_symbol_search: function(gc, sym::search, js_std::regex::symbol_search, false),
+            regexp_symbol_split: function(gc, sym::split, js_std::regex::symbol_split, false),
             date_ctor: function(gc, sym::Date, js_std::date::constructor, true),
             date_prototype: builtin_object(gc, OrdObject::null()),
             date_now: function(gc, sym::now, js_std::date::now, false),
             date_get_time: function(gc, sym::getTime, js_std::date::get_time, false),
+            date_parse: function(gc, sym::parse, js_std::date::parse, false),
+            date_utc: function(gc, sym::UTC, js_std::date::utc, false),
+            date_get_date: function(gc, sym::getDate, js_std::date::get_date, false),
+            date_get_day: function(gc, sym::getDay, js_std::date::get_day, false),
+            date_get_full_year: function(gc, sym::getFullYear, js_std::date::get_full_year, false),
+            date_get_hours: function(gc, sym::getHours, js_std::date::get_hours, false),
+            date_get_milliseconds: function(gc, sym::getMilliseconds, js_std::date::get_milliseconds, false),
+            date_get_minutes: function(gc, sym::getMinutes, js_std::date::get_minutes, false),
+            date_get_month: function(gc, sym::getMonth, js_std::date::get_month, false),
+            date_get_seconds: function(gc, sym::getSeconds, js_std::date::get_seconds, false),
+            date_get_timezone_offset: function(gc, sym::getTimezoneOffset, js_std::date::get_timezone_offset, false),
+            date_get_utc_date: function(gc, sym::getUTCDate, js_std::date::get_utc_date, false),
+            date_get_utc_day: function(gc, sym::getUTCDay, js_std::date::get_utc_day, false),
+            date_get_utc_full_year: function(gc, sym::getUTCFullYear, js_std::date::get_utc_full_year, false),
+            date_get_utc_hours: function(gc, sym::getUTCHours, js_std::date::get_utc_hours, false),
+            date_get_utc_milliseconds: function(gc, sym::getUTCMilliseconds, js_std::date::get_utc_milliseconds, false),
+            date_get_utc_minutes: function(gc, sym::getUTCMinutes, js_std::date::get_utc_minutes, false),
+            date_get_utc_month: function(gc, sym::getUTCMonth, js_std::date::get_utc_month, false),
+            date_get_utc_seconds: function(gc, sym::getUTCSeconds, js_std::date::get_utc_seconds, false),
+            date_set_date: function(gc, sym::setDate, js_std::date::set_date, false),
+            date_set_full_year: function(gc, sym::setFullYear, js_std::date::set_full_year, false),
+            date_set_hours: function(gc, sym::setHours, js_std::date::set_hours, false),
+            date_set_milliseconds: function(gc, sym::setMilliseconds, js_std::date::set_milliseconds, false),
+            date_set_minutes: function(gc, sym::setMinutes, js_std::date::set_minutes, false),
+            date_set_month: function(gc, sym::setMonth, js_std::date::set_month, false),
+            date_set_seconds: function(gc, sym::setSeconds, js_std::date::set_seconds, false),
+            date_set_time: function(gc, sym::setTime, js_std::date::set_time, false),
+            date_set_utc_date: function(gc, sym::setUTCDate, js_std::date::set_utc_date, false),
+            date_set_utc_full_year: function(gc, sym::setUTCFullYear, js_std::date::set_utc_full_year, false),
+            date_set_utc_hours: function(gc, sym::setUTCHours, js_std::date::set_utc_hours, false),
+            date_set_utc_milliseconds: function(gc, sym::setUTCMilliseconds, js_std::date::set_utc_milliseconds, false),
+            date_set_utc_minutes: function(gc, sym::setUTCMinutes, js_std::date::set_utc_minutes, false),
+            date_set_utc_month: function(gc, sym::setUTCMonth, js_std::date::set_utc_month, false),
+            date_set_utc_seconds: function(gc, sym::setUTCSeconds, js_std::date::set_utc_seconds, false),
+            date_to_iso_string: function(gc, sym::toISOString, js_std::date::to_iso_string, false),
+            date_to_json: function(gc, sym::toJSON, js_std::date::to_json, false),
+            date_to_string: function(gc, sym::toString, js_std::date::to_string, false),
+            date_to_utc_string: function(gc, sym::toUTCString, js_std::date::to_utc_string, false),
+            date_to_date_string: function(gc, sym::toDateString, js_std::date::to_date_string, false),
+            date_to_time_string: function(gc, sym::toTimeString, js_std::date::to_time_string, false),
+            date_to_locale_string: function(gc, sym::toLocaleString, js_std::date::to_locale_string, false),
+            date_to_locale_date_string: function(
+                gc,
+                sym::toLocaleDateString,
+                js_std::date::to_locale_date_string,
+                false,
+            ),
+            date_to_locale_time_string: function(
+                gc,
+                sym::toLocaleTimeString,
+                js_std::date::to_locale_time_string,
+                false,
+            ),
+            date_value_of: function(gc, sym::valueOf, js_std::date::value_of, false),
+            date_to_primitive: function(gc, sym::toPrimitive, js_std::date::to_primitive, false),
             json_ctor: function(gc, sym::JSON, js_std::json::constructor, true),
             json_parse: function(gc, sym::parse, js_std::json::parse, false),
+            json_stringify: function(gc, sym::stringify, js_std::json::stringify, false),
             weakmap_constructor: function(gc, sym::WeakMap, js_std::weakmap::constructor, true),
             weakmap_prototype: builtin_object(gc, WeakMap::null()),
             weakmap_set: function(gc, sym::set, js_std::weakmap::set, false),
@@ -585,6 +1049,137 @@ impl Statics {
             weakset_add: function(gc, sym::add, js_std::weakset::add, false),
             weakset_has: function(gc, sym::has, js_std::weakset::has, false),
             weakset_delete: function(gc, sym::delete, js_std::weakset::delete, false),
+            weakref_constructor: function(gc, sym::WeakRef, js_std::weakref::constructor, true),
+            weakref_prototype: builtin_object(gc, WeakRef::null()),
+            weakref_deref: function(gc, sym::deref, js_std::weakref::deref, false),
+            finalizationregistry_constructor: function(
+                gc,
+                sym::FinalizationRegistry,
+                js_std::finalizationregistry::constructor,
+                true,
+            ),
+            finalizationregistry_prototype: builtin_object(gc, FinalizationRegistry::null()),
+            finalizationregistry_register: function(gc, sym::register, js_std::finalizationregistry::register, false),
+            finalizationregistry_unregister: function(
+                gc,
+                sym::unregister,
+                js_std::finalizationregistry::unregister,
+                false,
+            ),
+            finalizationregistry_cleanup: function(gc, sym::empty, js_std::finalizationregistry::cleanup, false),
+            proxy_ctor: function(gc, sym::Proxy, js_std::proxy::constructor, true),
+            proxy_revocable: function(gc, sym::revocable, js_std::proxy::revocable, false),
+            reflect: empty_object(gc),
+            reflect_apply: function(gc, sym::apply, js_std::reflect::apply, false),
+            reflect_construct: function(gc, sym::construct, js_std::reflect::construct, false),
+            reflect_define_property: function(gc, sym::defineProperty, js_std::reflect::define_property, false),
+            reflect_delete_property: function(gc, sym::deleteProperty, js_std::reflect::delete_property, false),
+            reflect_get: function(gc, sym::get, js_std::reflect::get, false),
+            reflect_get_own_property_descriptor: function(
+                gc,
+                sym::getOwnPropertyDescriptor,
+                js_std::reflect::get_own_property_descriptor,
+                false,
+            ),
+            reflect_get_prototype_of: function(gc, sym::getPrototypeOf, js_std::reflect::get_prototype_of, false),
+            reflect_has: function(gc, sym::has, js_std::reflect::has, false),
+            reflect_own_keys: function(gc, sym::ownKeys, js_std::reflect::own_keys, false),
+            reflect_is_extensible: function(gc, sym::isExtensible, js_std::reflect::is_extensible, false),
+            reflect_prevent_extensions: function(
+                gc,
+                sym::preventExtensions,
+                js_std::reflect::prevent_extensions,
+                false,
+            ),
+            reflect_set: function(gc, sym::set, js_std::reflect::set, false),
+            reflect_set_prototype_of: function(gc, sym::setPrototypeOf, js_std::reflect::set_prototype_of, false),
+            #[cfg(feature = "intl")]
+            intl: IntlStatics::new(gc),
+        }
+    }
+}
+
+/// The builtins of the `Intl` namespace, which only exist if the `intl` feature is enabled
+#[cfg(feature = "intl")]
+#[derive(Trace)]
+pub struct IntlStatics {
+    pub intl: ObjectId,
+    pub get_canonical_locales: ObjectId,
+    pub number_format_ctor: ObjectId,
+    pub number_format_prototype: ObjectId,
+    pub number_format_supported_locales_of: ObjectId,
+    pub number_format_format: ObjectId,
+    pub number_format_bound_format: ObjectId,
+    pub number_format_format_to_parts: ObjectId,
+    pub number_format_resolved_options: ObjectId,
+    pub date_time_format_ctor: ObjectId,
+    pub date_time_format_prototype: ObjectId,
+    pub date_time_format_supported_locales_of: ObjectId,
+    pub date_time_format_format: ObjectId,
+    pub date_time_format_bound_format: ObjectId,
+    pub date_time_format_format_to_parts: ObjectId,
+    pub date_time_format_resolved_options: ObjectId,
+    pub collator_ctor: ObjectId,
+    pub collator_prototype: ObjectId,
+    pub collator_supported_locales_of: ObjectId,
+    pub collator_compare: ObjectId,
+    pub collator_bound_compare: ObjectId,
+    pub collator_resolved_options: ObjectId,
+}
+
+#[cfg(feature = "intl")]
+impl IntlStatics {
+    fn new(gc: &mut Allocator) -> Self {
+        use js_std::intl;
+
+        Self {
+            intl: empty_object(gc),
+            get_canonical_locales: function(gc, sym::getCanonicalLocales, intl::get_canonical_locales, false),
+            number_format_ctor: function(gc, sym::NumberFormat, intl::number_format_constructor, true),
+            number_format_prototype: builtin_object(gc, OrdObject::null()),
+            number_format_supported_locales_of: function(
+                gc,
+                sym::supportedLocalesOf,
+                intl::supported_locales_of,
+                false,
+            ),
+            number_format_format: function(gc, sym::format, intl::number_format_format, false),
+            number_format_bound_format: function(gc, sym::empty, intl::number_format_bound_format, false),
+            number_format_format_to_parts: function(gc, sym::formatToParts, intl::number_format_format_to_parts, false),
+            number_format_resolved_options: function(
+                gc,
+                sym::resolvedOptions,
+                intl::number_format_resolved_options,
+                false,
+            ),
+            date_time_format_ctor: function(gc, sym::DateTimeFormat, intl::date_time_format_constructor, true),
+            date_time_format_prototype: builtin_object(gc, OrdObject::null()),
+            date_time_format_supported_locales_of: function(
+                gc,
+                sym::supportedLocalesOf,
+                intl::supported_locales_of,
+                false,
+            ),
+            date_time_format_format: function(gc, sym::format, intl::date_time_format_format, false),
+            date_time_format_bound_format: function(gc, sym::empty, intl::date_time_format_bound_format, false),
+            date_time_format_format_to_parts: function(
+                gc,
+                sym::formatToParts,
+                intl::date_time_format_format_to_parts,
+                false,
+            ),
+            date_time_format_resolved_options: function(
+                gc,
+                sym::resolvedOptions,
+                intl::date_time_format_resolved_options,
+                false,
+            ),
+            collator_ctor: function(gc, sym::Collator, intl::collator_constructor, true),
+            collator_prototype: builtin_object(gc, OrdObject::null()),
+            collator_supported_locales_of: function(gc, sym::supportedLocalesOf, intl::supported_locales_of, false),
+            collator_compare: function(gc, sym::compare, intl::collator_compare, false),
+            collator_bound_compare: function(gc, sym::empty, intl::collator_bound_compare, false),
+            collator_resolved_options: function(gc, sym::resolvedOptions, intl::collator_resolved_options, false),
         }
     }
 }
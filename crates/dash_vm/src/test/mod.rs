@@ -5,12 +5,14 @@ use dash_middle::interner::sym;
 use dash_optimizer::OptLevel;
 
 use crate::Vm;
+use crate::eval::EvalError;
 use crate::gc::ObjectId;
 use crate::gc::persistent::Persistent;
+use crate::params::VmParams;
 use crate::value::object::{Object, OrdObject, PropertyValue};
 use crate::value::primitive::{Null, Number, Symbol, Undefined};
 use crate::value::propertykey::ToPropertyKey;
-use crate::value::{Root, Unpack, Value, ValueKind};
+use crate::value::{Root, Unpack, Unrooted, Value, ValueKind};
 
 const INTERPRETER: &str = include_str!("interpreter.js");
 
@@ -203,6 +205,357 @@ fn async_tasks() {
     assert!(vm.stack.is_empty());
 }
 
+#[test]
+fn weakref_finalization_registry() {
+    let mut vm = Vm::new(Default::default());
+    vm.eval(
+        r#"
+    globalThis.cleanedUp = [];
+    globalThis.registry = new FinalizationRegistry(held => cleanedUp.push(held));
+    globalThis.strong = {};
+    globalThis.strongRef = new WeakRef(strong);
+    globalThis.weakRef = new WeakRef({});
+    const token = {};
+    globalThis.token = token;
+    registry.register({}, 'collected');
+    registry.register({}, 'unregistered', token);
+    registry.register(strong, 'alive');
+    registry.unregister(token);
+    "#,
+        Default::default(),
+    )
+    .unwrap();
+
+    // Targets stay alive until the job that created the WeakRef finishes
+    vm.perform_gc();
+    let result = vm.eval("weakRef.deref() !== undefined", Default::default()).unwrap();
+    assert_eq!(result.root(&mut vm.scope()), Value::boolean(true));
+
+    vm.process_async_tasks();
+    vm.perform_gc();
+    vm.process_async_tasks();
+
+    let result = vm
+        .eval(
+            r#"
+    weakRef.deref() === undefined
+        && strongRef.deref() === strong
+        && cleanedUp.length === 1
+        && cleanedUp[0] === 'collected'
+    "#,
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(result.root(&mut vm.scope()), Value::boolean(true));
+
+    // Cells of collected targets can still be unregistered until the cleanup job runs
+    vm.eval(
+        "registry.register({}, 'should-not-run', token); registry.register({}, 'cleaned');",
+        Default::default(),
+    )
+    .unwrap();
+    vm.perform_gc();
+    vm.perform_gc();
+    let result = vm.eval("registry.unregister(token)", Default::default()).unwrap();
+    assert_eq!(result.root(&mut vm.scope()), Value::boolean(true));
+    vm.process_async_tasks();
+
+    let result = vm
+        .eval("cleanedUp.join() === 'collected,cleaned'", Default::default())
+        .unwrap();
+    assert_eq!(result.root(&mut vm.scope()), Value::boolean(true));
+}
+
+#[test]
+fn promise_combinators() {
+    let mut vm = Vm::new(Default::default());
+    vm.eval(
+        r#"
+    globalThis.results = [];
+    const log = (name) => (value) => results.push(name + ':' + JSON.stringify(value));
+
+    Promise.all([1, Promise.resolve(2), new Promise(r => r(3))]).then(log('all'));
+    Promise.all([1, Promise.reject('bad')]).catch(log('all-rejected'));
+    Promise.allSettled([1, Promise.reject('x')]).then(log('allSettled'));
+    Promise.any([Promise.reject(1), Promise.resolve(2)]).then(log('any'));
+    Promise.any([Promise.reject(1), Promise.reject(2)]).catch(e => log('any-rejected')([e instanceof AggregateError, e.errors]));
+    Promise.race([new Promise(() => {}), Promise.resolve('fast')]).then(log('race'));
+    Promise.resolve(5).finally(() => 1).then(log('finally'));
+    Promise.resolve(1).then(() => { throw 'thrown'; }).then(undefined, log('then-throw'));
+    new Promise(() => { throw 'executor'; }).catch(log('executor'));
+    "#,
+        Default::default(),
+    )
+    .unwrap();
+    vm.process_async_tasks();
+
+    let result = vm
+        .eval(
+            r#"
+    const expected = [
+        'all:[1,2,3]',
+        'all-rejected:"bad"',
+        'allSettled:[{"status":"fulfilled","value":1},{"status":"rejected","reason":"x"}]',
+        'any:2',
+        'any-rejected:[true,[1,2]]',
+        'race:"fast"',
+        'finally:5',
+        'then-throw:"thrown"',
+        'executor:"executor"',
+    ];
+    expected.every(e => results.includes(e)) && results.length === expected.length
+    "#,
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(result.root(&mut vm.scope()), Value::boolean(true));
+}
+
+#[test]
+fn async_generators() {
+    let mut vm = Vm::new(Default::default());
+    vm.eval(
+        r#"
+    globalThis.results = [];
+    async function* gen() {
+        const x = yield 1;
+        results.push('resumed:' + x);
+        await null;
+        yield Promise.resolve(2);
+        try { yield 3; } catch (e) { results.push('caught:' + e); }
+        return 4;
+    }
+    (async () => {
+        const g = gen();
+        // requests made before the previous one settles are queued
+        const requests = [g.next(), g.next('a'), g.next()];
+        for (const request of requests) {
+            const { value, done } = await request;
+            results.push(value + ':' + done);
+        }
+        const { value } = await g.throw('err');
+        results.push('thrown:' + value);
+        results.push('finished:' + (await g.next()).done);
+
+        for await (const v of gen()) results.push('for-await:' + v);
+        for await (const v of [Promise.resolve(5), 6]) results.push('sync:' + v);
+        const iterable = { async *[Symbol.asyncIterator]() { yield* [7, 8]; } };
+        for await (const v of iterable) results.push('iterable:' + v);
+    })();
+    "#,
+        Default::default(),
+    )
+    .unwrap();
+    vm.process_async_tasks();
+
+    let result = vm
+        .eval(
+            r#"
+    results.join(',') === [
+        'resumed:a', '1:false', '2:false', '3:false', 'caught:err', 'thrown:4', 'finished:true',
+        'for-await:1', 'resumed:undefined', 'for-await:2', 'for-await:3',
+        'sync:5', 'sync:6', 'iterable:7', 'iterable:8',
+    ].join(',')
+    "#,
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(result.root(&mut vm.scope()), Value::boolean(true));
+}
+
+#[test]
+fn generator_return_iterator_close() {
+    let mut vm = Vm::new(Default::default());
+    let result = vm
+        .eval(
+            r#"
+    const results = [];
+    function* gen(name) {
+        try {
+            yield 1;
+            yield 2;
+        } finally {
+            results.push('closed:' + name);
+        }
+    }
+
+    const g = gen('return');
+    g.next();
+    const { value, done } = g.return(5);
+    results.push(value + ':' + done);
+    results.push('after:' + g.next().done);
+
+    for (const x of gen('break')) break;
+    outer: for (const x of gen('outer')) {
+        for (const y of gen('inner')) continue outer;
+    }
+    (function() { for (const x of gen('return')) return; })();
+    try { for (const x of gen('throw')) throw 1; } catch {}
+    for (const x of gen('exhausted'));
+
+    function f() { try { try { return 1; } finally { results.push('inner'); } } finally { results.push('outer'); } }
+    results.push('nested:' + f());
+
+    results.join(',') === [
+        'closed:return', '5:true', 'after:true', 'closed:break', 'closed:inner', 'closed:inner', 'closed:outer',
+        'closed:return', 'closed:throw', 'closed:exhausted', 'inner', 'outer', 'nested:1',
+    ].join(',')
+    "#,
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(result.root(&mut vm.scope()), Value::boolean(true));
+}
+
+#[test]
+fn array_from_async() {
+    let mut vm = Vm::new(Default::default());
+    vm.eval(
+        r#"
+    globalThis.results = [];
+    const log = (name) => (value) => results.push(name + ':' + JSON.stringify(value));
+
+    const asyncIterable = {
+        [Symbol.asyncIterator]() {
+            let i = 0;
+            return { next: () => Promise.resolve({ value: ++i, done: i > 3 }) };
+        }
+    };
+    Array.fromAsync(asyncIterable).then(log('async'));
+    Array.fromAsync([Promise.resolve(1), 2], (x, i) => Promise.resolve(x * 10 + i)).then(log('mapped'));
+    Array.fromAsync({ length: 2, 0: 'a', 1: Promise.resolve('b') }).then(log('array-like'));
+    Array.fromAsync([Promise.reject('boom')]).catch(log('rejected'));
+    Array.fromAsync([1], 5).catch(e => log('bad-mapper')(e instanceof TypeError));
+    "#,
+        Default::default(),
+    )
+    .unwrap();
+    vm.process_async_tasks();
+
+    let result = vm
+        .eval(
+            r#"
+    const expected = [
+        'async:[1,2,3]',
+        'mapped:[10,21]',
+        'array-like:["a","b"]',
+        'rejected:"boom"',
+        'bad-mapper:true',
+    ];
+    expected.every(e => results.includes(e)) && results.length === expected.length
+    "#,
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(result.root(&mut vm.scope()), Value::boolean(true));
+}
+
+#[test]
+fn strict_mode_early_errors() {
+    let mut vm = Vm::new(Default::default());
+    for source in [
+        "'use strict'; function f(a, a) {}",
+        "function f(a, a) { 'use strict'; }",
+        "class C { m(a, a) {} }",
+        "'use strict'; with ({}) {}",
+        "'use strict'; 010",
+        "function f() { 'use strict'; return 07; }",
+        r"'use strict'; '\01'",
+        r"function f() { '\8'; 'use strict'; }",
+        "'use strict'; eval = 1",
+        "'use strict'; arguments++",
+        "'use strict'; [eval] = []",
+        "'use strict'; ({ arguments } = {})",
+        "function f(a = 1) { 'use strict'; }",
+        "function f(...a) { 'use strict'; }",
+        "({ a }) => { 'use strict'; }",
+    ] {
+        assert!(matches!(vm.eval(source, Default::default()), Err(EvalError::Middle(_))));
+    }
+
+    let value = vm
+        .eval("function f(a, a) { return 1; } f() + 010 + 09", Default::default())
+        .unwrap();
+    assert_eq!(value.root(&mut vm.scope()), Value::number(18.0));
+
+    let value = vm
+        .eval(
+            r"'use strict'; ({ eval: x } = {}); var x; '\0'.length",
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(value.root(&mut vm.scope()), Value::number(1.0));
+}
+
+#[test]
+fn super_property_outside_method() {
+    let mut vm = Vm::new(Default::default());
+    for source in [
+        "super.x",
+        "function f() { super.x }",
+        "function f(a = super[0]) {}",
+        "({ m() { function f() { return () => super.x } } })",
+    ] {
+        let result = vm.eval(source, Default::default());
+        assert!(matches!(result, Err(EvalError::Middle(_))), "{source}");
+    }
+
+    let value = vm
+        .eval(
+            "({ __proto__: { x: 1 }, m(a = () => super.x) { return a() + (() => super.x)() } }).m()",
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(value.root(&mut vm.scope()), Value::number(2.0));
+}
+
+#[test]
+fn duplicate_proto_in_object_literal() {
+    let mut vm = Vm::new(Default::default());
+    let result = vm.eval("({ __proto__: null, __proto__: null })", Default::default());
+    assert!(matches!(result, Err(EvalError::Middle(_))));
+
+    let value = vm
+        .eval(
+            "({ __proto__: null, ['__proto__']: 1, __proto__() {} }).__proto__()",
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(value.root(&mut vm.scope()), Value::undefined());
+}
+
+#[test]
+fn date_local_time_around_dst_transitions() {
+    // America/New_York in 2021: EDT (UTC-4) from 2021-03-14T07:00Z until 2021-11-07T06:00Z, EST (UTC-5) otherwise
+    fn new_york_offset(_: &mut Vm, utc_millis: i64) -> Result<i64, Unrooted> {
+        const HOUR: i64 = 60 * 60 * 1000;
+        match utc_millis {
+            1615705200000..1636264800000 => Ok(-4 * HOUR),
+            _ => Ok(-5 * HOUR),
+        }
+    }
+
+    let mut vm = Vm::new(VmParams::new().set_timezone_offset_callback(new_york_offset));
+    let result = vm
+        .eval(
+            r#"
+    const iso = (...args) => new Date(...args).toISOString();
+    // 02:30 is skipped when the clocks are turned forward, so the offset from before the transition applies
+    iso(2021, 2, 14, 2, 30) === '2021-03-14T07:30:00.000Z'
+        && new Date(2021, 2, 14, 2, 30).getHours() === 3
+        && iso(2021, 2, 14, 1, 30) === '2021-03-14T06:30:00.000Z'
+        && iso(2021, 2, 14, 3, 30) === '2021-03-14T07:30:00.000Z'
+        // 01:30 happens twice when the clocks are turned back, and the earlier one is used
+        && iso(2021, 10, 7, 1, 30) === '2021-11-07T05:30:00.000Z'
+        && iso(2021, 10, 7, 2, 30) === '2021-11-07T07:30:00.000Z'
+        && iso(2021, 5, 1) === '2021-06-01T04:00:00.000Z'
+    "#,
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(result.root(&mut vm.scope()), Value::boolean(true));
+}
+
 macro_rules! simple_test {
     ($testname:ident, $code:expr, $expected:expr) => {
         #[test]
@@ -727,3 +1080,1063 @@ simple_test!(
     ",
     Value::undefined()
 );
+
+simple_test!(
+    proxy_traps,
+    "
+    const target = { a: 1 };
+    const p = new Proxy(target, {
+        get(t, k) { return k in t ? t[k] : 42; },
+        set(t, k, v) { t[k] = v * 2; return true; },
+        has(t, k) { return k !== 'hidden'; },
+        deleteProperty(t, k) { return delete t[k]; }
+    });
+    assert(p.a === 1 && p.missing === 42);
+    p.b = 2;
+    assert(target.b === 4);
+    assert('anything' in p && !('hidden' in p));
+    delete p.a;
+    assert(!('a' in target));
+
+    const f = new Proxy(function(x) { return x + 1; }, {
+        apply(t, thisArg, args) { return t(...args) * 10; }
+    });
+    assert(typeof f === 'function' && f(1) === 20);
+
+    const frozen = Object.freeze({ x: 1 });
+    let threw = false;
+    try { new Proxy(frozen, { get() { return 2; } }).x; } catch (e) { threw = e instanceof TypeError; }
+    assert(threw);
+
+    const { proxy, revoke } = Proxy.revocable({}, {});
+    proxy.x = 1;
+    assert(proxy.x === 1);
+    revoke();
+    threw = false;
+    try { proxy.x; } catch (e) { threw = e instanceof TypeError; }
+    assert(threw);
+
+    const sets = [];
+    const inheritsProxy = Object.create(new Proxy({}, {
+        set(t, k, v, receiver) { sets.push(k, v, receiver === inheritsProxy); return true; }
+    }));
+    inheritsProxy.q = 1;
+    assert(sets.join() === 'q,1,true' && !inheritsProxy.hasOwnProperty('q'));
+
+    assert(Array.isArray(new Proxy([], {})) && Array.isArray(new Proxy(new Proxy([], {}), {})));
+    assert(!Array.isArray(new Proxy({}, {})));
+    const revocable = Proxy.revocable([], {});
+    revocable.revoke();
+    threw = false;
+    try { Array.isArray(revocable.proxy); } catch (e) { threw = e instanceof TypeError; }
+    assert(threw);
+    assert(JSON.stringify(new Proxy([1, 2], {})) === '[1,2]');
+    ",
+    Value::undefined()
+);
+
+simple_test!(
+    reflect,
+    "
+    const o = { a: 1 };
+    assert(Reflect.get(o, 'a') === 1);
+    assert(Reflect.set(o, 'b', 2) && o.b === 2);
+    assert(Reflect.has(o, 'b') && !Reflect.has(o, 'c'));
+    assert(Reflect.deleteProperty(o, 'b') && !('b' in o));
+    assert(Reflect.ownKeys(o).length === 1);
+    assert(Reflect.getPrototypeOf(o) === Object.prototype);
+    assert(Reflect.apply(Math.max, null, [1, 3, 2]) === 3);
+    class A { constructor(x) { this.x = x; } }
+    assert(Reflect.construct(A, [5]).x === 5);
+    Reflect.defineProperty(o, 'c', { value: 3 });
+    assert(!Reflect.getOwnPropertyDescriptor(o, 'c').writable);
+
+    let receiver = {}, setterThis, setterValue;
+    const accessor = { set x(v) { setterThis = this; setterValue = v; } };
+    assert(Reflect.set(accessor, 'x', 5, receiver) && setterThis === receiver && setterValue === 5);
+    assert(typeof Reflect.getOwnPropertyDescriptor(accessor, 'x').set === 'function');
+
+    const child = Object.create(accessor);
+    child.x = 6;
+    assert(setterThis === child && setterValue === 6 && !child.hasOwnProperty('x'));
+    assert(Reflect.set(Object.create({ get y() { return 1; } }), 'y', 2) === false);
+
+    const readonly = Object.create(Object.defineProperty({}, 'z', { value: 1 }));
+    assert(Reflect.set(readonly, 'z', 2) === false && !readonly.hasOwnProperty('z'));
+
+    const hidden = Object.defineProperty({}, 'w', { value: 1, writable: true, configurable: true });
+    assert(Reflect.set(hidden, 'w', 2) && hidden.w === 2 && Object.keys(hidden).length === 0);
+
+    const inherited = Object.create({ v: 1 });
+    receiver = {};
+    assert(Reflect.set(inherited, 'v', 2, receiver) && receiver.v === 2 && !inherited.hasOwnProperty('v'));
+    ",
+    Value::undefined()
+);
+
+simple_test!(
+    bigint,
+    "
+    assert(typeof 1n === 'bigint');
+    assert(2n ** 64n === 18446744073709551616n);
+    assert(0xffn + 1n === 256n && 7n / 2n === 3n && -7n % 3n === -1n);
+    assert((1n << 70n) >> 69n === 2n && ~5n === -6n && -(3n) === -3n);
+    assert((5n & 3n) === 1n && (5n | 3n) === 7n && (5n ^ 3n) === 6n);
+    assert(1n == 1 && 2n > 1 && 1n < 1.5 && '10' == 10n && 1n !== 1);
+    let x = 5n;
+    x++;
+    assert(x === 6n);
+    assert(BigInt(10) === 10n && BigInt('0x10') === 16n && BigInt(true) === 1n);
+    assert(BigInt.asIntN(8, 255n) === -1n && BigInt.asUintN(8, -1n) === 255n);
+    assert((255n).toString(16) === 'ff' && 10n + '' === '10' && Number(10n) === 10);
+    assert(typeof Object(1n) === 'object' && Object(1n).valueOf() === 1n);
+
+    let threw = false;
+    try { 1n + 1; } catch (e) { threw = e instanceof TypeError; }
+    assert(threw);
+    threw = false;
+    try { 1n / 0n; } catch (e) { threw = e instanceof RangeError; }
+    assert(threw);
+    threw = false;
+    try { BigInt(1.5); } catch (e) { threw = e instanceof RangeError; }
+    assert(threw);
+    ",
+    Value::undefined()
+);
+
+simple_test!(
+    json_stringify,
+    r#"
+    assert(JSON.stringify({ a: 1, b: 'x"\n', c: [1, undefined, NaN], d: undefined }) === '{"a":1,"b":"x\\"\\n","c":[1,null,null]}');
+    assert(JSON.stringify([1, { a: [] }], null, 2) === '[\n  1,\n  {\n    "a": []\n  }\n]');
+    assert(JSON.stringify({ a: 1, b: 2, c: { a: 3 } }, ['a', 'c']) === '{"a":1,"c":{"a":3}}');
+    assert(JSON.stringify({ a: 1 }, (k, v) => typeof v === 'number' ? v * 2 : v) === '{"a":2}');
+    assert(JSON.stringify({ d: { toJSON(k) { return k; } } }) === '{"d":"d"}');
+    assert(JSON.stringify(undefined) === undefined && JSON.stringify(new String('s')) === '"s"');
+    assert(JSON.stringify({ a: 1, [Symbol()]: 2 }) === '{"a":1}');
+
+    const o = {};
+    o.self = o;
+    let threw = false;
+    try { JSON.stringify(o); } catch (e) { threw = e instanceof TypeError; }
+    assert(threw);
+    threw = false;
+    try { JSON.stringify(1n); } catch (e) { threw = e instanceof TypeError; }
+    assert(threw);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    json_parse_reviver,
+    r#"
+    const keys = [];
+    const result = JSON.parse('{"a":[1,{"b":2}],"c":3}', function (k, v) {
+        keys.push(k);
+        if (k === 'c') return undefined;
+        return typeof v === 'number' ? v * 10 : v;
+    });
+    assert(keys.join(',') === '0,b,1,a,c,');
+    assert(JSON.stringify(result) === '{"a":[10,{"b":20}]}');
+
+    const revived = [];
+    JSON.parse('{"x":1,"a":{"b":1}}', function (k, v) {
+        if (k === 'x') this.a[Symbol()] = 1;
+        revived.push(typeof k);
+        return v;
+    });
+    assert(revived.join(',') === 'string,string,string,string');
+
+    let message;
+    try { JSON.parse('[1,\n  @]'); } catch (e) { message = e.message; }
+    assert(message === 'Unexpected token @ at line 2 column 3');
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    date,
+    r#"
+    const d = new Date(Date.UTC(2026, 9, 18, 12, 30, 15, 250));
+    assert(d.toISOString() === '2026-10-18T12:30:15.250Z');
+    assert(d.toUTCString() === 'Sun, 18 Oct 2026 12:30:15 GMT');
+    assert(d.toString() === 'Sun Oct 18 2026 12:30:15 GMT+0000');
+    assert(JSON.stringify({ d }) === '{"d":"2026-10-18T12:30:15.250Z"}');
+    assert(d.getFullYear() === 2026 && d.getMonth() === 9 && d.getDate() === 18 && d.getDay() === 0);
+    assert(d.getUTCHours() === 12 && d.getMinutes() === 30 && d.getSeconds() === 15 && d.getMilliseconds() === 250);
+    assert(typeof (d + 1) === 'string' && d - 0 === d.getTime());
+
+    assert(Date.parse('2026-10-18') === 1792281600000);
+    assert(Date.parse('2026-10-18T14:30:15.250+02:00') === d.getTime());
+    assert(Date.parse(d.toUTCString()) === 1792326615000);
+    assert(Date.parse(d.toString()) === 1792326615000);
+    assert(isNaN(Date.parse('not a date')));
+
+    const e = new Date(2026, 0, 31);
+    e.setMonth(1);
+    assert(e.getMonth() === 2 && e.getDate() === 3);
+    e.setHours(25, 61);
+    assert(e.toISOString() === '2026-03-04T02:01:00.000Z');
+    assert(new Date(-1).toISOString() === '1969-12-31T23:59:59.999Z');
+
+    const invalid = new Date(NaN);
+    assert(String(invalid) === 'Invalid Date' && isNaN(invalid.getDay()) && isNaN(invalid.setHours(1)));
+    invalid.setFullYear(2001);
+    assert(invalid.toISOString() === '2001-01-01T00:00:00.000Z');
+
+    let threw = false;
+    try { new Date(NaN).toISOString(); } catch (err) { threw = err instanceof RangeError; }
+    assert(threw);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    regex_flags,
+    r#"
+    assert(/^.$/u.test('😀') && /\p{Letter}+/u.exec('123Ωmega!')[0] === 'Ωmega');
+    assert(!/./.test('\n') && /./s.test('\n'));
+    assert(/^b$/m.test('a\nb') && !/^b$/.test('a\nb'));
+
+    const sticky = /foo/y;
+    assert(!sticky.test('barfoo') && sticky.lastIndex === 0);
+    sticky.lastIndex = 3;
+    assert(sticky.test('barfoo') && sticky.lastIndex === 6);
+
+    const global = /o/g;
+    assert(global.exec('foo').index === 1 && global.exec('foo').index === 2);
+    assert(global.exec('foo') === null && global.lastIndex === 0);
+
+    const match = /(a)(x)?(b)/d.exec('zzab');
+    assert(match.index === 2 && match.input === 'zzab' && match[2] === undefined);
+    assert(match.indices[0][0] === 2 && match.indices[0][1] === 4 && match.indices[2] === undefined);
+
+    assert(/a/gimsuyd.flags === 'dgimsuy' && /a/v.unicodeSets && !/a/.global);
+    assert(String(/a\/b/g) === '/a\\/b/g' && new RegExp(/a/g, 'i').flags === 'i');
+    assert(/[\p{L}--[a-z]]/v.test('A') && !/[\p{L}--[a-z]]/v.test('a'));
+
+    let threw = false;
+    try { new RegExp('a', 'gg'); } catch (e) { threw = e instanceof SyntaxError; }
+    assert(threw);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    regex_groups,
+    r#"
+    const line = /(?<level>[A-Z]+) \[(?<module>\w+)\](?: (?<code>\d+))?/d.exec('12:00 WARN [net] timeout');
+    assert(line.groups.level === 'WARN' && line.groups.module === 'net' && line.groups.code === undefined);
+    assert(Object.getPrototypeOf(line.groups) === null && 'code' in line.groups);
+    assert(line.indices.groups.module[0] === 12 && line.indices.groups.module[1] === 15);
+    assert(/(a)/.exec('a').groups === undefined);
+
+    assert(/\d+(?=px)/.exec('10em 20px')[0] === '20' && /^a(?!b)/.test('ac') && !/^a(?!b)/.test('ab'));
+    assert(/(?<=\$)\d+/.exec('5 $42')[0] === '42' && /(?<!\$)\b\d+/.exec('$5 42')[0] === '42');
+    assert(/(\w)\1/.exec('hello')[0] === 'll' && /^(?<q>['"]).*\k<q>$/.test('"hi"') && !/^(?<q>['"]).*\k<q>$/.test('"hi\''));
+
+    let threw = false;
+    try { new RegExp('(?<a>x)(?<a>y)'); } catch (e) { threw = e instanceof SyntaxError; }
+    assert(threw);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    string_regex_methods,
+    r#"
+    assert('a1b22c333'.match(/\d+/g).join(',') === '1,22,333' && 'xyz'.match(/q/g) === null);
+    assert('a1b2'.match(/(?<digit>\d)/).groups.digit === '1' && 'abc'.match('b').index === 1);
+
+    const all = Array.from('a1b22'.matchAll(/\d+/g), m => m[0] + '@' + m.index);
+    assert(all.join(',') === '1@1,22@3');
+    let threw = false;
+    try { 'a'.matchAll(/a/); } catch (e) { threw = e instanceof TypeError; }
+    assert(threw);
+
+    assert('hello'.search(/l+/) === 2 && 'hello'.search('z') === -1);
+
+    assert('2024-05-01'.replace(/(\d+)-(\d+)-(\d+)/, '$3/$2/$1') === '01/05/2024');
+    assert('john smith'.replace(/(?<first>\w+) (?<last>\w+)/, '$<last>, $<first>') === 'smith, john');
+    assert('aaa'.replace(/a/g, (match, offset) => offset) === '012');
+    assert('abc'.replace('b', "[$&$`$'$$]") === "a[bac$]c" && 'a-b-c'.replaceAll('-', '+') === 'a+b+c');
+    assert('x'.replaceAll('', '_') === '_x_' && 'ab12'.replace(/\d/g, '$10') === 'ab$10$10');
+
+    assert('a1b2c'.split(/(\d)/).join(',') === 'a,1,b,2,c' && 'a b c'.split(' ', 2).join(',') === 'a,b');
+    assert('abc'.split(/(?:)/).length === 3 && ''.split(/x/).length === 1 && ''.split(/(?:)/).length === 0);
+
+    const custom = { [Symbol.replace](string, replacement) { return string + replacement; } };
+    assert('str'.replace(custom, '!') === 'str!');
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    string_builder,
+    r#"
+    let output = '';
+    for (let i = 0; i < 1000; i++) output += 'row ' + i + ';';
+    assert(output.length === 7890 && output.endsWith('row 999;'));
+
+    const prefix = 'x'.repeat(70);
+    const a = prefix + 'y', b = prefix + 'y';
+    assert(a === b && a == b && [a].includes(b) && [a].indexOf(b) === 0);
+
+    const object = { [a]: 1 };
+    const map = new Map([[a, 2]]);
+    const set = new Set([a]);
+    assert(object[b] === 1 && map.get(b) === 2 && set.has(b));
+
+    // Both strings share the prefix but must not see each other's appended parts
+    const left = a + '1', right = a + '2';
+    assert(left.slice(-2) === 'y1' && right.slice(-2) === 'y2' && a.length === 71);
+    assert((a + a).length === 142 && 'ab'.concat('c', 'd') === 'abcd');
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    map_set_iteration,
+    r#"
+    const map = new Map([['a', 1], ['b', 2], ['c', 3]]);
+    let keys = '';
+    for (const [k, v] of map) keys += k + v;
+    assert(keys === 'a1b2c3');
+    assert([...map.keys()].join(',') === 'a,b,c' && [...map.values()].join(',') === '1,2,3');
+    assert(Map.prototype[Symbol.iterator] === Map.prototype.entries);
+
+    // Iterators observe entries deleted and added after they were created
+    const it = map.keys();
+    assert(it.next().value === 'a');
+    map.delete('b');
+    map.set('d', 4);
+    assert(it.next().value === 'c' && it.next().value === 'd' && it.next().done);
+    map.set('e', 5);
+    assert(it.next().done);
+
+    const set = new Set('abca');
+    assert(set.size() === 3 && [...set].join(',') === 'a,b,c');
+    assert(Set.prototype.keys === Set.prototype.values && Set.prototype[Symbol.iterator] === Set.prototype.values);
+    let visited = '';
+    set.forEach(function (v, k, s) {
+        assert(v === k && s === set && this === map);
+        if (v === 'a') set.add('z');
+        visited += v;
+    }, map);
+    assert(visited === 'abcz');
+
+    const cleared = set.values();
+    set.clear();
+    set.add(1);
+    assert(cleared.next().value === 1 && cleared.next().done);
+
+    function* pairs() { yield ['x', 1]; yield ['y', 2]; }
+    assert(new Map(pairs()).get('y') === 2 && new Set(null).size() === 0);
+    const entry = new Set([5]).entries().next().value;
+    assert(entry[0] === 5 && entry[1] === 5);
+
+    let closed = 0;
+    const endless = { [Symbol.iterator]() { return { next() { return { value: 1, done: false }; }, return() { closed++; return {}; } }; } };
+    let error;
+    try { new Map(endless); } catch (e) { error = e; }
+    assert(error instanceof TypeError && closed === 1);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    array_methods,
+    r#"
+    const array = [1, 2, 3, 4, 5];
+    assert(array.join() === '1,2,3,4,5' && [[1, [2]], 3].flat().length === 3);
+    assert([[1, [2, [3]]]].flat(Infinity).join('-') === '1-2-3');
+    assert(array.reduceRight((acc, x) => acc + x, '') === '54321' && [1, , 3].reduceRight((acc, x) => acc + x) === 4);
+    assert(array.flatMap(x => [x, [x * 2]]).length === 10);
+    assert(array.findLast(x => x % 2 === 0) === 4 && array.findLastIndex(x => x > 10) === -1);
+    assert([1, 2, 3, 4, 5].copyWithin(0, 3).join() === '4,5,3,4,5');
+    assert([1, 2, 3, 4, 5].copyWithin(1, 0, 3).join() === '1,1,2,3,5');
+
+    assert([3, 1, 2].toSorted().join() === '1,2,3' && [3, 1, 2].toSorted((a, b) => b - a).join() === '3,2,1');
+    assert(['b', undefined, 'a', 10, 9].sort().join() === '10,9,a,b,');
+    assert(array.toReversed().join() === '5,4,3,2,1' && array.join() === '1,2,3,4,5');
+    assert(array.toSpliced(1, 2, 'x').join() === '1,x,4,5' && array.toSpliced(2).join() === '1,2');
+    assert(array.with(-1, 'L').join() === '1,2,3,4,L' && array[4] === 5);
+    let threw = false;
+    try { array.with(5, 0); } catch (e) { threw = e instanceof RangeError; }
+    assert(threw);
+
+    const arrayLike = { length: 3, 0: 'a', 2: 'c' };
+    assert(Array.prototype.findLast.call(arrayLike, x => x === undefined) === undefined);
+    assert(Array.prototype.toReversed.call(arrayLike).join() === 'c,,a');
+
+    assert(Array.of(7, 8).join() === '7,8');
+    assert(Array.from('abc', (c, i) => c + i).join() === 'a0,b1,c2');
+    assert(Array.from({ length: 3 }, function (_, i) { return this.x * i; }, { x: 2 }).join() === '0,2,4');
+    assert(Array.from(new Set([1, 2, 2, 3])).join() === '1,2,3' && [...[1, 2].values()].length === 2);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    object_statics,
+    r#"
+    const obj = { a: 1, b: 2 };
+    assert(Object.values(obj).join() === '1,2' && Object.hasOwn(obj, 'a') && !Object.hasOwn(obj, 'toString'));
+    const fromEntries = Object.fromEntries(new Map([['x', 1], ['y', 2]]));
+    assert(fromEntries.x === 1 && fromEntries.y === 2);
+    assert(Object.is(NaN, NaN) && !Object.is(0, -0) && Object.is('ab', 'a' + 'b') && !Object.is({}, {}));
+
+    const groups = Object.groupBy([1, 2, 3, 4, 5], x => x % 2 ? 'odd' : 'even');
+    assert(groups.odd.join() === '1,3,5' && groups.even.join() === '2,4' && Object.getPrototypeOf(groups) === null);
+    const mapGroups = Map.groupBy([1, 2, 3], x => x > 1);
+    assert(mapGroups.get(true).join() === '2,3' && mapGroups.get(false).join() === '1');
+
+    Object.preventExtensions(obj);
+    obj.c = 3;
+    obj.a = 5;
+    assert(!Object.isExtensible(obj) && obj.c === undefined && obj.a === 5 && !Object.isSealed(obj));
+    let threw = false;
+    try { Object.defineProperty(obj, 'd', { value: 1 }); } catch (e) { threw = e instanceof TypeError; }
+    assert(threw && Reflect.defineProperty(obj, 'd', { value: 1 }) === false);
+    assert(Reflect.setPrototypeOf(obj, null) === false && Reflect.setPrototypeOf(obj, Object.prototype));
+
+    const frozen = Object.freeze({ a: 1 });
+    frozen.a = 2;
+    frozen.b = 3;
+    assert(Object.isFrozen(frozen) && Object.isSealed(frozen) && frozen.a === 1 && frozen.b === undefined);
+    const sealed = Object.seal({ a: 1 });
+    sealed.a = 2;
+    delete sealed.a;
+    assert(Object.isSealed(sealed) && !Object.isFrozen(sealed) && sealed.a === 2);
+    assert(Object.isFrozen(1) && !Object.isExtensible('x') && Object.isFrozen(Object.preventExtensions({})));
+
+    const array = Object.freeze([1, 2]);
+    array[0] = 5;
+    const mutators = [a => a.push(3), a => a.pop(), a => a.shift(), a => a.unshift(0), a => a.reverse(), a => a.fill(0)];
+    for (const mutate of mutators) {
+        threw = false;
+        try { mutate(array); } catch (e) { threw = e instanceof TypeError; }
+        assert(threw);
+    }
+    assert(array[0] === 1 && array.length === 2 && Object.isFrozen(array));
+    const fixed = Object.preventExtensions([1]);
+    threw = false;
+    try { fixed.push(2); } catch (e) { threw = e instanceof TypeError; }
+    assert(threw && fixed.length === 1 && fixed.shift() === 1 && fixed.length === 0);
+    threw = false;
+    try { Object.seal([1, 2]).splice(0, 1); } catch (e) { threw = e instanceof TypeError; }
+    assert(threw && [1].push(2, 3) === 3);
+
+    const proxy = new Proxy({}, {});
+    assert(Reflect.preventExtensions(proxy) && !Reflect.isExtensible(proxy));
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    number_formatting,
+    r#"
+    assert(String(1e21) === '1e+21' && String(1e-7) === '1e-7' && String(0.000001) === '0.000001');
+    assert(String(2 ** 60) === '1152921504606847000' && String(28635025024.4140625) === '28635025024.414062');
+    assert((255).toString(16) === 'ff' && (-255.5).toString(16) === '-ff.8' && (0.1).toString(3) === '0.0022002200220022002200220022002201');
+    assert((0.5).toString(2) === '0.1' && (1e21).toString(36) === '5v1j4f4ds7c000');
+
+    assert((2.5).toFixed(0) === '3' && (1.005).toFixed(2) === '1.00' && (9.96).toFixed(1) === '10.0');
+    assert((-0.0001).toFixed(2) === '-0.00' && (0.000001).toFixed(7) === '0.0000010' && (1e21).toFixed(2) === '1e+21');
+    assert((123.456).toPrecision(4) === '123.5' && (0.000123).toPrecision(2) === '0.00012' && (123456).toPrecision(2) === '1.2e+5');
+    assert((9.99).toPrecision(2) === '10' && (0).toPrecision(3) === '0.00' && (1e-7).toPrecision(1) === '1e-7');
+    assert((123.456).toExponential(2) === '1.23e+2' && (0).toExponential() === '0e+0' && (-1.55).toExponential(1) === '-1.6e+0');
+    assert((123456).toExponential() === '1.23456e+5' && (9.99).toExponential(1) === '1.0e+1');
+
+    let threw = false;
+    try { (1).toString(37); } catch (e) { threw = e instanceof RangeError; }
+    assert(threw);
+    threw = false;
+    try { (1).toPrecision(0); } catch (e) { threw = e instanceof RangeError; }
+    assert(threw && (NaN).toPrecision(200) === 'NaN');
+
+    assert(Number.parseFloat === parseFloat && Number.parseInt === parseInt);
+    assert(parseFloat('3.14abc') === 3.14 && parseFloat('  -1e3x') === -1000 && parseFloat('-Infinityx') === -Infinity);
+    assert(isNaN(parseFloat('abc')) && parseFloat('.5') === 0.5 && parseFloat('1e') === 1);
+    assert(parseInt('12px') === 12 && parseInt('0x1F') === 31 && parseInt('101', 2) === 5 && 1 / parseInt('-0') === -Infinity);
+    assert(isNaN(parseInt('z', 37)) && isNaN(parseInt('0x')) && Number.EPSILON === 2 ** -52);
+    "#,
+    Value::undefined()
+);
+
+#[cfg(feature = "intl")]
+simple_test!(
+    intl,
+    r#"
+    const nf = new Intl.NumberFormat('en-US');
+    assert(nf.format(1234567.891) === '1,234,567.891' && nf.format(-0) === '-0' && nf.format(Infinity) === '∞');
+    assert(nf.format === nf.format && [1000, 2000].map(nf.format).join(';') === '1,000;2,000');
+    assert(new Intl.NumberFormat('en', { maximumFractionDigits: 2 }).format(1.005) === '1.01');
+    assert(new Intl.NumberFormat('en', { style: 'currency', currency: 'USD' }).format(-1234.5) === '-$1,234.50');
+    assert(new Intl.NumberFormat('en', { style: 'currency', currency: 'JPY' }).format(1234.5) === '¥1,235');
+    assert(new Intl.NumberFormat('en', { style: 'currency', currency: 'GBP', currencyDisplay: 'code' }).format(1234.5) === 'GBP\u00a01,234.50');
+    assert(new Intl.NumberFormat('en', { style: 'currency', currency: 'USD', currencyDisplay: 'name' }).format(1) === '1.00 US dollars');
+    assert(new Intl.NumberFormat('en', { style: 'percent' }).format(0.256) === '26%');
+    assert(new Intl.NumberFormat('en', { maximumSignificantDigits: 3 }).format(123456) === '123,000');
+    assert(new Intl.NumberFormat('en', { useGrouping: false }).format(12345) === '12345');
+    const parts = new Intl.NumberFormat('en').formatToParts(-1234.5).map(p => p.type).join();
+    assert(parts === 'minusSign,integer,group,integer,decimal,fraction');
+    assert(new Intl.NumberFormat('en', { style: 'currency', currency: 'EUR' }).resolvedOptions().minimumFractionDigits === 2);
+
+    const d = new Date(Date.UTC(2024, 0, 5, 3, 4, 5, 123));
+    const f = options => new Intl.DateTimeFormat('en-US', Object.assign({ timeZone: 'UTC' }, options)).format(d);
+    assert(f({}) === '1/5/2024' && f({ dateStyle: 'full' }) === 'Friday, January 5, 2024');
+    assert(f({ year: 'numeric', month: 'short', day: 'numeric', hour: 'numeric', minute: 'numeric' }) === 'Jan 5, 2024, 3:04 AM');
+    assert(f({ dateStyle: 'long', timeStyle: 'short' }) === 'January 5, 2024 at 3:04 AM');
+    assert(f({ timeStyle: 'long' }) === '3:04:05 AM UTC' && f({ hour: 'numeric', minute: 'numeric', hour12: false }) === '03:04');
+    assert(d.toLocaleDateString('en-US', { timeZone: 'UTC' }) === '1/5/2024');
+    assert(d.toLocaleTimeString('en-US', { timeZone: 'UTC' }) === '3:04:05 AM');
+    assert(new Intl.DateTimeFormat('en', { timeZone: 'UTC', dateStyle: 'short' }).resolvedOptions().dateStyle === 'short');
+    assert(new Date(NaN).toLocaleString() === 'Invalid Date');
+    let threw = false;
+    try { new Intl.DateTimeFormat('en', { dateStyle: 'full', year: 'numeric' }); } catch (e) { threw = e instanceof TypeError; }
+    assert(threw);
+    threw = false;
+    try { new Intl.DateTimeFormat('en', { timeZone: 'Mars/Base' }); } catch (e) { threw = e instanceof RangeError; }
+    assert(threw);
+
+    const compare = new Intl.Collator('en').compare;
+    assert([' ', '_', '10', '9', 'a', 'A', 'ä', 'b', 'B', 'z'].reverse().sort(compare).join('|') === ' |_|10|9|a|A|ä|b|B|z');
+    assert(['x10', 'x2', 'X1', 'x1'].sort(new Intl.Collator('en', { numeric: true }).compare).join() === 'x1,X1,x2,x10');
+    assert(new Intl.Collator('en', { sensitivity: 'base' }).compare('ä', 'A') === 0 && 'a'.localeCompare('b') === -1);
+
+    assert(Intl.getCanonicalLocales(['EN-us', 'en-US', 'de-latn-de']).join() === 'en-US,de-Latn-DE');
+    assert(Intl.Collator.supportedLocalesOf(['fr', 'en-GB', 'und']).join() === 'en-GB,und');
+    threw = false;
+    try { Intl.getCanonicalLocales('en-'); } catch (e) { threw = e instanceof RangeError; }
+    assert(threw);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    locale_methods,
+    r#"
+    assert('a'.localeCompare('b') === -1 && 'b'.localeCompare('a') === 1 && 'a'.localeCompare('a') === 0);
+    assert(typeof (1234.5).toLocaleString() === 'string' && typeof new Date(0).toLocaleDateString() === 'string');
+    assert(new Date(NaN).toLocaleTimeString() === 'Invalid Date');
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    typed_arrays,
+    r#"
+    const bytes = new Uint8Array([1, 2, 300, -1]);
+    assert(bytes.join() === '1,2,44,255' && bytes.length === 4 && Uint8Array.BYTES_PER_ELEMENT === 1);
+    assert(new Uint8ClampedArray([1.5, 2.5, 300, -5, NaN]).join() === '2,2,255,0,0');
+    assert(new Int16Array([32769, -2]).join() === '-32767,-2' && new Float16Array([1.1, 65520]).join() === '1.099609375,Infinity');
+    assert(new BigInt64Array([-1n]).join() === '-1' && new BigUint64Array([-1n]).join() === '18446744073709551615');
+
+    const buffer = new ArrayBuffer(16);
+    const view = new Int32Array(buffer, 4, 2);
+    const sub = view.subarray(1);
+    sub[0] = 7;
+    assert(view.join() === '0,7' && sub.byteOffset === 8 && sub.buffer === buffer && new Uint8Array(buffer)[8] === 7);
+    const copy = view.slice(1);
+    copy[0] = 8;
+    assert(view[1] === 7 && view.byteLength === 8);
+
+    const floats = new Float64Array(4);
+    floats.set([1, 2], 1);
+    floats.set(new Int8Array([-1]), 3);
+    assert(floats.join() === '0,1,2,-1' && floats.indexOf(2) === 2 && floats.includes(-1));
+    assert(floats.map(x => x * 2).join() === '0,2,4,-2' && floats.filter(x => x > 0).length === 2);
+    assert([...new Int8Array([5, 6])].join() === '5,6' && new Uint8Array(3).fill(7, 1).join() === '0,7,7');
+    assert(Object.getPrototypeOf(Int8Array.prototype) === Object.getPrototypeOf(Uint8Array.prototype));
+
+    const shorts = new Int16Array([5, -1, 3]);
+    assert([...shorts.keys()].join() === '0,1,2' && [...shorts.entries()][1].join() === '1,-1');
+    assert(shorts.toSorted().join() === '-1,3,5' && shorts.toReversed().join() === '3,-1,5' && shorts.join() === '5,-1,3');
+    assert(shorts.with(-1, 9).join() === '5,-1,9' && shorts.sort((a, b) => b - a) === shorts && shorts.join() === '5,3,-1');
+    const signed = new Float64Array([NaN, 0, -0, -Infinity]).sort();
+    assert(signed[0] === -Infinity && Object.is(signed[1], -0) && Number.isNaN(signed[3]));
+    assert(new Uint8Array([1, 2, 3, 4, 5]).copyWithin(1, 0, 3).join() === '1,1,2,3,5');
+    assert(new Uint8Array([1, 2]).toLocaleString() === '1,2');
+    assert(Uint8Array.from([1, 2], x => x * 2).join() === '2,4' && Int8Array.of(1, 200).join() === '1,-56');
+    class Bytes extends Uint8Array {}
+    assert(Bytes.from(new Set([7])) instanceof Bytes && Bytes.of(1).length === 1);
+
+    let threw = false;
+    try { new Int32Array(new ArrayBuffer(7)); } catch (e) { threw = e instanceof RangeError; }
+    assert(threw);
+
+    const frozen = new Uint8Array(1);
+    threw = false;
+    try { Object.freeze(frozen); } catch (e) { threw = e instanceof TypeError; }
+    assert(threw && !Object.isExtensible(frozen) && Object.isFrozen(Object.freeze(new Uint8Array(0))));
+    assert(!Reflect.defineProperty(frozen, 0, { value: 1, writable: false }));
+    assert(!Reflect.defineProperty(frozen, 1, { value: 1, writable: true, enumerable: true, configurable: true }));
+    assert(Reflect.defineProperty(frozen, 0, { value: 2, writable: true, enumerable: true, configurable: true }) && frozen[0] === 2);
+
+    threw = false;
+    try { new Float64Array(2 ** 53 - 1); } catch (e) { threw = e instanceof RangeError; }
+    assert(threw);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    dataview,
+    r#"
+    const view = new DataView(new ArrayBuffer(8));
+    view.setUint16(0, 0x1234);
+    view.setUint16(2, 0x1234, true);
+    assert(view.getUint8(0) === 0x12 && view.getUint8(2) === 0x34 && view.getInt16(0, true) === 0x3412);
+    view.setFloat32(4, 1.5);
+    assert(view.getFloat32(4) === 1.5 && view.byteLength === 8);
+    view.setBigInt64(0, -2n, true);
+    assert(view.getBigInt64(0, true) === -2n && view.getInt8(0) === -2);
+    let threw = false;
+    try { view.getInt32(6); } catch (e) { threw = e instanceof RangeError; }
+    assert(threw);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    resizable_arraybuffer,
+    r#"
+    const rab = new ArrayBuffer(4, { maxByteLength: 16 });
+    const tracking = new Uint8Array(rab);
+    const fixed = new Uint8Array(rab, 0, 4);
+    const view = new DataView(rab, 1);
+    assert(rab.resizable && rab.maxByteLength === 16 && ArrayBuffer.isView(view) && !ArrayBuffer.isView(rab));
+    rab.resize(8);
+    assert(tracking.length === 8 && fixed.length === 4 && view.byteLength === 7);
+    tracking.fill(1);
+    rab.resize(2);
+    assert(tracking.length === 2 && fixed.length === 0 && fixed.byteOffset === 0);
+    let threw = false;
+    try { fixed.fill(0); } catch (e) { threw = e instanceof TypeError; }
+    assert(threw);
+    rab.resize(4);
+    assert(tracking.join() === '1,1,0,0' && fixed.length === 4);
+
+    const buffer = new Uint8Array([1, 2, 3, 4]).buffer;
+    assert(new Uint8Array(buffer.slice(1, -1)).join() === '2,3');
+    const bytes = new Uint8Array(buffer);
+    const transferred = buffer.transfer(6);
+    assert(buffer.detached && buffer.byteLength === 0 && bytes.length === 0);
+    assert(transferred.byteLength === 6 && new Uint8Array(transferred).join() === '1,2,3,4,0,0');
+    threw = false;
+    try { buffer.slice(); } catch (e) { threw = e instanceof TypeError; }
+    assert(threw);
+    assert(!rab.transferToFixedLength().resizable && rab.detached && tracking.length === 0);
+
+    const sab = new SharedArrayBuffer(4, { maxByteLength: 8 });
+    const shared = new Int8Array(sab);
+    sab.grow(6);
+    assert(sab.growable && shared.length === 6 && sab.slice(2) instanceof SharedArrayBuffer);
+    threw = false;
+    try { sab.grow(5); } catch (e) { threw = e instanceof RangeError; }
+    assert(threw);
+
+    // Resizable buffers only allocate what is in use, and lengths that cannot be allocated throw
+    const huge = new ArrayBuffer(1, { maxByteLength: Number.MAX_SAFE_INTEGER });
+    threw = false;
+    try { huge.resize(Number.MAX_SAFE_INTEGER); } catch (e) { threw = e instanceof RangeError; }
+    assert(threw && huge.byteLength === 1);
+    huge.resize(3);
+    assert(new Uint8Array(huge).join() === '0,0,0' && huge.transfer(2).maxByteLength === Number.MAX_SAFE_INTEGER);
+    threw = false;
+    try { new ArrayBuffer(Number.MAX_SAFE_INTEGER); } catch (e) { threw = e instanceof RangeError; }
+    assert(threw);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    tagged_templates,
+    r#"
+    function tag(strings, ...substitutions) {
+        return { strings, substitutions };
+    }
+    const { strings, substitutions } = tag`a${1}\n${2}\xzz`;
+    assert(strings.length === 3 && strings[0] === 'a' && strings[1] === '\n' && strings[2] === undefined);
+    assert(strings.raw.join() === 'a,\\n,\\xzz' && substitutions.join() === '1,2');
+    assert(Object.isFrozen(strings) && Object.isFrozen(strings.raw));
+    assert(!Object.getOwnPropertyDescriptor(strings, 'raw').enumerable);
+
+    const sites = [];
+    for (let i = 0; i < 2; i++) sites.push(tag`x`.strings);
+    assert(sites[0] === sites[1] && tag`x`.strings !== sites[0]);
+
+    const obj = { prefix: '>', tag(strings, value) { return this.prefix + strings[0] + value; } };
+    assert(obj.tag`a${1}` === '>a1');
+
+    assert(String.raw`C:\users\n${1}` === 'C:\\users\\n1');
+    assert(String.raw({ raw: ['a', 'b', 'c'] }, 1, 2, 3) === 'a1b2c');
+    assert(`\u{1F600}\uD83D\uDE00\
+` === '\u{1F600}\u{1F600}');
+    assert('a\
+b' === 'ab' && "\
+" === '' && `a\
+b` === 'ab' && `\
+${1}\
+` === '1');
+    assert(String.raw`a\
+b` === 'a\\\nb');
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    strict_mode,
+    r#"
+    function sloppy() { return this; }
+    function strict() { 'use strict'; return this; }
+    const arrow = () => { 'use strict'; return this; };
+    assert(sloppy() === globalThis && strict() === undefined && arrow() === globalThis);
+    class A { m() { return this; } }
+    const m = new A().m;
+    assert(m() === undefined && strict.call(1) === 1);
+
+    function throws(f, ctor) {
+        try { f(); } catch (e) { return e instanceof ctor; }
+        return false;
+    }
+    const frozen = Object.freeze({ a: 1 });
+    assert(throws(() => { 'use strict'; frozen.a = 2; }, TypeError));
+    assert(throws(() => { 'use strict'; frozen.b = 2; }, TypeError));
+    assert(throws(() => { 'use strict'; Object.freeze([1])[0] = 2; }, TypeError));
+    assert(throws(() => { 'use strict'; new Proxy({}, { set: () => false }).x = 1; }, TypeError));
+    assert(throws(() => { 'use strict'; ({ get x() { return 1; } }).x = 2; }, TypeError));
+    const inheritsReadonly = Object.create(frozen);
+    assert(throws(() => { 'use strict'; inheritsReadonly.a = 2; }, TypeError) && !inheritsReadonly.hasOwnProperty('a'));
+    assert(throws(function () { 'use strict'; return arguments.callee; }, TypeError));
+    frozen.a = 2;
+    frozen.b = 2;
+    assert(frozen.a === 1 && frozen.b === undefined);
+
+    assert(throws(() => { 'use strict'; undeclared = 1; }, ReferenceError));
+    assert(throws(() => { class B { static f() { undeclared = 1; } } B.f(); }, ReferenceError));
+    implicitGlobal = 1;
+    assert(globalThis.implicitGlobal === 1);
+    (() => { 'use strict'; implicitGlobal = 2; })();
+    assert(implicitGlobal === 2);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    super_property_access,
+    r#"
+    class A {
+        constructor(x) { this.x = x; }
+        greet(p) { return p + 'A' + this.x; }
+        get g() { return 'getA' + this.x; }
+        set s(v) { this.sv = 'A' + v; }
+        static st() { return 'staticA' + this.name; }
+    }
+    A.prototype.val = 5;
+    class B extends A {
+        constructor(x) { super(x); this.y = super.greet('c'); }
+        greet(p) { return 'B(' + super.greet(p) + ')'; }
+        get g() { return 'getB' + super.g; }
+        setS(v) { super.s = v; return this.sv; }
+        arrow() { return (() => super.greet('arrow'))(); }
+        computed(k) { return super[k]; }
+        assign() { super.z = 3; return this.hasOwnProperty('z') && A.prototype.z === undefined; }
+        static st() { return 'B' + super.st(); }
+        compound() { return [super.val += 2, this.val, super.val++, ++super.val, this.val, super.s *= 2, this.sv]; }
+        update(k) { return [super[k]--, this[k], String(super.big++), String(this.big)]; }
+    }
+    A.prototype.big = 1n;
+    const b = new B(1);
+    assert(b.greet('p') === 'B(pA1)' && b.g === 'getBgetA1' && b.y === 'cA1');
+    assert(b.arrow() === 'arrowA1' && b.computed('val') === 5);
+    assert(b.setS(4) === 'A4' && b.assign() && B.st() === 'BstaticAB');
+    assert(b.compound().join() === '7,7,5,6,6,NaN,ANaN' && A.prototype.val === 5);
+    assert(b.update('val').join() === '5,4,1,2' && A.prototype.big === 1n);
+
+    const base = { hi() { return 'base ' + this.n; } };
+    const o = { __proto__: base, n: 2, hi() { return 'o>' + super.hi(); } };
+    const o2 = { __proto__: o, n: 3, hi() { return 'o2>' + super.hi(); } };
+    assert(o.hi() === 'o>base 2' && o2.hi() === 'o2>o>base 3');
+
+    const gets = [];
+    const lp = { a: null, b: 0, c: 5, get g() { gets.push('g'); return 1; } };
+    const lo = {
+        __proto__: lp,
+        logical() { return [super.a ??= 1, super.b ||= 2, super.c &&= 3, super.c ??= 4, super.g ||= 5]; },
+        del(k) { try { delete super[k()]; } catch (e) { return e instanceof ReferenceError; } },
+    };
+    assert(lo.logical().join() === '1,2,3,5,1' && gets.join() === 'g');
+    assert(lo.a === 1 && lo.b === 2 && lo.c === 3 && lp.a === null && !lo.hasOwnProperty('g'));
+    let keyCalls = 0;
+    assert(lo.del(() => keyCalls++) && keyCalls === 1);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    destructuring_assignment,
+    r#"
+    let a = {}, obj = {}, rest = {}, k = 'q', arr = [1, 2, 3, 4];
+    const r = ([a.b, obj[k], ...rest.c] = arr);
+    assert(a.b === 1 && obj.q === 2 && rest.c.join() === '3,4' && r === arr);
+
+    const self = { m(o) { ({ x: this.x, y: this.y = 9 } = o); return this; } };
+    assert(self.m({ x: 1 }).x === 1 && self.y === 9);
+
+    let p, q, s, t, u, x, y, side = 0;
+    [p = side++, , q = 2] = [1, 0];
+    assert(p === 1 && q === 2 && side === 0);
+    ({ s = 2, t: [u = 7] = [] } = {});
+    assert(s === 2 && u === 7);
+    [{ x = 1 }, [y]] = [{}, [3]];
+    assert(x === 1 && y === 3);
+
+    function outer() {
+        let captured;
+        (() => { [captured, globalTarget] = [10, 20]; })();
+        return captured;
+    }
+    assert(outer() === 10 && globalTarget === 20);
+
+    let first, others;
+    ({ a: first, ...others } = { a: 1, b: 2 });
+    assert(first === 1 && Object.keys(others).join() === 'b');
+    const [head, ...tail] = 'abc';
+    assert(head === 'a' && tail.join() === 'b,c');
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    object_spread_and_proto_literal,
+    r#"
+    const sym = Symbol('s');
+    let calls = 0;
+    const src = { a: 1, get b() { calls++; return 2; }, [sym]: 3 };
+    Object.defineProperty(src, 'hidden', { value: 4, enumerable: false });
+    const o = { x: 0, ...src, ...null, ...undefined, ...'hi', ...[7] };
+    assert(o.a === 1 && o.b === 2 && o[sym] === 3 && calls === 1);
+    assert(!o.hasOwnProperty('hidden') && o.x === 0);
+    assert(o[0] === 7 && o[1] === 'i' && !o.hasOwnProperty('length'));
+    assert(Object.getOwnPropertyDescriptor(o, 'b').value === 2);
+
+    const override = { a: 1, ...{ a: 2 } };
+    assert(override.a === 2);
+
+    const proto = { greet() { return 'hi'; } };
+    const p = { __proto__: proto, own: 1 };
+    assert(Object.getPrototypeOf(p) === proto && p.greet() === 'hi');
+    assert(!p.hasOwnProperty('__proto__') && p.__proto__ === proto);
+
+    const computed = { ['__proto__']: 5 };
+    assert(computed.hasOwnProperty('__proto__') && Object.getPrototypeOf(computed) === Object.prototype);
+
+    const shorthand = (__proto__ => ({ __proto__ }))(proto);
+    assert(shorthand.hasOwnProperty('__proto__') && Object.getPrototypeOf(shorthand) === Object.prototype);
+    const method = { __proto__() {} };
+    assert(method.hasOwnProperty('__proto__') && Object.getPrototypeOf(method) === Object.prototype);
+    let first, second;
+    ({ __proto__: first, __proto__: second } = p);
+    assert(first === proto && second === proto && (({ __proto__ }) => __proto__)(p) === proto);
+
+    const r = {};
+    r.__proto__ = proto;
+    assert(Object.getPrototypeOf(r) === proto && !r.hasOwnProperty('__proto__'));
+
+    const bare = Object.create(null);
+    bare.__proto__ = 1;
+    assert(bare.__proto__ === 1 && Object.getPrototypeOf(bare) === null);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    native_catch_restores_stack,
+    r#"
+    const throwingIterable = { [Symbol.iterator]() { return { next() { throw 2; } }; } };
+    assert(Math.max(1, new Promise(() => { throw 1; }) ? 5 : 0, 3) === 5);
+    assert(Math.max(1, Promise.all(throwingIterable) ? 5 : 0, 3) === 5);
+    function nested() { [1, 2].map(() => { throw 3; }); }
+    assert(Math.max(1, new Promise(() => nested()) ? 5 : 0, 3) === 5);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    async_generator_throw_restores_stack,
+    r#"
+    assert(Math.max(1, (async function* () { null.x; })().next() ? 2 : 0, 3) === 3);
+    assert(Object.prototype.toString.call((async function* () {})()) === '[object AsyncGenerator]');
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    array_from_closes_iterators,
+    r#"
+    const throwingIterable = { [Symbol.iterator]() { return { next() { throw 2; } }; } };
+    assert(Math.max(1, Array.fromAsync(throwingIterable) ? 5 : 0, 3) === 5);
+
+    let closed = 0;
+    const iterable = {
+        [Symbol.iterator]() {
+            let i = 0;
+            return { next() { return { value: i++, done: false }; }, return() { closed++; return {}; } };
+        }
+    };
+    let error;
+    try {
+        Array.from(iterable, (v) => { if (v === 2) throw 'stop'; return v; });
+    } catch (e) {
+        error = e;
+    }
+    assert(error === 'stop' && closed === 1);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    iterator_destructuring_and_closing,
+    r#"
+    const order = [];
+    function* gen() { try { yield 1; yield 2; yield 3; } finally { order.push('gen'); } }
+    function f() { for (const x of gen()) { try { return x; } finally { order.push('body'); } } }
+    assert(f() === 1 && order.join() === 'body,gen');
+
+    const [a, b] = gen();
+    let c, d, rest;
+    [c, ...rest] = new Set([1, 2, 3]);
+    [, d] = new Set([4, 5]);
+    assert(a === 1 && b === 2 && c === 1 && rest.length === 2 && rest[1] === 3 && d === 5);
+
+    let closed = 0;
+    const closing = { [Symbol.iterator]() { return { next() { return { value: 7, done: false }; }, return() { closed++; return {}; } }; } };
+    const [z] = closing;
+    assert(z === 7 && closed === 1);
+
+    const bad = { [Symbol.iterator]() { return { next() { return { value: 1, done: false }; }, return() { return 1; } }; } };
+    let error;
+    try { for (const v of bad) break; } catch (e) { error = e; }
+    assert(error instanceof TypeError);
+    try { for (const v of bad) throw 'original'; } catch (e) { error = e; }
+    assert(error === 'original');
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    destructuring_assignment_computed_keys,
+    r#"
+    const order = [];
+    const source = { get a() { order.push('get a'); return 1; }, get b() { order.push('get b'); return undefined; } };
+    let a, b;
+    ({ [(order.push('key a'), 'a')]: a, ['b']: b = 2 } = source);
+    assert(a === 1 && b === 2 && order.join() === 'key a,get a,get b');
+
+    let x, rest;
+    [x, ...rest] = new Set([1, 2, 3]);
+    assert(x === 1 && rest.length === 2 && rest[0] === 2 && rest[1] === 3);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    rejected_assignments_and_deletions,
+    r#"
+    function throwsTypeError(f) {
+        try { f(); } catch (e) { return e instanceof TypeError; }
+        return false;
+    }
+    function f() {}
+    const frozen = Object.freeze({ a: 1 });
+
+    (function () {
+        'use strict';
+        assert(Reflect.set(frozen, 'a', 2) === false);
+        assert(Reflect.set(new Proxy({}, { set() { return false; } }), 'x', 1) === false);
+        assert(Reflect.deleteProperty({ a: undefined }, 'a') === true);
+        assert(Reflect.deleteProperty(frozen, 'a') === false);
+        assert(throwsTypeError(() => { f.name = 'x'; }));
+        assert(throwsTypeError(() => { (5).foo = 1; }));
+        assert(throwsTypeError(() => { 'abc'[0] = 'x'; }));
+        assert(throwsTypeError(() => { frozen.a += 1; }));
+        assert(throwsTypeError(() => { Object.preventExtensions({}).b = 1; }));
+        assert(throwsTypeError(() => delete frozen.a));
+    })();
+
+    f.name = 'x';
+    (5).foo = 1;
+    frozen.a = 2;
+    assert(f.name === 'f' && frozen.a === 1 && (delete frozen.a) === false);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    reflect_set_and_define_property,
+    r#"
+    const target = {}, receiver = {};
+    assert(Reflect.set(target, 'x', 1, receiver) && receiver.x === 1 && !('x' in target));
+    assert(Reflect.set(Object.freeze({ a: 1 }), 'a', 2) === false);
+    assert(Reflect.defineProperty(Object.freeze({ a: 1 }), 'a', { value: 2 }) === false);
+    assert(Reflect.defineProperty({}, 'a', { value: 2 }) === true);
+
+    let rec;
+    const p = new Proxy({}, { set(t, k, v, r) { rec = r; return true; } });
+    p.x = 1;
+    assert(rec === p);
+    Reflect.set(p, 'x', 1, receiver);
+    assert(rec === receiver);
+
+    const traps = [];
+    const q = new Proxy({}, {
+        set() { traps.push('set'); return true; },
+        defineProperty(t, k, d) { traps.push('defineProperty'); return Reflect.defineProperty(t, k, d); }
+    });
+    Object.defineProperty(q, 'k', { value: 1, writable: true, enumerable: true, configurable: true });
+    assert(traps.join() === 'defineProperty');
+    assert(Reflect.defineProperty(new Proxy({}, { defineProperty() { return false; } }), 'x', { value: 1 }) === false);
+
+    function f() {}
+    Object.defineProperty(f, 'name', { value: 'g' });
+    assert(f.name === 'g');
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    proto_assignment_and_class_heritage,
+    r#"
+    const proto = { marker: 1 };
+    const o = {};
+    assert(Reflect.set(o, '__proto__', proto) && Object.getPrototypeOf(o) === proto && Object.keys(o).length === 0);
+    const assigned = Object.assign({}, JSON.parse('{"__proto__":{"x":1}}'));
+    assert(assigned.x === 1 && Object.keys(assigned).length === 0);
+
+    delete Object.prototype.__proto__;
+    class A { m() { return 'a'; } }
+    class B extends A {}
+    const b = new B();
+    assert(b.m() === 'a' && b instanceof A && Object.getPrototypeOf(B) === A);
+
+    const plain = {};
+    plain.__proto__ = proto;
+    assert(Object.getPrototypeOf(plain) === Object.prototype && plain.__proto__ === proto);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    bigint_collection_keys,
+    r#"
+    const map = new Map([[1n, 'a']]);
+    assert(map.get(1n) === 'a' && map.has(BigInt(1)) && !map.has(2n));
+    map.set(1n, 'b');
+    assert(map.get(1n) === 'b');
+    const set = new Set([10n ** 30n, 10n ** 30n]);
+    assert(set.has(10n ** 30n) && set.delete(10n ** 30n) && !set.has(10n ** 30n));
+    "#,
+    Value::undefined()
+);
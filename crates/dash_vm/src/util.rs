@@ -3,6 +3,7 @@ use std::num::FpCategory;
 use dash_middle::interner::{Symbol, sym};
 
 use crate::localscope::LocalScope;
+use crate::value::primitive::MAX_SAFE_INTEGERF;
 
 /// Marks the code path leading to this call as cold, or "unlikely"
 #[cold]
@@ -27,8 +28,8 @@ pub fn likely(b: bool) -> bool {
 }
 
 pub fn intern_f64(sc: &mut LocalScope, n: f64) -> Symbol {
-    if n.trunc() == n && n >= 0.0 && n <= usize::MAX as f64 {
-        // Happy path: no fractional part and fits in a usize
+    if n.trunc() == n && (0.0..=MAX_SAFE_INTEGERF).contains(&n) {
+        // Happy path: no fractional part and small enough for every digit to be significant
         // This can use the specialized usize interner
         return sc.intern_usize(n as usize);
     }
@@ -37,19 +38,129 @@ pub fn intern_f64(sc: &mut LocalScope, n: f64) -> Symbol {
         FpCategory::Infinite if n.is_sign_positive() => sym::Infinity,
         FpCategory::Infinite => sym::NegInfinity,
         FpCategory::Nan => sym::NaN,
-        _ if n >= 1e21f64 || n <= -1e21f64 => {
-            let mut digits = 0;
-            let mut n = n;
-            while n >= 10f64 {
-                n /= 10f64;
-                digits += 1;
-            }
-            while n <= -10f64 {
-                n /= 10f64;
-                digits += 1;
+        _ => sc.intern(format_f64(n).as_ref()),
+    }
+}
+
+/// Returns the shortest decimal digits that uniquely identify the given finite, non-zero number
+/// along with the exponent `n` such that the number is `0.d1d2d3... * 10^n`.
+/// This is the `k`, `n` and `s` of the Number::toString algorithm.
+pub fn shortest_digits(n: f64) -> (Vec<u8>, i32) {
+    // `{:e}` produces the shortest representation that round trips, e.g. `1.2345e-7`
+    let formatted = format!("{:e}", n.abs());
+    let (mantissa, exponent) = formatted.split_once('e').unwrap();
+    let digits: Vec<u8> = mantissa.bytes().filter(|&b| b != b'.').collect();
+    let k = digits.len();
+
+    // If the number lies exactly between two shortest candidates, the spec requires picking the even one,
+    // whereas the formatter rounds away from zero. That can only happen if the exact decimal expansion
+    // has one more digit than the shortest representation and that digit is a 5.
+    let longer = format!("{:.*e}", k, n.abs());
+    if longer.split_once('e').unwrap().0.ends_with('5') {
+        let (exact, exponent) = exact_digits(n.abs());
+        if exact.len() == k + 1 {
+            let down = exact[..k].to_vec();
+            return if down[k - 1] % 2 == 0 {
+                (down, exponent)
+            } else {
+                round_digits((exact, exponent), k)
+            };
+        }
+    }
+
+    (digits, exponent.parse::<i32>().unwrap() + 1)
+}
+
+/// Returns the exact decimal digits of a finite, positive number (with trailing zeros removed)
+/// along with the exponent `n` such that the number is `0.d1d2d3... * 10^n`.
+pub fn exact_digits(num: f64) -> (Vec<u8>, i32) {
+    // Every double has a finite decimal expansion with no more than 767 significant digits,
+    // so formatting with enough precision is exact
+    let formatted = format!("{:.800e}", num);
+    let (mantissa, exponent) = formatted.split_once('e').unwrap();
+    let mut digits: Vec<u8> = mantissa.bytes().filter(|&b| b != b'.').collect();
+    while digits.len() > 1 && digits.last() == Some(&b'0') {
+        digits.pop();
+    }
+    (digits, exponent.parse::<i32>().unwrap() + 1)
+}
+
+/// Rounds exact digits (as returned by `exact_digits`) to `precision` significant digits.
+/// Ties are rounded up, i.e. towards the larger `n * 10^(e - f)` as required by the spec.
+pub fn round_digits((mut digits, mut exponent): (Vec<u8>, i32), precision: usize) -> (Vec<u8>, i32) {
+    let round_up = digits.get(precision).is_some_and(|&d| d >= b'5');
+    digits.resize(precision, b'0');
+
+    if round_up {
+        let mut carry = true;
+        for digit in digits.iter_mut().rev() {
+            if *digit == b'9' {
+                *digit = b'0';
+            } else {
+                *digit += 1;
+                carry = false;
+                break;
             }
-            sc.intern(format!("{n:.0}e+{digits}").as_ref())
         }
-        _ => sc.intern(n.to_string().as_ref()),
+
+        if carry {
+            // All digits were nines (or there were no digits at all), e.g. 9.96 -> 10.0
+            digits.insert(0, b'1');
+            digits.truncate(precision.max(1));
+            exponent += 1;
+        }
     }
+
+    (digits, exponent)
+}
+
+/// Formats the exponent part of a number in exponential notation, e.g. `e+21` or `e-7`
+pub fn push_exponent(out: &mut String, exponent: i32) {
+    out.push('e');
+    out.push(if exponent < 0 { '-' } else { '+' });
+    out.push_str(&exponent.unsigned_abs().to_string());
+}
+
+/// Implements the Number::toString(x) abstract operation for radix 10
+pub fn format_f64(n: f64) -> String {
+    if n.is_nan() {
+        return String::from("NaN");
+    }
+    if n == 0.0 {
+        return String::from("0");
+    }
+    if n.is_infinite() {
+        return String::from(if n > 0.0 { "Infinity" } else { "-Infinity" });
+    }
+
+    let mut out = String::new();
+    if n < 0.0 {
+        out.push('-');
+    }
+
+    let (digits, n) = shortest_digits(n);
+    let k = digits.len() as i32;
+    let digits = std::str::from_utf8(&digits).unwrap();
+
+    if k <= n && n <= 21 {
+        out.push_str(digits);
+        out.extend(std::iter::repeat_n('0', (n - k) as usize));
+    } else if 0 < n && n <= 21 {
+        out.push_str(&digits[..n as usize]);
+        out.push('.');
+        out.push_str(&digits[n as usize..]);
+    } else if -6 < n && n <= 0 {
+        out.push_str("0.");
+        out.extend(std::iter::repeat_n('0', -n as usize));
+        out.push_str(digits);
+    } else {
+        out.push_str(&digits[..1]);
+        if k > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        push_exponent(&mut out, n - 1);
+    }
+
+    out
 }
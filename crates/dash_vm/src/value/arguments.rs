@@ -5,7 +5,7 @@ use crate::localscope::LocalScope;
 use crate::{delegate, extract};
 
 use super::Value;
-use super::object::{Object, OrdObject, PropertyValue};
+use super::object::{Object, OrdObject, PropertyDataDescriptor, PropertyValue, PropertyValueKind};
 use super::propertykey::ToPropertyKey;
 
 #[derive(Debug, Trace)]
@@ -14,9 +14,11 @@ pub struct Arguments {
 }
 
 impl Arguments {
+    /// Creates the `arguments` object of a call. Accessing `callee` on the `arguments` object of a strict function throws.
     pub fn new(
         scope: &mut LocalScope<'_>,
         args: impl IntoIterator<IntoIter = impl ExactSizeIterator<Item = Value>>,
+        is_strict: bool,
     ) -> Self {
         let args = args.into_iter();
         let len = args.len();
@@ -34,6 +36,30 @@ impl Arguments {
                 scope,
             )
             .unwrap();
+        let symbol_iterator = scope.statics.symbol_iterator;
+        object
+            .set_property(
+                symbol_iterator.to_key(scope),
+                PropertyValue::static_non_enumerable(Value::object(scope.statics.array_values)),
+                scope,
+            )
+            .unwrap();
+        if is_strict {
+            let thrower = scope.statics.throw_type_error;
+            object
+                .set_property(
+                    sym::callee.to_key(scope),
+                    PropertyValue::new(
+                        PropertyValueKind::Trap {
+                            get: Some(thrower),
+                            set: Some(thrower),
+                        },
+                        PropertyDataDescriptor::empty(),
+                    ),
+                    scope,
+                )
+                .unwrap();
+        }
 
         Self { object }
     }
@@ -44,8 +70,11 @@ impl Object for Arguments {
         object,
         get_own_property_descriptor,
         get_prototype,
+        prevent_extensions,
+        is_extensible,
         set_prototype,
         set_property,
+        define_property,
         own_keys,
         delete_property,
         apply
@@ -18,8 +18,8 @@ use super::object::{Object, OrdObject, PropertyValue, PropertyValueKind};
 use super::ops::conversions::ValueConversion;
 use super::primitive::array_like_keys;
 use super::propertykey::{PropertyKey, ToPropertyKey};
-use super::root_ext::RootErrExt;
-use super::{Root, Unpack, Unrooted, Value};
+use super::proxy::Proxy;
+use super::{Root, Unpack, Unrooted, Value, ValueKind};
 
 pub mod table;
 
@@ -154,6 +154,8 @@ unsafe impl Trace for ArrayInner {
 #[derive(Debug, Trace)]
 pub struct Array {
     pub items: RefCell<ArrayInner>,
+    /// Whether the `length` property is writable. Cleared when the array is frozen.
+    length_writable: Cell<bool>,
     obj: OrdObject,
 }
 
@@ -166,6 +168,7 @@ impl Array {
     pub fn from_vec(items: Vec<PropertyValue>, vm: &Vm) -> Self {
         Self {
             items: RefCell::new(ArrayInner::Dense(items)),
+            length_writable: Cell::new(true),
             obj: OrdObject::with_prototype(vm.statics.array_prototype),
         }
     }
@@ -173,6 +176,7 @@ impl Array {
     pub fn from_table(vm: &Vm, table: ArrayTable) -> Self {
         Self {
             items: RefCell::new(ArrayInner::Table(table)),
+            length_writable: Cell::new(true),
             obj: OrdObject::with_prototype(vm.statics.array_prototype),
         }
     }
@@ -181,10 +185,26 @@ impl Array {
     pub fn with_hole(len: usize, obj: OrdObject) -> Self {
         Self {
             items: RefCell::new(ArrayInner::Table(ArrayTable::with_len(len as u32))),
+            length_writable: Cell::new(true),
             obj,
         }
     }
 
+    /// Sets an element of this array, respecting the writable bit of an existing element
+    /// and refusing to create new elements if the array is not extensible.
+    fn set_element(&self, index: u32, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
+        let allowed = match self.items.borrow().get(index) {
+            Some(MaybeHoley::Some(existing)) => existing.descriptor.contains(PropertyDataDescriptor::WRITABLE),
+            Some(MaybeHoley::Hole) => self.obj.is_extensible(sc)?,
+            None => self.length_writable.get() && self.obj.is_extensible(sc)?,
+        };
+
+        if allowed {
+            self.items.borrow_mut().set(index, value);
+        }
+        Ok(allowed)
+    }
+
     /// Tries to convert this holey array into a non-holey array
     pub fn try_convert_to_non_holey(&self) {
         self.items.borrow_mut().transition_to_dense_if_no_holes();
@@ -202,6 +222,7 @@ impl Array {
     pub fn with_obj(obj: OrdObject) -> Self {
         Self {
             items: RefCell::new(ArrayInner::Dense(Vec::new())),
+            length_writable: Cell::new(true),
             obj,
         }
     }
@@ -225,27 +246,36 @@ impl Object for Array {
                 }
             }
         } else if let Some(sym::length) = key.to_js_string(sc) {
+            let descriptor = if self.length_writable.get() {
+                PropertyDataDescriptor::WRITABLE
+            } else {
+                PropertyDataDescriptor::empty()
+            };
             return Ok(Some(PropertyValue {
                 kind: PropertyValueKind::Static(Value::number(items.len() as f64)),
-                descriptor: PropertyDataDescriptor::WRITABLE,
+                descriptor,
             }));
         }
 
-        self.obj.get_property_descriptor(key, sc)
+        self.obj.get_own_property_descriptor(key, sc)
     }
 
-    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<(), Value> {
+    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
         if let Some(index) = key.index() {
             if index < MAX_LENGTH {
-                self.items.borrow_mut().set(index, value);
-
-                return Ok(());
+                return self.set_element(index, value, sc);
             }
         } else if let Some(sym::length) = key.to_js_string(sc) {
+            if !self.length_writable.get() {
+                return Ok(false);
+            }
+
+            let writable = value.descriptor.contains(PropertyDataDescriptor::WRITABLE);
             let value = value.kind().get_or_apply(sc, This::default()).root(sc)?;
             if let Ok(new_len) = u32::try_from(value.to_number(sc)? as usize) {
                 self.items.borrow_mut().resize(new_len);
-                return Ok(());
+                self.length_writable.set(writable);
+                return Ok(true);
             }
 
             throw!(sc, RangeError, "Invalid array length")
@@ -254,19 +284,21 @@ impl Object for Array {
         self.obj.set_property(key, value, sc)
     }
 
-    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<Unrooted, Value> {
+    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Value> {
         if let Some(index) = key.index() {
             if index < MAX_LENGTH {
                 let mut items = self.items.borrow_mut();
-                match items.delete(index) {
-                    Some(MaybeHoley::Some(value)) => {
-                        return value.get_or_apply(sc, This::default()).root_err(sc);
-                    }
-                    Some(MaybeHoley::Hole) | None => return Ok(Value::undefined().into()),
+                if let Some(MaybeHoley::Some(existing)) = items.get(index)
+                    && !existing.descriptor.contains(PropertyDataDescriptor::CONFIGURABLE)
+                {
+                    return Ok(false);
                 }
+
+                items.delete(index);
+                return Ok(true);
             }
         } else if let Some(sym::length) = key.to_js_string(sc) {
-            return Ok(Unrooted::new(Value::undefined()));
+            return Ok(false);
         }
 
         self.obj.delete_property(key, sc)
@@ -290,20 +322,30 @@ impl Object for Array {
         self.obj.get_prototype(sc)
     }
 
-    fn own_keys(&self, sc: &mut LocalScope<'_>, _: OwnKeysMode) -> Result<Vec<Value>, Value> {
+    delegate!(obj, prevent_extensions, is_extensible);
+
+    fn own_keys(&self, sc: &mut LocalScope<'_>, mode: OwnKeysMode) -> Result<Vec<Value>, Value> {
         let items = self.items.borrow();
         // TODO: this should not include holey indices
-        Ok(array_like_keys(sc, items.len() as usize).collect())
+        Ok(array_like_keys(sc, items.len() as usize, mode).collect())
     }
 
     extract!(self);
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrayIteratorKind {
+    Keys,
+    Values,
+    Entries,
+}
+
 #[derive(Debug, Trace)]
 pub struct ArrayIterator {
     index: Cell<usize>,
     length: usize,
     value: Value,
+    kind: ArrayIteratorKind,
     obj: OrdObject,
 }
 
@@ -314,9 +356,12 @@ impl Object for ArrayIterator {
         get_property,
         get_property_descriptor,
         set_property,
+        define_property,
         delete_property,
         set_prototype,
         get_prototype,
+        prevent_extensions,
+        is_extensible,
         own_keys
     );
 
@@ -335,12 +380,17 @@ impl Object for ArrayIterator {
 
 impl ArrayIterator {
     pub fn new(sc: &mut LocalScope, value: Value) -> Result<Self, Value> {
+        Self::with_kind(sc, value, ArrayIteratorKind::Values)
+    }
+
+    pub fn with_kind(sc: &mut LocalScope, value: Value, kind: ArrayIteratorKind) -> Result<Self, Value> {
         let length = value.length_of_array_like(sc)?;
 
         Ok(ArrayIterator {
             index: Cell::new(0),
             length,
             value,
+            kind,
             obj: OrdObject::with_prototype(sc.statics.array_iterator_prototype),
         })
     }
@@ -350,6 +400,7 @@ impl ArrayIterator {
             index: Cell::new(0),
             length: 0,
             value: Value::null(),
+            kind: ArrayIteratorKind::Values,
             obj: OrdObject::null(),
         }
     }
@@ -359,7 +410,19 @@ impl ArrayIterator {
 
         if index < self.length {
             self.index.set(index + 1);
-            self.value.get_property(index.to_key(sc), sc).map(Some)
+            let key = Value::number(index as f64);
+            match self.kind {
+                ArrayIteratorKind::Keys => Ok(Some(key.into())),
+                ArrayIteratorKind::Values => self.value.get_property(index.to_key(sc), sc).map(Some),
+                ArrayIteratorKind::Entries => {
+                    let value = self.value.get_property(index.to_key(sc), sc).root(sc)?;
+                    let entry = Array::from_vec(
+                        vec![PropertyValue::static_default(key), PropertyValue::static_default(value)],
+                        sc,
+                    );
+                    Ok(Some(Value::object(sc.register(entry)).into()))
+                }
+            }
         } else {
             Ok(None)
         }
@@ -375,6 +438,27 @@ impl ArrayIterator {
 }
 
 /// Equivalent to calling get_property, but specialized for arrays
+/// Implements the abstract operation `IsArray`, which sees through proxies.
+/// Throws a TypeError if a proxy in the chain has been revoked.
+pub fn is_array(value: Value, sc: &mut LocalScope<'_>) -> Result<bool, Value> {
+    let ValueKind::Object(mut object) = value.unpack() else {
+        return Ok(false);
+    };
+
+    while let Some(proxy) = object.extract::<Proxy>(sc) {
+        if proxy.is_revoked() {
+            throw!(
+                sc,
+                TypeError,
+                "Cannot perform 'IsArray' on a proxy that has been revoked"
+            )
+        }
+        object = proxy.target();
+    }
+
+    Ok(object.extract::<Array>(sc).is_some())
+}
+
 pub fn spec_array_get_property(scope: &mut LocalScope<'_>, target: &Value, index: usize) -> Result<Unrooted, Unrooted> {
     if let Ok(index) = u32::try_from(index)
         && index < MAX_LENGTH
@@ -399,17 +483,13 @@ pub fn spec_array_set_property(
     target: &Value,
     index: usize,
     value: PropertyValue,
-) -> Result<(), Value> {
+) -> Result<bool, Value> {
     // specialize array path
-    if let Some(arr) = target.unpack().downcast_ref::<Array>(scope) {
-        let mut inner = arr.items.borrow_mut();
-
-        if let Ok(index) = u32::try_from(index)
-            && index < MAX_LENGTH
-        {
-            inner.set(index, value);
-            return Ok(());
-        }
+    if let Some(arr) = target.unpack().downcast_ref::<Array>(scope)
+        && let Ok(index) = u32::try_from(index)
+        && index < MAX_LENGTH
+    {
+        return arr.set_element(index, value, scope);
     }
 
     target.set_property(index.to_key(scope), value, scope)
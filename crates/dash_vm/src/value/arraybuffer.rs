@@ -1,8 +1,10 @@
-use std::cell::Cell;
+use std::alloc::{self, Layout};
+use std::cell::{Cell, UnsafeCell};
 
 use dash_proc_macro::Trace;
 
 use crate::gc::ObjectId;
+use crate::gc::trace::{Trace, TraceCtxt};
 use crate::localscope::LocalScope;
 use crate::value::object::This;
 use crate::{Vm, delegate, extract};
@@ -11,62 +13,159 @@ use super::Unrooted;
 use super::function::args::CallArgs;
 use super::object::{Object, OrdObject};
 
+/// Allocates zeroed storage, or returns `None` if the allocation fails.
+/// Unlike `vec![Cell::new(0); len]`, this lets the allocator hand out lazily zeroed memory
+/// and does not abort the process for lengths that cannot be allocated.
+fn zeroed_storage(len: usize) -> Option<Vec<Cell<u8>>> {
+    if len == 0 {
+        return Some(Vec::new());
+    }
+
+    let layout = Layout::array::<Cell<u8>>(len).ok()?;
+    // SAFETY: the layout has a non-zero size
+    let ptr = unsafe { alloc::alloc_zeroed(layout) };
+    if ptr.is_null() {
+        return None;
+    }
+    // SAFETY: the pointer was allocated by the global allocator with the layout of `len` elements,
+    // all of which are initialized to zero
+    Some(unsafe { Vec::from_raw_parts(ptr.cast(), len, len) })
+}
+
+/// The backing storage of a buffer. Resizable buffers only allocate what is in use and reallocate it when growing.
+#[derive(Debug)]
+struct Storage(UnsafeCell<Vec<Cell<u8>>>);
+
+unsafe impl Trace for Storage {
+    fn trace(&self, _: &mut TraceCtxt<'_>) {}
+}
+
 #[derive(Debug, Trace)]
 pub struct ArrayBuffer {
-    storage: Vec<Cell<u8>>,
+    storage: Storage,
+    byte_length: Cell<usize>,
+    /// `None` for fixed-length buffers
+    max_byte_length: Option<usize>,
+    detached: Cell<bool>,
     obj: OrdObject,
 }
 
 impl ArrayBuffer {
     pub fn from_storage(vm: &Vm, storage: Vec<Cell<u8>>) -> Self {
         Self {
-            storage,
+            byte_length: Cell::new(storage.len()),
+            storage: Storage(UnsafeCell::new(storage)),
+            max_byte_length: None,
+            detached: Cell::new(false),
             obj: OrdObject::with_prototype(vm.statics.arraybuffer_prototype),
         }
     }
 
     pub fn new(vm: &Vm) -> Self {
-        Self::with_capacity(0, OrdObject::with_prototype(vm.statics.arraybuffer_prototype))
+        Self::from_storage(vm, Vec::new())
     }
 
-    pub fn with_capacity(capacity: usize, obj: OrdObject) -> Self {
-        Self {
-            storage: vec![Cell::new(0); capacity],
+    /// Creates a fixed-length buffer, or returns `None` if the storage cannot be allocated
+    pub fn with_capacity(capacity: usize, obj: OrdObject) -> Option<Self> {
+        Self::with_max_byte_length(capacity, None, obj)
+    }
+
+    /// Creates a resizable buffer, or returns `None` if the storage cannot be allocated.
+    /// The caller must ensure that `byte_length <= max_byte_length`.
+    pub fn resizable(byte_length: usize, max_byte_length: usize, obj: OrdObject) -> Option<Self> {
+        Self::with_max_byte_length(byte_length, Some(max_byte_length), obj)
+    }
+
+    fn with_max_byte_length(byte_length: usize, max_byte_length: Option<usize>, obj: OrdObject) -> Option<Self> {
+        Some(Self {
+            storage: Storage(UnsafeCell::new(zeroed_storage(byte_length)?)),
+            byte_length: Cell::new(byte_length),
+            max_byte_length,
+            detached: Cell::new(false),
             obj,
-        }
+        })
     }
 
     pub fn empty() -> Self {
-        Self {
-            storage: Vec::new(),
-            obj: OrdObject::null(),
-        }
+        Self::with_capacity(0, OrdObject::null()).unwrap()
     }
 
     pub fn storage(&self) -> &[Cell<u8>] {
-        &self.storage
+        // SAFETY: the storage is only reallocated in `resize`, whose caller guarantees that no slices are alive
+        let storage = unsafe { &*self.storage.0.get() };
+        &storage[..self.byte_length.get()]
     }
 
     pub fn len(&self) -> usize {
-        self.storage.len()
+        self.byte_length.get()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.storage.len() == 0
+        self.len() == 0
+    }
+
+    pub fn is_resizable(&self) -> bool {
+        self.max_byte_length.is_some()
+    }
+
+    /// Returns the maximum byte length, which is the byte length for fixed-length buffers
+    pub fn max_byte_length(&self) -> usize {
+        self.max_byte_length.unwrap_or(self.len())
+    }
+
+    pub fn is_detached(&self) -> bool {
+        self.detached.get()
+    }
+
+    /// Detaches the buffer, which makes it (and all views of it) have a length of zero.
+    /// The storage is freed once the buffer is garbage collected.
+    pub fn detach(&self) {
+        self.detached.set(true);
+        self.byte_length.set(0);
+    }
+
+    /// Changes the byte length of a resizable buffer. The caller must ensure that the buffer is resizable,
+    /// not detached and that the new length does not exceed the maximum byte length.
+    ///
+    /// Returns `false` if the storage could not be grown, in which case the buffer is left unchanged.
+    ///
+    /// # Safety
+    /// Growing may reallocate the storage, so no slices returned by [`ArrayBuffer::storage`] may be alive.
+    pub unsafe fn resize(&self, byte_length: usize) -> bool {
+        assert!(byte_length <= self.max_byte_length.unwrap_or(0));
+        // SAFETY: the caller guarantees that there are no other references to the storage
+        let storage = unsafe { &mut *self.storage.0.get() };
+
+        if let Some(additional) = byte_length.checked_sub(storage.len()) {
+            if storage.try_reserve(additional).is_err() {
+                return false;
+            }
+            storage.resize_with(byte_length, Default::default);
+        }
+
+        // Bytes that come back into view when growing again must be zero
+        for byte in storage.get(byte_length..self.len()).unwrap_or_default() {
+            byte.set(0);
+        }
+        self.byte_length.set(byte_length);
+        true
     }
 }
 
 impl Object for ArrayBuffer {
     delegate!(
         obj,
-        get_own_property_descriptor, // TODO: byteLength
+        get_own_property_descriptor,
         get_property,
         get_property_descriptor,
         set_property,
+        define_property,
         delete_property,
         set_prototype,
         get_prototype,
-        own_keys // TODO: byteLength
+        prevent_extensions,
+        is_extensible,
+        own_keys
     );
 
     fn apply(
@@ -81,3 +180,40 @@ impl Object for ArrayBuffer {
 
     extract!(self);
 }
+
+/// A `SharedArrayBuffer`. Views can extract the underlying [`ArrayBuffer`] like with any other buffer,
+/// but it cannot be detached and only grows.
+#[derive(Debug, Trace)]
+pub struct SharedArrayBuffer {
+    inner: ArrayBuffer,
+}
+
+impl SharedArrayBuffer {
+    pub fn new(inner: ArrayBuffer) -> Self {
+        Self { inner }
+    }
+
+    pub fn inner(&self) -> &ArrayBuffer {
+        &self.inner
+    }
+}
+
+impl Object for SharedArrayBuffer {
+    delegate!(
+        inner,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        apply,
+        own_keys
+    );
+
+    extract!(self, inner);
+}
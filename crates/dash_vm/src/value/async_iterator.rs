@@ -0,0 +1,50 @@
+use dash_proc_macro::Trace;
+
+use crate::{delegate, extract};
+
+use super::Value;
+use super::object::{Object, OrdObject};
+
+/// Wraps a sync iterator for use in `for await..of` loops when an object has no `Symbol.asyncIterator` method
+#[derive(Debug, Trace)]
+pub struct AsyncFromSyncIterator {
+    sync_iterator: Value,
+    obj: OrdObject,
+}
+
+impl AsyncFromSyncIterator {
+    pub fn new(sync_iterator: Value, obj: OrdObject) -> Self {
+        Self { sync_iterator, obj }
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            sync_iterator: Value::undefined(),
+            obj: OrdObject::null(),
+        }
+    }
+
+    pub fn sync_iterator(&self) -> Value {
+        self.sync_iterator
+    }
+}
+
+impl Object for AsyncFromSyncIterator {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        apply,
+        own_keys
+    );
+
+    extract!(self);
+}
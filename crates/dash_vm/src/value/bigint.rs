@@ -0,0 +1,279 @@
+use std::cmp::Ordering;
+
+use dash_proc_macro::Trace;
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, Signed, ToPrimitive, Zero};
+
+use crate::gc::ObjectId;
+use crate::gc::trace::{Trace, TraceCtxt};
+use crate::localscope::LocalScope;
+use crate::value::object::{OwnKeysMode, This};
+use crate::{Vm, delegate, extract, throw};
+
+use super::boxed::BigInt as BoxedBigInt;
+use super::function::args::CallArgs;
+use super::object::{Object, PropertyValue};
+use super::ops::conversions::{PreferredType, ValueConversion};
+use super::primitive::InternalSlots;
+use super::propertykey::PropertyKey;
+use super::string::JsString;
+use super::{Typeof, Unpack, Unrooted, Value, ValueKind};
+
+/// The maximum number of bits a BigInt is allowed to have.
+///
+/// Operations that would produce a larger BigInt throw a RangeError instead of trying to allocate it.
+pub const MAX_BIGINT_BITS: u64 = 1 << 30;
+
+/// The heap allocation that backs a BigInt primitive.
+#[derive(Debug)]
+pub struct HeapBigInt(BigInt);
+
+unsafe impl Trace for HeapBigInt {
+    fn trace(&self, _: &mut TraceCtxt<'_>) {}
+}
+
+impl Object for HeapBigInt {
+    fn get_own_property_descriptor(
+        &self,
+        _key: PropertyKey,
+        _sc: &mut LocalScope,
+    ) -> Result<Option<PropertyValue>, Unrooted> {
+        Ok(None)
+    }
+
+    fn set_property(&self, _key: PropertyKey, _value: PropertyValue, _sc: &mut LocalScope) -> Result<bool, Value> {
+        Ok(false)
+    }
+
+    fn delete_property(&self, _key: PropertyKey, _sc: &mut LocalScope) -> Result<bool, Value> {
+        Ok(true)
+    }
+
+    fn set_prototype(&self, _value: Value, _sc: &mut LocalScope) -> Result<(), Value> {
+        Ok(())
+    }
+
+    fn get_prototype(&self, sc: &mut LocalScope) -> Result<Value, Value> {
+        Ok(sc.statics.bigint_prototype.into())
+    }
+
+    fn prevent_extensions(&self, _sc: &mut LocalScope) -> Result<(), Value> {
+        Ok(())
+    }
+
+    fn is_extensible(&self, _sc: &mut LocalScope) -> Result<bool, Value> {
+        Ok(false)
+    }
+
+    fn apply(
+        &self,
+        _callee: ObjectId,
+        _this: This,
+        _args: CallArgs,
+        scope: &mut LocalScope,
+    ) -> Result<Unrooted, Unrooted> {
+        throw!(scope, TypeError, "bigint is not a function")
+    }
+
+    fn own_keys(&self, _: &mut LocalScope<'_>, _: OwnKeysMode) -> Result<Vec<Value>, Value> {
+        Ok(Vec::new())
+    }
+
+    fn type_of(&self, _: &Vm) -> Typeof {
+        Typeof::Bigint
+    }
+
+    extract!(self);
+}
+
+/// A BigInt primitive value.
+///
+/// Unlike other primitives, the value itself does not fit in a [`Value`], so this is a handle to a heap allocated integer.
+/// BigInts are immutable, so they can be freely shared.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Trace)]
+pub struct JsBigInt {
+    inner: ObjectId,
+}
+
+impl JsBigInt {
+    pub fn new(sc: &mut LocalScope<'_>, value: BigInt) -> Self {
+        Self {
+            inner: sc.register(HeapBigInt(value)),
+        }
+    }
+
+    pub fn from_id(inner: ObjectId) -> Self {
+        Self { inner }
+    }
+
+    pub fn id(&self) -> ObjectId {
+        self.inner
+    }
+
+    /// Returns the integer value of this BigInt
+    pub fn get(&self, vm: &Vm) -> &BigInt {
+        &self
+            .inner
+            .extract::<HeapBigInt>(vm)
+            .expect("invariant violated: JsBigInt did not point to a HeapBigInt")
+            .0
+    }
+}
+
+impl Object for JsBigInt {
+    delegate!(
+        inner,
+        get_own_property_descriptor,
+        set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        apply,
+        own_keys,
+        type_of
+    );
+
+    fn internal_slots(&self, _: &Vm) -> Option<&dyn InternalSlots> {
+        Some(self)
+    }
+
+    extract!(self, inner);
+}
+
+impl InternalSlots for JsBigInt {
+    fn bigint_value(&self, _: &Vm) -> Option<JsBigInt> {
+        Some(*self)
+    }
+}
+
+impl ValueConversion for JsBigInt {
+    fn to_primitive(&self, _sc: &mut LocalScope, _preferred_type: Option<PreferredType>) -> Result<Value, Value> {
+        Ok(Value::bigint(*self))
+    }
+
+    fn to_number(&self, sc: &mut LocalScope) -> Result<f64, Value> {
+        throw!(sc, TypeError, "Cannot convert a BigInt value to a number")
+    }
+
+    fn to_boolean(&self, sc: &mut LocalScope<'_>) -> Result<bool, Value> {
+        Ok(!self.get(sc).is_zero())
+    }
+
+    fn to_js_string(&self, sc: &mut LocalScope) -> Result<JsString, Value> {
+        let string = self.get(sc).to_string();
+        Ok(sc.intern(string).into())
+    }
+
+    fn length_of_array_like(&self, _sc: &mut LocalScope) -> Result<usize, Value> {
+        Ok(0)
+    }
+
+    fn to_object(&self, sc: &mut LocalScope) -> Result<ObjectId, Value> {
+        let bigint = BoxedBigInt::new(sc, *self);
+        Ok(sc.register(bigint))
+    }
+}
+
+/// Implements the StringToBigInt abstract operation.
+///
+/// Returns `None` if the string is not a valid integer literal.
+pub fn parse_bigint(s: &str) -> Option<BigInt> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Some(BigInt::zero());
+    }
+
+    let (digits, radix) = match s.get(..2) {
+        Some("0x" | "0X") => (&s[2..], 16),
+        Some("0o" | "0O") => (&s[2..], 8),
+        Some("0b" | "0B") => (&s[2..], 2),
+        _ => (s.strip_prefix('+').unwrap_or(s), 10),
+    };
+
+    let (negative, digits) = match digits.strip_prefix('-') {
+        Some(digits) if radix == 10 && !s.starts_with('+') => (true, digits),
+        _ => (false, digits),
+    };
+
+    // NB: from_str_radix also accepts underscores, so validate the digits ourselves
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+
+    let value = BigInt::parse_bytes(digits.as_bytes(), radix)?;
+    Some(if negative { -value } else { value })
+}
+
+/// Parses a BigInt literal as it appears in source code (without the `n` suffix)
+pub fn parse_bigint_literal(s: &str) -> Option<BigInt> {
+    parse_bigint(&s.replace('_', ""))
+}
+
+/// Implements the NumberToBigInt abstract operation.
+///
+/// Returns `None` if the number is not an integer.
+pub fn number_to_bigint(n: f64) -> Option<BigInt> {
+    if n.is_finite() && n.trunc() == n {
+        BigInt::from_f64(n)
+    } else {
+        None
+    }
+}
+
+/// Converts a BigInt to the nearest number
+pub fn bigint_to_number(b: &BigInt) -> f64 {
+    b.to_f64().unwrap_or(if b.is_negative() {
+        f64::NEG_INFINITY
+    } else {
+        f64::INFINITY
+    })
+}
+
+/// Compares a BigInt with a number, returning `None` if the number is NaN.
+pub fn compare_bigint_number(b: &BigInt, n: f64) -> Option<Ordering> {
+    if n.is_nan() {
+        return None;
+    }
+    if n.is_infinite() {
+        return Some(if n > 0.0 { Ordering::Less } else { Ordering::Greater });
+    }
+
+    let trunc = n.trunc();
+    let ordering = b.cmp(&BigInt::from_f64(trunc)?);
+    if ordering != Ordering::Equal {
+        return Some(ordering);
+    }
+
+    // The integer parts are equal, so the fractional part of the number decides
+    Some(if n > trunc {
+        Ordering::Less
+    } else if n < trunc {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    })
+}
+
+/// Implements the ToBigInt abstract operation.
+pub fn to_bigint(value: Value, sc: &mut LocalScope<'_>) -> Result<JsBigInt, Value> {
+    let prim = value.to_primitive(sc, Some(PreferredType::Number))?;
+    match prim.unpack() {
+        ValueKind::BigInt(b) => Ok(b),
+        ValueKind::Boolean(b) => Ok(JsBigInt::new(sc, BigInt::from(b as u8))),
+        ValueKind::String(s) => match parse_bigint(s.res(sc)) {
+            Some(b) => Ok(JsBigInt::new(sc, b)),
+            None => {
+                let s = s.res(sc).to_owned();
+                throw!(sc, SyntaxError, "Cannot convert {} to a BigInt", s)
+            }
+        },
+        ValueKind::Number(n) => throw!(sc, TypeError, "Cannot convert {} to a BigInt", n),
+        ValueKind::Undefined(_) => throw!(sc, TypeError, "Cannot convert undefined to a BigInt"),
+        ValueKind::Null(_) => throw!(sc, TypeError, "Cannot convert null to a BigInt"),
+        ValueKind::Symbol(_) => throw!(sc, TypeError, "Cannot convert a Symbol value to a BigInt"),
+        ValueKind::Object(_) | ValueKind::External(_) => unreachable!("to_primitive returned an object"),
+    }
+}
@@ -7,6 +7,7 @@ use crate::{PropertyValue, Vm, delegate, extract};
 use dash_proc_macro::Trace;
 
 use super::Value;
+use super::bigint::JsBigInt;
 use super::object::{Object, OrdObject};
 use super::primitive::{InternalSlots, Symbol as PrimitiveSymbol};
 
@@ -38,9 +39,12 @@ macro_rules! boxed_primitive {
                 delegate!(
                     obj,
                     set_property,
+                    define_property,
                     delete_property,
                     set_prototype,
                     get_prototype,
+                    prevent_extensions,
+                    is_extensible,
                     own_keys,
                     apply
                 );
@@ -98,7 +102,8 @@ boxed_primitive! {
     Number number_prototype f64, // TODO: should this store a primitive::Number?
     Boolean boolean_prototype bool,
     String string_prototype JsString,
-    Symbol symbol_prototype PrimitiveSymbol
+    Symbol symbol_prototype PrimitiveSymbol,
+    BigInt bigint_prototype JsBigInt
 }
 
 impl InternalSlots for Number {
@@ -120,3 +125,9 @@ impl InternalSlots for String {
 }
 
 impl InternalSlots for Symbol {}
+
+impl InternalSlots for BigInt {
+    fn bigint_value(&self, _: &Vm) -> Option<JsBigInt> {
+        Some(self.inner)
+    }
+}
@@ -0,0 +1,75 @@
+use std::cell::Cell;
+
+use dash_proc_macro::Trace;
+
+use crate::gc::ObjectId;
+use crate::{Vm, delegate, extract};
+
+use super::arraybuffer::ArrayBuffer;
+use super::object::{Object, OrdObject};
+
+#[derive(Debug, Trace)]
+pub struct DataView {
+    arraybuffer: ObjectId,
+    byte_offset: usize,
+    /// The number of bytes, or `None` if the view covers the rest of the buffer after the byte offset
+    byte_length: Option<usize>,
+    obj: OrdObject,
+}
+
+impl DataView {
+    pub fn new(arraybuffer: ObjectId, byte_offset: usize, byte_length: Option<usize>, obj: OrdObject) -> Self {
+        Self {
+            arraybuffer,
+            byte_offset,
+            byte_length,
+            obj,
+        }
+    }
+
+    pub fn arraybuffer(&self, vm: &Vm) -> &ArrayBuffer {
+        self.arraybuffer.extract(vm).unwrap()
+    }
+
+    pub fn arraybuffer_id(&self) -> ObjectId {
+        self.arraybuffer
+    }
+
+    pub fn byte_offset(&self) -> usize {
+        self.byte_offset
+    }
+
+    /// Returns the view of the buffer, or `None` if the view is out of bounds of its buffer or the buffer is detached
+    pub fn storage<'a>(&'a self, vm: &'a Vm) -> Option<&'a [Cell<u8>]> {
+        let buffer = self.arraybuffer(vm);
+        if buffer.is_detached() {
+            return None;
+        }
+
+        let storage = buffer.storage();
+        match self.byte_length {
+            Some(byte_length) => storage.get(self.byte_offset..self.byte_offset + byte_length),
+            None => storage.get(self.byte_offset..),
+        }
+    }
+}
+
+impl Object for DataView {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        apply,
+        own_keys
+    );
+
+    extract!(self);
+}
@@ -1,15 +1,32 @@
+use std::cell::Cell;
+use std::fmt::Write;
+
 use dash_proc_macro::Trace;
 
 use crate::localscope::LocalScope;
 use crate::{delegate, extract, throw};
 
 use super::Value;
-use super::object::{OrdObject, Object};
+use super::object::{Object, OrdObject};
 use super::root_ext::RootErrExt;
 
+pub const MS_PER_SECOND: f64 = 1000.0;
+pub const MS_PER_MINUTE: f64 = 60.0 * MS_PER_SECOND;
+pub const MS_PER_HOUR: f64 = 60.0 * MS_PER_MINUTE;
+pub const MS_PER_DAY: f64 = 24.0 * MS_PER_HOUR;
+
+/// The largest time value a Date can hold (in both directions)
+const MAX_TIME: f64 = 8.64e15;
+
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
 #[derive(Debug, Trace)]
 pub struct Date {
-    pub timestamp: u64,
+    /// The time value in milliseconds since the epoch, or NaN for invalid dates
+    pub timestamp: Cell<f64>,
     object: OrdObject,
 }
 
@@ -20,7 +37,14 @@ impl Date {
         };
         let timestamp = cb(sc).root_err(sc)?;
 
-        Ok(Self { timestamp, object })
+        Ok(Self::with_timestamp(object, timestamp as f64))
+    }
+
+    pub fn with_timestamp(object: OrdObject, timestamp: f64) -> Self {
+        Self {
+            timestamp: Cell::new(time_clip(timestamp)),
+            object,
+        }
     }
 }
 
@@ -31,12 +55,486 @@ impl Object for Date {
         get_property,
         get_property_descriptor,
         set_property,
+        define_property,
         delete_property,
         set_prototype,
         get_prototype,
+        prevent_extensions,
+        is_extensible,
         apply,
         own_keys
     );
 
     extract!(self);
 }
+
+/// The individual components of a time value
+#[derive(Debug, Copy, Clone)]
+pub struct DateFields {
+    pub year: f64,
+    /// The month, starting at 0 for January
+    pub month: f64,
+    /// The day of the month, starting at 1
+    pub date: f64,
+    pub hours: f64,
+    pub minutes: f64,
+    pub seconds: f64,
+    pub milliseconds: f64,
+    /// The day of the week, starting at 0 for Sunday
+    pub weekday: f64,
+}
+
+impl DateFields {
+    /// Splits a finite time value into its components
+    pub fn from_time(t: f64) -> Self {
+        let day = (t / MS_PER_DAY).floor();
+        // Adding zero normalizes -0 to +0
+        let time = t.rem_euclid(MS_PER_DAY) + 0.0;
+        let (year, month, date) = civil_from_days(day as i64);
+
+        Self {
+            year: year as f64,
+            month: f64::from(month - 1),
+            date: f64::from(date),
+            hours: (time / MS_PER_HOUR).floor(),
+            minutes: (time / MS_PER_MINUTE).floor() % 60.0,
+            seconds: (time / MS_PER_SECOND).floor() % 60.0,
+            milliseconds: time % MS_PER_SECOND,
+            weekday: (day + 4.0).rem_euclid(7.0),
+        }
+    }
+}
+
+/// Returns the number of days since the epoch for a date in the proleptic gregorian calendar
+///
+/// <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = i64::from(month);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Returns the year, month (1-12) and day (1-31) for a number of days since the epoch
+///
+/// <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Implements the MakeTime abstract operation
+pub fn make_time(hour: f64, min: f64, sec: f64, ms: f64) -> f64 {
+    if !(hour.is_finite() && min.is_finite() && sec.is_finite() && ms.is_finite()) {
+        return f64::NAN;
+    }
+
+    hour.trunc() * MS_PER_HOUR + min.trunc() * MS_PER_MINUTE + sec.trunc() * MS_PER_SECOND + ms.trunc()
+}
+
+/// Implements the MakeDay abstract operation
+pub fn make_day(year: f64, month: f64, date: f64) -> f64 {
+    if !(year.is_finite() && month.is_finite() && date.is_finite()) {
+        return f64::NAN;
+    }
+
+    let (year, month, date) = (year.trunc(), month.trunc(), date.trunc());
+    let ym = year + (month / 12.0).floor();
+    if ym.abs() > 400_000.0 {
+        return f64::NAN;
+    }
+    let mn = month.rem_euclid(12.0);
+
+    days_from_civil(ym as i64, mn as u32 + 1, 1) as f64 + date - 1.0
+}
+
+/// Implements the MakeDate abstract operation
+pub fn make_date(day: f64, time: f64) -> f64 {
+    let tv = day * MS_PER_DAY + time;
+    if tv.is_finite() { tv } else { f64::NAN }
+}
+
+/// Implements the TimeClip abstract operation
+pub fn time_clip(time: f64) -> f64 {
+    if !time.is_finite() || time.abs() > MAX_TIME {
+        return f64::NAN;
+    }
+
+    // Adding zero normalizes -0 to +0
+    time.trunc() + 0.0
+}
+
+/// Returns the offset of local time from UTC at the given time value, in milliseconds.
+///
+/// Uses the embedder's timezone offset callback, falling back to UTC if none is set.
+pub fn local_tza(sc: &mut LocalScope<'_>, t: f64) -> Result<f64, Value> {
+    match sc.params.timezone_offset_callback {
+        Some(cb) if t.is_finite() => Ok(cb(sc, t as i64).root_err(sc)? as f64),
+        _ => Ok(0.0),
+    }
+}
+
+/// Implements the LocalTime abstract operation, converting a UTC time value to local time
+pub fn local_time(sc: &mut LocalScope<'_>, t: f64) -> Result<f64, Value> {
+    Ok(t + local_tza(sc, t)?)
+}
+
+/// Implements the UTC abstract operation, converting a local time value to UTC.
+///
+/// A local time that occurs twice because the clocks were turned back resolves to the earlier instant,
+/// and a local time that is skipped because the clocks were turned forward uses the offset from before the transition.
+pub fn utc(sc: &mut LocalScope<'_>, t: f64) -> Result<f64, Value> {
+    if !t.is_finite() {
+        return Ok(f64::NAN);
+    }
+
+    // Offsets are less than a day, so the offsets a day before and after include both sides of any transition
+    let offset_before = local_tza(sc, t - MS_PER_DAY)?;
+    let offset_after = local_tza(sc, t + MS_PER_DAY)?;
+
+    let mut earliest: Option<f64> = None;
+    for offset in [offset_before, offset_after] {
+        let instant = t - offset;
+        if local_tza(sc, instant)? == offset {
+            earliest = Some(earliest.map_or(instant, |earliest| earliest.min(instant)));
+        }
+    }
+    Ok(earliest.unwrap_or(t - offset_before))
+}
+
+fn write_year(out: &mut String, year: f64) {
+    if year < 0.0 {
+        let _ = write!(out, "-{:04}", -year);
+    } else {
+        let _ = write!(out, "{year:04}");
+    }
+}
+
+/// Formats the date portion of a local time value, e.g. `Sun Oct 18 2026`
+pub fn format_date(fields: &DateFields) -> String {
+    let mut out = format!(
+        "{} {} {:02} ",
+        WEEKDAYS[fields.weekday as usize], MONTHS[fields.month as usize], fields.date
+    );
+    write_year(&mut out, fields.year);
+    out
+}
+
+/// Formats the time portion of a local time value along with the timezone, e.g. `12:00:00 GMT+0200`
+pub fn format_time(fields: &DateFields, offset: f64) -> String {
+    let sign = if offset < 0.0 { '-' } else { '+' };
+    let offset = (offset.abs() / MS_PER_MINUTE) as u32;
+
+    format!(
+        "{:02}:{:02}:{:02} GMT{sign}{:02}{:02}",
+        fields.hours,
+        fields.minutes,
+        fields.seconds,
+        offset / 60,
+        offset % 60
+    )
+}
+
+/// Formats a UTC time value as specified by `Date.prototype.toUTCString`, e.g. `Sun, 18 Oct 2026 12:00:00 GMT`
+pub fn format_utc(fields: &DateFields) -> String {
+    let mut out = format!(
+        "{}, {:02} {} ",
+        WEEKDAYS[fields.weekday as usize], fields.date, MONTHS[fields.month as usize]
+    );
+    write_year(&mut out, fields.year);
+    let _ = write!(
+        out,
+        " {:02}:{:02}:{:02} GMT",
+        fields.hours, fields.minutes, fields.seconds
+    );
+    out
+}
+
+/// Formats a UTC time value in the date time string format, e.g. `2026-10-18T12:00:00.000Z`
+pub fn format_iso(fields: &DateFields) -> String {
+    let mut out = String::new();
+    if (0.0..=9999.0).contains(&fields.year) {
+        let _ = write!(out, "{:04}", fields.year);
+    } else {
+        let sign = if fields.year < 0.0 { '-' } else { '+' };
+        let _ = write!(out, "{sign}{:06}", fields.year.abs());
+    }
+
+    let _ = write!(
+        out,
+        "-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        fields.month + 1.0,
+        fields.date,
+        fields.hours,
+        fields.minutes,
+        fields.seconds,
+        fields.milliseconds
+    );
+    out
+}
+
+/// The result of parsing a date string
+#[derive(Debug, Copy, Clone)]
+pub struct ParsedDate {
+    pub time: f64,
+    /// Whether the string did not specify a timezone and must be interpreted as local time
+    pub local: bool,
+}
+
+/// Parses a date string, first trying the date time string format and then falling back
+/// to the formats produced by `toString` and `toUTCString`.
+pub fn parse_date(s: &str) -> Option<ParsedDate> {
+    let s = s.trim();
+    parse_iso_date(s).or_else(|| parse_fallback_date(s))
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    idx: usize,
+}
+
+impl Cursor<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.idx).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.idx += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Reads exactly `count` ASCII digits
+    fn digits(&mut self, count: usize) -> Option<u32> {
+        let digits = self.bytes.get(self.idx..self.idx + count)?;
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        self.idx += count;
+        Some(digits.iter().fold(0, |acc, d| acc * 10 + u32::from(d - b'0')))
+    }
+}
+
+/// Parses the date time string format (`YYYY-MM-DDTHH:mm:ss.sssZ` and its shorter forms)
+fn parse_iso_date(s: &str) -> Option<ParsedDate> {
+    let mut cursor = Cursor {
+        bytes: s.as_bytes(),
+        idx: 0,
+    };
+
+    let year = match cursor.peek()? {
+        sign @ (b'+' | b'-') => {
+            cursor.idx += 1;
+            let year = i64::from(cursor.digits(6)?);
+            if sign == b'-' {
+                if year == 0 {
+                    // -000000 is not a valid year
+                    return None;
+                }
+                -year
+            } else {
+                year
+            }
+        }
+        _ => i64::from(cursor.digits(4)?),
+    };
+
+    let mut month = 1;
+    let mut day = 1;
+    if cursor.eat(b'-') {
+        month = cursor.digits(2)?;
+        if cursor.eat(b'-') {
+            day = cursor.digits(2)?;
+        }
+    }
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let (mut hours, mut minutes, mut seconds, mut ms) = (0, 0, 0, 0);
+    let mut has_time = false;
+    let mut offset = None;
+
+    if cursor.eat(b'T') || cursor.eat(b't') || cursor.eat(b' ') {
+        has_time = true;
+        hours = cursor.digits(2)?;
+        if !cursor.eat(b':') {
+            return None;
+        }
+        minutes = cursor.digits(2)?;
+        if cursor.eat(b':') {
+            seconds = cursor.digits(2)?;
+            if cursor.eat(b'.') {
+                let start = cursor.idx;
+                while cursor.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    cursor.idx += 1;
+                }
+                let fraction = &s[start..cursor.idx];
+                if fraction.is_empty() {
+                    return None;
+                }
+                // Only the first three digits are significant
+                ms = format!("{fraction:0<3}")[..3].parse().ok()?;
+            }
+        }
+
+        if cursor.eat(b'Z') || cursor.eat(b'z') {
+            offset = Some(0.0);
+        } else if let Some(sign @ (b'+' | b'-')) = cursor.peek() {
+            cursor.idx += 1;
+            let offset_hours = cursor.digits(2)?;
+            cursor.eat(b':');
+            let offset_minutes = cursor.digits(2)?;
+            let value = f64::from(offset_hours) * MS_PER_HOUR + f64::from(offset_minutes) * MS_PER_MINUTE;
+            offset = Some(if sign == b'-' { -value } else { value });
+        }
+    }
+
+    if cursor.idx != s.len()
+        || hours > 24
+        || minutes > 59
+        || seconds > 59
+        || (hours == 24 && (minutes, seconds, ms) != (0, 0, 0))
+    {
+        return None;
+    }
+
+    let day = days_from_civil(year, month, day) as f64;
+    let time = make_time(f64::from(hours), f64::from(minutes), f64::from(seconds), f64::from(ms));
+    let time = make_date(day, time);
+
+    Some(match offset {
+        Some(offset) => ParsedDate {
+            time: time - offset,
+            local: false,
+        },
+        // Date-only forms are interpreted as UTC, date-time forms as local time
+        None => ParsedDate { time, local: has_time },
+    })
+}
+
+/// Parses loosely formatted dates such as `Sun Oct 18 2026 12:00:00 GMT+0200`, `Sun, 18 Oct 2026 12:00:00 GMT`,
+/// `October 18, 2026 12:00` or `10/18/2026`
+fn parse_fallback_date(s: &str) -> Option<ParsedDate> {
+    let mut year = None;
+    let mut month = None;
+    let mut day = None;
+    let (mut hours, mut minutes, mut seconds) = (0, 0, 0);
+    let mut pm = None;
+    let mut offset = None;
+
+    // Anything in parentheses is a comment (usually the timezone name)
+    let s = s.split('(').next()?;
+
+    for token in s
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty())
+    {
+        let lower = token.to_ascii_lowercase();
+
+        if let Some(rest) = ["gmt", "utc", "z"].iter().find_map(|tz| lower.strip_prefix(tz)) {
+            offset = Some(0.0);
+            if !rest.is_empty() {
+                offset = Some(parse_offset(rest)?);
+            }
+        } else if token.starts_with(['+', '-']) && offset.is_some() {
+            offset = Some(parse_offset(token)?);
+        } else if lower == "am" || lower == "pm" {
+            pm = Some(lower == "pm");
+        } else if token.contains(':') {
+            let mut parts = token.split(':');
+            hours = parts.next()?.parse().ok()?;
+            minutes = parts.next()?.parse().ok()?;
+            seconds = parts.next().map_or(Some(0), |s| s.parse().ok())?;
+        } else if token.contains('/') {
+            let parts = token.split('/').map(|p| p.parse().ok()).collect::<Option<Vec<i64>>>()?;
+            let [a, b, c] = parts[..] else { return None };
+            if token.find('/')? == 4 {
+                (year, month, day) = (Some(a), Some(b as u32), Some(c as u32));
+            } else {
+                (month, day, year) = (Some(a as u32), Some(b as u32), Some(c));
+            }
+        } else if token.bytes().all(|c| c.is_ascii_alphabetic() || c == b'.') {
+            if let Some(index) = MONTHS.iter().position(|m| lower.starts_with(&m.to_ascii_lowercase())) {
+                month = Some(index as u32 + 1);
+            } else if !WEEKDAYS.iter().any(|d| lower.starts_with(&d.to_ascii_lowercase())) {
+                return None;
+            }
+        } else {
+            let value = token.parse::<i64>().ok()?;
+            if day.is_none() && token.len() <= 2 {
+                day = Some(value as u32);
+            } else if year.is_none() {
+                year = Some(value);
+            } else {
+                return None;
+            }
+        }
+    }
+
+    let (year, month, day) = (year?, month?, day?);
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    match pm {
+        Some(_) if hours > 12 => return None,
+        Some(true) if hours < 12 => hours += 12,
+        Some(false) if hours == 12 => hours = 0,
+        _ => {}
+    }
+    if hours > 24 || minutes > 59 || seconds > 59 {
+        return None;
+    }
+
+    let day = days_from_civil(year, month, day) as f64;
+    let time = make_date(day, make_time(hours as f64, minutes as f64, seconds as f64, 0.0));
+
+    Some(match offset {
+        Some(offset) => ParsedDate {
+            time: time - offset,
+            local: false,
+        },
+        None => ParsedDate { time, local: true },
+    })
+}
+
+/// Parses a timezone offset of the form `+HHMM` or `+HH:MM`, returning it in milliseconds
+fn parse_offset(s: &str) -> Option<f64> {
+    let (sign, rest) = match s.as_bytes().first()? {
+        b'+' => (1.0, &s[1..]),
+        b'-' => (-1.0, &s[1..]),
+        _ => return None,
+    };
+
+    let rest = rest.replace(':', "");
+    if rest.len() != 4 || !rest.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let hours: f64 = rest[..2].parse().ok()?;
+    let minutes: f64 = rest[2..].parse().ok()?;
+    Some(sign * (hours * MS_PER_HOUR + minutes * MS_PER_MINUTE))
+}
@@ -85,12 +85,12 @@ impl Object for Error {
         }
     }
 
-    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<(), Value> {
+    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
         // TODO: this should special case name/stack
         self.obj.set_property(key, value, sc)
     }
 
-    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<Unrooted, Value> {
+    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Value> {
         // TODO: delete/clear property
         self.obj.delete_property(key, sc)
     }
@@ -113,6 +113,8 @@ impl Object for Error {
         self.obj.get_prototype(sc)
     }
 
+    delegate!(obj, prevent_extensions, is_extensible);
+
     fn own_keys(&self, sc: &mut LocalScope<'_>, mode: OwnKeysMode) -> Result<Vec<Value>, Value> {
         self.obj.own_keys(sc, mode)
     }
@@ -167,9 +169,12 @@ macro_rules! define_error_type {
                     get_property,
                     get_property_descriptor,
                     set_property,
+                    define_property,
                     delete_property,
                     set_prototype,
                     get_prototype,
+                    prevent_extensions,
+                    is_extensible,
                     apply,
                     own_keys
                 );
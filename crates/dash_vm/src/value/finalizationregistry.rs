@@ -0,0 +1,138 @@
+use std::cell::{Cell, RefCell};
+
+use crate::gc::trace::{Trace, TraceCtxt};
+use crate::gc::{Allocator, ObjectId};
+use crate::{delegate, extract};
+
+use super::Value;
+use super::object::{Object, OrdObject};
+
+#[derive(Debug)]
+struct RegistryCell {
+    /// Weakly held. `None` once the target is collected, until the cell is cleaned up.
+    target: Option<ObjectId>,
+    held_value: Value,
+    /// Weakly held. Cleared when the token is collected, since it can then never be passed to `unregister`.
+    unregister_token: Option<ObjectId>,
+}
+
+#[derive(Debug)]
+pub struct FinalizationRegistry {
+    cleanup_callback: Option<ObjectId>,
+    cells: RefCell<Vec<RegistryCell>>,
+    /// Whether a cleanup job for this registry is queued and has not started running yet
+    cleanup_queued: Cell<bool>,
+    obj: OrdObject,
+}
+
+unsafe impl Trace for FinalizationRegistry {
+    fn trace(&self, cx: &mut TraceCtxt<'_>) {
+        let Self {
+            cleanup_callback,
+            cells,
+            cleanup_queued: _,
+            obj,
+        } = self;
+        cleanup_callback.trace(cx);
+        for RegistryCell {
+            target: _,
+            held_value,
+            unregister_token: _,
+        } in cells.borrow().iter()
+        {
+            held_value.trace(cx);
+        }
+        obj.trace(cx);
+    }
+}
+
+impl FinalizationRegistry {
+    pub fn new(cleanup_callback: ObjectId, obj: OrdObject) -> Self {
+        Self {
+            cleanup_callback: Some(cleanup_callback),
+            cells: RefCell::new(Vec::new()),
+            cleanup_queued: Cell::new(false),
+            obj,
+        }
+    }
+
+    pub fn null() -> Self {
+        Self {
+            cleanup_callback: None,
+            cells: RefCell::new(Vec::new()),
+            cleanup_queued: Cell::new(false),
+            obj: OrdObject::null(),
+        }
+    }
+
+    pub fn cleanup_callback(&self) -> Option<ObjectId> {
+        self.cleanup_callback
+    }
+
+    pub fn register(&self, target: ObjectId, held_value: Value, unregister_token: Option<ObjectId>) {
+        self.cells.borrow_mut().push(RegistryCell {
+            target: Some(target),
+            held_value,
+            unregister_token,
+        });
+    }
+
+    /// Removes all cells registered with the given token. Returns true if any cell was removed.
+    pub fn unregister(&self, unregister_token: ObjectId) -> bool {
+        let mut cells = self.cells.borrow_mut();
+        let len = cells.len();
+        cells.retain(|cell| cell.unregister_token != Some(unregister_token));
+        cells.len() != len
+    }
+
+    /// Marks the cells of targets that were not marked as collected and clears unmarked unregister tokens.
+    /// Returns true if a cleanup job needs to be queued for this registry.
+    /// Must be called after tracing and before sweeping.
+    pub(crate) fn collect_unmarked(&self, alloc: &Allocator) -> bool {
+        let mut collected = false;
+        for cell in self.cells.borrow_mut().iter_mut() {
+            if cell.target.is_some_and(|target| !alloc.is_marked(target)) {
+                cell.target = None;
+                collected = true;
+            }
+
+            if cell.unregister_token.is_some_and(|token| !alloc.is_marked(token)) {
+                cell.unregister_token = None;
+            }
+        }
+
+        collected && !self.cleanup_queued.replace(true)
+    }
+
+    /// Called when the cleanup job starts running, so that targets collected from now on queue a new job
+    pub fn start_cleanup(&self) {
+        self.cleanup_queued.set(false);
+    }
+
+    /// Removes the first cell whose target was collected and returns its held value
+    pub fn take_collected(&self) -> Option<Value> {
+        let mut cells = self.cells.borrow_mut();
+        let index = cells.iter().position(|cell| cell.target.is_none())?;
+        Some(cells.remove(index).held_value)
+    }
+}
+
+impl Object for FinalizationRegistry {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        apply,
+        own_keys
+    );
+
+    extract!(self);
+}
@@ -123,9 +123,12 @@ impl Object for ThenTask {
         get_property,
         get_property_descriptor,
         set_property,
+        define_property,
         delete_property,
         set_prototype,
         get_prototype,
+        prevent_extensions,
+        is_extensible,
         own_keys
     );
 
@@ -144,10 +147,12 @@ impl Object for ThenTask {
             PromiseAction::Reject => scope.statics.generator_iterator_throw,
         };
         // TODO: this probably wont work because when it gets to an await point, the generator doesnt know how to handle it
-        let value = progress_fn
-            .apply(This::bound(self.generator_iter), [promise_value].into(), scope)
-            .root(scope)
-            .and_then(|result| result.get_property(sym::value.to_key(scope), scope).root(scope));
+        let value = scope.catch_exception(|scope| {
+            progress_fn
+                .apply(This::bound(self.generator_iter), [promise_value].into(), scope)
+                .root(scope)
+                .and_then(|result| result.get_property(sym::value.to_key(scope), scope).root(scope))
+        });
 
         // - Repeat what we are doing above.
         // Check if generator iterator is done:
@@ -0,0 +1,185 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+
+use dash_proc_macro::Trace;
+
+use crate::gc::ObjectId;
+use crate::js_std::async_generator::perform_action;
+use crate::localscope::LocalScope;
+use crate::value::object::{Object, OrdObject, This};
+use crate::value::{Typeof, Unrooted, Value, ValueContext};
+use crate::{Vm, delegate, extract};
+
+use super::args::CallArgs;
+use super::generator::{GeneratorFunction, GeneratorIterator};
+use super::user::UserFunction;
+
+#[derive(Debug, Trace)]
+pub struct AsyncGeneratorFunction {
+    pub inner: GeneratorFunction,
+}
+
+impl AsyncGeneratorFunction {
+    pub fn new(fun: UserFunction) -> Self {
+        Self {
+            inner: GeneratorFunction::new(fun),
+        }
+    }
+
+    pub(crate) fn handle_function_call(
+        &self,
+        scope: &mut LocalScope,
+        callee: ObjectId,
+        this: This,
+        args: CallArgs,
+        _new_target: Option<ObjectId>,
+    ) -> Result<Value, Unrooted> {
+        let (args, arguments) = self.inner.prepare_call(scope, args);
+        let obj = OrdObject::with_prototype(scope.statics.async_generator_prototype);
+        let iter = AsyncGeneratorIterator {
+            inner: GeneratorIterator::with_obj(callee, obj, args, arguments, Vec::new(), this),
+            queue: RefCell::new(VecDeque::new()),
+            executing: Cell::new(false),
+        };
+        Ok(Value::object(scope.register(iter)))
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AsyncGeneratorRequestKind {
+    Next,
+    Return,
+    Throw,
+}
+
+/// What to do once a promise awaited by an async generator is settled
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AsyncGeneratorAction {
+    /// Resume the generator with the fulfilled value (`await` fulfilled)
+    ResumeNext,
+    /// Throw the rejection reason into the generator (`await` or `yield` rejected)
+    ResumeThrow,
+    /// Return from the generator at the `yield` it is suspended at with the fulfilled value (`return()` called)
+    ResumeReturn,
+    /// Resolve the current request with `{ value, done: false }` (`yield` fulfilled)
+    Yield,
+    /// Resolve the current request with `{ value, done: true }` (`return` fulfilled)
+    Return,
+    /// Reject the current request (`return` rejected)
+    Reject,
+}
+
+/// A pending call to `next`, `return` or `throw` of an async generator
+#[derive(Debug, Trace)]
+pub struct AsyncGeneratorRequest {
+    pub kind: AsyncGeneratorRequestKind,
+    pub value: Value,
+    /// The promise that was returned to the caller and gets settled once this request is processed
+    pub promise: ObjectId,
+}
+
+#[derive(Debug, Trace)]
+pub struct AsyncGeneratorIterator {
+    inner: GeneratorIterator,
+    /// Requests are processed one at a time, in the order they were made
+    queue: RefCell<VecDeque<AsyncGeneratorRequest>>,
+    /// Whether the request at the front of the queue is currently being processed.
+    /// This stays true while the generator is suspended at an `await` (or awaiting a yielded or returned value).
+    executing: Cell<bool>,
+}
+
+impl AsyncGeneratorIterator {
+    pub fn inner(&self) -> &GeneratorIterator {
+        &self.inner
+    }
+
+    pub fn queue(&self) -> &RefCell<VecDeque<AsyncGeneratorRequest>> {
+        &self.queue
+    }
+
+    pub fn is_executing(&self) -> bool {
+        self.executing.get()
+    }
+
+    pub fn set_executing(&self, executing: bool) {
+        self.executing.set(executing);
+    }
+}
+
+impl Object for AsyncGeneratorIterator {
+    delegate!(
+        inner,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        apply,
+        own_keys
+    );
+
+    fn type_of(&self, _: &Vm) -> Typeof {
+        Typeof::Object
+    }
+
+    extract!(self);
+}
+
+/// A callable object that is passed to `.then()` on promises awaited by an async generator,
+/// which continues processing the generator's current request once the promise is settled.
+#[derive(Debug, Trace)]
+pub struct AsyncGeneratorTask {
+    generator: ObjectId,
+    action: AsyncGeneratorAction,
+    obj: OrdObject,
+}
+
+impl AsyncGeneratorTask {
+    pub fn new(vm: &Vm, generator: ObjectId, action: AsyncGeneratorAction) -> Self {
+        Self {
+            generator,
+            action,
+            obj: OrdObject::new(vm),
+        }
+    }
+}
+
+impl Object for AsyncGeneratorTask {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        own_keys
+    );
+
+    fn apply(
+        &self,
+        _callee: ObjectId,
+        _this: This,
+        args: CallArgs,
+        scope: &mut LocalScope,
+    ) -> Result<Unrooted, Unrooted> {
+        let value = args.first().unwrap_or_undefined();
+        perform_action(scope, self.generator, self.action, value)?;
+        Ok(Value::undefined().into())
+    }
+
+    fn type_of(&self, _: &Vm) -> Typeof {
+        Typeof::Function
+    }
+
+    extract!(self);
+}
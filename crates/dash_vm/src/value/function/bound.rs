@@ -34,9 +34,12 @@ impl Object for BoundFunction {
         get_property,
         get_property_descriptor,
         set_property,
+        define_property,
         delete_property,
         set_prototype,
         get_prototype,
+        prevent_extensions,
+        is_extensible,
         own_keys
     );
 
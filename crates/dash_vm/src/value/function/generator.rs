@@ -25,17 +25,11 @@ impl GeneratorFunction {
         Self { function }
     }
 
-    pub(crate) fn handle_function_call(
-        &self,
-        scope: &mut LocalScope,
-        callee: ObjectId,
-        this: This,
-        args: CallArgs,
-        _new_target: Option<ObjectId>,
-    ) -> Result<Value, Unrooted> {
+    /// Evaluates the arguments of a call, returning the initial stack of the generator and the `arguments` object if needed
+    pub(crate) fn prepare_call(&self, scope: &mut LocalScope, args: CallArgs) -> (Vec<Value>, Option<ObjectId>) {
         let mut arguments = None;
         if self.function.inner().arguments_local.is_some() {
-            let args = Arguments::new(scope, args.iter().cloned());
+            let args = Arguments::new(scope, args.iter().cloned(), self.function.inner().is_strict);
             let args = scope.register(args);
             arguments = Some(args);
         }
@@ -51,6 +45,18 @@ impl GeneratorFunction {
             scope.stack.drain(sp..).collect::<Vec<_>>()
         };
 
+        (args, arguments)
+    }
+
+    pub(crate) fn handle_function_call(
+        &self,
+        scope: &mut LocalScope,
+        callee: ObjectId,
+        this: This,
+        args: CallArgs,
+        _new_target: Option<ObjectId>,
+    ) -> Result<Value, Unrooted> {
+        let (args, arguments) = self.prepare_call(scope, args);
         let iter = GeneratorIterator::new(callee, scope, args, arguments, Vec::new(), this);
         Ok(Value::object(scope.register(iter)))
     }
@@ -65,6 +71,11 @@ pub enum GeneratorState {
         try_blocks: Vec<TryBlock>,
         arguments: Option<ObjectId>,
         this: This,
+        /// A pending return or exception of a finally block the generator is suspended in
+        delayed_ret: Option<Result<Unrooted, Unrooted>>,
+        /// Whether the generator is suspended at a `yield` (as opposed to an `await`),
+        /// which expects the resumption kind on the stack in addition to the value
+        at_yield: bool,
     },
 }
 
@@ -87,11 +98,14 @@ unsafe impl Trace for GeneratorState {
                 arguments,
                 try_blocks,
                 this,
+                delayed_ret,
+                at_yield: _,
             } => {
                 stack.trace(cx);
                 arguments.trace(cx);
                 try_blocks.trace(cx);
                 this.trace(cx);
+                delayed_ret.trace(cx);
             }
         }
     }
@@ -112,16 +126,30 @@ impl GeneratorIterator {
         arguments: Option<ObjectId>,
         try_blocks: Vec<TryBlock>,
         this: This,
+    ) -> Self {
+        let obj = OrdObject::with_prototype(vm.statics.generator_iterator_prototype);
+        Self::with_obj(function, obj, stack, arguments, try_blocks, this)
+    }
+
+    pub fn with_obj(
+        function: ObjectId,
+        obj: OrdObject,
+        stack: Vec<Value>,
+        arguments: Option<ObjectId>,
+        try_blocks: Vec<TryBlock>,
+        this: This,
     ) -> Self {
         Self {
             function,
-            obj: OrdObject::with_prototype(vm.statics.generator_iterator_prototype),
+            obj,
             state: RefCell::new(GeneratorState::Running {
                 ip: Ip(0),
                 stack,
                 arguments,
                 try_blocks,
                 this,
+                delayed_ret: None,
+                at_yield: false,
             }),
         }
     }
@@ -154,9 +182,12 @@ impl Object for GeneratorIterator {
         get_property,
         get_property_descriptor,
         set_property,
+        define_property,
         delete_property,
         set_prototype,
         get_prototype,
+        prevent_extensions,
+        is_extensible,
         own_keys
     );
 
@@ -12,10 +12,12 @@ use crate::gc::{Allocator, ObjectId};
 use crate::localscope::LocalScope;
 use crate::value::arguments::Arguments;
 use crate::value::object::{OwnKeysMode, This};
-use crate::{Vm, extract, throw};
+use crate::value::root_ext::RootErrExt;
+use crate::{Vm, delegate, extract, throw};
 use dash_middle::interner::sym;
 
 use self::r#async::AsyncFunction;
+use self::async_generator::AsyncGeneratorFunction;
 use self::closure::Closure;
 use self::generator::GeneratorFunction;
 use self::native::{CallContext, NativeFunction};
@@ -30,6 +32,7 @@ use super::{PureBuiltin, Root, Typeof, Unpack, Unrooted, Value, ValueKind};
 
 pub mod args;
 pub mod r#async;
+pub mod async_generator;
 pub mod bound;
 pub mod closure;
 pub mod generator;
@@ -45,6 +48,7 @@ pub enum FunctionKind {
     User(UserFunction),
     Generator(GeneratorFunction),
     Async(AsyncFunction),
+    AsyncGenerator(AsyncGeneratorFunction),
     Closure(Closure),
 }
 
@@ -121,6 +125,7 @@ unsafe impl Trace for FunctionKind {
             Self::User(user) => user.trace(cx),
             Self::Generator(generator) => generator.trace(cx),
             Self::Async(async_) => async_.trace(cx),
+            Self::AsyncGenerator(generator) => generator.trace(cx),
             Self::Native {
                 constructable: _,
                 function: _,
@@ -137,6 +142,7 @@ impl Debug for FunctionKind {
             Self::User(..) => f.write_str("UserFunction"),
             Self::Generator(..) => f.write_str("GeneratorFunction"),
             Self::Async(..) => f.write_str("AsyncFunction"),
+            Self::AsyncGenerator(..) => f.write_str("AsyncGeneratorFunction"),
             Self::Closure(..) => f.write_str("closure"),
         }
     }
@@ -231,6 +237,7 @@ impl Function {
             FunctionKind::User(function) => Some(function),
             FunctionKind::Generator(generator) => Some(&generator.function),
             FunctionKind::Async(function) => Some(&function.inner.function),
+            FunctionKind::AsyncGenerator(generator) => Some(&generator.inner.function),
             FunctionKind::Closure(closure) => Some(&closure.fun),
             FunctionKind::Native { .. } => None,
         }
@@ -255,7 +262,8 @@ fn handle_call(
                 throw!(scope, TypeError, "{} is not constructable", name);
             }
 
-            let this = this.to_value(scope)?;
+            // Native functions are treated as non-strict, so they receive the global object if `this` is missing
+            let this = this.to_value(false, scope)?;
             // TODO: pass `This` to native fns as-is?
             let cx = CallContext {
                 args,
@@ -281,6 +289,9 @@ fn handle_call(
         FunctionKind::Generator(fun) => fun
             .handle_function_call(scope, callee, this, args, new_target)
             .map(Into::into),
+        FunctionKind::AsyncGenerator(fun) => fun
+            .handle_function_call(scope, callee, this, args, new_target)
+            .map(Into::into),
         FunctionKind::Closure(closure) => {
             if new_target.is_some() {
                 throw!(scope, TypeError, "closure is not constructable");
@@ -311,7 +322,15 @@ impl Object for Function {
         key: PropertyKey,
         sc: &mut LocalScope,
     ) -> Result<Option<PropertyValue>, Unrooted> {
-        match key.to_js_string(sc) {
+        let name = key.to_js_string(sc);
+        if let Some(sym::name | sym::length) = name
+            && let Some(desc) = self.obj.get_own_property_descriptor(key, sc)?
+        {
+            // Redefined with `Object.defineProperty`
+            return Ok(Some(desc));
+        }
+
+        match name {
             Some(sym::name) => {
                 let name = self.name().unwrap_or_else(|| sym::empty.into());
                 return Ok(Some(PropertyValue {
@@ -337,18 +356,37 @@ impl Object for Function {
         self.obj.get_own_property_descriptor(key, sc)
     }
 
-    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<(), Value> {
-        if let Some(sym::prototype) = key.to_js_string(sc) {
-            let prototype = value.get_or_apply(sc, This::default()).root(sc)?;
-            // TODO: function prototype does not need to be an object
-            self.prototype.set(Some(prototype.to_object(sc)?));
-            return Ok(());
+    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
+        match key.to_js_string(sc) {
+            Some(sym::prototype) => {
+                let prototype = value.get_or_apply(sc, This::default()).root(sc)?;
+                // TODO: function prototype does not need to be an object
+                self.prototype.set(Some(prototype.to_object(sc)?));
+                return Ok(true);
+            }
+            // `name` and `length` are not writable unless they were redefined, see `get_own_property_descriptor`
+            Some(sym::name | sym::length) if self.obj.get_own_property_descriptor(key, sc).root_err(sc)?.is_some() => {}
+            Some(sym::name) => return Ok(false),
+            Some(sym::length) if self.inner_user_function().is_some() => return Ok(false),
+            _ => {}
         }
 
         self.obj.set_property(key, value, sc)
     }
 
-    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<Unrooted, Value> {
+    fn define_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
+        match key.to_js_string(sc) {
+            Some(sym::name | sym::length) => self.obj.define_property(key, value, sc),
+            _ => self.set_property(key, value, sc),
+        }
+    }
+
+    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Value> {
+        if let Some(sym::prototype) = key.to_js_string(sc) {
+            // `prototype` is not configurable, see `get_own_property_descriptor`
+            return Ok(false);
+        }
+
         self.obj.delete_property(key, sc)
     }
 
@@ -398,6 +436,8 @@ impl Object for Function {
         self.obj.get_prototype(sc)
     }
 
+    delegate!(obj, prevent_extensions, is_extensible);
+
     fn own_keys(&self, _: &mut LocalScope<'_>, mode: OwnKeysMode) -> Result<Vec<Value>, Value> {
         Ok(match mode {
             OwnKeysMode::All | OwnKeysMode::AllStrings => {
@@ -426,7 +466,7 @@ pub(crate) fn adjust_stack_from_flat_call(
         let args = scope.stack[old_sp..].to_vec();
         // TODO: this assertion is wrong for (function(){ return arguments })(...[1, 2]). args.len() is correct
         debug_assert_eq!(args.len(), argc);
-        let args = Arguments::new(scope, args);
+        let args = Arguments::new(scope, args, user_function.inner().is_strict);
         let args = scope.register(args);
         arguments = Some(args);
     }
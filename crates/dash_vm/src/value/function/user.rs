@@ -1,3 +1,4 @@
+use std::cell::Cell;
 use std::rc::Rc;
 
 use dash_middle::compiler::constant::Function;
@@ -18,11 +19,17 @@ use super::extend_stack_from_args;
 pub struct UserFunction {
     inner: Rc<Function>,
     externals: Rc<[ExternalValue]>,
+    /// The [[HomeObject]] of this function, used as the base for `super` property lookups
+    home_object: Cell<Option<ObjectId>>,
 }
 
 impl UserFunction {
     pub fn new(inner: Rc<Function>, externals: Rc<[ExternalValue]>) -> Self {
-        Self { inner, externals }
+        Self {
+            inner,
+            externals,
+            home_object: Cell::new(None),
+        }
     }
 
     pub fn externals(&self) -> &Rc<[ExternalValue]> {
@@ -33,6 +40,14 @@ impl UserFunction {
         &self.inner
     }
 
+    pub fn home_object(&self) -> Option<ObjectId> {
+        self.home_object.get()
+    }
+
+    pub fn set_home_object(&self, home_object: Option<ObjectId>) {
+        self.home_object.set(home_object);
+    }
+
     pub(crate) fn handle_function_call(
         &self,
         scope: &mut LocalScope,
@@ -44,7 +59,7 @@ impl UserFunction {
 
         let mut arguments = None;
         if self.inner.arguments_local.is_some() {
-            let args = Arguments::new(scope, args.iter().cloned());
+            let args = Arguments::new(scope, args.iter().cloned(), self.inner.is_strict);
             let args = scope.register(args);
             arguments = Some(args);
         }
@@ -0,0 +1,353 @@
+use std::cell::Cell;
+use std::cmp::Ordering;
+
+use dash_middle::interner::sym;
+use dash_proc_macro::Trace;
+
+use crate::gc::ObjectId;
+use crate::gc::trace::{Trace, TraceCtxt};
+use crate::localscope::LocalScope;
+use crate::value::Value;
+use crate::value::object::{Object, OrdObject};
+use crate::{delegate, extract};
+
+use super::{
+    Locale, canonicalize_locale_list, coerce_options_to_object, get_bool_option, get_string_option, resolve_locale,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Usage {
+    Sort,
+    Search,
+}
+
+impl Usage {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Sort => "sort",
+            Self::Search => "search",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sensitivity {
+    /// Only base letters are significant: `a = á = A`
+    Base,
+    /// Base letters and accents are significant: `a = A`, but `a ≠ á`
+    Accent,
+    /// Base letters and case are significant: `a = á`, but `a ≠ A`
+    Case,
+    /// Everything is significant
+    Variant,
+}
+
+impl Sensitivity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Base => "base",
+            Self::Accent => "accent",
+            Self::Case => "case",
+            Self::Variant => "variant",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CaseFirst {
+    Upper,
+    Lower,
+    False,
+}
+
+impl CaseFirst {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Upper => "upper",
+            Self::Lower => "lower",
+            Self::False => "false",
+        }
+    }
+}
+
+/// Collation elements are compared level by level: first all primary weights (the base letters),
+/// then all secondary weights (accents) and finally all tertiary weights (case and variants)
+#[derive(Debug, Copy, Clone)]
+struct CollationElement {
+    primary: u32,
+    secondary: u8,
+    tertiary: u8,
+}
+
+const SPACE_BASE: u32 = 0x0100_0000;
+const PUNCTUATION_BASE: u32 = 0x0200_0000;
+const SYMBOL_BASE: u32 = 0x0300_0000;
+const DIGIT_BASE: u32 = 0x0400_0000;
+const LATIN_BASE: u32 = 0x0500_0000;
+const OTHER_LETTER_BASE: u32 = 0x0600_0000;
+
+/// Punctuation in the order of the root collation
+const PUNCTUATION: &str = "_-,;:!?.'\"()[]{}@*/\\&#%`^+<=>|~$";
+
+/// Latin letters with diacritics, the base letters they decompose to and the secondary weight of the diacritic
+#[rustfmt::skip]
+const DIACRITICS: &[(&str, &str, u8)] = &[
+    // acute
+    ("áéíóúýćńśźĺŕÁÉÍÓÚÝĆŃŚŹĹŔ", "aeiouycnszlrAEIOUYCNSZLR", 1),
+    // grave
+    ("àèìòùÀÈÌÒÙ", "aeiouAEIOU", 2),
+    // breve
+    ("ăĕğĭŏŭĂĔĞĬŎŬ", "aegiouAEGIOU", 3),
+    // circumflex
+    ("âêîôûĉĝĥĵŝŵŷÂÊÎÔÛĈĜĤĴŜŴŶ", "aeioucghjswyAEIOUCGHJSWY", 4),
+    // caron
+    ("ǎčďěǐňǒřšťǔžǍČĎĚǏŇǑŘŠŤǓŽ", "acdeinorstuzACDEINORSTUZ", 5),
+    // ring
+    ("åůÅŮ", "auAU", 6),
+    // diaeresis
+    ("äëïöüÿÄËÏÖÜŸ", "aeiouyAEIOUY", 7),
+    // double acute
+    ("őűŐŰ", "ouOU", 8),
+    // tilde
+    ("ãñõĩũÃÑÕĨŨ", "anoiuANOIU", 9),
+    // dot above
+    ("ȧċėġȯżȦĊĖĠȮŻİ", "acegozACEGOZI", 10),
+    // cedilla
+    ("çģķļņŗşţÇĢĶĻŅŖŞŢ", "cgklnrstCGKLNRST", 11),
+    // ogonek
+    ("ąęįųĄĘĮŲ", "aeiuAEIU", 12),
+    // macron
+    ("āēīōūĀĒĪŌŪ", "aeiouAEIOU", 13),
+    // hook above
+    ("ảẻỉỏủỷẢẺỈỎỦỶ", "aeiouyAEIOUY", 14),
+    // dot below
+    ("ạẹịọụỵẠẸỊỌỤỴ", "aeiouyAEIOUY", 15),
+    // stroke
+    ("øđħłŧØĐĦŁŦ", "odhltODHLT", 16),
+    // letters that are variants of another letter without being a combination of letter and diacritic
+    ("ðıŀÐĿ", "dilDL", 17),
+];
+
+/// Letters that expand to multiple letters
+const EXPANSIONS: &[(char, &str)] = &[('æ', "ae"), ('Æ', "AE"), ('œ', "oe"), ('Œ', "OE"), ('ß', "ss")];
+
+/// The resolved options of an `Intl.Collator`
+#[derive(Debug, Clone)]
+pub struct CollatorOptions {
+    pub locale: Locale,
+    pub usage: Usage,
+    pub sensitivity: Sensitivity,
+    pub ignore_punctuation: bool,
+    pub numeric: bool,
+    pub case_first: CaseFirst,
+}
+
+unsafe impl Trace for CollatorOptions {
+    fn trace(&self, _: &mut TraceCtxt<'_>) {
+        // No GC references
+    }
+}
+
+impl CollatorOptions {
+    /// Implements the InitializeCollator abstract operation
+    pub fn new(sc: &mut LocalScope<'_>, locales: Value, options: Value) -> Result<Self, Value> {
+        let requested = canonicalize_locale_list(sc, locales)?;
+        let options = coerce_options_to_object(sc, options)?;
+        let usage = get_string_option(
+            sc,
+            options,
+            sym::usage,
+            &[("sort", Usage::Sort), ("search", Usage::Search)],
+        )?
+        .unwrap_or(Usage::Sort);
+        // Only the lookup matcher is implemented, but the option is still validated
+        get_string_option(sc, options, sym::localeMatcher, &[("lookup", ()), ("best fit", ())])?;
+        let numeric = get_bool_option(sc, options, sym::numeric)?.unwrap_or(false);
+        let case_first = get_string_option(
+            sc,
+            options,
+            sym::caseFirst,
+            &[
+                ("upper", CaseFirst::Upper),
+                ("lower", CaseFirst::Lower),
+                ("false", CaseFirst::False),
+            ],
+        )?
+        .unwrap_or(CaseFirst::False);
+        let locale = resolve_locale(&requested);
+        let sensitivity = get_string_option(
+            sc,
+            options,
+            sym::sensitivity,
+            &[
+                ("base", Sensitivity::Base),
+                ("accent", Sensitivity::Accent),
+                ("case", Sensitivity::Case),
+                ("variant", Sensitivity::Variant),
+            ],
+        )?
+        .unwrap_or(Sensitivity::Variant);
+        let ignore_punctuation = get_bool_option(sc, options, sym::ignorePunctuation)?.unwrap_or(false);
+
+        Ok(Self {
+            locale,
+            usage,
+            sensitivity,
+            ignore_punctuation,
+            numeric,
+            case_first,
+        })
+    }
+
+    fn push_letter(&self, c: char, secondary: u8, variant: bool, out: &mut Vec<CollationElement>) {
+        let primary = match c.to_ascii_lowercase() {
+            lower @ 'a'..='z' => LATIN_BASE + u32::from(lower as u8 - b'a'),
+            'þ' | 'Þ' => LATIN_BASE + 26,
+            other => OTHER_LETTER_BASE + other.to_lowercase().next().map_or(other as u32, |c| c as u32),
+        };
+        let upper = c.is_uppercase();
+        let case = match self.case_first {
+            CaseFirst::Upper => u8::from(!upper),
+            CaseFirst::Lower | CaseFirst::False => u8::from(upper),
+        };
+
+        out.push(CollationElement {
+            primary,
+            secondary,
+            // Variants such as ligatures sort after both cases of the plain letters
+            tertiary: if variant { case + 2 } else { case },
+        });
+    }
+
+    fn push_char(&self, c: char, out: &mut Vec<CollationElement>) {
+        if let Some(&(_, expansion)) = EXPANSIONS.iter().find(|&&(ch, _)| ch == c) {
+            for c in expansion.chars() {
+                self.push_letter(c, 0, true, out);
+            }
+            return;
+        }
+
+        if let Some((base, secondary)) = DIACRITICS.iter().find_map(|&(chars, bases, secondary)| {
+            let index = chars.chars().position(|ch| ch == c)?;
+            Some((bases.chars().nth(index).unwrap(), secondary))
+        }) {
+            self.push_letter(base, secondary, false, out);
+            return;
+        }
+
+        if c.is_alphabetic() {
+            self.push_letter(c, 0, false, out);
+            return;
+        }
+
+        let primary = if let Some(digit) = c.to_digit(10) {
+            DIGIT_BASE + digit
+        } else if c.is_whitespace() {
+            if self.ignore_punctuation {
+                return;
+            }
+            SPACE_BASE + c as u32
+        } else if let Some(index) = PUNCTUATION.chars().position(|p| p == c) {
+            if self.ignore_punctuation {
+                return;
+            }
+            PUNCTUATION_BASE + index as u32
+        } else {
+            SYMBOL_BASE + c as u32
+        };
+
+        out.push(CollationElement {
+            primary,
+            secondary: 0,
+            tertiary: 0,
+        });
+    }
+
+    fn collation_elements(&self, s: &str) -> Vec<CollationElement> {
+        let mut out = Vec::with_capacity(s.len());
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            if self.numeric && c.is_ascii_digit() {
+                let mut digits = String::from(c);
+                while let Some(&d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+                    digits.push(d);
+                    chars.next();
+                }
+
+                // Numbers with more significant digits are larger, so the length is compared first
+                let digits = match digits.trim_start_matches('0') {
+                    "" => "0",
+                    digits => digits,
+                };
+                out.push(CollationElement {
+                    primary: DIGIT_BASE + 10 + digits.len() as u32,
+                    secondary: 0,
+                    tertiary: 0,
+                });
+                out.extend(digits.bytes().map(|d| CollationElement {
+                    primary: DIGIT_BASE + u32::from(d - b'0'),
+                    secondary: 0,
+                    tertiary: 0,
+                }));
+            } else {
+                self.push_char(c, &mut out);
+            }
+        }
+        out
+    }
+
+    /// Implements the CompareStrings abstract operation
+    pub fn compare(&self, left: &str, right: &str) -> Ordering {
+        let left = self.collation_elements(left);
+        let right = self.collation_elements(right);
+
+        let primary = left.iter().map(|e| e.primary).cmp(right.iter().map(|e| e.primary));
+        let secondary = || left.iter().map(|e| e.secondary).cmp(right.iter().map(|e| e.secondary));
+        let tertiary = || left.iter().map(|e| e.tertiary).cmp(right.iter().map(|e| e.tertiary));
+
+        match self.sensitivity {
+            Sensitivity::Base => primary,
+            Sensitivity::Accent => primary.then_with(secondary),
+            Sensitivity::Case => primary.then_with(tertiary),
+            Sensitivity::Variant => primary.then_with(secondary).then_with(tertiary),
+        }
+    }
+}
+
+#[derive(Debug, Trace)]
+pub struct Collator {
+    pub options: CollatorOptions,
+    /// The function returned by the `compare` getter, which is created lazily
+    pub bound_compare: Cell<Option<ObjectId>>,
+    object: OrdObject,
+}
+
+impl Collator {
+    pub fn new(options: CollatorOptions, object: OrdObject) -> Self {
+        Self {
+            options,
+            bound_compare: Cell::new(None),
+            object,
+        }
+    }
+}
+
+impl Object for Collator {
+    delegate!(
+        object,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        apply,
+        own_keys
+    );
+
+    extract!(self);
+}
@@ -0,0 +1,815 @@
+use std::cell::Cell;
+
+use dash_middle::interner::{Symbol, sym};
+use dash_proc_macro::Trace;
+
+use crate::gc::ObjectId;
+use crate::gc::trace::{Trace, TraceCtxt};
+use crate::localscope::LocalScope;
+use crate::value::date::{self, DateFields, MS_PER_MINUTE};
+use crate::value::object::{Object, OrdObject};
+use crate::value::ops::conversions::ValueConversion;
+use crate::value::propertykey::ToPropertyKey;
+use crate::value::root_ext::RootErrExt;
+use crate::value::{Root, Unpack, Value, ValueKind};
+use crate::{delegate, extract, throw};
+
+use super::{
+    Locale, canonicalize_locale_list, coerce_options_to_object, get_bool_option, get_number_option, get_string_option,
+    resolve_locale,
+};
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TimeZone {
+    Utc,
+    /// A fixed offset from UTC in minutes
+    Offset(i32),
+    /// The local time zone as reported by the embedder
+    Local,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FieldStyle {
+    Numeric,
+    TwoDigit,
+    Long,
+    Short,
+    Narrow,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DateTimeStyle {
+    Full,
+    Long,
+    Medium,
+    Short,
+}
+
+impl DateTimeStyle {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::Long => "long",
+            Self::Medium => "medium",
+            Self::Short => "short",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HourCycle {
+    /// 0-11
+    H11,
+    /// 1-12
+    H12,
+    /// 0-23
+    H23,
+    /// 1-24
+    H24,
+}
+
+impl HourCycle {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::H11 => "h11",
+            Self::H12 => "h12",
+            Self::H23 => "h23",
+            Self::H24 => "h24",
+        }
+    }
+
+    fn pattern_char(self) -> char {
+        match self {
+            Self::H11 => 'K',
+            Self::H12 => 'h',
+            Self::H23 => 'H',
+            Self::H24 => 'k',
+        }
+    }
+
+    pub fn is_twelve_hour(self) -> bool {
+        matches!(self, Self::H11 | Self::H12)
+    }
+}
+
+/// The components that must be present in a format. Missing components are filled in with defaults.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RequiredComponents {
+    Date,
+    Time,
+    Any,
+}
+
+/// The components that are added if none of the required components are present
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DefaultComponents {
+    Date,
+    Time,
+    All,
+}
+
+/// The type of a part returned by `Intl.DateTimeFormat.prototype.formatToParts`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DatePartKind {
+    Weekday,
+    Year,
+    Month,
+    Day,
+    DayPeriod,
+    Hour,
+    Minute,
+    Second,
+    FractionalSecond,
+    TimeZoneName,
+    Literal,
+}
+
+impl DatePartKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Weekday => "weekday",
+            Self::Year => "year",
+            Self::Month => "month",
+            Self::Day => "day",
+            Self::DayPeriod => "dayPeriod",
+            Self::Hour => "hour",
+            Self::Minute => "minute",
+            Self::Second => "second",
+            Self::FractionalSecond => "fractionalSecond",
+            Self::TimeZoneName => "timeZoneName",
+            Self::Literal => "literal",
+        }
+    }
+}
+
+/// The value of a component in the resolved options, e.g. `"2-digit"` for `minute` or `3` for `fractionalSecondDigits`
+pub enum ResolvedComponent {
+    String(&'static str),
+    Number(u32),
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+struct Components {
+    weekday: Option<FieldStyle>,
+    year: Option<FieldStyle>,
+    month: Option<FieldStyle>,
+    day: Option<FieldStyle>,
+    hour: Option<FieldStyle>,
+    minute: Option<FieldStyle>,
+    second: Option<FieldStyle>,
+    fractional_second_digits: Option<u32>,
+    /// Whether the time zone name is long (`Coordinated Universal Time`) or short (`UTC`)
+    long_time_zone_name: Option<bool>,
+}
+
+impl Components {
+    fn has_date(&self) -> bool {
+        self.weekday.is_some() || self.year.is_some() || self.month.is_some() || self.day.is_some()
+    }
+
+    fn has_time(&self) -> bool {
+        self.hour.is_some() || self.minute.is_some() || self.second.is_some() || self.fractional_second_digits.is_some()
+    }
+
+    fn for_date_style(style: DateTimeStyle, locale: Locale) -> Self {
+        let (weekday, month, year) = match style {
+            DateTimeStyle::Full => (Some(FieldStyle::Long), FieldStyle::Long, FieldStyle::Numeric),
+            DateTimeStyle::Long => (None, FieldStyle::Long, FieldStyle::Numeric),
+            DateTimeStyle::Medium => (None, FieldStyle::Short, FieldStyle::Numeric),
+            // The root locale always uses the full year
+            DateTimeStyle::Short if locale == Locale::Root => (None, FieldStyle::Numeric, FieldStyle::Numeric),
+            DateTimeStyle::Short => (None, FieldStyle::Numeric, FieldStyle::TwoDigit),
+        };
+
+        Self {
+            weekday,
+            year: Some(year),
+            month: Some(month),
+            day: Some(FieldStyle::Numeric),
+            ..Self::default()
+        }
+    }
+
+    fn for_time_style(style: DateTimeStyle) -> Self {
+        Self {
+            hour: Some(FieldStyle::Numeric),
+            minute: Some(FieldStyle::TwoDigit),
+            second: (style != DateTimeStyle::Short).then_some(FieldStyle::TwoDigit),
+            long_time_zone_name: match style {
+                DateTimeStyle::Full => Some(true),
+                DateTimeStyle::Long => Some(false),
+                _ => None,
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Builds the pattern for the date components, such as `EEEE, MMMM d, y`
+    fn date_pattern(&self, locale: Locale) -> String {
+        let weekday = self.weekday.map(|style| match style {
+            FieldStyle::Long => "EEEE",
+            FieldStyle::Narrow => "EEEEE",
+            _ => "EEE",
+        });
+        let year = self.year.map(|style| match style {
+            FieldStyle::TwoDigit => "yy",
+            _ => "y",
+        });
+        let day = self.day.map(|style| match style {
+            FieldStyle::TwoDigit => "dd",
+            _ if locale == Locale::Root && self.month.is_some() => "dd",
+            _ => "d",
+        });
+        let textual_month = self.month.and_then(|style| match style {
+            FieldStyle::Long => Some("MMMM"),
+            FieldStyle::Short => Some("MMM"),
+            FieldStyle::Narrow => Some("MMMMM"),
+            FieldStyle::Numeric | FieldStyle::TwoDigit => None,
+        });
+
+        let date = match (locale, textual_month) {
+            (Locale::Root, Some(month)) => [year, Some(month), day]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" "),
+            (_, Some(month)) => match (day, year) {
+                (Some(day), Some(year)) => format!("{month} {day}, {year}"),
+                (Some(day), None) => format!("{month} {day}"),
+                (None, Some(year)) => format!("{month} {year}"),
+                (None, None) => month.to_owned(),
+            },
+            (Locale::Root, None) => {
+                let month = self.month.map(|_| "MM");
+                [year, month, day].into_iter().flatten().collect::<Vec<_>>().join("-")
+            }
+            (_, None) => {
+                let month = self.month.map(|style| match style {
+                    FieldStyle::TwoDigit => "MM",
+                    _ => "M",
+                });
+                [month, day, year].into_iter().flatten().collect::<Vec<_>>().join("/")
+            }
+        };
+
+        match weekday {
+            Some(weekday) if date.is_empty() => weekday.to_owned(),
+            Some(weekday) if locale == Locale::Root => format!("{date}, {weekday}"),
+            Some(weekday) => format!("{weekday}, {date}"),
+            None => date,
+        }
+    }
+
+    /// Builds the pattern for the time components, such as `h:mm:ss a`
+    fn time_pattern(&self, hour_cycle: HourCycle) -> String {
+        let mut pattern = String::new();
+        if let Some(hour) = self.hour {
+            pattern.push(hour_cycle.pattern_char());
+            // Hours in a 24 hour cycle are always displayed with two digits
+            if hour == FieldStyle::TwoDigit || !hour_cycle.is_twelve_hour() {
+                pattern.push(hour_cycle.pattern_char());
+            }
+        }
+        if let Some(minute) = self.minute {
+            if !pattern.is_empty() {
+                pattern.push(':');
+            }
+            let padded = minute == FieldStyle::TwoDigit || self.hour.is_some() || self.second.is_some();
+            pattern.push_str(if padded { "mm" } else { "m" });
+        }
+        // Fractional seconds are never displayed without the seconds if there are larger components
+        let implied_second = self.fractional_second_digits.is_some() && (self.hour.is_some() || self.minute.is_some());
+        if let Some(second) = self.second.or(implied_second.then_some(FieldStyle::TwoDigit)) {
+            if !pattern.is_empty() {
+                pattern.push(':');
+            }
+            let padded = second == FieldStyle::TwoDigit || self.minute.is_some();
+            pattern.push_str(if padded { "ss" } else { "s" });
+        }
+        if let Some(digits) = self.fractional_second_digits {
+            if !pattern.is_empty() {
+                pattern.push('.');
+            }
+            pattern.extend(std::iter::repeat_n('S', digits as usize));
+        }
+        if self.hour.is_some() && hour_cycle.is_twelve_hour() {
+            pattern.push_str(" a");
+        }
+        pattern
+    }
+}
+
+/// The resolved options of an `Intl.DateTimeFormat`
+#[derive(Debug, Clone)]
+pub struct DateTimeFormatOptions {
+    pub locale: Locale,
+    pub time_zone: TimeZone,
+    /// The time zone identifier as reported by `resolvedOptions()`
+    pub time_zone_id: String,
+    /// The hour cycle, if hours are part of the format
+    pub hour_cycle: Option<HourCycle>,
+    pub date_style: Option<DateTimeStyle>,
+    pub time_style: Option<DateTimeStyle>,
+    /// The pattern in the format of the Unicode LDML, e.g. `M/d/y, h:mm:ss a`
+    pub pattern: String,
+}
+
+unsafe impl Trace for DateTimeFormatOptions {
+    fn trace(&self, _: &mut TraceCtxt<'_>) {
+        // No GC references
+    }
+}
+
+/// Formats an offset from UTC in minutes as a time zone identifier such as `+05:30`
+fn offset_time_zone_id(offset: i32) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.unsigned_abs();
+    format!("{sign}{:02}:{:02}", offset / 60, offset % 60)
+}
+
+/// Parses a time zone identifier, returning the time zone and its canonical identifier
+fn parse_time_zone(id: &str) -> Option<(TimeZone, String)> {
+    if ["UTC", "ETC/UTC", "GMT", "ETC/GMT"].contains(&id.to_ascii_uppercase().as_str()) {
+        return Some((TimeZone::Utc, String::from("UTC")));
+    }
+
+    // Offset time zones: ±HH, ±HHMM or ±HH:MM
+    let (sign, rest) = match id.as_bytes().first()? {
+        b'+' => (1, &id[1..]),
+        b'-' => (-1, &id[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = match rest.len() {
+        2 => (rest, "00"),
+        4 => rest.split_at(2),
+        5 if rest.as_bytes()[2] == b':' => (&rest[..2], &rest[3..]),
+        _ => return None,
+    };
+    if !hours.bytes().chain(minutes.bytes()).all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = (hours.parse::<i32>().ok()?, minutes.parse::<i32>().ok()?);
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+
+    let offset = sign * (hours * 60 + minutes);
+    Some((TimeZone::Offset(offset), offset_time_zone_id(offset)))
+}
+
+fn get_component_option(
+    sc: &mut LocalScope<'_>,
+    options: Value,
+    property: Symbol,
+    values: &[(&str, FieldStyle)],
+    explicit: &mut Option<Symbol>,
+) -> Result<Option<FieldStyle>, Value> {
+    let value = get_string_option(sc, options, property, values)?;
+    if value.is_some() {
+        explicit.get_or_insert(property);
+    }
+    Ok(value)
+}
+
+impl DateTimeFormatOptions {
+    /// Implements the CreateDateTimeFormat abstract operation
+    pub fn new(
+        sc: &mut LocalScope<'_>,
+        locales: Value,
+        options: Value,
+        required: RequiredComponents,
+        defaults: DefaultComponents,
+    ) -> Result<Self, Value> {
+        const NUMERIC: &[(&str, FieldStyle)] = &[("numeric", FieldStyle::Numeric), ("2-digit", FieldStyle::TwoDigit)];
+        const TEXT: &[(&str, FieldStyle)] = &[
+            ("long", FieldStyle::Long),
+            ("short", FieldStyle::Short),
+            ("narrow", FieldStyle::Narrow),
+        ];
+        const MONTH: &[(&str, FieldStyle)] = &[
+            ("numeric", FieldStyle::Numeric),
+            ("2-digit", FieldStyle::TwoDigit),
+            ("long", FieldStyle::Long),
+            ("short", FieldStyle::Short),
+            ("narrow", FieldStyle::Narrow),
+        ];
+        const STYLES: &[(&str, DateTimeStyle)] = &[
+            ("full", DateTimeStyle::Full),
+            ("long", DateTimeStyle::Long),
+            ("medium", DateTimeStyle::Medium),
+            ("short", DateTimeStyle::Short),
+        ];
+
+        let requested = canonicalize_locale_list(sc, locales)?;
+        let options = coerce_options_to_object(sc, options)?;
+        // Only the lookup matcher is implemented, but the option is still validated
+        get_string_option(sc, options, sym::localeMatcher, &[("lookup", ()), ("best fit", ())])?;
+        let hour12 = get_bool_option(sc, options, sym::hour12)?;
+        let hour_cycle = get_string_option(
+            sc,
+            options,
+            sym::hourCycle,
+            &[
+                ("h11", HourCycle::H11),
+                ("h12", HourCycle::H12),
+                ("h23", HourCycle::H23),
+                ("h24", HourCycle::H24),
+            ],
+        )?;
+        let locale = resolve_locale(&requested);
+
+        let time_zone = options.get_property(sym::timeZone.to_key(sc), sc).root(sc)?;
+        let (time_zone, time_zone_id) = if matches!(time_zone.unpack(), ValueKind::Undefined(_)) {
+            if sc.params.timezone_offset_callback.is_some() {
+                // The embedder only provides offsets, so the identifier is the current offset
+                let now = match sc.params.time_millis_callback {
+                    Some(cb) => cb(sc).root_err(sc)? as f64,
+                    None => 0.0,
+                };
+                let offset = date::local_tza(sc, now)? / MS_PER_MINUTE;
+                (TimeZone::Local, offset_time_zone_id(offset as i32))
+            } else {
+                (TimeZone::Utc, String::from("UTC"))
+            }
+        } else {
+            let time_zone = time_zone.to_js_string(sc)?.res(sc).to_owned();
+            match parse_time_zone(&time_zone) {
+                Some(time_zone) => time_zone,
+                None => throw!(sc, RangeError, "Invalid time zone specified: {}", time_zone),
+            }
+        };
+
+        let mut explicit = None;
+        let mut components = Components {
+            weekday: get_component_option(sc, options, sym::weekday, TEXT, &mut explicit)?,
+            year: get_component_option(sc, options, sym::year, NUMERIC, &mut explicit)?,
+            month: get_component_option(sc, options, sym::month, MONTH, &mut explicit)?,
+            day: get_component_option(sc, options, sym::day, NUMERIC, &mut explicit)?,
+            hour: get_component_option(sc, options, sym::hour, NUMERIC, &mut explicit)?,
+            minute: get_component_option(sc, options, sym::minute, NUMERIC, &mut explicit)?,
+            second: get_component_option(sc, options, sym::second, NUMERIC, &mut explicit)?,
+            fractional_second_digits: get_number_option(sc, options, sym::fractionalSecondDigits, 1, 3)?,
+            long_time_zone_name: get_string_option(
+                sc,
+                options,
+                sym::timeZoneName,
+                &[("short", false), ("long", true)],
+            )?,
+        };
+        if components.fractional_second_digits.is_some() {
+            explicit.get_or_insert(sym::fractionalSecondDigits);
+        }
+        if components.long_time_zone_name.is_some() {
+            explicit.get_or_insert(sym::timeZoneName);
+        }
+
+        let date_style = get_string_option(sc, options, sym::dateStyle, STYLES)?;
+        let time_style = get_string_option(sc, options, sym::timeStyle, STYLES)?;
+
+        if date_style.is_some() || time_style.is_some() {
+            if let Some(explicit) = explicit {
+                let option = sc.interner.resolve(explicit).to_owned();
+                let style = if date_style.is_some() { "dateStyle" } else { "timeStyle" };
+                throw!(sc, TypeError, "Can't set option {} when {} is used", option, style);
+            }
+            if required == RequiredComponents::Date && time_style.is_some() {
+                throw!(sc, TypeError, "Invalid option : timeStyle");
+            }
+            if required == RequiredComponents::Time && date_style.is_some() {
+                throw!(sc, TypeError, "Invalid option : dateStyle");
+            }
+        } else {
+            let need_defaults = match required {
+                RequiredComponents::Date => !components.has_date(),
+                RequiredComponents::Time => !components.has_time(),
+                RequiredComponents::Any => !components.has_date() && !components.has_time(),
+            };
+            if need_defaults && matches!(defaults, DefaultComponents::Date | DefaultComponents::All) {
+                components.year = Some(FieldStyle::Numeric);
+                components.month = Some(FieldStyle::Numeric);
+                components.day = Some(FieldStyle::Numeric);
+            }
+            if need_defaults && matches!(defaults, DefaultComponents::Time | DefaultComponents::All) {
+                components.hour = Some(FieldStyle::Numeric);
+                components.minute = Some(FieldStyle::Numeric);
+                components.second = Some(FieldStyle::Numeric);
+            }
+        }
+
+        let (date, time) = match (date_style, time_style) {
+            (None, None) => {
+                let time = Components {
+                    weekday: None,
+                    year: None,
+                    month: None,
+                    day: None,
+                    ..components
+                };
+                (components.has_date().then_some(components), Some(time))
+            }
+            (date_style, time_style) => (
+                date_style.map(|style| Components::for_date_style(style, locale)),
+                time_style.map(Components::for_time_style),
+            ),
+        };
+
+        let hour_cycle = time.filter(|time| time.hour.is_some()).map(|_| match hour12 {
+            Some(true) => HourCycle::H12,
+            Some(false) => HourCycle::H23,
+            None => hour_cycle.unwrap_or(match locale {
+                Locale::Root => HourCycle::H23,
+                Locale::En | Locale::EnUs => HourCycle::H12,
+            }),
+        });
+
+        let mut pattern = date.map(|date| date.date_pattern(locale)).unwrap_or_default();
+        let time_pattern = time.map(|time| time.time_pattern(hour_cycle.unwrap_or(HourCycle::H23)));
+        if let Some(time_pattern) = time_pattern.filter(|p| !p.is_empty()) {
+            if !pattern.is_empty() {
+                let long_date = match date_style {
+                    Some(style) => matches!(style, DateTimeStyle::Full | DateTimeStyle::Long),
+                    None => date.is_some_and(|date| date.month == Some(FieldStyle::Long)),
+                };
+                pattern.push_str(match locale {
+                    Locale::Root => " ",
+                    _ if long_date => " 'at' ",
+                    _ => ", ",
+                });
+            }
+            pattern.push_str(&time_pattern);
+        }
+        if let Some(long) = time.and_then(|time| time.long_time_zone_name) {
+            if !pattern.is_empty() {
+                pattern.push_str(if time.is_some_and(|time| time.has_time()) {
+                    " "
+                } else {
+                    ", "
+                });
+            }
+            pattern.push_str(if long { "zzzz" } else { "z" });
+        }
+
+        Ok(Self {
+            locale,
+            time_zone,
+            time_zone_id,
+            hour_cycle,
+            date_style,
+            time_style,
+            pattern,
+        })
+    }
+
+    /// Returns the pattern fields (like `yy` or `MMMM`) and literals
+    fn pattern_items(&self) -> impl Iterator<Item = Result<(char, usize), String>> + '_ {
+        let mut chars = self.pattern.chars().peekable();
+        std::iter::from_fn(move || {
+            let c = chars.next()?;
+            if c == '\'' {
+                let mut literal = String::new();
+                for c in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                    literal.push(c);
+                }
+                Some(Err(literal))
+            } else if c.is_ascii_alphabetic() {
+                let mut count = 1;
+                while chars.next_if_eq(&c).is_some() {
+                    count += 1;
+                }
+                Some(Ok((c, count)))
+            } else {
+                Some(Err(c.to_string()))
+            }
+        })
+    }
+
+    /// Returns the components of the pattern as they are reported by `resolvedOptions()`
+    pub fn resolved_components(&self) -> Vec<(Symbol, ResolvedComponent)> {
+        let numeric = |count| ResolvedComponent::String(if count == 2 { "2-digit" } else { "numeric" });
+        let text = |count| {
+            ResolvedComponent::String(match count {
+                4 => "long",
+                5 => "narrow",
+                _ => "short",
+            })
+        };
+
+        let mut components: Vec<_> = self
+            .pattern_items()
+            .filter_map(Result::ok)
+            .filter_map(|(c, count)| match c {
+                'E' => Some((sym::weekday, text(count))),
+                'y' => Some((sym::year, numeric(count))),
+                'M' if count <= 2 => Some((sym::month, numeric(count))),
+                'M' => Some((sym::month, text(count))),
+                'd' => Some((sym::day, numeric(count))),
+                'h' | 'H' | 'K' | 'k' => Some((sym::hour, numeric(count))),
+                'm' => Some((sym::minute, numeric(count))),
+                's' => Some((sym::second, numeric(count))),
+                'S' => Some((sym::fractionalSecondDigits, ResolvedComponent::Number(count as u32))),
+                'z' if count == 4 => Some((sym::timeZoneName, ResolvedComponent::String("long"))),
+                'z' => Some((sym::timeZoneName, ResolvedComponent::String("short"))),
+                _ => None,
+            })
+            .collect();
+
+        // Properties are reported in the order of the table of components in the spec
+        let order = [
+            sym::weekday,
+            sym::year,
+            sym::month,
+            sym::day,
+            sym::hour,
+            sym::minute,
+            sym::second,
+            sym::fractionalSecondDigits,
+            sym::timeZoneName,
+        ];
+        components.sort_by_key(|&(key, _)| order.iter().position(|&k| k == key));
+        components
+    }
+
+    pub fn date_style_name(&self) -> Option<&'static str> {
+        self.date_style.map(DateTimeStyle::name)
+    }
+
+    pub fn time_style_name(&self) -> Option<&'static str> {
+        self.time_style.map(DateTimeStyle::name)
+    }
+
+    fn time_zone_name(&self, offset_minutes: i32, long: bool) -> String {
+        match (self.time_zone, long) {
+            (TimeZone::Utc, false) => String::from("UTC"),
+            (TimeZone::Utc, true) => String::from("Coordinated Universal Time"),
+            _ if offset_minutes == 0 => String::from("GMT"),
+            (_, true) => format!("GMT{}", offset_time_zone_id(offset_minutes)),
+            (_, false) => {
+                let sign = if offset_minutes < 0 { '-' } else { '+' };
+                let offset = offset_minutes.unsigned_abs();
+                match offset % 60 {
+                    0 => format!("GMT{sign}{}", offset / 60),
+                    minutes => format!("GMT{sign}{}:{minutes:02}", offset / 60),
+                }
+            }
+        }
+    }
+
+    /// Implements the FormatDateTimePattern abstract operation for a finite time value
+    pub fn format_to_parts(&self, sc: &mut LocalScope<'_>, t: f64) -> Result<Vec<(DatePartKind, String)>, Value> {
+        let offset = match self.time_zone {
+            TimeZone::Utc => 0.0,
+            TimeZone::Offset(minutes) => f64::from(minutes) * MS_PER_MINUTE,
+            TimeZone::Local => date::local_tza(sc, t)?,
+        };
+        let fields = DateFields::from_time(t + offset);
+        let english = self.locale != Locale::Root;
+
+        let mut parts: Vec<(DatePartKind, String)> = Vec::new();
+        for item in self.pattern_items() {
+            let (c, count) = match item {
+                Ok(field) => field,
+                Err(literal) => {
+                    match parts.last_mut() {
+                        Some((DatePartKind::Literal, last)) => last.push_str(&literal),
+                        _ => parts.push((DatePartKind::Literal, literal)),
+                    }
+                    continue;
+                }
+            };
+
+            let number = |n: f64| {
+                if count == 2 {
+                    format!("{:02}", (n as i64).rem_euclid(100))
+                } else {
+                    (n as i64).to_string()
+                }
+            };
+            let hours = fields.hours as i64;
+            let part = match c {
+                // Years before 1 AD are displayed as years of the BC era
+                'y' => (
+                    DatePartKind::Year,
+                    number(if fields.year <= 0.0 {
+                        1.0 - fields.year
+                    } else {
+                        fields.year
+                    }),
+                ),
+                'M' => {
+                    let month = fields.month as usize;
+                    let value = match count {
+                        1 | 2 => number(fields.month + 1.0),
+                        _ if !english => format!("M{:02}", month + 1),
+                        3 => MONTHS[month][..3].to_owned(),
+                        4 => MONTHS[month].to_owned(),
+                        _ => MONTHS[month][..1].to_owned(),
+                    };
+                    (DatePartKind::Month, value)
+                }
+                'd' => (DatePartKind::Day, number(fields.date)),
+                'E' => {
+                    let weekday = WEEKDAYS[fields.weekday as usize];
+                    let value = match count {
+                        4 if english => weekday,
+                        5 => &weekday[..1],
+                        _ => &weekday[..3],
+                    };
+                    (DatePartKind::Weekday, value.to_owned())
+                }
+                'a' => (
+                    DatePartKind::DayPeriod,
+                    String::from(if hours < 12 { "AM" } else { "PM" }),
+                ),
+                'h' => (DatePartKind::Hour, number(((hours + 11) % 12 + 1) as f64)),
+                'K' => (DatePartKind::Hour, number((hours % 12) as f64)),
+                'H' => (DatePartKind::Hour, number(hours as f64)),
+                'k' => (DatePartKind::Hour, number(if hours == 0 { 24.0 } else { hours as f64 })),
+                'm' => (DatePartKind::Minute, number(fields.minutes)),
+                's' => (DatePartKind::Second, number(fields.seconds)),
+                'S' => {
+                    let mut millis = format!("{:03}", fields.milliseconds as u32);
+                    millis.truncate(count);
+                    (DatePartKind::FractionalSecond, millis)
+                }
+                'z' => (
+                    DatePartKind::TimeZoneName,
+                    self.time_zone_name((offset / MS_PER_MINUTE) as i32, count == 4),
+                ),
+                _ => (DatePartKind::Literal, std::iter::repeat_n(c, count).collect()),
+            };
+            parts.push(part);
+        }
+
+        Ok(parts)
+    }
+
+    /// Implements the FormatDateTime abstract operation for a finite time value
+    pub fn format(&self, sc: &mut LocalScope<'_>, t: f64) -> Result<String, Value> {
+        Ok(self.format_to_parts(sc, t)?.into_iter().map(|(_, part)| part).collect())
+    }
+}
+
+#[derive(Debug, Trace)]
+pub struct DateTimeFormat {
+    pub options: DateTimeFormatOptions,
+    /// The function returned by the `format` getter, which is created lazily
+    pub bound_format: Cell<Option<ObjectId>>,
+    object: OrdObject,
+}
+
+impl DateTimeFormat {
+    pub fn new(options: DateTimeFormatOptions, object: OrdObject) -> Self {
+        Self {
+            options,
+            bound_format: Cell::new(None),
+            object,
+        }
+    }
+}
+
+impl Object for DateTimeFormat {
+    delegate!(
+        object,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        apply,
+        own_keys
+    );
+
+    extract!(self);
+}
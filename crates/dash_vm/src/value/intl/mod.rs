@@ -0,0 +1,254 @@
+//! Locale negotiation and option processing shared by the `Intl` services.
+//!
+//! Locale data only exists for `en-US` (which `en` also uses) and the root locale `und`.
+//! Any other requested locale falls back to the default locale, `en-US`.
+
+use dash_middle::interner::Symbol;
+
+use crate::localscope::LocalScope;
+use crate::throw;
+use crate::value::object::{Object, OrdObject};
+use crate::value::ops::conversions::ValueConversion;
+use crate::value::propertykey::ToPropertyKey;
+use crate::value::root_ext::RootErrExt;
+use crate::value::{Root, Unpack, Value, ValueKind};
+
+pub mod collator;
+pub mod date_time_format;
+pub mod number_format;
+
+/// A locale with available locale data
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Locale {
+    EnUs,
+    En,
+    Root,
+}
+
+impl Locale {
+    pub fn tag(self) -> &'static str {
+        match self {
+            Self::EnUs => "en-US",
+            Self::En => "en",
+            Self::Root => "und",
+        }
+    }
+
+    fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "en-US" => Some(Self::EnUs),
+            "en" => Some(Self::En),
+            "und" => Some(Self::Root),
+            _ => None,
+        }
+    }
+}
+
+/// Canonicalizes a structurally valid BCP 47 language tag, e.g. `EN-latn-us` becomes `en-Latn-US`
+pub fn canonicalize_language_tag(tag: &str) -> Option<String> {
+    /// The kind of subtag that is expected next
+    #[derive(PartialEq, PartialOrd)]
+    enum Position {
+        Script,
+        Region,
+        Variant,
+        Extension,
+    }
+
+    let mut subtags = tag.split('-');
+    let language = subtags.next()?;
+    if !matches!(language.len(), 2..=3 | 5..=8) || !language.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let mut out = language.to_ascii_lowercase();
+    let mut position = Position::Script;
+    // Extension singletons (like the `u` in `en-u-nu-latn`) must be followed by at least one subtag
+    let mut dangling_singleton = false;
+    for subtag in subtags {
+        if subtag.is_empty() || subtag.len() > 8 || !subtag.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return None;
+        }
+
+        out.push('-');
+        let alphabetic = subtag.bytes().all(|b| b.is_ascii_alphabetic());
+        let numeric = subtag.bytes().all(|b| b.is_ascii_digit());
+        if subtag.len() == 1 {
+            position = Position::Extension;
+            dangling_singleton = true;
+            out.push_str(&subtag.to_ascii_lowercase());
+        } else if position == Position::Extension {
+            dangling_singleton = false;
+            out.push_str(&subtag.to_ascii_lowercase());
+        } else if position == Position::Script && subtag.len() == 4 && alphabetic {
+            position = Position::Region;
+            out.push_str(&subtag[..1].to_ascii_uppercase());
+            out.push_str(&subtag[1..].to_ascii_lowercase());
+        } else if position <= Position::Region && ((subtag.len() == 2 && alphabetic) || (subtag.len() == 3 && numeric))
+        {
+            position = Position::Variant;
+            out.push_str(&subtag.to_ascii_uppercase());
+        } else if subtag.len() >= 5 || (subtag.len() == 4 && subtag.as_bytes()[0].is_ascii_digit()) {
+            position = Position::Variant;
+            out.push_str(&subtag.to_ascii_lowercase());
+        } else {
+            return None;
+        }
+    }
+
+    (!dangling_singleton).then_some(out)
+}
+
+/// Implements the CanonicalizeLocaleList abstract operation
+pub fn canonicalize_locale_list(sc: &mut LocalScope<'_>, locales: Value) -> Result<Vec<String>, Value> {
+    if matches!(locales.unpack(), ValueKind::Undefined(_)) {
+        return Ok(Vec::new());
+    }
+
+    let tags = match locales.unpack() {
+        ValueKind::String(string) => vec![Value::string(string)],
+        _ => {
+            let object = Value::object(locales.to_object(sc)?);
+            let len = object.length_of_array_like(sc)?;
+            let mut tags = Vec::with_capacity(len);
+            for index in 0..len {
+                let key = index.to_key(sc);
+                if object.has_property(key, sc).root_err(sc)? {
+                    tags.push(object.get_property(key, sc).root(sc)?);
+                }
+            }
+            tags
+        }
+    };
+
+    let mut seen = Vec::with_capacity(tags.len());
+    for tag in tags {
+        if !matches!(tag.unpack(), ValueKind::String(_) | ValueKind::Object(_)) {
+            throw!(sc, TypeError, "Language ID should be string or object.");
+        }
+
+        let tag = tag.to_js_string(sc)?;
+        let Some(canonicalized) = canonicalize_language_tag(tag.res(sc)) else {
+            throw!(sc, RangeError, "Incorrect locale information provided");
+        };
+        if !seen.contains(&canonicalized) {
+            seen.push(canonicalized);
+        }
+    }
+
+    Ok(seen)
+}
+
+/// Implements the BestAvailableLocale abstract operation, ignoring any extensions of the tag
+fn best_available_locale(tag: &str) -> Option<Locale> {
+    let mut candidate = tag.split("-u-").next().unwrap_or(tag);
+    loop {
+        if let Some(locale) = Locale::from_tag(candidate) {
+            return Some(locale);
+        }
+
+        let (rest, _) = candidate.rsplit_once('-')?;
+        // Never leave a singleton subtag at the end, e.g. `en-x-private` -> `en`
+        candidate = match rest.rsplit_once('-') {
+            Some((before, singleton)) if singleton.len() == 1 => before,
+            _ => rest,
+        };
+    }
+}
+
+/// Implements the ResolveLocale abstract operation using the lookup matcher
+pub fn resolve_locale(requested: &[String]) -> Locale {
+    requested
+        .iter()
+        .find_map(|tag| best_available_locale(tag))
+        .unwrap_or(Locale::EnUs)
+}
+
+/// Implements the SupportedLocales abstract operation
+pub fn supported_locales(requested: Vec<String>) -> Vec<String> {
+    requested
+        .into_iter()
+        .filter(|tag| best_available_locale(tag).is_some())
+        .collect()
+}
+
+/// Implements the CoerceOptionsToObject abstract operation
+pub fn coerce_options_to_object(sc: &mut LocalScope<'_>, options: Value) -> Result<Value, Value> {
+    if matches!(options.unpack(), ValueKind::Undefined(_)) {
+        Ok(Value::object(sc.register(OrdObject::null())))
+    } else {
+        Ok(Value::object(options.to_object(sc)?))
+    }
+}
+
+/// Implements the GetOption abstract operation for options of type string,
+/// mapping the value to the corresponding entry in `values`
+pub fn get_string_option<T: Copy>(
+    sc: &mut LocalScope<'_>,
+    options: Value,
+    property: Symbol,
+    values: &[(&str, T)],
+) -> Result<Option<T>, Value> {
+    let value = options.get_property(property.to_key(sc), sc).root(sc)?;
+    if matches!(value.unpack(), ValueKind::Undefined(_)) {
+        return Ok(None);
+    }
+
+    let value = value.to_js_string(sc)?.res(sc).to_owned();
+    match values.iter().find(|(name, _)| *name == value) {
+        Some(&(_, value)) => Ok(Some(value)),
+        None => {
+            let property = sc.interner.resolve(property).to_owned();
+            throw!(
+                sc,
+                RangeError,
+                "Value {} out of range for options property {}",
+                value,
+                property
+            )
+        }
+    }
+}
+
+/// Implements the GetOption abstract operation for options of type boolean
+pub fn get_bool_option(sc: &mut LocalScope<'_>, options: Value, property: Symbol) -> Result<Option<bool>, Value> {
+    let value = options.get_property(property.to_key(sc), sc).root(sc)?;
+    if matches!(value.unpack(), ValueKind::Undefined(_)) {
+        Ok(None)
+    } else {
+        Ok(Some(value.to_boolean(sc)?))
+    }
+}
+
+/// Implements the DefaultNumberOption abstract operation
+pub fn default_number_option(
+    sc: &mut LocalScope<'_>,
+    value: Value,
+    property: Symbol,
+    min: u32,
+    max: u32,
+) -> Result<Option<u32>, Value> {
+    if matches!(value.unpack(), ValueKind::Undefined(_)) {
+        return Ok(None);
+    }
+
+    let value = value.to_number(sc)?;
+    if value.is_nan() || value < f64::from(min) || value > f64::from(max) {
+        let property = sc.interner.resolve(property).to_owned();
+        throw!(sc, RangeError, "{} value is out of range.", property);
+    }
+
+    Ok(Some(value.floor() as u32))
+}
+
+/// Implements the GetNumberOption abstract operation
+pub fn get_number_option(
+    sc: &mut LocalScope<'_>,
+    options: Value,
+    property: Symbol,
+    min: u32,
+    max: u32,
+) -> Result<Option<u32>, Value> {
+    let value = options.get_property(property.to_key(sc), sc).root(sc)?;
+    default_number_option(sc, value, property, min, max)
+}
@@ -0,0 +1,597 @@
+use std::cell::Cell;
+
+use dash_middle::interner::sym;
+use dash_proc_macro::Trace;
+
+use crate::gc::ObjectId;
+use crate::gc::trace::{Trace, TraceCtxt};
+use crate::localscope::LocalScope;
+use crate::util::{round_digits, shortest_digits};
+use crate::value::object::{Object, OrdObject};
+use crate::value::ops::conversions::ValueConversion;
+use crate::value::propertykey::ToPropertyKey;
+use crate::value::{Root, Unpack, Value, ValueKind};
+use crate::{delegate, extract, throw};
+
+use super::{
+    Locale, canonicalize_locale_list, coerce_options_to_object, default_number_option, get_number_option,
+    get_string_option, resolve_locale,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NumberStyle {
+    Decimal,
+    Percent,
+    Currency,
+}
+
+impl NumberStyle {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Decimal => "decimal",
+            Self::Percent => "percent",
+            Self::Currency => "currency",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CurrencyDisplay {
+    Symbol,
+    NarrowSymbol,
+    Code,
+    Name,
+}
+
+impl CurrencyDisplay {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Symbol => "symbol",
+            Self::NarrowSymbol => "narrowSymbol",
+            Self::Code => "code",
+            Self::Name => "name",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Grouping {
+    Always,
+    Auto,
+    /// Only group if there are at least two digits in the highest group, e.g. `1000` but `10,000`
+    Min2,
+    Never,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SignDisplay {
+    Auto,
+    Never,
+    Always,
+    ExceptZero,
+    Negative,
+}
+
+impl SignDisplay {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Never => "never",
+            Self::Always => "always",
+            Self::ExceptZero => "exceptZero",
+            Self::Negative => "negative",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Rounding {
+    Fraction { min: u32, max: u32 },
+    Significant { min: u32, max: u32 },
+}
+
+/// The type of a part returned by `Intl.NumberFormat.prototype.formatToParts`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NumberPartKind {
+    Integer,
+    Group,
+    Decimal,
+    Fraction,
+    MinusSign,
+    PlusSign,
+    PercentSign,
+    Currency,
+    Literal,
+    Nan,
+    Infinity,
+}
+
+impl NumberPartKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Integer => "integer",
+            Self::Group => "group",
+            Self::Decimal => "decimal",
+            Self::Fraction => "fraction",
+            Self::MinusSign => "minusSign",
+            Self::PlusSign => "plusSign",
+            Self::PercentSign => "percentSign",
+            Self::Currency => "currency",
+            Self::Literal => "literal",
+            Self::Nan => "nan",
+            Self::Infinity => "infinity",
+        }
+    }
+}
+
+struct CurrencyData {
+    code: &'static str,
+    symbol: &'static str,
+    /// The symbol used by the root locale, which avoids ambiguous symbols like `$`
+    root_symbol: &'static str,
+    narrow_symbol: &'static str,
+    digits: u32,
+    singular_name: &'static str,
+    plural_name: &'static str,
+}
+
+#[rustfmt::skip]
+const CURRENCIES: &[CurrencyData] = &[
+    CurrencyData { code: "AUD", symbol: "A$", root_symbol: "A$", narrow_symbol: "$", digits: 2, singular_name: "Australian dollar", plural_name: "Australian dollars" },
+    CurrencyData { code: "BRL", symbol: "R$", root_symbol: "R$", narrow_symbol: "R$", digits: 2, singular_name: "Brazilian real", plural_name: "Brazilian reals" },
+    CurrencyData { code: "CAD", symbol: "CA$", root_symbol: "CA$", narrow_symbol: "$", digits: 2, singular_name: "Canadian dollar", plural_name: "Canadian dollars" },
+    CurrencyData { code: "CHF", symbol: "CHF", root_symbol: "CHF", narrow_symbol: "CHF", digits: 2, singular_name: "Swiss franc", plural_name: "Swiss francs" },
+    CurrencyData { code: "CNY", symbol: "CN¥", root_symbol: "CN¥", narrow_symbol: "¥", digits: 2, singular_name: "Chinese yuan", plural_name: "Chinese yuan" },
+    CurrencyData { code: "EUR", symbol: "€", root_symbol: "€", narrow_symbol: "€", digits: 2, singular_name: "euro", plural_name: "euros" },
+    CurrencyData { code: "GBP", symbol: "£", root_symbol: "£", narrow_symbol: "£", digits: 2, singular_name: "British pound", plural_name: "British pounds" },
+    CurrencyData { code: "INR", symbol: "₹", root_symbol: "₹", narrow_symbol: "₹", digits: 2, singular_name: "Indian rupee", plural_name: "Indian rupees" },
+    CurrencyData { code: "JPY", symbol: "¥", root_symbol: "JP¥", narrow_symbol: "¥", digits: 0, singular_name: "Japanese yen", plural_name: "Japanese yen" },
+    CurrencyData { code: "KRW", symbol: "₩", root_symbol: "₩", narrow_symbol: "₩", digits: 0, singular_name: "South Korean won", plural_name: "South Korean won" },
+    CurrencyData { code: "MXN", symbol: "MX$", root_symbol: "MX$", narrow_symbol: "$", digits: 2, singular_name: "Mexican peso", plural_name: "Mexican pesos" },
+    CurrencyData { code: "USD", symbol: "$", root_symbol: "US$", narrow_symbol: "$", digits: 2, singular_name: "US dollar", plural_name: "US dollars" },
+];
+
+fn currency_data(code: &str) -> Option<&'static CurrencyData> {
+    CURRENCIES.iter().find(|c| c.code == code)
+}
+
+/// Returns the number of fraction digits conventionally used for a currency
+fn currency_digits(code: &str) -> u32 {
+    currency_data(code).map_or(2, |c| c.digits)
+}
+
+/// A value to be formatted, i.e. the result of the ToIntlMathematicalValue abstract operation
+#[derive(Debug, Clone)]
+pub enum MathematicalValue {
+    /// A finite value `0.d1d2d3... * 10^exponent`, where an empty list of digits represents zero
+    Finite {
+        negative: bool,
+        digits: Vec<u8>,
+        exponent: i32,
+    },
+    Infinity {
+        negative: bool,
+    },
+    NaN,
+}
+
+impl MathematicalValue {
+    pub fn from_f64(n: f64) -> Self {
+        if n.is_nan() {
+            Self::NaN
+        } else if n.is_infinite() {
+            Self::Infinity { negative: n < 0.0 }
+        } else if n == 0.0 {
+            Self::Finite {
+                negative: n.is_sign_negative(),
+                digits: Vec::new(),
+                exponent: 0,
+            }
+        } else {
+            let (digits, exponent) = shortest_digits(n);
+            Self::Finite {
+                negative: n < 0.0,
+                digits,
+                exponent,
+            }
+        }
+    }
+
+    /// Creates a value from an integer in its decimal string representation, such as a stringified BigInt
+    pub fn from_integer_string(s: &str) -> Self {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s),
+        };
+        let digits = digits.trim_start_matches('0');
+        let mut digits = digits.as_bytes().to_vec();
+        let exponent = digits.len() as i32;
+        while digits.last() == Some(&b'0') {
+            digits.pop();
+        }
+
+        Self::Finite {
+            negative,
+            digits,
+            exponent,
+        }
+    }
+}
+
+/// The resolved options of an `Intl.NumberFormat`
+#[derive(Debug, Clone)]
+pub struct NumberFormatOptions {
+    pub locale: Locale,
+    pub style: NumberStyle,
+    /// The upper case ISO 4217 currency code. Always present for the currency style.
+    pub currency: Option<String>,
+    pub currency_display: CurrencyDisplay,
+    pub minimum_integer_digits: u32,
+    pub rounding: Rounding,
+    pub grouping: Grouping,
+    pub sign_display: SignDisplay,
+}
+
+unsafe impl Trace for NumberFormatOptions {
+    fn trace(&self, _: &mut TraceCtxt<'_>) {
+        // No GC references
+    }
+}
+
+impl NumberFormatOptions {
+    /// Implements the InitializeNumberFormat abstract operation
+    pub fn new(sc: &mut LocalScope<'_>, locales: Value, options: Value) -> Result<Self, Value> {
+        let requested = canonicalize_locale_list(sc, locales)?;
+        let options = coerce_options_to_object(sc, options)?;
+        // Only the lookup matcher is implemented, but the option is still validated
+        get_string_option(sc, options, sym::localeMatcher, &[("lookup", ()), ("best fit", ())])?;
+        let locale = resolve_locale(&requested);
+
+        let style = get_string_option(
+            sc,
+            options,
+            sym::style,
+            &[
+                ("decimal", NumberStyle::Decimal),
+                ("percent", NumberStyle::Percent),
+                ("currency", NumberStyle::Currency),
+            ],
+        )?
+        .unwrap_or(NumberStyle::Decimal);
+
+        let currency = options.get_property(sym::currency.to_key(sc), sc).root(sc)?;
+        let currency = if matches!(currency.unpack(), ValueKind::Undefined(_)) {
+            None
+        } else {
+            let currency = currency.to_js_string(sc)?.res(sc).to_owned();
+            if currency.len() != 3 || !currency.bytes().all(|b| b.is_ascii_alphabetic()) {
+                throw!(sc, RangeError, "Invalid currency code : {}", currency);
+            }
+            Some(currency.to_ascii_uppercase())
+        };
+
+        let currency_display = get_string_option(
+            sc,
+            options,
+            sym::currencyDisplay,
+            &[
+                ("symbol", CurrencyDisplay::Symbol),
+                ("narrowSymbol", CurrencyDisplay::NarrowSymbol),
+                ("code", CurrencyDisplay::Code),
+                ("name", CurrencyDisplay::Name),
+            ],
+        )?
+        .unwrap_or(CurrencyDisplay::Symbol);
+
+        let currency = match (style, currency) {
+            (NumberStyle::Currency, None) => {
+                throw!(sc, TypeError, "Currency code is required with currency style.")
+            }
+            (NumberStyle::Currency, currency) => currency,
+            _ => None,
+        };
+
+        let (default_min_fraction, default_max_fraction) = match (style, &currency) {
+            (NumberStyle::Currency, Some(code)) => (currency_digits(code), currency_digits(code)),
+            (NumberStyle::Percent, _) => (0, 0),
+            _ => (0, 3),
+        };
+
+        // SetNumberFormatDigitOptions
+        let minimum_integer_digits = get_number_option(sc, options, sym::minimumIntegerDigits, 1, 21)?.unwrap_or(1);
+        let min_fraction = options
+            .get_property(sym::minimumFractionDigits.to_key(sc), sc)
+            .root(sc)?;
+        let max_fraction = options
+            .get_property(sym::maximumFractionDigits.to_key(sc), sc)
+            .root(sc)?;
+        let min_significant = options
+            .get_property(sym::minimumSignificantDigits.to_key(sc), sc)
+            .root(sc)?;
+        let max_significant = options
+            .get_property(sym::maximumSignificantDigits.to_key(sc), sc)
+            .root(sc)?;
+
+        let rounding = if !matches!(min_significant.unpack(), ValueKind::Undefined(_))
+            || !matches!(max_significant.unpack(), ValueKind::Undefined(_))
+        {
+            let min = default_number_option(sc, min_significant, sym::minimumSignificantDigits, 1, 21)?.unwrap_or(1);
+            let max = default_number_option(sc, max_significant, sym::maximumSignificantDigits, min, 21)?.unwrap_or(21);
+            Rounding::Significant { min, max }
+        } else {
+            let min = default_number_option(sc, min_fraction, sym::minimumFractionDigits, 0, 100)?;
+            let max = default_number_option(sc, max_fraction, sym::maximumFractionDigits, 0, 100)?;
+            let (min, max) = match (min, max) {
+                (None, None) => (default_min_fraction, default_max_fraction),
+                (None, Some(max)) => (default_min_fraction.min(max), max),
+                (Some(min), None) => (min, default_max_fraction.max(min)),
+                (Some(min), Some(max)) if min > max => {
+                    throw!(sc, RangeError, "maximumFractionDigits value is out of range.")
+                }
+                (Some(min), Some(max)) => (min, max),
+            };
+            Rounding::Fraction { min, max }
+        };
+
+        // GetBooleanOrStringNumberFormatOption
+        let use_grouping = options.get_property(sym::useGrouping.to_key(sc), sc).root(sc)?;
+        let grouping = if matches!(use_grouping.unpack(), ValueKind::Undefined(_)) {
+            Grouping::Auto
+        } else if use_grouping == Value::boolean(true) {
+            Grouping::Always
+        } else if !use_grouping.to_boolean(sc)? {
+            Grouping::Never
+        } else {
+            let use_grouping = use_grouping.to_js_string(sc)?.res(sc).to_owned();
+            match use_grouping.as_str() {
+                "always" => Grouping::Always,
+                "auto" | "true" | "false" => Grouping::Auto,
+                "min2" => Grouping::Min2,
+                other => throw!(
+                    sc,
+                    RangeError,
+                    "Value {} out of range for options property useGrouping",
+                    other
+                ),
+            }
+        };
+
+        let sign_display = get_string_option(
+            sc,
+            options,
+            sym::signDisplay,
+            &[
+                ("auto", SignDisplay::Auto),
+                ("never", SignDisplay::Never),
+                ("always", SignDisplay::Always),
+                ("exceptZero", SignDisplay::ExceptZero),
+                ("negative", SignDisplay::Negative),
+            ],
+        )?
+        .unwrap_or(SignDisplay::Auto);
+
+        Ok(Self {
+            locale,
+            style,
+            currency,
+            currency_display,
+            minimum_integer_digits,
+            rounding,
+            grouping,
+            sign_display,
+        })
+    }
+
+    /// Rounds the digits according to the rounding options,
+    /// returning the digits of the integer and fraction part
+    fn round(&self, digits: Vec<u8>, exponent: i32) -> (String, String) {
+        let max_digits = match self.rounding {
+            Rounding::Fraction { max, .. } => exponent + max as i32,
+            Rounding::Significant { max, .. } => max as i32,
+        };
+
+        let (mut digits, mut exponent) = if max_digits < 0 {
+            (Vec::new(), 0)
+        } else if (max_digits as usize) < digits.len() {
+            round_digits((digits, exponent), max_digits as usize)
+        } else {
+            (digits, exponent)
+        };
+        while digits.last() == Some(&b'0') {
+            digits.pop();
+        }
+
+        if digits.is_empty() {
+            // Zero has one significant digit in front of the decimal point
+            exponent = 1;
+        }
+        let min_digits = match self.rounding {
+            Rounding::Fraction { min, .. } => exponent + min as i32,
+            Rounding::Significant { min, .. } => min as i32,
+        };
+        if (digits.len() as i32) < min_digits {
+            digits.resize(min_digits as usize, b'0');
+        }
+
+        let mut integer = String::new();
+        let mut fraction = String::new();
+        for (index, &digit) in digits.iter().enumerate() {
+            if (index as i32) < exponent {
+                integer.push(char::from(digit));
+            } else {
+                fraction.push(char::from(digit));
+            }
+        }
+        if exponent > digits.len() as i32 {
+            integer.extend(std::iter::repeat_n('0', exponent as usize - digits.len()));
+        } else if exponent < 0 {
+            fraction.insert_str(0, &"0".repeat(-exponent as usize));
+        }
+        if integer.len() < self.minimum_integer_digits as usize {
+            integer.insert_str(0, &"0".repeat(self.minimum_integer_digits as usize - integer.len()));
+        }
+
+        (integer, fraction)
+    }
+
+    fn currency_symbol(&self, data: &CurrencyData) -> &'static str {
+        match self.currency_display {
+            _ if self.locale == Locale::Root => data.root_symbol,
+            CurrencyDisplay::NarrowSymbol => data.narrow_symbol,
+            _ => data.symbol,
+        }
+    }
+
+    /// Implements the PartitionNumberPattern abstract operation
+    pub fn format_to_parts(&self, value: MathematicalValue) -> Vec<(NumberPartKind, String)> {
+        let mut number = Vec::new();
+        let (negative, zero) = match value {
+            MathematicalValue::NaN => {
+                number.push((NumberPartKind::Nan, String::from("NaN")));
+                (false, true)
+            }
+            MathematicalValue::Infinity { negative } => {
+                number.push((NumberPartKind::Infinity, String::from("∞")));
+                (negative, false)
+            }
+            MathematicalValue::Finite {
+                negative,
+                digits,
+                exponent,
+            } => {
+                let exponent = match self.style {
+                    NumberStyle::Percent if !digits.is_empty() => exponent + 2,
+                    _ => exponent,
+                };
+                let (integer, fraction) = self.round(digits, exponent);
+                let zero = integer.bytes().chain(fraction.bytes()).all(|b| b == b'0');
+
+                let min_grouping_length = match self.grouping {
+                    Grouping::Always | Grouping::Auto => 4,
+                    Grouping::Min2 => 5,
+                    Grouping::Never => usize::MAX,
+                };
+                if integer.len() >= min_grouping_length {
+                    let first_group = match integer.len() % 3 {
+                        0 => 3,
+                        n => n,
+                    };
+                    number.push((NumberPartKind::Integer, integer[..first_group].to_owned()));
+                    for group in integer.as_bytes()[first_group..].chunks(3) {
+                        number.push((NumberPartKind::Group, String::from(",")));
+                        number.push((NumberPartKind::Integer, String::from_utf8(group.to_vec()).unwrap()));
+                    }
+                } else {
+                    number.push((NumberPartKind::Integer, integer));
+                }
+
+                if !fraction.is_empty() {
+                    number.push((NumberPartKind::Decimal, String::from(".")));
+                    number.push((NumberPartKind::Fraction, fraction));
+                }
+
+                (negative, zero)
+            }
+        };
+
+        let sign = match self.sign_display {
+            SignDisplay::Auto => negative.then_some(NumberPartKind::MinusSign),
+            SignDisplay::Never => None,
+            SignDisplay::Always if negative => Some(NumberPartKind::MinusSign),
+            SignDisplay::Always => Some(NumberPartKind::PlusSign),
+            SignDisplay::ExceptZero if zero => None,
+            SignDisplay::ExceptZero if negative => Some(NumberPartKind::MinusSign),
+            SignDisplay::ExceptZero => Some(NumberPartKind::PlusSign),
+            SignDisplay::Negative => (negative && !zero).then_some(NumberPartKind::MinusSign),
+        };
+
+        let mut parts = Vec::with_capacity(number.len() + 3);
+        match sign {
+            Some(NumberPartKind::MinusSign) => parts.push((NumberPartKind::MinusSign, String::from("-"))),
+            Some(_) => parts.push((NumberPartKind::PlusSign, String::from("+"))),
+            None => {}
+        }
+
+        match (self.style, self.currency.as_deref()) {
+            (NumberStyle::Currency, Some(code)) => {
+                let data = currency_data(code);
+                if self.currency_display == CurrencyDisplay::Name && self.locale != Locale::Root {
+                    // English only distinguishes between one and other: `1 US dollar`, but `1.00 US dollars`
+                    let one = matches!(number.as_slice(), [(NumberPartKind::Integer, integer)] if integer == "1");
+                    let name = match data {
+                        Some(data) if one => data.singular_name.to_owned(),
+                        Some(data) => data.plural_name.to_owned(),
+                        None => code.to_owned(),
+                    };
+                    parts.extend(number);
+                    parts.push((NumberPartKind::Literal, String::from(" ")));
+                    parts.push((NumberPartKind::Currency, name));
+                } else {
+                    let symbol = match data {
+                        Some(data) if self.currency_display != CurrencyDisplay::Code => {
+                            self.currency_symbol(data).to_owned()
+                        }
+                        _ => code.to_owned(),
+                    };
+                    // Symbols that end in a letter are separated from the number, e.g. `CHF 1.00`
+                    let spaced = self.locale == Locale::Root || symbol.ends_with(|c: char| c.is_alphabetic());
+                    parts.push((NumberPartKind::Currency, symbol));
+                    if spaced {
+                        parts.push((NumberPartKind::Literal, String::from("\u{a0}")));
+                    }
+                    parts.extend(number);
+                }
+            }
+            (NumberStyle::Percent, _) => {
+                parts.extend(number);
+                parts.push((NumberPartKind::PercentSign, String::from("%")));
+            }
+            _ => parts.extend(number),
+        }
+
+        parts
+    }
+
+    /// Implements the FormatNumeric abstract operation
+    pub fn format(&self, value: MathematicalValue) -> String {
+        self.format_to_parts(value).into_iter().map(|(_, part)| part).collect()
+    }
+}
+
+#[derive(Debug, Trace)]
+pub struct NumberFormat {
+    pub options: NumberFormatOptions,
+    /// The function returned by the `format` getter, which is created lazily
+    pub bound_format: Cell<Option<ObjectId>>,
+    object: OrdObject,
+}
+
+impl NumberFormat {
+    pub fn new(options: NumberFormatOptions, object: OrdObject) -> Self {
+        Self {
+            options,
+            bound_format: Cell::new(None),
+            object,
+        }
+    }
+}
+
+impl Object for NumberFormat {
+    delegate!(
+        object,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        apply,
+        own_keys
+    );
+
+    extract!(self);
+}
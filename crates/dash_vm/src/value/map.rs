@@ -1,16 +1,116 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
 use ahash::HashMap;
 use dash_proc_macro::Trace;
 
+use crate::gc::trace::Trace;
 use crate::{Vm, delegate, extract};
 
 use super::Value;
 use super::object::{Object, OrdObject};
 
+/// An insertion-ordered hash table used as the backing storage for `Map` and `Set`.
+///
+/// Deleted entries leave a hole behind, so that the position of live iterators stays valid
+/// while the table is being mutated. The holes are only released once the table becomes empty,
+/// at which point the generation is bumped and outdated iterators restart from the beginning.
+#[derive(Debug, Trace)]
+pub struct OrderedTable<V: Trace> {
+    entries: Vec<Option<(Value, V)>>,
+    indices: HashMap<Value, usize>,
+    generation: u32,
+}
+
+impl<V: Trace + Clone> OrderedTable<V> {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            indices: HashMap::default(),
+            generation: 0,
+        }
+    }
+
+    pub fn insert(&mut self, key: Value, value: V) {
+        match self.indices.get(&key) {
+            Some(&index) => self.entries[index] = Some((key, value)),
+            None => {
+                self.indices.insert(key, self.entries.len());
+                self.entries.push(Some((key, value)));
+            }
+        }
+    }
+
+    pub fn contains(&self, key: &Value) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    pub fn get(&self, key: &Value) -> Option<&V> {
+        let index = *self.indices.get(key)?;
+        self.entries[index].as_ref().map(|(_, v)| v)
+    }
+
+    pub fn remove(&mut self, key: &Value) -> bool {
+        match self.indices.remove(key) {
+            Some(index) => {
+                self.entries[index] = None;
+                if self.indices.is_empty() {
+                    self.clear();
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.indices.clear();
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Returns the next live entry at or after the cursor and advances it past that entry
+    pub fn next_entry(&self, cursor: &TableCursor) -> Option<(Value, V)> {
+        if cursor.generation.get() != self.generation {
+            cursor.generation.set(self.generation);
+            cursor.index.set(0);
+        }
+
+        let start = cursor.index.get();
+        let (offset, entry) = self
+            .entries
+            .get(start..)?
+            .iter()
+            .enumerate()
+            .find_map(|(i, e)| Some((i, e.as_ref()?)))?;
+        cursor.index.set(start + offset + 1);
+        Some(entry.clone())
+    }
+}
+
+impl<V: Trace + Clone> Default for OrderedTable<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The position of an iterator in an [`OrderedTable`]
+#[derive(Debug, Default, Trace)]
+pub struct TableCursor {
+    index: Cell<usize>,
+    generation: Cell<u32>,
+}
+
 #[derive(Debug, Trace)]
 pub struct Map {
-    inner: RefCell<HashMap<Value, Value>>,
+    inner: RefCell<OrderedTable<Value>>,
     obj: OrdObject,
 }
 
@@ -21,7 +121,7 @@ impl Map {
 
     pub fn with_obj(obj: OrdObject) -> Self {
         Self {
-            inner: RefCell::new(HashMap::default()),
+            inner: RefCell::new(OrderedTable::new()),
             obj,
         }
     }
@@ -31,7 +131,7 @@ impl Map {
     }
 
     pub fn has(&self, item: &Value) -> bool {
-        self.inner.borrow().contains_key(item)
+        self.inner.borrow().contains(item)
     }
 
     pub fn get(&self, item: &Value) -> Option<Value> {
@@ -39,7 +139,7 @@ impl Map {
     }
 
     pub fn delete(&self, item: &Value) -> bool {
-        self.inner.borrow_mut().remove(item).is_some()
+        self.inner.borrow_mut().remove(item)
     }
 
     pub fn clear(&self) {
@@ -49,6 +149,10 @@ impl Map {
     pub fn size(&self) -> usize {
         self.inner.borrow().len()
     }
+
+    pub fn next_entry(&self, cursor: &TableCursor) -> Option<(Value, Value)> {
+        self.inner.borrow().next_entry(cursor)
+    }
 }
 
 impl Object for Map {
@@ -58,9 +162,73 @@ impl Object for Map {
         get_property,
         get_property_descriptor,
         set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        apply,
+        own_keys
+    );
+
+    extract!(self);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapIteratorKind {
+    Keys,
+    Values,
+    Entries,
+}
+
+#[derive(Debug, Trace)]
+pub struct MapIterator {
+    /// The map being iterated over, or `None` once the iterator is exhausted
+    map: Cell<Option<Value>>,
+    cursor: TableCursor,
+    kind: MapIteratorKind,
+    obj: OrdObject,
+}
+
+impl MapIterator {
+    pub fn new(vm: &Vm, map: Value, kind: MapIteratorKind) -> Self {
+        Self {
+            map: Cell::new(Some(map)),
+            cursor: TableCursor::default(),
+            kind,
+            obj: OrdObject::with_prototype(vm.statics.map_iterator_prototype),
+        }
+    }
+
+    pub fn kind(&self) -> MapIteratorKind {
+        self.kind
+    }
+
+    /// Returns the next key and value, or `None` if the iterator is done
+    pub fn next(&self, vm: &Vm) -> Option<(Value, Value)> {
+        let map = self.map.get()?;
+        let entry = map.extract::<Map>(vm).and_then(|map| map.next_entry(&self.cursor));
+        if entry.is_none() {
+            self.map.set(None);
+        }
+        entry
+    }
+}
+
+impl Object for MapIterator {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        define_property,
         delete_property,
         set_prototype,
         get_prototype,
+        prevent_extensions,
+        is_extensible,
         apply,
         own_keys
     );
@@ -1,21 +1,29 @@
 pub mod arguments;
 pub mod array;
 pub mod arraybuffer;
+pub mod async_iterator;
+pub mod bigint;
 pub mod boxed;
 pub mod conversions;
+pub mod dataview;
 pub mod date;
 pub mod error;
+pub mod finalizationregistry;
 pub mod function;
+#[cfg(feature = "intl")]
+pub mod intl;
 pub mod map;
 pub mod object;
 pub mod ops;
 pub mod primitive;
 pub mod promise;
 pub mod propertykey;
+pub mod proxy;
 pub mod regex;
 pub mod set;
 pub mod typedarray;
 pub mod weakmap;
+pub mod weakref;
 pub mod weakset;
 
 use std::any::TypeId;
@@ -26,6 +34,7 @@ use dash_middle::interner::{self, sym};
 use dash_middle::util::ThreadSafeStorage;
 use dash_proc_macro::Trace;
 use function::args::CallArgs;
+use num_traits::Zero;
 use propertykey::ToPropertyKey;
 
 pub mod string;
@@ -37,6 +46,7 @@ use crate::value::object::{OwnKeysMode, This};
 use crate::value::primitive::{Null, Undefined};
 use crate::{Vm, delegate, throw};
 
+use self::bigint::JsBigInt;
 use self::object::{Object, PropertyValue};
 use self::primitive::{InternalSlots, Number, Symbol};
 use self::string::JsString;
@@ -100,6 +110,7 @@ impl Value {
     0b1_11111111111_11 01	null
     0b0_11111111111_11 10	object
     0b1_11111111111_11 10	external
+    0b1_11111111111_11 11	bigint
                      ^ This bit is always set for non-doubles
      */
     const TAG_MASK: u64 = 0b1_11111111111_1111 << (64 - 16);
@@ -112,6 +123,7 @@ impl Value {
     pub(crate) const NULL_MASK: u64 = 0b0_11111111111_1110 << (64 - 16);
     pub(crate) const OBJECT_MASK: u64 = 0b1_11111111111_1110 << (64 - 16);
     pub(crate) const EXTERNAL_MASK: u64 = 0b0_11111111111_1111 << (64 - 16);
+    pub(crate) const BIGINT_MASK: u64 = 0b1_11111111111_1111 << (64 - 16);
 
     pub fn number(v: f64) -> Self {
         #[cold]
@@ -159,6 +171,10 @@ impl Value {
         Self(Self::EXTERNAL_MASK | id.raw() as u64)
     }
 
+    pub fn bigint(v: JsBigInt) -> Self {
+        Self(Self::BIGINT_MASK | v.id().raw() as u64)
+    }
+
     /// Constructs a value from its raw bits.
     #[inline]
     pub fn from_raw(raw: u64) -> Self {
@@ -188,6 +204,7 @@ impl Unpack for Value {
             Self::EXTERNAL_MASK => ValueKind::External(ExternalValue {
                 inner: ObjectId::from_raw(self.0 as u32),
             }),
+            Self::BIGINT_MASK => ValueKind::BigInt(JsBigInt::from_id(ObjectId::from_raw(self.0 as u32))),
             _ if (self.0 & Self::NON_NUMBER_MASK) != Self::NON_NUMBER_MASK => {
                 ValueKind::Number(Number(f64::from_bits(self.0)))
             }
@@ -221,6 +238,7 @@ unsafe impl Trace for Value {
             ValueKind::Undefined(_) => {}
             ValueKind::Null(_) => {}
             ValueKind::Symbol(s) => s.trace(cx),
+            ValueKind::BigInt(b) => b.trace(cx),
         }
     }
 }
@@ -238,6 +256,7 @@ impl Object for Value {
             ValueKind::Undefined(u) => u.get_property(this, key, sc),
             ValueKind::Null(n) => n.get_property(this, key, sc),
             ValueKind::Symbol(s) => s.get_property(this, key, sc),
+            ValueKind::BigInt(b) => b.get_property(this, key, sc),
         }
     }
 
@@ -251,6 +270,7 @@ impl Object for Value {
             ValueKind::Undefined(u) => u.get_own_property(this, key, sc),
             ValueKind::Null(n) => n.get_own_property(this, key, sc),
             ValueKind::Symbol(s) => s.get_own_property(this, key, sc),
+            ValueKind::BigInt(b) => b.get_own_property(this, key, sc),
         }
     }
 
@@ -266,6 +286,7 @@ impl Object for Value {
             ValueKind::Undefined(u) => u.get_property_descriptor(key, sc),
             ValueKind::Null(n) => n.get_property_descriptor(key, sc),
             ValueKind::Symbol(s) => s.get_property_descriptor(key, sc),
+            ValueKind::BigInt(b) => b.get_property_descriptor(key, sc),
             ValueKind::Object(o) => o.get_property_descriptor(key, sc),
             ValueKind::External(e) => e.get_property_descriptor(key, sc),
         }
@@ -283,12 +304,27 @@ impl Object for Value {
             ValueKind::Undefined(u) => u.get_own_property_descriptor(key, sc),
             ValueKind::Null(n) => n.get_own_property_descriptor(key, sc),
             ValueKind::Symbol(s) => s.get_own_property_descriptor(key, sc),
+            ValueKind::BigInt(b) => b.get_own_property_descriptor(key, sc),
             ValueKind::Object(o) => o.get_own_property_descriptor(key, sc),
             ValueKind::External(e) => e.get_own_property_descriptor(key, sc),
         }
     }
 
-    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<(), Value> {
+    fn has_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Unrooted> {
+        match self.unpack() {
+            ValueKind::Object(o) => o.has_property(key, sc),
+            ValueKind::Number(n) => n.has_property(key, sc),
+            ValueKind::Boolean(b) => b.has_property(key, sc),
+            ValueKind::String(s) => s.has_property(key, sc),
+            ValueKind::External(e) => e.has_property(key, sc),
+            ValueKind::Undefined(u) => u.has_property(key, sc),
+            ValueKind::Null(n) => n.has_property(key, sc),
+            ValueKind::Symbol(s) => s.has_property(key, sc),
+            ValueKind::BigInt(b) => b.has_property(key, sc),
+        }
+    }
+
+    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
         match self.unpack() {
             ValueKind::Object(h) => h.set_property(key, value, sc),
             ValueKind::Number(n) => n.set_property(key, value, sc),
@@ -298,10 +334,19 @@ impl Object for Value {
             ValueKind::Undefined(u) => u.set_property(key, value, sc),
             ValueKind::Null(n) => n.set_property(key, value, sc),
             ValueKind::Symbol(s) => s.set_property(key, value, sc),
+            ValueKind::BigInt(b) => b.set_property(key, value, sc),
+        }
+    }
+
+    fn define_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
+        match self.unpack() {
+            ValueKind::Object(o) => o.define_property(key, value, sc),
+            ValueKind::External(o) => o.define_property(key, value, sc),
+            _ => self.set_property(key, value, sc),
         }
     }
 
-    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<Unrooted, Value> {
+    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Value> {
         match self.unpack() {
             ValueKind::Object(o) => o.delete_property(key, sc),
             ValueKind::Number(n) => n.delete_property(key, sc),
@@ -311,6 +356,7 @@ impl Object for Value {
             ValueKind::Undefined(u) => u.delete_property(key, sc),
             ValueKind::Null(n) => n.delete_property(key, sc),
             ValueKind::Symbol(s) => s.delete_property(key, sc),
+            ValueKind::BigInt(b) => b.delete_property(key, sc),
         }
     }
 
@@ -322,6 +368,7 @@ impl Object for Value {
             ValueKind::Undefined(u) => u.set_prototype(value, sc),
             ValueKind::Null(n) => n.set_prototype(value, sc),
             ValueKind::Symbol(s) => s.set_prototype(value, sc),
+            ValueKind::BigInt(b) => b.set_prototype(value, sc),
             ValueKind::Object(o) => o.set_prototype(value, sc),
             ValueKind::External(e) => e.set_prototype(value, sc),
         }
@@ -335,11 +382,29 @@ impl Object for Value {
             ValueKind::Undefined(u) => u.get_prototype(sc),
             ValueKind::Null(n) => n.get_prototype(sc),
             ValueKind::Symbol(s) => s.get_prototype(sc),
+            ValueKind::BigInt(b) => b.get_prototype(sc),
             ValueKind::Object(o) => o.get_prototype(sc),
             ValueKind::External(e) => e.get_prototype(sc),
         }
     }
 
+    fn prevent_extensions(&self, sc: &mut LocalScope) -> Result<(), Value> {
+        match self.unpack() {
+            ValueKind::Object(o) => o.prevent_extensions(sc),
+            ValueKind::External(e) => e.prevent_extensions(sc),
+            // Primitives can never be extended
+            _ => Ok(()),
+        }
+    }
+
+    fn is_extensible(&self, sc: &mut LocalScope) -> Result<bool, Value> {
+        match self.unpack() {
+            ValueKind::Object(o) => o.is_extensible(sc),
+            ValueKind::External(e) => e.is_extensible(sc),
+            _ => Ok(false),
+        }
+    }
+
     fn apply(&self, _: ObjectId, this: This, args: CallArgs, scope: &mut LocalScope) -> Result<Unrooted, Unrooted> {
         self.apply(this, args, scope)
     }
@@ -352,6 +417,7 @@ impl Object for Value {
             ValueKind::Undefined(u) => u.own_keys(sc, mode),
             ValueKind::Null(n) => n.own_keys(sc, mode),
             ValueKind::Symbol(s) => s.own_keys(sc, mode),
+            ValueKind::BigInt(b) => b.own_keys(sc, mode),
             ValueKind::Object(o) => o.own_keys(sc, mode),
             ValueKind::External(e) => e.own_keys(sc, mode),
         }
@@ -367,6 +433,7 @@ impl Object for Value {
             ValueKind::Object(o) => o.type_of(vm),
             ValueKind::Null(_) => Typeof::Object,
             ValueKind::Symbol(_) => Typeof::Symbol,
+            ValueKind::BigInt(_) => Typeof::Bigint,
         }
     }
 
@@ -396,6 +463,7 @@ impl Object for Value {
                 ValueKind::Undefined(undefined) => undefined.extract_type_raw(vm, type_id),
                 ValueKind::Null(null) => null.extract_type_raw(vm, type_id),
                 ValueKind::Symbol(symbol) => symbol.extract_type_raw(vm, type_id),
+                ValueKind::BigInt(bigint) => bigint.extract_type_raw(vm, type_id),
                 ValueKind::Object(alloc_id) => alloc_id.extract_type_raw(vm, type_id),
                 ValueKind::External(external_value) => external_value.extract_type_raw(vm, type_id),
             }
@@ -431,6 +499,15 @@ impl InternalSlots for Value {
             _ => None,
         }
     }
+
+    fn bigint_value(&self, vm: &Vm) -> Option<JsBigInt> {
+        match self.unpack() {
+            ValueKind::BigInt(b) => Some(b),
+            ValueKind::Object(obj) => obj.internal_slots(vm).and_then(|slots| slots.bigint_value(vm)),
+            ValueKind::External(ext) => ext.internal_slots(vm).and_then(|slots| slots.bigint_value(vm)),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -447,6 +524,8 @@ pub enum ValueKind {
     Null(Null),
     /// The symbol type
     Symbol(Symbol),
+    /// The BigInt type
+    BigInt(JsBigInt),
     /// The object type
     Object(ObjectId),
     /// An "external" value that is being used by other functions.
@@ -667,13 +746,17 @@ impl Object for ExternalValue {
     delegate!(
         inner,
         set_property,
+        define_property,
         delete_property,
         set_prototype,
         own_keys,
         get_own_property_descriptor,
         get_property,
         get_property_descriptor,
+        has_property,
         get_prototype,
+        prevent_extensions,
+        is_extensible,
         type_of,
         internal_slots
     );
@@ -720,6 +803,7 @@ impl Value {
             ValueKind::Undefined(u) => u.get_property(This::bound(*self), key, sc),
             ValueKind::Null(n) => n.get_property(This::bound(*self), key, sc),
             ValueKind::Symbol(s) => s.get_property(This::bound(*self), key, sc),
+            ValueKind::BigInt(b) => b.get_property(This::bound(*self), key, sc),
         }
     }
 
@@ -736,6 +820,10 @@ impl Value {
             ValueKind::Undefined(_) => throw!(sc, TypeError, "undefined is not a function"),
             ValueKind::Null(_) => throw!(sc, TypeError, "null is not a function"),
             ValueKind::Symbol(s) => throw!(sc, TypeError, "{:?} is not a function", s),
+            ValueKind::BigInt(b) => {
+                let b = b.get(sc).to_string();
+                throw!(sc, TypeError, "{}n is not a function", b)
+            }
         }
     }
 
@@ -773,6 +861,10 @@ impl Value {
             ValueKind::Undefined(_) => throw!(sc, TypeError, "undefined is not a constructor"),
             ValueKind::Null(_) => throw!(sc, TypeError, "null is not a constructor"),
             ValueKind::Symbol(s) => throw!(sc, TypeError, "{:?} is not a constructor", s),
+            ValueKind::BigInt(b) => {
+                let b = b.get(sc).to_string();
+                throw!(sc, TypeError, "{}n is not a constructor", b)
+            }
         }
     }
 
@@ -795,6 +887,10 @@ impl Value {
             ValueKind::Undefined(_) => throw!(sc, TypeError, "undefined is not a constructor"),
             ValueKind::Null(_) => throw!(sc, TypeError, "null is not a constructor"),
             ValueKind::Symbol(s) => throw!(sc, TypeError, "{:?} is not a constructor", s),
+            ValueKind::BigInt(b) => {
+                let b = b.get(sc).to_string();
+                throw!(sc, TypeError, "{}n is not a constructor", b)
+            }
         }
     }
 
@@ -804,6 +900,7 @@ impl Value {
             ValueKind::String(s) => !s.res(sc).is_empty(),
             ValueKind::Number(Number(n)) => n != 0.0 && !n.is_nan(),
             ValueKind::Symbol(_) => true,
+            ValueKind::BigInt(b) => !b.get(sc).is_zero(),
             ValueKind::Object(_) => true,
             ValueKind::Undefined(_) => false,
             ValueKind::Null(_) => false,
@@ -820,6 +917,16 @@ impl Value {
         }
     }
 
+    /// Interns the value if it is a string that might not be interned, or a BigInt,
+    /// so that it can be compared and hashed by its bits, e.g. as the key of a `Map`
+    pub fn interned(self, sc: &mut LocalScope<'_>) -> Value {
+        match self.unpack() {
+            ValueKind::String(string) => Value::string(string.interned(sc)),
+            ValueKind::BigInt(bigint) => Value::bigint(sc.canonical_bigint(bigint)),
+            _ => self,
+        }
+    }
+
     pub fn unbox_external(self, vm: &Vm) -> Value {
         match self.unpack() {
             ValueKind::External(e) => e.inner(vm),
@@ -993,17 +1100,23 @@ impl<O: Object + 'static> Object for PureBuiltin<O> {
         get_property,
         get_property_descriptor,
         get_prototype,
+        is_extensible,
         apply,
         construct,
         type_of
     );
 
-    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<(), Value> {
+    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
         sc.impure_builtins();
         self.inner.set_property(key, value, sc)
     }
 
-    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<Unrooted, Value> {
+    fn define_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
+        sc.impure_builtins();
+        self.inner.define_property(key, value, sc)
+    }
+
+    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Value> {
         sc.impure_builtins();
         self.inner.delete_property(key, sc)
     }
@@ -1013,6 +1126,11 @@ impl<O: Object + 'static> Object for PureBuiltin<O> {
         self.inner.set_prototype(value, sc)
     }
 
+    fn prevent_extensions(&self, sc: &mut LocalScope) -> Result<(), Value> {
+        sc.impure_builtins();
+        self.inner.prevent_extensions(sc)
+    }
+
     fn own_keys(&self, sc: &mut LocalScope<'_>, mode: OwnKeysMode) -> Result<Vec<Value>, Value> {
         self.inner.own_keys(sc, mode)
     }
@@ -12,10 +12,11 @@ use dash_middle::interner::sym;
 use dash_proc_macro::Trace;
 use rustc_hash::FxHasher;
 
-use crate::Vm;
 use crate::localscope::LocalScope;
+use crate::{Vm, throw};
 
 use super::function::args::CallArgs;
+use super::ops::conversions::ValueConversion;
 use super::primitive::InternalSlots;
 use super::propertykey::{PropertyKey, ToPropertyKey};
 use super::root_ext::RootErrExt;
@@ -74,14 +75,44 @@ pub trait Object: Debug + Trace {
         }
     }
 
-    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<(), Value>;
+    /// Checks whether this object or any object in its prototype chain has a property with the given key.
+    /// This is what the `in` operator evaluates.
+    fn has_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Unrooted> {
+        self.get_property_descriptor(key, sc).map(|desc| desc.is_some())
+    }
+
+    /// Sets a property, or defines it if it does not exist yet.
+    ///
+    /// Returns `false` if the property is not writable or the object is not extensible.
+    /// Whether that is an error is up to the caller: assignments only throw in strict mode code.
+    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value>;
+
+    /// Defines an own property, as done by `Object.defineProperty`.
+    ///
+    /// Unlike `set_property`, this does not invoke setters and can change the attributes of a configurable property.
+    /// Returns `false` if the property cannot be defined.
+    fn define_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
+        self.set_property(key, value, sc)
+    }
 
-    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<Unrooted, Value>;
+    /// Deletes a property. Returns `false` if the property exists and is not configurable.
+    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Value>;
 
     fn set_prototype(&self, value: Value, sc: &mut LocalScope) -> Result<(), Value>;
 
     fn get_prototype(&self, sc: &mut LocalScope) -> Result<Value, Value>;
 
+    /// Implements the `[[PreventExtensions]]` internal method. After this returns successfully,
+    /// no new properties can be added to the object and its prototype can no longer be changed.
+    fn prevent_extensions(&self, sc: &mut LocalScope) -> Result<(), Value> {
+        throw!(sc, TypeError, "Cannot prevent extensions of this object")
+    }
+
+    /// Implements the `[[IsExtensible]]` internal method.
+    fn is_extensible(&self, _: &mut LocalScope) -> Result<bool, Value> {
+        Ok(true)
+    }
+
     fn apply(&self, callee: ObjectId, this: This, args: CallArgs, scope: &mut LocalScope)
     -> Result<Unrooted, Unrooted>;
 
@@ -142,22 +173,41 @@ macro_rules! delegate {
             self.$field.get_property_descriptor(key, sc)
         }
     };
+    (override $field:ident, has_property) => {
+        fn has_property(
+            &self,
+            key: $crate::value::propertykey::PropertyKey,
+            sc: &mut $crate::localscope::LocalScope,
+        ) -> Result<bool, $crate::value::Unrooted> {
+            self.$field.has_property(key, sc)
+        }
+    };
     (override $field:ident, set_property) => {
         fn set_property(
             &self,
             key: $crate::value::propertykey::PropertyKey,
             value: $crate::value::object::PropertyValue,
             sc: &mut $crate::localscope::LocalScope,
-        ) -> Result<(), $crate::value::Value> {
+        ) -> Result<bool, $crate::value::Value> {
             self.$field.set_property(key, value, sc)
         }
     };
+    (override $field:ident, define_property) => {
+        fn define_property(
+            &self,
+            key: $crate::value::propertykey::PropertyKey,
+            value: $crate::value::object::PropertyValue,
+            sc: &mut $crate::localscope::LocalScope,
+        ) -> Result<bool, $crate::value::Value> {
+            self.$field.define_property(key, value, sc)
+        }
+    };
     (override $field:ident, delete_property) => {
         fn delete_property(
             &self,
             key: $crate::value::propertykey::PropertyKey,
             sc: &mut $crate::localscope::LocalScope,
-        ) -> Result<$crate::value::Unrooted, $crate::value::Value> {
+        ) -> Result<bool, $crate::value::Value> {
             self.$field.delete_property(key, sc)
         }
     };
@@ -171,6 +221,16 @@ macro_rules! delegate {
             self.$field.get_prototype(sc)
         }
     };
+    (override $field:ident, prevent_extensions) => {
+        fn prevent_extensions(&self, sc: &mut $crate::localscope::LocalScope) -> Result<(), $crate::value::Value> {
+            self.$field.prevent_extensions(sc)
+        }
+    };
+    (override $field:ident, is_extensible) => {
+        fn is_extensible(&self, sc: &mut $crate::localscope::LocalScope) -> Result<bool, $crate::value::Value> {
+            self.$field.is_extensible(sc)
+        }
+    };
     (override $field:ident, own_keys) => {
         fn own_keys(&self, sc: &mut $crate::localscope::LocalScope<'_>, mode: $crate::value::object::OwnKeysMode) -> Result<Vec<$crate::value::Value>, $crate::value::Value> {
             self.$field.own_keys(sc, mode)
@@ -531,11 +591,19 @@ impl Object for ObjectId {
         unsafe { (self.vtable(sc).js_get_property_descriptor)(self.data_ptr(sc), key, sc) }
     }
 
-    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<(), Value> {
+    fn has_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Unrooted> {
+        unsafe { (self.vtable(sc).js_has_property)(self.data_ptr(sc), key, sc) }
+    }
+
+    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
         unsafe { (self.vtable(sc).js_set_property)(self.data_ptr(sc), key, value, sc) }
     }
 
-    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<Unrooted, Value> {
+    fn define_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
+        unsafe { (self.vtable(sc).js_define_property)(self.data_ptr(sc), key, value, sc) }
+    }
+
+    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Value> {
         unsafe { (self.vtable(sc).js_delete_property)(self.data_ptr(sc), key, sc) }
     }
 
@@ -547,6 +615,14 @@ impl Object for ObjectId {
         unsafe { (self.vtable(sc).js_get_prototype)(self.data_ptr(sc), sc) }
     }
 
+    fn prevent_extensions(&self, sc: &mut LocalScope) -> Result<(), Value> {
+        unsafe { (self.vtable(sc).js_prevent_extensions)(self.data_ptr(sc), sc) }
+    }
+
+    fn is_extensible(&self, sc: &mut LocalScope) -> Result<bool, Value> {
+        unsafe { (self.vtable(sc).js_is_extensible)(self.data_ptr(sc), sc) }
+    }
+
     fn apply(
         &self,
         callee: ObjectId,
@@ -624,7 +700,8 @@ impl ObjectId {
     }
 
     pub fn set_integrity_level(self, level: IntegrityLevel, sc: &mut LocalScope<'_>) -> Result<(), Value> {
-        // TODO: invoke [[PreventExtensions]]
+        self.prevent_extensions(sc)?;
+
         let keys = self.own_keys(sc, OwnKeysMode::All)?;
         for key in keys {
             let key = PropertyKey::from_value(sc, key)?;
@@ -636,11 +713,38 @@ impl ObjectId {
                 {
                     desc.descriptor.remove(PropertyDataDescriptor::WRITABLE);
                 }
-                self.set_property(key, desc, sc)?;
+                if !self.define_property(key, desc, sc)? {
+                    let key = key.to_value(sc).to_js_string(sc)?.res(sc).to_owned();
+                    throw!(sc, TypeError, "Cannot redefine property: {}", key)
+                }
             }
         }
         Ok(())
     }
+
+    pub fn test_integrity_level(self, level: IntegrityLevel, sc: &mut LocalScope<'_>) -> Result<bool, Value> {
+        if self.is_extensible(sc)? {
+            return Ok(false);
+        }
+
+        let keys = self.own_keys(sc, OwnKeysMode::All)?;
+        for key in keys {
+            let key = PropertyKey::from_value(sc, key)?;
+
+            if let Some(desc) = self.get_own_property_descriptor(key, sc).root_err(sc)? {
+                if desc.descriptor.contains(PropertyDataDescriptor::CONFIGURABLE) {
+                    return Ok(false);
+                }
+                if let IntegrityLevel::Frozen = level
+                    && let PropertyValueKind::Static(_) = desc.kind
+                    && desc.descriptor.contains(PropertyDataDescriptor::WRITABLE)
+                {
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
 }
 
 pub enum IntegrityLevel {
@@ -15,9 +15,13 @@ use crate::localscope::LocalScope;
 use crate::util::cold_path;
 use crate::value::function::args::CallArgs;
 use crate::value::object::{OwnKeysMode, This};
+use crate::value::ops::equality::same_value;
 use crate::value::primitive::Symbol;
 use crate::value::propertykey::{PropertyKey, PropertyKeyInner, ToPropertyKey};
+use crate::value::proxy::Proxy;
+use crate::value::root_ext::RootErrExt;
 use crate::value::string::JsString;
+use crate::value::typedarray::TypedArray;
 use crate::value::{Root, Unpack, Unrooted, Value, ValueKind};
 use crate::{Vm, extract, throw};
 
@@ -59,6 +63,7 @@ unsafe impl Trace for OrdObject {
                     cap: _,
                     string_key_count: _,
                     symbol_key_count: _,
+                    extensible: _,
                     prototype,
                     data: _,
                 } = unsafe { &*property_vec.0.as_ptr() };
@@ -151,23 +156,9 @@ fn get_own_property_descriptor_inline(
     object: &InnerOrdObject,
     key: PropertyKey,
 ) -> Result<Option<PropertyValue>, Unrooted> {
-    // TODO: stop handling __proto__ here and do that in the handler instead?
-
     match *object {
-        InnerOrdObject::Cow { prototype } => {
-            if key == PropertyKey::PROTO {
-                Ok(Some(prototype))
-            } else {
-                Ok(None)
-            }
-        }
-        InnerOrdObject::Linear(ref property_vec) => {
-            if key == PropertyKey::PROTO {
-                Ok(Some(property_vec.get_prototype()))
-            } else {
-                Ok(property_vec.get_property(key))
-            }
-        }
+        InnerOrdObject::Cow { .. } => Ok(None),
+        InnerOrdObject::Linear(ref property_vec) => Ok(property_vec.get_property(key)),
     }
 }
 
@@ -212,81 +203,75 @@ impl Object for OrdObject {
         get_own_property_descriptor_inline(&cell, key)
     }
 
-    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope<'_>) -> Result<(), Value> {
-        // TODO: stop handling __proto__ here and do that in the handler instead? the way we do that here is kinda wrong.
+    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope<'_>) -> Result<bool, Value> {
         // SAFETY: no reentrancy possible from here
         let mut guard = unsafe { self.0.borrow_mut() };
 
         match &mut *guard {
             InnerOrdObject::Cow { prototype } => {
-                if key == PropertyKey::PROTO {
-                    *prototype = value;
-                    Ok(())
-                } else {
-                    let prototype = *prototype;
-                    *guard = InnerOrdObject::Linear(PropertyVec::new(prototype));
-                    drop(guard);
-                    self.set_property(key, value, sc)
-                }
+                let prototype = *prototype;
+                *guard = InnerOrdObject::Linear(PropertyVec::new(prototype));
+                drop(guard);
+                self.set_property(key, value, sc)
             }
-            InnerOrdObject::Linear(property_vec) => {
-                if key == PropertyKey::PROTO {
-                    *property_vec.get_prototype_mut() = value;
-                    Ok(())
-                } else {
-                    match property_vec.set_property(key, value) {
-                        SetPropertyResult::Ok => Ok(()),
-                        // TODO: throw in strict mode?
-                        SetPropertyResult::NotWritable => {
-                            cold_path();
-                            Ok(())
-                        }
-                        SetPropertyResult::InvokeSetter(alloc_id) => {
-                            drop(guard);
-
-                            if let PropertyValueKind::Static(value) = value.kind() {
-                                match alloc_id.apply(This::default(), [*value].into(), sc) {
-                                    Ok(_) => Ok(()),
-                                    Err(err) => Err(err.root(sc)),
-                                }
-                            } else {
-                                Ok(())
-                            }
+            InnerOrdObject::Linear(property_vec) => match property_vec.set_property(key, value) {
+                SetPropertyResult::Ok => Ok(true),
+                SetPropertyResult::NotWritable | SetPropertyResult::NotExtensible => {
+                    cold_path();
+                    Ok(false)
+                }
+                SetPropertyResult::InvokeSetter(alloc_id) => {
+                    drop(guard);
+
+                    // The object does not know its own id here, so there is no receiver to pass.
+                    // Assignments go through `set_with_receiver`, which calls setters itself and never gets here.
+
+                    if let PropertyValueKind::Static(value) = value.kind() {
+                        match alloc_id.apply(This::default(), [*value].into(), sc) {
+                            Ok(_) => Ok(true),
+                            Err(err) => Err(err.root(sc)),
                         }
+                    } else {
+                        Ok(true)
                     }
                 }
+            },
+        }
+    }
+
+    fn define_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope<'_>) -> Result<bool, Value> {
+        if let Some(current) = self.get_own_property_descriptor(key, sc).root_err(sc)?
+            && !current.descriptor.contains(PropertyDataDescriptor::CONFIGURABLE)
+            && !can_redefine_non_configurable(current, &value, sc)
+        {
+            return Ok(false);
+        }
+
+        // SAFETY: no reentrancy possible from here
+        let mut guard = unsafe { self.0.borrow_mut() };
+
+        match &mut *guard {
+            InnerOrdObject::Cow { prototype } => {
+                let prototype = *prototype;
+                *guard = InnerOrdObject::Linear(PropertyVec::new(prototype));
+                drop(guard);
+                self.define_property(key, value, sc)
             }
+            InnerOrdObject::Linear(property_vec) => Ok(property_vec.define_property(key, value)),
         }
     }
 
-    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope<'_>) -> Result<Unrooted, Value> {
+    fn delete_property(&self, key: PropertyKey, _: &mut LocalScope<'_>) -> Result<bool, Value> {
         // SAFETY: no reentrancy possible from here
         let cell = unsafe { &mut *self.0.borrow_mut() };
 
         match cell {
-            InnerOrdObject::Cow { prototype: _ } => Ok(Value::undefined().into()),
-            InnerOrdObject::Linear(property_vec) => match property_vec.delete_property(key) {
-                Some(pv) => match pv.kind() {
-                    PropertyValueKind::Static(v) => {
-                        sc.add(*v);
-                        Ok((*v).into())
-                    }
-                    PropertyValueKind::Trap { get, set } => {
-                        if let Some(get) = get {
-                            sc.add(*get);
-                        }
-                        if let Some(set) = set {
-                            sc.add(*set);
-                        }
-                        Ok(Value::undefined().into())
-                    }
-                },
-                None => Ok(Value::undefined().into()),
-            },
+            InnerOrdObject::Cow { prototype: _ } => Ok(true),
+            InnerOrdObject::Linear(property_vec) => Ok(property_vec.delete_property(key)),
         }
     }
 
-    fn set_prototype(&self, value: Value, _: &mut LocalScope<'_>) -> Result<(), Value> {
+    fn set_prototype(&self, value: Value, sc: &mut LocalScope<'_>) -> Result<(), Value> {
         // SAFETY: no reentrancy possible from here
         let cell = unsafe { &mut *self.0.borrow_mut() };
 
@@ -296,12 +281,41 @@ impl Object for OrdObject {
                 Ok(())
             }
             InnerOrdObject::Linear(property_vec) => {
+                if !property_vec.is_extensible() && property_vec.get_prototype().kind().as_static() != Some(&value) {
+                    throw!(sc, TypeError, "Cannot set prototype of a non-extensible object")
+                }
                 *property_vec.get_prototype_mut() = PropertyValue::static_default(value);
                 Ok(())
             }
         }
     }
 
+    fn prevent_extensions(&self, _: &mut LocalScope<'_>) -> Result<(), Value> {
+        // SAFETY: no reentrancy possible from here
+        let mut guard = unsafe { self.0.borrow_mut() };
+
+        // The extensibility bit lives in the property vector allocation, so a copy-on-write object
+        // needs to be promoted first
+        if let InnerOrdObject::Cow { prototype } = *guard {
+            *guard = InnerOrdObject::Linear(PropertyVec::new(prototype));
+        }
+
+        if let InnerOrdObject::Linear(property_vec) = &mut *guard {
+            property_vec.prevent_extensions();
+        }
+        Ok(())
+    }
+
+    fn is_extensible(&self, _: &mut LocalScope<'_>) -> Result<bool, Value> {
+        // SAFETY: no reentrancy possible from here
+        let cell = unsafe { self.0.borrow() };
+
+        match *cell {
+            InnerOrdObject::Cow { .. } => Ok(true),
+            InnerOrdObject::Linear(ref property_vec) => Ok(property_vec.is_extensible()),
+        }
+    }
+
     fn get_prototype(&self, sc: &mut LocalScope<'_>) -> Result<Value, Value> {
         // SAFETY: no reentrancy possible from here
         let cell = unsafe { self.0.borrow() };
@@ -465,12 +479,175 @@ unsafe fn align_ptr_mut<T>(ptr: *mut T) -> *mut T {
     unsafe { ptr.byte_add(extra_align) }
 }
 
+/// Throws the TypeError for a rejected assignment to a property of `target`.
+///
+/// Rejected assignments are only an error in strict mode code and in built-in functions that require them to succeed,
+/// so this is up to the caller of `set_property`.
+pub fn reject_set_property(target: Value, key: PropertyKey, sc: &mut LocalScope<'_>) -> Result<(), Value> {
+    let name = JsString::from(key.any_js_string(sc)).res(sc).to_owned();
+    let ValueKind::Object(object) = target.unpack() else {
+        throw!(sc, TypeError, "Cannot create property '{}' on primitive value", name)
+    };
+
+    if object.extract::<Proxy>(sc).is_some() {
+        throw!(
+            sc,
+            TypeError,
+            "'set' on proxy: trap returned falsish for property '{}'",
+            name
+        )
+    }
+
+    // The property that rejected the assignment may also be inherited
+    match object.get_property_descriptor(key, sc).root_err(sc)? {
+        Some(PropertyValue {
+            kind: PropertyValueKind::Trap { .. },
+            ..
+        }) => throw!(sc, TypeError, "Cannot set property '{}' which has only a getter", name),
+        Some(desc) if !desc.descriptor.contains(PropertyDataDescriptor::WRITABLE) => {
+            throw!(sc, TypeError, "Cannot assign to read only property '{}'", name)
+        }
+        _ => throw!(sc, TypeError, "Cannot add property {}, object is not extensible", name),
+    }
+}
+
+/// Checks whether a non-configurable property can be redefined with the given value and attributes.
+/// Its attributes cannot change, except for a data property becoming non-writable,
+/// and the value of a non-writable property has to stay the same.
+fn can_redefine_non_configurable(current: PropertyValue, new: &PropertyValue, vm: &Vm) -> bool {
+    if new.descriptor.contains(PropertyDataDescriptor::CONFIGURABLE)
+        || new.descriptor.contains(PropertyDataDescriptor::ENUMERABLE)
+            != current.descriptor.contains(PropertyDataDescriptor::ENUMERABLE)
+    {
+        return false;
+    }
+
+    match (current.kind, new.kind) {
+        (PropertyValueKind::Static(current_value), PropertyValueKind::Static(new_value)) => {
+            current.descriptor.contains(PropertyDataDescriptor::WRITABLE)
+                || (!new.descriptor.contains(PropertyDataDescriptor::WRITABLE)
+                    && same_value(current_value, new_value, vm))
+        }
+        (
+            PropertyValueKind::Trap { get, set },
+            PropertyValueKind::Trap {
+                get: new_get,
+                set: new_set,
+            },
+        ) => get == new_get && set == new_set,
+        _ => false,
+    }
+}
+
+/// Implements the `[[Set]]` internal method with an explicit receiver,
+/// which is the `this` value of setters and the object that a data property is defined on.
+///
+/// This walks the prototype chain starting at `target` until it finds the property (or a proxy, which takes over).
+/// Returns `false` if the assignment is rejected.
+pub fn set_with_receiver(
+    target: Value,
+    key: PropertyKey,
+    value: Value,
+    receiver: Value,
+    sc: &mut LocalScope<'_>,
+) -> Result<bool, Value> {
+    let mut current = target;
+
+    let desc = loop {
+        if let ValueKind::Object(object) = current.unpack() {
+            if let Some(proxy) = object.extract::<Proxy>(sc) {
+                return proxy.set(key, value, receiver, sc);
+            }
+
+            // Integer-indexed elements of typed arrays never look at the prototype chain
+            if key.index_usize().is_some()
+                && let Some(typed_array) = object.extract::<TypedArray>(sc)
+            {
+                if current == receiver {
+                    return current.set_property(key, PropertyValue::static_default(value), sc);
+                }
+                if typed_array.get_own_property_descriptor(key, sc).root_err(sc)?.is_none() {
+                    return Ok(true);
+                }
+            }
+        }
+
+        if let Some(desc) = current.get_own_property_descriptor(key, sc).root_err(sc)? {
+            break Some(desc);
+        }
+
+        let proto = current.get_prototype(sc)?;
+        if !matches!(proto.unpack(), ValueKind::Object(_) | ValueKind::External(_)) {
+            break None;
+        }
+        current = proto;
+    };
+
+    match desc {
+        Some(PropertyValue {
+            kind: PropertyValueKind::Trap { set, .. },
+            ..
+        }) => {
+            let Some(set) = set else { return Ok(false) };
+            set.apply(This::bound(receiver), [value].into(), sc).root_err(sc)?;
+            Ok(true)
+        }
+        Some(desc) if !desc.descriptor.contains(PropertyDataDescriptor::WRITABLE) => Ok(false),
+        // Fast path: an own writable data property is updated in place, keeping its attributes
+        Some(desc) if current == receiver => receiver.set_property(
+            key,
+            PropertyValue::new(PropertyValueKind::Static(value), desc.descriptor),
+            sc,
+        ),
+        _ => {
+            if !matches!(receiver.unpack(), ValueKind::Object(_) | ValueKind::External(_)) {
+                return Ok(false);
+            }
+
+            match receiver.get_own_property_descriptor(key, sc).root_err(sc)? {
+                Some(PropertyValue {
+                    kind: PropertyValueKind::Static(_),
+                    descriptor,
+                }) if descriptor.contains(PropertyDataDescriptor::WRITABLE) => {
+                    let value = PropertyValue::new(PropertyValueKind::Static(value), descriptor);
+                    receiver.define_property(key, value, sc)
+                }
+                Some(_) => Ok(false),
+                None => receiver.define_property(key, PropertyValue::static_default(value), sc),
+            }
+        }
+    }
+}
+
+/// Sets a property and throws a TypeError if the assignment is rejected
+pub fn set_property_or_throw(
+    target: Value,
+    key: PropertyKey,
+    value: Value,
+    sc: &mut LocalScope<'_>,
+) -> Result<(), Value> {
+    if !set_with_receiver(target, key, value, target, sc)? {
+        reject_set_property(target, key, sc)?;
+    }
+    Ok(())
+}
+
+/// Deletes a property and throws a TypeError if the deletion is rejected
+pub fn delete_property_or_throw(target: Value, key: PropertyKey, sc: &mut LocalScope<'_>) -> Result<(), Value> {
+    if !target.delete_property(key, sc)? {
+        let name = JsString::from(key.any_js_string(sc)).res(sc).to_owned();
+        throw!(sc, TypeError, "Cannot delete property '{}'", name)
+    }
+    Ok(())
+}
+
 #[derive(Debug)]
 struct PropertyVec(NonNull<PropertyVecAllocation>);
 
 enum SetPropertyResult {
     Ok,
     NotWritable,
+    NotExtensible,
     InvokeSetter(ObjectId),
 }
 
@@ -522,6 +699,7 @@ impl PropertyVec {
                 prototype,
                 string_key_count: 0,
                 symbol_key_count: 0,
+                extensible: true,
             });
         }
 
@@ -561,6 +739,7 @@ impl PropertyVec {
                     prototype: (*old_ptr).prototype,
                     string_key_count: (*old_ptr).string_key_count,
                     symbol_key_count: (*old_ptr).symbol_key_count,
+                    extensible: (*old_ptr).extensible,
                 });
 
                 self.0 = new_ptr;
@@ -577,11 +756,25 @@ impl PropertyVec {
         }
     }
 
+    /// Defines a property without invoking setters, replacing the value and attributes of an existing property.
+    pub fn define_property(&mut self, key: PropertyKey, value: PropertyValue) -> bool {
+        match self.find_key_index(key) {
+            Some(idx) => {
+                let (values, descriptors) = self.values_descriptors_mut();
+                let (value, descriptor) = property_value_to_internal(value);
+                values[idx as usize] = value;
+                descriptors[idx as usize] = descriptor;
+                true
+            }
+            None => matches!(self.set_property(key, value), SetPropertyResult::Ok),
+        }
+    }
+
     pub fn set_property(&mut self, key: PropertyKey, value: PropertyValue) -> SetPropertyResult {
         if let Some(idx) = self.find_key_index(key) {
             let (values, descriptors) = self.values_descriptors_mut();
 
-            let (val, descr) = property_value_to_internal(value);
+            let (val, mut descr) = property_value_to_internal(value);
 
             let value = &mut values[idx as usize];
             let descriptor = &mut descriptors[idx as usize];
@@ -590,6 +783,11 @@ impl PropertyVec {
                 return SetPropertyResult::NotWritable;
             }
 
+            if !descriptor.contains(InternalLinearPropertyVecDescriptor::CONFIGURABLE) {
+                // The attributes of a non-configurable property cannot change, except for it becoming non-writable
+                descr = *descriptor & (descr | !InternalLinearPropertyVecDescriptor::WRITABLE);
+            }
+
             if descriptor.contains(InternalLinearPropertyVecDescriptor::GET_SET) {
                 cold_path();
                 if descr.contains(InternalLinearPropertyVecDescriptor::GET) {
@@ -615,7 +813,10 @@ impl PropertyVec {
 
             SetPropertyResult::Ok
         } else {
-            // Property does not exist. Define it.
+            // Property does not exist. Define it, unless extensions were prevented.
+            if !self.is_extensible() {
+                return SetPropertyResult::NotExtensible;
+            }
 
             self.ensure_additional_properties(const { NonZero::new(1).unwrap() });
 
@@ -685,12 +886,16 @@ impl PropertyVec {
         }
     }
 
-    pub fn delete_property(&mut self, key: PropertyKey) -> Option<PropertyValue> {
-        let index = self.find_key_index(key)? as usize;
+    /// Deletes a property. Returns `false` if the property exists and is not configurable.
+    pub fn delete_property(&mut self, key: PropertyKey) -> bool {
+        let Some(index) = self.find_key_index(key) else {
+            return true;
+        };
+        let index = index as usize;
         let value = self.property_value_at_index(index);
 
         if !value.descriptor.contains(PropertyDataDescriptor::CONFIGURABLE) {
-            return None;
+            return false;
         }
 
         fn delete_from_section<T>(ptr: *mut MaybeUninit<T>, index: usize, section_len: usize) {
@@ -726,7 +931,7 @@ impl PropertyVec {
             }
         }
 
-        Some(value)
+        true
     }
 
     pub fn property_value_at_index(&self, index: usize) -> PropertyValue {
@@ -792,6 +997,14 @@ impl PropertyVec {
         unsafe { &mut (*self.0.as_ptr()).prototype }
     }
 
+    pub fn is_extensible(&self) -> bool {
+        unsafe { (*self.0.as_ptr()).extensible }
+    }
+
+    pub fn prevent_extensions(&mut self) {
+        unsafe { (*self.0.as_ptr()).extensible = false };
+    }
+
     fn find_key_index(&self, key: PropertyKey) -> Option<u32> {
         let (keys, off, search) = match key.inner() {
             PropertyKeyInner::String(js_string) => (self.string_keys(), 0, js_string.sym().raw()),
@@ -1170,6 +1383,8 @@ struct PropertyVecAllocation {
     cap: NonZero<u32>,
     string_key_count: u32,
     symbol_key_count: u32,
+    /// Whether new properties can be added to this object. Cleared by `[[PreventExtensions]]` and never set again.
+    extensible: bool,
     // TODO: it really just needs to be a Value
     prototype: PropertyValue,
     data: [Aligned4Zst; 0],
@@ -85,9 +85,10 @@ impl This {
         }
     }
 
-    pub fn to_value(self, scope: &mut LocalScope<'_>) -> Result<Value, Value> {
+    /// Evaluates this `this` binding in either strict mode or non-strict mode code.
+    pub fn to_value(self, is_strict: bool, scope: &mut LocalScope<'_>) -> Result<Value, Value> {
         match self.kind() {
-            // TODO: once we have strict mode, eval to undefined
+            ThisKind::Default if is_strict => Ok(Value::undefined()),
             ThisKind::Default => Ok(Value::object(scope.global)),
             ThisKind::Bound(value) => Ok(value),
             ThisKind::BeforeSuper { .. } => {
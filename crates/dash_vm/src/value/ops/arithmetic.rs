@@ -1,10 +1,105 @@
+use num_bigint::BigInt;
+use num_traits::{Signed, ToPrimitive, Zero};
+
 use crate::localscope::LocalScope;
+use crate::throw;
+use crate::value::bigint::{JsBigInt, MAX_BIGINT_BITS};
 use crate::value::object::Object as _;
-use crate::value::{Typeof, Value};
+use crate::value::{Typeof, Unpack, Value, ValueKind};
+
+use super::conversions::{PreferredType, ValueConversion};
+
+/// Converts a number to a 32-bit integer, as used by the bitwise operators
+fn int32(n: f64) -> i32 {
+    n as i64 as i32
+}
+
+/// Shifts a BigInt to the left by `shift` bits. Negative shifts shift to the right.
+fn bigint_shl(value: &BigInt, shift: &BigInt) -> Result<BigInt, &'static str> {
+    if value.is_zero() {
+        return Ok(BigInt::zero());
+    }
+
+    match shift.to_i64() {
+        Some(shift) if shift < 0 => Ok(value >> shift.unsigned_abs().min(u64::from(u32::MAX))),
+        Some(shift) if value.bits() + shift as u64 <= MAX_BIGINT_BITS => Ok(value << shift as u64),
+        // Shifting right by a huge amount leaves only the sign
+        None if shift.is_negative() => Ok(if value.is_negative() {
+            BigInt::from(-1)
+        } else {
+            BigInt::zero()
+        }),
+        _ => Err("Maximum BigInt size exceeded"),
+    }
+}
+
+fn bigint_pow(base: &BigInt, exponent: &BigInt) -> Result<BigInt, &'static str> {
+    if exponent.is_negative() {
+        return Err("Exponent must be non-negative");
+    }
+    if base.is_zero() || base.magnitude().bits() == 1 {
+        // 0, 1 and -1 never grow
+        return Ok(match exponent.to_u32() {
+            Some(exponent) => base.pow(exponent),
+            None if base.is_negative() && exponent.bit(0) => base.clone(),
+            None if base.is_zero() => BigInt::zero(),
+            None => BigInt::from(1),
+        });
+    }
 
-use super::conversions::ValueConversion;
+    match exponent.to_u32() {
+        Some(exponent) if base.bits() * u64::from(exponent) <= MAX_BIGINT_BITS => Ok(base.pow(exponent)),
+        _ => Err("Maximum BigInt size exceeded"),
+    }
+}
 
 impl Value {
+    /// Implements the ToNumeric abstract operation, converting the value to either a number or a BigInt.
+    pub fn to_numeric(&self, sc: &mut LocalScope) -> Result<Value, Value> {
+        let prim = self.to_primitive(sc, Some(PreferredType::Number))?;
+        if let ValueKind::BigInt(_) = prim.unpack() {
+            return Ok(prim);
+        }
+        Ok(Value::number(prim.to_number(sc)?))
+    }
+
+    /// Returns the value `1` with the same numeric type as this value, for use in increments and decrements.
+    ///
+    /// The value should be the result of [`Value::to_numeric`].
+    pub fn numeric_one(&self, sc: &mut LocalScope) -> Value {
+        match self.unpack() {
+            ValueKind::BigInt(_) => Value::bigint(JsBigInt::new(sc, BigInt::from(1))),
+            _ => Value::number(1.0),
+        }
+    }
+
+    /// Applies a binary numeric operator, dispatching to either the number or BigInt implementation.
+    ///
+    /// Errors returned by the BigInt implementation are thrown as RangeErrors.
+    fn numeric_binary(
+        self,
+        other: Self,
+        sc: &mut LocalScope,
+        number: impl FnOnce(f64, f64) -> f64,
+        bigint: impl FnOnce(&BigInt, &BigInt) -> Result<BigInt, &'static str>,
+    ) -> Result<Value, Value> {
+        let left = self.to_numeric(sc)?;
+        let right = other.to_numeric(sc)?;
+
+        match (left.unpack(), right.unpack()) {
+            (ValueKind::Number(left), ValueKind::Number(right)) => Ok(Value::number(number(left.0, right.0))),
+            (ValueKind::BigInt(left), ValueKind::BigInt(right)) => match bigint(left.get(sc), right.get(sc)) {
+                Ok(result) => Ok(Value::bigint(JsBigInt::new(sc, result))),
+                Err(message) => throw!(sc, RangeError, "{}", message),
+            },
+            _ => throw!(
+                sc,
+                TypeError,
+                "Cannot mix BigInt and other types, use explicit conversions"
+            ),
+        }
+    }
+
     pub fn add(self, other: Self, scope: &mut LocalScope) -> Result<Value, Value> {
         let left = self.to_primitive(scope, None)?;
         let right = other.to_primitive(scope, None)?;
@@ -15,87 +110,120 @@ impl Value {
         if leftstr || rightstr {
             let lstr = left.to_js_string(scope)?;
             let rstr = right.to_js_string(scope)?;
-            let out = format!("{}{}", lstr.res(scope), rstr.res(scope));
-            Ok(Value::string(scope.intern(out).into()))
+            Ok(Value::string(lstr.concat(rstr, scope)))
         } else {
-            let lnum = left.to_number(scope)?;
-            let rnum = right.to_number(scope)?;
-            Ok(Value::number(lnum + rnum))
+            left.numeric_binary(right, scope, |l, r| l + r, |l, r| Ok(l + r))
         }
     }
 
     pub fn sub(self, other: Self, scope: &mut LocalScope) -> Result<Value, Value> {
-        let lnum = self.to_number(scope)?;
-        let rnum = other.to_number(scope)?;
-        Ok(Value::number(lnum - rnum))
+        self.numeric_binary(other, scope, |l, r| l - r, |l, r| Ok(l - r))
     }
 
     pub fn mul(self, other: Self, scope: &mut LocalScope) -> Result<Value, Value> {
-        let lnum = self.to_number(scope)?;
-        let rnum = other.to_number(scope)?;
-        Ok(Value::number(lnum * rnum))
+        self.numeric_binary(other, scope, |l, r| l * r, |l, r| Ok(l * r))
     }
 
     pub fn div(self, other: Self, scope: &mut LocalScope) -> Result<Value, Value> {
-        let lnum = self.to_number(scope)?;
-        let rnum = other.to_number(scope)?;
-        Ok(Value::number(lnum / rnum))
+        self.numeric_binary(
+            other,
+            scope,
+            |l, r| l / r,
+            |l, r| {
+                if r.is_zero() {
+                    Err("Division by zero")
+                } else {
+                    Ok(l / r)
+                }
+            },
+        )
     }
 
     pub fn rem(self, other: Self, scope: &mut LocalScope) -> Result<Value, Value> {
-        let lnum = self.to_number(scope)?;
-        let rnum = other.to_number(scope)?;
-        Ok(Value::number(lnum % rnum))
+        self.numeric_binary(
+            other,
+            scope,
+            |l, r| l % r,
+            |l, r| {
+                if r.is_zero() {
+                    Err("Division by zero")
+                } else {
+                    Ok(l % r)
+                }
+            },
+        )
     }
 
     pub fn pow(self, other: Self, scope: &mut LocalScope) -> Result<Value, Value> {
-        let lnum = self.to_number(scope)?;
-        let rnum = other.to_number(scope)?;
-        Ok(Value::number(lnum.powf(rnum)))
+        self.numeric_binary(other, scope, f64::powf, bigint_pow)
     }
 
     pub fn not(self, sc: &mut LocalScope<'_>) -> Value {
         Value::boolean(!self.is_truthy(sc))
     }
 
+    pub fn neg(self, scope: &mut LocalScope) -> Result<Value, Value> {
+        let value = self.to_numeric(scope)?;
+        match value.unpack() {
+            ValueKind::BigInt(b) => {
+                let result = -b.get(scope);
+                Ok(Value::bigint(JsBigInt::new(scope, result)))
+            }
+            _ => Ok(Value::number(-value.to_number(scope)?)),
+        }
+    }
+
     pub fn bitor(self, other: Self, scope: &mut LocalScope) -> Result<Value, Value> {
-        let this = self.to_int32(scope)?;
-        let that = other.to_int32(scope)?;
-        Ok(Value::number((this | that) as f64))
+        self.numeric_binary(other, scope, |l, r| (int32(l) | int32(r)) as f64, |l, r| Ok(l | r))
     }
 
     pub fn bitxor(self, other: Self, scope: &mut LocalScope) -> Result<Value, Value> {
-        let this = self.to_int32(scope)?;
-        let that = other.to_int32(scope)?;
-        Ok(Value::number((this ^ that) as f64))
+        self.numeric_binary(other, scope, |l, r| (int32(l) ^ int32(r)) as f64, |l, r| Ok(l ^ r))
     }
 
     pub fn bitand(self, other: Self, scope: &mut LocalScope) -> Result<Value, Value> {
-        let this = self.to_int32(scope)?;
-        let that = other.to_int32(scope)?;
-        Ok(Value::number((this & that) as f64))
+        self.numeric_binary(other, scope, |l, r| (int32(l) & int32(r)) as f64, |l, r| Ok(l & r))
     }
 
     pub fn bitshl(self, other: Self, scope: &mut LocalScope) -> Result<Value, Value> {
-        let this = self.to_int32(scope)?;
-        let that = other.to_int32(scope)?;
-        Ok(Value::number((this << that) as f64))
+        self.numeric_binary(
+            other,
+            scope,
+            |l, r| int32(l).wrapping_shl(int32(r) as u32) as f64,
+            bigint_shl,
+        )
     }
 
     pub fn bitshr(self, other: Self, scope: &mut LocalScope) -> Result<Value, Value> {
-        let this = self.to_int32(scope)?;
-        let that = other.to_int32(scope)?;
-        Ok(Value::number((this >> that) as f64))
+        self.numeric_binary(
+            other,
+            scope,
+            |l, r| int32(l).wrapping_shr(int32(r) as u32) as f64,
+            |l, r| bigint_shl(l, &-r),
+        )
     }
 
     pub fn bitushr(self, other: Self, scope: &mut LocalScope) -> Result<Value, Value> {
-        let this = self.to_int32(scope)? as u32;
-        let that = other.to_int32(scope)? as u32;
+        let left = self.to_numeric(scope)?;
+        let right = other.to_numeric(scope)?;
+
+        if let (ValueKind::BigInt(_), _) | (_, ValueKind::BigInt(_)) = (left.unpack(), right.unpack()) {
+            throw!(scope, TypeError, "BigInts have no unsigned right shift, use >> instead")
+        }
+
+        let this = left.to_int32(scope)? as u32;
+        let that = right.to_int32(scope)? as u32;
         Ok(Value::number((this.wrapping_shr(that)) as f64))
     }
 
     pub fn bitnot(self, scope: &mut LocalScope) -> Result<Value, Value> {
-        let this = self.to_int32(scope)?;
-        Ok(Value::number((!this) as f64))
+        let value = self.to_numeric(scope)?;
+        match value.unpack() {
+            ValueKind::BigInt(b) => {
+                let result = !b.get(scope);
+                Ok(Value::bigint(JsBigInt::new(scope, result)))
+            }
+            _ => Ok(Value::number((!value.to_int32(scope)?) as f64)),
+        }
     }
 }
@@ -3,7 +3,9 @@ use dash_middle::interner::sym;
 use crate::gc::ObjectId;
 use crate::localscope::LocalScope;
 use crate::throw;
-use crate::value::boxed::{Boolean, Number as BoxedNumber, String as BoxedString, Symbol as BoxedSymbol};
+use crate::value::boxed::{
+    BigInt as BoxedBigInt, Boolean, Number as BoxedNumber, String as BoxedString, Symbol as BoxedSymbol,
+};
 use crate::value::function::args::CallArgs;
 use crate::value::object::{Object, This};
 use crate::value::primitive::{MAX_SAFE_INTEGERF, Number};
@@ -32,6 +34,18 @@ pub trait ValueConversion {
         self.to_length(sc).map(|x| x as usize)
     }
 
+    fn to_index(&self, sc: &mut LocalScope) -> Result<usize, Value> {
+        // Let integer be ? ToIntegerOrInfinity(value).
+        let integer = self.to_integer_or_infinity(sc)?;
+        // 2. If integer is not in the inclusive interval from 0 to 2^53 - 1, throw a RangeError exception.
+        if !(0.0..=MAX_SAFE_INTEGERF).contains(&integer) {
+            throw!(sc, RangeError, "Invalid index: {}", integer);
+        }
+
+        // 3. Return integer.
+        Ok(integer as usize)
+    }
+
     fn to_integer_or_infinity(&self, sc: &mut LocalScope) -> Result<f64, Value> {
         // Let number be ? ToNumber(argument).
         let number = self.to_number(sc)?;
@@ -79,6 +93,7 @@ impl ValueConversion for Value {
                 _ => Ok(s.res(sc).parse::<f64>().unwrap_or(f64::NAN)),
             },
             ValueKind::Symbol(_) => throw!(sc, TypeError, "Cannot convert symbol to number"),
+            ValueKind::BigInt(b) => b.to_number(sc),
             ValueKind::Object(_) => self.to_primitive(sc, Some(PreferredType::Number))?.to_number(sc),
             ValueKind::External(_) => unreachable!(),
         }
@@ -92,6 +107,7 @@ impl ValueConversion for Value {
             ValueKind::Number(Number(n)) => Ok(n != 0.0 && !n.is_nan()),
             ValueKind::String(s) => Ok(!s.res(sc).is_empty()),
             ValueKind::Symbol(_) => Ok(true),
+            ValueKind::BigInt(b) => b.to_boolean(sc),
             ValueKind::Object(_) => Ok(true),
             ValueKind::External(_) => unreachable!(),
         }
@@ -104,6 +120,7 @@ impl ValueConversion for Value {
             ValueKind::Null(n) => ValueConversion::to_js_string(&n, sc),
             ValueKind::Undefined(u) => ValueConversion::to_js_string(&u, sc),
             ValueKind::Number(n) => ValueConversion::to_js_string(&n, sc),
+            ValueKind::BigInt(b) => ValueConversion::to_js_string(&b, sc),
             ValueKind::Object(_) => self.to_primitive(sc, Some(PreferredType::String))?.to_js_string(sc),
             ValueKind::Symbol(_) => throw!(sc, TypeError, "Cannot convert symbol to a string"),
             ValueKind::External(_) => unreachable!(),
@@ -171,6 +188,7 @@ impl ValueConversion for Value {
             ValueKind::Symbol(s) => register_dyn(sc, |sc| BoxedSymbol::new(sc, s)),
             ValueKind::Number(Number(n)) => register_dyn(sc, |sc| BoxedNumber::new(sc, n)),
             ValueKind::String(s) => register_dyn(sc, |sc| BoxedString::new(sc, s)),
+            ValueKind::BigInt(b) => register_dyn(sc, |sc| BoxedBigInt::new(sc, b)),
             ValueKind::External(_) => unreachable!(),
         }
     }
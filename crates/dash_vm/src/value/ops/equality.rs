@@ -1,20 +1,44 @@
 use std::cmp::Ordering;
 
+use crate::Vm;
 use crate::localscope::LocalScope;
+use crate::value::bigint::{compare_bigint_number, parse_bigint};
 use crate::value::object::Object as _;
+use crate::value::primitive::Number;
 use crate::value::{Unpack, Value, ValueKind};
 
 use super::conversions::ValueConversion;
 
 fn ord_value(left: Value, right: Value, sc: &mut LocalScope) -> Result<Option<Ordering>, Value> {
-    if let (ValueKind::String(left), ValueKind::String(right)) = (left.unpack(), right.unpack()) {
-        let left = left.res(sc);
-        let right = right.res(sc);
-        Ok(Some(left.cmp(right)))
-    } else {
-        let left = left.to_number(sc)?;
-        let right = right.to_number(sc)?;
-        Ok(left.partial_cmp(&right))
+    match (left.unpack(), right.unpack()) {
+        (ValueKind::String(left), ValueKind::String(right)) => {
+            let left = left.res(sc);
+            let right = right.res(sc);
+            Ok(Some(left.cmp(right)))
+        }
+        (ValueKind::BigInt(left), ValueKind::String(right)) => {
+            Ok(parse_bigint(right.res(sc)).map(|right| left.get(sc).cmp(&right)))
+        }
+        (ValueKind::String(left), ValueKind::BigInt(right)) => {
+            Ok(parse_bigint(left.res(sc)).map(|left| left.cmp(right.get(sc))))
+        }
+        _ => {
+            let left = left.to_numeric(sc)?;
+            let right = right.to_numeric(sc)?;
+
+            match (left.unpack(), right.unpack()) {
+                (ValueKind::BigInt(left), ValueKind::BigInt(right)) => Ok(Some(left.get(sc).cmp(right.get(sc)))),
+                (ValueKind::BigInt(left), ValueKind::Number(right)) => Ok(compare_bigint_number(left.get(sc), right.0)),
+                (ValueKind::Number(left), ValueKind::BigInt(right)) => {
+                    Ok(compare_bigint_number(right.get(sc), left.0).map(Ordering::reverse))
+                }
+                _ => {
+                    let left = left.to_number(sc)?;
+                    let right = right.to_number(sc)?;
+                    Ok(left.partial_cmp(&right))
+                }
+            }
+        }
     }
 }
 
@@ -38,7 +62,7 @@ pub fn ge(left: Value, right: Value, sc: &mut LocalScope) -> Result<bool, Value>
 pub fn eq(left: Value, right: Value, sc: &mut LocalScope) -> Result<bool, Value> {
     // TODO: fast path for same tag..?
     if left.type_of(sc) == right.type_of(sc) {
-        return Ok(strict_eq(left, right));
+        return Ok(strict_eq(left, right, sc));
     }
 
     let left_full = left.unpack();
@@ -60,6 +84,22 @@ pub fn eq(left: Value, right: Value, sc: &mut LocalScope) -> Result<bool, Value>
         return Ok(num == right.0);
     }
 
+    if let (ValueKind::BigInt(left), ValueKind::Number(right)) = (left_full, right_full) {
+        return Ok(compare_bigint_number(left.get(sc), right.0) == Some(Ordering::Equal));
+    }
+
+    if let (ValueKind::Number(left), ValueKind::BigInt(right)) = (left_full, right_full) {
+        return Ok(compare_bigint_number(right.get(sc), left.0) == Some(Ordering::Equal));
+    }
+
+    if let (ValueKind::BigInt(left), ValueKind::String(right)) = (left_full, right_full) {
+        return Ok(parse_bigint(right.res(sc)).is_some_and(|right| *left.get(sc) == right));
+    }
+
+    if let (ValueKind::String(left), ValueKind::BigInt(right)) = (left_full, right_full) {
+        return Ok(parse_bigint(left.res(sc)).is_some_and(|left| left == *right.get(sc)));
+    }
+
     if let ValueKind::Boolean(b) = left_full {
         return eq(Value::number(b.into()), right, sc);
     }
@@ -68,14 +108,20 @@ pub fn eq(left: Value, right: Value, sc: &mut LocalScope) -> Result<bool, Value>
         return eq(left, Value::number(b.into()), sc);
     }
 
-    if let (ValueKind::String(_) | ValueKind::Number(_) | ValueKind::Symbol(_), ValueKind::Object(_)) =
+    if let (
+        ValueKind::String(_) | ValueKind::Number(_) | ValueKind::Symbol(_) | ValueKind::BigInt(_),
+        ValueKind::Object(_),
+    ) =
         (left_full, right_full)
     {
         let right = right.to_primitive(sc, None)?;
         return eq(left, right, sc);
     }
 
-    if let (ValueKind::Object(_), ValueKind::String(_) | ValueKind::Number(_) | ValueKind::Symbol(_)) =
+    if let (
+        ValueKind::Object(_),
+        ValueKind::String(_) | ValueKind::Number(_) | ValueKind::Symbol(_) | ValueKind::BigInt(_),
+    ) =
         (left_full, right_full)
     {
         let left = left.to_primitive(sc, None)?;
@@ -85,24 +131,44 @@ pub fn eq(left: Value, right: Value, sc: &mut LocalScope) -> Result<bool, Value>
     Ok(false)
 }
 
-pub fn strict_eq(left: Value, right: Value) -> bool {
+pub fn strict_eq(left: Value, right: Value, vm: &Vm) -> bool {
     match (left.unpack(), right.unpack()) {
         (ValueKind::Number(l), ValueKind::Number(r)) => l == r,
         (ValueKind::Boolean(l), ValueKind::Boolean(r)) => l == r,
-        (ValueKind::String(l), ValueKind::String(r)) => l == r,
+        (ValueKind::String(l), ValueKind::String(r)) => l.content_eq(r, vm),
         (ValueKind::Undefined(_), ValueKind::Undefined(_)) => true,
         (ValueKind::Null(_), ValueKind::Null(_)) => true,
         (ValueKind::Symbol(l), ValueKind::Symbol(r)) => l == r,
         (ValueKind::Object(l), ValueKind::Object(r)) => l == r,
+        (ValueKind::BigInt(l), ValueKind::BigInt(r)) => l.get(vm) == r.get(vm),
         (ValueKind::External(_), ValueKind::External(_)) => panic!("cannot compare external values"),
         _ => false,
     }
 }
 
+/// Implements the SameValue abstract operation, which unlike strict equality treats NaN as equal to itself
+/// and distinguishes +0 from -0
+pub fn same_value(left: Value, right: Value, vm: &Vm) -> bool {
+    match (left.unpack(), right.unpack()) {
+        (ValueKind::Number(Number(l)), ValueKind::Number(Number(r))) => {
+            (l.is_nan() && r.is_nan()) || (l == r && l.is_sign_negative() == r.is_sign_negative())
+        }
+        _ => strict_eq(left, right, vm),
+    }
+}
+
+/// Implements the SameValueZero abstract operation, which unlike strict equality treats NaN as equal to itself
+pub fn same_value_zero(left: Value, right: Value, vm: &Vm) -> bool {
+    match (left.unpack(), right.unpack()) {
+        (ValueKind::Number(Number(l)), ValueKind::Number(Number(r))) => (l.is_nan() && r.is_nan()) || l == r,
+        _ => strict_eq(left, right, vm),
+    }
+}
+
 pub fn ne(left: Value, right: Value, sc: &mut LocalScope) -> Result<bool, Value> {
     eq(left, right, sc).map(|v| !v)
 }
 
-pub fn strict_ne(left: Value, right: Value) -> bool {
-    !strict_eq(left, right)
+pub fn strict_ne(left: Value, right: Value, vm: &Vm) -> bool {
+    !strict_eq(left, right, vm)
 }
@@ -1,5 +1,5 @@
+use std::fmt;
 use std::hash::{Hash, Hasher};
-use std::{fmt, iter};
 
 use dash_middle::interner::{self, sym};
 use dash_proc_macro::Trace;
@@ -10,6 +10,7 @@ use crate::util::intern_f64;
 use crate::value::object::{OwnKeysMode, This};
 use crate::{Vm, extract, throw};
 
+use super::bigint::JsBigInt;
 use super::boxed::{Boolean as BoxedBoolean, Number as BoxedNumber, Symbol as BoxedSymbol};
 use super::function::args::CallArgs;
 use super::object::{Object, PropertyValue};
@@ -32,12 +33,12 @@ impl Object for f64 {
         Ok(None)
     }
 
-    fn set_property(&self, _key: PropertyKey, _value: PropertyValue, _sc: &mut LocalScope) -> Result<(), Value> {
-        Ok(())
+    fn set_property(&self, _key: PropertyKey, _value: PropertyValue, _sc: &mut LocalScope) -> Result<bool, Value> {
+        Ok(false)
     }
 
-    fn delete_property(&self, _key: PropertyKey, _sc: &mut LocalScope) -> Result<Unrooted, Value> {
-        Ok(Unrooted::new(Value::undefined()))
+    fn delete_property(&self, _key: PropertyKey, _sc: &mut LocalScope) -> Result<bool, Value> {
+        Ok(true)
     }
 
     fn set_prototype(&self, _value: Value, _sc: &mut LocalScope) -> Result<(), Value> {
@@ -83,12 +84,12 @@ impl Object for bool {
         Ok(None)
     }
 
-    fn set_property(&self, _key: PropertyKey, _value: PropertyValue, _sc: &mut LocalScope) -> Result<(), Value> {
-        Ok(())
+    fn set_property(&self, _key: PropertyKey, _value: PropertyValue, _sc: &mut LocalScope) -> Result<bool, Value> {
+        Ok(false)
     }
 
-    fn delete_property(&self, _key: PropertyKey, _sc: &mut LocalScope) -> Result<Unrooted, Value> {
-        Ok(Unrooted::new(Value::undefined()))
+    fn delete_property(&self, _key: PropertyKey, _sc: &mut LocalScope) -> Result<bool, Value> {
+        Ok(true)
     }
 
     fn set_prototype(&self, _value: Value, _sc: &mut LocalScope) -> Result<(), Value> {
@@ -124,10 +125,17 @@ impl Object for bool {
     extract!(self);
 }
 
-pub fn array_like_keys<'a, 'b>(sc: &'a mut LocalScope<'b>, len: usize) -> impl Iterator<Item = Value> + use<'a, 'b> {
+/// Returns the keys of an array-like object, i.e. its indices followed by the non-enumerable `length` key
+pub fn array_like_keys<'a, 'b>(
+    sc: &'a mut LocalScope<'b>,
+    len: usize,
+    mode: OwnKeysMode,
+) -> impl Iterator<Item = Value> + use<'a, 'b> {
+    let include_length = !matches!(mode, OwnKeysMode::OnlyEnumerable);
+
     (0..len)
         .map(|i| sc.intern_usize(i))
-        .chain(iter::once_with(|| sym::length))
+        .chain(include_length.then_some(sym::length))
         .map(|x| Value::string(x.into()))
 }
 
@@ -148,14 +156,14 @@ impl Object for Undefined {
         throw!(sc, TypeError, "Cannot read property {} of undefined", key)
     }
 
-    fn set_property(&self, key: PropertyKey, _value: PropertyValue, sc: &mut LocalScope) -> Result<(), Value> {
+    fn set_property(&self, key: PropertyKey, _value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
         let key = key.any_js_string(sc);
         let key = sc.interner.resolve(key).to_owned();
         throw!(sc, TypeError, "Cannot set property {:?} of undefined", key)
     }
 
-    fn delete_property(&self, _key: PropertyKey, _sc: &mut LocalScope) -> Result<Unrooted, Value> {
-        Ok(Unrooted::new(Value::undefined()))
+    fn delete_property(&self, _key: PropertyKey, _sc: &mut LocalScope) -> Result<bool, Value> {
+        Ok(true)
     }
 
     fn set_prototype(&self, _value: Value, sc: &mut LocalScope) -> Result<(), Value> {
@@ -198,14 +206,14 @@ impl Object for Null {
         throw!(sc, TypeError, "Cannot read property {} of null", key)
     }
 
-    fn set_property(&self, key: PropertyKey, _value: PropertyValue, sc: &mut LocalScope) -> Result<(), Value> {
+    fn set_property(&self, key: PropertyKey, _value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
         let key = key.any_js_string(sc);
         let key = sc.interner.resolve(key).to_owned();
         throw!(sc, TypeError, "Cannot set property {:?} of null", key)
     }
 
-    fn delete_property(&self, _key: PropertyKey, _sc: &mut LocalScope) -> Result<Unrooted, Value> {
-        Ok(Unrooted::new(Value::undefined()))
+    fn delete_property(&self, _key: PropertyKey, _sc: &mut LocalScope) -> Result<bool, Value> {
+        Ok(true)
     }
 
     fn set_prototype(&self, _value: Value, sc: &mut LocalScope) -> Result<(), Value> {
@@ -258,12 +266,12 @@ impl Object for Symbol {
         Ok(None)
     }
 
-    fn set_property(&self, _key: PropertyKey, _value: PropertyValue, _sc: &mut LocalScope) -> Result<(), Value> {
-        Ok(())
+    fn set_property(&self, _key: PropertyKey, _value: PropertyValue, _sc: &mut LocalScope) -> Result<bool, Value> {
+        Ok(false)
     }
 
-    fn delete_property(&self, _key: PropertyKey, _sc: &mut LocalScope) -> Result<Unrooted, Value> {
-        Ok(Unrooted::new(Value::undefined()))
+    fn delete_property(&self, _key: PropertyKey, _sc: &mut LocalScope) -> Result<bool, Value> {
+        Ok(true)
     }
 
     fn set_prototype(&self, _value: Value, _sc: &mut LocalScope) -> Result<(), Value> {
@@ -313,6 +321,9 @@ pub trait InternalSlots {
     fn boolean_value(&self, _: &Vm) -> Option<bool> {
         None
     }
+    fn bigint_value(&self, _: &Vm) -> Option<JsBigInt> {
+        None
+    }
 }
 
 // TODO: do we even need this given that we have it for the Number wrapper? same for Rc<str> str etc
@@ -497,11 +508,11 @@ impl Object for Number {
         self.0.get_own_property_descriptor(key, sc)
     }
 
-    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<(), Value> {
+    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
         self.0.set_property(key, value, sc)
     }
 
-    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<Unrooted, Value> {
+    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Value> {
         self.0.delete_property(key, sc)
     }
 
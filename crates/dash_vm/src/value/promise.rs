@@ -6,7 +6,7 @@ use crate::gc::ObjectId;
 use crate::gc::trace::{Trace, TraceCtxt};
 use crate::localscope::LocalScope;
 use crate::value::object::{OwnKeysMode, This};
-use crate::{PromiseAction, Vm, extract};
+use crate::{PromiseAction, Vm, delegate, extract};
 
 use super::function::args::CallArgs;
 use super::object::{Object, OrdObject, PropertyValue};
@@ -90,11 +90,11 @@ impl Object for Promise {
         self.obj.get_own_property_descriptor(key, sc)
     }
 
-    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<(), Value> {
+    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
         self.obj.set_property(key, value, sc)
     }
 
-    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<Unrooted, Value> {
+    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Value> {
         self.obj.delete_property(key, sc)
     }
 
@@ -106,6 +106,8 @@ impl Object for Promise {
         self.obj.get_prototype(sc)
     }
 
+    delegate!(obj, prevent_extensions, is_extensible);
+
     fn apply(
         &self,
         callee: ObjectId,
@@ -147,11 +149,11 @@ impl Object for PromiseResolver {
         self.obj.get_own_property_descriptor(key, sc)
     }
 
-    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<(), Value> {
+    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
         self.obj.set_property(key, value, sc)
     }
 
-    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<Unrooted, Value> {
+    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Value> {
         self.obj.delete_property(key, sc)
     }
 
@@ -163,6 +165,8 @@ impl Object for PromiseResolver {
         self.obj.get_prototype(sc)
     }
 
+    delegate!(obj, prevent_extensions, is_extensible);
+
     fn apply(
         &self,
         _callee: ObjectId,
@@ -215,11 +219,11 @@ impl Object for PromiseRejecter {
         self.obj.get_own_property_descriptor(key, sc)
     }
 
-    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<(), Value> {
+    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
         self.obj.set_property(key, value, sc)
     }
 
-    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<Unrooted, Value> {
+    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Value> {
         self.obj.delete_property(key, sc)
     }
 
@@ -231,6 +235,8 @@ impl Object for PromiseRejecter {
         self.obj.get_prototype(sc)
     }
 
+    delegate!(obj, prevent_extensions, is_extensible);
+
     fn apply(
         &self,
         _callee: ObjectId,
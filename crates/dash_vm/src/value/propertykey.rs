@@ -112,7 +112,8 @@ impl PropertyKey {
         if let Ok(n) = string.res(sc).parse::<u32>() {
             Self(PropertyKeyInner::Index(n))
         } else {
-            Self(PropertyKeyInner::String(string))
+            // Keys are compared by their symbol, so they must be interned
+            Self(PropertyKeyInner::String(string.interned(sc)))
         }
     }
 
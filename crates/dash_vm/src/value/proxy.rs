@@ -0,0 +1,542 @@
+use std::cell::Cell;
+
+use dash_middle::interner::{self, sym};
+use dash_proc_macro::Trace;
+
+use crate::gc::ObjectId;
+use crate::localscope::LocalScope;
+use crate::value::object::{OwnKeysMode, This, ThisKind};
+use crate::value::root_ext::RootErrExt;
+use crate::{Vm, delegate, extract, throw};
+
+use super::array::Array;
+use super::function::args::CallArgs;
+use super::object::ordinary::set_with_receiver;
+use super::object::{Object, OrdObject, PropertyDataDescriptor, PropertyValue, PropertyValueKind};
+use super::ops::conversions::ValueConversion;
+use super::propertykey::{PropertyKey, ToPropertyKey};
+use super::{Root, Typeof, Unpack, Unrooted, Value, ValueKind};
+
+/// A proxy exotic object.
+///
+/// All internal methods are forwarded to the corresponding trap on the handler object,
+/// or to the target object if the handler does not define that trap.
+#[derive(Debug, Trace)]
+pub struct Proxy {
+    target: ObjectId,
+    /// The handler object, or `None` if the proxy has been revoked
+    handler: Cell<Option<ObjectId>>,
+}
+
+/// A trap function that was looked up on a proxy handler
+#[derive(Copy, Clone)]
+struct ProxyTrap {
+    handler: ObjectId,
+    function: ObjectId,
+}
+
+impl ProxyTrap {
+    fn call(self, args: impl Into<CallArgs>, sc: &mut LocalScope<'_>) -> Result<Value, Value> {
+        self.function
+            .apply(This::bound(Value::object(self.handler)), args.into(), sc)
+            .root(sc)
+    }
+}
+
+fn is_non_configurable(desc: Option<PropertyValue>) -> bool {
+    desc.is_some_and(|desc| !desc.descriptor.contains(PropertyDataDescriptor::CONFIGURABLE))
+}
+
+impl Proxy {
+    pub fn new(target: ObjectId, handler: ObjectId) -> Self {
+        Self {
+            target,
+            handler: Cell::new(Some(handler)),
+        }
+    }
+
+    pub fn target(&self) -> ObjectId {
+        self.target
+    }
+
+    pub fn revoke(&self) {
+        self.handler.set(None);
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.handler.get().is_none()
+    }
+
+    /// Looks up the trap with the given name on the handler.
+    /// Returns `None` if the handler does not define it, in which case the operation should be forwarded to the target.
+    fn trap(&self, name: interner::Symbol, sc: &mut LocalScope<'_>) -> Result<Option<ProxyTrap>, Value> {
+        let Some(handler) = self.handler.get() else {
+            let name = sc.interner.resolve(name).to_owned();
+            throw!(
+                sc,
+                TypeError,
+                "Cannot perform '{}' on a proxy that has been revoked",
+                name
+            )
+        };
+
+        let function = handler.get_property(name.to_key(sc), sc).root(sc)?;
+        match function.unpack() {
+            ValueKind::Undefined(_) | ValueKind::Null(_) => Ok(None),
+            ValueKind::Object(function) if matches!(function.type_of(sc), Typeof::Function) => {
+                Ok(Some(ProxyTrap { handler, function }))
+            }
+            _ => {
+                let name = sc.interner.resolve(name).to_owned();
+                throw!(sc, TypeError, "'{}' on proxy: trap is not a function", name)
+            }
+        }
+    }
+
+    /// Implements the `[[Set]]` internal method. `receiver` is usually the proxy itself.
+    pub fn set(&self, key: PropertyKey, value: Value, receiver: Value, sc: &mut LocalScope<'_>) -> Result<bool, Value> {
+        let Some(trap) = self.trap(sym::set, sc)? else {
+            return set_with_receiver(Value::object(self.target), key, value, receiver, sc);
+        };
+
+        let key_value = key.to_value(sc);
+        let success = trap
+            .call([Value::object(self.target), key_value, value, receiver], sc)?
+            .is_truthy(sc);
+
+        if !success {
+            return Ok(false);
+        }
+
+        if let Some(desc) = self.target.get_own_property_descriptor(key, sc).root_err(sc)?
+            && !desc.descriptor.contains(PropertyDataDescriptor::CONFIGURABLE)
+        {
+            match desc.kind {
+                PropertyValueKind::Static(target_value)
+                    if !desc.descriptor.contains(PropertyDataDescriptor::WRITABLE) && target_value != value =>
+                {
+                    throw!(
+                        sc,
+                        TypeError,
+                        "'set' on proxy: trap returned truish for a non-writable, non-configurable property with a different value"
+                    )
+                }
+                PropertyValueKind::Trap { set: None, .. } => {
+                    throw!(
+                        sc,
+                        TypeError,
+                        "'set' on proxy: trap returned truish for a non-configurable accessor property without a setter"
+                    )
+                }
+                _ => {}
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+impl Object for Proxy {
+    fn get_own_property_descriptor(
+        &self,
+        key: PropertyKey,
+        sc: &mut LocalScope,
+    ) -> Result<Option<PropertyValue>, Unrooted> {
+        let Some(trap) = self.trap(sym::getOwnPropertyDescriptor, sc)? else {
+            return self.target.get_own_property_descriptor(key, sc);
+        };
+
+        let key_value = key.to_value(sc);
+        let result = trap.call([Value::object(self.target), key_value], sc)?;
+        let target_desc = self.target.get_own_property_descriptor(key, sc)?;
+
+        match result.unpack() {
+            ValueKind::Undefined(_) => {
+                if is_non_configurable(target_desc) {
+                    throw!(
+                        sc,
+                        TypeError,
+                        "'getOwnPropertyDescriptor' on proxy: trap returned undefined for a property which is non-configurable on the target"
+                    )
+                }
+                Ok(None)
+            }
+            ValueKind::Object(_) => {
+                let desc = PropertyValue::from_descriptor_value(sc, result)?;
+                if !desc.descriptor.contains(PropertyDataDescriptor::CONFIGURABLE) && !is_non_configurable(target_desc)
+                {
+                    throw!(
+                        sc,
+                        TypeError,
+                        "'getOwnPropertyDescriptor' on proxy: trap reported non-configurability for a property which is either non-existent or configurable on the target"
+                    )
+                }
+                Ok(Some(desc))
+            }
+            _ => throw!(
+                sc,
+                TypeError,
+                "'getOwnPropertyDescriptor' on proxy: trap returned neither object nor undefined"
+            ),
+        }
+    }
+
+    fn get_property(&self, this: This, key: PropertyKey, sc: &mut LocalScope<'_>) -> Result<Unrooted, Unrooted> {
+        let Some(trap) = self.trap(sym::get, sc)? else {
+            return Object::get_property(&self.target, this, key, sc);
+        };
+
+        let receiver = match this.kind() {
+            ThisKind::Bound(value) => value,
+            _ => Value::undefined(),
+        };
+        let key_value = key.to_value(sc);
+        let value = trap.call([Value::object(self.target), key_value, receiver], sc)?;
+
+        if let Some(desc) = self.target.get_own_property_descriptor(key, sc)?
+            && !desc.descriptor.contains(PropertyDataDescriptor::CONFIGURABLE)
+        {
+            match desc.kind {
+                PropertyValueKind::Static(target_value)
+                    if !desc.descriptor.contains(PropertyDataDescriptor::WRITABLE) && target_value != value =>
+                {
+                    throw!(
+                        sc,
+                        TypeError,
+                        "'get' on proxy: property is a non-writable, non-configurable data property on the target but the trap did not return its actual value"
+                    )
+                }
+                PropertyValueKind::Trap { get: None, .. } if !matches!(value.unpack(), ValueKind::Undefined(_)) => {
+                    throw!(
+                        sc,
+                        TypeError,
+                        "'get' on proxy: property is a non-configurable accessor property without a getter on the target but the trap did not return undefined"
+                    )
+                }
+                _ => {}
+            }
+        }
+
+        Ok(value.into())
+    }
+
+    fn has_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Unrooted> {
+        let Some(trap) = self.trap(sym::has, sc)? else {
+            return self.target.has_property(key, sc);
+        };
+
+        let key_value = key.to_value(sc);
+        let found = trap.call([Value::object(self.target), key_value], sc)?.is_truthy(sc);
+
+        if !found && is_non_configurable(self.target.get_own_property_descriptor(key, sc)?) {
+            throw!(
+                sc,
+                TypeError,
+                "'has' on proxy: trap returned falsish for a property which is non-configurable on the target"
+            )
+        }
+
+        Ok(found)
+    }
+
+    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
+        match value.kind {
+            // Without access to the proxy itself, the target has to be used as the receiver.
+            // Assignments go through `set_with_receiver`, which passes the proxy.
+            PropertyValueKind::Static(value) => self.set(key, value, Value::object(self.target), sc),
+            PropertyValueKind::Trap { .. } => self.define_property(key, value, sc),
+        }
+    }
+
+    fn define_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
+        let Some(trap) = self.trap(sym::defineProperty, sc)? else {
+            return self.target.define_property(key, value, sc);
+        };
+
+        let key_value = key.to_value(sc);
+        let descriptor = value.to_descriptor_value(sc)?;
+        let success = trap
+            .call([Value::object(self.target), key_value, descriptor], sc)?
+            .is_truthy(sc);
+
+        if !success {
+            return Ok(false);
+        }
+
+        let target_desc = self.target.get_own_property_descriptor(key, sc).root_err(sc)?;
+        if !value.descriptor.contains(PropertyDataDescriptor::CONFIGURABLE) {
+            match target_desc {
+                None => throw!(
+                    sc,
+                    TypeError,
+                    "'defineProperty' on proxy: trap returned truish for defining a non-configurable property which does not exist on the target"
+                ),
+                Some(desc) if desc.descriptor.contains(PropertyDataDescriptor::CONFIGURABLE) => throw!(
+                    sc,
+                    TypeError,
+                    "'defineProperty' on proxy: trap returned truish for defining a non-configurable property which is configurable on the target"
+                ),
+                Some(_) => {}
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Value> {
+        let Some(trap) = self.trap(sym::deleteProperty, sc)? else {
+            return self.target.delete_property(key, sc);
+        };
+
+        let key_value = key.to_value(sc);
+        let deleted = trap.call([Value::object(self.target), key_value], sc)?.is_truthy(sc);
+
+        if !deleted {
+            return Ok(false);
+        }
+
+        if is_non_configurable(self.target.get_own_property_descriptor(key, sc).root_err(sc)?) {
+            throw!(
+                sc,
+                TypeError,
+                "'deleteProperty' on proxy: trap returned truish for a property which is non-configurable on the target"
+            )
+        }
+
+        Ok(true)
+    }
+
+    fn set_prototype(&self, value: Value, sc: &mut LocalScope) -> Result<(), Value> {
+        let Some(trap) = self.trap(sym::setPrototypeOf, sc)? else {
+            return self.target.set_prototype(value, sc);
+        };
+
+        let success = trap.call([Value::object(self.target), value], sc)?.is_truthy(sc);
+        if !success {
+            throw!(sc, TypeError, "'setPrototypeOf' on proxy: trap returned falsish")
+        }
+
+        Ok(())
+    }
+
+    fn get_prototype(&self, sc: &mut LocalScope) -> Result<Value, Value> {
+        let Some(trap) = self.trap(sym::getPrototypeOf, sc)? else {
+            return self.target.get_prototype(sc);
+        };
+
+        let prototype = trap.call([Value::object(self.target)], sc)?;
+        match prototype.unpack() {
+            ValueKind::Object(_) | ValueKind::Null(_) => Ok(prototype),
+            _ => throw!(
+                sc,
+                TypeError,
+                "'getPrototypeOf' on proxy: trap returned neither object nor null"
+            ),
+        }
+    }
+
+    fn prevent_extensions(&self, sc: &mut LocalScope) -> Result<(), Value> {
+        let Some(trap) = self.trap(sym::preventExtensions, sc)? else {
+            return self.target.prevent_extensions(sc);
+        };
+
+        let success = trap.call([Value::object(self.target)], sc)?.is_truthy(sc);
+        if !success {
+            throw!(sc, TypeError, "'preventExtensions' on proxy: trap returned falsish")
+        }
+
+        if self.target.is_extensible(sc)? {
+            throw!(
+                sc,
+                TypeError,
+                "'preventExtensions' on proxy: trap returned truish but the proxy target is extensible"
+            )
+        }
+
+        Ok(())
+    }
+
+    fn is_extensible(&self, sc: &mut LocalScope) -> Result<bool, Value> {
+        let Some(trap) = self.trap(sym::isExtensible, sc)? else {
+            return self.target.is_extensible(sc);
+        };
+
+        let result = trap.call([Value::object(self.target)], sc)?.is_truthy(sc);
+        if result != self.target.is_extensible(sc)? {
+            throw!(
+                sc,
+                TypeError,
+                "'isExtensible' on proxy: trap result does not reflect extensibility of proxy target"
+            )
+        }
+
+        Ok(result)
+    }
+
+    fn apply(&self, _: ObjectId, this: This, args: CallArgs, sc: &mut LocalScope) -> Result<Unrooted, Unrooted> {
+        if !matches!(self.target.type_of(sc), Typeof::Function) {
+            throw!(sc, TypeError, "proxy is not a function")
+        }
+
+        let Some(trap) = self.trap(sym::apply, sc)? else {
+            return self.target.apply(this, args, sc);
+        };
+
+        let this = match this.kind() {
+            ThisKind::Bound(value) => value,
+            _ => Value::undefined(),
+        };
+        let args = Array::from_vec(args.into_iter().map(PropertyValue::static_default).collect(), sc);
+        let args = Value::object(sc.register(args));
+
+        Ok(trap.call([Value::object(self.target), this, args], sc)?.into())
+    }
+
+    fn construct(
+        &self,
+        _: ObjectId,
+        this: This,
+        args: CallArgs,
+        new_target: ObjectId,
+        sc: &mut LocalScope,
+    ) -> Result<Unrooted, Unrooted> {
+        if !matches!(self.target.type_of(sc), Typeof::Function) {
+            throw!(sc, TypeError, "proxy is not a constructor")
+        }
+
+        let Some(trap) = self.trap(sym::construct, sc)? else {
+            return self.target.construct_with_target(this, args, new_target, sc);
+        };
+
+        let args = Array::from_vec(args.into_iter().map(PropertyValue::static_default).collect(), sc);
+        let args = Value::object(sc.register(args));
+
+        let result = trap.call([Value::object(self.target), args, Value::object(new_target)], sc)?;
+        match result.unpack() {
+            ValueKind::Object(_) => Ok(result.into()),
+            _ => throw!(sc, TypeError, "'construct' on proxy: trap returned non-object"),
+        }
+    }
+
+    fn own_keys(&self, sc: &mut LocalScope<'_>, mode: OwnKeysMode) -> Result<Vec<Value>, Value> {
+        let Some(trap) = self.trap(sym::ownKeys, sc)? else {
+            return self.target.own_keys(sc, mode);
+        };
+
+        let result = trap.call([Value::object(self.target)], sc)?;
+        if !matches!(result.unpack(), ValueKind::Object(_)) {
+            throw!(sc, TypeError, "'ownKeys' on proxy: trap returned a non-object")
+        }
+
+        let len = result.length_of_array_like(sc)?;
+        let mut keys = Vec::with_capacity(len);
+        for i in 0..len {
+            let key = result.get_property(i.to_key(sc), sc).root(sc)?;
+            if !matches!(key.unpack(), ValueKind::String(_) | ValueKind::Symbol(_)) {
+                throw!(
+                    sc,
+                    TypeError,
+                    "'ownKeys' on proxy: trap result contains an element that is neither a string nor a symbol"
+                )
+            }
+
+            let key = PropertyKey::from_value(sc, key)?;
+            if keys.contains(&key) {
+                throw!(
+                    sc,
+                    TypeError,
+                    "'ownKeys' on proxy: trap result contains duplicate entries"
+                )
+            }
+            keys.push(key);
+        }
+
+        for target_key in self.target.own_keys(sc, OwnKeysMode::All)? {
+            let target_key = PropertyKey::from_value(sc, target_key)?;
+            if !keys.contains(&target_key)
+                && is_non_configurable(self.target.get_own_property_descriptor(target_key, sc).root_err(sc)?)
+            {
+                throw!(
+                    sc,
+                    TypeError,
+                    "'ownKeys' on proxy: trap result does not include all non-configurable keys of the target"
+                )
+            }
+        }
+
+        let mut values = Vec::with_capacity(keys.len());
+        for key in keys {
+            let include = match mode {
+                OwnKeysMode::All => true,
+                OwnKeysMode::AllStrings => key.to_js_string(sc).is_some(),
+                OwnKeysMode::AllSymbols => key.to_js_string(sc).is_none(),
+                OwnKeysMode::OnlyEnumerable => self
+                    .get_own_property_descriptor(key, sc)
+                    .root_err(sc)?
+                    .is_some_and(|desc| desc.descriptor.contains(PropertyDataDescriptor::ENUMERABLE)),
+            };
+
+            if include {
+                values.push(key.to_value(sc));
+            }
+        }
+
+        Ok(values)
+    }
+
+    fn type_of(&self, vm: &Vm) -> Typeof {
+        self.target.type_of(vm)
+    }
+
+    extract!(self);
+}
+
+/// The `revoke` function returned by `Proxy.revocable`
+#[derive(Debug, Trace)]
+pub struct ProxyRevoker {
+    proxy: Cell<Option<ObjectId>>,
+    obj: OrdObject,
+}
+
+impl ProxyRevoker {
+    pub fn new(vm: &Vm, proxy: ObjectId) -> Self {
+        Self {
+            proxy: Cell::new(Some(proxy)),
+            obj: OrdObject::with_prototype(vm.statics.function_proto),
+        }
+    }
+}
+
+impl Object for ProxyRevoker {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        own_keys
+    );
+
+    fn apply(&self, _: ObjectId, _: This, _: CallArgs, sc: &mut LocalScope) -> Result<Unrooted, Unrooted> {
+        if let Some(proxy) = self.proxy.take() {
+            proxy
+                .extract::<Proxy>(sc)
+                .expect("ProxyRevoker must refer to a proxy")
+                .revoke();
+        }
+
+        Ok(Value::undefined().into())
+    }
+
+    fn type_of(&self, _: &Vm) -> Typeof {
+        Typeof::Function
+    }
+
+    extract!(self);
+}
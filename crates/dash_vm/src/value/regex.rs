@@ -1,13 +1,18 @@
 use std::cell::Cell;
 
+use dash_middle::interner::{Symbol, sym};
 use dash_proc_macro::Trace;
-use dash_regex::Regex;
+use dash_regex::{Flags, Regex};
 
 use crate::gc::trace::{Trace, TraceCtxt};
+use crate::localscope::LocalScope;
 use crate::{Vm, delegate, extract};
 
-use super::object::{Object, OrdObject};
+use super::object::{Object, OrdObject, PropertyDataDescriptor, PropertyValue, PropertyValueKind};
+use super::ops::conversions::ValueConversion;
+use super::propertykey::PropertyKey;
 use super::string::JsString;
+use super::{Unrooted, Value};
 
 #[derive(Debug)]
 pub struct RegExpInner {
@@ -68,16 +73,71 @@ impl RegExp {
     }
 }
 
+/// The flag properties of RegExp objects
+const FLAG_PROPERTIES: [(Symbol, Flags); 8] = [
+    (sym::global, Flags::GLOBAL),
+    (sym::ignoreCase, Flags::IGNORE_CASE),
+    (sym::multiline, Flags::MULTILINE),
+    (sym::dotAll, Flags::DOT_ALL),
+    (sym::unicode, Flags::UNICODE),
+    (sym::unicodeSets, Flags::UNICODE_SETS),
+    (sym::sticky, Flags::STICKY),
+    (sym::hasIndices, Flags::HAS_INDICES),
+];
+
 impl Object for RegExp {
+    fn get_own_property_descriptor(
+        &self,
+        key: PropertyKey,
+        sc: &mut LocalScope,
+    ) -> Result<Option<PropertyValue>, Unrooted> {
+        if let Some(inner) = &self.inner
+            && let Some(name) = key.to_js_string(sc)
+        {
+            let flags = inner.regex.flags();
+            let value = match name {
+                sym::lastIndex => {
+                    let value = Value::number(inner.last_index.get() as f64);
+                    return Ok(Some(PropertyValue::new(
+                        PropertyValueKind::Static(value),
+                        PropertyDataDescriptor::WRITABLE,
+                    )));
+                }
+                sym::source => Some(Value::string(inner.source)),
+                sym::flags => Some(Value::string(sc.intern(flags.to_string()).into())),
+                name => FLAG_PROPERTIES
+                    .iter()
+                    .find(|&&(flag_name, _)| flag_name == name)
+                    .map(|&(_, flag)| Value::boolean(flags.contains(flag))),
+            };
+
+            if let Some(value) = value {
+                return Ok(Some(PropertyValue::static_empty(value)));
+            }
+        }
+
+        self.object.get_own_property_descriptor(key, sc)
+    }
+
+    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
+        if let Some(inner) = &self.inner
+            && key.to_js_string(sc).is_some_and(|name| name == sym::lastIndex)
+            && let PropertyValueKind::Static(value) = value.kind
+        {
+            inner.last_index.set(value.to_length_u(sc)?);
+            return Ok(true);
+        }
+
+        self.object.set_property(key, value, sc)
+    }
+
     delegate!(
         object,
-        get_own_property_descriptor,
-        get_property,
-        get_property_descriptor,
-        set_property,
         delete_property,
         set_prototype,
         get_prototype,
+        prevent_extensions,
+        is_extensible,
         apply,
         own_keys
     );
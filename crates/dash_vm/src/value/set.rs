@@ -1,16 +1,16 @@
-use std::cell::RefCell;
-use std::collections::HashSet;
+use std::cell::{Cell, RefCell};
 
 use dash_proc_macro::Trace;
 
 use crate::{Vm, delegate, extract};
 
 use super::Value;
+use super::map::{OrderedTable, TableCursor};
 use super::object::{Object, OrdObject};
 
 #[derive(Debug, Trace)]
 pub struct Set {
-    inner: RefCell<HashSet<Value>>,
+    inner: RefCell<OrderedTable<()>>,
     obj: OrdObject,
 }
 
@@ -21,13 +21,13 @@ impl Set {
 
     pub fn with_obj(obj: OrdObject) -> Self {
         Self {
-            inner: RefCell::new(HashSet::new()),
+            inner: RefCell::new(OrderedTable::new()),
             obj,
         }
     }
 
     pub fn add(&self, item: Value) {
-        self.inner.borrow_mut().insert(item);
+        self.inner.borrow_mut().insert(item, ());
     }
 
     pub fn has(&self, item: &Value) -> bool {
@@ -45,11 +45,18 @@ impl Set {
     pub fn size(&self) -> usize {
         self.inner.borrow().len()
     }
+
+    pub fn next_value(&self, cursor: &TableCursor) -> Option<Value> {
+        self.inner.borrow().next_entry(cursor).map(|(value, ())| value)
+    }
 }
 
 impl Extend<Value> for Set {
     fn extend<T: IntoIterator<Item = Value>>(&mut self, iter: T) {
-        self.inner.borrow_mut().extend(iter);
+        let inner = self.inner.get_mut();
+        for item in iter {
+            inner.insert(item, ());
+        }
     }
 }
 
@@ -60,9 +67,72 @@ impl Object for Set {
         get_property,
         get_property_descriptor,
         set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        apply,
+        own_keys
+    );
+
+    extract!(self);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetIteratorKind {
+    Values,
+    Entries,
+}
+
+#[derive(Debug, Trace)]
+pub struct SetIterator {
+    /// The set being iterated over, or `None` once the iterator is exhausted
+    set: Cell<Option<Value>>,
+    cursor: TableCursor,
+    kind: SetIteratorKind,
+    obj: OrdObject,
+}
+
+impl SetIterator {
+    pub fn new(vm: &Vm, set: Value, kind: SetIteratorKind) -> Self {
+        Self {
+            set: Cell::new(Some(set)),
+            cursor: TableCursor::default(),
+            kind,
+            obj: OrdObject::with_prototype(vm.statics.set_iterator_prototype),
+        }
+    }
+
+    pub fn kind(&self) -> SetIteratorKind {
+        self.kind
+    }
+
+    /// Returns the next value, or `None` if the iterator is done
+    pub fn next(&self, vm: &Vm) -> Option<Value> {
+        let set = self.set.get()?;
+        let value = set.extract::<Set>(vm).and_then(|set| set.next_value(&self.cursor));
+        if value.is_none() {
+            self.set.set(None);
+        }
+        value
+    }
+}
+
+impl Object for SetIterator {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        define_property,
         delete_property,
         set_prototype,
         get_prototype,
+        prevent_extensions,
+        is_extensible,
         apply,
         own_keys
     );
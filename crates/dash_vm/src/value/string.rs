@@ -46,6 +46,26 @@ impl JsString {
     pub fn len(self, sc: &mut LocalScope<'_>) -> usize {
         self.res(sc).len()
     }
+
+    /// Concatenates two strings. Long strings built up this way are not interned, which keeps repeated concatenation linear.
+    pub fn concat(self, other: JsString, sc: &mut LocalScope<'_>) -> JsString {
+        sc.concat(self.sym, other.sym).into()
+    }
+
+    /// Returns the interned version of this string.
+    ///
+    /// Strings created by [`JsString::concat`] may not be interned, so this must be used where strings are compared by their symbol,
+    /// such as property keys.
+    pub fn interned(self, sc: &mut LocalScope<'_>) -> JsString {
+        sc.intern_symbol(self.sym).into()
+    }
+
+    /// Compares the contents of two strings, which also works for strings that are not interned
+    pub fn content_eq(self, other: JsString, vm: &Vm) -> bool {
+        self.sym == other.sym
+            || (!(vm.interner.is_interned(self.sym) && vm.interner.is_interned(other.sym))
+                && vm.interner.resolve(self.sym) == vm.interner.resolve(other.sym))
+    }
 }
 
 impl ValueConversion for JsString {
@@ -100,12 +120,17 @@ impl Object for JsString {
         Ok(None)
     }
 
-    fn set_property(&self, _: PropertyKey, _: PropertyValue, _: &mut LocalScope) -> Result<(), Value> {
-        Ok(())
+    fn set_property(&self, _: PropertyKey, _: PropertyValue, _: &mut LocalScope) -> Result<bool, Value> {
+        // The characters and the length are not writable, and primitives cannot have other own properties
+        Ok(false)
     }
 
-    fn delete_property(&self, _: PropertyKey, _: &mut LocalScope) -> Result<Unrooted, Value> {
-        Ok(Unrooted::new(Value::undefined()))
+    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Value> {
+        if let Some(index) = key.index_usize() {
+            Ok(index >= self.len(sc))
+        } else {
+            Ok(key.to_js_string(sc) != Some(sym::length))
+        }
     }
 
     fn set_prototype(&self, _: Value, _: &mut LocalScope) -> Result<(), Value> {
@@ -127,9 +152,9 @@ impl Object for JsString {
         throw!(scope, TypeError, "'{}' is not a function", v)
     }
 
-    fn own_keys(&self, sc: &mut LocalScope<'_>, _: OwnKeysMode) -> Result<Vec<Value>, Value> {
+    fn own_keys(&self, sc: &mut LocalScope<'_>, mode: OwnKeysMode) -> Result<Vec<Value>, Value> {
         let len = self.len(sc);
-        Ok(array_like_keys(sc, len).collect())
+        Ok(array_like_keys(sc, len, mode).collect())
     }
 
     fn type_of(&self, _: &Vm) -> Typeof {
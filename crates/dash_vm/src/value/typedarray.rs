@@ -1,19 +1,23 @@
-use dash_middle::interner::sym;
+use std::cell::Cell;
+
 use dash_proc_macro::Trace;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
 
 use crate::gc::ObjectId;
 use crate::localscope::LocalScope;
 use crate::value::object::{OwnKeysMode, This};
-use crate::{Vm, extract};
+use crate::{Vm, delegate, extract};
 
 use super::arraybuffer::ArrayBuffer;
+use super::bigint::{JsBigInt, to_bigint};
 use super::function::args::CallArgs;
-use super::object::{Object, OrdObject, PropertyValue};
+use super::object::{Object, OrdObject, PropertyDataDescriptor, PropertyValue, PropertyValueKind};
 use super::ops::conversions::ValueConversion;
 use super::propertykey::PropertyKey;
 use super::{Root, Unrooted, Value};
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum TypedArrayKind {
     Int8Array,
     Uint8Array,
@@ -22,10 +26,88 @@ pub enum TypedArrayKind {
     Uint16Array,
     Int32Array,
     Uint32Array,
+    BigInt64Array,
+    BigUint64Array,
+    Float16Array,
     Float32Array,
     Float64Array,
 }
 
+/// Truncates a number and wraps it into the range of a 64-bit integer.
+/// Truncating the result further implements the ToInt8, ToUint16, etc. abstract operations.
+fn to_wrapping_u64(n: f64) -> u64 {
+    if !n.is_finite() {
+        return 0;
+    }
+
+    // The remainder is exact, but adding 2^64 to small negative numbers would lose precision
+    let n = n.trunc() % 18446744073709551616.0;
+    if n >= 0.0 {
+        n as u64
+    } else if n >= i64::MIN as f64 {
+        n as i64 as u64
+    } else {
+        (n + 18446744073709551616.0) as u64
+    }
+}
+
+/// Implements the ToUint8Clamp abstract operation
+fn to_uint8_clamp(n: f64) -> u8 {
+    if n.is_nan() {
+        0
+    } else {
+        n.clamp(0.0, 255.0).round_ties_even() as u8
+    }
+}
+
+/// Converts a number to the bits of the nearest IEEE 754 half precision float, rounding ties to even
+pub fn f64_to_f16_bits(n: f64) -> u16 {
+    let sign = if n.is_sign_negative() { 0x8000 } else { 0 };
+    let abs = n.abs();
+
+    if abs.is_nan() {
+        sign | 0x7e00
+    } else if abs >= 65520.0 {
+        // Halfway between the largest finite half (65504) and the next power of two rounds up to infinity
+        sign | 0x7c00
+    } else if abs < 2f64.powi(-14) {
+        // Subnormal halves are multiples of 2^-24. Rounding up to 2^-14 produces the smallest normal half.
+        sign | (abs * 2f64.powi(24)).round_ties_even() as u16
+    } else {
+        let exponent = ((abs.to_bits() >> 52) & 0x7ff) as i32 - 1023;
+        let mantissa = ((abs / 2f64.powi(exponent) - 1.0) * 1024.0).round_ties_even() as u16;
+        // The mantissa may round up to the next power of two
+        let (exponent, mantissa) = if mantissa == 1024 {
+            (exponent + 1, 0)
+        } else {
+            (exponent, mantissa)
+        };
+        sign | ((exponent + 15) as u16) << 10 | mantissa
+    }
+}
+
+/// Converts the bits of an IEEE 754 half precision float to a number
+pub fn f16_bits_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let mantissa = f64::from(bits & 0x3ff);
+    let magnitude = match (bits >> 10) & 0x1f {
+        0 => mantissa * 2f64.powi(-24),
+        0x1f if mantissa == 0.0 => f64::INFINITY,
+        0x1f => f64::NAN,
+        exponent => (1.0 + mantissa / 1024.0) * 2f64.powi(i32::from(exponent) - 15),
+    };
+    sign * magnitude
+}
+
+/// Copies up to 8 bytes out of a buffer, for decoding them with [`TypedArrayKind::decode`]
+pub fn read_bytes(src: &[Cell<u8>]) -> [u8; 8] {
+    let mut bytes = [0; 8];
+    for (dest, src) in bytes.iter_mut().zip(src) {
+        *dest = src.get();
+    }
+    bytes
+}
+
 impl TypedArrayKind {
     pub const fn bytes_per_element(self) -> usize {
         match self {
@@ -36,40 +118,142 @@ impl TypedArrayKind {
             TypedArrayKind::Uint16Array => 2,
             TypedArrayKind::Int32Array => 4,
             TypedArrayKind::Uint32Array => 4,
+            TypedArrayKind::BigInt64Array => 8,
+            TypedArrayKind::BigUint64Array => 8,
+            TypedArrayKind::Float16Array => 2,
             TypedArrayKind::Float32Array => 4,
             TypedArrayKind::Float64Array => 8,
         }
     }
+
+    /// Whether elements of this kind are BigInts rather than numbers
+    pub const fn is_bigint(self) -> bool {
+        matches!(self, TypedArrayKind::BigInt64Array | TypedArrayKind::BigUint64Array)
+    }
+
+    pub fn prototype(self, vm: &Vm) -> ObjectId {
+        match self {
+            TypedArrayKind::Uint8Array => vm.statics.uint8array_prototype,
+            TypedArrayKind::Uint8ClampedArray => vm.statics.uint8clampedarray_prototype,
+            TypedArrayKind::Int8Array => vm.statics.int8array_prototype,
+            TypedArrayKind::Int16Array => vm.statics.int16array_prototype,
+            TypedArrayKind::Uint16Array => vm.statics.uint16array_prototype,
+            TypedArrayKind::Int32Array => vm.statics.int32array_prototype,
+            TypedArrayKind::Uint32Array => vm.statics.uint32array_prototype,
+            TypedArrayKind::BigInt64Array => vm.statics.bigint64array_prototype,
+            TypedArrayKind::BigUint64Array => vm.statics.biguint64array_prototype,
+            TypedArrayKind::Float16Array => vm.statics.float16array_prototype,
+            TypedArrayKind::Float32Array => vm.statics.float32array_prototype,
+            TypedArrayKind::Float64Array => vm.statics.float64array_prototype,
+        }
+    }
+
+    /// Converts a value to the byte representation of an element of this kind.
+    /// Only the first `bytes_per_element()` bytes of the returned array are used.
+    pub fn encode(self, sc: &mut LocalScope<'_>, value: Value, little_endian: bool) -> Result<[u8; 8], Value> {
+        macro_rules! bytes {
+            ($value:expr) => {{
+                let value = $value;
+                let mut bytes = [0; 8];
+                let src = if little_endian {
+                    value.to_le_bytes()
+                } else {
+                    value.to_be_bytes()
+                };
+                bytes[..src.len()].copy_from_slice(&src);
+                bytes
+            }};
+        }
+
+        if self.is_bigint() {
+            let value = to_bigint(value, sc)?;
+            // The two's complement representation of the lowest 64 bits is the same for both kinds
+            let bits = (value.get(sc) & BigInt::from(u64::MAX)).to_u64().unwrap_or_default();
+            return Ok(bytes!(bits));
+        }
+
+        let n = value.to_number(sc)?;
+        Ok(match self {
+            TypedArrayKind::Int8Array | TypedArrayKind::Uint8Array => bytes!(to_wrapping_u64(n) as u8),
+            TypedArrayKind::Uint8ClampedArray => bytes!(to_uint8_clamp(n)),
+            TypedArrayKind::Int16Array | TypedArrayKind::Uint16Array => bytes!(to_wrapping_u64(n) as u16),
+            TypedArrayKind::Int32Array | TypedArrayKind::Uint32Array => bytes!(to_wrapping_u64(n) as u32),
+            TypedArrayKind::Float16Array => bytes!(f64_to_f16_bits(n)),
+            TypedArrayKind::Float32Array => bytes!(n as f32),
+            TypedArrayKind::Float64Array => bytes!(n),
+            TypedArrayKind::BigInt64Array | TypedArrayKind::BigUint64Array => unreachable!(),
+        })
+    }
+
+    /// Creates a value from the byte representation of an element of this kind.
+    /// Only the first `bytes_per_element()` bytes are used.
+    pub fn decode(self, sc: &mut LocalScope<'_>, bytes: [u8; 8], little_endian: bool) -> Value {
+        macro_rules! from_bytes {
+            ($ty:ty) => {{
+                let buf = bytes[..size_of::<$ty>()].try_into().unwrap();
+                if little_endian {
+                    <$ty>::from_le_bytes(buf)
+                } else {
+                    <$ty>::from_be_bytes(buf)
+                }
+            }};
+        }
+
+        let n = match self {
+            TypedArrayKind::Int8Array => f64::from(from_bytes!(i8)),
+            TypedArrayKind::Uint8Array | TypedArrayKind::Uint8ClampedArray => f64::from(from_bytes!(u8)),
+            TypedArrayKind::Int16Array => f64::from(from_bytes!(i16)),
+            TypedArrayKind::Uint16Array => f64::from(from_bytes!(u16)),
+            TypedArrayKind::Int32Array => f64::from(from_bytes!(i32)),
+            TypedArrayKind::Uint32Array => f64::from(from_bytes!(u32)),
+            TypedArrayKind::Float16Array => f16_bits_to_f64(from_bytes!(u16)),
+            TypedArrayKind::Float32Array => f64::from(from_bytes!(f32)),
+            TypedArrayKind::Float64Array => from_bytes!(f64),
+            TypedArrayKind::BigInt64Array => {
+                return Value::bigint(JsBigInt::new(sc, BigInt::from(from_bytes!(i64))));
+            }
+            TypedArrayKind::BigUint64Array => {
+                return Value::bigint(JsBigInt::new(sc, BigInt::from(from_bytes!(u64))));
+            }
+        };
+        Value::number(n)
+    }
 }
 
 #[derive(Debug, Trace)]
 pub struct TypedArray {
     arraybuffer: ObjectId,
     kind: TypedArrayKind,
+    byte_offset: usize,
+    /// The number of elements, or `None` if the view covers the rest of the buffer after the byte offset
+    length: Option<usize>,
     obj: OrdObject,
 }
 
 impl TypedArray {
+    /// Creates a typed array that views the entire buffer
     pub fn with_obj(arraybuffer: ObjectId, kind: TypedArrayKind, obj: OrdObject) -> Self {
-        Self { arraybuffer, kind, obj }
+        Self::with_view(arraybuffer, kind, 0, None, obj)
     }
 
-    pub fn new(vm: &Vm, arraybuffer: ObjectId, kind: TypedArrayKind) -> Self {
-        Self::with_obj(
+    pub fn with_view(
+        arraybuffer: ObjectId,
+        kind: TypedArrayKind,
+        byte_offset: usize,
+        length: Option<usize>,
+        obj: OrdObject,
+    ) -> Self {
+        Self {
             arraybuffer,
             kind,
-            OrdObject::with_prototype(match kind {
-                TypedArrayKind::Uint8Array => vm.statics.uint8array_prototype,
-                TypedArrayKind::Uint8ClampedArray => vm.statics.uint8array_prototype,
-                TypedArrayKind::Int8Array => vm.statics.int8array_prototype,
-                TypedArrayKind::Int16Array => vm.statics.int16array_prototype,
-                TypedArrayKind::Uint16Array => vm.statics.uint16array_prototype,
-                TypedArrayKind::Int32Array => vm.statics.int32array_prototype,
-                TypedArrayKind::Uint32Array => vm.statics.uint32array_prototype,
-                TypedArrayKind::Float32Array => vm.statics.float32array_prototype,
-                TypedArrayKind::Float64Array => vm.statics.float64array_prototype,
-            }),
-        )
+            byte_offset,
+            length,
+            obj,
+        }
+    }
+
+    pub fn new(vm: &Vm, arraybuffer: ObjectId, kind: TypedArrayKind) -> Self {
+        Self::with_obj(arraybuffer, kind, OrdObject::with_prototype(kind.prototype(vm)))
     }
 
     pub fn kind(&self) -> TypedArrayKind {
@@ -79,6 +263,73 @@ impl TypedArray {
     pub fn arraybuffer(&self, vm: &Vm) -> &ArrayBuffer {
         self.arraybuffer.extract(vm).unwrap()
     }
+
+    pub fn arraybuffer_id(&self) -> ObjectId {
+        self.arraybuffer
+    }
+
+    pub fn byte_offset(&self) -> usize {
+        self.byte_offset
+    }
+
+    /// Whether the length of this typed array follows the length of a resizable buffer
+    pub fn is_length_tracking(&self) -> bool {
+        self.length.is_none()
+    }
+
+    /// Implements the IsTypedArrayOutOfBounds abstract operation, which is also true if the buffer is detached
+    pub fn is_out_of_bounds(&self, vm: &Vm) -> bool {
+        let buffer = self.arraybuffer(vm);
+        let end = match self.length {
+            Some(length) => self.byte_offset + length * self.kind.bytes_per_element(),
+            None => self.byte_offset,
+        };
+        buffer.is_detached() || end > buffer.len()
+    }
+
+    /// Returns the number of elements, which is 0 if the view is out of bounds of its buffer
+    pub fn length(&self, vm: &Vm) -> usize {
+        if self.is_out_of_bounds(vm) {
+            return 0;
+        }
+
+        match self.length {
+            Some(length) => length,
+            None => (self.arraybuffer(vm).len() - self.byte_offset) / self.kind.bytes_per_element(),
+        }
+    }
+
+    pub fn byte_length(&self, vm: &Vm) -> usize {
+        self.length(vm) * self.kind.bytes_per_element()
+    }
+
+    /// Returns the bytes of the buffer that are viewed by this typed array
+    pub fn storage<'a>(&'a self, vm: &'a Vm) -> &'a [Cell<u8>] {
+        let byte_length = self.byte_length(vm);
+        let storage = self.arraybuffer.extract::<ArrayBuffer>(vm).unwrap().storage();
+        storage
+            .get(self.byte_offset..self.byte_offset + byte_length)
+            .unwrap_or_default()
+    }
+
+    /// Returns the element at the given index, or `None` if it is out of bounds
+    pub fn get(&self, sc: &mut LocalScope<'_>, index: usize) -> Option<Value> {
+        let size = self.kind.bytes_per_element();
+        let bytes = read_bytes(self.storage(sc).get(index * size..(index + 1) * size)?);
+        Some(self.kind.decode(sc, bytes, cfg!(target_endian = "little")))
+    }
+
+    /// Converts the value to the element type and stores it at the given index. Out of bounds writes are ignored.
+    pub fn set(&self, sc: &mut LocalScope<'_>, index: usize, value: Value) -> Result<(), Value> {
+        let size = self.kind.bytes_per_element();
+        let src = self.kind.encode(sc, value, cfg!(target_endian = "little"))?;
+        if let Some(dest) = self.storage(sc).get(index * size..(index + 1) * size) {
+            for (dest, src) in dest.iter().zip(src) {
+                dest.set(src);
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Object for TypedArray {
@@ -89,98 +340,42 @@ impl Object for TypedArray {
     ) -> Result<Option<PropertyValue>, Unrooted> {
         // TODO: wrong when String no longer stores numbers
         if let Some(index) = key.index_usize() {
-            let arraybuffer = self.arraybuffer(sc);
-
-            let bytes = arraybuffer.storage();
-            let index = index * self.kind.bytes_per_element();
-
-            macro_rules! decode_from {
-                (ty: $ty:ty, size: $size:expr) => {
-                    bytes
-                        .get(index..index + $size)
-                        .map(|x| {
-                            let mut arr = [0; $size];
-                            for (dest, src) in arr.iter_mut().zip(x.iter()) {
-                                *dest = src.get();
-                            }
-                            arr
-                        })
-                        .map(<$ty>::from_ne_bytes)
-                        .map(f64::from)
-                };
-            }
-
-            let value = match self.kind {
-                TypedArrayKind::Int8Array => decode_from!(ty: i8, size: 1),
-                TypedArrayKind::Uint8Array => decode_from!(ty: u8, size: 1),
-                TypedArrayKind::Uint8ClampedArray => decode_from!(ty: u8, size: 1),
-                TypedArrayKind::Int16Array => decode_from!(ty: i16, size: 2),
-                TypedArrayKind::Uint16Array => decode_from!(ty: u16, size: 2),
-                TypedArrayKind::Int32Array => decode_from!(ty: i32, size: 4),
-                TypedArrayKind::Uint32Array => decode_from!(ty: u32, size: 4),
-                TypedArrayKind::Float32Array => decode_from!(ty: f32, size: 4),
-                TypedArrayKind::Float64Array => decode_from!(ty: f64, size: 8),
-            };
-
-            if let Some(value) = value {
-                return Ok(Some(PropertyValue::static_default(Value::number(value))));
-            }
-        } else if key.to_js_string(sc) == Some(sym::length) {
-            let len = self.arraybuffer(sc).len();
-            // TODO: make this a getter once we support getting the arraybuffer from subclasses
-            return Ok(Some(PropertyValue::static_default(Value::number(len as f64))));
+            return Ok(self.get(sc, index).map(PropertyValue::static_default));
         }
 
         self.obj.get_own_property_descriptor(key, sc)
     }
 
-    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<(), Value> {
+    fn set_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
         if let Some(index) = key.index_usize() {
-            let arraybuffer = self.arraybuffer.extract::<ArrayBuffer>(sc);
-
             // TODO: not undefined as this
             let value = value.kind().get_or_apply(sc, This::default()).root(sc)?;
-            let value = value.to_number(sc)?;
-            if let Some(arraybuffer) = arraybuffer {
-                let bytes = arraybuffer.storage();
-                let index = index * self.kind.bytes_per_element();
-
-                macro_rules! encode_into {
-                    (ty: $ty:ty, size: $size:expr) => {{
-                        let size = $size;
-                        let dest = bytes.get(index..index + size);
-                        let src = <$ty>::to_ne_bytes(value as $ty);
-
-                        if let Some(dest) = dest {
-                            assert!(dest.len() >= size);
-
-                            for (dest, src) in dest.iter().zip(src.iter().copied()) {
-                                dest.set(src);
-                            }
-                        }
-
-                        return Ok(());
-                    }};
-                }
+            self.set(sc, index, value)?;
+            return Ok(true);
+        }
 
-                match self.kind {
-                    TypedArrayKind::Int8Array => encode_into!(ty: i8, size: 1),
-                    TypedArrayKind::Uint8Array => encode_into!(ty: u8, size: 1),
-                    TypedArrayKind::Uint8ClampedArray => encode_into!(ty: u8, size: 1),
-                    TypedArrayKind::Int16Array => encode_into!(ty: i16, size: 2),
-                    TypedArrayKind::Uint16Array => encode_into!(ty: u16, size: 2),
-                    TypedArrayKind::Int32Array => encode_into!(ty: i32, size: 4),
-                    TypedArrayKind::Uint32Array => encode_into!(ty: u32, size: 4),
-                    TypedArrayKind::Float32Array => encode_into!(ty: f32, size: 4),
-                    TypedArrayKind::Float64Array => encode_into!(ty: f64, size: 8),
-                }
+        self.obj.set_property(key, value, sc)
+    }
+
+    fn define_property(&self, key: PropertyKey, value: PropertyValue, sc: &mut LocalScope) -> Result<bool, Value> {
+        if let Some(index) = key.index_usize() {
+            // Elements are always writable, enumerable and configurable data properties
+            if index >= self.length(sc) || value.descriptor != PropertyDataDescriptor::default() {
+                return Ok(false);
             }
+            return match value.kind {
+                PropertyValueKind::Static(value) => {
+                    self.set(sc, index, value)?;
+                    Ok(true)
+                }
+                PropertyValueKind::Trap { .. } => Ok(false),
+            };
         }
 
-        self.obj.set_property(key, value, sc)
+        self.obj.define_property(key, value, sc)
     }
 
-    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<Unrooted, Value> {
+    fn delete_property(&self, key: PropertyKey, sc: &mut LocalScope) -> Result<bool, Value> {
         self.obj.delete_property(key, sc)
     }
 
@@ -192,6 +387,8 @@ impl Object for TypedArray {
         self.obj.get_prototype(sc)
     }
 
+    delegate!(obj, prevent_extensions, is_extensible);
+
     fn apply(
         &self,
         callee: ObjectId,
@@ -203,7 +400,12 @@ impl Object for TypedArray {
     }
 
     fn own_keys(&self, sc: &mut LocalScope<'_>, mode: OwnKeysMode) -> Result<Vec<Value>, Value> {
-        self.obj.own_keys(sc, mode)
+        let mut keys = Vec::new();
+        if !matches!(mode, OwnKeysMode::AllSymbols) {
+            keys.extend((0..self.length(sc)).map(|i| Value::string(sc.intern_usize(i).into())));
+        }
+        keys.extend(self.obj.own_keys(sc, mode)?);
+        Ok(keys)
     }
 
     extract!(self);
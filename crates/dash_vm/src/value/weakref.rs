@@ -0,0 +1,71 @@
+use std::cell::Cell;
+
+use crate::gc::trace::{Trace, TraceCtxt};
+use crate::gc::{Allocator, ObjectId};
+use crate::{delegate, extract};
+
+use super::object::{Object, OrdObject};
+
+#[derive(Debug)]
+pub struct WeakRef {
+    /// The target is intentionally not traced. It is cleared by the GC once the target is no longer reachable.
+    target: Cell<Option<ObjectId>>,
+    obj: OrdObject,
+}
+
+unsafe impl Trace for WeakRef {
+    fn trace(&self, cx: &mut TraceCtxt<'_>) {
+        let Self { target: _, obj } = self;
+        obj.trace(cx);
+    }
+}
+
+impl WeakRef {
+    pub fn new(target: ObjectId, obj: OrdObject) -> Self {
+        Self {
+            target: Cell::new(Some(target)),
+            obj,
+        }
+    }
+
+    pub fn null() -> Self {
+        Self {
+            target: Cell::new(None),
+            obj: OrdObject::null(),
+        }
+    }
+
+    /// Returns the target, or `None` if it has been garbage collected
+    pub fn target(&self) -> Option<ObjectId> {
+        self.target.get()
+    }
+
+    /// Clears the target if it was not marked. Must be called after tracing and before sweeping.
+    pub(crate) fn clear_if_unmarked(&self, alloc: &Allocator) {
+        if let Some(target) = self.target.get()
+            && !alloc.is_marked(target)
+        {
+            self.target.set(None);
+        }
+    }
+}
+
+impl Object for WeakRef {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        define_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        prevent_extensions,
+        is_extensible,
+        apply,
+        own_keys
+    );
+
+    extract!(self);
+}